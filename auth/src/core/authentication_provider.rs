@@ -1,7 +1,8 @@
 use crate::AuthenticationDetails;
 use elf_model::User;
 
-pub trait AuthenticationProvider {
+/// provider is shared by requests, so it must be thread safe
+pub trait AuthenticationProvider: Send + Sync {
     fn accept(&self, details: &AuthenticationDetails) -> bool;
     fn authenticate(&self, details: &AuthenticationDetails) -> Option<User>;
 }
@@ -66,6 +66,16 @@ impl Display for StdErr {
     }
 }
 
+impl StdErr {
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+
+    pub fn details(&self) -> &Option<StdErrDetail> {
+        &self.details
+    }
+}
+
 /// - print backtrace if environment variables [RUST_BACKTRACE] or [RUST_LIB_BACKTRACE] turns on.
 /// - always with caller location
 impl StdErr {
@@ -88,6 +98,7 @@ impl StdErr {
         enabled
     }
 
+    /// log backtrace at debug level, when backtrace is enabled
     fn print_backtrace() {
         if !Self::backtrace_enabled() {
             return;
@@ -96,7 +107,7 @@ impl StdErr {
         let backtrace = Backtrace::capture();
         match backtrace.status() {
             BacktraceStatus::Captured => {
-                println!("{:#?}", backtrace);
+                tracing::debug!("{:#?}", backtrace);
             }
             _ => {}
         }
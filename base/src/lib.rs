@@ -1,6 +1,8 @@
 mod display;
 mod envs;
 mod error;
+mod logging;
+mod metrics;
 mod utils;
 
 pub mod serde;
@@ -8,4 +10,6 @@ pub mod serde;
 pub use display::*;
 pub use envs::*;
 pub use error::*;
+pub use logging::*;
+pub use metrics::*;
 pub use utils::*;
@@ -0,0 +1,43 @@
+use crate::{ErrorCode, StdErrCode, StdR};
+
+/// output format of logs, see [crate::LoggingInitializer]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum LogFormat {
+    /// single line, human readable
+    Compact,
+    /// multiple lines, human readable, for local development
+    Pretty,
+    /// one json object per line, for log collectors
+    Json,
+}
+
+impl LogFormat {
+    pub fn parse(format: &str) -> StdR<Self> {
+        match format.trim().to_ascii_lowercase().as_str() {
+            "compact" => Ok(Self::Compact),
+            "pretty" => Ok(Self::Pretty),
+            "json" => Ok(Self::Json),
+            s => StdErrCode::EnvValueTypeMismatch.msg(format!(
+                "Invalid log format[{}], only compact, pretty and json are supported.",
+                s
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::LogFormat;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(LogFormat::parse("json").unwrap(), LogFormat::Json);
+        assert_eq!(LogFormat::parse(" Pretty ").unwrap(), LogFormat::Pretty);
+        assert_eq!(LogFormat::parse("COMPACT").unwrap(), LogFormat::Compact);
+        assert!(LogFormat::parse("xml")
+            .err()
+            .unwrap()
+            .to_string()
+            .contains("STDE-00103"));
+    }
+}
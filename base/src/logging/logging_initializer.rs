@@ -0,0 +1,52 @@
+use crate::{EnvConfig, ErrorCode, LogFormat, StdErrCode, VoidR};
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::EnvFilter;
+
+/// install the global [tracing] subscriber.
+/// - [LOG_LEVEL], filter directives, e.g. [info] or [info,elf_pipeline_kernel=debug], default [info],
+/// - [LOG_FORMAT], one of [compact], [pretty] and [json], default [compact],
+/// - [LOG_SPAN_CLOSE], log when span closed, with time busy and idle, default [false].
+///
+/// spans and their fields are attached to each log, e.g. trace id of pipeline trigger,
+/// which can be used to correlate logs across services.
+pub struct LoggingInitializer;
+
+impl LoggingInitializer {
+    /// TIP call it at system startup, and only once
+    pub fn init(envs: &EnvConfig) -> VoidR {
+        let level = envs
+            .get_string("LOG_LEVEL")?
+            .unwrap_or_else(|| "info".to_string());
+        let filter = EnvFilter::try_new(&level).or_else(|e| {
+            StdErrCode::EnvValueTypeMismatch.msg(format!(
+                "Invalid value[LOG_LEVEL={}] from environment, caused by {}.",
+                level, e
+            ))
+        })?;
+        let format = match envs.get_string("LOG_FORMAT")? {
+            Some(format) => LogFormat::parse(&format)?,
+            _ => LogFormat::Compact,
+        };
+        let span_events = if envs.get_bool("LOG_SPAN_CLOSE")?.unwrap_or(false) {
+            FmtSpan::CLOSE
+        } else {
+            FmtSpan::NONE
+        };
+
+        let builder = tracing_subscriber::fmt()
+            .with_env_filter(filter)
+            .with_span_events(span_events);
+        let result = match format {
+            LogFormat::Compact => builder.compact().try_init(),
+            LogFormat::Pretty => builder.pretty().try_init(),
+            LogFormat::Json => builder
+                .json()
+                .with_current_span(true)
+                .with_span_list(true)
+                .try_init(),
+        };
+        result.or_else(|e| {
+            StdErrCode::EnvInit.msg(format!("Failed to initialize logging, caused by {}.", e))
+        })
+    }
+}
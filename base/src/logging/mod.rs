@@ -0,0 +1,5 @@
+mod log_format;
+mod logging_initializer;
+
+pub use log_format::*;
+pub use logging_initializer::*;
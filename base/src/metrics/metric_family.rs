@@ -0,0 +1,50 @@
+use std::fmt::{Display, Formatter};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MetricType {
+    /// monotonically increasing value
+    Counter,
+    /// distribution of observed values in buckets
+    Histogram,
+}
+
+impl Display for MetricType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Counter => write!(f, "counter"),
+            Self::Histogram => write!(f, "histogram"),
+        }
+    }
+}
+
+/// label name and value pairs, sorted by name
+pub type MetricLabels = Vec<(String, String)>;
+
+#[derive(Clone, Debug)]
+pub struct HistogramValue {
+    /// upper bound and cumulative count of each bucket, the [+Inf] bucket is not included
+    pub buckets: Vec<(f64, u64)>,
+    pub sum: f64,
+    pub count: u64,
+}
+
+#[derive(Clone, Debug)]
+pub enum MetricValue {
+    Counter(u64),
+    Histogram(HistogramValue),
+}
+
+#[derive(Clone, Debug)]
+pub struct MetricSample {
+    pub labels: MetricLabels,
+    pub value: MetricValue,
+}
+
+/// snapshot of all samples of one metric, see [crate::MetricsRegistry::gather].
+#[derive(Clone, Debug)]
+pub struct MetricFamily {
+    pub name: String,
+    pub help: String,
+    pub r#type: MetricType,
+    pub samples: Vec<MetricSample>,
+}
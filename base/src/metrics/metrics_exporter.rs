@@ -0,0 +1,15 @@
+use crate::{MetricFamily, MetricsRegistry};
+
+/// render metrics for a monitoring system,
+/// [crate::PrometheusTextExporter] is provided, implement it for the others.
+pub trait MetricsExporter: Send + Sync {
+    /// content type of rendered, for http response
+    fn content_type(&self) -> &'static str;
+
+    fn export(&self, families: &[MetricFamily]) -> String;
+
+    /// render all metrics of [MetricsRegistry]
+    fn export_all(&self) -> String {
+        self.export(&MetricsRegistry::gather())
+    }
+}
@@ -0,0 +1,223 @@
+use crate::{HistogramValue, MetricFamily, MetricLabels, MetricSample, MetricType, MetricValue};
+use std::collections::BTreeMap;
+use std::sync::{OnceLock, RwLock};
+
+/// upper bounds of histogram buckets, in seconds, from 100 microseconds to 10 seconds
+pub const METRICS_DEFAULT_BUCKETS: [f64; 14] = [
+    0.0001, 0.0005, 0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+enum Series {
+    Counter(u64),
+    /// count of each bucket is not cumulative, the last one is [+Inf]
+    Histogram {
+        counts: Vec<u64>,
+        sum: f64,
+        count: u64,
+    },
+}
+
+struct Family {
+    help: String,
+    r#type: MetricType,
+    series: BTreeMap<MetricLabels, Series>,
+}
+
+static METRICS_REGISTRY: OnceLock<RwLock<BTreeMap<String, Family>>> = OnceLock::new();
+
+/// process wide metrics, metric is created on first recording.
+/// recording never fails, a metric recorded with another type is ignored and warned.
+pub struct MetricsRegistry;
+
+impl MetricsRegistry {
+    fn registry() -> &'static RwLock<BTreeMap<String, Family>> {
+        METRICS_REGISTRY.get_or_init(|| RwLock::new(BTreeMap::new()))
+    }
+
+    fn labels_of(labels: &[(&str, &str)]) -> MetricLabels {
+        let mut labels: MetricLabels = labels
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        labels.sort();
+        labels
+    }
+
+    fn record<C, U>(
+        name: &str,
+        help: &str,
+        r#type: MetricType,
+        labels: &[(&str, &str)],
+        create: C,
+        update: U,
+    ) where
+        C: FnOnce() -> Series,
+        U: FnOnce(&mut Series),
+    {
+        let mut registry = Self::registry().write().unwrap_or_else(|e| e.into_inner());
+        let family = registry.entry(name.to_string()).or_insert_with(|| Family {
+            help: help.to_string(),
+            r#type,
+            series: BTreeMap::new(),
+        });
+        if family.r#type != r#type {
+            tracing::warn!(
+                "Metric[{}] is {}, cannot be recorded as {}.",
+                name,
+                family.r#type,
+                r#type
+            );
+            return;
+        }
+        update(
+            family
+                .series
+                .entry(Self::labels_of(labels))
+                .or_insert_with(create),
+        );
+    }
+
+    pub fn increase_counter(name: &str, help: &str, labels: &[(&str, &str)], value: u64) {
+        Self::record(
+            name,
+            help,
+            MetricType::Counter,
+            labels,
+            || Series::Counter(0),
+            |series| {
+                if let Series::Counter(count) = series {
+                    *count += value;
+                }
+            },
+        )
+    }
+
+    /// observe value into [METRICS_DEFAULT_BUCKETS]
+    pub fn observe_histogram(name: &str, help: &str, labels: &[(&str, &str)], value: f64) {
+        Self::record(
+            name,
+            help,
+            MetricType::Histogram,
+            labels,
+            || Series::Histogram {
+                counts: vec![0; METRICS_DEFAULT_BUCKETS.len() + 1],
+                sum: 0.0,
+                count: 0,
+            },
+            |series| {
+                if let Series::Histogram { counts, sum, count } = series {
+                    let index = METRICS_DEFAULT_BUCKETS
+                        .iter()
+                        .position(|bound| value <= *bound)
+                        .unwrap_or(METRICS_DEFAULT_BUCKETS.len());
+                    counts[index] += 1;
+                    *sum += value;
+                    *count += 1;
+                }
+            },
+        )
+    }
+
+    /// snapshot of all metrics, sorted by name and labels
+    pub fn gather() -> Vec<MetricFamily> {
+        let registry = Self::registry().read().unwrap_or_else(|e| e.into_inner());
+        registry
+            .iter()
+            .map(|(name, family)| MetricFamily {
+                name: name.clone(),
+                help: family.help.clone(),
+                r#type: family.r#type,
+                samples: family
+                    .series
+                    .iter()
+                    .map(|(labels, series)| MetricSample {
+                        labels: labels.clone(),
+                        value: match series {
+                            Series::Counter(count) => MetricValue::Counter(*count),
+                            Series::Histogram { counts, sum, count } => {
+                                let mut cumulative = 0;
+                                let buckets = METRICS_DEFAULT_BUCKETS
+                                    .iter()
+                                    .zip(counts.iter())
+                                    .map(|(bound, bucket_count)| {
+                                        cumulative += bucket_count;
+                                        (*bound, cumulative)
+                                    })
+                                    .collect();
+                                MetricValue::Histogram(HistogramValue {
+                                    buckets,
+                                    sum: *sum,
+                                    count: *count,
+                                })
+                            }
+                        },
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// snapshot of given metric
+    pub fn gather_one(name: &str) -> Option<MetricFamily> {
+        Self::gather()
+            .into_iter()
+            .find(|family| family.name == name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{MetricType, MetricValue, MetricsRegistry};
+
+    #[test]
+    fn test_counter() {
+        MetricsRegistry::increase_counter(
+            "test_counter_total",
+            "test",
+            &[("b", "2"), ("a", "1")],
+            1,
+        );
+        MetricsRegistry::increase_counter(
+            "test_counter_total",
+            "test",
+            &[("a", "1"), ("b", "2")],
+            2,
+        );
+        MetricsRegistry::increase_counter("test_counter_total", "test", &[("a", "3")], 1);
+        // type mismatched, ignored
+        MetricsRegistry::observe_histogram("test_counter_total", "test", &[], 1.0);
+
+        let family = MetricsRegistry::gather_one("test_counter_total").unwrap();
+        assert_eq!(family.r#type, MetricType::Counter);
+        assert_eq!(family.samples.len(), 2);
+        assert_eq!(
+            family.samples[0].labels,
+            vec![
+                ("a".to_string(), "1".to_string()),
+                ("b".to_string(), "2".to_string())
+            ]
+        );
+        assert!(matches!(family.samples[0].value, MetricValue::Counter(3)));
+        assert!(matches!(family.samples[1].value, MetricValue::Counter(1)));
+    }
+
+    #[test]
+    fn test_histogram() {
+        MetricsRegistry::observe_histogram("test_histogram_seconds", "test", &[], 0.0002);
+        MetricsRegistry::observe_histogram("test_histogram_seconds", "test", &[], 0.003);
+        MetricsRegistry::observe_histogram("test_histogram_seconds", "test", &[], 20.0);
+
+        let family = MetricsRegistry::gather_one("test_histogram_seconds").unwrap();
+        match &family.samples[0].value {
+            MetricValue::Histogram(histogram) => {
+                assert_eq!(histogram.count, 3);
+                assert_eq!(histogram.buckets[0], (0.0001, 0));
+                assert_eq!(histogram.buckets[1], (0.0005, 1));
+                assert_eq!(histogram.buckets[3], (0.005, 2));
+                assert_eq!(histogram.buckets.last().unwrap(), &(10.0, 2));
+                assert!((histogram.sum - 20.0032).abs() < 1e-9);
+            }
+            _ => panic!("histogram expected"),
+        }
+    }
+}
@@ -0,0 +1,9 @@
+mod metric_family;
+mod metrics_exporter;
+mod metrics_registry;
+mod prometheus_text_exporter;
+
+pub use metric_family::*;
+pub use metrics_exporter::*;
+pub use metrics_registry::*;
+pub use prometheus_text_exporter::*;
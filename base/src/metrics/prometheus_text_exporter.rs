@@ -0,0 +1,151 @@
+use crate::{MetricFamily, MetricLabels, MetricValue, MetricsExporter};
+
+/// prometheus text exposition format, version 0.0.4
+#[derive(Default)]
+pub struct PrometheusTextExporter;
+
+impl PrometheusTextExporter {
+    fn escape_help(help: &str) -> String {
+        help.replace('\\', "\\\\").replace('\n', "\\n")
+    }
+
+    fn escape_label_value(value: &str) -> String {
+        value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+    }
+
+    /// [{a="1",b="2"}], or empty string when no label
+    fn labels_to_str(labels: &MetricLabels, le: Option<&str>) -> String {
+        let mut pairs: Vec<String> = labels
+            .iter()
+            .map(|(name, value)| format!("{}=\"{}\"", name, Self::escape_label_value(value)))
+            .collect();
+        if let Some(le) = le {
+            pairs.push(format!("le=\"{}\"", le));
+        }
+        if pairs.is_empty() {
+            String::new()
+        } else {
+            format!("{{{}}}", pairs.join(","))
+        }
+    }
+}
+
+impl MetricsExporter for PrometheusTextExporter {
+    fn content_type(&self) -> &'static str {
+        "text/plain; version=0.0.4; charset=utf-8"
+    }
+
+    fn export(&self, families: &[MetricFamily]) -> String {
+        let mut lines = vec![];
+        for family in families {
+            let name = &family.name;
+            lines.push(format!(
+                "# HELP {} {}",
+                name,
+                Self::escape_help(&family.help)
+            ));
+            lines.push(format!("# TYPE {} {}", name, family.r#type));
+            for sample in family.samples.iter() {
+                let labels = &sample.labels;
+                match &sample.value {
+                    MetricValue::Counter(count) => lines.push(format!(
+                        "{}{} {}",
+                        name,
+                        Self::labels_to_str(labels, None),
+                        count
+                    )),
+                    MetricValue::Histogram(histogram) => {
+                        for (bound, count) in histogram.buckets.iter() {
+                            lines.push(format!(
+                                "{}_bucket{} {}",
+                                name,
+                                Self::labels_to_str(labels, Some(&bound.to_string())),
+                                count
+                            ));
+                        }
+                        lines.push(format!(
+                            "{}_bucket{} {}",
+                            name,
+                            Self::labels_to_str(labels, Some("+Inf")),
+                            histogram.count
+                        ));
+                        lines.push(format!(
+                            "{}_sum{} {}",
+                            name,
+                            Self::labels_to_str(labels, None),
+                            histogram.sum
+                        ));
+                        lines.push(format!(
+                            "{}_count{} {}",
+                            name,
+                            Self::labels_to_str(labels, None),
+                            histogram.count
+                        ));
+                    }
+                }
+            }
+        }
+        lines.push(String::new());
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        HistogramValue, MetricFamily, MetricSample, MetricType, MetricValue, MetricsExporter,
+        PrometheusTextExporter,
+    };
+
+    #[test]
+    fn test_export() {
+        let families = vec![
+            MetricFamily {
+                name: "elf_test_total".to_string(),
+                help: "test counter".to_string(),
+                r#type: MetricType::Counter,
+                samples: vec![
+                    MetricSample {
+                        labels: vec![("code".to_string(), "PLKN-\"1\"".to_string())],
+                        value: MetricValue::Counter(3),
+                    },
+                    MetricSample {
+                        labels: vec![],
+                        value: MetricValue::Counter(1),
+                    },
+                ],
+            },
+            MetricFamily {
+                name: "elf_test_seconds".to_string(),
+                help: "test histogram".to_string(),
+                r#type: MetricType::Histogram,
+                samples: vec![MetricSample {
+                    labels: vec![("step".to_string(), "unit".to_string())],
+                    value: MetricValue::Histogram(HistogramValue {
+                        buckets: vec![(0.5, 1), (1.0, 2)],
+                        sum: 1.25,
+                        count: 3,
+                    }),
+                }],
+            },
+        ];
+        assert_eq!(
+            PrometheusTextExporter.export(&families),
+            r#"# HELP elf_test_total test counter
+# TYPE elf_test_total counter
+elf_test_total{code="PLKN-\"1\""} 3
+elf_test_total 1
+# HELP elf_test_seconds test histogram
+# TYPE elf_test_seconds histogram
+elf_test_seconds_bucket{step="unit",le="0.5"} 1
+elf_test_seconds_bucket{step="unit",le="1"} 2
+elf_test_seconds_bucket{step="unit",le="+Inf"} 3
+elf_test_seconds_sum{step="unit"} 1.25
+elf_test_seconds_count{step="unit"} 3
+"#
+        );
+    }
+}
@@ -11,7 +11,7 @@ pub fn deserialize<'de, D>(deserializer: D) -> Result<NaiveDate, D::Error>
 where
     D: Deserializer<'de>,
 {
-    let s: &str = Deserialize::deserialize(deserializer)?;
+    let s: String = Deserialize::deserialize(deserializer)?;
     let dt = s.to_date().map_err(Error::custom)?;
 
     Ok(dt)
@@ -14,7 +14,7 @@ pub fn deserialize<'de, D>(deserializer: D) -> Result<NaiveDateTime, D::Error>
 where
     D: Deserializer<'de>,
 {
-    let s: &str = Deserialize::deserialize(deserializer)?;
+    let s: String = Deserialize::deserialize(deserializer)?;
     let dt = s.to_datetime().map_err(Error::custom)?;
 
     Ok(dt)
@@ -11,7 +11,7 @@ pub fn deserialize<'de, D>(deserializer: D) -> Result<NaiveTime, D::Error>
 where
     D: Deserializer<'de>,
 {
-    let s: &str = Deserialize::deserialize(deserializer)?;
+    let s: String = Deserialize::deserialize(deserializer)?;
     let dt = s.to_time().map_err(Error::custom)?;
 
     Ok(dt)
@@ -17,7 +17,7 @@ pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<NaiveDate>, D::Erro
 where
     D: Deserializer<'de>,
 {
-    let s: Option<&str> = Option::deserialize(deserializer)?;
+    let s: Option<String> = Option::deserialize(deserializer)?;
     if let Some(s) = s {
         Ok(Some(s.to_date().map_err(Error::custom)?))
     } else {
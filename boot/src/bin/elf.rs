@@ -0,0 +1,305 @@
+use chrono::{Duration, Local};
+use elf_auth::Principal;
+use elf_base::{Envs, StdErr};
+use elf_boot::{
+    CliValue, CompiledPipelineDump, EnvsBoot, MetaDirectory, MonitorLogSummary, ServerEnvs,
+};
+use elf_model::{
+    FactorEncryptMethod, PipelineTriggerData, PipelineTriggerType, TopicData, TopicDataValue,
+};
+use elf_pipeline_kernel::PipelineExecuteFacade;
+use elf_runtime_model_kernel::{
+    FactorCrypto, IdGen, SnowflakeIdGenerator, TopicDataProvider, TopicSchemaProvider, TopicService,
+};
+use std::path::Path;
+use std::process::ExitCode;
+use std::sync::Arc;
+
+const USAGE: &str = "\
+Usage: elf <command> [arguments] [options]
+
+Commands:
+  validate [files...]   compile all pipelines of meta directory and given files, requires --meta
+  dump                  print compiled structure of pipeline, requires --meta and --pipeline
+  trigger               trigger pipelines of topic by data, requires --topic, --tenant and --data,
+                        only given pipeline is triggered when --pipeline given
+  id [count]            generate snowflake ids, 1 by default
+  encrypt <value>       encrypt factor value, requires --method and --tenant
+  decrypt <value>       decrypt factor value, requires --method and --tenant
+  logs                  list recent pipeline monitor logs, latest first, requires --tenant
+
+Options:
+  --env <file>          env file, repeatable, [./.env] is used when not given
+  --meta <dir>          meta directory, contains [topics/*.json] and [pipelines/*.json]
+  --pipeline <id>       pipeline id
+  --topic <name>        topic name
+  --tenant <tenant id>  tenant id
+  --data <file>         json object file of topic data
+  --type <type>         trigger type, insert/merge/insert-or-merge/delete, insert by default
+  --method <method>     factor encrypt method, e.g. AES256-PKCS5-PADDING, MD5, MASK-MAIL
+  --node <node id>      snowflake node id, SNOWFLAKE_NODE_ID by default
+  --status <status>     monitor log status, e.g. DONE, ERROR
+  --hours <hours>       monitor logs updated in recent hours, 24 by default
+  --limit <count>       max count of monitor logs, 20 by default";
+
+const PIPELINE_MONITOR_LOG_TOPIC: &str = "raw_pipeline_monitor_log";
+
+#[derive(Default)]
+struct Args {
+    command: Option<String>,
+    arguments: Vec<String>,
+    envs: Vec<String>,
+    meta: Option<String>,
+    pipeline_id: Option<String>,
+    topic: Option<String>,
+    tenant_id: Option<String>,
+    data: Option<String>,
+    trigger_type: Option<String>,
+    method: Option<String>,
+    node_id: Option<String>,
+    status: Option<String>,
+    hours: Option<String>,
+    limit: Option<String>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args::default();
+    let mut iter = std::env::args().skip(1);
+    while let Some(arg) = iter.next() {
+        let mut value_of = |name: &str| {
+            iter.next()
+                .ok_or_else(|| format!("Value of option[{}] is missed.", name))
+        };
+        match arg.as_str() {
+            "--env" => args.envs.push(value_of("--env")?),
+            "--meta" => args.meta = Some(value_of("--meta")?),
+            "--pipeline" => args.pipeline_id = Some(value_of("--pipeline")?),
+            "--topic" => args.topic = Some(value_of("--topic")?),
+            "--tenant" => args.tenant_id = Some(value_of("--tenant")?),
+            "--data" => args.data = Some(value_of("--data")?),
+            "--type" => args.trigger_type = Some(value_of("--type")?),
+            "--method" => args.method = Some(value_of("--method")?),
+            "--node" => args.node_id = Some(value_of("--node")?),
+            "--status" => args.status = Some(value_of("--status")?),
+            "--hours" => args.hours = Some(value_of("--hours")?),
+            "--limit" => args.limit = Some(value_of("--limit")?),
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            _ if arg.starts_with("--") => return Err(format!("Unknown option[{}].", arg)),
+            _ if args.command.is_none() => args.command = Some(arg),
+            _ => args.arguments.push(arg),
+        }
+    }
+    Ok(args)
+}
+
+fn required<'a>(value: &'a Option<String>, name: &str) -> Result<&'a String, String> {
+    value
+        .as_ref()
+        .ok_or_else(|| format!("Option[{}] is required.\n\n{}", name, USAGE))
+}
+
+fn number_of<T: std::str::FromStr>(
+    value: &Option<String>,
+    name: &str,
+    default: T,
+) -> Result<T, String> {
+    match value {
+        Some(value) => value
+            .parse()
+            .map_err(|_| format!("Value[{}] of [{}] is not a valid number.", value, name)),
+        _ => Ok(default),
+    }
+}
+
+fn read_file(file: &str) -> Result<String, String> {
+    std::fs::read_to_string(file)
+        .map_err(|e| format!("Failed to read file[{}], caused by {}.", file, e))
+}
+
+fn std_err(e: StdErr) -> String {
+    e.to_string()
+}
+
+fn load_meta(args: &Args) -> Result<MetaDirectory, String> {
+    let mut meta = MetaDirectory::load(required(&args.meta, "--meta")?).map_err(std_err)?;
+    for file in args.arguments.iter() {
+        meta.add_file(Path::new(file)).map_err(std_err)?;
+    }
+    Ok(meta)
+}
+
+/// all pipelines are compiled, failed when any of them failed
+fn validate(args: &Args) -> Result<String, String> {
+    let meta = load_meta(args)?;
+    let mut lines = vec![];
+    let mut failed = 0;
+    for (pipeline_schema, compiled) in meta.compile_all() {
+        match compiled {
+            Ok(_) => lines.push(format!(
+                "[OK]    pipeline[{}] {}",
+                pipeline_schema.pipeline_id(),
+                pipeline_schema.name()
+            )),
+            Err(e) => {
+                failed += 1;
+                lines.push(format!(
+                    "[ERROR] pipeline[{}] {}\n{}",
+                    pipeline_schema.pipeline_id(),
+                    pipeline_schema.name(),
+                    e
+                ))
+            }
+        }
+    }
+    lines.push(format!(
+        "{} topic(s), {} pipeline(s), {} failed.",
+        meta.topic_count(),
+        meta.pipelines().len(),
+        failed
+    ));
+    let report = lines.join("\n");
+    if failed == 0 {
+        Ok(report)
+    } else {
+        Err(report)
+    }
+}
+
+fn dump(args: &Args) -> Result<String, String> {
+    let meta = load_meta(args)?;
+    let compiled_pipeline = meta
+        .compile(required(&args.pipeline_id, "--pipeline")?)
+        .map_err(std_err)?;
+    Ok(CompiledPipelineDump::dump(&compiled_pipeline))
+}
+
+async fn trigger(args: &Args) -> Result<String, String> {
+    let topic = required(&args.topic, "--topic")?;
+    let tenant_id = required(&args.tenant_id, "--tenant")?;
+    let data: TopicData = serde_json::from_str(&read_file(required(&args.data, "--data")?)?)
+        .map_err(|e| format!("Topic data is invalid, caused by {}.", e))?;
+    let trigger_type = match &args.trigger_type {
+        Some(trigger_type) => PipelineTriggerType::parse(trigger_type).map_err(std_err)?,
+        _ => PipelineTriggerType::Insert,
+    };
+
+    let trigger_data = PipelineTriggerData::new()
+        .code(topic.clone())
+        .data(data)
+        .trigger_type(trigger_type)
+        .tenant_id(tenant_id.clone());
+    let facade = PipelineExecuteFacade::with(&Principal::fake_super_admin(), trigger_data)
+        .map_err(std_err)?;
+    let trace_id = facade.trace_id();
+    let data_id = match &args.pipeline_id {
+        Some(pipeline_id) => facade.execute_single(pipeline_id).await,
+        _ => facade.execute().await,
+    }
+    .map_err(std_err)?;
+    Ok(format!(
+        "Triggered, trace[{}], data[{}].",
+        trace_id, data_id
+    ))
+}
+
+fn generate_ids(args: &Args) -> Result<String, String> {
+    let count = number_of(&args.arguments.first().cloned(), "count", 1usize)?;
+    let mut ids = vec![];
+    for _ in 0..count {
+        ids.push(IdGen::next_id().map_err(std_err)?.to_string());
+    }
+    Ok(ids.join("\n"))
+}
+
+fn crypto(args: &Args, encrypt: bool) -> Result<String, String> {
+    let method =
+        FactorEncryptMethod::parse(required(&args.method, "--method")?).map_err(std_err)?;
+    let tenant_id = Arc::new(required(&args.tenant_id, "--tenant")?.clone());
+    let value = match args.arguments.as_slice() {
+        [value] => TopicDataValue::Str(value.clone()),
+        _ => return Err(format!("Exactly one value is required.\n\n{}", USAGE)),
+    };
+    let Some(crypto) = FactorCrypto::get(&method, &tenant_id).map_err(std_err)? else {
+        return Ok(CliValue::plain(&value));
+    };
+    let crypted = if encrypt {
+        crypto.encrypt(&value)
+    } else {
+        crypto.decrypt(&value)
+    }
+    .map_err(std_err)?;
+    Ok(CliValue::plain(&crypted.unwrap_or(value)))
+}
+
+fn logs(args: &Args) -> Result<String, String> {
+    let tenant_id = required(&args.tenant_id, "--tenant")?;
+    let hours = number_of(&args.hours, "--hours", 24i64)?;
+    let limit = number_of(&args.limit, "--limit", 20usize)?;
+
+    let topic_schema = TopicService::schema()
+        .and_then(|service| service.by_code(&PIPELINE_MONITOR_LOG_TOPIC.to_string(), tenant_id))
+        .map_err(std_err)?;
+    let end = Local::now().naive_local();
+    let start = end - Duration::hours(hours);
+    let rows = TopicService::data()
+        .and_then(|service| service.find_updated_between(&topic_schema, &start, &end))
+        .map_err(std_err)?;
+    let logs = MonitorLogSummary::list(
+        &rows,
+        args.pipeline_id.as_deref(),
+        args.status.as_deref(),
+        limit,
+    );
+    if logs.is_empty() {
+        return Ok(String::from("No monitor log found."));
+    }
+    Ok(logs
+        .iter()
+        .map(MonitorLogSummary::to_line)
+        .collect::<Vec<String>>()
+        .join("\n"))
+}
+
+async fn run(args: Args) -> Result<String, String> {
+    let Some(command) = args.command.clone() else {
+        return Err(USAGE.to_string());
+    };
+    let envs = Envs::with_files(args.envs.clone()).map_err(std_err)?;
+    EnvsBoot::init(&envs).map_err(std_err)?;
+    ServerEnvs::init(&envs).map_err(std_err)?;
+    // trace id and topic data id are generated on trigger as well
+    let node_id = number_of(&args.node_id, "--node", ServerEnvs::snowflake_node_id())?;
+    IdGen::set(Box::new(
+        SnowflakeIdGenerator::new(node_id).map_err(std_err)?,
+    ))
+    .map_err(std_err)?;
+
+    match command.as_str() {
+        "validate" => validate(&args),
+        "dump" => dump(&args),
+        "trigger" => trigger(&args).await,
+        "id" => generate_ids(&args),
+        "encrypt" => crypto(&args, true),
+        "decrypt" => crypto(&args, false),
+        "logs" => logs(&args),
+        _ => Err(format!("Unknown command[{}].\n\n{}", command, USAGE)),
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let result = match parse_args() {
+        Ok(args) => run(args).await,
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok(output) => {
+            println!("{}", output);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use elf_auth::AuthenticationManager;
+use elf_base::{Envs, VoidR};
+use elf_boot::{ElfServer, EnvsBoot, ServerEnvs, SuperAdminPatAuthenticationProvider};
+use elf_runtime_model_kernel::{IdGen, SnowflakeIdGenerator};
+use std::process::ExitCode;
+
+/// Usage: elf-server [env files...]
+///
+/// environments are loaded from given files and os, or from [./.env] and os when no file given.
+async fn run() -> VoidR {
+    let envs = Envs::with_files(std::env::args().skip(1).collect())?;
+    EnvsBoot::init(&envs)?;
+    ServerEnvs::init(&envs)?;
+    IdGen::set(Box::new(SnowflakeIdGenerator::new(
+        ServerEnvs::snowflake_node_id(),
+    )?))?;
+
+    let mut authenticator = AuthenticationManager::new();
+    match SuperAdminPatAuthenticationProvider::from_envs() {
+        Some(provider) => authenticator = authenticator.register_provider(Box::new(provider)),
+        None => tracing::warn!(
+            "No authentication provider registered, all requests which require authentication will be rejected."
+        ),
+    }
+
+    ElfServer::serve(authenticator).await
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    match run().await {
+        Ok(_) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
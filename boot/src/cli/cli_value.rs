@@ -0,0 +1,29 @@
+use elf_model::TopicDataValue;
+
+/// plain text of topic data value for console, instead of the debug like [Display].
+pub struct CliValue;
+
+impl CliValue {
+    pub fn plain(value: &TopicDataValue) -> String {
+        match value {
+            TopicDataValue::Str(s) => s.clone(),
+            TopicDataValue::Num(n) => n.to_plain_string(),
+            TopicDataValue::Bool(b) => b.to_string(),
+            TopicDataValue::DateTime(dt) => dt.to_string(),
+            TopicDataValue::Date(d) => d.to_string(),
+            TopicDataValue::Time(t) => t.to_string(),
+            TopicDataValue::None => String::new(),
+            TopicDataValue::Map(_) | TopicDataValue::Vec(_) => {
+                serde_json::to_string(value).unwrap_or_else(|_| value.to_string())
+            }
+        }
+    }
+
+    /// [-] when value is absent or empty
+    pub fn plain_or_dash(value: Option<&TopicDataValue>) -> String {
+        match value.map(Self::plain) {
+            Some(s) if !s.is_empty() => s,
+            _ => String::from("-"),
+        }
+    }
+}
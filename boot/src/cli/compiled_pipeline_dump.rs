@@ -0,0 +1,66 @@
+use elf_pipeline_kernel::CompiledPipeline;
+
+/// indented text of compiled pipeline, e.g.
+/// ```text
+/// pipeline[p1] orders, on topic[t1] orders, trigger by insert, enabled, with prerequisite
+///   stage[s1] stage
+///     unit[u1] unit, loop on [items]
+///       action[a1] copy-to-memory
+/// ```
+pub struct CompiledPipelineDump;
+
+impl CompiledPipelineDump {
+    fn prerequisite(conditional: bool) -> &'static str {
+        if conditional {
+            ", with prerequisite"
+        } else {
+            ""
+        }
+    }
+
+    pub fn dump(compiled_pipeline: &CompiledPipeline) -> String {
+        let pipeline = compiled_pipeline.pipeline_schema().pipeline();
+        let topic_schema = compiled_pipeline.topic_schema();
+        let mut lines = vec![format!(
+            "pipeline[{}] {}, on topic[{}] {}, trigger by {}, {}{}",
+            pipeline.pipeline_id,
+            pipeline.name,
+            topic_schema.topic_id(),
+            topic_schema.name(),
+            pipeline.r#type,
+            if pipeline.enabled {
+                "enabled"
+            } else {
+                "disabled"
+            },
+            Self::prerequisite(pipeline.on.is_some())
+        )];
+        for compiled_stage in compiled_pipeline.stages().iter() {
+            let stage = compiled_stage.stage();
+            lines.push(format!(
+                "  stage[{}] {}{}",
+                stage.stage_id,
+                stage.name,
+                Self::prerequisite(stage.conditional)
+            ));
+            for compiled_unit in compiled_stage.units().iter() {
+                let unit = compiled_unit.unit();
+                lines.push(format!(
+                    "    unit[{}] {}{}{}",
+                    unit.unit_id,
+                    unit.name,
+                    match compiled_unit.loop_variable_name() {
+                        Some(name) => format!(", loop on [{}]", name),
+                        _ => String::new(),
+                    },
+                    Self::prerequisite(unit.conditional)
+                ));
+                for compiled_action in compiled_unit.actions().iter() {
+                    let (action_id, action_type) = compiled_action.action_id_and_type();
+                    lines.push(format!("      action[{}] {}", action_id, action_type));
+                }
+            }
+        }
+        lines.join("\n")
+    }
+}
@@ -0,0 +1,316 @@
+use crate::BootErrorCode;
+use elf_base::{ErrorCode, StdErr, StdR, VoidR, VoidResultHelper};
+use elf_model::{Pipeline, PipelineId, Topic, TopicId};
+use elf_pipeline_kernel::CompiledPipeline;
+use elf_runtime_model_kernel::{PipelineSchema, TopicSchema};
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+struct MetaPipeline {
+    schema: Arc<PipelineSchema>,
+    /// all topics referenced by pipeline, includes the trigger topic
+    topic_ids: BTreeSet<TopicId>,
+}
+
+/// topics and pipelines loaded from json files, one topic or one pipeline in each file.
+/// layout of directory is
+/// - [topics/*.json],
+/// - [pipelines/*.json].
+///
+/// pipelines are compiled against topics of directory only, no meta storage touched.
+#[derive(Default)]
+pub struct MetaDirectory {
+    topics: HashMap<Arc<TopicId>, Arc<TopicSchema>>,
+    pipelines: Vec<MetaPipeline>,
+}
+
+impl MetaDirectory {
+    /// sub directory which does not exist is ignored, errors of all files are accumulated.
+    pub fn load(dir: &str) -> StdR<Self> {
+        if !Path::new(dir).is_dir() {
+            return BootErrorCode::MetaFileRead.msg(format!("Meta directory[{}] not found.", dir));
+        }
+
+        let mut meta = Self::default();
+        let mut errors = vec![];
+        for sub_dir in ["topics", "pipelines"] {
+            let sub_dir = Path::new(dir).join(sub_dir);
+            if !sub_dir.is_dir() {
+                continue;
+            }
+            match Self::json_files(&sub_dir) {
+                Ok(files) => {
+                    for file in files {
+                        errors = errors.collect(meta.add_file(&file));
+                    }
+                }
+                Err(e) => errors.push(e),
+            }
+        }
+        errors.accumulate()?;
+        Ok(meta)
+    }
+
+    /// sorted by file name
+    fn json_files(dir: &Path) -> StdR<Vec<PathBuf>> {
+        let failed = |e: std::io::Error| -> StdErr {
+            BootErrorCode::MetaFileRead.e_msg(format!(
+                "Failed to read directory[{}], caused by {}.",
+                dir.display(),
+                e
+            ))
+        };
+        let mut files = vec![];
+        for entry in std::fs::read_dir(dir).map_err(failed)? {
+            let path = entry.map_err(failed)?.path();
+            if path.is_file() && path.extension().is_some_and(|ext| ext == "json") {
+                files.push(path);
+            }
+        }
+        files.sort();
+        Ok(files)
+    }
+
+    fn invalid<R>(file: &Path, e: impl Display) -> StdR<R> {
+        BootErrorCode::MetaFileInvalid.msg(format!(
+            "File[{}] is invalid, caused by {}.",
+            file.display(),
+            e
+        ))
+    }
+
+    fn collect_topic_ids(json: &Value, topic_ids: &mut BTreeSet<TopicId>) {
+        match json {
+            Value::Object(map) => {
+                for (key, value) in map.iter() {
+                    match value {
+                        Value::String(topic_id) if key == "topicId" => {
+                            topic_ids.insert(topic_id.clone());
+                        }
+                        _ => Self::collect_topic_ids(value, topic_ids),
+                    }
+                }
+            }
+            Value::Array(values) => {
+                for value in values.iter() {
+                    Self::collect_topic_ids(value, topic_ids)
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// file is recognized as pipeline when it has [stages], otherwise as topic.
+    /// the former one is replaced when id duplicated.
+    pub fn add_file(&mut self, file: &Path) -> VoidR {
+        let content = std::fs::read_to_string(file).or_else(|e| {
+            BootErrorCode::MetaFileRead.msg(format!(
+                "Failed to read file[{}], caused by {}.",
+                file.display(),
+                e
+            ))
+        })?;
+        let json: Value = serde_json::from_str(&content).or_else(|e| Self::invalid(file, e))?;
+        if json.get("stages").is_some() {
+            let mut topic_ids = BTreeSet::new();
+            Self::collect_topic_ids(&json, &mut topic_ids);
+            let pipeline: Pipeline =
+                serde_json::from_value(json).or_else(|e| Self::invalid(file, e))?;
+            let schema =
+                Arc::new(PipelineSchema::new(pipeline).or_else(|e| Self::invalid(file, e))?);
+            self.pipelines
+                .retain(|pipeline| pipeline.schema.pipeline_id() != schema.pipeline_id());
+            self.pipelines.push(MetaPipeline { schema, topic_ids });
+        } else {
+            let topic: Topic = serde_json::from_value(json).or_else(|e| Self::invalid(file, e))?;
+            let schema = Arc::new(TopicSchema::new(topic).or_else(|e| Self::invalid(file, e))?);
+            self.topics.insert(schema.topic_id().clone(), schema);
+        }
+        Ok(())
+    }
+
+    pub fn topic_count(&self) -> usize {
+        self.topics.len()
+    }
+
+    /// in loading order
+    pub fn pipelines(&self) -> Vec<&Arc<PipelineSchema>> {
+        self.pipelines
+            .iter()
+            .map(|pipeline| &pipeline.schema)
+            .collect()
+    }
+
+    fn find_pipeline(&self, pipeline_id: &PipelineId) -> StdR<&MetaPipeline> {
+        match self
+            .pipelines
+            .iter()
+            .find(|pipeline| pipeline.schema.pipeline_id().as_ref() == pipeline_id)
+        {
+            Some(pipeline) => Ok(pipeline),
+            _ => BootErrorCode::MetaPipelineNotFound.msg(format!(
+                "Pipeline[{}] not found in meta directory.",
+                pipeline_id
+            )),
+        }
+    }
+
+    /// all referenced topics must be in directory,
+    /// otherwise compiling falls back to meta storage.
+    fn compile_pipeline(&self, pipeline: &MetaPipeline) -> StdR<CompiledPipeline> {
+        let missed_topic_ids: Vec<&str> = pipeline
+            .topic_ids
+            .iter()
+            .filter(|topic_id| !self.topics.contains_key(*topic_id))
+            .map(|topic_id| topic_id.as_str())
+            .collect();
+        let trigger_topic_id = pipeline.schema.pipeline().topic_id.as_ref();
+        let topic_schema = match self.topics.get(trigger_topic_id) {
+            Some(topic_schema) if missed_topic_ids.is_empty() => topic_schema.clone(),
+            _ => {
+                return BootErrorCode::MetaTopicNotFound.msg(format!(
+                    "Topic[{}] referenced by pipeline[{}] not found in meta directory.",
+                    if missed_topic_ids.is_empty() {
+                        trigger_topic_id.clone()
+                    } else {
+                        missed_topic_ids.join(", ")
+                    },
+                    pipeline.schema.pipeline_id()
+                ));
+            }
+        };
+        CompiledPipeline::compile_with_topics(
+            topic_schema,
+            pipeline.schema.clone(),
+            self.topics.clone(),
+        )
+    }
+
+    pub fn compile(&self, pipeline_id: &PipelineId) -> StdR<CompiledPipeline> {
+        self.compile_pipeline(self.find_pipeline(pipeline_id)?)
+    }
+
+    /// compile all pipelines, in loading order
+    pub fn compile_all(&self) -> Vec<(Arc<PipelineSchema>, StdR<CompiledPipeline>)> {
+        self.pipelines
+            .iter()
+            .map(|pipeline| (pipeline.schema.clone(), self.compile_pipeline(pipeline)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CompiledPipelineDump, MetaDirectory};
+    use elf_model::{
+        ConstantParameter, CopyToMemoryAction, Factor, FactorType, Pipeline, PipelineStage,
+        PipelineTriggerType, PipelineUnit, Topic, TopicKind, TopicType,
+    };
+    use std::path::PathBuf;
+
+    fn topic(topic_id: &str) -> Topic {
+        Topic::new()
+            .topic_id(topic_id.to_string())
+            .name(String::from("orders"))
+            .r#type(TopicType::Distinct)
+            .kind(TopicKind::Business)
+            .factors(vec![Factor::new()
+                .factor_id(String::from("f1"))
+                .name(String::from("amount"))
+                .r#type(FactorType::Number)])
+            .tenant_id(String::from("tenant-1"))
+    }
+
+    fn pipeline(pipeline_id: &str, topic_id: &str) -> Pipeline {
+        Pipeline::new()
+            .pipeline_id(pipeline_id.to_string())
+            .name(String::from("orders"))
+            .topic_id(topic_id.to_string())
+            .r#type(PipelineTriggerType::Insert)
+            .stages(vec![PipelineStage::new()
+                .stage_id(String::from("s1"))
+                .name(String::from("stage"))
+                .units(vec![PipelineUnit::new()
+                    .unit_id(String::from("u1"))
+                    .name(String::from("unit"))
+                    .r#do(vec![CopyToMemoryAction::init()
+                        .action_id(String::from("a1"))
+                        .variable_name(String::from("total"))
+                        .source(ConstantParameter::of(String::from("amount")).to_parameter())
+                        .to_action()])])])
+            .tenant_id(String::from("tenant-1"))
+    }
+
+    fn meta_dir(name: &str, files: Vec<(&str, String)>) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("elf-meta-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        for (file, content) in files {
+            let file = dir.join(file);
+            std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+            std::fs::write(file, content).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_compile() {
+        let dir = meta_dir(
+            "compile",
+            vec![
+                (
+                    "topics/orders.json",
+                    serde_json::to_string(&topic("t1")).unwrap(),
+                ),
+                (
+                    "pipelines/p1.json",
+                    serde_json::to_string(&pipeline("p1", "t1")).unwrap(),
+                ),
+                (
+                    "pipelines/p2.json",
+                    serde_json::to_string(&pipeline("p2", "t2")).unwrap(),
+                ),
+                ("pipelines/readme.txt", String::from("ignored")),
+            ],
+        );
+        let meta = MetaDirectory::load(dir.to_str().unwrap()).unwrap();
+        assert_eq!(meta.topic_count(), 1);
+        assert_eq!(meta.pipelines().len(), 2);
+
+        let compiled = meta.compile_all();
+        assert_eq!(
+            CompiledPipelineDump::dump(compiled[0].1.as_ref().unwrap()),
+            "pipeline[p1] orders, on topic[t1] orders, trigger by insert, enabled
+  stage[s1] stage
+    unit[u1] unit
+      action[a1] copy-to-memory"
+        );
+        let error = compiled[1].1.as_ref().err().unwrap();
+        assert_eq!(error.code(), "BOOT-00202");
+
+        assert_eq!(
+            meta.compile(&String::from("p3")).err().unwrap().code(),
+            "BOOT-00203"
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_invalid() {
+        let dir = meta_dir(
+            "invalid",
+            vec![
+                ("topics/a.json", String::from("not json")),
+                ("topics/b.json", String::from(r#"{"name": "no id"}"#)),
+            ],
+        );
+        let error = MetaDirectory::load(dir.to_str().unwrap()).err().unwrap();
+        let message = error.to_string();
+        assert!(message.contains("BOOT-00201"));
+        assert!(message.contains("a.json"));
+        assert!(message.contains("b.json"));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
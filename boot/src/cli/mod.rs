@@ -0,0 +1,9 @@
+mod cli_value;
+mod compiled_pipeline_dump;
+mod meta_directory;
+mod monitor_log_summary;
+
+pub use cli_value::*;
+pub use compiled_pipeline_dump::*;
+pub use meta_directory::*;
+pub use monitor_log_summary::*;
@@ -0,0 +1,127 @@
+use crate::CliValue;
+use elf_model::TopicData;
+
+/// one line summary of pipeline monitor log, which is stored as topic data of [raw_pipeline_monitor_log].
+pub struct MonitorLogSummary {
+    pub start_time: String,
+    pub status: String,
+    pub pipeline_id: String,
+    pub trace_id: String,
+    pub data_id: String,
+    pub spent_in_mills: String,
+    pub error: Option<String>,
+}
+
+impl MonitorLogSummary {
+    fn of(data: &TopicData) -> Self {
+        let value_of = |name: &str| CliValue::plain_or_dash(data.get(name));
+        Self {
+            start_time: value_of("startTime"),
+            status: value_of("status"),
+            pipeline_id: value_of("pipelineId"),
+            trace_id: value_of("traceId"),
+            data_id: value_of("dataId"),
+            spent_in_mills: value_of("spentInMills"),
+            error: data
+                .get("error")
+                .map(CliValue::plain)
+                .filter(|error| !error.is_empty()),
+        }
+    }
+
+    /// filtered by pipeline id and status (case-insensitive), latest first, at most [limit] logs.
+    pub fn list(
+        rows: &[TopicData],
+        pipeline_id: Option<&str>,
+        status: Option<&str>,
+        limit: usize,
+    ) -> Vec<Self> {
+        let mut logs: Vec<Self> = rows
+            .iter()
+            .map(Self::of)
+            .filter(|log| pipeline_id.is_none_or(|pipeline_id| log.pipeline_id == pipeline_id))
+            .filter(|log| status.is_none_or(|status| log.status.eq_ignore_ascii_case(status)))
+            .collect();
+        // start time is formatted as [yyyy-MM-dd HH:mm:ss], so sort as string
+        logs.sort_by(|a, b| b.start_time.cmp(&a.start_time));
+        logs.truncate(limit);
+        logs
+    }
+
+    pub fn to_line(&self) -> String {
+        let line = format!(
+            "{} {} pipeline[{}] trace[{}] data[{}] {}ms",
+            self.start_time,
+            self.status,
+            self.pipeline_id,
+            self.trace_id,
+            self.data_id,
+            self.spent_in_mills
+        );
+        match &self.error {
+            Some(error) => format!("{}\n  {}", line, error.replace('\n', "\n  ")),
+            _ => line,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::MonitorLogSummary;
+    use elf_model::{TopicData, TopicDataValue};
+
+    fn log(start_time: &str, pipeline_id: &str, status: &str) -> TopicData {
+        TopicData::from([
+            (
+                String::from("startTime"),
+                TopicDataValue::Str(start_time.to_string()),
+            ),
+            (
+                String::from("pipelineId"),
+                TopicDataValue::Str(pipeline_id.to_string()),
+            ),
+            (
+                String::from("status"),
+                TopicDataValue::Str(status.to_string()),
+            ),
+            (
+                String::from("traceId"),
+                TopicDataValue::Str(String::from("100")),
+            ),
+            (
+                String::from("spentInMills"),
+                TopicDataValue::Str(String::from("12")),
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_list() {
+        let mut error = log("2024-01-02 10:00:00", "p1", "ERROR");
+        error.insert(
+            String::from("error"),
+            TopicDataValue::Str(String::from("failed\ncaused by x")),
+        );
+        let rows = vec![
+            log("2024-01-01 10:00:00", "p1", "DONE"),
+            error,
+            log("2024-01-03 10:00:00", "p2", "DONE"),
+            log("2024-01-04 10:00:00", "p1", "DONE"),
+        ];
+
+        let lines: Vec<String> = MonitorLogSummary::list(&rows, Some("p1"), None, 2)
+            .iter()
+            .map(MonitorLogSummary::to_line)
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                "2024-01-04 10:00:00 DONE pipeline[p1] trace[100] data[-] 12ms",
+                "2024-01-02 10:00:00 ERROR pipeline[p1] trace[100] data[-] 12ms\n  failed\n  caused by x",
+            ]
+        );
+
+        let logs = MonitorLogSummary::list(&rows, None, Some("done"), 10);
+        assert_eq!(logs.len(), 3);
+    }
+}
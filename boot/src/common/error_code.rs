@@ -0,0 +1,36 @@
+use elf_base::ErrorCode;
+
+pub enum BootErrorCode {
+    // server
+    ServerBind,
+    ServerServe,
+    // request
+    RequestBodyInvalid,
+    RequestTenantIdMissed,
+    RequestTopicNameMissed,
+    RequestPipelineIdMissed,
+    // meta files
+    MetaFileRead,
+    MetaFileInvalid,
+    MetaTopicNotFound,
+    MetaPipelineNotFound,
+}
+
+impl ErrorCode for BootErrorCode {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::ServerBind => "BOOT-00001",
+            Self::ServerServe => "BOOT-00002",
+
+            Self::RequestBodyInvalid => "BOOT-00100",
+            Self::RequestTenantIdMissed => "BOOT-00101",
+            Self::RequestTopicNameMissed => "BOOT-00102",
+            Self::RequestPipelineIdMissed => "BOOT-00103",
+
+            Self::MetaFileRead => "BOOT-00200",
+            Self::MetaFileInvalid => "BOOT-00201",
+            Self::MetaTopicNotFound => "BOOT-00202",
+            Self::MetaPipelineNotFound => "BOOT-00203",
+        }
+    }
+}
@@ -0,0 +1,3 @@
+mod error_code;
+
+pub use error_code::*;
@@ -1,15 +1,17 @@
-use elf_base::{DateTimeFormatterInitializer, EnvConfig, VoidR};
-use elf_pipeline_kernel::{InMemoryFuncCall, PipelineExecuteEnvs};
+use elf_base::{DateTimeFormatterInitializer, EnvConfig, LoggingInitializer, VoidR};
+use elf_pipeline_kernel::{InMemoryFuncCall, PipelineExecuteEnvs, SubjectDatasetEnvs};
 use elf_runtime_model_kernel::AesCrypto;
 
 pub struct EnvsBoot;
 
 impl EnvsBoot {
     pub fn init(env_config: &EnvConfig) -> VoidR {
+        LoggingInitializer::init(env_config)?;
         DateTimeFormatterInitializer::init(env_config)?;
         AesCrypto::init(env_config)?;
         InMemoryFuncCall::init(env_config)?;
         PipelineExecuteEnvs::init(env_config)?;
+        SubjectDatasetEnvs::init(env_config)?;
 
         Ok(())
     }
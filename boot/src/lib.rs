@@ -1,3 +1,9 @@
+mod cli;
+mod common;
 mod envs;
+mod server;
 
+pub use cli::*;
+pub use common::*;
 pub use envs::*;
+pub use server::*;
@@ -0,0 +1,153 @@
+use crate::{BootErrorCode, PipelineDataRoutes, ServerEnvs, SystemRoutes, TopicDataRoutes};
+use axum::Router;
+use elf_auth::{AuthenticationManager, Authorization};
+use elf_base::{ErrorCode, VoidR};
+use elf_model::UserRole;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+
+pub struct ServerState {
+    authorization: Authorization,
+}
+
+impl ServerState {
+    /// only admin and super admin are allowed, same as python version
+    pub fn new(authenticator: AuthenticationManager) -> Self {
+        Self {
+            authorization: Authorization::new(
+                authenticator,
+                vec![UserRole::Admin, UserRole::SuperAdmin],
+            ),
+        }
+    }
+
+    pub fn authorization(&self) -> &Authorization {
+        &self.authorization
+    }
+}
+
+pub struct ElfServer;
+
+impl ElfServer {
+    pub fn router(authenticator: AuthenticationManager) -> Router {
+        Router::new()
+            .merge(SystemRoutes::router())
+            .merge(PipelineDataRoutes::router())
+            .merge(TopicDataRoutes::router())
+            .with_state(Arc::new(ServerState::new(authenticator)))
+    }
+
+    async fn shutdown_signal() {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!("Failed to listen shutdown signal, caused by {}.", e);
+        }
+        tracing::info!("Elf server is shutting down.");
+    }
+
+    /// serve on [ServerEnvs::address], until ctrl-c received.
+    /// environments must be initialized before serving.
+    pub async fn serve(authenticator: AuthenticationManager) -> VoidR {
+        let address = ServerEnvs::address();
+        let listener = TcpListener::bind(&address).await.or_else(|e| {
+            BootErrorCode::ServerBind.msg(format!(
+                "Failed to bind server on [{}], caused by {}.",
+                address, e
+            ))
+        })?;
+        tracing::info!("Elf server is listening on [{}].", address);
+
+        axum::serve(listener, Self::router(authenticator))
+            .with_graceful_shutdown(Self::shutdown_signal())
+            .await
+            .or_else(|e| {
+                BootErrorCode::ServerServe.msg(format!("Server stopped, caused by {}.", e))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ElfServer, SuperAdminPatAuthenticationProvider};
+    use axum::body::Body;
+    use axum::http::header::AUTHORIZATION;
+    use axum::http::{Method, Request, StatusCode};
+    use axum::Router;
+    use elf_auth::AuthenticationManager;
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    fn router() -> Router {
+        ElfServer::router(AuthenticationManager::new().register_provider(Box::new(
+            SuperAdminPatAuthenticationProvider::new(String::from("super-pat")),
+        )))
+    }
+
+    async fn call(request: Request<Body>) -> (StatusCode, Vec<u8>) {
+        let response = router().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, body.to_vec())
+    }
+
+    fn trigger(authorization: Option<&str>, body: &str) -> Request<Body> {
+        let mut builder = Request::builder()
+            .method(Method::POST)
+            .uri("/pipeline/data");
+        if let Some(authorization) = authorization {
+            builder = builder.header(AUTHORIZATION, authorization);
+        }
+        builder.body(Body::from(body.to_string())).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_system() {
+        let (status, body) = call(Request::get("/health").body(Body::empty()).unwrap()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, br#"{"status":"UP"}"#);
+
+        let (status, _) = call(Request::get("/metrics").body(Body::empty()).unwrap()).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_unauthorized() {
+        let (status, body) = call(trigger(None, "{}")).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["code"], "AUTH-00002");
+
+        let (status, _) = call(trigger(Some("pat wrong-pat"), "{}")).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, _) = call(trigger(Some("basic super-pat"), "{}")).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_bad_request() {
+        let (status, body) = call(trigger(Some("pat super-pat"), "not json")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["code"], "BOOT-00100");
+
+        // trace id given, avoid generating id
+        let (status, body) = call(trigger(Some("pat super-pat"), r#"{"traceId": "1"}"#)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        let codes: Vec<&Value> = body["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|error| &error["code"])
+            .collect();
+        assert_eq!(
+            codes,
+            vec![
+                &json!("PLKN-00007"),
+                &json!("PLKN-00001"),
+                &json!("PLKN-00006")
+            ]
+        );
+    }
+}
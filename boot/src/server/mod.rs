@@ -0,0 +1,17 @@
+mod elf_server;
+mod pipeline_data_routes;
+mod server_envs;
+mod server_error;
+mod server_principal;
+mod super_admin_pat_authentication_provider;
+mod system_routes;
+mod topic_data_routes;
+
+pub use elf_server::*;
+pub use pipeline_data_routes::*;
+pub use server_envs::*;
+pub use server_error::*;
+pub use server_principal::*;
+pub use super_admin_pat_authentication_provider::*;
+pub use system_routes::*;
+pub use topic_data_routes::*;
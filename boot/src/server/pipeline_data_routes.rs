@@ -0,0 +1,112 @@
+use crate::{BootErrorCode, ServerPrincipal, ServerR, ServerState};
+use axum::body::Bytes;
+use axum::extract::Query;
+use axum::routing::post;
+use axum::Json;
+use axum::Router;
+use elf_base::{ErrorCode, StdR};
+use elf_model::{
+    PipelineId, PipelineTriggerData, PipelineTriggerResult, PipelineTriggerTraceId, TopicDataId,
+};
+use elf_pipeline_kernel::PipelineExecuteFacade;
+use serde::Deserialize;
+use std::sync::Arc;
+
+#[derive(Deserialize)]
+pub struct PipelineQuery {
+    pipeline_id: Option<PipelineId>,
+}
+
+/// trigger pipelines by topic data, compatible with python version.
+/// - [POST /pipeline/data], trigger all pipelines of topic, and wait for finish,
+/// - [POST /pipeline/data/async], trigger all pipelines of topic, and return immediately,
+/// - [POST /pipeline/data/single?pipeline_id=], trigger given pipeline, and wait for finish,
+/// - [POST /pipeline/data/single/async?pipeline_id=], trigger given pipeline, and return immediately.
+pub struct PipelineDataRoutes;
+
+impl PipelineDataRoutes {
+    pub fn router() -> Router<Arc<ServerState>> {
+        Router::new()
+            .route("/pipeline/data", post(Self::trigger))
+            .route("/pipeline/data/async", post(Self::trigger_async))
+            .route("/pipeline/data/single", post(Self::trigger_single))
+            .route(
+                "/pipeline/data/single/async",
+                post(Self::trigger_single_async),
+            )
+    }
+
+    /// body is parsed here instead of [Json] extractor, to respond with same error format
+    fn trigger_data(body: &Bytes) -> StdR<PipelineTriggerData> {
+        serde_json::from_slice(body).or_else(|e| {
+            BootErrorCode::RequestBodyInvalid.msg(format!(
+                "Pipeline trigger data is invalid, caused by {}.",
+                e
+            ))
+        })
+    }
+
+    fn pipeline_id(query: PipelineQuery) -> StdR<PipelineId> {
+        match query.pipeline_id {
+            Some(pipeline_id) if !pipeline_id.trim().is_empty() => Ok(pipeline_id),
+            _ => BootErrorCode::RequestPipelineIdMissed
+                .msg("Pipeline id is required to trigger single pipeline."),
+        }
+    }
+
+    fn result(
+        trace_id: Arc<PipelineTriggerTraceId>,
+        data_id: TopicDataId,
+    ) -> Json<PipelineTriggerResult> {
+        Json(
+            PipelineTriggerResult::new()
+                .received(true)
+                .trace_id(trace_id.to_string())
+                .internal_data_id(data_id),
+        )
+    }
+
+    async fn trigger(
+        ServerPrincipal(principal): ServerPrincipal,
+        body: Bytes,
+    ) -> ServerR<Json<PipelineTriggerResult>> {
+        let facade = PipelineExecuteFacade::with(&principal, Self::trigger_data(&body)?)?;
+        let trace_id = facade.trace_id();
+        let data_id = facade.execute().await?;
+        Ok(Self::result(trace_id, data_id))
+    }
+
+    async fn trigger_async(
+        ServerPrincipal(principal): ServerPrincipal,
+        body: Bytes,
+    ) -> ServerR<Json<PipelineTriggerResult>> {
+        let facade = PipelineExecuteFacade::with(&principal, Self::trigger_data(&body)?)?;
+        let trace_id = facade.trace_id();
+        let data_id = facade.execute_async()?;
+        Ok(Self::result(trace_id, data_id))
+    }
+
+    async fn trigger_single(
+        ServerPrincipal(principal): ServerPrincipal,
+        Query(query): Query<PipelineQuery>,
+        body: Bytes,
+    ) -> ServerR<Json<PipelineTriggerResult>> {
+        let pipeline_id = Self::pipeline_id(query)?;
+        let facade = PipelineExecuteFacade::with(&principal, Self::trigger_data(&body)?)?;
+        let trace_id = facade.trace_id();
+        let data_id = facade.execute_single(&pipeline_id).await?;
+        Ok(Self::result(trace_id, data_id))
+    }
+
+    async fn trigger_single_async(
+        ServerPrincipal(principal): ServerPrincipal,
+        Query(query): Query<PipelineQuery>,
+        body: Bytes,
+    ) -> ServerR<Json<PipelineTriggerResult>> {
+        let pipeline_id = Self::pipeline_id(query)?;
+        let facade = PipelineExecuteFacade::with(&principal, Self::trigger_data(&body)?)?;
+        let trace_id = facade.trace_id();
+        let data_id = facade.execute_single_async(&pipeline_id)?;
+        Ok(Self::result(trace_id, data_id))
+    }
+}
@@ -0,0 +1,80 @@
+use elf_base::{EnvConfig, ErrorCode, StdErrCode, VoidR};
+use std::sync::OnceLock;
+
+pub struct ServerEnvs {
+    host: String,
+    port: u16,
+    /// node id of snowflake id generator, must be unique in cluster
+    snowflake_node_id: u64,
+    /// personal access token of super admin, no super admin token when not given
+    super_admin_pat: Option<String>,
+}
+
+static SERVER_ENVS: OnceLock<ServerEnvs> = OnceLock::new();
+
+impl ServerEnvs {
+    fn init_default() -> Self {
+        Self {
+            host: String::from("0.0.0.0"),
+            port: 8000,
+            snowflake_node_id: 0,
+            super_admin_pat: None,
+        }
+    }
+
+    /// initialize server environments by given environment
+    /// TIP call it at server startup
+    pub fn init(envs: &EnvConfig) -> VoidR {
+        let host = envs
+            .get_string("SERVER_HOST")?
+            .unwrap_or(String::from("0.0.0.0"));
+        let port = match envs.get_int("SERVER_PORT")? {
+            Some(port) => u16::try_from(port).or_else(|_| {
+                StdErrCode::EnvValueTypeMismatch
+                    .msg(format!("Server port[{}] is out of range.", port))
+            })?,
+            None => 8000,
+        };
+        let snowflake_node_id = match envs.get_int("SNOWFLAKE_NODE_ID")? {
+            Some(node_id) => u64::try_from(node_id).or_else(|_| {
+                StdErrCode::EnvValueTypeMismatch.msg(format!(
+                    "Snowflake node id[{}] cannot be negative.",
+                    node_id
+                ))
+            })?,
+            None => 0,
+        };
+        let super_admin_pat = envs
+            .get_string("SERVER_SUPER_ADMIN_PAT")?
+            .filter(|pat| !pat.trim().is_empty());
+
+        let envs = ServerEnvs {
+            host,
+            port,
+            snowflake_node_id,
+            super_admin_pat,
+        };
+
+        SERVER_ENVS
+            .set(envs)
+            .or_else(|_| StdErrCode::EnvInit.msg("Failed to initialize server environment."))
+    }
+
+    fn envs() -> &'static ServerEnvs {
+        SERVER_ENVS.get_or_init(Self::init_default)
+    }
+
+    /// [host:port]
+    pub fn address() -> String {
+        let envs = Self::envs();
+        format!("{}:{}", envs.host, envs.port)
+    }
+
+    pub fn snowflake_node_id() -> u64 {
+        Self::envs().snowflake_node_id
+    }
+
+    pub fn super_admin_pat() -> Option<&'static String> {
+        Self::envs().super_admin_pat.as_ref()
+    }
+}
@@ -0,0 +1,155 @@
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use elf_auth::AuthErrorCode;
+use elf_base::{ErrorCode, StdErr, StdErrDetail};
+use elf_pipeline_kernel::PipelineKernelErrorCode;
+use serde_json::{json, Value};
+
+/// response [StdErr] as json body, with http status decided by error code.
+/// body is
+/// ```json
+/// {"code": "PLKN-00001", "detail": "...", "errors": [{"code": "...", "detail": "..."}]}
+/// ```
+/// - [detail] is absent when error has no message,
+/// - [errors] is present only when error accumulates sub errors.
+pub struct ServerError(StdErr);
+
+pub type ServerR<T> = Result<T, ServerError>;
+
+impl From<StdErr> for ServerError {
+    fn from(error: StdErr) -> Self {
+        Self(error)
+    }
+}
+
+impl ServerError {
+    fn status_of_code(code: &str) -> StatusCode {
+        match code.split_once('-') {
+            _ if code == AuthErrorCode::Forbidden.code() => StatusCode::FORBIDDEN,
+            // authentication failed or user cannot be recognized as principal
+            Some(("AUTH", _)) => StatusCode::UNAUTHORIZED,
+            _ if code == PipelineKernelErrorCode::TriggerPipelineNotFound.code()
+                || code == PipelineKernelErrorCode::TopicDataIdNotFound.code() =>
+            {
+                StatusCode::NOT_FOUND
+            }
+            // checks of trigger data, [PLKN-00001] to [PLKN-00099]
+            Some(("PLKN", no)) if no < "00100" => StatusCode::BAD_REQUEST,
+            // checks of request, [BOOT-00100] to [BOOT-00199]
+            Some(("BOOT", no)) if no.starts_with("001") => StatusCode::BAD_REQUEST,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    /// status of accumulated error is the most severe one of its sub errors
+    fn status_of(error: &StdErr) -> StatusCode {
+        match error.details() {
+            Some(StdErrDetail::Sub(errors)) if !errors.is_empty() => errors
+                .iter()
+                .map(Self::status_of)
+                .max_by_key(|status| status.as_u16())
+                .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+            _ => Self::status_of_code(error.code()),
+        }
+    }
+
+    fn body_of(error: &StdErr) -> Value {
+        let mut body = json!({ "code": error.code() });
+        match error.details() {
+            Some(StdErrDetail::Str(detail)) => {
+                body["detail"] = json!(detail);
+            }
+            Some(StdErrDetail::Sub(errors)) => {
+                body["errors"] = Value::Array(errors.iter().map(Self::body_of).collect());
+            }
+            None => {}
+        }
+        body
+    }
+
+    pub fn status(&self) -> StatusCode {
+        Self::status_of(&self.0)
+    }
+}
+
+impl IntoResponse for ServerError {
+    fn into_response(self) -> Response {
+        let status = self.status();
+        if status.is_server_error() {
+            tracing::error!("{}", self.0);
+        } else {
+            tracing::debug!("{}", self.0);
+        }
+        (status, Json(Self::body_of(&self.0))).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BootErrorCode, ServerError};
+    use axum::http::StatusCode;
+    use elf_auth::AuthErrorCode;
+    use elf_base::{ErrorCode, StdErr, VoidResultHelper};
+    use elf_pipeline_kernel::PipelineKernelErrorCode;
+    use serde_json::json;
+
+    #[test]
+    fn test_status() {
+        let status = |error: StdErr| ServerError::from(error).status();
+
+        assert_eq!(
+            status(AuthErrorCode::Unauthorized.e()),
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(status(AuthErrorCode::Forbidden.e()), StatusCode::FORBIDDEN);
+        assert_eq!(
+            status(PipelineKernelErrorCode::TriggerCodeMissed.e()),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            status(PipelineKernelErrorCode::TriggerPipelineNotFound.e()),
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            status(PipelineKernelErrorCode::IncorrectDataPath.e()),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+        assert_eq!(
+            status(BootErrorCode::RequestBodyInvalid.e()),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            status(BootErrorCode::ServerBind.e()),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+
+        let accumulated = vec![
+            PipelineKernelErrorCode::TriggerCodeMissed.e_msg("code missed"),
+            AuthErrorCode::Forbidden.e_msg("forbidden"),
+        ]
+        .accumulate()
+        .unwrap_err();
+        assert_eq!(status(accumulated), StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn test_body() {
+        let accumulated = vec![
+            PipelineKernelErrorCode::TriggerCodeMissed.e_msg("code missed"),
+            PipelineKernelErrorCode::TriggerDataMissed.e(),
+        ]
+        .accumulate()
+        .unwrap_err();
+        assert_eq!(
+            ServerError::body_of(&accumulated),
+            json!({
+                "code": "STDE-99998",
+                "errors": [
+                    {"code": "PLKN-00001", "detail": "code missed"},
+                    {"code": "PLKN-00006"}
+                ]
+            })
+        );
+    }
+}
@@ -0,0 +1,51 @@
+use crate::{ServerError, ServerState};
+use axum::extract::FromRequestParts;
+use axum::http::header::AUTHORIZATION;
+use axum::http::request::Parts;
+use elf_auth::{AuthErrorCode, AuthenticationScheme, Principal};
+use elf_base::{ErrorCode, StdR};
+use std::sync::Arc;
+
+/// principal of request, authorized by [Authorization] header,
+/// same as python version, the header is
+/// - [Bearer <token>],
+/// - [pat <personal access token>].
+pub struct ServerPrincipal(pub Principal);
+
+impl ServerPrincipal {
+    fn parse_authorization(parts: &Parts) -> StdR<(AuthenticationScheme, String)> {
+        let header = match parts.headers.get(AUTHORIZATION) {
+            Some(header) => header.to_str().or_else(|_| {
+                AuthErrorCode::Unauthorized.msg("Authorization header must be visible ascii.")
+            })?,
+            None => return AuthErrorCode::Unauthorized.msg("Authorization header is missed."),
+        };
+        match header.trim().split_once(' ') {
+            Some((scheme, token)) if !token.trim().is_empty() => {
+                let token = token.trim().to_string();
+                match scheme.to_lowercase().as_str() {
+                    "bearer" => Ok((AuthenticationScheme::Bearer, token)),
+                    "pat" => Ok((AuthenticationScheme::Pat, token)),
+                    _ => AuthErrorCode::Unauthorized.msg(format!(
+                        "Authorization scheme[{}] is not supported.",
+                        scheme
+                    )),
+                }
+            }
+            _ => AuthErrorCode::Unauthorized.msg("Authorization header is incorrect."),
+        }
+    }
+}
+
+impl FromRequestParts<Arc<ServerState>> for ServerPrincipal {
+    type Rejection = ServerError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &Arc<ServerState>,
+    ) -> Result<Self, Self::Rejection> {
+        let (scheme, token) = Self::parse_authorization(parts)?;
+        let principal = Principal::authorize_token(state.authorization(), scheme, token)?;
+        Ok(Self(principal))
+    }
+}
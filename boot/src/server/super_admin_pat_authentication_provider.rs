@@ -0,0 +1,64 @@
+use crate::ServerEnvs;
+use elf_auth::{AuthenticationDetails, AuthenticationProvider, AuthenticationScheme};
+use elf_model::{User, UserRole};
+use subtle::ConstantTimeEq;
+
+/// authenticate the personal access token given by [SERVER_SUPER_ADMIN_PAT] as super admin,
+/// the user is same as [elf_auth::Principal::fake_super_admin].
+/// typically for trusted clients, e.g. data ingestion from other systems.
+pub struct SuperAdminPatAuthenticationProvider {
+    pat: String,
+}
+
+impl SuperAdminPatAuthenticationProvider {
+    pub fn new(pat: String) -> Self {
+        Self { pat }
+    }
+
+    /// none when [SERVER_SUPER_ADMIN_PAT] not given
+    pub fn from_envs() -> Option<Self> {
+        ServerEnvs::super_admin_pat().map(|pat| Self::new(pat.clone()))
+    }
+}
+
+impl AuthenticationProvider for SuperAdminPatAuthenticationProvider {
+    fn accept(&self, details: &AuthenticationDetails) -> bool {
+        matches!(details.scheme(), AuthenticationScheme::Pat)
+    }
+
+    fn authenticate(&self, details: &AuthenticationDetails) -> Option<User> {
+        // constant time, avoid leaking the secret by timing
+        if details.token().as_bytes().ct_eq(self.pat.as_bytes()).into() {
+            Some(
+                User::new()
+                    .tenant_id(String::from("-1"))
+                    .user_id(String::from("1"))
+                    .name(String::from("imma-super"))
+                    .role(UserRole::SuperAdmin),
+            )
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SuperAdminPatAuthenticationProvider;
+    use elf_auth::{AuthenticationDetails, AuthenticationProvider, AuthenticationScheme};
+
+    #[test]
+    fn test_authenticate() {
+        let provider = SuperAdminPatAuthenticationProvider::new(String::from("super-pat"));
+        let authenticate = |token: &str| {
+            provider.authenticate(&AuthenticationDetails::new(
+                AuthenticationScheme::Pat,
+                token.to_string(),
+            ))
+        };
+        assert!(authenticate("super-pat").is_some());
+        assert!(authenticate("super-pas").is_none());
+        assert!(authenticate("super-pat-").is_none());
+        assert!(authenticate("").is_none());
+    }
+}
@@ -0,0 +1,34 @@
+use crate::ServerState;
+use axum::http::header::CONTENT_TYPE;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Json;
+use axum::Router;
+use elf_base::{MetricsExporter, PrometheusTextExporter};
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+/// no authentication required
+/// - [GET /health], always up when server is serving,
+/// - [GET /metrics], metrics in prometheus text format.
+pub struct SystemRoutes;
+
+impl SystemRoutes {
+    pub fn router() -> Router<Arc<ServerState>> {
+        Router::new()
+            .route("/health", get(Self::health))
+            .route("/metrics", get(Self::metrics))
+    }
+
+    async fn health() -> Json<Value> {
+        Json(json!({ "status": "UP" }))
+    }
+
+    async fn metrics() -> impl IntoResponse {
+        let exporter = PrometheusTextExporter;
+        (
+            [(CONTENT_TYPE, exporter.content_type())],
+            exporter.export_all(),
+        )
+    }
+}
@@ -0,0 +1,62 @@
+use crate::{BootErrorCode, ServerPrincipal, ServerR, ServerState};
+use axum::extract::Query;
+use axum::routing::get;
+use axum::Json;
+use axum::Router;
+use elf_auth::{AuthErrorCode, Principal};
+use elf_base::{ErrorCode, StdR};
+use elf_model::{TenantId, TopicCode, TopicData};
+use elf_runtime_model_kernel::{TopicDataProvider, TopicSchemaProvider, TopicService};
+use serde::Deserialize;
+use std::sync::Arc;
+
+#[derive(Deserialize)]
+pub struct TopicDataQuery {
+    topic_name: Option<TopicCode>,
+    tenant_id: Option<TenantId>,
+}
+
+/// read topic data, compatible with python version.
+/// - [GET /topic/data/all?topic_name=&tenant_id=], all rows of topic,
+///   tenant id is required for super admin, and ignored for others.
+pub struct TopicDataRoutes;
+
+impl TopicDataRoutes {
+    pub fn router() -> Router<Arc<ServerState>> {
+        Router::new().route("/topic/data/all", get(Self::find_all))
+    }
+
+    /// same rule as pipeline trigger,
+    /// super admin must give tenant id, others can only read data of their own tenant.
+    fn tenant_id(principal: &Principal, tenant_id: Option<TenantId>) -> StdR<TenantId> {
+        let tenant_id = tenant_id.filter(|tenant_id| !tenant_id.trim().is_empty());
+        match (principal.is_super_admin(), tenant_id) {
+            (true, Some(tenant_id)) => Ok(tenant_id),
+            (true, None) => BootErrorCode::RequestTenantIdMissed
+                .msg("Tenant id is required when topic data read by super admin."),
+            (false, Some(tenant_id)) if &tenant_id != principal.tenant_id() => {
+                AuthErrorCode::Forbidden.msg("Tenant id does not match the principal's.")
+            }
+            (false, _) => Ok(principal.tenant_id().clone()),
+        }
+    }
+
+    fn topic_name(topic_name: Option<TopicCode>) -> StdR<TopicCode> {
+        match topic_name {
+            Some(topic_name) if !topic_name.trim().is_empty() => Ok(topic_name),
+            _ => BootErrorCode::RequestTopicNameMissed.msg("Topic name is required."),
+        }
+    }
+
+    async fn find_all(
+        ServerPrincipal(principal): ServerPrincipal,
+        Query(query): Query<TopicDataQuery>,
+    ) -> ServerR<Json<Vec<TopicData>>> {
+        let tenant_id = Self::tenant_id(&principal, query.tenant_id);
+        let topic_name = Self::topic_name(query.topic_name);
+        let (tenant_id, topic_name) = (tenant_id?, topic_name?);
+
+        let topic_schema = TopicService::schema()?.by_code(&topic_name, &tenant_id)?;
+        Ok(Json(TopicService::data()?.find_all(&topic_schema)?))
+    }
+}
@@ -3,7 +3,7 @@ use elf_base::{ErrorCode, StdR};
 use elf_model_marco::{adapt_model, Display, Serde, StrEnum};
 use std::cmp::PartialEq;
 
-#[derive(Display, Serde, PartialEq, Debug, StrEnum)]
+#[derive(Display, Serde, Clone, PartialEq, Debug, StrEnum)]
 pub enum FactorType {
     Sequence,
     Number,
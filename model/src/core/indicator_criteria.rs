@@ -0,0 +1,31 @@
+use crate::{
+    BaseDataModel, BucketId, FactorOrSubjectDatasetColumnId, IndicatorId, ParameterJoint, Storable,
+};
+use chrono::NaiveDateTime;
+use elf_base::serde::option_naive_datetime;
+use elf_model_marco::adapt_model;
+
+#[adapt_model(storable)]
+pub struct IndicatorCriteriaOnBucket {
+    pub bucket_id: Option<BucketId>,
+    /// factor (or column when indicator is on subject) which bucket applied on,
+    /// use factor of indicator when not declared
+    pub factor_id: Option<FactorOrSubjectDatasetColumnId>,
+}
+
+/// compute value of indicator, rows are grouped by segments of buckets
+#[adapt_model(storable)]
+pub struct IndicatorCriteria {
+    pub indicator_id: Option<IndicatorId>,
+    /// factor (or column when indicator is on subject) of time, time range is ignored when not declared
+    pub time_factor_id: Option<FactorOrSubjectDatasetColumnId>,
+    /// included
+    #[serde(with = "option_naive_datetime")]
+    pub start_time: Option<NaiveDateTime>,
+    /// excluded
+    #[serde(with = "option_naive_datetime")]
+    pub end_time: Option<NaiveDateTime>,
+    /// works together with the filter of indicator
+    pub filter: Option<ParameterJoint>,
+    pub buckets: Option<Vec<IndicatorCriteriaOnBucket>>,
+}
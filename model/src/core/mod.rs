@@ -11,6 +11,7 @@ mod either_types;
 mod enumeration;
 mod factor;
 mod indicator;
+mod indicator_criteria;
 mod measure_method;
 mod monitor_log;
 mod objective;
@@ -20,6 +21,7 @@ mod objective_factor;
 mod objective_report;
 mod objective_target;
 mod objective_timeframe;
+mod objective_values;
 mod objective_variable;
 mod pipeline;
 mod pipeline_trigger_data;
@@ -46,6 +48,7 @@ pub use either_types::*;
 pub use enumeration::*;
 pub use factor::*;
 pub use indicator::*;
+pub use indicator_criteria::*;
 pub use measure_method::*;
 pub use monitor_log::*;
 pub use objective::*;
@@ -55,6 +58,7 @@ pub use objective_factor::*;
 pub use objective_report::*;
 pub use objective_target::*;
 pub use objective_timeframe::*;
+pub use objective_values::*;
 pub use objective_variable::*;
 pub use pipeline::*;
 pub use pipeline_trigger_data::*;
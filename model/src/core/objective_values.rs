@@ -0,0 +1,73 @@
+use crate::{
+    BaseDataModel, BreakdownTargetId, DataResult, ObjectiveFactorId, ObjectiveId,
+    ObjectiveTargetId, Storable,
+};
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use elf_base::serde::option_naive_datetime;
+use elf_model_marco::adapt_model;
+
+#[adapt_model(storable)]
+pub struct ObjectiveTimeRangeValues {
+    /// included
+    #[serde(with = "option_naive_datetime")]
+    pub start: Option<NaiveDateTime>,
+    /// excluded
+    #[serde(with = "option_naive_datetime")]
+    pub end: Option<NaiveDateTime>,
+}
+
+#[adapt_model(storable)]
+pub struct ObjectiveFactorValues {
+    pub uuid: Option<ObjectiveFactorId>,
+    pub name: Option<String>,
+    pub current_value: Option<BigDecimal>,
+    /// value of previous cycle, e.g. last month when time frame is on month
+    pub previous_value: Option<BigDecimal>,
+    /// value of same cycle of last year
+    pub chain_value: Option<BigDecimal>,
+    pub failed: Option<bool>,
+}
+
+#[adapt_model(storable)]
+pub struct ObjectiveTargetValues {
+    pub uuid: Option<ObjectiveTargetId>,
+    pub name: Option<String>,
+    pub tobe: Option<BigDecimal>,
+    pub current_value: Option<BigDecimal>,
+    /// computed only when previous cycle is asked
+    pub previous_value: Option<BigDecimal>,
+    /// computed only when chain cycle is asked
+    pub chain_value: Option<BigDecimal>,
+    /// current value vs to be value, the bigger the better
+    pub achievement_rate: Option<BigDecimal>,
+    /// is to be value achieved, according to better side
+    pub achieved: Option<bool>,
+    /// month-on-month when time frame is on month, (current - previous) / previous
+    pub previous_change_rate: Option<BigDecimal>,
+    /// year-on-year, (current - chain) / chain
+    pub chain_change_rate: Option<BigDecimal>,
+    pub failed: Option<bool>,
+}
+
+#[adapt_model(storable)]
+pub struct ObjectiveValues {
+    pub objective_id: Option<ObjectiveId>,
+    /// none when objective is not on time frame
+    pub current: Option<ObjectiveTimeRangeValues>,
+    pub previous: Option<ObjectiveTimeRangeValues>,
+    pub chain: Option<ObjectiveTimeRangeValues>,
+    pub targets: Option<Vec<ObjectiveTargetValues>>,
+    pub factors: Option<Vec<ObjectiveFactorValues>>,
+}
+
+#[adapt_model(storable)]
+pub struct BreakdownTargetValues {
+    pub uuid: Option<BreakdownTargetId>,
+    pub target_id: Option<ObjectiveTargetId>,
+    pub name: Option<String>,
+    /// columns are dimensions and as is value of target,
+    /// each row is a combination of dimension values
+    pub data: Option<DataResult>,
+    pub failed: Option<bool>,
+}
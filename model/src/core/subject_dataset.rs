@@ -1,9 +1,11 @@
 use crate::{
-    BaseDataModel, ModelErrorCode, Pageable, ParameterCondition, Storable, SubjectDatasetColumnId,
-    SubjectId,
+    BaseDataModel, DataPage, DataResultSet, ModelErrorCode, PageDataCell, PageDataSet, Pageable,
+    ParameterCondition, Storable, SubjectDatasetColumnId, SubjectId,
 };
 use elf_base::{ErrorCode, StdR};
 use elf_model_marco::{adapt_model, Display, Serde, StrEnum};
+use serde_json::Value;
+use std::collections::HashMap;
 
 #[derive(Display, Serde, StrEnum)]
 pub enum SubjectDatasetCriteriaIndicatorArithmetic {
@@ -54,3 +56,56 @@ impl Pageable for SubjectDatasetCriteria {
         }
     }
 }
+
+/// one page of subject dataset, cells of row are in the same order of columns.
+#[adapt_model(storable)]
+pub struct SubjectDatasetPage {
+    pub columns: Option<Vec<String>>,
+    pub data: Option<DataResultSet>,
+    pub page_number: Option<u32>,
+    pub page_size: Option<u32>,
+    pub item_count: Option<u32>,
+    pub page_count: Option<u32>,
+}
+
+impl Pageable for SubjectDatasetPage {
+    fn page_number(&self) -> u32 {
+        self.page_number.unwrap_or(1)
+    }
+
+    fn page_size(&self) -> u32 {
+        self.page_size.unwrap_or(20)
+    }
+}
+
+/// each row is converted to a map cell, key is column name.
+impl DataPage for SubjectDatasetPage {
+    fn data(&self) -> PageDataSet {
+        let (Some(columns), Some(data)) = (&self.columns, &self.data) else {
+            return vec![];
+        };
+        data.iter()
+            .map(|row| {
+                let map: HashMap<String, Value> = columns
+                    .iter()
+                    .zip(row.iter())
+                    .map(|(column, cell)| {
+                        (
+                            column.clone(),
+                            serde_json::to_value(cell).unwrap_or(Value::Null),
+                        )
+                    })
+                    .collect();
+                vec![PageDataCell::Map(map)]
+            })
+            .collect()
+    }
+
+    fn item_count(&self) -> u32 {
+        self.item_count.unwrap_or(0)
+    }
+
+    fn page_count(&self) -> u32 {
+        self.page_count.unwrap_or(0)
+    }
+}
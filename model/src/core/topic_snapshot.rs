@@ -7,7 +7,7 @@ use elf_base::serde::{option_naive_date, option_naive_datetime};
 use elf_base::{ErrorCode, StdR};
 use elf_model_marco::{adapt_model, Display, Serde, StrEnum};
 
-#[derive(Display, Serde, StrEnum)]
+#[derive(Display, Serde, PartialEq, Debug, Clone, StrEnum)]
 pub enum TopicSnapshotFrequency {
     Daily,
     Weekly,
@@ -25,16 +25,16 @@ pub struct TopicSnapshotScheduler {
     pub pipeline_id: Option<PipelineId>,
     pub frequency: Option<TopicSnapshotFrequency>,
     pub filter: Option<ParameterJoint>,
-    /// only for weekly
+    /// only for weekly, 0 is sunday, 6 is saturday
     pub weekday: Option<i8>,
-    /// only for monthly
+    /// only for monthly, 1 - 31, the last day of month is used when month doesn't have given day
     pub day: Option<i8>,
     pub hour: Option<i8>,
     pub minute: Option<i8>,
     pub enabled: Option<bool>,
 }
 
-#[derive(Display, Serde, StrEnum)]
+#[derive(Display, Serde, PartialEq, Debug, Clone, StrEnum)]
 pub enum TopicSnapshotJobLockStatus {
     Ready,
     #[display = "fail"]
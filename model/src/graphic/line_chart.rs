@@ -14,8 +14,8 @@ pub struct LineChartSettingsSeries {
 
 #[adapt_model(storable)]
 pub struct LineChartSettings {
-    series: Option<LineChartSettingsSeries>,
-    label: Option<BarChartSettingsLabel>,
+    pub series: Option<LineChartSettingsSeries>,
+    pub label: Option<BarChartSettingsLabel>,
     /// [EChartsLegendHolder]
     pub legend: Option<EChartsLegend>,
     /// [EChartsGridHolder]
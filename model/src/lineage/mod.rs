@@ -1,24 +1,60 @@
-use crate::{BaseDataModel, Storable, TenantId};
-use elf_model_marco::adapt_model;
-use std::collections::HashMap;
+use crate::{BaseDataModel, ModelErrorCode, PipelineId, Storable, TenantId};
+use elf_base::{ErrorCode, StdR};
+use elf_model_marco::{adapt_model, Display, Serde, StrEnum};
 
-#[adapt_model(storable)]
-pub struct LineageGraphs {
-    pub directed: Option<bool>,
-    pub multigraph: Option<bool>,
-    /// TODO don't know the exact type, since in python, it is [Dict]
-    pub graph: Option<HashMap<String, String>>,
-    pub tenant_id: Option<TenantId>,
+#[derive(Display, Serde, PartialEq, Eq, Hash, PartialOrd, Ord, Debug, Clone, StrEnum)]
+pub enum LineageNodeType {
+    Topic,
+    Factor,
+    Pipeline,
+    Subject,
+    Indicator,
+}
+
+#[derive(Display, Serde, PartialEq, Eq, Hash, PartialOrd, Ord, Debug, Clone, StrEnum)]
+pub enum LineageLinkType {
+    /// topic -> factor
+    Contains,
+    /// topic -> pipeline, pipeline is triggered by topic
+    Triggers,
+    /// topic -> pipeline, topic is read by pipeline
+    Reads,
+    /// pipeline -> topic, topic is written (insert, merge or delete) by pipeline
+    Writes,
+    /// factor -> factor, value is copied directly
+    Direct,
+    /// factor -> factor, value is computed
+    Computed,
+    /// factor/topic/subject -> pipeline/subject/indicator,
+    /// source is referred by target, but not flows to a factor.
+    /// e.g. factor is used in condition of pipeline.
+    UsedBy,
 }
 
 #[adapt_model(storable)]
-pub struct LineageNodes {
+pub struct LineageNode {
+    /// unique in graph
     pub id: Option<String>,
+    pub r#type: Option<LineageNodeType>,
     pub name: Option<String>,
 }
 
 #[adapt_model(storable)]
-pub struct LineageLinks {
-    /// TODO put a placeholder to let the [adapt_model] work
-    _placeholder: Option<i8>,
+pub struct LineageLink {
+    /// id of source node
+    pub source: Option<String>,
+    /// id of target node
+    pub target: Option<String>,
+    pub r#type: Option<LineageLinkType>,
+    /// the pipeline which creates this link, if there is
+    pub pipeline_id: Option<PipelineId>,
+}
+
+#[adapt_model(storable)]
+pub struct LineageGraph {
+    pub directed: Option<bool>,
+    pub multigraph: Option<bool>,
+    pub nodes: Option<Vec<LineageNode>>,
+    pub links: Option<Vec<LineageLink>>,
+    pub tenant_id: Option<TenantId>,
 }
@@ -7,7 +7,7 @@ use elf_base::serde::{option_naive_date, option_naive_datetime};
 use elf_base::{ErrorCode, StdR};
 use elf_model_marco::{adapt_model, Display, Serde, StrEnum};
 
-#[derive(Display, Serde, StrEnum)]
+#[derive(Display, Serde, PartialEq, Debug, Clone, StrEnum)]
 pub enum MonitorJobLockStatus {
     Ready,
     #[display = "fail"]
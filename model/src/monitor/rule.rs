@@ -6,7 +6,7 @@ use elf_base::serde::option_naive_datetime;
 use elf_base::{ErrorCode, StdR};
 use elf_model_marco::{adapt_model, Display, Serde, StrEnum};
 
-#[derive(Display, Serde, StrEnum)]
+#[derive(Display, Serde, PartialEq, Debug, Clone, StrEnum)]
 pub enum MonitorRuleCode {
     // structure
     RawMismatchStructure,
@@ -44,28 +44,28 @@ pub enum MonitorRuleCode {
     FactorAndAnother,
 }
 
-#[derive(Display, Serde, StrEnum)]
+#[derive(Display, Serde, PartialEq, Debug, Clone, StrEnum)]
 pub enum MonitorRuleGrade {
     Global,
     Topic,
     Factor,
 }
 
-#[derive(Display, Serde, StrEnum)]
+#[derive(Display, Serde, PartialEq, Debug, Clone, StrEnum)]
 pub enum MonitorRuleSeverity {
     Fatal,
     Warn,
     Trace,
 }
 
-#[derive(Display, Serde, StrEnum)]
+#[derive(Display, Serde, PartialEq, Debug, Clone, StrEnum)]
 pub enum MonitorRuleStatisticalInterval {
     Daily,
     Weekly,
     Monthly,
 }
 
-#[derive(Display, Serde, StrEnum)]
+#[derive(Display, Serde, PartialEq, Debug, Clone, StrEnum)]
 pub enum MonitorRuleCompareOperator {
     #[display = "eq"]
     Equal,
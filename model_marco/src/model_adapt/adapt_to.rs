@@ -111,11 +111,11 @@ impl AdaptTo {
 
     fn audit_fields() -> proc_macro2::TokenStream {
         quote! {
-            #[serde(skip_serializing_if = "Option::is_none", with = "option_naive_datetime")]
+            #[serde(default, skip_serializing_if = "Option::is_none", with = "option_naive_datetime")]
             pub created_at: Option<chrono::NaiveDateTime>,
             #[serde(skip_serializing_if = "Option::is_none")]
             pub created_by: Option<UserId>,
-            #[serde(skip_serializing_if = "Option::is_none", with = "option_naive_datetime")]
+            #[serde(default, skip_serializing_if = "Option::is_none", with = "option_naive_datetime")]
             pub last_modified_at: Option<chrono::NaiveDateTime>,
             #[serde(skip_serializing_if = "Option::is_none")]
             pub last_modified_by: Option<UserId>,
@@ -179,7 +179,7 @@ impl AdaptTo {
 
     fn last_visit_fields() -> proc_macro2::TokenStream {
         quote! {
-            #[serde(skip_serializing_if = "Option::is_none", with = "option_naive_datetime")]
+            #[serde(default, skip_serializing_if = "Option::is_none", with = "option_naive_datetime")]
             pub last_visit_time: Option<chrono::NaiveDateTime>,
         }
     }
@@ -0,0 +1,30 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use elf_pipeline_kernel::{DataPath, DataPathSimplifierFixtures};
+use std::hint::black_box;
+
+fn evaluate(c: &mut Criterion) {
+    let in_memory_data = DataPathSimplifierFixtures::in_memory_data();
+    let written: Vec<DataPath> = DataPathSimplifierFixtures::CORPUS
+        .iter()
+        .map(|(path, _)| DataPathSimplifierFixtures::as_is(path).unwrap())
+        .collect();
+    let simplified: Vec<DataPath> = DataPathSimplifierFixtures::CORPUS
+        .iter()
+        .map(|(path, _)| DataPath::from_str(path).unwrap())
+        .collect();
+
+    let mut group = c.benchmark_group("data_path_evaluate");
+    for (name, paths) in [("as_is", &written), ("simplified", &simplified)] {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                for path in paths.iter() {
+                    black_box(in_memory_data.value_of(path).unwrap());
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, evaluate);
+criterion_main!(benches);
@@ -0,0 +1,254 @@
+use crate::{PipelineIndexBuilder, PipelineKernelErrorCode};
+use elf_base::{ErrorCode, StdR, VoidR};
+use elf_model::{FactorId, FactorIndex, PipelineId, PipelineIndex, TenantId, Topic, TopicId};
+use elf_runtime_model_kernel::{
+    AnalysisService, FactorIndexBuilder, FactorIndexProvider, IdGen, PipelineIndexProvider,
+    PipelineSchema, PipelineSchemaProvider, PipelineService, TopicSchemaProvider, TopicService,
+};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
+/// maintain the factor indexes and pipeline indexes,
+/// and search on them for data catalog.
+pub struct AnalysisIndexer;
+
+impl AnalysisIndexer {
+    /// regenerate factor indexes of given topic.
+    /// pipelines referring given topic are reindexed also,
+    /// - pipelines triggered by given topic, since the factor names in data path of them are resolved by this topic,
+    /// - pipelines whose actions read or write factors of given topic, found by the existing pipeline indexes.
+    pub fn index_topic(topic: &Topic) -> VoidR {
+        let topic_id = match &topic.topic_id {
+            Some(topic_id) => topic_id,
+            _ => {
+                return PipelineKernelErrorCode::AnalysisTopicIdMissed
+                    .msg("Topic id is required for indexing factors.");
+            }
+        };
+        let tenant_id = match &topic.tenant_id {
+            Some(tenant_id) => tenant_id,
+            _ => {
+                return PipelineKernelErrorCode::AnalysisTenantIdMissed.msg(format!(
+                    "Tenant id is required for indexing topic[{}].",
+                    topic_id
+                ));
+            }
+        };
+
+        let mut indexes = FactorIndexBuilder::of_topic(topic);
+        for index in indexes.iter_mut() {
+            index.factor_index_id = Some(IdGen::next_id()?.to_string());
+        }
+        AnalysisService::factor_index()?.replace_by_topic(topic_id, tenant_id, indexes)?;
+
+        let triggered = PipelineService::schema()?
+            .by_topic_id(topic_id, tenant_id)?
+            .unwrap_or_default();
+        let mut indexes =
+            AnalysisService::pipeline_index()?.find_by_mapping_to(topic_id, None, tenant_id)?;
+        indexes.extend(
+            AnalysisService::pipeline_index()?.find_by_source_from(topic_id, None, tenant_id)?,
+        );
+        for pipeline in triggered.iter() {
+            Self::index_pipeline(pipeline)?;
+        }
+        for pipeline_id in Self::referring_pipelines(&triggered, &indexes) {
+            if let Some(pipeline) =
+                PipelineService::schema()?.by_pipeline_id(&pipeline_id, tenant_id)?
+            {
+                Self::index_pipeline(&pipeline)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// pipelines of given indexes, excludes the triggered ones.
+    fn referring_pipelines(
+        triggered: &[Arc<PipelineSchema>],
+        indexes: &[PipelineIndex],
+    ) -> Vec<PipelineId> {
+        Self::distinct_pipelines(indexes)
+            .into_iter()
+            .filter(|pipeline_id| {
+                !triggered
+                    .iter()
+                    .any(|pipeline| pipeline.pipeline_id().as_ref() == pipeline_id)
+            })
+            .collect()
+    }
+
+    /// regenerate pipeline indexes of given pipeline.
+    pub fn index_pipeline(pipeline: &Arc<PipelineSchema>) -> VoidR {
+        let tenant_id = pipeline.tenant_id();
+        let mut topics = HashMap::new();
+        for topic_id in PipelineIndexBuilder::topic_ids(pipeline) {
+            let topic = TopicService::schema()?.by_id(&topic_id, tenant_id)?;
+            topics.insert(topic_id, topic);
+        }
+
+        let mut indexes = PipelineIndexBuilder::of_pipeline(pipeline, &topics)?;
+        for index in indexes.iter_mut() {
+            index.pipeline_index_id = Some(IdGen::next_id()?.to_string());
+        }
+        AnalysisService::pipeline_index()?.replace_by_pipeline(
+            pipeline.pipeline_id(),
+            tenant_id,
+            indexes,
+        )
+    }
+
+    /// remove pipeline indexes of given pipeline, typically on pipeline deleted.
+    pub fn remove_pipeline(pipeline_id: &PipelineId, tenant_id: &TenantId) -> VoidR {
+        AnalysisService::pipeline_index()?.remove_by_pipeline(pipeline_id, tenant_id)
+    }
+
+    /// remove factor indexes of given topic, typically on topic deleted.
+    pub fn remove_topic(topic_id: &TopicId, tenant_id: &TenantId) -> VoidR {
+        AnalysisService::factor_index()?.remove_by_topic(topic_id, tenant_id)
+    }
+
+    /// search factors by name, label or description.
+    pub fn search_factors(text: &String, tenant_id: &TenantId) -> StdR<Vec<FactorIndex>> {
+        AnalysisService::factor_index()?.find_by_text(text, tenant_id)
+    }
+
+    /// pipelines which write given factor, or any factor of given topic when factor is none.
+    pub fn pipelines_writing(
+        topic_id: &TopicId,
+        factor_id: Option<&FactorId>,
+        tenant_id: &TenantId,
+    ) -> StdR<Vec<PipelineId>> {
+        let indexes = AnalysisService::pipeline_index()?
+            .find_by_mapping_to(topic_id, factor_id, tenant_id)?;
+        Ok(Self::distinct_pipelines(&indexes))
+    }
+
+    /// pipelines which read given factor, or any factor of given topic when factor is none,
+    /// and write the value to another factor.
+    pub fn pipelines_reading(
+        topic_id: &TopicId,
+        factor_id: Option<&FactorId>,
+        tenant_id: &TenantId,
+    ) -> StdR<Vec<PipelineId>> {
+        let indexes = AnalysisService::pipeline_index()?
+            .find_by_source_from(topic_id, factor_id, tenant_id)?;
+        Ok(Self::distinct_pipelines(&indexes))
+    }
+
+    /// factors (topic id and factor id) whose value are sourced from given topic.
+    pub fn factors_sourced_from(
+        topic_id: &TopicId,
+        tenant_id: &TenantId,
+    ) -> StdR<Vec<(TopicId, FactorId)>> {
+        let indexes =
+            AnalysisService::pipeline_index()?.find_by_source_from(topic_id, None, tenant_id)?;
+        Ok(Self::distinct_mapping_to(&indexes))
+    }
+
+    fn distinct_pipelines(indexes: &[PipelineIndex]) -> Vec<PipelineId> {
+        indexes
+            .iter()
+            .filter_map(|index| index.pipeline_id.clone())
+            .collect::<BTreeSet<PipelineId>>()
+            .into_iter()
+            .collect()
+    }
+
+    fn distinct_mapping_to(indexes: &[PipelineIndex]) -> Vec<(TopicId, FactorId)> {
+        indexes
+            .iter()
+            .filter_map(
+                |index| match (&index.mapping_to_topic_id, &index.mapping_to_factor_id) {
+                    (Some(topic_id), Some(factor_id)) => {
+                        Some((topic_id.clone(), factor_id.clone()))
+                    }
+                    _ => None,
+                },
+            )
+            .collect::<BTreeSet<(TopicId, FactorId)>>()
+            .into_iter()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AnalysisIndexer, PipelineIndexBuilder, TestFixtures};
+    use elf_model::{
+        FactorType, InsertRowAction, Pipeline, PipelineAction, PipelineIndex, PipelineStage,
+        PipelineTriggerType, PipelineUnit,
+    };
+    use elf_runtime_model_kernel::PipelineSchema;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    fn pipeline(
+        pipeline_id: &str,
+        topic_id: &str,
+        actions: Vec<PipelineAction>,
+    ) -> Arc<PipelineSchema> {
+        let pipeline = Pipeline::new()
+            .pipeline_id(pipeline_id.to_string())
+            .name(format!("pipeline-{}", pipeline_id))
+            .topic_id(topic_id.to_string())
+            .r#type(PipelineTriggerType::Insert)
+            .stages(vec![PipelineStage::new()
+                .stage_id(String::from("s1"))
+                .units(vec![PipelineUnit::new()
+                    .unit_id(String::from("u1"))
+                    .r#do(actions)])])
+            .tenant_id(TestFixtures::tenant_id());
+        Arc::new(PipelineSchema::new(pipeline).unwrap())
+    }
+
+    fn insert_row(
+        action_id: &str,
+        topic_id: &str,
+        source: (&str, &str),
+        factor_id: &str,
+    ) -> PipelineAction {
+        InsertRowAction::init()
+            .action_id(action_id.to_string())
+            .topic_id(topic_id.to_string())
+            .mapping(vec![TestFixtures::mapping(
+                TestFixtures::topic_factor(source.0, source.1),
+                factor_id,
+            )])
+            .to_action()
+    }
+
+    #[test]
+    fn test_referring_pipelines() {
+        let t1 = TestFixtures::topic("t1", vec![("f1", "amount", FactorType::Number)]);
+        let t2 = TestFixtures::topic("t2", vec![("g1", "amount", FactorType::Number)]);
+        let t3 = TestFixtures::topic("t3", vec![("h1", "amount", FactorType::Number)]);
+        // p1 is triggered by t1, writes g1 of t2
+        let p1 = pipeline("p1", "t1", vec![insert_row("a1", "t2", ("t1", "f1"), "g1")]);
+        // p2 is triggered by t2, writes t3
+        let p2 = pipeline("p2", "t2", vec![insert_row("a2", "t3", ("t2", "g1"), "h1")]);
+        let topics = HashMap::from([
+            (String::from("t1"), t1.clone()),
+            (String::from("t2"), t2),
+            (String::from("t3"), t3),
+        ]);
+        let mut indexes = PipelineIndexBuilder::of_pipeline(&p1, &topics).unwrap();
+        indexes.extend(PipelineIndexBuilder::of_pipeline(&p2, &topics).unwrap());
+
+        // factor g1 of t2 renamed, p1 is not triggered by t2, but writes g1
+        let renamed = TestFixtures::topic("t2", vec![("g1", "total", FactorType::Number)]);
+        let indexes_of_t2: Vec<PipelineIndex> = indexes
+            .into_iter()
+            .filter(|index| {
+                index.mapping_to_topic_id.as_deref() == Some("t2")
+                    || index.source_from_topic_id.as_deref() == Some("t2")
+            })
+            .collect();
+        let referring = AnalysisIndexer::referring_pipelines(&[p2], &indexes_of_t2);
+        assert_eq!(referring, vec![String::from("p1")]);
+
+        let topics = HashMap::from([(String::from("t1"), t1), (String::from("t2"), renamed)]);
+        let reindexed = PipelineIndexBuilder::of_pipeline(&p1, &topics).unwrap();
+        assert_eq!(1, reindexed.len());
+        assert_eq!(Some("g1"), reindexed[0].mapping_to_factor_id.as_deref());
+    }
+}
@@ -0,0 +1,24 @@
+use crate::AnalysisIndexer;
+use elf_auth::Principal;
+use elf_base::VoidR;
+use elf_model::{Pipeline, Topic};
+use elf_runtime_model_kernel::{
+    PipelineMetaProvider, PipelineSchema, PipelineService, TopicMetaProvider, TopicService,
+};
+use std::sync::Arc;
+
+/// save topic and pipeline meta, and keep the analysis indexes synchronized.
+/// each save is recorded as an operation of principal by meta service.
+pub struct MetaSaveFacade;
+
+impl MetaSaveFacade {
+    pub fn save_topic(principal: &Principal, topic: Topic) -> VoidR {
+        TopicService::meta()?.save(principal, &topic)?;
+        AnalysisIndexer::index_topic(&topic)
+    }
+
+    pub fn save_pipeline(principal: &Principal, pipeline: Pipeline) -> VoidR {
+        PipelineService::meta()?.save(principal, &pipeline)?;
+        AnalysisIndexer::index_pipeline(&Arc::new(PipelineSchema::new(pipeline)?))
+    }
+}
@@ -0,0 +1,7 @@
+mod analysis_indexer;
+mod meta_save_facade;
+mod pipeline_index_builder;
+
+pub use analysis_indexer::*;
+pub use meta_save_facade::*;
+pub use pipeline_index_builder::*;
@@ -0,0 +1,247 @@
+use crate::{DataLineage, LineageFlow, LineageNodeKey, LineagePipelineWalker};
+use chrono::{NaiveDateTime, Utc};
+use elf_base::StdR;
+use elf_model::{PipelineIndex, PipelineIndexRefType, TopicId};
+use elf_runtime_model_kernel::{ArcPipelineAction, PipelineSchema, TopicSchema};
+use std::collections::{BTreeSet, HashMap};
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// build pipeline indexes of pipeline, one index for each factor flow of write actions.
+/// - flow is direct when the source is a factor, copied without any computation,
+/// - flow is computed otherwise,
+/// - source is none when target factor is written by constant only.
+///
+/// index id is not assigned here, should be assigned before persist.
+pub struct PipelineIndexBuilder;
+
+impl PipelineIndexBuilder {
+    /// topic ids of trigger topic, and topics read or written by actions.
+    /// these topics are required to resolve the factor names in data path.
+    pub fn topic_ids(pipeline: &PipelineSchema) -> BTreeSet<TopicId> {
+        let mut topic_ids = BTreeSet::new();
+        topic_ids.insert(pipeline.pipeline().topic_id.to_string());
+        for stage in pipeline.pipeline().stages.iter() {
+            for unit in stage.units.iter() {
+                for action in unit.r#do.iter() {
+                    let topic_id = match action.deref() {
+                        ArcPipelineAction::Alarm(_)
+                        | ArcPipelineAction::CopyToMemory(_)
+                        | ArcPipelineAction::WriteToExternal(_) => continue,
+                        ArcPipelineAction::ReadRow(action) => &action.topic_id,
+                        ArcPipelineAction::ReadRows(action) => &action.topic_id,
+                        ArcPipelineAction::ReadFactor(action) => &action.topic_id,
+                        ArcPipelineAction::ReadFactors(action) => &action.topic_id,
+                        ArcPipelineAction::Exists(action) => &action.topic_id,
+                        ArcPipelineAction::InsertRow(action) => &action.topic_id,
+                        ArcPipelineAction::MergeRow(action) => &action.topic_id,
+                        ArcPipelineAction::InsertOrMergeRow(action) => &action.topic_id,
+                        ArcPipelineAction::WriteFactor(action) => &action.topic_id,
+                        ArcPipelineAction::DeleteRow(action) => &action.topic_id,
+                        ArcPipelineAction::DeleteRows(action) => &action.topic_id,
+                    };
+                    topic_ids.insert(topic_id.to_string());
+                }
+            }
+        }
+        topic_ids
+    }
+
+    pub fn of_pipeline(
+        pipeline: &PipelineSchema,
+        topics: &HashMap<TopicId, Arc<TopicSchema>>,
+    ) -> StdR<Vec<PipelineIndex>> {
+        Self::of_pipeline_at(pipeline, topics, Utc::now().naive_utc())
+    }
+
+    pub fn of_pipeline_at(
+        pipeline: &PipelineSchema,
+        topics: &HashMap<TopicId, Arc<TopicSchema>>,
+        now: NaiveDateTime,
+    ) -> StdR<Vec<PipelineIndex>> {
+        // lineage is not used, only the flows are needed
+        let mut lineage = DataLineage::new();
+        let flows = LineagePipelineWalker::new(pipeline, topics).walk(&mut lineage)?;
+        Ok(flows
+            .into_iter()
+            .map(|flow| Self::of_flow(pipeline, flow, now))
+            .collect())
+    }
+
+    fn of_flow(pipeline: &PipelineSchema, flow: LineageFlow, now: NaiveDateTime) -> PipelineIndex {
+        let mut index = PipelineIndex::new();
+        index.pipeline_id = Some(pipeline.pipeline_id().to_string());
+        index.pipeline_name = Some(pipeline.name().to_string());
+        index.stage_id = Some(flow.stage_id.to_string());
+        index.stage_name = Some(flow.stage_name.to_string());
+        index.unit_id = Some(flow.unit_id.to_string());
+        index.unit_name = Some(flow.unit_name.to_string());
+        index.action_id = Some(flow.action_id.to_string());
+        if let LineageNodeKey::Factor(topic_id, factor_id) = flow.target {
+            index.mapping_to_topic_id = Some(topic_id);
+            index.mapping_to_factor_id = Some(factor_id);
+        }
+        if let Some(LineageNodeKey::Factor(topic_id, factor_id)) = flow.source {
+            index.source_from_topic_id = Some(topic_id);
+            index.source_from_factor_id = Some(factor_id);
+        }
+        index.ref_type = Some(if flow.direct {
+            PipelineIndexRefType::Direct
+        } else {
+            PipelineIndexRefType::Computed
+        });
+        index.tenant_id = Some(pipeline.tenant_id().to_string());
+        index.created_at = Some(now);
+        index.last_modified_at = Some(now);
+        index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{PipelineIndexBuilder, TestFixtures};
+    use elf_model::{
+        ConstantParameter, FactorType, InsertRowAction, NotEmptyExpression, ParameterJoint,
+        Pipeline, PipelineIndex, PipelineIndexRefType, PipelineStage, PipelineTriggerType,
+        PipelineUnit, WriteFactorAction, YearOfParameter,
+    };
+    use elf_runtime_model_kernel::PipelineSchema;
+    use std::collections::HashMap;
+
+    fn pipeline() -> PipelineSchema {
+        let insert = InsertRowAction::init()
+            .action_id(String::from("a1"))
+            .topic_id(String::from("t2"))
+            .mapping(vec![
+                TestFixtures::mapping(TestFixtures::topic_factor("t1", "f1"), "g1"),
+                TestFixtures::mapping(
+                    YearOfParameter::init()
+                        .parameter(Box::new(TestFixtures::topic_factor("t1", "f3")))
+                        .to_parameter(),
+                    "g2",
+                ),
+                TestFixtures::mapping(
+                    ConstantParameter::of(String::from("&cur.code")).to_parameter(),
+                    "g3",
+                ),
+                TestFixtures::mapping(
+                    ConstantParameter::of(String::from("fixed")).to_parameter(),
+                    "g4",
+                ),
+            ])
+            .to_action();
+        let write = WriteFactorAction::init()
+            .action_id(String::from("a2"))
+            .topic_id(String::from("t2"))
+            .factor_id(String::from("g1"))
+            .by(ParameterJoint::and(vec![NotEmptyExpression::init()
+                .left(TestFixtures::topic_factor("t2", "g3"))
+                .to_condition()]))
+            .source(ConstantParameter::of(String::from("{amount}{code}")).to_parameter())
+            .to_action();
+        let pipeline = Pipeline::new()
+            .pipeline_id(String::from("p1"))
+            .name(String::from("raw to target"))
+            .topic_id(String::from("t1"))
+            .r#type(PipelineTriggerType::Insert)
+            .stages(vec![PipelineStage::new()
+                .stage_id(String::from("s1"))
+                .name(String::from("stage"))
+                .units(vec![PipelineUnit::new()
+                    .unit_id(String::from("u1"))
+                    .name(String::from("unit"))
+                    .r#do(vec![insert, write])])])
+            .tenant_id(TestFixtures::tenant_id());
+        PipelineSchema::new(pipeline).unwrap()
+    }
+
+    fn find<'a>(
+        indexes: &'a [PipelineIndex],
+        action_id: &str,
+        factor_id: &str,
+    ) -> Vec<&'a PipelineIndex> {
+        indexes
+            .iter()
+            .filter(|index| {
+                index.action_id.as_deref() == Some(action_id)
+                    && index.mapping_to_factor_id.as_deref() == Some(factor_id)
+            })
+            .collect()
+    }
+
+    fn is_direct(index: &PipelineIndex) -> bool {
+        matches!(index.ref_type, Some(PipelineIndexRefType::Direct))
+    }
+
+    #[test]
+    fn test_of_pipeline() {
+        let mut topics = HashMap::new();
+        topics.insert(
+            String::from("t1"),
+            TestFixtures::topic(
+                "t1",
+                vec![
+                    ("f1", "amount", FactorType::Number),
+                    ("f2", "code", FactorType::Text),
+                    ("f3", "date", FactorType::Date),
+                ],
+            ),
+        );
+        topics.insert(
+            String::from("t2"),
+            TestFixtures::topic(
+                "t2",
+                vec![
+                    ("g1", "amount", FactorType::Text),
+                    ("g2", "year", FactorType::Year),
+                    ("g3", "code", FactorType::Text),
+                    ("g4", "fixed", FactorType::Text),
+                ],
+            ),
+        );
+        let pipeline = pipeline();
+        assert_eq!(
+            PipelineIndexBuilder::topic_ids(&pipeline)
+                .into_iter()
+                .collect::<Vec<String>>(),
+            vec![String::from("t1"), String::from("t2")]
+        );
+
+        let indexes = PipelineIndexBuilder::of_pipeline(&pipeline, &topics).unwrap();
+        assert_eq!(6, indexes.len());
+
+        let g1 = find(&indexes, "a1", "g1");
+        assert_eq!(1, g1.len());
+        assert!(is_direct(g1[0]));
+        assert_eq!(Some("t1"), g1[0].source_from_topic_id.as_deref());
+        assert_eq!(Some("f1"), g1[0].source_from_factor_id.as_deref());
+        assert_eq!(Some("t2"), g1[0].mapping_to_topic_id.as_deref());
+        assert_eq!(Some("stage"), g1[0].stage_name.as_deref());
+        assert_eq!(Some("unit"), g1[0].unit_name.as_deref());
+        assert_eq!(Some("raw to target"), g1[0].pipeline_name.as_deref());
+        assert_eq!(Some(TestFixtures::TENANT_ID), g1[0].tenant_id.as_deref());
+
+        let g2 = find(&indexes, "a1", "g2");
+        assert_eq!(1, g2.len());
+        assert!(!is_direct(g2[0]));
+        assert_eq!(Some("f3"), g2[0].source_from_factor_id.as_deref());
+
+        let g3 = find(&indexes, "a1", "g3");
+        assert_eq!(1, g3.len());
+        assert!(is_direct(g3[0]));
+        assert_eq!(Some("f2"), g3[0].source_from_factor_id.as_deref());
+
+        // constant only, no source
+        let g4 = find(&indexes, "a1", "g4");
+        assert_eq!(1, g4.len());
+        assert!(!is_direct(g4[0]));
+        assert!(g4[0].source_from_topic_id.is_none());
+        assert!(g4[0].source_from_factor_id.is_none());
+
+        // concatenated from two factors
+        let a2 = find(&indexes, "a2", "g1");
+        assert_eq!(2, a2.len());
+        assert!(a2.iter().all(|index| !is_direct(index)));
+        assert!(a2.iter().all(|index| index.pipeline_index_id.is_none()));
+    }
+}
@@ -0,0 +1,127 @@
+use elf_pipeline_kernel::{DataPath, DataPathEvaluator};
+use std::io::Read;
+use std::process::ExitCode;
+use std::sync::Arc;
+
+const USAGE: &str = "\
+Usage: data_path_eval <path> [options]
+
+Evaluate data path against json documents, current data is read from stdin when --current not given.
+
+Options:
+  --current <file>      json object file of current data
+  --previous <file>     json object file of previous data
+  --variables <file>    json object file of variables
+  --tenant <tenant id>  recognize functions registered for tenant
+  --json                print evaluation as json";
+
+#[derive(Default)]
+struct Args {
+    path: Option<String>,
+    current: Option<String>,
+    previous: Option<String>,
+    variables: Option<String>,
+    tenant_id: Option<String>,
+    json: bool,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args::default();
+    let mut iter = std::env::args().skip(1);
+    while let Some(arg) = iter.next() {
+        let mut value_of = |name: &str| {
+            iter.next()
+                .ok_or_else(|| format!("Value of option[{}] is missed.", name))
+        };
+        match arg.as_str() {
+            "--current" => args.current = Some(value_of("--current")?),
+            "--previous" => args.previous = Some(value_of("--previous")?),
+            "--variables" => args.variables = Some(value_of("--variables")?),
+            "--tenant" => args.tenant_id = Some(value_of("--tenant")?),
+            "--json" => args.json = true,
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            _ if arg.starts_with("--") => return Err(format!("Unknown option[{}].", arg)),
+            _ if args.path.is_some() => return Err(format!("Unexpected argument[{}].", arg)),
+            _ => args.path = Some(arg),
+        }
+    }
+    Ok(args)
+}
+
+fn read_file(file: &str) -> Result<String, String> {
+    std::fs::read_to_string(file)
+        .map_err(|e| format!("Failed to read file[{}], caused by {}.", file, e))
+}
+
+fn read_stdin() -> Result<String, String> {
+    let mut json = String::new();
+    std::io::stdin()
+        .read_to_string(&mut json)
+        .map_err(|e| format!("Failed to read stdin, caused by {}.", e))?;
+    Ok(json)
+}
+
+fn run(args: Args) -> Result<String, String> {
+    let path = args.path.ok_or_else(|| USAGE.to_string())?;
+
+    let current = match &args.current {
+        Some(file) => read_file(file)?,
+        _ => read_stdin()?,
+    };
+    let mut evaluator = DataPathEvaluator::of_current(&current).map_err(|e| e.to_string())?;
+    if let Some(file) = &args.previous {
+        evaluator = evaluator
+            .with_previous(&read_file(file)?)
+            .map_err(|e| e.to_string())?;
+    }
+    if let Some(file) = &args.variables {
+        evaluator = evaluator
+            .with_variables(&read_file(file)?)
+            .map_err(|e| e.to_string())?;
+    }
+    if let Some(tenant_id) = &args.tenant_id {
+        evaluator = evaluator.with_tenant_id(tenant_id.clone());
+    }
+
+    let data_path = match evaluator.parse_path(&path) {
+        Ok(data_path) => data_path,
+        Err(e) => {
+            let diagnostics = match &args.tenant_id {
+                Some(tenant_id) => {
+                    DataPath::diagnose_of_tenant(&path, &Arc::new(tenant_id.clone()))
+                }
+                _ => DataPath::diagnose(&path),
+            };
+            if diagnostics.is_empty() {
+                return Err(e.to_string());
+            }
+            return Err(diagnostics
+                .iter()
+                .map(|diagnostic| diagnostic.render(&path))
+                .collect::<Vec<String>>()
+                .join("\n\n"));
+        }
+    };
+    let evaluation = evaluator
+        .evaluate_path(&data_path)
+        .map_err(|e| e.to_string())?;
+
+    if args.json {
+        serde_json::to_string_pretty(&evaluation).map_err(|e| e.to_string())
+    } else {
+        Ok(evaluation.to_string())
+    }
+}
+
+fn main() -> ExitCode {
+    match parse_args().and_then(run) {
+        Ok(output) => {
+            println!("{}", output);
+            ExitCode::SUCCESS
+        }
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use crate::{
+    NotificationMessage, NotificationParams, NotificationSenders, PipelineKernelErrorCode,
+};
+use elf_base::{ErrorCode, StdR, StringUtils};
+use elf_model::{NotificationDefinition, NotificationType, OperationType, TenantId, UserId};
+use elf_runtime_model_kernel::{
+    CatalogService, DataOwner, DataOwnership, MetaTupleType, OperationRecord,
+};
+use serde_json::Value;
+
+/// notify owners of catalogs when a topic in catalog is changed,
+/// host calls [CatalogChangeNotifier::notify] after the change is recorded,
+/// see [elf_runtime_model_kernel::OperationLogService].
+///
+/// message is sent by the given notification, inactive owners are not notified.
+/// for email notification, receivers are the emails of owners, instead of the "to" param.
+pub struct CatalogChangeNotifier {
+    notification: NotificationDefinition,
+    senders: NotificationSenders,
+}
+
+impl CatalogChangeNotifier {
+    pub fn new(notification: NotificationDefinition, senders: NotificationSenders) -> Self {
+        Self {
+            notification,
+            senders,
+        }
+    }
+
+    /// returns ids of notified owners, nothing is sent when the change is not on a topic.
+    pub fn notify(&self, record: &OperationRecord, tenant_id: &TenantId) -> StdR<Vec<UserId>> {
+        if !matches!(record.tuple_type, MetaTupleType::Topic) {
+            return Ok(vec![]);
+        }
+        let ownerships = CatalogService::owners_of_topic(&record.tuple_id, tenant_id)?;
+        self.notify_owners(record, &ownerships)
+    }
+
+    /// returns ids of notified owners, nothing is sent when there is no owner to notify.
+    pub fn notify_owners(
+        &self,
+        record: &OperationRecord,
+        ownerships: &[DataOwnership],
+    ) -> StdR<Vec<UserId>> {
+        let Some(r#type) = &self.notification.r#type else {
+            return PipelineKernelErrorCode::NotificationNotFound.msg(format!(
+                "Type of notification[{}] is missed.",
+                self.notification.notification_id.as_deref().unwrap_or("")
+            ));
+        };
+
+        let mut owners: Vec<&DataOwner> = vec![];
+        for owner in ownerships.iter().flat_map(|ownership| ownership.owners()) {
+            if owner.active && !owners.iter().any(|o| o.user_id == owner.user_id) {
+                owners.push(owner);
+            }
+        }
+        let mut params = NotificationParams::of(&self.notification);
+        if matches!(r#type, NotificationType::Email) {
+            owners.retain(|owner| owner.email.as_ref().is_some_and(|e| e.is_not_blank()));
+            let receivers: Vec<&str> = owners
+                .iter()
+                .filter_map(|owner| owner.email.as_deref())
+                .collect();
+            params = params.with("to", receivers.join(","));
+        }
+        if owners.is_empty() {
+            return Ok(vec![]);
+        }
+
+        self.senders
+            .sender_of(r#type)
+            .send(&params, &Self::message_of(record, ownerships))?;
+        Ok(owners.iter().map(|owner| owner.user_id.clone()).collect())
+    }
+
+    /// plain text, lists the catalogs and the changed paths of topic
+    fn message_of(record: &OperationRecord, ownerships: &[DataOwnership]) -> NotificationMessage {
+        let topic_name = record
+            .after
+            .as_ref()
+            .or(record.before.as_ref())
+            .and_then(|topic| topic.get("name"))
+            .and_then(Value::as_str)
+            .unwrap_or(&record.tuple_id);
+        let action = match record.operation_type {
+            OperationType::Create => "created",
+            OperationType::Update => "updated",
+            OperationType::Delete => "deleted",
+        };
+        let catalogs = ownerships
+            .iter()
+            .map(|ownership| {
+                format!(
+                    "catalog[{}]",
+                    ownership
+                        .catalog_name
+                        .as_deref()
+                        .unwrap_or(&ownership.catalog_id)
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        let mut body = format!("Topic[{}] in {} is {}.", topic_name, catalogs, action);
+        let diffs = record.diffs();
+        if matches!(record.operation_type, OperationType::Update) && !diffs.is_empty() {
+            body.push_str("\n\nChanges:");
+            for diff in diffs {
+                body.push_str(&format!("\n- {} {}", diff.path, diff.kind));
+            }
+        }
+        NotificationMessage {
+            subject: format!("Topic[{}] is {}", topic_name, action),
+            body,
+            html: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        CatalogChangeNotifier, NotificationMessage, NotificationParams, NotificationSender,
+        NotificationSenders,
+    };
+    use elf_base::VoidR;
+    use elf_model::{NotificationDefinition, NotificationParam, NotificationType, User};
+    use elf_runtime_model_kernel::{DataOwner, DataOwnership, MetaTupleType, OperationRecord};
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    struct RecordingSender(Arc<Mutex<Vec<String>>>);
+
+    impl NotificationSender for RecordingSender {
+        fn send(&self, params: &NotificationParams, message: &NotificationMessage) -> VoidR {
+            self.0.lock().unwrap().push(format!(
+                "{}|{}|{}",
+                params.get("to").unwrap_or(""),
+                message.subject,
+                message.body
+            ));
+            Ok(())
+        }
+    }
+
+    fn owner(user_id: &str, email: Option<&str>, active: bool) -> DataOwner {
+        let mut user = User::new()
+            .user_id(user_id.to_string())
+            .name(user_id.to_string())
+            .is_active(active);
+        if let Some(email) = email {
+            user = user.email(email.to_string());
+        }
+        DataOwner::of(&user_id.to_string(), Some(user))
+    }
+
+    fn ownerships() -> Vec<DataOwnership> {
+        vec![
+            DataOwnership {
+                catalog_id: String::from("c1"),
+                catalog_name: Some(String::from("Sales")),
+                tech_owner: Some(owner("u1", Some("u1@elf.io"), true)),
+                biz_owner: Some(owner("u2", None, true)),
+            },
+            DataOwnership {
+                catalog_id: String::from("c2"),
+                catalog_name: None,
+                tech_owner: Some(owner("u1", Some("u1@elf.io"), true)),
+                biz_owner: Some(owner("u3", Some("u3@elf.io"), false)),
+            },
+        ]
+    }
+
+    fn notifier(r#type: NotificationType, sent: &Arc<Mutex<Vec<String>>>) -> CatalogChangeNotifier {
+        let notification = NotificationDefinition::new()
+            .notification_id(String::from("n1"))
+            .r#type(r#type)
+            .params(vec![NotificationParam::new()
+                .name(String::from("to"))
+                .value(String::from("admin@elf.io"))]);
+        let senders = NotificationSenders::default()
+            .email(Box::new(RecordingSender(sent.clone())))
+            .slack(Box::new(RecordingSender(sent.clone())));
+        CatalogChangeNotifier::new(notification, senders)
+    }
+
+    fn record() -> OperationRecord {
+        OperationRecord::updated(
+            MetaTupleType::Topic,
+            String::from("t1"),
+            json!({"topicId": "t1", "name": "orders", "factors": [{"name": "amount"}]}),
+            json!({"topicId": "t1", "name": "orders", "factors": [{"name": "total"}]}),
+        )
+    }
+
+    #[test]
+    fn test_notify_owners() {
+        let sent = Arc::new(Mutex::new(vec![]));
+        // email, owners without email or inactive are not notified
+        let notified = notifier(NotificationType::Email, &sent)
+            .notify_owners(&record(), &ownerships())
+            .unwrap();
+        assert_eq!(notified, vec!["u1"]);
+        assert_eq!(
+            sent.lock().unwrap().pop().unwrap(),
+            "u1@elf.io|Topic[orders] is updated|Topic[orders] in catalog[Sales], catalog[c2] is updated.\n\nChanges:\n- factors[0].name changed"
+        );
+
+        // webhook, active owners are notified
+        let notified = notifier(NotificationType::Slack, &sent)
+            .notify_owners(&record(), &ownerships())
+            .unwrap();
+        assert_eq!(notified, vec!["u1", "u2"]);
+        assert_eq!(sent.lock().unwrap().len(), 1);
+
+        // no owner
+        sent.lock().unwrap().clear();
+        let notified = notifier(NotificationType::Slack, &sent)
+            .notify_owners(&record(), &[])
+            .unwrap();
+        assert!(notified.is_empty());
+        assert!(sent.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,3 @@
+mod catalog_change_notifier;
+
+pub use catalog_change_notifier::*;
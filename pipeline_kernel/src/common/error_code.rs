@@ -25,6 +25,10 @@ pub enum PipelineKernelErrorCode {
     IncorrectDataPath,
     PreviousTopicDataMissed,
     FailedToGetNextSeq,
+    FunctionNameInvalid,
+    FunctionNameConflict,
+    FunctionRegistryUnavailable,
+    EvaluateDataInvalid,
     // schema
     FactorNotFound,
     // pipeline
@@ -38,6 +42,7 @@ pub enum PipelineKernelErrorCode {
     ComputeParameterNotADate,
     ActionVariableIsNotPlain,
     UnitLoopVariableMissed,
+    PipelineTypeMismatch,
     // execution
     ExecutionRoundIndexOutOfRange,
     ExecutionRoundHasNoTask,
@@ -45,6 +50,60 @@ pub enum PipelineKernelErrorCode {
     IncorrectExecutionRoundForAddingTask,
     UnitLoopVariableNotAVec,
     ParallelUnitExecute,
+    // analysis
+    AnalysisTopicIdMissed,
+    AnalysisTenantIdMissed,
+    // subject
+    SubjectNotFound,
+    SubjectIdMissed,
+    SubjectTenantIdMissed,
+    SubjectDatasetMissed,
+    SubjectColumnIdMissed,
+    SubjectColumnParameterMissed,
+    SubjectColumnNotFound,
+    SubjectRecalculateColumnRefersRecalculate,
+    SubjectJoinIncomplete,
+    SubjectTopicNotJoined,
+    SubjectTopicNotDetermined,
+    SubjectFactorNameConflict,
+    SubjectAggregateValueNotNumeric,
+    SubjectDatasetTooLarge,
+    // indicator
+    IndicatorNotFound,
+    IndicatorIdMissed,
+    IndicatorBaseOnMissed,
+    IndicatorFactorNotFound,
+    BucketNotFound,
+    BucketFactorMissed,
+    BucketSegmentValueNotNumeric,
+    IndicatorTimeValueNotDatetime,
+    // objective
+    ObjectiveTimeframeInvalid,
+    ObjectiveFactorNotFound,
+    ObjectiveFactorCircularReference,
+    ObjectiveParameterMissed,
+    ObjectiveParameterNotSupported,
+    ObjectiveValueNotNumeric,
+    ObjectiveValueNotDate,
+    ObjectiveBucketSegmentNotFound,
+    ObjectiveTargetNotFound,
+    BreakdownDimensionInvalid,
+    DerivedObjectiveDefinitionMissed,
+    ObjectiveNotFound,
+    // topic snapshot
+    TopicSnapshotSchedulerIncomplete,
+    // subscription
+    SubscriptionEventIncomplete,
+    NotificationNotFound,
+    NotificationParamMissed,
+    NotificationSendFailed,
+    NotificationParamInvalid,
+    // report
+    ReportSubjectMissed,
+    ReportColumnNotFound,
+    ReportFunnelInvalid,
+    ReportIndicatorMissed,
+    ReportChartNotSupported,
 }
 
 impl ErrorCode for PipelineKernelErrorCode {
@@ -73,6 +132,10 @@ impl ErrorCode for PipelineKernelErrorCode {
             Self::IncorrectDataPath => "PLKN-00105",
             Self::PreviousTopicDataMissed => "PLKN-00106",
             Self::FailedToGetNextSeq => "PLKN-00107",
+            Self::FunctionNameInvalid => "PLKN-00108",
+            Self::FunctionNameConflict => "PLKN-00109",
+            Self::FunctionRegistryUnavailable => "PLKN-00110",
+            Self::EvaluateDataInvalid => "PLKN-00111",
 
             Self::FactorNotFound => "PLKN-00200",
 
@@ -86,6 +149,7 @@ impl ErrorCode for PipelineKernelErrorCode {
             Self::ComputeParameterNotADate => "PLKN-00307",
             Self::ActionVariableIsNotPlain => "PLKN-00308",
             Self::UnitLoopVariableMissed => "PLKN-00309",
+            Self::PipelineTypeMismatch => "PLKN-00310",
 
             Self::ExecutionRoundIndexOutOfRange => "PLKN-00400",
             Self::ExecutionRoundHasNoTask => "PLKN-00401",
@@ -93,6 +157,60 @@ impl ErrorCode for PipelineKernelErrorCode {
             Self::IncorrectExecutionRoundForAddingTask => "PLKN-00403",
             Self::UnitLoopVariableNotAVec => "PLKN-00404",
             Self::ParallelUnitExecute => "PLKN-00405",
+
+            Self::AnalysisTopicIdMissed => "PLKN-00500",
+            Self::AnalysisTenantIdMissed => "PLKN-00501",
+
+            Self::SubjectNotFound => "PLKN-00600",
+            Self::SubjectIdMissed => "PLKN-00601",
+            Self::SubjectTenantIdMissed => "PLKN-00602",
+            Self::SubjectDatasetMissed => "PLKN-00603",
+            Self::SubjectColumnIdMissed => "PLKN-00604",
+            Self::SubjectColumnParameterMissed => "PLKN-00605",
+            Self::SubjectColumnNotFound => "PLKN-00606",
+            Self::SubjectRecalculateColumnRefersRecalculate => "PLKN-00607",
+            Self::SubjectJoinIncomplete => "PLKN-00608",
+            Self::SubjectTopicNotJoined => "PLKN-00609",
+            Self::SubjectTopicNotDetermined => "PLKN-00610",
+            Self::SubjectFactorNameConflict => "PLKN-00611",
+            Self::SubjectAggregateValueNotNumeric => "PLKN-00612",
+            Self::SubjectDatasetTooLarge => "PLKN-00613",
+
+            Self::IndicatorNotFound => "PLKN-00700",
+            Self::IndicatorIdMissed => "PLKN-00701",
+            Self::IndicatorBaseOnMissed => "PLKN-00702",
+            Self::IndicatorFactorNotFound => "PLKN-00703",
+            Self::BucketNotFound => "PLKN-00704",
+            Self::BucketFactorMissed => "PLKN-00705",
+            Self::BucketSegmentValueNotNumeric => "PLKN-00706",
+            Self::IndicatorTimeValueNotDatetime => "PLKN-00707",
+
+            Self::ObjectiveTimeframeInvalid => "PLKN-00800",
+            Self::ObjectiveFactorNotFound => "PLKN-00801",
+            Self::ObjectiveFactorCircularReference => "PLKN-00802",
+            Self::ObjectiveParameterMissed => "PLKN-00803",
+            Self::ObjectiveParameterNotSupported => "PLKN-00804",
+            Self::ObjectiveValueNotNumeric => "PLKN-00805",
+            Self::ObjectiveValueNotDate => "PLKN-00806",
+            Self::ObjectiveBucketSegmentNotFound => "PLKN-00807",
+            Self::ObjectiveTargetNotFound => "PLKN-00808",
+            Self::BreakdownDimensionInvalid => "PLKN-00809",
+            Self::DerivedObjectiveDefinitionMissed => "PLKN-00810",
+            Self::ObjectiveNotFound => "PLKN-00811",
+
+            Self::TopicSnapshotSchedulerIncomplete => "PLKN-00900",
+
+            Self::SubscriptionEventIncomplete => "PLKN-01000",
+            Self::NotificationNotFound => "PLKN-01001",
+            Self::NotificationParamMissed => "PLKN-01002",
+            Self::NotificationSendFailed => "PLKN-01003",
+            Self::NotificationParamInvalid => "PLKN-01004",
+
+            Self::ReportSubjectMissed => "PLKN-01100",
+            Self::ReportColumnNotFound => "PLKN-01101",
+            Self::ReportFunnelInvalid => "PLKN-01102",
+            Self::ReportIndicatorMissed => "PLKN-01103",
+            Self::ReportChartNotSupported => "PLKN-01104",
         }
     }
 }
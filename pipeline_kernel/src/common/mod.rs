@@ -1,3 +1,7 @@
 mod error_code;
+#[cfg(test)]
+mod test_fixtures;
 
 pub use error_code::*;
+#[cfg(test)]
+pub use test_fixtures::*;
@@ -0,0 +1,55 @@
+use elf_model::{
+    Factor, FactorType, MappingFactor, Parameter, ParameterKind, TenantId, Topic,
+    TopicFactorParameter, TopicKind, TopicType,
+};
+use elf_runtime_model_kernel::TopicSchema;
+use std::sync::Arc;
+
+/// meta fixtures shared by tests of crate
+pub struct TestFixtures;
+
+impl TestFixtures {
+    pub const TENANT_ID: &'static str = "tenant-1";
+
+    pub fn tenant_id() -> TenantId {
+        String::from(Self::TENANT_ID)
+    }
+
+    /// distinct business topic named [topic-{topic_id}] of [TENANT_ID],
+    /// factors are given as (factor id, factor name, factor type).
+    pub fn topic(topic_id: &str, factors: Vec<(&str, &str, FactorType)>) -> Arc<TopicSchema> {
+        let topic = Topic::new()
+            .topic_id(topic_id.to_string())
+            .name(format!("topic-{}", topic_id))
+            .r#type(TopicType::Distinct)
+            .kind(TopicKind::Business)
+            .factors(
+                factors
+                    .into_iter()
+                    .map(|(factor_id, name, r#type)| {
+                        Factor::new()
+                            .factor_id(factor_id.to_string())
+                            .name(name.to_string())
+                            .r#type(r#type)
+                    })
+                    .collect(),
+            )
+            .tenant_id(Self::tenant_id());
+        Arc::new(TopicSchema::new(topic).unwrap())
+    }
+
+    pub fn topic_factor(topic_id: &str, factor_id: &str) -> Parameter {
+        TopicFactorParameter {
+            kind: Some(ParameterKind::Topic),
+            topic_id: Some(topic_id.to_string()),
+            factor_id: Some(factor_id.to_string()),
+        }
+        .to_parameter()
+    }
+
+    pub fn mapping(source: Parameter, factor_id: &str) -> MappingFactor {
+        MappingFactor::direct()
+            .source(source)
+            .factor_id(factor_id.to_string())
+    }
+}
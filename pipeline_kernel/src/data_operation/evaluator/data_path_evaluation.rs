@@ -0,0 +1,75 @@
+use crate::{ArcTopicDataValue, DataPathSegment, DataPathSpan};
+use elf_base::DisplayLines;
+use serde::{Serialize, Serializer};
+use std::fmt::{Display, Formatter};
+use std::ops::Deref;
+use std::sync::Arc;
+
+fn serialize_value<S>(value: &Arc<ArcTopicDataValue>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    value.deref().serialize(serializer)
+}
+
+/// value retrieved by one segment of data path.
+#[derive(Serialize, Debug)]
+pub struct DataPathSegmentTrace {
+    pub path: String,
+    pub span: DataPathSpan,
+    #[serde(serialize_with = "serialize_value")]
+    pub value: Arc<ArcTopicDataValue>,
+}
+
+impl DataPathSegmentTrace {
+    pub fn of(segment: &DataPathSegment, value: &Arc<ArcTopicDataValue>) -> Self {
+        Self {
+            path: segment.this_path(),
+            span: DataPathSpan {
+                start: segment.start_at(),
+                end: segment.end_at(),
+            },
+            value: value.clone(),
+        }
+    }
+}
+
+impl Display for DataPathSegmentTrace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[{}, {}) {} -> {}",
+            self.span.start, self.span.end, self.path, self.value
+        )
+    }
+}
+
+/// result of [crate::DataPathEvaluator].
+/// trace is empty when path is static, which is computed on parsing and reads nothing.
+#[derive(Serialize, Debug)]
+pub struct DataPathEvaluation {
+    pub path: String,
+    #[serde(serialize_with = "serialize_value")]
+    pub value: Arc<ArcTopicDataValue>,
+    pub is_static: bool,
+    pub trace: Vec<DataPathSegmentTrace>,
+}
+
+impl Display for DataPathEvaluation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let trace_str = if self.is_static {
+            DisplayLines::indent("static, no segment evaluated".to_string())
+        } else {
+            self.trace
+                .iter()
+                .map(|trace| DisplayLines::indent(trace.to_string()))
+                .collect::<Vec<String>>()
+                .join("\n")
+        };
+        write!(
+            f,
+            "DataPathEvaluation[path={}, value={}]\n{}",
+            self.path, self.value, trace_str
+        )
+    }
+}
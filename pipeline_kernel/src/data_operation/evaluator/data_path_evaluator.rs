@@ -0,0 +1,160 @@
+use crate::{
+    ArcTopicData, ArcTopicDataBuilder, DataPath, DataPathEvaluation, DataPathSegmentTrace,
+    InMemoryData, PipelineKernelErrorCode,
+};
+use elf_base::{ErrorCode, StdR};
+use elf_model::{TenantId, TopicData};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// evaluate data path against json documents, outside of pipeline.
+/// json documents are converted by [ArcTopicDataBuilder], the same as trigger data.
+/// e.g.
+/// ```text
+/// DataPathEvaluator::of_current(r#"{"a": {"b": [1, 2]}}"#)?.evaluate("a.b.&count")
+/// ```
+#[derive(Default)]
+pub struct DataPathEvaluator {
+    tenant_id: Option<Arc<TenantId>>,
+    previous_data: Option<ArcTopicData>,
+    current_data: Option<ArcTopicData>,
+    variables: Option<ArcTopicData>,
+}
+
+impl DataPathEvaluator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// given json must be an object
+    pub fn of_current(json: &str) -> StdR<Self> {
+        Self::new().with_current(json)
+    }
+
+    /// given json must be an object
+    pub fn with_current(mut self, json: &str) -> StdR<Self> {
+        self.current_data = Some(Self::parse_json("current", json)?);
+        Ok(self)
+    }
+
+    /// given json must be an object
+    pub fn with_previous(mut self, json: &str) -> StdR<Self> {
+        self.previous_data = Some(Self::parse_json("previous", json)?);
+        Ok(self)
+    }
+
+    /// given json must be an object, key is variable name
+    pub fn with_variables(mut self, json: &str) -> StdR<Self> {
+        self.variables = Some(Self::parse_json("variables", json)?);
+        Ok(self)
+    }
+
+    /// functions registered for given tenant are recognized when parsing path
+    pub fn with_tenant_id(mut self, tenant_id: TenantId) -> Self {
+        self.tenant_id = Some(Arc::new(tenant_id));
+        self
+    }
+
+    fn parse_json(name: &str, json: &str) -> StdR<ArcTopicData> {
+        match serde_json::from_str::<TopicData>(json) {
+            Ok(data) => Ok(ArcTopicData::build(data)),
+            Err(e) => PipelineKernelErrorCode::EvaluateDataInvalid.msg(format!(
+                "Failed to parse {} data as json object, caused by {}.",
+                name, e
+            )),
+        }
+    }
+
+    pub fn parse_path(&self, path: &str) -> StdR<DataPath> {
+        match &self.tenant_id {
+            Some(tenant_id) => DataPath::from_str_of_tenant(path, tenant_id),
+            _ => DataPath::from_str(path),
+        }
+    }
+
+    /// parse given path and evaluate it, value of each segment is traced.
+    /// raise error when parse or evaluate failed, use [DataPath::diagnose] for details of parsing.
+    pub fn evaluate(&self, path: &str) -> StdR<DataPathEvaluation> {
+        let data_path = self.parse_path(path)?;
+        self.evaluate_path(&data_path)
+    }
+
+    pub fn evaluate_path(&self, path: &DataPath) -> StdR<DataPathEvaluation> {
+        let variables = match &self.variables {
+            Some(variables) => variables.as_ref().clone(),
+            _ => HashMap::new(),
+        };
+        let in_memory_data =
+            InMemoryData::new(self.previous_data.clone(), self.current_data.clone())
+                .with_variables(variables);
+
+        let mut trace = vec![];
+        let value = in_memory_data.value_of_traced(path, |segment, value| {
+            trace.push(DataPathSegmentTrace::of(segment, value))
+        })?;
+
+        Ok(DataPathEvaluation {
+            path: path.full_path(),
+            value,
+            is_static: path.static_value().is_some(),
+            trace,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ArcTopicDataValue, DataPathEvaluator};
+
+    #[test]
+    fn test_evaluate() {
+        let evaluator = DataPathEvaluator::of_current(r#"{"a": {"b": [1, 2, 3]}, "c": "x"}"#)
+            .unwrap()
+            .with_previous(r#"{"c": "y"}"#)
+            .unwrap()
+            .with_variables(r#"{"v": 10}"#)
+            .unwrap();
+
+        let evaluation = evaluator.evaluate("a.b.&count").unwrap();
+        assert!(
+            matches!(evaluation.value.as_ref(), ArcTopicDataValue::Num(n) if n.to_string() == "3")
+        );
+        assert!(!evaluation.is_static);
+        let traced: Vec<(String, usize, usize)> = evaluation
+            .trace
+            .iter()
+            .map(|t| (t.path.clone(), t.span.start, t.span.end))
+            .collect();
+        assert_eq!(
+            traced,
+            vec![
+                ("a".to_string(), 0, 1),
+                ("b".to_string(), 2, 3),
+                ("&count".to_string(), 4, 10),
+            ]
+        );
+
+        let evaluation = evaluator.evaluate("&old.c").unwrap();
+        assert_eq!(evaluation.value.to_string(), "Str[y]");
+        let evaluation = evaluator.evaluate("v").unwrap();
+        assert_eq!(evaluation.value.to_string(), "Num[10]");
+        let evaluation = evaluator.evaluate("c").unwrap();
+        assert_eq!(evaluation.value.to_string(), "Str[x]");
+        assert_eq!(
+            serde_json::to_string(&evaluation).unwrap(),
+            r#"{"path":"c","value":"x","is_static":false,"trace":[{"path":"c","span":{"start":0,"end":1},"value":"x"}]}"#
+        );
+    }
+
+    #[test]
+    fn test_evaluate_failed() {
+        let error = DataPathEvaluator::of_current("[1]").err().unwrap();
+        assert!(error.to_string().contains("PLKN-00111"));
+
+        let evaluator = DataPathEvaluator::new();
+        let error = evaluator.evaluate("a..b").err().unwrap();
+        assert!(error.to_string().contains("PLKN-00105"));
+        let error = evaluator.evaluate("a").err().unwrap();
+        assert!(error.to_string().contains("PLKN-00102"));
+    }
+}
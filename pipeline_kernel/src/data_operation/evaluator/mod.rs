@@ -0,0 +1,5 @@
+mod data_path_evaluation;
+mod data_path_evaluator;
+
+pub use data_path_evaluation::*;
+pub use data_path_evaluator::*;
@@ -21,6 +21,12 @@ impl InMemoryData {
         }
     }
 
+    /// replace variables by given
+    pub fn with_variables(mut self, variables: HashMap<String, Arc<ArcTopicDataValue>>) -> Self {
+        self.variables = variables;
+        self
+    }
+
     /// get an instance which allowed get value from current data only
     pub fn current_only(&mut self) -> &Self {
         self.current_data_only = true;
@@ -115,15 +121,34 @@ impl InMemoryData {
     }
 
     pub fn value_of(&self, path: &DataPath) -> StdR<Arc<ArcTopicDataValue>> {
+        self.value_of_traced(path, |_, _| {})
+    }
+
+    /// same as [value_of], given [on_segment] is called with value of each segment once it is retrieved.
+    /// not called when path is static.
+    pub fn value_of_traced<F>(
+        &self,
+        path: &DataPath,
+        mut on_segment: F,
+    ) -> StdR<Arc<ArcTopicDataValue>>
+    where
+        F: FnMut(&DataPathSegment, &Arc<ArcTopicDataValue>),
+    {
+        if let Some(value) = path.static_value() {
+            return Ok(value.to_value());
+        }
+
         let segments = path.segments();
         if let Some((first, rest)) = segments.split_first() {
             let top_value = self.segment_value_from_memory(first)?;
+            on_segment(first, &top_value);
             if rest.is_empty() {
                 Ok(top_value)
             } else {
                 let mut last_value = top_value;
                 for segment in rest {
                     let value = self.segment_value_from_source(&last_value, segment)?;
+                    on_segment(segment, &value);
                     last_value = value;
                 }
 
@@ -1,6 +1,6 @@
 use crate::{
-    ArcFrom, ArcTopicDataValue, FuncDataPath, InMemoryData, InMemoryFuncCall,
-    PipelineKernelErrorCode,
+    ArcFrom, ArcTopicDataValue, DataPathFunc, FuncDataPath, InMemoryData, InMemoryFuncCall,
+    PipelineKernelErrorCode, RegisteredFunction,
 };
 use bigdecimal::{BigDecimal, FromPrimitive};
 use chrono::{Timelike, Utc};
@@ -27,17 +27,50 @@ impl FuncDataPath {
         for param in params[param_start_index..].iter() {
             param_values.push(param.value_from_memory(in_memory_data)?);
         }
-        Ok(InMemoryFuncCall::compute(
-            &self,
-            source.clone(),
-            param_values,
-        )?)
+        match self.func() {
+            DataPathFunc::Predefined(_) => {
+                InMemoryFuncCall::compute(self, source.clone(), param_values)
+            }
+            DataPathFunc::Registered(func) => {
+                self.call_registered(func, source.clone(), param_values)
+            }
+        }
+    }
+
+    /// check the min/max parameter count, same as [InMemoryFuncCall::compute]
+    fn call_registered(
+        &self,
+        func: &RegisteredFunction,
+        context: Arc<ArcTopicDataValue>,
+        params: Vec<Arc<ArcTopicDataValue>>,
+    ) -> StdR<Arc<ArcTopicDataValue>> {
+        let param_count = params.len();
+        let signature = func.signature();
+        let min_param_count = signature.min_param_count();
+        if param_count < min_param_count {
+            return PipelineKernelErrorCode::IncorrectDataPath.msg(format!(
+                "Function[path={}, name={}] has no enough parameters, at least {} are required, but only {} are currently provided.",
+                self.full_path(),
+                func, min_param_count, param_count
+            ));
+        }
+        if let Some(max_param_count) = signature.max_param_count()
+            && param_count > max_param_count
+        {
+            return PipelineKernelErrorCode::IncorrectDataPath.msg(format!(
+                "Function[path={}, name={}] has too many parameters, at most {} are accepted, but {} are currently provided.",
+                self.full_path(),
+                func, max_param_count, param_count
+            ));
+        }
+
+        func.call(context, params)
     }
 
     pub fn value_from_memory(&self, in_memory_data: &InMemoryData) -> StdR<Arc<ArcTopicDataValue>> {
         let func = self.func();
         match func {
-            VariablePredefineFunctions::NextSeq => {
+            DataPathFunc::Predefined(VariablePredefineFunctions::NextSeq) => {
                 if let Some(seq) = BigDecimal::from_u128(IdGen::next_id()?) {
                     Ok(ArcTopicDataValue::arc_from(seq))
                 } else {
@@ -48,15 +81,19 @@ impl FuncDataPath {
                     ))
                 }
             }
-            VariablePredefineFunctions::FromPreviousTriggerData => Ok(ArcTopicDataValue::wrap(
+            DataPathFunc::Predefined(VariablePredefineFunctions::FromPreviousTriggerData) => Ok(ArcTopicDataValue::wrap(
                 in_memory_data.get_previous_data()?.clone(),
             )),
-            VariablePredefineFunctions::FromCurrentTriggerData => Ok(ArcTopicDataValue::wrap(
+            DataPathFunc::Predefined(VariablePredefineFunctions::FromCurrentTriggerData) => Ok(ArcTopicDataValue::wrap(
                 in_memory_data.get_current_data()?.clone(),
             )),
-            VariablePredefineFunctions::Now => Ok(ArcTopicDataValue::arc_from(
-                Utc::now().naive_utc().with_nanosecond(0).unwrap(),
-            )),
+            DataPathFunc::Predefined(VariablePredefineFunctions::Now) => Ok(
+                ArcTopicDataValue::arc_from(Utc::now().naive_utc().with_nanosecond(0).unwrap()),
+            ),
+            // context disallowed, all params are parameters
+            DataPathFunc::Registered(registered) if !registered.signature().require_context() => {
+                self.get_value(&Arc::new(ArcTopicDataValue::None), 0, in_memory_data)
+            }
             _ => {
                 // the first parameter of function is context
                 let (params, param_count) = if let Some(params) = self.params() {
@@ -5,6 +5,7 @@ use std::sync::Arc;
 
 pub struct InMemoryFuncCall<'a> {
     path: &'a FuncDataPath,
+    func: &'a VariablePredefineFunctions,
 }
 
 /// property
@@ -22,7 +23,7 @@ impl<'a> InMemoryFuncCall<'a> {
     }
 
     pub fn func(&self) -> &VariablePredefineFunctions {
-        self.path.func()
+        self.func
     }
 }
 
@@ -33,7 +34,7 @@ impl<'a> InMemoryFuncCall<'a> {
         context: Arc<ArcTopicDataValue>,
         params: Vec<Arc<ArcTopicDataValue>>,
     ) -> StdR<Arc<ArcTopicDataValue>> {
-        match self.path.func() {
+        match self.func {
             VariablePredefineFunctions::NextSeq => self.context_disallowed(),
             VariablePredefineFunctions::Count => self.resolve_count_of_vec_or_map(context, params),
             VariablePredefineFunctions::Length | VariablePredefineFunctions::Len => {
@@ -122,8 +123,14 @@ impl<'a> InMemoryFuncCall<'a> {
         context: Arc<ArcTopicDataValue>,
         params: Vec<Arc<ArcTopicDataValue>>,
     ) -> StdR<Arc<ArcTopicDataValue>> {
+        let Some(func) = path.predefined_func() else {
+            return PipelineKernelErrorCode::VariableFuncNotSupported.msg(format!(
+                "Function[path={}, name={}] is not predefined.",
+                path.full_path(),
+                path.func()
+            ));
+        };
         let param_count = params.len();
-        let func = path.func();
         let min_param_count = func.min_param_count();
         if param_count < min_param_count {
             return PipelineKernelErrorCode::IncorrectDataPath.msg(format!(
@@ -142,6 +149,6 @@ impl<'a> InMemoryFuncCall<'a> {
             }
         }
 
-        Self { path }.do_compute(context, params)
+        Self { path, func }.do_compute(context, params)
     }
 }
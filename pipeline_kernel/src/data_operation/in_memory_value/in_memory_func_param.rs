@@ -2,24 +2,41 @@ use crate::{ArcFrom, ArcTopicDataValue, FuncDataPathParam, FuncParamValue, InMem
 use elf_base::StdR;
 use std::sync::Arc;
 
+impl FuncParamValue {
+    pub fn to_value(&self) -> Arc<ArcTopicDataValue> {
+        match self {
+            Self::Str(s) => ArcTopicDataValue::arc_from(s.clone()),
+            Self::Num(n) => ArcTopicDataValue::arc_from(n.clone()),
+            Self::Bool(b) => ArcTopicDataValue::arc_from(*b),
+            Self::DateTime(dt) => ArcTopicDataValue::arc_from(*dt),
+            Self::Date(d) => ArcTopicDataValue::arc_from(*d),
+            Self::Time(t) => ArcTopicDataValue::arc_from(*t),
+            Self::None => Arc::new(ArcTopicDataValue::None),
+        }
+    }
+
+    /// returns none when given value is map or vec, which cannot be a param value
+    pub fn of_value(value: &ArcTopicDataValue) -> Option<Self> {
+        match value {
+            ArcTopicDataValue::Str(s) => Some(Self::Str(s.to_string())),
+            ArcTopicDataValue::Num(n) => Some(Self::Num(n.as_ref().clone())),
+            ArcTopicDataValue::Bool(b) => Some(Self::Bool(*b)),
+            ArcTopicDataValue::DateTime(dt) => Some(Self::DateTime(*dt.as_ref())),
+            ArcTopicDataValue::Date(d) => Some(Self::Date(*d.as_ref())),
+            ArcTopicDataValue::Time(t) => Some(Self::Time(*t.as_ref())),
+            ArcTopicDataValue::None => Some(Self::None),
+            ArcTopicDataValue::Map(_) | ArcTopicDataValue::Vec(_) => None,
+        }
+    }
+}
+
 impl FuncDataPathParam {
     pub fn value_from_memory(&self, in_memory_data: &InMemoryData) -> StdR<Arc<ArcTopicDataValue>> {
         match self {
             Self::Path(data_path) => in_memory_data.value_of(data_path),
             Self::Plain(plain_path) => plain_path.value_from_memory(in_memory_data),
             Self::Func(func_path) => func_path.value_from_memory(in_memory_data),
-            Self::Value(data_path_param) => {
-                let value = data_path_param.value();
-                match value {
-                    FuncParamValue::Str(s) => Ok(ArcTopicDataValue::arc_from(s.clone())),
-                    FuncParamValue::Num(n) => Ok(ArcTopicDataValue::arc_from(n.clone())),
-                    FuncParamValue::Bool(b) => Ok(ArcTopicDataValue::arc_from(*b)),
-                    FuncParamValue::DateTime(dt) => Ok(ArcTopicDataValue::arc_from(dt.clone())),
-                    FuncParamValue::Date(d) => Ok(ArcTopicDataValue::arc_from(d.clone())),
-                    FuncParamValue::Time(t) => Ok(ArcTopicDataValue::arc_from(t.clone())),
-                    FuncParamValue::None => Ok(Arc::new(ArcTopicDataValue::None)),
-                }
-            }
+            Self::Value(data_path_param) => Ok(data_path_param.value().to_value()),
         }
     }
 }
@@ -1,5 +1,7 @@
 mod cast;
 mod check;
+mod evaluator;
 mod in_memory_value;
 
+pub use evaluator::*;
 pub use in_memory_value::*;
@@ -1,4 +1,4 @@
-use crate::{DataPathSegment, PathStr};
+use crate::{DataPathSegment, FuncParamValue, PathStr};
 use elf_base::DisplayLines;
 use std::fmt::{Display, Formatter};
 
@@ -31,11 +31,22 @@ pub struct DataPath {
     path: PathStr,
     /// at least one segment, which means no [.] included
     segments: Vec<DataPathSegment>,
+    /// value of path when all segments are static, see [crate::DataPathSimplifier]
+    static_value: Option<FuncParamValue>,
 }
 
 impl DataPath {
     pub fn new(path: PathStr, segments: Vec<DataPathSegment>) -> Self {
-        Self { path, segments }
+        Self {
+            path,
+            segments,
+            static_value: None,
+        }
+    }
+
+    pub fn with_static_value(mut self, static_value: Option<FuncParamValue>) -> Self {
+        self.static_value = static_value;
+        self
     }
 
     pub fn path(&self) -> &PathStr {
@@ -63,6 +74,19 @@ impl DataPath {
     pub fn segments(&self) -> &Vec<DataPathSegment> {
         &self.segments
     }
+
+    pub fn segments_mut(&mut self) -> &mut Vec<DataPathSegment> {
+        &mut self.segments
+    }
+
+    pub fn static_value(&self) -> Option<&FuncParamValue> {
+        self.static_value.as_ref()
+    }
+
+    /// static path is independent of in-memory data, value is computed when parsing
+    pub fn is_static(&self) -> bool {
+        self.static_value.is_some()
+    }
 }
 
 impl Display for DataPath {
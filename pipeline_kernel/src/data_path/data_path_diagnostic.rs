@@ -0,0 +1,241 @@
+use serde::{Serialize, Serializer};
+use std::fmt::{Display, Formatter};
+
+/// serialized as [code]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DataPathDiagnosticCode {
+    UnexpectedChar,
+    EmptyPath,
+    BlankSegment,
+    UnclosedBrace,
+    DisallowedCharInFunctionName,
+    EmptyFunctionName,
+    UnknownFunction,
+    WhitespaceInFunctionName,
+    MissingFunctionParams,
+    UnexpectedContext,
+    MissingContext,
+    InvalidContext,
+    UnclosedParam,
+    UnclosedParenthesis,
+    TooManyParams,
+    TooFewParams,
+    TrailingWhitespaces,
+}
+
+impl DataPathDiagnosticCode {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::UnexpectedChar => "unexpected-char",
+            Self::EmptyPath => "empty-path",
+            Self::BlankSegment => "blank-segment",
+            Self::UnclosedBrace => "unclosed-brace",
+            Self::DisallowedCharInFunctionName => "disallowed-char-in-function-name",
+            Self::EmptyFunctionName => "empty-function-name",
+            Self::UnknownFunction => "unknown-function",
+            Self::WhitespaceInFunctionName => "whitespace-in-function-name",
+            Self::MissingFunctionParams => "missing-function-params",
+            Self::UnexpectedContext => "unexpected-context",
+            Self::MissingContext => "missing-context",
+            Self::InvalidContext => "invalid-context",
+            Self::UnclosedParam => "unclosed-param",
+            Self::UnclosedParenthesis => "unclosed-parenthesis",
+            Self::TooManyParams => "too-many-params",
+            Self::TooFewParams => "too-few-params",
+            Self::TrailingWhitespaces => "trailing-whitespaces",
+        }
+    }
+}
+
+impl Serialize for DataPathDiagnosticCode {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.code())
+    }
+}
+
+impl Display for DataPathDiagnosticCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+/// char index range [start, end) of full path, same as [crate::PathStr].
+/// start equals end when nothing to point at, e.g. empty path.
+#[derive(Serialize, Clone, Copy, PartialEq, Debug)]
+pub struct DataPathSpan {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// structured error of data path parsing,
+/// serialized as json for editor, or rendered with caret underline for logs.
+#[derive(Serialize, Clone, Debug)]
+pub struct DataPathDiagnostic {
+    pub code: DataPathDiagnosticCode,
+    pub message: String,
+    pub span: DataPathSpan,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub help: Option<String>,
+}
+
+impl DataPathDiagnostic {
+    pub fn new<M>(code: DataPathDiagnosticCode, message: M, start: usize, end: usize) -> Self
+    where
+        M: Into<String>,
+    {
+        Self {
+            code,
+            message: message.into(),
+            span: DataPathSpan { start, end },
+            help: None,
+        }
+    }
+
+    pub fn with_help<H>(mut self, help: H) -> Self
+    where
+        H: Into<String>,
+    {
+        self.help = Some(help.into());
+        self
+    }
+
+    /// flat message, for the error raised by parser
+    pub fn to_error_message(&self, full_path: &str) -> String {
+        let DataPathSpan { start, end } = self.span;
+        if end <= start + 1 {
+            format!(
+                "Incorrect data path[{}], caused by {} at index[{}].",
+                full_path, self.message, start
+            )
+        } else {
+            format!(
+                "Incorrect data path[{}], caused by {} at index[{}, {}].",
+                full_path, self.message, start, end
+            )
+        }
+    }
+
+    /// render as below, whitespace chars of path are shown as space to keep the carets aligned.
+    /// ```text
+    /// error[unclosed-brace]: closing "}" is not matched
+    ///   |
+    ///   | a.{b
+    ///   |   ^
+    ///   = help: add "}" to close the wrapped path
+    /// ```
+    pub fn render(&self, full_path: &str) -> String {
+        let line: String = full_path
+            .chars()
+            .map(|c| if c.is_whitespace() { ' ' } else { c })
+            .collect();
+        let DataPathSpan { start, end } = self.span;
+        let carets = "^".repeat((end.max(start + 1)) - start);
+        let mut rendered = format!(
+            "error[{}]: {}\n  |\n  | {}\n  | {}{}",
+            self.code,
+            self.message,
+            line,
+            " ".repeat(start),
+            carets
+        );
+        if let Some(help) = &self.help {
+            rendered.push_str(&format!("\n  = help: {}", help));
+        }
+        rendered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DataPath, DataPathDiagnosticCode, DataPathParser};
+
+    #[test]
+    fn test_recover() {
+        assert!(DataPath::diagnose("a.b.&sum").is_empty());
+
+        let diagnostics = DataPath::diagnose("a..b.&foo.c,d");
+        let codes_and_spans: Vec<(DataPathDiagnosticCode, usize, usize)> = diagnostics
+            .iter()
+            .map(|d| (d.code, d.span.start, d.span.end))
+            .collect();
+        assert_eq!(
+            codes_and_spans,
+            vec![
+                (DataPathDiagnosticCode::UnexpectedChar, 2, 3),
+                (DataPathDiagnosticCode::UnknownFunction, 5, 9),
+                (DataPathDiagnosticCode::UnexpectedChar, 11, 12),
+            ]
+        );
+
+        // no top level dot after unclosed brace, stop recovering
+        let diagnostics = DataPath::diagnose("a.&len(b.{c");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, DataPathDiagnosticCode::UnclosedBrace);
+
+        // function after recovered segment still has context
+        let diagnostics = DataPath::diagnose("x.&foo(1).y.&length");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, DataPathDiagnosticCode::UnknownFunction);
+    }
+
+    #[test]
+    fn test_render() {
+        let path = "a..b.&foo";
+        let diagnostics = DataPath::diagnose(path);
+        assert_eq!(
+            diagnostics[0].render(path),
+            r#"error[unexpected-char]: incorrect dot
+  |
+  | a..b.&foo
+  |   ^
+  = help: use "\." if it is a part of name"#
+        );
+        assert_eq!(
+            diagnostics[1].render(path),
+            r#"error[unknown-function]: unrecognized function name[&foo]
+  |
+  | a..b.&foo
+  |      ^^^^
+  = help: function name is case sensitive, e.g. &sum, &yearDiff, or register it for tenant"#
+        );
+        assert_eq!(
+            diagnostics[0].to_error_message(path),
+            "Incorrect data path[a..b.&foo], caused by incorrect dot at index[2]."
+        );
+        assert_eq!(
+            diagnostics[1].to_error_message(path),
+            "Incorrect data path[a..b.&foo], caused by unrecognized function name[&foo] at index[5, 9]."
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        assert_eq!(
+            serde_json::to_string(&DataPath::diagnose("")).unwrap(),
+            r#"[{"code":"empty-path","message":"not content determined","span":{"start":0,"end":0}}]"#
+        );
+        assert_eq!(
+            serde_json::to_string(&DataPath::diagnose("a.&sum(b")).unwrap(),
+            r#"[{"code":"unclosed-param","message":"the closing \",\" or \")\" is not matched","span":{"start":7,"end":8},"help":"add \",\" or \")\" to end the parameter"}]"#
+        );
+    }
+
+    #[test]
+    fn test_nested() {
+        // parser is sendable, diagnostics are owned by states instead of shared
+        fn assert_send<T: Send>() {}
+        assert_send::<DataPathParser>();
+
+        // reported in parameter of function parameter, handed back to the top level
+        let diagnostics = DataPath::diagnose("a.&concat(&len(&foo(b)),c)");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, DataPathDiagnosticCode::UnknownFunction);
+        assert_eq!(
+            (diagnostics[0].span.start, diagnostics[0].span.end),
+            (15, 19)
+        );
+    }
+}
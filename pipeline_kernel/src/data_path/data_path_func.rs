@@ -1,21 +1,102 @@
-use crate::{FuncDataPathParam, PathStr};
+use crate::{FuncDataPathParam, PathStr, RegisteredFunction};
 use elf_base::DisplayLines;
 use elf_model::VariablePredefineFunctions;
 use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+
+/// function of data path, predefined or registered by host application.
+/// restrictions of both are read in the same way, see [VariablePredefineFunctions].
+pub enum DataPathFunc {
+    Predefined(VariablePredefineFunctions),
+    Registered(Arc<RegisteredFunction>),
+}
+
+impl DataPathFunc {
+    pub fn require_context(&self) -> bool {
+        match self {
+            Self::Predefined(func) => func.require_context(),
+            Self::Registered(func) => func.signature().require_context(),
+        }
+    }
+
+    pub fn allow_none_context(&self) -> bool {
+        match self {
+            Self::Predefined(func) => func.allow_none_context(),
+            Self::Registered(func) => func.signature().allow_none_context(),
+        }
+    }
+
+    pub fn allow_blank_context(&self) -> bool {
+        match self {
+            Self::Predefined(func) => func.allow_blank_context(),
+            Self::Registered(func) => func.signature().allow_blank_context(),
+        }
+    }
+
+    pub fn min_param_count(&self) -> usize {
+        match self {
+            Self::Predefined(func) => func.min_param_count(),
+            Self::Registered(func) => func.signature().min_param_count(),
+        }
+    }
+
+    pub fn max_param_count(&self) -> Option<usize> {
+        match self {
+            Self::Predefined(func) => func.max_param_count(),
+            Self::Registered(func) => func.signature().max_param_count(),
+        }
+    }
+
+    pub fn allow_none_param(&self, param_index: usize) -> bool {
+        match self {
+            Self::Predefined(func) => func.allow_none_param(param_index),
+            Self::Registered(func) => func.signature().allow_none_param(param_index),
+        }
+    }
+
+    pub fn allow_blank_param(&self, param_index: usize) -> bool {
+        match self {
+            Self::Predefined(func) => func.allow_blank_param(param_index),
+            Self::Registered(func) => func.signature().allow_blank_param(param_index),
+        }
+    }
+}
+
+impl From<VariablePredefineFunctions> for DataPathFunc {
+    fn from(func: VariablePredefineFunctions) -> Self {
+        Self::Predefined(func)
+    }
+}
+
+impl Display for DataPathFunc {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Predefined(func) => write!(f, "{}", func),
+            Self::Registered(func) => write!(f, "{}", func),
+        }
+    }
+}
 
 pub struct FuncDataPath {
     path: PathStr,
-    func: VariablePredefineFunctions,
+    func: DataPathFunc,
     params: Option<Vec<FuncDataPathParam>>,
+    /// function is deterministic and all params are values,
+    /// see [crate::DataPathSimplifier]
+    is_static: bool,
 }
 
 impl FuncDataPath {
-    pub fn new(
-        path: PathStr,
-        func: VariablePredefineFunctions,
-        params: Option<Vec<FuncDataPathParam>>,
-    ) -> Self {
-        Self { path, func, params }
+    pub fn new<F>(path: PathStr, func: F, params: Option<Vec<FuncDataPathParam>>) -> Self
+    where
+        F: Into<DataPathFunc>,
+    {
+        Self {
+            path,
+            func: func.into(),
+            params,
+            is_static: false,
+        }
     }
 
     pub fn path(&self) -> &PathStr {
@@ -40,14 +121,36 @@ impl FuncDataPath {
         self.path.end_index()
     }
 
-    pub fn func(&self) -> &VariablePredefineFunctions {
+    pub fn func(&self) -> &DataPathFunc {
         &self.func
     }
 
+    /// none when it is a registered function
+    pub fn predefined_func(&self) -> Option<&VariablePredefineFunctions> {
+        match &self.func {
+            DataPathFunc::Predefined(func) => Some(func),
+            DataPathFunc::Registered(_) => None,
+        }
+    }
+
     pub fn params(&self) -> &Option<Vec<FuncDataPathParam>> {
         &self.params
     }
 
+    pub fn params_mut(&mut self) -> &mut Option<Vec<FuncDataPathParam>> {
+        &mut self.params
+    }
+
+    /// static function is independent of in-memory data,
+    /// its value is determined by params, and the context if it is not the first segment.
+    pub fn is_static(&self) -> bool {
+        self.is_static
+    }
+
+    pub fn set_static(&mut self, is_static: bool) {
+        self.is_static = is_static;
+    }
+
     /// take params from path, leaving [none] instead
     /// if origin params is [none], return empty vec.
     pub fn take_params(&mut self) -> Vec<FuncDataPathParam> {
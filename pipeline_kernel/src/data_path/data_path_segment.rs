@@ -22,6 +22,14 @@ impl DataPathSegment {
         self.path().full()
     }
 
+    /// plain segment always reads data, function segment is static when it is marked.
+    pub fn is_static(&self) -> bool {
+        match self {
+            Self::Plain(_) => false,
+            Self::Func(func_path) => func_path.is_static(),
+        }
+    }
+
     /// return position is included
     pub fn start_at(&self) -> usize {
         self.path().start_index()
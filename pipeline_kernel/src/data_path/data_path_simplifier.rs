@@ -0,0 +1,279 @@
+use crate::{
+    DataPathFunc, DataPathSegment, FuncDataPath, FuncDataPathParam, FuncParamValue, FuncParamValuePath,
+    InMemoryData,
+};
+use elf_model::VariablePredefineFunctions;
+
+/// simplify the parsed data path, value of path is never changed by simplification.
+/// - function is marked as static when it is deterministic and all params are values,
+///   static function in params is folded into a value param,
+/// - path in params is folded into a value param when all segments are static,
+/// - for concat function, params of nested concat function are lifted,
+///   and adjacent string params are merged.
+///
+/// folding is skipped when value cannot be computed, or it is a map or vec.
+/// in this case, error still raised when the path is evaluated, same as not simplified.
+pub struct DataPathSimplifier;
+
+impl DataPathSimplifier {
+    /// function reads nothing from in-memory data, and always returns same value for same params.
+    /// [VariablePredefineFunctions::Join] is excluded,
+    /// it depends on environment, which might be initialized after path parsed.
+    /// registered function is never deterministic, it is opaque to kernel.
+    fn is_deterministic(func: &DataPathFunc) -> bool {
+        let DataPathFunc::Predefined(func) = func else {
+            return false;
+        };
+        !matches!(
+            func,
+            VariablePredefineFunctions::NextSeq
+                | VariablePredefineFunctions::Now
+                | VariablePredefineFunctions::FromCurrentTriggerData
+                | VariablePredefineFunctions::FromPreviousTriggerData
+                | VariablePredefineFunctions::Join
+        )
+    }
+
+    pub fn simplify(segments: &mut [DataPathSegment]) {
+        for segment in segments.iter_mut() {
+            if let DataPathSegment::Func(func_path) = segment {
+                Self::simplify_func(func_path);
+            }
+        }
+    }
+
+    fn simplify_func(func_path: &mut FuncDataPath) {
+        let is_concat = matches!(
+            func_path.predefined_func(),
+            Some(VariablePredefineFunctions::Concat)
+        );
+        if let Some(params) = func_path.params_mut() {
+            params.iter_mut().for_each(Self::simplify_param);
+            if is_concat {
+                Self::flatten_concat(params);
+            }
+        }
+
+        let is_static = Self::is_deterministic(func_path.func())
+            && func_path
+                .params()
+                .iter()
+                .flatten()
+                .all(|param| matches!(param, FuncDataPathParam::Value(_)));
+        func_path.set_static(is_static);
+    }
+
+    fn simplify_param(param: &mut FuncDataPathParam) {
+        let folded = match param {
+            FuncDataPathParam::Value(_) | FuncDataPathParam::Plain(_) => None,
+            FuncDataPathParam::Func(func_path) => {
+                Self::simplify_func(func_path);
+                Self::static_value_of_func(func_path)
+                    .map(|value| FuncParamValuePath::new(func_path.path().clone(), value))
+            }
+            FuncDataPathParam::Path(data_path) => {
+                Self::simplify(data_path.segments_mut());
+                Self::static_value_of(data_path.segments())
+                    .map(|value| FuncParamValuePath::new(data_path.path().clone(), value))
+            }
+        };
+        if let Some(value_path) = folded {
+            *param = FuncDataPathParam::Value(value_path);
+        }
+    }
+
+    /// concat is associative, so
+    /// - params of nested concat function are lifted, only when it has at least 2 params,
+    ///   otherwise error raised when it is evaluated,
+    /// - adjacent string params are merged, only when at least 2 params left,
+    ///   since the first param might be the context.
+    fn flatten_concat(params: &mut Vec<FuncDataPathParam>) {
+        let mut lifted = Vec::with_capacity(params.len());
+        for param in params.drain(..) {
+            match param {
+                FuncDataPathParam::Func(mut func_path)
+                    if matches!(
+                        func_path.predefined_func(),
+                        Some(VariablePredefineFunctions::Concat)
+                    )
+                        && func_path.params().as_ref().is_some_and(|p| p.len() > 1) =>
+                {
+                    lifted.extend(func_path.take_params())
+                }
+                _ => lifted.push(param),
+            }
+        }
+
+        let merged_count = lifted
+            .iter()
+            .enumerate()
+            .filter(|(index, param)| {
+                *index == 0 || !Self::is_str(param) || !Self::is_str(&lifted[index - 1])
+            })
+            .count();
+        if merged_count < 2 {
+            *params = lifted;
+            return;
+        }
+
+        for param in lifted {
+            if let Some(FuncDataPathParam::Value(last)) = params.last_mut()
+                && let FuncDataPathParam::Value(current) = &param
+                && let (FuncParamValue::Str(last_str), FuncParamValue::Str(current_str)) =
+                    (last.value(), current.value())
+            {
+                let value = FuncParamValue::Str(format!("{}{}", last_str, current_str));
+                *last = FuncParamValuePath::new(last.path().extend_to(current.path()), value);
+            } else {
+                params.push(param);
+            }
+        }
+    }
+
+    fn is_str(param: &FuncDataPathParam) -> bool {
+        match param {
+            FuncDataPathParam::Value(value_path) => {
+                matches!(value_path.value(), FuncParamValue::Str(_))
+            }
+            _ => false,
+        }
+    }
+
+    /// value of static function, as the first segment of path, or as a param
+    fn static_value_of_func(func_path: &FuncDataPath) -> Option<FuncParamValue> {
+        if !func_path.is_static() {
+            return None;
+        }
+        func_path
+            .value_from_memory(&InMemoryData::new(None, None))
+            .ok()
+            .and_then(|value| FuncParamValue::of_value(&value))
+    }
+
+    /// value of segments when all of them are static,
+    /// none when any segment is not static, or value cannot be computed, or it is a map or vec.
+    pub fn static_value_of(segments: &[DataPathSegment]) -> Option<FuncParamValue> {
+        let mut funcs = vec![];
+        for segment in segments {
+            match segment {
+                DataPathSegment::Func(func_path) if func_path.is_static() => funcs.push(func_path),
+                _ => return None,
+            }
+        }
+        let (first, rest) = funcs.split_first()?;
+        let in_memory_data = InMemoryData::new(None, None);
+        let mut value = first.value_from_memory(&in_memory_data).ok()?;
+        for func_path in rest {
+            value = func_path.value_from_source(&value, &in_memory_data).ok()?;
+        }
+        FuncParamValue::of_value(&value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        DataPath, DataPathSegment, DataPathSimplifierFixtures, FuncDataPathParam, FuncParamValue,
+        InMemoryData,
+    };
+
+    fn in_memory_data() -> InMemoryData {
+        DataPathSimplifierFixtures::in_memory_data()
+    }
+
+    fn params_of(segment: &DataPathSegment) -> &Vec<FuncDataPathParam> {
+        match segment {
+            DataPathSegment::Func(func_path) => func_path.params().as_ref().unwrap(),
+            _ => panic!(),
+        }
+    }
+
+    fn assert_str_param(param: &FuncDataPathParam, value: &str) {
+        match param {
+            FuncDataPathParam::Value(value_path) => {
+                assert!(matches!(value_path.value(), FuncParamValue::Str(s) if s == value))
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn test_fold() {
+        // function with constant params
+        let path = DataPath::from_str("&len({}abc)").unwrap();
+        assert!(path.segments()[0].is_static());
+        assert_str_param(&params_of(&path.segments()[0])[0], "abc");
+        assert!(
+            matches!(path.static_value(), Some(FuncParamValue::Num(n)) if n.to_string() == "3")
+        );
+
+        // constant params of non-first segment are folded, but path reads data
+        let path = DataPath::from_str("customer.name.&concat({}-,{}vip)").unwrap();
+        assert!(!path.segments()[0].is_static());
+        assert!(path.segments()[2].is_static());
+        let params = params_of(&path.segments()[2]);
+        assert_eq!(params.len(), 2);
+        assert_str_param(&params[0], "-");
+        assert_str_param(&params[1], "vip");
+        assert!(!path.is_static());
+
+        // not deterministic
+        let path = DataPath::from_str("&len(&now())").unwrap();
+        assert!(!path.segments()[0].is_static());
+        assert!(!path.is_static());
+
+        // value cannot be computed, leave it to evaluation
+        let path = DataPath::from_str("&len({}abc.&split({}b))").unwrap();
+        assert!(!path.is_static());
+        assert!(in_memory_data().value_of(&path).is_err());
+    }
+
+    #[test]
+    fn test_flatten_concat() {
+        // adjacent strings are merged
+        let path = DataPath::from_str("{}ORD-{order.no}").unwrap();
+        let params = params_of(&path.segments()[0]);
+        assert_eq!(params.len(), 2);
+        assert_str_param(&params[0], "ORD-");
+        assert!(!path.segments()[0].is_static());
+
+        // nested concat is lifted
+        let path = DataPath::from_str("&concat(a,{}x{b})").unwrap();
+        let params = params_of(&path.segments()[0]);
+        assert_eq!(params.len(), 3);
+        assert!(matches!(&params[0], FuncDataPathParam::Plain(_)));
+        assert_str_param(&params[1], "x");
+        assert!(matches!(&params[2], FuncDataPathParam::Plain(_)));
+        assert_eq!(
+            in_memory_data().value_of(&path).unwrap().to_string(),
+            "Str[1x2]"
+        );
+
+        // literal concat of constants, at least 2 params are kept
+        let path = DataPath::from_str("a{}").unwrap();
+        assert_eq!(params_of(&path.segments()[0]).len(), 2);
+        assert!(path.is_static());
+        assert_eq!(
+            in_memory_data().value_of(&path).unwrap().to_string(),
+            "Str[a]"
+        );
+    }
+
+    /// simplified path must have same value as the one written,
+    /// see [benches/data_path_simplifier.rs] for the evaluation cost.
+    #[test]
+    fn test_static_segments() {
+        let in_memory_data = in_memory_data();
+        for (text, is_static) in DataPathSimplifierFixtures::CORPUS {
+            let written = DataPathSimplifierFixtures::as_is(text).unwrap();
+            let simplified = DataPath::from_str(text).unwrap();
+            assert_eq!(simplified.is_static(), is_static, "path[{}]", text);
+            assert_eq!(
+                in_memory_data.value_of(&written).unwrap().to_string(),
+                in_memory_data.value_of(&simplified).unwrap().to_string(),
+                "value of path[{}] changed by simplification",
+                text
+            );
+        }
+    }
+}
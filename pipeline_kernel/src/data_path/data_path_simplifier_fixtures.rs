@@ -0,0 +1,50 @@
+use crate::{ArcFrom, ArcTopicDataValue, DataPath, DataPathParser, InMemoryData, PathStr};
+use elf_base::StdR;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// paths and data shared by tests of [crate::DataPathSimplifier] and benches/data_path_simplifier.rs,
+/// add new static folding case to [DataPathSimplifierFixtures::CORPUS].
+#[doc(hidden)]
+pub struct DataPathSimplifierFixtures;
+
+impl DataPathSimplifierFixtures {
+    /// path, and is static or not after simplification
+    pub const CORPUS: [(&'static str, bool); 9] = [
+        ("order.no", false),
+        ("{}ORD-{order.no}", false),
+        ("&concat({}ORD-,{}2024-,order.no)", false),
+        ("&yearDiff({}2020-01-01,{}2024-06-30)", true),
+        ("&concat({}prefix,{}-,{}suffix)", true),
+        ("&len({}abcdef)", true),
+        ("customer.name.&concat({}-,{}vip)", false),
+        ("&concat(customer.name,{}x{order.no})", false),
+        ("&upper(&concat({}ab,{}cd)).&len", true),
+    ];
+
+    /// path as written, without simplification
+    pub fn as_is(path: &str) -> StdR<DataPath> {
+        let all_chars: Arc<Vec<char>> = Arc::new(path.chars().collect());
+        let mut parser = DataPathParser::by_path(all_chars.clone());
+        parser.parse_as_is()?;
+        Ok(DataPath::new(PathStr::of_chars(all_chars), parser.segments))
+    }
+
+    pub fn in_memory_data() -> InMemoryData {
+        let str = |s: &str| ArcTopicDataValue::arc_from(s.to_string());
+        let order = HashMap::from([(String::from("no"), str("001"))]);
+        let customer = HashMap::from([(String::from("name"), str("Alice"))]);
+        InMemoryData::new(
+            None,
+            Some(Arc::new(HashMap::from([
+                (String::from("order"), ArcTopicDataValue::arc_from(order)),
+                (
+                    String::from("customer"),
+                    ArcTopicDataValue::arc_from(customer),
+                ),
+                (String::from("a"), str("1")),
+                (String::from("b"), str("2")),
+            ]))),
+        )
+    }
+}
@@ -0,0 +1,201 @@
+use crate::{
+    FunctionImplementation, FunctionSignature, PipelineKernelErrorCode, RegisteredFunction,
+};
+use elf_base::{ErrorCode, StdR, VoidR};
+use elf_model::{TenantId, VariablePredefineFunctions};
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
+
+type TenantFunctions = HashMap<String, Arc<RegisteredFunction>>;
+
+static FUNCTION_REGISTRY: OnceLock<RwLock<HashMap<TenantId, TenantFunctions>>> = OnceLock::new();
+
+/// functions registered by host application, per tenant.
+/// data path parser resolves function name against [VariablePredefineFunctions] first,
+/// then the registered functions of tenant.
+///
+/// the parsed data path holds the registered function,
+/// so re-register or unregister does not affect the compiled pipelines.
+pub struct FunctionRegistry;
+
+impl FunctionRegistry {
+    fn functions() -> &'static RwLock<HashMap<TenantId, TenantFunctions>> {
+        FUNCTION_REGISTRY.get_or_init(|| RwLock::new(HashMap::new()))
+    }
+
+    /// name can contain [A-Za-z0-9_] only, and cannot be same as any predefined function.
+    /// replace the existing one when name is registered already.
+    pub fn register<I>(
+        tenant_id: &TenantId,
+        signature: FunctionSignature,
+        implementation: I,
+    ) -> VoidR
+    where
+        I: FunctionImplementation + 'static,
+    {
+        let name = signature.name();
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return PipelineKernelErrorCode::FunctionNameInvalid.msg(format!(
+                "Function name[{}] is invalid, only [A-Za-z0-9_] is allowed.",
+                name
+            ));
+        }
+        if VariablePredefineFunctions::try_parse(format!("&{}", name)).is_some() {
+            return PipelineKernelErrorCode::FunctionNameConflict.msg(format!(
+                "Function name[{}] conflicts with predefined function.",
+                name
+            ));
+        }
+
+        let name = name.clone();
+        let function = Arc::new(RegisteredFunction::new(signature, Box::new(implementation)));
+        match Self::functions().write() {
+            Ok(mut guard) => {
+                guard
+                    .entry(tenant_id.clone())
+                    .or_default()
+                    .insert(name, function);
+                Ok(())
+            }
+            Err(e) => PipelineKernelErrorCode::FunctionRegistryUnavailable.msg(e.to_string()),
+        }
+    }
+
+    /// returns true when function registered and removed
+    pub fn unregister(tenant_id: &TenantId, name: &str) -> StdR<bool> {
+        match Self::functions().write() {
+            Ok(mut guard) => Ok(guard
+                .get_mut(tenant_id)
+                .and_then(|functions| functions.remove(name))
+                .is_some()),
+            Err(e) => PipelineKernelErrorCode::FunctionRegistryUnavailable.msg(e.to_string()),
+        }
+    }
+
+    /// name is without the leading [&]
+    pub fn find(tenant_id: &TenantId, name: &str) -> StdR<Option<Arc<RegisteredFunction>>> {
+        match Self::functions().read() {
+            Ok(guard) => Ok(guard
+                .get(tenant_id)
+                .and_then(|functions| functions.get(name))
+                .cloned()),
+            Err(e) => PipelineKernelErrorCode::FunctionRegistryUnavailable.msg(e.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ArcFrom, ArcTopicDataValue, DataPath, DataPathDiagnosticCode, FunctionRegistry,
+        FunctionSignature, InMemoryData,
+    };
+    use elf_base::StdR;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    fn str(s: &str) -> Arc<ArcTopicDataValue> {
+        ArcTopicDataValue::arc_from(s.to_string())
+    }
+
+    fn mask(
+        context: Arc<ArcTopicDataValue>,
+        params: Vec<Arc<ArcTopicDataValue>>,
+    ) -> StdR<Arc<ArcTopicDataValue>> {
+        let keep = match params.first().map(|p| p.as_ref()) {
+            Some(ArcTopicDataValue::Str(s)) => s.parse::<usize>().unwrap_or(0),
+            Some(ArcTopicDataValue::Num(n)) => n.to_string().parse::<usize>().unwrap_or(0),
+            _ => 0,
+        };
+        match context.as_ref() {
+            ArcTopicDataValue::Str(s) => {
+                let chars: Vec<char> = s.chars().collect();
+                let masked: String = chars
+                    .iter()
+                    .enumerate()
+                    .map(|(index, c)| if index < keep { *c } else { '*' })
+                    .collect();
+                Ok(str(&masked))
+            }
+            _ => Ok(context),
+        }
+    }
+
+    fn in_memory_data() -> InMemoryData {
+        InMemoryData::new(
+            None,
+            Some(Arc::new(HashMap::from([(
+                String::from("name"),
+                str("Alice"),
+            )]))),
+        )
+    }
+
+    #[test]
+    fn test_register() {
+        let tenant_id = Arc::new(String::from("registry-tenant-1"));
+        FunctionRegistry::register(
+            &tenant_id,
+            FunctionSignature::new("mask").min_params(0).max_params(1),
+            mask,
+        )
+        .unwrap();
+        FunctionRegistry::register(
+            &tenant_id,
+            FunctionSignature::new("hello").context(false).max_params(0),
+            |_: Arc<ArcTopicDataValue>, _: Vec<Arc<ArcTopicDataValue>>| Ok(str("hello")),
+        )
+        .unwrap();
+
+        let data = in_memory_data();
+        let value_of = |path: &str| {
+            let path = DataPath::from_str_of_tenant(path, &tenant_id).unwrap();
+            match data.value_of(&path).unwrap().as_ref() {
+                ArcTopicDataValue::Str(s) => s.to_string(),
+                ArcTopicDataValue::Num(n) => n.to_string(),
+                other => panic!("unexpected value {}", other),
+            }
+        };
+        assert_eq!(value_of("name.&mask({}2)"), "Al***");
+        assert_eq!(value_of("&mask(name)"), "*****");
+        assert_eq!(value_of("&hello"), "hello");
+        assert_eq!(value_of("&hello.&length"), "5");
+        // registered function in parameter of predefined one
+        assert_eq!(
+            value_of("&concat(&hello(),{}-,name.&mask({}1))"),
+            "hello-A****"
+        );
+
+        // signature is checked by parser
+        assert!(DataPath::from_str_of_tenant("name.&mask({}1,{}2)", &tenant_id).is_err());
+        assert!(DataPath::from_str_of_tenant("name.&hello", &tenant_id).is_err());
+        // not resolved without tenant, or in another tenant
+        assert!(DataPath::from_str("name.&mask").is_err());
+        let diagnostics = DataPath::diagnose_of_tenant(
+            "name.&mask",
+            &Arc::new(String::from("registry-tenant-2")),
+        );
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, DataPathDiagnosticCode::UnknownFunction);
+
+        assert!(FunctionRegistry::unregister(&tenant_id, "mask").unwrap());
+        assert!(!FunctionRegistry::unregister(&tenant_id, "mask").unwrap());
+        assert!(DataPath::from_str_of_tenant("name.&mask", &tenant_id).is_err());
+    }
+
+    #[test]
+    fn test_register_rejected() {
+        let tenant_id = String::from("registry-tenant-3");
+        let echo = |context: Arc<ArcTopicDataValue>, _: Vec<Arc<ArcTopicDataValue>>| Ok(context);
+        // predefined function goes first, cannot be overridden
+        let error = FunctionRegistry::register(&tenant_id, FunctionSignature::new("sum"), echo)
+            .unwrap_err();
+        assert!(error.to_string().contains("PLKN-00109"));
+        let error = FunctionRegistry::register(&tenant_id, FunctionSignature::new("my-func"), echo)
+            .unwrap_err();
+        assert!(error.to_string().contains("PLKN-00108"));
+        let error =
+            FunctionRegistry::register(&tenant_id, FunctionSignature::new(""), echo).unwrap_err();
+        assert!(error.to_string().contains("PLKN-00108"));
+    }
+}
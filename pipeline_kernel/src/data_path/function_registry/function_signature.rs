@@ -0,0 +1,115 @@
+/// signature of a registered function, restrictions are same as the `#[restrict]` of
+/// [elf_model::VariablePredefineFunctions].
+/// - context: function requires context or not, default true,
+/// - none_context/blank_context: context can be none/blank string or not, default false,
+/// - min_params: default 0,
+/// - max_params: default no limit,
+/// - none_params/blank_params: parameters can be none/blank string or not, default false.
+pub struct FunctionSignature {
+    name: String,
+    context: bool,
+    none_context: bool,
+    blank_context: bool,
+    min_param_count: usize,
+    max_param_count: Option<usize>,
+    none_params: bool,
+    blank_params: bool,
+}
+
+impl FunctionSignature {
+    /// name is without the leading [&]
+    pub fn new<N>(name: N) -> Self
+    where
+        N: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            context: true,
+            none_context: false,
+            blank_context: false,
+            min_param_count: 0,
+            max_param_count: None,
+            none_params: false,
+            blank_params: false,
+        }
+    }
+
+    pub fn context(mut self, context: bool) -> Self {
+        self.context = context;
+        self
+    }
+
+    pub fn none_context(mut self, none_context: bool) -> Self {
+        self.none_context = none_context;
+        self
+    }
+
+    pub fn blank_context(mut self, blank_context: bool) -> Self {
+        self.blank_context = blank_context;
+        self
+    }
+
+    pub fn min_params(mut self, min_param_count: usize) -> Self {
+        self.min_param_count = min_param_count;
+        self
+    }
+
+    pub fn max_params(mut self, max_param_count: usize) -> Self {
+        self.max_param_count = Some(max_param_count);
+        self
+    }
+
+    pub fn none_params(mut self, none_params: bool) -> Self {
+        self.none_params = none_params;
+        self
+    }
+
+    pub fn blank_params(mut self, blank_params: bool) -> Self {
+        self.blank_params = blank_params;
+        self
+    }
+}
+
+/// same as [elf_model::VariablePredefineFunctions]
+impl FunctionSignature {
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn require_context(&self) -> bool {
+        self.context
+    }
+
+    /// returns false if context is not allowed.
+    pub fn allow_none_context(&self) -> bool {
+        self.context && self.none_context
+    }
+
+    /// returns false if context is not allowed.
+    pub fn allow_blank_context(&self) -> bool {
+        self.context && self.blank_context
+    }
+
+    pub fn min_param_count(&self) -> usize {
+        self.min_param_count
+    }
+
+    pub fn max_param_count(&self) -> Option<usize> {
+        self.max_param_count
+    }
+
+    /// returns false if given parameter index is over max param count.
+    pub fn allow_none_param(&self, param_index: usize) -> bool {
+        self.accepts_param_at(param_index) && self.none_params
+    }
+
+    /// returns false if given parameter index is over max param count.
+    pub fn allow_blank_param(&self, param_index: usize) -> bool {
+        self.accepts_param_at(param_index) && self.blank_params
+    }
+
+    fn accepts_param_at(&self, param_index: usize) -> bool {
+        self.max_param_count
+            .is_none_or(|max_param_count| param_index < max_param_count)
+    }
+}
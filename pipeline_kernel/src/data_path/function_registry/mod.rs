@@ -0,0 +1,7 @@
+mod function_registry;
+mod function_signature;
+mod registered_function;
+
+pub use function_registry::*;
+pub use function_signature::*;
+pub use registered_function::*;
@@ -0,0 +1,66 @@
+use crate::{ArcTopicDataValue, FunctionSignature};
+use elf_base::StdR;
+use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+
+/// implementation of a registered function.
+/// context is [ArcTopicDataValue::None] when function does not require context.
+/// parameter count is checked by signature before call.
+pub trait FunctionImplementation: Send + Sync {
+    fn call(
+        &self,
+        context: Arc<ArcTopicDataValue>,
+        params: Vec<Arc<ArcTopicDataValue>>,
+    ) -> StdR<Arc<ArcTopicDataValue>>;
+}
+
+impl<F> FunctionImplementation for F
+where
+    F: Fn(Arc<ArcTopicDataValue>, Vec<Arc<ArcTopicDataValue>>) -> StdR<Arc<ArcTopicDataValue>>
+        + Send
+        + Sync,
+{
+    fn call(
+        &self,
+        context: Arc<ArcTopicDataValue>,
+        params: Vec<Arc<ArcTopicDataValue>>,
+    ) -> StdR<Arc<ArcTopicDataValue>> {
+        self(context, params)
+    }
+}
+
+pub struct RegisteredFunction {
+    signature: FunctionSignature,
+    implementation: Box<dyn FunctionImplementation>,
+}
+
+impl RegisteredFunction {
+    pub fn new(
+        signature: FunctionSignature,
+        implementation: Box<dyn FunctionImplementation>,
+    ) -> Self {
+        Self {
+            signature,
+            implementation,
+        }
+    }
+
+    pub fn signature(&self) -> &FunctionSignature {
+        &self.signature
+    }
+
+    pub fn call(
+        &self,
+        context: Arc<ArcTopicDataValue>,
+        params: Vec<Arc<ArcTopicDataValue>>,
+    ) -> StdR<Arc<ArcTopicDataValue>> {
+        self.implementation.call(context, params)
+    }
+}
+
+/// same as [elf_model::VariablePredefineFunctions], with the leading [&]
+impl Display for RegisteredFunction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "&{}", self.signature.name())
+    }
+}
@@ -1,17 +1,25 @@
 mod data_path;
+mod data_path_diagnostic;
 mod data_path_func;
 mod data_path_func_param;
 mod data_path_plain;
 mod data_path_segment;
+mod data_path_simplifier;
+mod data_path_simplifier_fixtures;
+mod function_registry;
 mod parser;
 mod path_str;
 mod static_values;
 
 pub use data_path::*;
+pub use data_path_diagnostic::*;
 pub use data_path_func::*;
 pub use data_path_func_param::*;
 pub use data_path_plain::*;
 pub use data_path_segment::*;
+pub use data_path_simplifier::*;
+pub use data_path_simplifier_fixtures::*;
+pub use function_registry::*;
 pub use parser::*;
 pub use path_str::*;
 pub use static_values::*;
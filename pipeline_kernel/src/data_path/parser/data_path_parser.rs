@@ -1,8 +1,9 @@
 use crate::{
-    DataPath, DataPathParser, DataPathSegment, PathStr, PipelineKernelErrorCode, PlainDataPath,
+    DataPath, DataPathDiagnostic, DataPathParser, DataPathSegment, DataPathSimplifier, PathStr,
+    PipelineKernelErrorCode, PlainDataPath,
 };
 use elf_base::{ErrorCode, StdR};
-use elf_model::FactorType;
+use elf_model::{FactorType, TenantId};
 use elf_runtime_model_kernel::{ArcFactor, TopicSchema};
 use std::sync::Arc;
 
@@ -53,10 +54,103 @@ impl DataPath {
     /// and fail fast
     pub fn from_str(path: &str) -> StdR<DataPath> {
         let all_chars: Arc<Vec<char>> = Arc::new(path.chars().collect());
-        let mut parser = DataPathParser::by_path(all_chars.clone());
+        Self::parse_by(DataPathParser::by_path(all_chars.clone()), all_chars)
+    }
+
+    /// same as [from_str], and the functions registered for given tenant are resolved,
+    /// see [crate::FunctionRegistry].
+    pub fn from_str_of_tenant(path: &str, tenant_id: &Arc<TenantId>) -> StdR<DataPath> {
+        let all_chars: Arc<Vec<char>> = Arc::new(path.chars().collect());
+        Self::parse_by(
+            DataPathParser::by_path_of_tenant(all_chars.clone(), tenant_id.clone()),
+            all_chars,
+        )
+    }
+
+    fn parse_by(mut parser: DataPathParser, all_chars: Arc<Vec<char>>) -> StdR<DataPath> {
         parser.parse()?;
+        let static_value = DataPathSimplifier::static_value_of(&parser.segments);
+
+        Ok(DataPath::new(PathStr::of_chars(all_chars), parser.segments)
+            .with_static_value(static_value))
+    }
+
+    /// same syntax as [from_str], but not fail fast.
+    /// when an error met, skip to the next top level dot and continue parsing,
+    /// all errors are reported as diagnostics. empty when path is correct.
+    pub fn diagnose(path: &str) -> Vec<DataPathDiagnostic> {
+        let all_chars: Arc<Vec<char>> = Arc::new(path.chars().collect());
+        Self::diagnose_by(DataPathParser::by_path(all_chars.clone()), all_chars)
+    }
 
-        Ok(DataPath::new(PathStr::of_chars(all_chars), parser.segments))
+    /// same as [diagnose], and the functions registered for given tenant are resolved.
+    pub fn diagnose_of_tenant(path: &str, tenant_id: &Arc<TenantId>) -> Vec<DataPathDiagnostic> {
+        let all_chars: Arc<Vec<char>> = Arc::new(path.chars().collect());
+        Self::diagnose_by(
+            DataPathParser::by_path_of_tenant(all_chars.clone(), tenant_id.clone()),
+            all_chars,
+        )
+    }
+
+    fn diagnose_by(
+        mut parser: DataPathParser,
+        all_chars: Arc<Vec<char>>,
+    ) -> Vec<DataPathDiagnostic> {
+        let mut resume_at = 0;
+        while parser.parse_as_is().is_err() {
+            let error_start = parser
+                .inner
+                .last_diagnostic()
+                .map(|diagnostic| diagnostic.span.start)
+                .unwrap_or(all_chars.len());
+            let Some(index_of_dot) =
+                Self::index_of_top_level_dot(&all_chars, error_start.max(resume_at))
+            else {
+                break;
+            };
+            // the skipped part is taken as a plain segment,
+            // so the following segments are parsed with context
+            parser.append_segment(DataPathSegment::Plain(PlainDataPath::new(
+                PathStr::part_of_chars(all_chars.clone(), resume_at, index_of_dot),
+                None,
+            )));
+            resume_at = index_of_dot + 1;
+            parser.inner.move_char_index_to(resume_at);
+            parser.inner.clear_in_memory_chars();
+        }
+
+        let mut diagnostics: Vec<DataPathDiagnostic> = vec![];
+        for diagnostic in parser.inner.take_diagnostics() {
+            // same error might be reported again after recovered, e.g. path ends with dot
+            if !diagnostics
+                .iter()
+                .any(|d| d.code == diagnostic.code && d.span == diagnostic.span)
+            {
+                diagnostics.push(diagnostic);
+            }
+        }
+        diagnostics
+    }
+
+    /// find the first dot not escaped and not in any parenthesis or brace, starts from given index.
+    fn index_of_top_level_dot(all_chars: &[char], from: usize) -> Option<usize> {
+        let mut depth = 0;
+        let mut escaped = false;
+        for (index, char) in all_chars.iter().enumerate() {
+            if escaped {
+                escaped = false;
+                continue;
+            }
+            match char {
+                '\\' => escaped = true,
+                '(' | '{' => depth += 1,
+                // unmatched closing is ignored
+                ')' | '}' if depth > 0 => depth -= 1,
+                '.' if depth == 0 && index >= from => return Some(index),
+                _ => {}
+            }
+        }
+        None
     }
 }
 
@@ -134,7 +228,7 @@ mod tests {
             match segment {
                 DataPathSegment::Func(func_path) => {
                     assert_eq!(func_path.this_path(), path);
-                    f1(func_path.func());
+                    f1(func_path.predefined_func().unwrap());
                     assert!(func_path.params().is_some());
                     if let Some(params) = func_path.params() {
                         f2(params);
@@ -152,7 +246,7 @@ mod tests {
             match segment {
                 DataPathSegment::Func(func_path) => {
                     assert_eq!(func_path.this_path(), path);
-                    f1(func_path.func());
+                    f1(func_path.predefined_func().unwrap());
                     assert!(func_path.params().is_none());
                 }
                 _ => {}
@@ -1,106 +1,127 @@
-use crate::DataPathFuncParser;
+use crate::{DataPathDiagnostic, DataPathDiagnosticCode, DataPathFuncParser};
 use elf_base::StdR;
 
 /// error raise helper of data path, function parser
 impl DataPathFuncParser {
     /// report error at [index of ampersand, current char index)
     #[track_caller]
-    pub fn incorrect_function_has_context<R>(&self) -> StdR<R> {
+    pub fn incorrect_function_has_context<R>(&mut self) -> StdR<R> {
         let start_char_index = self.start_char_index_of_func;
         let end_char_index = self.inner.current_char_index();
-        self.inner.error(format!(
-            "Incorrect data path[{}], caused by function[{}] cannot have context at index[{}, {}].",
-            self.inner.full_path(),
-            self.inner.part_path(start_char_index, end_char_index),
-            start_char_index,
-            end_char_index
-        ))
+        self.inner.report(
+            DataPathDiagnostic::new(
+                DataPathDiagnosticCode::UnexpectedContext,
+                format!(
+                    "function[{}] cannot have context",
+                    self.inner.part_path(start_char_index, end_char_index)
+                ),
+                start_char_index,
+                end_char_index,
+            )
+            .with_help("move the function to the start of path, or into a parameter"),
+        )
     }
 
     /// report error at [index of ampersand, current char index)
     #[track_caller]
-    pub fn incorrect_function_has_no_context<R>(&self) -> StdR<R> {
+    pub fn incorrect_function_has_no_context<R>(&mut self) -> StdR<R> {
         let start_char_index = self.start_char_index_of_func;
         let end_char_index = self.inner.current_char_index();
-        self.inner.error(format!(
-            "Incorrect data path[{}], caused by function[{}] must have context at index[{}, {}].",
-            self.inner.full_path(),
-            self.inner.part_path(start_char_index, end_char_index),
-            start_char_index,
-            end_char_index
-        ))
+        self.inner.report(
+            DataPathDiagnostic::new(
+                DataPathDiagnosticCode::MissingContext,
+                format!(
+                    "function[{}] must have context",
+                    self.inner.part_path(start_char_index, end_char_index)
+                ),
+                start_char_index,
+                end_char_index,
+            )
+            .with_help("call it after a segment, or pass the context as first parameter"),
+        )
     }
 
     /// report error at [index of left parenthesis]
     #[track_caller]
     pub fn incorrect_function_params_not_close<R>(
-        &self,
+        &mut self,
         index_of_left_parenthesis: usize,
     ) -> StdR<R> {
-        self.inner.error(format!(
-            "Incorrect data path[{}], caused by the closing \")\" is not matched, the opening \"(\" is at index [{}].",
-            self.inner.full_path(), index_of_left_parenthesis
-        ))
+        self.inner.report(
+            DataPathDiagnostic::new(
+                DataPathDiagnosticCode::UnclosedParenthesis,
+                "the closing \")\" is not matched",
+                index_of_left_parenthesis,
+                index_of_left_parenthesis + 1,
+            )
+            .with_help("add \")\" to close the parameters"),
+        )
     }
 
     /// report error at [index of left parenthesis, current char index)
     #[track_caller]
     pub fn incorrect_function_param_over_max_count<R>(
-        &self,
+        &mut self,
         index_of_left_parenthesis: usize,
         max_count: usize,
     ) -> StdR<R> {
-        self.inner.error(format!(
-            "Incorrect data path[{}], caused by function[{}] can accept a maximum of {} parameters at index[{}, {}].",
-            self.inner.full_path(),
-            self.func.to_string(),
-            max_count,
+        self.inner.report(DataPathDiagnostic::new(
+            DataPathDiagnosticCode::TooManyParams,
+            format!(
+                "function[{}] can accept a maximum of {} parameters",
+                self.func, max_count
+            ),
             index_of_left_parenthesis,
-            self.inner.current_char_index()
+            self.inner.current_char_index(),
         ))
     }
 
     /// report error at [index of left parenthesis, current char index)
     #[track_caller]
     pub fn incorrect_function_param_below_min_count<R>(
-        &self,
+        &mut self,
         index_of_left_parenthesis: usize,
         min_count: usize,
     ) -> StdR<R> {
-        self.inner.error(format!(
-            "Incorrect data path[{}], caused by function[{}] can accept a minimum of {} parameters at index[{}, {}].",
-            self.inner.full_path(),
-            self.func.to_string(),
-            min_count,
+        self.inner.report(DataPathDiagnostic::new(
+            DataPathDiagnosticCode::TooFewParams,
+            format!(
+                "function[{}] can accept a minimum of {} parameters",
+                self.func, min_count
+            ),
             index_of_left_parenthesis,
-            self.inner.current_char_index()
+            self.inner.current_char_index(),
         ))
     }
 
     /// report error at [current char index - in memory chars count, current char index)
     #[track_caller]
     pub fn incorrect_function_param_tailing_whitespaces<R>(
-        &self,
+        &mut self,
         in_memory_chars_count: usize,
     ) -> StdR<R> {
-        self.inner.error(format!(
-            "Incorrect data path[{}], caused by function[{}] parameter has unexpected tailing whitespaces at index[{}, {}].",
-            self.inner.full_path(),
-            self.func.to_string(),
-            self.inner.char_index_before_current(in_memory_chars_count),
-            self.inner.current_char_index()
-        ))
+        self.inner.report(
+            DataPathDiagnostic::new(
+                DataPathDiagnosticCode::TrailingWhitespaces,
+                format!(
+                    "function[{}] parameter has unexpected tailing whitespaces",
+                    self.func
+                ),
+                self.inner.char_index_before_current(in_memory_chars_count) as usize,
+                self.inner.current_char_index(),
+            )
+            .with_help("remove whitespace(s) before \",\" or \")\""),
+        )
     }
 
     /// report error at [current char index - in memory chars count, current char index)
     #[track_caller]
-    pub fn incorrect_function_invalid_context<R>(&self, chars_count: usize) -> StdR<R> {
-        self.inner.error(format!(
-            "Incorrect data path[{}], caused by context of function[{}] is invalid at index[{}, {}].",
-            self.inner.full_path(),
-            self.func.to_string(),
-            self.inner.char_index_before_current(chars_count),
-            self.inner.current_char_index()
+    pub fn incorrect_function_invalid_context<R>(&mut self, chars_count: usize) -> StdR<R> {
+        self.inner.report(DataPathDiagnostic::new(
+            DataPathDiagnosticCode::InvalidContext,
+            format!("context of function[{}] is invalid", self.func),
+            self.inner.char_index_before_current(chars_count) as usize,
+            self.inner.current_char_index(),
         ))
     }
 }
@@ -34,7 +34,10 @@ impl DataPathFuncParser {
             inner: ParserInnerState::new_at_current_char_and_copy_in_memory_chars(&mut self.inner),
             segments: vec![],
         };
-        path_parser.parse_till_param_end(param_start_char_index)?;
+        let parsed = path_parser.parse_till_param_end(param_start_char_index);
+        // hand back diagnostics, no matter parsed or not
+        self.inner.take_diagnostics_of(&mut path_parser.inner);
+        parsed?;
         let delegate = &mut DataPathFuncParserDelegate {
             parser: self,
             param_start_char_index,
@@ -46,7 +49,7 @@ impl DataPathFuncParser {
 
     /// check if there are tailing whitespaces when parameter already parsed.
     /// if there are, raise error.
-    fn check_tailing_whitespaces_when_param_parsed(&self) -> VoidR {
+    fn check_tailing_whitespaces_when_param_parsed(&mut self) -> VoidR {
         if self.inner.in_memory_chars_is_not_empty() {
             // never happens, all chars should be consumed in parse_param
             self.incorrect_function_param_tailing_whitespaces(self.inner.in_memory_chars_count())
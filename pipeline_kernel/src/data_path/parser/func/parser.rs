@@ -1,11 +1,10 @@
-use crate::{FuncDataPathParam, ParserInnerState};
-use elf_model::VariablePredefineFunctions;
+use crate::{DataPathFunc, FuncDataPathParam, ParserInnerState};
 
 /// data path, function parser
 pub struct DataPathFuncParser {
     pub inner: ParserInnerState,
     pub start_char_index_of_func: usize,
-    pub func: VariablePredefineFunctions,
+    pub func: DataPathFunc,
     pub params: Vec<FuncDataPathParam>,
     pub with_context: bool,
 }
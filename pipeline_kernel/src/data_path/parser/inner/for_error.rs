@@ -1,68 +1,80 @@
-use crate::{ParserInnerState, PipelineKernelErrorCode};
+use crate::{
+    DataPathDiagnostic, DataPathDiagnosticCode, ParserInnerState, PipelineKernelErrorCode,
+};
 use elf_base::{ErrorCode, StdErrCode, StdR};
 
 /// report error
 impl ParserInnerState {
+    /// collect the diagnostic, and raise error with flat message of it
     #[track_caller]
-    pub fn error<S, R>(&self, msg: S) -> StdR<R>
-    where
-        S: Into<String>,
-    {
-        PipelineKernelErrorCode::IncorrectDataPath.msg(msg)
+    pub fn report<R>(&mut self, diagnostic: DataPathDiagnostic) -> StdR<R> {
+        let message = diagnostic.to_error_message(&self.full_path());
+        self.collect_diagnostic(diagnostic);
+        PipelineKernelErrorCode::IncorrectDataPath.msg(message)
     }
 
     #[track_caller]
-    pub fn incorrect_char_at_previous_index<R>(&self, char: &char) -> StdR<R> {
-        self.error(format!(
-            "Incorrect data path[{}], caused by incorrect {} at index[{}].",
-            self.full_path(),
-            char,
-            self.previous_char_index()
-        ))
+    pub fn incorrect_char_at_previous_index<R>(&mut self, char: char) -> StdR<R> {
+        let index = self.previous_char_index() as usize;
+        self.report(
+            DataPathDiagnostic::new(
+                DataPathDiagnosticCode::UnexpectedChar,
+                format!("incorrect {}", char),
+                index,
+                index + 1,
+            )
+            .with_help(format!("use \"\\{}\" if it is a part of name", char)),
+        )
     }
 
     #[track_caller]
-    fn incorrect_char_at_index<R>(&self, reason: &str) -> StdR<R> {
-        self.error(format!(
-            "Incorrect data path[{}], caused by incorrect {} at index[{}].",
-            self.full_path(),
-            reason,
-            self.current_char_index()
-        ))
+    fn incorrect_char_at_index<R>(&mut self, reason: &str) -> StdR<R> {
+        let index = self.current_char_index();
+        let mut diagnostic = DataPathDiagnostic::new(
+            DataPathDiagnosticCode::UnexpectedChar,
+            format!("incorrect {}", reason),
+            index,
+            index + 1,
+        );
+        if let Some(char) = self.current_char() {
+            diagnostic =
+                diagnostic.with_help(format!("use \"\\{}\" if it is a part of name", char));
+        }
+        self.report(diagnostic)
     }
 
     #[track_caller]
-    pub fn incorrect_dot<R>(&self) -> StdR<R> {
+    pub fn incorrect_dot<R>(&mut self) -> StdR<R> {
         self.incorrect_char_at_index("dot")
     }
 
     #[track_caller]
-    pub fn incorrect_comma<R>(&self) -> StdR<R> {
+    pub fn incorrect_comma<R>(&mut self) -> StdR<R> {
         self.incorrect_char_at_index("comma")
     }
 
     #[track_caller]
-    pub fn incorrect_left_parenthesis<R>(&self) -> StdR<R> {
+    pub fn incorrect_left_parenthesis<R>(&mut self) -> StdR<R> {
         self.incorrect_char_at_index("left parenthesis")
     }
 
     #[track_caller]
-    pub fn incorrect_right_parenthesis<R>(&self) -> StdR<R> {
+    pub fn incorrect_right_parenthesis<R>(&mut self) -> StdR<R> {
         self.incorrect_char_at_index("right parenthesis")
     }
 
     #[track_caller]
-    pub fn incorrect_left_brace<R>(&self) -> StdR<R> {
+    pub fn incorrect_left_brace<R>(&mut self) -> StdR<R> {
         self.incorrect_char_at_index("left brace")
     }
 
     #[track_caller]
-    pub fn incorrect_right_brace<R>(&self) -> StdR<R> {
+    pub fn incorrect_right_brace<R>(&mut self) -> StdR<R> {
         self.incorrect_char_at_index("right brace")
     }
 
     #[track_caller]
-    pub fn incorrect_ampersand<R>(&self) -> StdR<R> {
+    pub fn incorrect_ampersand<R>(&mut self) -> StdR<R> {
         self.incorrect_char_at_index("ampersand")
     }
 
@@ -1,4 +1,6 @@
+use crate::DataPathDiagnostic;
 use elf_base::StringUtils;
+use elf_model::TenantId;
 use std::sync::Arc;
 
 pub struct ParserInnerState {
@@ -8,6 +10,11 @@ pub struct ParserInnerState {
     char_index: usize,
     /// in-memory chars, not consumed yet
     in_memory_chars: String,
+    /// diagnostics reported by this state,
+    /// the ones of sub states are handed back by [take_diagnostics_of]
+    diagnostics: Vec<DataPathDiagnostic>,
+    /// tenant of path, to resolve the registered functions
+    tenant_id: Option<Arc<TenantId>>,
 }
 
 /// create
@@ -18,6 +25,8 @@ impl ParserInnerState {
             all_chars,
             char_index: 0,
             in_memory_chars: String::new(),
+            diagnostics: vec![],
+            tenant_id: None,
         }
     }
 
@@ -27,6 +36,8 @@ impl ParserInnerState {
             all_chars: other.all_chars.clone(),
             char_index: other.char_index,
             in_memory_chars: String::new(),
+            diagnostics: vec![],
+            tenant_id: other.tenant_id.clone(),
         }
     }
 
@@ -44,6 +55,8 @@ impl ParserInnerState {
                 other.clear_in_memory_chars();
                 chars
             },
+            diagnostics: vec![],
+            tenant_id: other.tenant_id.clone(),
         }
     }
 
@@ -53,10 +66,24 @@ impl ParserInnerState {
             all_chars: other.all_chars.clone(),
             char_index: other.char_index + 1,
             in_memory_chars: String::new(),
+            diagnostics: vec![],
+            tenant_id: other.tenant_id.clone(),
         }
     }
 }
 
+// for tenant
+impl ParserInnerState {
+    pub fn with_tenant_id(mut self, tenant_id: Arc<TenantId>) -> Self {
+        self.tenant_id = Some(tenant_id);
+        self
+    }
+
+    pub fn tenant_id(&self) -> Option<&Arc<TenantId>> {
+        self.tenant_id.as_ref()
+    }
+}
+
 // for chars
 impl ParserInnerState {
     pub fn all_chars(&self) -> &Arc<Vec<char>> {
@@ -169,3 +196,26 @@ impl ParserInnerState {
         self.in_memory_chars.clear()
     }
 }
+
+/// for diagnostics
+impl ParserInnerState {
+    pub fn collect_diagnostic(&mut self, diagnostic: DataPathDiagnostic) {
+        self.diagnostics.push(diagnostic)
+    }
+
+    /// move diagnostics of sub state into this one,
+    /// sub state is created to parse function, parameter or wrapped path.
+    pub fn take_diagnostics_of(&mut self, sub: &mut Self) {
+        self.diagnostics.append(&mut sub.diagnostics)
+    }
+
+    /// get the last reported diagnostic
+    pub fn last_diagnostic(&self) -> Option<&DataPathDiagnostic> {
+        self.diagnostics.last()
+    }
+
+    /// take all reported diagnostics, diagnostics is empty after taken
+    pub fn take_diagnostics(&mut self) -> Vec<DataPathDiagnostic> {
+        std::mem::take(&mut self.diagnostics)
+    }
+}
@@ -8,7 +8,10 @@ impl LiteralConcatFuncParser {
             inner: ParserInnerState::new_at_current_char(&self.inner),
             segments: vec![],
         };
-        path_parser.parse_till_right_brace()?;
+        let parsed = path_parser.parse_till_right_brace();
+        // hand back diagnostics, no matter parsed or not
+        self.inner.take_diagnostics_of(&mut path_parser.inner);
+        parsed?;
         path_parser.hand_back_to_literal_concat(self);
 
         // then check the current char, which is after the enclosing "}"
@@ -1,122 +1,172 @@
-use crate::DataPathParser;
+use crate::{DataPathDiagnostic, DataPathDiagnosticCode, DataPathFunc, DataPathParser};
 use elf_base::StdR;
-use elf_model::VariablePredefineFunctions;
 
 impl DataPathParser {
     /// path is empty
     #[track_caller]
-    pub fn incorrect_empty_path<R>(&self) -> StdR<R> {
-        self.inner
-            .error("Incorrect data path, caused by not content determined.")
+    pub fn incorrect_empty_path<R>(&mut self) -> StdR<R> {
+        self.inner.report(DataPathDiagnostic::new(
+            DataPathDiagnosticCode::EmptyPath,
+            "not content determined",
+            0,
+            0,
+        ))
     }
 
     /// report error at [index of blank string start, current char index)
     #[track_caller]
-    pub fn incorrect_blank_segment<R>(&self) -> StdR<R> {
-        self.inner.error(format!(
-            "Incorrect data path[{}], caused by blank segment at index[{}, {}].",
-            self.inner.full_path(),
+    pub fn incorrect_blank_segment<R>(&mut self) -> StdR<R> {
+        let start_char_index =
             self.inner
-                .char_index_before_current(self.inner.in_memory_chars_count()),
-            self.inner.current_char_index(),
-        ))
+                .char_index_before_current(self.inner.in_memory_chars_count()) as usize;
+        self.inner.report(
+            DataPathDiagnostic::new(
+                DataPathDiagnosticCode::BlankSegment,
+                "blank segment",
+                start_char_index,
+                self.inner.current_char_index(),
+            )
+            .with_help("segment between dots cannot be blank"),
+        )
     }
 
     /// report error at [index of left brace]
     #[track_caller]
-    pub fn incorrect_wrapped_path<R>(&self, index_of_left_brace: usize) -> StdR<R> {
-        self.inner.error(format!(
-            "Incorrect data path[{}], caused by the closing \"}}\" is not matched, the opening \"{{\" is at index [{}].",
-            self.inner.full_path(), index_of_left_brace
-        ))
+    pub fn incorrect_wrapped_path<R>(&mut self, index_of_left_brace: usize) -> StdR<R> {
+        self.inner.report(
+            DataPathDiagnostic::new(
+                DataPathDiagnosticCode::UnclosedBrace,
+                "the closing \"}\" is not matched",
+                index_of_left_brace,
+                index_of_left_brace + 1,
+            )
+            .with_help("add \"}\" to close the wrapped path"),
+        )
     }
 
     /// report error at [current char index]
     #[track_caller]
-    pub fn incorrect_function_name_char<R>(&self, char: char) -> StdR<R> {
-        self.inner.error(format!(
-            "Incorrect data path[{}], caused by disallowed char[{}] in function name at index[{}].",
-            self.inner.full_path(),
-            char,
-            self.inner.current_char_index()
-        ))
+    pub fn incorrect_function_name_char<R>(&mut self, char: char) -> StdR<R> {
+        let char_index = self.inner.current_char_index();
+        self.inner.report(
+            DataPathDiagnostic::new(
+                DataPathDiagnosticCode::DisallowedCharInFunctionName,
+                format!("disallowed char[{}] in function name", char),
+                char_index,
+                char_index + 1,
+            )
+            .with_help("function name can only contain [A-Za-z0-9_]"),
+        )
     }
 
     /// report error at [current char index - 1]
     #[track_caller]
-    pub fn incorrect_empty_function_name<R>(&self) -> StdR<R> {
-        self.inner.error(format!(
-            "Incorrect data path[{}], caused by empty function name at index[{}].",
-            self.inner.full_path(),
-            self.inner.previous_char_index()
-        ))
+    pub fn incorrect_empty_function_name<R>(&mut self) -> StdR<R> {
+        let char_index = self.inner.previous_char_index() as usize;
+        self.inner.report(
+            DataPathDiagnostic::new(
+                DataPathDiagnosticCode::EmptyFunctionName,
+                "empty function name",
+                char_index,
+                char_index + 1,
+            )
+            .with_help("add function name after \"&\", or use \"\\&\" if it is a part of name"),
+        )
     }
 
     /// report error at [current char index - in memory chars count, current char index)
     #[track_caller]
-    pub fn incorrect_function_name<R>(&self, in_memory_chars_count: usize) -> StdR<R> {
+    pub fn incorrect_function_name<R>(&mut self, in_memory_chars_count: usize) -> StdR<R> {
         let end_char_index = self.inner.current_char_index();
         let start_char_index = self.inner.char_index_before_current(in_memory_chars_count) as usize;
-        self.inner.error(format!(
-            "Incorrect data path[{}], caused by unrecognized function name[{}] at index[{}, {}].",
-            self.inner.full_path(),
-            self.inner.part_path(start_char_index, end_char_index),
-            start_char_index,
-            end_char_index
-        ))
+        self.inner.report(
+            DataPathDiagnostic::new(
+                DataPathDiagnosticCode::UnknownFunction,
+                format!(
+                    "unrecognized function name[{}]",
+                    self.inner.part_path(start_char_index, end_char_index)
+                ),
+                start_char_index,
+                end_char_index,
+            )
+            .with_help(
+                "function name is case sensitive, e.g. &sum, &yearDiff, or register it for tenant",
+            ),
+        )
     }
 
     /// report error at [index of ampersand, current char index + 1)
     #[track_caller]
     pub fn incorrect_function_name_contains_whitespace<R>(
-        &self,
+        &mut self,
         index_of_ampersand: usize,
     ) -> StdR<R> {
         let end_char_index = self.inner.next_char_index();
-        self.inner.error(format!(
-            "Incorrect data path[{}], caused by function name[{}] contains whitespace(s) at index[{}, {}].",
-            self.inner.full_path(),
-            self.inner.part_path(index_of_ampersand,end_char_index),
-            index_of_ampersand,
-            end_char_index
-        ))
+        self.inner.report(
+            DataPathDiagnostic::new(
+                DataPathDiagnosticCode::WhitespaceInFunctionName,
+                format!(
+                    "function name[{}] contains whitespace(s)",
+                    self.inner.part_path(index_of_ampersand, end_char_index)
+                ),
+                index_of_ampersand,
+                end_char_index,
+            )
+            .with_help("remove whitespace(s) in function name"),
+        )
     }
 
     /// report error at [index of ampersand, current char index)
     #[track_caller]
-    pub fn incorrect_function_no_param<R>(&self, func: &VariablePredefineFunctions) -> StdR<R> {
+    pub fn incorrect_function_no_param<R>(&mut self, func: &DataPathFunc) -> StdR<R> {
         let end_char_index = self.inner.current_char_index();
         let start_char_index =
             self.inner
                 .char_index_before_current(func.to_string().chars().count()) as usize;
-        self.inner.error(format!(
-            "Incorrect data path[{}], caused by function[{}] must have parameter(s) at index[{}, {}].",
-            self.inner.full_path(),
-            self.inner.part_path(start_char_index,end_char_index),
-            start_char_index,
-            end_char_index
-        ))
+        let func_name = self.inner.part_path(start_char_index, end_char_index);
+        self.inner.report(
+            DataPathDiagnostic::new(
+                DataPathDiagnosticCode::MissingFunctionParams,
+                format!("function[{}] must have parameter(s)", func_name),
+                start_char_index,
+                end_char_index,
+            )
+            .with_help(format!("add parameter(s), e.g. {}(...)", func_name)),
+        )
     }
 
     /// report error at [index of ampersand, current char index)
     #[track_caller]
-    pub fn incorrect_function_has_context<R>(&self, index_of_ampersand: usize) -> StdR<R> {
+    pub fn incorrect_function_has_context<R>(&mut self, index_of_ampersand: usize) -> StdR<R> {
         let end_char_index = self.inner.current_char_index();
-        self.inner.error(format!(
-            "Incorrect data path[{}], caused by function[{}] cannot have context at index[{}, {}].",
-            self.inner.full_path(),
-            self.inner.part_path(index_of_ampersand, end_char_index),
-            index_of_ampersand,
-            end_char_index
-        ))
+        self.inner.report(
+            DataPathDiagnostic::new(
+                DataPathDiagnosticCode::UnexpectedContext,
+                format!(
+                    "function[{}] cannot have context",
+                    self.inner.part_path(index_of_ampersand, end_char_index)
+                ),
+                index_of_ampersand,
+                end_char_index,
+            )
+            .with_help("move the function to the start of path, or into a parameter"),
+        )
     }
 
     /// report error at [index of param start]
     #[track_caller]
-    pub fn incorrect_function_param_not_close<R>(&self, param_start_char_index: usize) -> StdR<R> {
-        self.inner.error(format!(
-            "Incorrect data path[{}], caused by the closing \",\" or \")\" is not matched, parameter starts at index [{}].",
-            self.inner.full_path(), param_start_char_index
-        ))
+    pub fn incorrect_function_param_not_close<R>(
+        &mut self,
+        param_start_char_index: usize,
+    ) -> StdR<R> {
+        self.inner.report(
+            DataPathDiagnostic::new(
+                DataPathDiagnosticCode::UnclosedParam,
+                "the closing \",\" or \")\" is not matched",
+                param_start_char_index,
+                param_start_char_index + 1,
+            )
+            .with_help("add \",\" or \")\" to end the parameter"),
+        )
     }
 }
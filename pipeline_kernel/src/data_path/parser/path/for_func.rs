@@ -1,21 +1,35 @@
 use crate::{
-    DataPathFuncParser, DataPathParser, DataPathSegment, FuncDataPath, ParserInnerState, PathStr,
+    DataPathFunc, DataPathFuncParser, DataPathParser, DataPathSegment, FuncDataPath,
+    FunctionRegistry, ParserInnerState, PathStr,
 };
 use elf_base::{StdR, VoidR};
 use elf_model::VariablePredefineFunctions;
 
 /// consume function
 impl DataPathParser {
-    /// consume in-memory chars as a function name.
+    /// find function registered for tenant of path, by given name (with the leading [&]).
+    fn find_registered_func(&self, name: &str) -> StdR<Option<DataPathFunc>> {
+        match self.inner.tenant_id() {
+            Some(tenant_id) => Ok(FunctionRegistry::find(tenant_id, &name[1..])?
+                .map(DataPathFunc::Registered)),
+            None => Ok(None),
+        }
+    }
+
+    /// consume in-memory chars as a function name,
+    /// resolve against the predefined functions first, then the registered functions of tenant.
     /// the in-memory chars never be empty, at least a [&] in it.
     /// and clear in-memory chars if consumed, will not move char index
-    fn consume_in_memory_chars_as_func_name(&mut self) -> StdR<VariablePredefineFunctions> {
+    fn consume_in_memory_chars_as_func_name(&mut self) -> StdR<DataPathFunc> {
         let in_memory_chars_count = self.inner.in_memory_chars_count();
         if in_memory_chars_count <= 1 {
             self.incorrect_empty_function_name()
         } else if let Some(func) =
             VariablePredefineFunctions::try_parse(self.inner.in_memory_chars())
         {
+            self.inner.clear_in_memory_chars();
+            Ok(DataPathFunc::Predefined(func))
+        } else if let Some(func) = self.find_registered_func(self.inner.in_memory_chars())? {
             self.inner.clear_in_memory_chars();
             Ok(func)
         } else {
@@ -96,7 +110,10 @@ impl DataPathParser {
                 params: vec![],
                 with_context: self.has_segment(),
             };
-            func_parser.parse()?;
+            let parsed = func_parser.parse();
+            // hand back diagnostics, no matter parsed or not
+            self.inner.take_diagnostics_of(&mut func_parser.inner);
+            parsed?;
             // hand back
             // copy char index to current state
             self.inner
@@ -27,7 +27,7 @@ impl DataPathParser {
     /// - one of [.,(] or start of full path: should create,
     /// - [}], not create,
     /// - otherwise raise error
-    fn should_create_concat_function(&self, index_of_char_before: i64) -> StdR<bool> {
+    fn should_create_concat_function(&mut self, index_of_char_before: i64) -> StdR<bool> {
         let inner = &self.inner;
 
         let char_before: Option<&char> = inner.char_at(index_of_char_before);
@@ -122,7 +122,11 @@ impl DataPathParser {
             inner: ParserInnerState::new_at_next_char(&self.inner),
             params,
         };
-        literal_concat_func_parser.parse()?;
+        let parsed = literal_concat_func_parser.parse();
+        // hand back diagnostics, no matter parsed or not
+        self.inner
+            .take_diagnostics_of(&mut literal_concat_func_parser.inner);
+        parsed?;
         // hand back
         // copy char index to current state
         self.inner
@@ -1,4 +1,4 @@
-use crate::DataPathParser;
+use crate::{DataPathParser, DataPathSimplifier};
 use elf_base::VoidR;
 
 /// consume path
@@ -15,7 +15,7 @@ impl DataPathParser {
     fn consume_in_memory_chars_before_dot(&mut self) -> VoidR {
         if self.inner.in_memory_chars_is_empty() {
             // check the previous char
-            if let Some(previous_char) = self.inner.previous_char() {
+            if let Some(previous_char) = self.inner.previous_char().copied() {
                 match previous_char {
                     // dot is not allowed as start of path
                     '.' | '(' | '{' | '&' | ',' => self.inner.incorrect_dot(),
@@ -45,7 +45,7 @@ impl DataPathParser {
     fn consume_in_memory_chars_before_end(&mut self) -> VoidR {
         if self.inner.in_memory_chars_is_empty() {
             // check the previous char
-            if let Some(previous_char) = self.inner.previous_char() {
+            if let Some(previous_char) = self.inner.previous_char().copied() {
                 match previous_char {
                     // previous char is start of something, and not ends yet
                     '.' | '(' | '{' | '&' | ',' => {
@@ -75,7 +75,17 @@ impl DataPathParser {
     /// - after last [.], cannot be blank,
     /// - for the literal concat function, functions that are not wrapped in `{}` are not allowed to appear.
     ///   e.g. [a.&len {b.len}] is not allowed.
+    ///
+    /// segments are simplified after parsed, see [DataPathSimplifier].
     pub fn parse(&mut self) -> VoidR {
+        self.parse_as_is()?;
+        DataPathSimplifier::simplify(&mut self.segments);
+
+        Ok(())
+    }
+
+    /// same as [parse], but segments are kept as they are written.
+    pub fn parse_as_is(&mut self) -> VoidR {
         loop {
             if let Some(char) = self.inner.current_char() {
                 match char {
@@ -104,8 +114,6 @@ impl DataPathParser {
             }
         }
 
-        // TODO staticize and simplify data path
-
         Ok(())
     }
 
@@ -113,7 +121,7 @@ impl DataPathParser {
     fn consume_in_memory_chars_before_right_brace(&mut self) -> VoidR {
         if self.inner.in_memory_chars_is_empty() {
             // check the previous char
-            if let Some(previous_char) = self.inner.previous_char() {
+            if let Some(previous_char) = self.inner.previous_char().copied() {
                 match previous_char {
                     // previous char is start of something, and not ends yet
                     '.' | '(' | '&' | ',' => self.inner.incorrect_right_brace(),
@@ -178,7 +186,7 @@ impl DataPathParser {
     fn consume_in_memory_chars_before_comma(&mut self) -> VoidR {
         if self.inner.in_memory_chars_is_empty() {
             // check the previous char
-            if let Some(previous_char) = self.inner.previous_char() {
+            if let Some(previous_char) = self.inner.previous_char().copied() {
                 match previous_char {
                     // previous char is start of something, and not ends yet
                     '.' | '(' | '{' | '&' | ',' => self.inner.incorrect_comma(),
@@ -201,7 +209,7 @@ impl DataPathParser {
     fn consume_in_memory_chars_before_right_parenthesis(&mut self) -> VoidR {
         if self.inner.in_memory_chars_is_empty() {
             // check the previous char
-            if let Some(previous_char) = self.inner.previous_char() {
+            if let Some(previous_char) = self.inner.previous_char().copied() {
                 match previous_char {
                     // previous char is start of something, and not ends yet
                     '.' | '{' | '&' | ',' => self.inner.incorrect_right_parenthesis(),
@@ -1,5 +1,5 @@
 use crate::{DataPathSegment, FuncDataPath, ParserInnerState};
-use elf_model::VariablePredefineFunctions;
+use elf_model::{TenantId, VariablePredefineFunctions};
 use std::sync::Arc;
 
 pub struct DataPathParser {
@@ -14,6 +14,14 @@ impl DataPathParser {
             segments: vec![],
         }
     }
+
+    /// functions registered for given tenant are resolved, besides the predefined ones.
+    pub fn by_path_of_tenant(all_chars: Arc<Vec<char>>, tenant_id: Arc<TenantId>) -> Self {
+        DataPathParser {
+            inner: ParserInnerState::new(all_chars).with_tenant_id(tenant_id),
+            segments: vec![],
+        }
+    }
 }
 
 /// segments operations
@@ -25,8 +33,8 @@ impl DataPathParser {
     pub fn pop_last_concat_function(&mut self) -> Option<FuncDataPath> {
         if let Some(last_segment) = self.segments.last() {
             match last_segment {
-                DataPathSegment::Func(data_path) => match data_path.func() {
-                    VariablePredefineFunctions::Concat => {
+                DataPathSegment::Func(data_path) => match data_path.predefined_func() {
+                    Some(VariablePredefineFunctions::Concat) => {
                         if let Some(segment) = self.segments.pop() {
                             match segment {
                                 DataPathSegment::Func(data_path) => Some(data_path),
@@ -2,6 +2,7 @@ use std::fmt::{Display, Formatter};
 use std::sync::Arc;
 
 /// path string with start and end index in the full string
+#[derive(Clone)]
 pub struct PathStr {
     full_path: Arc<Vec<char>>,
     start_index: usize,
@@ -41,6 +42,15 @@ impl PathStr {
         }
     }
 
+    /// path from start of this to end of given, given must be part of the same full path
+    pub fn extend_to(&self, end: &PathStr) -> Self {
+        PathStr {
+            full_path: self.full_path.clone(),
+            start_index: self.start_index,
+            end_index: end.end_index,
+        }
+    }
+
     /// get string path
     pub fn this(&self) -> String {
         self.full_path[self.start_index..self.end_index]
@@ -0,0 +1,305 @@
+use crate::{ArcTopicDataValue, PipelineKernelErrorCode};
+use bigdecimal::BigDecimal;
+use elf_base::{ErrorCode, NumericUtils, StdR};
+use elf_model::{
+    Bucket, BucketId, CategorySegment, NumericValueSegment, RangeBucketValueIncluding,
+    OTHER_CATEGORY_SEGMENT_VALUE,
+};
+use std::ops::Deref;
+
+pub struct NumericBucketSegment {
+    pub name: String,
+    /// no lower bound when not declared
+    pub min: Option<BigDecimal>,
+    /// no upper bound when not declared
+    pub max: Option<BigDecimal>,
+}
+
+pub struct CategoryBucketSegment {
+    pub name: String,
+    pub values: Vec<String>,
+}
+
+pub enum IndicatorBucketSegments {
+    /// numeric value bucket and numeric value measure bucket
+    Numeric {
+        include: RangeBucketValueIncluding,
+        segments: Vec<NumericBucketSegment>,
+    },
+    /// category measure bucket and enum measure bucket
+    Category {
+        segments: Vec<CategoryBucketSegment>,
+        /// index of segment which contains [OTHER_CATEGORY_SEGMENT_VALUE]
+        others: Option<usize>,
+    },
+}
+
+/// bucket which maps value to the segment it falls in.
+pub struct IndicatorBucket {
+    bucket_id: BucketId,
+    name: String,
+    segments: IndicatorBucketSegments,
+}
+
+impl IndicatorBucket {
+    pub fn compile(bucket: Bucket) -> StdR<Self> {
+        let (bucket_id, name, segments) = match bucket {
+            Bucket::NumericValue(bucket) => (
+                bucket.bucket_id,
+                bucket.name,
+                Self::numeric(bucket.include, bucket.segments)?,
+            ),
+            Bucket::NumericValueMeasure(bucket) => (
+                bucket.bucket_id,
+                bucket.name,
+                Self::numeric(bucket.include, bucket.segments)?,
+            ),
+            Bucket::CategoryMeasure(bucket) => (
+                bucket.bucket_id,
+                bucket.name,
+                Self::category(bucket.segments),
+            ),
+            Bucket::EnumMeasure(bucket) => (
+                bucket.bucket_id,
+                bucket.name,
+                Self::category(bucket.segments),
+            ),
+        };
+        let bucket_id = bucket_id.unwrap_or_default();
+        Ok(Self {
+            name: name.unwrap_or_else(|| bucket_id.clone()),
+            bucket_id,
+            segments,
+        })
+    }
+
+    /// include min by default
+    fn numeric(
+        include: Option<RangeBucketValueIncluding>,
+        segments: Option<Vec<NumericValueSegment>>,
+    ) -> StdR<IndicatorBucketSegments> {
+        let mut compiled = vec![];
+        for segment in segments.unwrap_or_default() {
+            let (min, max) = match segment.value {
+                Some(value) => (
+                    Self::bound_of(value.min, &segment.name)?,
+                    Self::bound_of(value.max, &segment.name)?,
+                ),
+                _ => (None, None),
+            };
+            compiled.push(NumericBucketSegment {
+                name: segment.name.unwrap_or_default(),
+                min,
+                max,
+            });
+        }
+        Ok(IndicatorBucketSegments::Numeric {
+            include: include.unwrap_or(RangeBucketValueIncluding::IncludeMin),
+            segments: compiled,
+        })
+    }
+
+    fn bound_of(bound: Option<String>, name: &Option<String>) -> StdR<Option<BigDecimal>> {
+        match bound {
+            Some(bound) if !bound.trim().is_empty() => {
+                match bound.trim().to_string().to_decimal() {
+                    Ok(bound) => Ok(Some(bound)),
+                    _ => PipelineKernelErrorCode::BucketSegmentValueNotNumeric.msg(format!(
+                        "Bound[{}] of segment[{}] is not numeric.",
+                        bound,
+                        name.as_deref().unwrap_or("")
+                    )),
+                }
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn category(segments: Option<Vec<CategorySegment>>) -> IndicatorBucketSegments {
+        let segments: Vec<CategoryBucketSegment> = segments
+            .unwrap_or_default()
+            .into_iter()
+            .map(|segment| CategoryBucketSegment {
+                name: segment.name.unwrap_or_default(),
+                values: segment.value.unwrap_or_default(),
+            })
+            .collect();
+        let others = segments.iter().position(|segment| {
+            segment
+                .values
+                .iter()
+                .any(|value| value == OTHER_CATEGORY_SEGMENT_VALUE)
+        });
+        IndicatorBucketSegments::Category { segments, others }
+    }
+}
+
+impl IndicatorBucket {
+    pub fn bucket_id(&self) -> &BucketId {
+        &self.bucket_id
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn segment_name(&self, index: usize) -> &String {
+        match &self.segments {
+            IndicatorBucketSegments::Numeric { segments, .. } => &segments[index].name,
+            IndicatorBucketSegments::Category { segments, .. } => &segments[index].name,
+        }
+    }
+
+    /// index of segment by given name
+    pub fn segment_index(&self, name: &String) -> Option<usize> {
+        match &self.segments {
+            IndicatorBucketSegments::Numeric { segments, .. } => {
+                segments.iter().position(|segment| &segment.name == name)
+            }
+            IndicatorBucketSegments::Category { segments, .. } => {
+                segments.iter().position(|segment| &segment.name == name)
+            }
+        }
+    }
+
+    /// index of segment which given value falls in, none when falls in no segment.
+    /// first matched segment is used when segments are overlapped.
+    pub fn segment_of(&self, value: &ArcTopicDataValue) -> Option<usize> {
+        match &self.segments {
+            IndicatorBucketSegments::Numeric { include, segments } => {
+                let value = Self::decimal_of(value)?;
+                segments.iter().position(|segment| {
+                    let above_min = match &segment.min {
+                        Some(min) if matches!(include, RangeBucketValueIncluding::IncludeMin) => {
+                            &value >= min
+                        }
+                        Some(min) => &value > min,
+                        _ => true,
+                    };
+                    let below_max = match &segment.max {
+                        Some(max) if matches!(include, RangeBucketValueIncluding::IncludeMax) => {
+                            &value <= max
+                        }
+                        Some(max) => &value < max,
+                        _ => true,
+                    };
+                    above_min && below_max
+                })
+            }
+            IndicatorBucketSegments::Category { segments, others } => {
+                let value = Self::text_of(value)?;
+                segments
+                    .iter()
+                    .position(|segment| segment.values.contains(&value))
+                    .or(*others)
+            }
+        }
+    }
+
+    fn decimal_of(value: &ArcTopicDataValue) -> Option<BigDecimal> {
+        match value {
+            ArcTopicDataValue::Num(num) => Some(num.deref().clone()),
+            ArcTopicDataValue::Str(str) if str.is_numeric() => str.to_decimal().ok(),
+            _ => None,
+        }
+    }
+
+    fn text_of(value: &ArcTopicDataValue) -> Option<String> {
+        match value {
+            ArcTopicDataValue::Str(str) if !str.is_empty() => Some(str.deref().clone()),
+            ArcTopicDataValue::Num(num) => Some(num.normalized().to_string()),
+            ArcTopicDataValue::Bool(bool) => Some(bool.to_string()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ArcFrom, ArcTopicDataValue, IndicatorBucket};
+    use bigdecimal::BigDecimal;
+    use elf_model::{
+        CategoryMeasureBucket, CategorySegment, NumericSegmentValue, NumericValueBucket,
+        NumericValueSegment, RangeBucketValueIncluding,
+    };
+    use std::sync::Arc;
+
+    fn segment(name: &str, min: Option<&str>, max: Option<&str>) -> NumericValueSegment {
+        let mut value = NumericSegmentValue::new();
+        value.min = min.map(|min| min.to_string());
+        value.max = max.map(|max| max.to_string());
+        NumericValueSegment::new()
+            .name(name.to_string())
+            .value(value)
+    }
+
+    fn num(value: i32) -> Arc<ArcTopicDataValue> {
+        ArcTopicDataValue::arc_from(BigDecimal::from(value))
+    }
+
+    #[test]
+    fn test_numeric() {
+        let segments = vec![
+            segment("low", None, Some("10")),
+            segment("middle", Some("10"), Some("20")),
+            segment("high", Some("20"), None),
+        ];
+        let bucket = IndicatorBucket::compile(
+            NumericValueBucket::init()
+                .bucket_id("b1".to_string())
+                .segments(segments)
+                .to_bucket(),
+        )
+        .unwrap();
+        assert_eq!(bucket.name(), "b1");
+        assert_eq!(bucket.segment_of(&num(9)), Some(0));
+        assert_eq!(bucket.segment_of(&num(10)), Some(1));
+        assert_eq!(bucket.segment_of(&num(20)), Some(2));
+        assert_eq!(
+            bucket.segment_of(&ArcTopicDataValue::arc_from("15".to_string())),
+            Some(1)
+        );
+        assert_eq!(bucket.segment_of(&ArcTopicDataValue::None), None);
+
+        let segments = vec![
+            segment("low", None, Some("10")),
+            segment("middle", Some("10"), Some("20")),
+        ];
+        let bucket = IndicatorBucket::compile(
+            NumericValueBucket::init()
+                .bucket_id("b2".to_string())
+                .include(RangeBucketValueIncluding::IncludeMax)
+                .segments(segments)
+                .to_bucket(),
+        )
+        .unwrap();
+        assert_eq!(bucket.segment_of(&num(10)), Some(0));
+        assert_eq!(bucket.segment_of(&num(20)), Some(1));
+        assert_eq!(bucket.segment_of(&num(21)), None);
+    }
+
+    #[test]
+    fn test_category() {
+        let bucket = IndicatorBucket::compile(
+            CategoryMeasureBucket::with_gender()
+                .bucket_id("b1".to_string())
+                .name("gender".to_string())
+                .segments(vec![
+                    CategorySegment::new()
+                        .name("female".to_string())
+                        .value(vec!["F".to_string()]),
+                    CategorySegment::new()
+                        .name("others".to_string())
+                        .value(vec!["&others".to_string()]),
+                ])
+                .to_bucket(),
+        )
+        .unwrap();
+        assert_eq!(bucket.name(), "gender");
+        let str = |s: &str| ArcTopicDataValue::arc_from(s.to_string());
+        assert_eq!(bucket.segment_of(&str("F")), Some(0));
+        assert_eq!(bucket.segment_of(&str("M")), Some(1));
+        assert_eq!(bucket.segment_name(1), "others");
+        assert_eq!(bucket.segment_of(&ArcTopicDataValue::None), None);
+    }
+}
@@ -0,0 +1,39 @@
+use crate::{
+    ArcTopicData, SubjectDataSource, SubjectDatasetPlan, SubjectJoiner, SubjectSourceCriteria,
+};
+use elf_base::StdR;
+use elf_runtime_model_kernel::TopicSchema;
+use std::sync::Arc;
+
+/// rows which indicator computed on, with the schema to locate factors of rows.
+/// - indicator on topic: schema is topic, rows are topic data,
+/// - indicator on subject: schema is subject, factors are columns, rows are subject dataset.
+pub struct IndicatorDataset {
+    schema: Arc<TopicSchema>,
+    rows: Vec<ArcTopicData>,
+}
+
+impl IndicatorDataset {
+    pub fn of_topic<S: SubjectDataSource>(schema: Arc<TopicSchema>, source: &S) -> StdR<Self> {
+        let rows = SubjectJoiner::rows_of(&schema, source, &SubjectSourceCriteria::all())?;
+        Ok(Self { schema, rows })
+    }
+
+    pub fn of_subject<S: SubjectDataSource>(plan: &SubjectDatasetPlan, source: &S) -> StdR<Self> {
+        let schema = plan.subject_schema().clone();
+        let rows = plan
+            .execute(source)?
+            .iter()
+            .map(|row| plan.data_of(row))
+            .collect();
+        Ok(Self { schema, rows })
+    }
+
+    pub fn schema(&self) -> &Arc<TopicSchema> {
+        &self.schema
+    }
+
+    pub fn rows(&self) -> &Vec<ArcTopicData> {
+        &self.rows
+    }
+}
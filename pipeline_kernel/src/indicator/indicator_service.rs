@@ -0,0 +1,544 @@
+use crate::{
+    ArcTopicData, ArcTopicDataValue, CompiledParameterJoint, DataPath, InMemoryData,
+    IndicatorBucket, IndicatorDataset, PipelineKernelErrorCode, SubjectAggregator,
+    SubjectDatasetEngine, SubjectDatasetPlan, TopicStorageDataSource,
+};
+use chrono::{NaiveDateTime, NaiveTime};
+use elf_base::{DateTimeUtils, ErrorCode, StdR, StringUtils};
+use elf_model::{
+    Bucket, BucketId, DataResult, DataResultSetCell, FactorOrSubjectDatasetColumnId, Indicator,
+    IndicatorAggregateArithmetic, IndicatorBaseOn, IndicatorCriteria, IndicatorId, ParameterJoint,
+    SubjectColumnArithmetic, TenantId,
+};
+use elf_runtime_model_kernel::{
+    ArcParameterJoint, BucketMetaProvider, IndicatorMetaProvider, SubjectMetaProvider,
+    SubjectService, TopicSchema, TopicSchemaProvider, TopicService,
+};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+/// values of rows, grouped by the segment indexes of buckets
+type IndicatorGroups = BTreeMap<Vec<usize>, Vec<Arc<ArcTopicDataValue>>>;
+/// aggregated value of groups
+type IndicatorAggregated = Vec<(Vec<usize>, Arc<ArcTopicDataValue>)>;
+
+/// compute value of indicator in time range, filtered and grouped by buckets.
+/// result is tabular, columns are names of buckets and name of indicator,
+/// each row is a combination of segments, in the order of segments declared in buckets.
+/// rows which fall in no segment of any bucket are ignored.
+pub struct IndicatorService;
+
+impl IndicatorMetaProvider for IndicatorService {}
+
+impl BucketMetaProvider for IndicatorService {}
+
+impl IndicatorService {
+    /// indicator and buckets are found by id, rows are read from topic storage.
+    pub fn compute(criteria: IndicatorCriteria, tenant_id: &TenantId) -> StdR<DataResult> {
+        let indicator = Self::find_indicator(&criteria, tenant_id)?;
+        let dataset = Self::dataset_of(&indicator, tenant_id)?;
+        Self::compute_on(
+            indicator,
+            criteria,
+            |bucket_id| Self::find_bucket(bucket_id, tenant_id),
+            &dataset,
+        )
+    }
+
+    fn find_indicator(criteria: &IndicatorCriteria, tenant_id: &TenantId) -> StdR<Indicator> {
+        let Some(indicator_id) = criteria
+            .indicator_id
+            .as_ref()
+            .filter(|id| id.is_not_blank())
+        else {
+            return PipelineKernelErrorCode::IndicatorIdMissed
+                .msg("Indicator id is required for computing indicator.");
+        };
+        Self::find_indicator_by_id(indicator_id, tenant_id)
+    }
+
+    pub fn find_indicator_by_id(
+        indicator_id: &IndicatorId,
+        tenant_id: &TenantId,
+    ) -> StdR<Indicator> {
+        match Self::meta()?.find_by_id(indicator_id, tenant_id)? {
+            Some(indicator) => Ok(indicator),
+            _ => PipelineKernelErrorCode::IndicatorNotFound
+                .msg(format!("Indicator[{}] not found.", indicator_id)),
+        }
+    }
+
+    pub fn find_bucket(bucket_id: &BucketId, tenant_id: &TenantId) -> StdR<Bucket> {
+        match Self::bucket()?.find_by_id(bucket_id, tenant_id)? {
+            Some(bucket) => Ok(bucket),
+            _ => PipelineKernelErrorCode::BucketNotFound
+                .msg(format!("Bucket[{}] not found.", bucket_id)),
+        }
+    }
+
+    /// rows of topic or subject which indicator based on, read from topic storage.
+    pub fn dataset_of(indicator: &Indicator, tenant_id: &TenantId) -> StdR<IndicatorDataset> {
+        let source = TopicStorageDataSource;
+        let topic_or_subject_id = indicator.topic_or_subject_id.clone().unwrap_or_default();
+        match &indicator.base_on {
+            Some(IndicatorBaseOn::Topic) => IndicatorDataset::of_topic(
+                TopicService::schema()?.by_id(&topic_or_subject_id, tenant_id)?,
+                &source,
+            ),
+            Some(IndicatorBaseOn::Subject) => {
+                let Some(subject) =
+                    SubjectService::meta()?.find_by_id(&topic_or_subject_id, tenant_id)?
+                else {
+                    return PipelineKernelErrorCode::SubjectNotFound
+                        .msg(format!("Subject[{}] not found.", topic_or_subject_id));
+                };
+                let plan = SubjectDatasetPlan::compile(subject, |topic_id| {
+                    TopicService::schema()?.by_id(topic_id, tenant_id)
+                })?;
+                IndicatorDataset::of_subject(&plan, &source)
+            }
+            _ => PipelineKernelErrorCode::IndicatorBaseOnMissed.msg(format!(
+                "Indicator[{}] must be based on topic or subject.",
+                indicator.indicator_id.as_deref().unwrap_or("")
+            )),
+        }
+    }
+
+    /// compute on given dataset, which must be the topic or subject of indicator.
+    pub fn compute_on<FindBucket>(
+        indicator: Indicator,
+        criteria: IndicatorCriteria,
+        find_bucket: FindBucket,
+        dataset: &IndicatorDataset,
+    ) -> StdR<DataResult>
+    where
+        FindBucket: Fn(&BucketId) -> StdR<Bucket>,
+    {
+        let name = [&indicator.name, &indicator.indicator_id]
+            .into_iter()
+            .flatten()
+            .find(|name| name.is_not_blank())
+            .cloned()
+            .unwrap_or_else(|| String::from("value"));
+        let (buckets, groups) =
+            Self::aggregate(indicator, criteria, find_bucket, dataset, |_| Ok(true))?;
+
+        let data = groups
+            .into_iter()
+            .map(|(key, value)| {
+                let mut row: Vec<DataResultSetCell> = key
+                    .iter()
+                    .zip(buckets.iter())
+                    .map(|(index, bucket)| {
+                        DataResultSetCell::Str(bucket.segment_name(*index).clone())
+                    })
+                    .collect();
+                row.push(SubjectDatasetEngine::cell_of(&value));
+                row
+            })
+            .collect();
+        let mut columns: Vec<String> = buckets.iter().map(|bucket| bucket.name().clone()).collect();
+        columns.push(name);
+        Ok(DataResult::new().columns(columns).data(data))
+    }
+
+    /// value of indicator on given dataset, no bucket.
+    /// rows are accepted by given function additionally.
+    pub fn value_on<Accept>(
+        indicator: Indicator,
+        dataset: &IndicatorDataset,
+        accept: Accept,
+    ) -> StdR<Arc<ArcTopicDataValue>>
+    where
+        Accept: Fn(&ArcTopicData) -> StdR<bool>,
+    {
+        let (_, groups) = Self::aggregate(
+            indicator,
+            IndicatorCriteria::new(),
+            |bucket_id| {
+                PipelineKernelErrorCode::BucketNotFound
+                    .msg(format!("Bucket[{}] not found.", bucket_id))
+            },
+            dataset,
+            accept,
+        )?;
+        // always has one group when there is no bucket
+        Ok(groups
+            .into_iter()
+            .next()
+            .map(|(_, value)| value)
+            .unwrap_or_else(|| Arc::new(ArcTopicDataValue::None)))
+    }
+
+    /// returns compiled buckets, and aggregated value of each group.
+    /// groups are keyed by the segment indexes of buckets, in the order of segments.
+    fn aggregate<FindBucket, Accept>(
+        indicator: Indicator,
+        criteria: IndicatorCriteria,
+        find_bucket: FindBucket,
+        dataset: &IndicatorDataset,
+        accept: Accept,
+    ) -> StdR<(Vec<IndicatorBucket>, IndicatorAggregated)>
+    where
+        FindBucket: Fn(&BucketId) -> StdR<Bucket>,
+        Accept: Fn(&ArcTopicData) -> StdR<bool>,
+    {
+        let schema = dataset.schema();
+        let value_path = match &indicator.factor_id {
+            Some(factor_id) if factor_id.is_not_blank() => Some(Self::path_of(schema, factor_id)?),
+            _ => None,
+        };
+        let arithmetic = Self::arithmetic_of(value_path.is_some(), &indicator.aggregate_arithmetic);
+        let time_path = match &criteria.time_factor_id {
+            Some(factor_id) if factor_id.is_not_blank() => Some(Self::path_of(schema, factor_id)?),
+            _ => None,
+        };
+
+        let mut buckets = vec![];
+        let mut bucket_paths = vec![];
+        for on in criteria.buckets.unwrap_or_default() {
+            let Some(bucket_id) = on.bucket_id.filter(|id| id.is_not_blank()) else {
+                return PipelineKernelErrorCode::BucketNotFound
+                    .msg("Bucket id is required for grouping indicator.");
+            };
+            let bucket = IndicatorBucket::compile(find_bucket(&bucket_id)?)?;
+            let factor_id = match on.factor_id.filter(|id| id.is_not_blank()) {
+                Some(factor_id) => factor_id,
+                _ => match &indicator.factor_id {
+                    Some(factor_id) if factor_id.is_not_blank() => factor_id.clone(),
+                    _ => {
+                        return PipelineKernelErrorCode::BucketFactorMissed.msg(format!(
+                            "Factor of bucket[{}] is required, since indicator has no factor.",
+                            bucket_id
+                        ));
+                    }
+                },
+            };
+            buckets.push(bucket);
+            bucket_paths.push(Self::path_of(schema, &factor_id)?);
+        }
+
+        let mut conditions = vec![];
+        if let Some(filter) = indicator.filter
+            && filter.enabled.unwrap_or(false)
+            && let Some(joint) = filter.joint
+        {
+            conditions.push(joint.to_condition());
+        }
+        if let Some(joint) = criteria.filter {
+            conditions.push(joint.to_condition());
+        }
+        let filter = if conditions.is_empty() {
+            None
+        } else {
+            let mut topic_schemas = HashMap::new();
+            topic_schemas.insert(schema.topic_id().clone(), schema.clone());
+            Some(CompiledParameterJoint::compile(
+                &ArcParameterJoint::new(ParameterJoint::and(conditions))?,
+                &mut topic_schemas,
+                schema.tenant_id(),
+            )?)
+        };
+
+        let mut groups = IndicatorGroups::new();
+        if buckets.is_empty() {
+            // always has one row when there is no bucket
+            groups.insert(vec![], vec![]);
+        }
+        'rows: for row in dataset.rows() {
+            let mut in_memory_data = InMemoryData::new(None, Some(row.clone()));
+            if let Some(time_path) = &time_path {
+                let time = in_memory_data.current_only().value_of(time_path)?;
+                if !Self::in_time_range(&time, &criteria.start_time, &criteria.end_time)? {
+                    continue;
+                }
+            }
+            if let Some(filter) = &filter
+                && !filter.is_true(&mut in_memory_data)?
+            {
+                continue;
+            }
+            if !accept(row)? {
+                continue;
+            }
+            let mut key = vec![];
+            for (bucket, path) in buckets.iter().zip(bucket_paths.iter()) {
+                let value = in_memory_data.current_only().value_of(path)?;
+                match bucket.segment_of(&value) {
+                    Some(index) => key.push(index),
+                    _ => continue 'rows,
+                }
+            }
+            let value = match &value_path {
+                Some(value_path) => in_memory_data.current_only().value_of(value_path)?,
+                // count rows
+                _ => Arc::new(ArcTopicDataValue::Bool(true)),
+            };
+            groups.entry(key).or_default().push(value);
+        }
+
+        let mut aggregated = vec![];
+        for (key, values) in groups {
+            let value = SubjectAggregator::apply(&arithmetic, values.iter().collect())?;
+            aggregated.push((key, value));
+        }
+        Ok((buckets, aggregated))
+    }
+
+    /// path of factor (or column) of topic (or subject)
+    pub fn path_of(
+        schema: &TopicSchema,
+        factor_id: &FactorOrSubjectDatasetColumnId,
+    ) -> StdR<DataPath> {
+        match schema.factor_by_id(factor_id) {
+            Some(factor) => DataPath::from_factor(factor, schema),
+            _ => PipelineKernelErrorCode::IndicatorFactorNotFound.msg(format!(
+                "Factor[{}] not found in topic or subject[{}].",
+                factor_id,
+                schema.topic_id()
+            )),
+        }
+    }
+
+    /// count when there is no factor, or no arithmetic declared
+    fn arithmetic_of(
+        has_factor: bool,
+        arithmetic: &Option<IndicatorAggregateArithmetic>,
+    ) -> SubjectColumnArithmetic {
+        if !has_factor {
+            return SubjectColumnArithmetic::Count;
+        }
+        match arithmetic {
+            Some(IndicatorAggregateArithmetic::DistinctCount) => {
+                SubjectColumnArithmetic::DistinctCount
+            }
+            Some(IndicatorAggregateArithmetic::Sum) => SubjectColumnArithmetic::Sum,
+            Some(IndicatorAggregateArithmetic::Avg) => SubjectColumnArithmetic::Avg,
+            Some(IndicatorAggregateArithmetic::Max) => SubjectColumnArithmetic::Max,
+            Some(IndicatorAggregateArithmetic::Min) => SubjectColumnArithmetic::Min,
+            _ => SubjectColumnArithmetic::Count,
+        }
+    }
+
+    /// [start, end), row is excluded when time is none
+    fn in_time_range(
+        value: &ArcTopicDataValue,
+        start: &Option<NaiveDateTime>,
+        end: &Option<NaiveDateTime>,
+    ) -> StdR<bool> {
+        let time = match value {
+            ArcTopicDataValue::None => return Ok(false),
+            ArcTopicDataValue::DateTime(datetime) => **datetime,
+            ArcTopicDataValue::Date(date) => date.and_time(NaiveTime::MIN),
+            ArcTopicDataValue::Str(str) if str.is_empty() => return Ok(false),
+            ArcTopicDataValue::Str(str) => str.to_datetime_loose()?,
+            other => {
+                return PipelineKernelErrorCode::IndicatorTimeValueNotDatetime.msg(format!(
+                    "Time value[{}] of indicator is not a datetime.",
+                    other
+                ));
+            }
+        };
+        Ok(start.is_none_or(|start| time >= start) && end.is_none_or(|end| time < end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{IndicatorDataset, IndicatorService, SubjectDataSource, SubjectSourceCriteria};
+    use bigdecimal::BigDecimal;
+    use chrono::NaiveDate;
+    use elf_base::StdR;
+    use elf_model::{
+        CategoryMeasureBucket, CategorySegment, ConstantParameter, DataResult, DataResultSetCell,
+        Factor, FactorType, Indicator, IndicatorAggregateArithmetic, IndicatorBaseOn,
+        IndicatorCriteria, IndicatorCriteriaOnBucket, MoreThanExpression, NumericSegmentValue,
+        NumericValueBucket, NumericValueSegment, ParameterJoint, ParameterKind, Topic, TopicData,
+        TopicDataValue, TopicFactorParameter, TopicKind, TopicType,
+    };
+    use elf_runtime_model_kernel::TopicSchema;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    struct InMemorySource {
+        rows: Vec<TopicData>,
+    }
+
+    impl SubjectDataSource for InMemorySource {
+        fn rows(
+            &self,
+            _topic: &Arc<TopicSchema>,
+            _criteria: &SubjectSourceCriteria,
+        ) -> StdR<Vec<TopicData>> {
+            Ok(self.rows.clone())
+        }
+    }
+
+    fn dataset() -> IndicatorDataset {
+        let topic = Topic::new()
+            .topic_id(String::from("sales"))
+            .name(String::from("sales"))
+            .r#type(TopicType::Raw)
+            .kind(TopicKind::Business)
+            .factors(vec![
+                Factor::new()
+                    .factor_id(String::from("s1"))
+                    .name(String::from("region"))
+                    .r#type(FactorType::Text),
+                Factor::new()
+                    .factor_id(String::from("s2"))
+                    .name(String::from("amount"))
+                    .r#type(FactorType::Number),
+                Factor::new()
+                    .factor_id(String::from("s3"))
+                    .name(String::from("soldDate"))
+                    .r#type(FactorType::Date),
+            ])
+            .tenant_id(String::from("tenant-1"));
+        let sale = |region: &str, amount: i32, month: u32, day: u32| {
+            let mut data = HashMap::new();
+            data.insert(
+                String::from("region"),
+                TopicDataValue::Str(region.to_string()),
+            );
+            data.insert(
+                String::from("amount"),
+                TopicDataValue::Num(BigDecimal::from(amount)),
+            );
+            data.insert(
+                String::from("soldDate"),
+                TopicDataValue::Date(NaiveDate::from_ymd_opt(2026, month, day).unwrap()),
+            );
+            data
+        };
+        let source = InMemorySource {
+            rows: vec![
+                sale("east", 5, 1, 5),
+                sale("east", 15, 1, 20),
+                sale("west", 25, 2, 3),
+                sale("west", 12, 1, 10),
+                sale("north", 30, 1, 11),
+            ],
+        };
+        IndicatorDataset::of_topic(Arc::new(TopicSchema::new(topic).unwrap()), &source).unwrap()
+    }
+
+    fn criteria() -> IndicatorCriteria {
+        IndicatorCriteria::new()
+            .indicator_id(String::from("i1"))
+            .time_factor_id(String::from("s3"))
+            .start_time(
+                NaiveDate::from_ymd_opt(2026, 1, 1)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap(),
+            )
+            .end_time(
+                NaiveDate::from_ymd_opt(2026, 2, 1)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap(),
+            )
+    }
+
+    fn texts(result: DataResult) -> Vec<String> {
+        result
+            .data
+            .unwrap()
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| match cell {
+                        DataResultSetCell::Str(str) => str.clone(),
+                        DataResultSetCell::Num(num) => num.to_plain_string(),
+                        other => format!("{:?}", other),
+                    })
+                    .collect::<Vec<String>>()
+                    .join(",")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_buckets() {
+        let indicator = Indicator::new()
+            .indicator_id(String::from("i1"))
+            .name(String::from("sales"))
+            .topic_or_subject_id(String::from("sales"))
+            .base_on(IndicatorBaseOn::Topic)
+            .factor_id(String::from("s2"))
+            .aggregate_arithmetic(IndicatorAggregateArithmetic::Sum);
+        let segment = |name: &str, min: Option<&str>, max: Option<&str>| {
+            let mut value = NumericSegmentValue::new();
+            value.min = min.map(|min| min.to_string());
+            value.max = max.map(|max| max.to_string());
+            NumericValueSegment::new()
+                .name(name.to_string())
+                .value(value)
+        };
+        let criteria = criteria().buckets(vec![
+            IndicatorCriteriaOnBucket::new().bucket_id(String::from("b1")),
+            IndicatorCriteriaOnBucket::new()
+                .bucket_id(String::from("b2"))
+                .factor_id(String::from("s1")),
+        ]);
+        let result = IndicatorService::compute_on(
+            indicator,
+            criteria,
+            |bucket_id| match bucket_id.as_str() {
+                "b1" => Ok(NumericValueBucket::init()
+                    .bucket_id(String::from("b1"))
+                    .name(String::from("level"))
+                    .segments(vec![
+                        segment("low", None, Some("10")),
+                        segment("high", Some("10"), None),
+                    ])
+                    .to_bucket()),
+                _ => Ok(CategoryMeasureBucket::init()
+                    .bucket_id(String::from("b2"))
+                    .name(String::from("area"))
+                    .segments(vec![
+                        CategorySegment::new()
+                            .name(String::from("E"))
+                            .value(vec![String::from("east")]),
+                        CategorySegment::new()
+                            .name(String::from("O"))
+                            .value(vec![String::from("&others")]),
+                    ])
+                    .to_bucket()),
+            },
+            &dataset(),
+        )
+        .unwrap();
+        assert_eq!(
+            result.columns.clone().unwrap(),
+            vec!["level", "area", "sales"]
+        );
+        assert_eq!(texts(result), vec!["low,E,5", "high,E,15", "high,O,42"]);
+    }
+
+    #[test]
+    fn test_count() {
+        let indicator = Indicator::new()
+            .indicator_id(String::from("i1"))
+            .topic_or_subject_id(String::from("sales"))
+            .base_on(IndicatorBaseOn::Topic);
+        let criteria = criteria().filter(ParameterJoint::and(vec![MoreThanExpression::init()
+            .left(
+                TopicFactorParameter {
+                    kind: Some(ParameterKind::Topic),
+                    topic_id: Some(String::from("sales")),
+                    factor_id: Some(String::from("s2")),
+                }
+                .to_parameter(),
+            )
+            // plain constant is a path, concat with empty to get literal
+            .right(ConstantParameter::of(String::from("10{}")).to_parameter())
+            .to_condition()]));
+        let result =
+            IndicatorService::compute_on(indicator, criteria, |_| panic!("no bucket"), &dataset())
+                .unwrap();
+        assert_eq!(result.columns.clone().unwrap(), vec!["i1"]);
+        assert_eq!(texts(result), vec!["3"]);
+    }
+}
@@ -0,0 +1,7 @@
+mod indicator_bucket;
+mod indicator_dataset;
+mod indicator_service;
+
+pub use indicator_bucket::*;
+pub use indicator_dataset::*;
+pub use indicator_service::*;
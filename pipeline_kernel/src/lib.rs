@@ -1,11 +1,29 @@
+mod analysis;
+mod catalog;
 mod common;
 mod data_operation;
 mod data_path;
+mod indicator;
+mod lineage;
+mod objective;
 mod pipeline;
+mod report;
+mod snapshot;
+mod subject;
+mod subscription;
 mod topic;
 
+pub use analysis::*;
+pub use catalog::*;
 pub use common::*;
 pub use data_operation::*;
 pub use data_path::*;
+pub use indicator::*;
+pub use lineage::*;
+pub use objective::*;
 pub use pipeline::*;
+pub use report::*;
+pub use snapshot::*;
+pub use subject::*;
+pub use subscription::*;
 pub use topic::*;
@@ -0,0 +1,170 @@
+use crate::{LineageImpact, LineageNodeKey};
+use elf_model::{LineageGraph, LineageLink, LineageLinkType, LineageNode, PipelineId, TenantId};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone)]
+pub struct LineageEdge {
+    pub source: LineageNodeKey,
+    pub target: LineageNodeKey,
+    pub r#type: LineageLinkType,
+    /// the pipeline which creates this edge
+    pub pipeline_id: Option<PipelineId>,
+}
+
+/// directed lineage graph.
+/// nodes are sorted by key, and edges are sorted and deduplicated,
+/// so the exported graph is stable for same metadata.
+#[derive(Default)]
+pub struct DataLineage {
+    /// node key to node name
+    nodes: BTreeMap<LineageNodeKey, String>,
+    edges: BTreeSet<LineageEdge>,
+}
+
+impl DataLineage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn nodes(&self) -> &BTreeMap<LineageNodeKey, String> {
+        &self.nodes
+    }
+
+    pub fn edges(&self) -> &BTreeSet<LineageEdge> {
+        &self.edges
+    }
+
+    pub fn contains(&self, key: &LineageNodeKey) -> bool {
+        self.nodes.contains_key(key)
+    }
+
+    pub fn name_of(&self, key: &LineageNodeKey) -> Option<&String> {
+        self.nodes.get(key)
+    }
+
+    /// add node, or replace the name of existing node
+    pub fn add_node(&mut self, key: LineageNodeKey, name: String) {
+        self.nodes.insert(key, name);
+    }
+
+    /// node which is not added before is created with id as name,
+    /// it happens when the node is referred but not declared, such as topic not given.
+    pub fn add_edge(
+        &mut self,
+        source: LineageNodeKey,
+        target: LineageNodeKey,
+        r#type: LineageLinkType,
+        pipeline_id: Option<PipelineId>,
+    ) {
+        if !self.nodes.contains_key(&source) {
+            self.nodes.insert(source.clone(), source.id());
+        }
+        if !self.nodes.contains_key(&target) {
+            self.nodes.insert(target.clone(), target.id());
+        }
+        self.edges.insert(LineageEdge {
+            source,
+            target,
+            r#type,
+            pipeline_id,
+        });
+    }
+
+    /// edges will be followed when traverse from the given start node.
+    /// - start from factor, follow the factor level edges, which are direct, computed and used by,
+    /// - otherwise, follow the topic level edges, which are triggers, reads, writes and used by.
+    ///
+    /// contains is never followed, otherwise all factors of topic are involved.
+    fn follows(start: &LineageNodeKey, r#type: &LineageLinkType) -> bool {
+        match r#type {
+            LineageLinkType::Contains => false,
+            LineageLinkType::UsedBy => true,
+            LineageLinkType::Direct | LineageLinkType::Computed => start.is_factor(),
+            LineageLinkType::Triggers | LineageLinkType::Reads | LineageLinkType::Writes => {
+                !start.is_factor()
+            }
+        }
+    }
+
+    fn traverse(&self, start: &LineageNodeKey, downstream: bool) -> Vec<LineageNodeKey> {
+        let mut visited = BTreeSet::new();
+        let mut queue = VecDeque::new();
+        queue.push_back(start);
+        while let Some(key) = queue.pop_front() {
+            for edge in self.edges.iter() {
+                if !Self::follows(start, &edge.r#type) {
+                    continue;
+                }
+                let (from, to) = if downstream {
+                    (&edge.source, &edge.target)
+                } else {
+                    (&edge.target, &edge.source)
+                };
+                if from == key && to != start && visited.insert(to.clone()) {
+                    queue.push_back(to);
+                }
+            }
+        }
+        visited.into_iter().collect()
+    }
+
+    /// all nodes which flow to the given node, transitively.
+    pub fn upstream(&self, key: &LineageNodeKey) -> Vec<LineageNodeKey> {
+        self.traverse(key, false)
+    }
+
+    /// all nodes which the given node flows to, transitively.
+    pub fn downstream(&self, key: &LineageNodeKey) -> Vec<LineageNodeKey> {
+        self.traverse(key, true)
+    }
+
+    /// what is affected when the given node is dropped.
+    /// for topic, the downstream of all its factors are included.
+    pub fn impact(&self, key: &LineageNodeKey) -> LineageImpact {
+        let mut impact = LineageImpact::new(key.clone());
+        impact.add_all(self.downstream(key));
+        if let LineageNodeKey::Topic(_) = key {
+            self.edges
+                .iter()
+                .filter(|edge| edge.r#type == LineageLinkType::Contains && &edge.source == key)
+                .for_each(|edge| {
+                    impact.add(edge.target.clone());
+                    impact.add_all(self.downstream(&edge.target));
+                });
+        }
+        impact
+    }
+
+    pub fn to_graph(&self, tenant_id: &TenantId) -> LineageGraph {
+        LineageGraph::new()
+            .directed(true)
+            .multigraph(true)
+            .nodes(
+                self.nodes
+                    .iter()
+                    .map(|(key, name)| {
+                        LineageNode::new()
+                            .id(key.id())
+                            .r#type(key.node_type())
+                            .name(name.clone())
+                    })
+                    .collect(),
+            )
+            .links(
+                self.edges
+                    .iter()
+                    .map(|edge| {
+                        let link = LineageLink::new()
+                            .source(edge.source.id())
+                            .target(edge.target.id())
+                            .r#type(edge.r#type.clone());
+                        match &edge.pipeline_id {
+                            Some(pipeline_id) => link.pipeline_id(pipeline_id.clone()),
+                            _ => link,
+                        }
+                    })
+                    .collect(),
+            )
+            .tenant_id(tenant_id.clone())
+    }
+}
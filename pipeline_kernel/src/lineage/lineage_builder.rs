@@ -0,0 +1,322 @@
+use crate::{DataLineage, LineageNodeKey, LineagePipelineWalker, LineageRefsFinder};
+use elf_base::{StdR, VoidR};
+use elf_model::{Indicator, IndicatorBaseOn, LineageLinkType, Subject, TopicId};
+use elf_runtime_model_kernel::{ArcParameter, ArcParameterJoint, PipelineSchema, TopicSchema};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// build lineage from topics, pipelines, subjects and indicators.
+/// tuples are resolved on [build], so the order of adding is not matter.
+/// subjects and indicators which have no id are ignored.
+#[derive(Default)]
+pub struct LineageBuilder {
+    topics: HashMap<TopicId, Arc<TopicSchema>>,
+    pipelines: Vec<Arc<PipelineSchema>>,
+    subjects: Vec<Subject>,
+    indicators: Vec<Indicator>,
+}
+
+impl LineageBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn topic(mut self, topic: Arc<TopicSchema>) -> Self {
+        self.topics.insert(topic.topic_id().to_string(), topic);
+        self
+    }
+
+    pub fn pipeline(mut self, pipeline: Arc<PipelineSchema>) -> Self {
+        self.pipelines.push(pipeline);
+        self
+    }
+
+    pub fn subject(mut self, subject: Subject) -> Self {
+        self.subjects.push(subject);
+        self
+    }
+
+    pub fn indicator(mut self, indicator: Indicator) -> Self {
+        self.indicators.push(indicator);
+        self
+    }
+
+    pub fn build(self) -> StdR<DataLineage> {
+        let mut lineage = DataLineage::new();
+
+        for topic in self.topics.values() {
+            let topic_key = LineageNodeKey::Topic(topic.topic_id().to_string());
+            lineage.add_node(topic_key.clone(), topic.name().to_string());
+            for factor in topic.topic().factors.iter() {
+                let factor_key = LineageNodeKey::Factor(
+                    topic.topic_id().to_string(),
+                    factor.factor_id.to_string(),
+                );
+                lineage.add_node(factor_key.clone(), factor.name.to_string());
+                lineage.add_edge(
+                    topic_key.clone(),
+                    factor_key,
+                    LineageLinkType::Contains,
+                    None,
+                );
+            }
+        }
+
+        for pipeline in self.pipelines.iter() {
+            LineagePipelineWalker::new(pipeline, &self.topics).walk(&mut lineage)?;
+        }
+        for subject in self.subjects {
+            Self::subject_lineage(&self.topics, subject, &mut lineage)?;
+        }
+        for indicator in self.indicators {
+            Self::indicator_lineage(indicator, &mut lineage);
+        }
+
+        Ok(lineage)
+    }
+
+    /// factors of columns, joins and filters are used by subject,
+    /// and topics of them also.
+    fn subject_lineage(
+        topics: &HashMap<TopicId, Arc<TopicSchema>>,
+        subject: Subject,
+        lineage: &mut DataLineage,
+    ) -> VoidR {
+        let Some(subject_id) = subject.subject_id else {
+            return Ok(());
+        };
+        let subject_key = LineageNodeKey::Subject(subject_id.clone());
+        lineage.add_node(subject_key.clone(), subject.name.unwrap_or(subject_id));
+
+        let Some(dataset) = subject.dataset else {
+            return Ok(());
+        };
+        let finder = LineageRefsFinder::new(topics, None);
+        let mut factors = vec![];
+        for column in dataset.columns.unwrap_or_default() {
+            if let Some(parameter) = column.parameter {
+                let parameter = ArcParameter::new(parameter)?;
+                factors.extend(finder.refs_of_parameter(&parameter)?.factors);
+            }
+        }
+        for join in dataset.joins.unwrap_or_default() {
+            if let (Some(topic_id), Some(factor_id)) = (join.topic_id, join.factor_id) {
+                factors.push(LineageNodeKey::Factor(topic_id, factor_id));
+            }
+            if let (Some(topic_id), Some(factor_id)) =
+                (join.secondary_topic_id, join.secondary_factor_id)
+            {
+                factors.push(LineageNodeKey::Factor(topic_id, factor_id));
+            }
+        }
+        if let Some(filters) = dataset.filters {
+            let joint = ArcParameterJoint::new(filters)?;
+            finder.refs_of_joint(&joint, &mut factors)?;
+        }
+
+        for factor in factors {
+            if let LineageNodeKey::Factor(topic_id, _) = &factor {
+                lineage.add_edge(
+                    LineageNodeKey::Topic(topic_id.clone()),
+                    subject_key.clone(),
+                    LineageLinkType::UsedBy,
+                    None,
+                );
+            }
+            lineage.add_edge(factor, subject_key.clone(), LineageLinkType::UsedBy, None);
+        }
+        Ok(())
+    }
+
+    /// indicator is on topic (and factor) or subject
+    fn indicator_lineage(indicator: Indicator, lineage: &mut DataLineage) {
+        let Some(indicator_id) = indicator.indicator_id else {
+            return;
+        };
+        let indicator_key = LineageNodeKey::Indicator(indicator_id.clone());
+        lineage.add_node(
+            indicator_key.clone(),
+            indicator.name.unwrap_or(indicator_id),
+        );
+
+        let Some(topic_or_subject_id) = indicator.topic_or_subject_id else {
+            return;
+        };
+        match indicator.base_on {
+            Some(IndicatorBaseOn::Subject) => lineage.add_edge(
+                LineageNodeKey::Subject(topic_or_subject_id),
+                indicator_key,
+                LineageLinkType::UsedBy,
+                None,
+            ),
+            _ => {
+                if let Some(factor_id) = indicator.factor_id {
+                    lineage.add_edge(
+                        LineageNodeKey::Factor(topic_or_subject_id.clone(), factor_id),
+                        indicator_key.clone(),
+                        LineageLinkType::UsedBy,
+                        None,
+                    );
+                }
+                lineage.add_edge(
+                    LineageNodeKey::Topic(topic_or_subject_id),
+                    indicator_key,
+                    LineageLinkType::UsedBy,
+                    None,
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{LineageBuilder, LineageNodeKey, TestFixtures};
+    use elf_model::{
+        ConstantParameter, FactorType, Indicator, IndicatorBaseOn, InsertRowAction,
+        LineageLinkType, Pipeline, PipelineStage, PipelineTriggerType, PipelineUnit, Subject,
+        SubjectDataset, SubjectDatasetColumn, YearOfParameter,
+    };
+    use elf_runtime_model_kernel::PipelineSchema;
+    use std::sync::Arc;
+
+    fn pipeline() -> Arc<PipelineSchema> {
+        let action = InsertRowAction::init()
+            .action_id(String::from("a1"))
+            .topic_id(String::from("t2"))
+            .mapping(vec![
+                TestFixtures::mapping(TestFixtures::topic_factor("t1", "f1"), "g1"),
+                TestFixtures::mapping(
+                    YearOfParameter::init()
+                        .parameter(Box::new(TestFixtures::topic_factor("t1", "f3")))
+                        .to_parameter(),
+                    "g2",
+                ),
+                TestFixtures::mapping(
+                    ConstantParameter::of(String::from("&cur.code.&length")).to_parameter(),
+                    "g3",
+                ),
+                TestFixtures::mapping(
+                    ConstantParameter::of(String::from("&old.code")).to_parameter(),
+                    "g4",
+                ),
+            ])
+            .to_action();
+        let pipeline = Pipeline::new()
+            .pipeline_id(String::from("p1"))
+            .name(String::from("raw to target"))
+            .topic_id(String::from("t1"))
+            .r#type(PipelineTriggerType::Insert)
+            .stages(vec![PipelineStage::new()
+                .stage_id(String::from("s1"))
+                .units(vec![PipelineUnit::new()
+                    .unit_id(String::from("u1"))
+                    .r#do(vec![action])])])
+            .tenant_id(TestFixtures::tenant_id());
+        Arc::new(PipelineSchema::new(pipeline).unwrap())
+    }
+
+    fn factor(topic_id: &str, factor_id: &str) -> LineageNodeKey {
+        LineageNodeKey::Factor(topic_id.to_string(), factor_id.to_string())
+    }
+
+    #[test]
+    fn test_lineage() {
+        let lineage = LineageBuilder::new()
+            .topic(TestFixtures::topic(
+                "t1",
+                vec![
+                    ("f1", "amount", FactorType::Number),
+                    ("f2", "code", FactorType::Text),
+                    ("f3", "date", FactorType::Date),
+                ],
+            ))
+            .topic(TestFixtures::topic(
+                "t2",
+                vec![
+                    ("g1", "amount", FactorType::Number),
+                    ("g2", "year", FactorType::Year),
+                    ("g3", "codeLength", FactorType::Unsigned),
+                    ("g4", "oldCode", FactorType::Text),
+                ],
+            ))
+            .pipeline(pipeline())
+            .subject(
+                Subject::new()
+                    .subject_id(String::from("s1"))
+                    .name(String::from("amounts"))
+                    .dataset(SubjectDataset::new().columns(vec![
+                        SubjectDatasetColumn::new()
+                            .column_id(String::from("c1"))
+                            .parameter(TestFixtures::topic_factor("t2", "g1")),
+                    ])),
+            )
+            .indicator(
+                Indicator::new()
+                    .indicator_id(String::from("i1"))
+                    .name(String::from("total amount"))
+                    .topic_or_subject_id(String::from("s1"))
+                    .base_on(IndicatorBaseOn::Subject),
+            )
+            .build()
+            .unwrap();
+
+        let link_type = |source: &LineageNodeKey, target: &LineageNodeKey| {
+            lineage
+                .edges()
+                .iter()
+                .find(|edge| &edge.source == source && &edge.target == target)
+                .map(|edge| edge.r#type.clone())
+        };
+        assert_eq!(
+            link_type(&factor("t1", "f1"), &factor("t2", "g1")),
+            Some(LineageLinkType::Direct)
+        );
+        assert_eq!(
+            link_type(&factor("t1", "f3"), &factor("t2", "g2")),
+            Some(LineageLinkType::Computed)
+        );
+        assert_eq!(
+            link_type(&factor("t1", "f2"), &factor("t2", "g3")),
+            Some(LineageLinkType::Computed)
+        );
+        assert_eq!(
+            link_type(&factor("t1", "f2"), &factor("t2", "g4")),
+            Some(LineageLinkType::Direct)
+        );
+
+        assert_eq!(
+            lineage.upstream(&factor("t2", "g2")),
+            vec![factor("t1", "f3")]
+        );
+        let downstream = lineage.downstream(&factor("t1", "f1"));
+        assert!(downstream.contains(&factor("t2", "g1")));
+        assert!(downstream.contains(&LineageNodeKey::Pipeline(String::from("p1"))));
+        assert!(downstream.contains(&LineageNodeKey::Subject(String::from("s1"))));
+        assert!(downstream.contains(&LineageNodeKey::Indicator(String::from("i1"))));
+        assert!(!downstream.contains(&factor("t2", "g2")));
+
+        let impact = lineage.impact(&factor("t1", "f2"));
+        assert_eq!(impact.factors.len(), 2);
+        assert_eq!(impact.pipelines.len(), 1);
+        assert!(impact.subjects.is_empty());
+
+        let impact = lineage.impact(&LineageNodeKey::Topic(String::from("t1")));
+        assert_eq!(impact.factors.len(), 7);
+        assert!(impact
+            .topics
+            .contains(&LineageNodeKey::Topic(String::from("t2"))));
+        assert_eq!(impact.indicators.len(), 1);
+
+        let dot = lineage.to_dot();
+        assert!(
+            dot.contains(r#""factor:t1:f1" -> "factor:t2:g1" [label="direct", pipeline="p1"];"#)
+        );
+        let graphml = lineage.to_graphml();
+        assert!(graphml.contains(
+            r#"<node id="pipeline:p1"><data key="name">raw to target</data><data key="type">pipeline</data></node>"#
+        ));
+        let graph = lineage.to_graph(&TestFixtures::tenant_id());
+        assert_eq!(graph.nodes.unwrap().len(), lineage.nodes().len());
+    }
+}
@@ -0,0 +1,99 @@
+use crate::{DataLineage, LineageNodeKey};
+use elf_model::LineageNodeType;
+
+/// export lineage to GraphML and DOT.
+impl DataLineage {
+    fn xml_escape(value: &str) -> String {
+        value
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+            .replace('\'', "&apos;")
+    }
+
+    fn dot_escape(value: &str) -> String {
+        value.replace('\\', "\\\\").replace('"', "\\\"")
+    }
+
+    fn dot_shape(key: &LineageNodeKey) -> &'static str {
+        match key.node_type() {
+            LineageNodeType::Topic => "box",
+            LineageNodeType::Factor => "ellipse",
+            LineageNodeType::Pipeline => "hexagon",
+            LineageNodeType::Subject => "folder",
+            LineageNodeType::Indicator => "diamond",
+        }
+    }
+
+    pub fn to_graphml(&self) -> String {
+        let mut lines = vec![
+            String::from(r#"<?xml version="1.0" encoding="UTF-8"?>"#),
+            String::from(r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#),
+            String::from(r#"  <key id="name" for="node" attr.name="name" attr.type="string"/>"#),
+            String::from(r#"  <key id="type" for="node" attr.name="type" attr.type="string"/>"#),
+            String::from(
+                r#"  <key id="linkType" for="edge" attr.name="type" attr.type="string"/>"#,
+            ),
+            String::from(
+                r#"  <key id="pipelineId" for="edge" attr.name="pipelineId" attr.type="string"/>"#,
+            ),
+            String::from(r#"  <graph id="lineage" edgedefault="directed">"#),
+        ];
+        for (key, name) in self.nodes() {
+            lines.push(format!(
+                r#"    <node id="{}"><data key="name">{}</data><data key="type">{}</data></node>"#,
+                Self::xml_escape(&key.id()),
+                Self::xml_escape(name),
+                key.node_type()
+            ));
+        }
+        for edge in self.edges() {
+            let pipeline_id = match &edge.pipeline_id {
+                Some(pipeline_id) => format!(
+                    r#"<data key="pipelineId">{}</data>"#,
+                    Self::xml_escape(pipeline_id)
+                ),
+                _ => String::new(),
+            };
+            lines.push(format!(
+                r#"    <edge source="{}" target="{}"><data key="linkType">{}</data>{}</edge>"#,
+                Self::xml_escape(&edge.source.id()),
+                Self::xml_escape(&edge.target.id()),
+                edge.r#type,
+                pipeline_id
+            ));
+        }
+        lines.push(String::from("  </graph>"));
+        lines.push(String::from("</graphml>"));
+        lines.join("\n")
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut lines = vec![String::from("digraph lineage {")];
+        for (key, name) in self.nodes() {
+            lines.push(format!(
+                r#"  "{}" [label="{}", shape={}, type="{}"];"#,
+                Self::dot_escape(&key.id()),
+                Self::dot_escape(name),
+                Self::dot_shape(key),
+                key.node_type()
+            ));
+        }
+        for edge in self.edges() {
+            let pipeline_id = match &edge.pipeline_id {
+                Some(pipeline_id) => format!(r#", pipeline="{}""#, Self::dot_escape(pipeline_id)),
+                _ => String::new(),
+            };
+            lines.push(format!(
+                r#"  "{}" -> "{}" [label="{}"{}];"#,
+                Self::dot_escape(&edge.source.id()),
+                Self::dot_escape(&edge.target.id()),
+                edge.r#type,
+                pipeline_id
+            ));
+        }
+        lines.push(String::from("}"));
+        lines.join("\n")
+    }
+}
@@ -0,0 +1,16 @@
+use crate::LineageNodeKey;
+use elf_model::{PipelineActionId, PipelineStageId, PipelineUnitId};
+use std::sync::Arc;
+
+/// value flows from source factor to target factor, in a write action of pipeline.
+/// source is none when the value is constant only, e.g. a fixed value or a predefined function.
+pub struct LineageFlow {
+    pub stage_id: Arc<PipelineStageId>,
+    pub stage_name: Arc<String>,
+    pub unit_id: Arc<PipelineUnitId>,
+    pub unit_name: Arc<String>,
+    pub action_id: Arc<PipelineActionId>,
+    pub source: Option<LineageNodeKey>,
+    pub target: LineageNodeKey,
+    pub direct: bool,
+}
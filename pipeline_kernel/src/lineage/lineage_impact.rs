@@ -0,0 +1,51 @@
+use crate::LineageNodeKey;
+use std::collections::BTreeSet;
+
+/// nodes affected by dropping the given node, grouped by node type.
+pub struct LineageImpact {
+    pub dropped: LineageNodeKey,
+    pub topics: BTreeSet<LineageNodeKey>,
+    pub factors: BTreeSet<LineageNodeKey>,
+    pub pipelines: BTreeSet<LineageNodeKey>,
+    pub subjects: BTreeSet<LineageNodeKey>,
+    pub indicators: BTreeSet<LineageNodeKey>,
+}
+
+impl LineageImpact {
+    pub fn new(dropped: LineageNodeKey) -> Self {
+        Self {
+            dropped,
+            topics: BTreeSet::new(),
+            factors: BTreeSet::new(),
+            pipelines: BTreeSet::new(),
+            subjects: BTreeSet::new(),
+            indicators: BTreeSet::new(),
+        }
+    }
+
+    /// the dropped one is ignored
+    pub fn add(&mut self, key: LineageNodeKey) {
+        if key == self.dropped {
+            return;
+        }
+        match key {
+            LineageNodeKey::Topic(_) => self.topics.insert(key),
+            LineageNodeKey::Factor(_, _) => self.factors.insert(key),
+            LineageNodeKey::Pipeline(_) => self.pipelines.insert(key),
+            LineageNodeKey::Subject(_) => self.subjects.insert(key),
+            LineageNodeKey::Indicator(_) => self.indicators.insert(key),
+        };
+    }
+
+    pub fn add_all(&mut self, keys: Vec<LineageNodeKey>) {
+        keys.into_iter().for_each(|key| self.add(key));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.topics.is_empty()
+            && self.factors.is_empty()
+            && self.pipelines.is_empty()
+            && self.subjects.is_empty()
+            && self.indicators.is_empty()
+    }
+}
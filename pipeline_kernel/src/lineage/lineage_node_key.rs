@@ -0,0 +1,38 @@
+use elf_model::{FactorId, IndicatorId, LineageNodeType, PipelineId, SubjectId, TopicId};
+
+/// typed key of lineage node, id of node is generated by key,
+/// and it is unique in lineage graph.
+#[derive(PartialEq, Eq, Hash, PartialOrd, Ord, Debug, Clone)]
+pub enum LineageNodeKey {
+    Topic(TopicId),
+    Factor(TopicId, FactorId),
+    Pipeline(PipelineId),
+    Subject(SubjectId),
+    Indicator(IndicatorId),
+}
+
+impl LineageNodeKey {
+    pub fn id(&self) -> String {
+        match self {
+            Self::Topic(topic_id) => format!("topic:{}", topic_id),
+            Self::Factor(topic_id, factor_id) => format!("factor:{}:{}", topic_id, factor_id),
+            Self::Pipeline(pipeline_id) => format!("pipeline:{}", pipeline_id),
+            Self::Subject(subject_id) => format!("subject:{}", subject_id),
+            Self::Indicator(indicator_id) => format!("indicator:{}", indicator_id),
+        }
+    }
+
+    pub fn node_type(&self) -> LineageNodeType {
+        match self {
+            Self::Topic(_) => LineageNodeType::Topic,
+            Self::Factor(_, _) => LineageNodeType::Factor,
+            Self::Pipeline(_) => LineageNodeType::Pipeline,
+            Self::Subject(_) => LineageNodeType::Subject,
+            Self::Indicator(_) => LineageNodeType::Indicator,
+        }
+    }
+
+    pub fn is_factor(&self) -> bool {
+        matches!(self, Self::Factor(_, _))
+    }
+}
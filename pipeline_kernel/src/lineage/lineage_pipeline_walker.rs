@@ -0,0 +1,314 @@
+use crate::{DataLineage, LineageFlow, LineageNodeKey, LineageRefsFinder, LineageVariable};
+use elf_base::{StdR, VoidR};
+use elf_model::{FactorId, LineageLinkType, PipelineActionId, PipelineId, TopicId};
+use elf_runtime_model_kernel::{
+    ArcMappingFactor, ArcParameter, ArcParameterJoint, ArcPipelineAction, ArcPipelineStage,
+    ArcPipelineUnit, PipelineSchema, TopicSchema,
+};
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// walk through the pipeline, stages, units and actions,
+/// to find out the factor level lineage.
+/// memory variables are tracked in declaration order, loop is ignored.
+/// the factor flows of write actions are collected and returned.
+pub struct LineagePipelineWalker<'a> {
+    pipeline: &'a PipelineSchema,
+    finder: LineageRefsFinder<'a>,
+    stage: Option<Arc<ArcPipelineStage>>,
+    unit: Option<Arc<ArcPipelineUnit>>,
+    flows: Vec<LineageFlow>,
+}
+
+impl<'a> LineagePipelineWalker<'a> {
+    pub fn new(
+        pipeline: &'a PipelineSchema,
+        topics: &'a HashMap<TopicId, Arc<TopicSchema>>,
+    ) -> Self {
+        Self {
+            pipeline,
+            finder: LineageRefsFinder::new(topics, Some(pipeline.pipeline().topic_id.deref()))
+                .with_tenant_id(pipeline.pipeline().tenant_id.clone()),
+            stage: None,
+            unit: None,
+            flows: vec![],
+        }
+    }
+
+    fn pipeline_id(&self) -> &PipelineId {
+        self.pipeline.pipeline_id().deref()
+    }
+
+    fn trigger_topic_id(&self) -> &TopicId {
+        self.pipeline.pipeline().topic_id.deref()
+    }
+
+    pub fn walk(mut self, lineage: &mut DataLineage) -> StdR<Vec<LineageFlow>> {
+        let pipeline_key = LineageNodeKey::Pipeline(self.pipeline_id().clone());
+        lineage.add_node(pipeline_key.clone(), self.pipeline.name().to_string());
+        self.link(
+            lineage,
+            LineageNodeKey::Topic(self.trigger_topic_id().clone()),
+            pipeline_key.clone(),
+            LineageLinkType::Triggers,
+        );
+
+        let pipeline = self.pipeline.pipeline().clone();
+        self.joint(lineage, &pipeline.on)?;
+        for stage in pipeline.stages.iter() {
+            self.stage = Some(stage.clone());
+            self.joint(lineage, &stage.on)?;
+            for unit in stage.units.iter() {
+                self.unit = Some(unit.clone());
+                self.joint(lineage, &unit.on)?;
+                for action in unit.r#do.iter() {
+                    self.action(lineage, action)?;
+                }
+            }
+        }
+        Ok(self.flows)
+    }
+
+    fn link(
+        &self,
+        lineage: &mut DataLineage,
+        source: LineageNodeKey,
+        target: LineageNodeKey,
+        r#type: LineageLinkType,
+    ) {
+        lineage.add_edge(source, target, r#type, Some(self.pipeline_id().clone()));
+    }
+
+    /// all referred factors are used by pipeline
+    fn used(&self, lineage: &mut DataLineage, factors: &Vec<LineageNodeKey>) {
+        let pipeline_key = LineageNodeKey::Pipeline(self.pipeline_id().clone());
+        for factor in factors {
+            self.link(
+                lineage,
+                factor.clone(),
+                pipeline_key.clone(),
+                LineageLinkType::UsedBy,
+            );
+        }
+    }
+
+    fn read(&self, lineage: &mut DataLineage, topic_id: &TopicId) {
+        self.link(
+            lineage,
+            LineageNodeKey::Topic(topic_id.clone()),
+            LineageNodeKey::Pipeline(self.pipeline_id().clone()),
+            LineageLinkType::Reads,
+        );
+    }
+
+    fn write(&self, lineage: &mut DataLineage, topic_id: &TopicId) {
+        self.link(
+            lineage,
+            LineageNodeKey::Pipeline(self.pipeline_id().clone()),
+            LineageNodeKey::Topic(topic_id.clone()),
+            LineageLinkType::Writes,
+        );
+    }
+
+    fn joint(&self, lineage: &mut DataLineage, joint: &Option<Arc<ArcParameterJoint>>) -> VoidR {
+        if let Some(joint) = joint {
+            let mut factors = vec![];
+            self.finder.refs_of_joint(joint, &mut factors)?;
+            self.used(lineage, &factors);
+        }
+        Ok(())
+    }
+
+    /// write source to target factor
+    fn flow(
+        &mut self,
+        lineage: &mut DataLineage,
+        action_id: &Arc<PipelineActionId>,
+        source: &Arc<ArcParameter>,
+        topic_id: &TopicId,
+        factor_id: &FactorId,
+    ) -> VoidR {
+        let target = LineageNodeKey::Factor(topic_id.clone(), factor_id.clone());
+        let refs = self.finder.refs_of_parameter(source)?;
+        let r#type = if refs.direct {
+            LineageLinkType::Direct
+        } else {
+            LineageLinkType::Computed
+        };
+        for factor in refs.factors.iter() {
+            self.link(lineage, factor.clone(), target.clone(), r#type.clone());
+        }
+        self.used(lineage, &refs.factors);
+        self.used(lineage, &vec![target.clone()]);
+        self.collect(action_id, refs.factors, target, refs.direct);
+        Ok(())
+    }
+
+    /// stage and unit always exist, since flow is only from action.
+    /// duplicated sources are merged.
+    fn collect(
+        &mut self,
+        action_id: &Arc<PipelineActionId>,
+        mut sources: Vec<LineageNodeKey>,
+        target: LineageNodeKey,
+        direct: bool,
+    ) {
+        let (Some(stage), Some(unit)) = (&self.stage, &self.unit) else {
+            return;
+        };
+        sources.sort();
+        sources.dedup();
+        let flow = |source: Option<LineageNodeKey>| LineageFlow {
+            stage_id: stage.stage_id.clone(),
+            stage_name: stage.name.clone(),
+            unit_id: unit.unit_id.clone(),
+            unit_name: unit.name.clone(),
+            action_id: action_id.clone(),
+            source,
+            target: target.clone(),
+            direct,
+        };
+        if sources.is_empty() {
+            self.flows.push(flow(None));
+        } else {
+            let flows: Vec<LineageFlow> = sources.into_iter().map(|s| flow(Some(s))).collect();
+            self.flows.extend(flows);
+        }
+    }
+
+    fn mapping(
+        &mut self,
+        lineage: &mut DataLineage,
+        action_id: &Arc<PipelineActionId>,
+        mapping: &Arc<Vec<Arc<ArcMappingFactor>>>,
+        topic_id: &TopicId,
+    ) -> VoidR {
+        for mapping_factor in mapping.iter() {
+            self.flow(
+                lineage,
+                action_id,
+                &mapping_factor.source,
+                topic_id,
+                &mapping_factor.factor_id,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn action(&mut self, lineage: &mut DataLineage, action: &Arc<ArcPipelineAction>) -> VoidR {
+        match action.deref() {
+            ArcPipelineAction::Alarm(action) => self.joint(lineage, &action.on)?,
+            ArcPipelineAction::CopyToMemory(action) => {
+                let refs = self.finder.refs_of_parameter(&action.source)?;
+                self.used(lineage, &refs.factors);
+                self.finder.declare(
+                    action.variable_name.to_string(),
+                    LineageVariable::Factors(refs.factors),
+                );
+            }
+            ArcPipelineAction::WriteToExternal(_) => {}
+            ArcPipelineAction::ReadRow(action) => {
+                self.read(lineage, &action.topic_id);
+                self.joint(lineage, &Some(action.by.clone()))?;
+                self.finder.declare(
+                    action.variable_name.to_string(),
+                    LineageVariable::Row(action.topic_id.to_string()),
+                );
+            }
+            ArcPipelineAction::ReadRows(action) => {
+                self.read(lineage, &action.topic_id);
+                self.joint(lineage, &Some(action.by.clone()))?;
+                self.finder.declare(
+                    action.variable_name.to_string(),
+                    LineageVariable::Row(action.topic_id.to_string()),
+                );
+            }
+            ArcPipelineAction::ReadFactor(action) => {
+                self.read(lineage, &action.topic_id);
+                self.joint(lineage, &Some(action.by.clone()))?;
+                let factor = LineageNodeKey::Factor(
+                    action.topic_id.to_string(),
+                    action.factor_id.to_string(),
+                );
+                self.used(lineage, &vec![factor.clone()]);
+                self.finder.declare(
+                    action.variable_name.to_string(),
+                    LineageVariable::Factors(vec![factor]),
+                );
+            }
+            ArcPipelineAction::ReadFactors(action) => {
+                self.read(lineage, &action.topic_id);
+                self.joint(lineage, &Some(action.by.clone()))?;
+                let factor = LineageNodeKey::Factor(
+                    action.topic_id.to_string(),
+                    action.factor_id.to_string(),
+                );
+                self.used(lineage, &vec![factor.clone()]);
+                self.finder.declare(
+                    action.variable_name.to_string(),
+                    LineageVariable::Factors(vec![factor]),
+                );
+            }
+            ArcPipelineAction::Exists(action) => {
+                self.read(lineage, &action.topic_id);
+                let mut factors = vec![];
+                self.finder.refs_of_joint(&action.by, &mut factors)?;
+                self.used(lineage, &factors);
+                self.finder.declare(
+                    action.variable_name.to_string(),
+                    LineageVariable::Factors(factors),
+                );
+            }
+            ArcPipelineAction::InsertRow(action) => {
+                self.write(lineage, &action.topic_id);
+                self.mapping(
+                    lineage,
+                    &action.action_id,
+                    &action.mapping,
+                    &action.topic_id,
+                )?;
+            }
+            ArcPipelineAction::MergeRow(action) => {
+                self.write(lineage, &action.topic_id);
+                self.mapping(
+                    lineage,
+                    &action.action_id,
+                    &action.mapping,
+                    &action.topic_id,
+                )?;
+                self.joint(lineage, &Some(action.by.clone()))?;
+            }
+            ArcPipelineAction::InsertOrMergeRow(action) => {
+                self.write(lineage, &action.topic_id);
+                self.mapping(
+                    lineage,
+                    &action.action_id,
+                    &action.mapping,
+                    &action.topic_id,
+                )?;
+                self.joint(lineage, &Some(action.by.clone()))?;
+            }
+            ArcPipelineAction::WriteFactor(action) => {
+                self.write(lineage, &action.topic_id);
+                self.flow(
+                    lineage,
+                    &action.action_id,
+                    &action.source,
+                    &action.topic_id,
+                    &action.factor_id,
+                )?;
+                self.joint(lineage, &Some(action.by.clone()))?;
+            }
+            ArcPipelineAction::DeleteRow(action) => {
+                self.write(lineage, &action.topic_id);
+                self.joint(lineage, &Some(action.by.clone()))?;
+            }
+            ArcPipelineAction::DeleteRows(action) => {
+                self.write(lineage, &action.topic_id);
+                self.joint(lineage, &Some(action.by.clone()))?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,280 @@
+use crate::{DataPath, DataPathSegment, FuncDataPath, FuncDataPathParam, LineageNodeKey};
+use elf_base::{StdR, StringUtils, VoidR};
+use elf_model::{TenantId, TopicId, VariablePredefineFunctions};
+use elf_runtime_model_kernel::{
+    ArcComputedParameter, ArcParameter, ArcParameterCondition, ArcParameterExpression,
+    ArcParameterJoint, TopicSchema,
+};
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// what a memory variable refers to
+pub enum LineageVariable {
+    /// read row(s), sub path of variable refers to factor of topic
+    Row(TopicId),
+    /// value computed from factors
+    Factors(Vec<LineageNodeKey>),
+}
+
+/// factors referred by parameter or data path.
+/// direct is true when the value is copied from a single factor without any computing.
+pub struct LineageRefs {
+    pub factors: Vec<LineageNodeKey>,
+    pub direct: bool,
+}
+
+impl LineageRefs {
+    fn computed(factors: Vec<LineageNodeKey>) -> Self {
+        Self {
+            factors,
+            direct: false,
+        }
+    }
+}
+
+/// find out the factors referred by parameters and data paths.
+/// - plain path is resolved from memory variables first, then the trigger topic,
+/// - [&cur] and [&old] are resolved from the trigger topic,
+/// - without trigger topic (e.g. subject), only topic factor parameter is resolved.
+pub struct LineageRefsFinder<'a> {
+    topics: &'a HashMap<TopicId, Arc<TopicSchema>>,
+    trigger_topic_id: Option<&'a TopicId>,
+    variables: HashMap<String, LineageVariable>,
+    /// to resolve the registered functions in data path
+    tenant_id: Option<Arc<TenantId>>,
+}
+
+impl<'a> LineageRefsFinder<'a> {
+    pub fn new(
+        topics: &'a HashMap<TopicId, Arc<TopicSchema>>,
+        trigger_topic_id: Option<&'a TopicId>,
+    ) -> Self {
+        Self {
+            topics,
+            trigger_topic_id,
+            variables: HashMap::new(),
+            tenant_id: None,
+        }
+    }
+
+    pub fn with_tenant_id(mut self, tenant_id: Arc<TenantId>) -> Self {
+        self.tenant_id = Some(tenant_id);
+        self
+    }
+
+    fn parse_path(&self, path: &str) -> StdR<DataPath> {
+        match &self.tenant_id {
+            Some(tenant_id) => DataPath::from_str_of_tenant(path, tenant_id),
+            None => DataPath::from_str(path),
+        }
+    }
+
+    pub fn declare(&mut self, variable_name: String, variable: LineageVariable) {
+        self.variables.insert(variable_name, variable);
+    }
+
+    pub fn refs_of_joint(
+        &self,
+        joint: &Arc<ArcParameterJoint>,
+        factors: &mut Vec<LineageNodeKey>,
+    ) -> VoidR {
+        for filter in joint.filters.iter() {
+            match filter.deref() {
+                ArcParameterCondition::Joint(joint) => self.refs_of_joint(joint, factors)?,
+                ArcParameterCondition::Expression(expression) => {
+                    let (left, right) = match expression.deref() {
+                        ArcParameterExpression::Empty(e) => (&e.left, None),
+                        ArcParameterExpression::NotEmpty(e) => (&e.left, None),
+                        ArcParameterExpression::Equals(e) => (&e.left, Some(&e.right)),
+                        ArcParameterExpression::NotEquals(e) => (&e.left, Some(&e.right)),
+                        ArcParameterExpression::LessThan(e) => (&e.left, Some(&e.right)),
+                        ArcParameterExpression::LessThanOrEquals(e) => (&e.left, Some(&e.right)),
+                        ArcParameterExpression::MoreThan(e) => (&e.left, Some(&e.right)),
+                        ArcParameterExpression::MoreThanOrEquals(e) => (&e.left, Some(&e.right)),
+                        ArcParameterExpression::In(e) => (&e.left, Some(&e.right)),
+                        ArcParameterExpression::NotIn(e) => (&e.left, Some(&e.right)),
+                    };
+                    factors.extend(self.refs_of_parameter(left)?.factors);
+                    if let Some(right) = right {
+                        factors.extend(self.refs_of_parameter(right)?.factors);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn refs_of_parameters(&self, parameters: &Arc<Vec<Arc<ArcParameter>>>) -> StdR<LineageRefs> {
+        let mut factors = vec![];
+        for parameter in parameters.iter() {
+            factors.extend(self.refs_of_parameter(parameter)?.factors);
+        }
+        Ok(LineageRefs::computed(factors))
+    }
+
+    pub fn refs_of_parameter(&self, parameter: &Arc<ArcParameter>) -> StdR<LineageRefs> {
+        match parameter.deref() {
+            ArcParameter::Topic(parameter) => Ok(LineageRefs {
+                factors: vec![LineageNodeKey::Factor(
+                    parameter.topic_id.to_string(),
+                    parameter.factor_id.to_string(),
+                )],
+                direct: true,
+            }),
+            ArcParameter::Constant(parameter) => {
+                if parameter.value.is_blank() {
+                    Ok(LineageRefs::computed(vec![]))
+                } else {
+                    Ok(self.refs_of_path(&self.parse_path(&parameter.value)?))
+                }
+            }
+            ArcParameter::Computed(parameter) => match parameter.deref() {
+                ArcComputedParameter::None(p) => self.refs_of_parameters(&p.parameters),
+                ArcComputedParameter::Add(p) => self.refs_of_parameters(&p.parameters),
+                ArcComputedParameter::Subtract(p) => self.refs_of_parameters(&p.parameters),
+                ArcComputedParameter::Multiply(p) => self.refs_of_parameters(&p.parameters),
+                ArcComputedParameter::Divide(p) => self.refs_of_parameters(&p.parameters),
+                ArcComputedParameter::Modulus(p) => self.refs_of_parameters(&p.parameters),
+                ArcComputedParameter::YearOf(p) => self.refs_of_computed(&p.parameter),
+                ArcComputedParameter::HalfYearOf(p) => self.refs_of_computed(&p.parameter),
+                ArcComputedParameter::QuarterOf(p) => self.refs_of_computed(&p.parameter),
+                ArcComputedParameter::MonthOf(p) => self.refs_of_computed(&p.parameter),
+                ArcComputedParameter::WeekOfYear(p) => self.refs_of_computed(&p.parameter),
+                ArcComputedParameter::WeekOfMonth(p) => self.refs_of_computed(&p.parameter),
+                ArcComputedParameter::DayOfMonth(p) => self.refs_of_computed(&p.parameter),
+                ArcComputedParameter::DayOfWeek(p) => self.refs_of_computed(&p.parameter),
+                ArcComputedParameter::CaseThen(p) => {
+                    let mut factors = vec![];
+                    for route in p.parameters.iter() {
+                        if let Some(on) = &route.on {
+                            self.refs_of_joint(on, &mut factors)?;
+                        }
+                        factors.extend(self.refs_of_parameter(&route.parameter)?.factors);
+                    }
+                    Ok(LineageRefs::computed(factors))
+                }
+            },
+        }
+    }
+
+    fn refs_of_computed(&self, parameter: &Arc<ArcParameter>) -> StdR<LineageRefs> {
+        Ok(LineageRefs::computed(
+            self.refs_of_parameter(parameter)?.factors,
+        ))
+    }
+
+    /// path is direct when it is plain names only, and refers to one factor.
+    /// [&cur] and [&old] are allowed as the first segment.
+    fn refs_of_path(&self, path: &DataPath) -> LineageRefs {
+        let mut factors = vec![];
+        self.refs_of_segments(path.segments(), &mut factors);
+        let direct = factors.len() == 1
+            && path
+                .segments()
+                .iter()
+                .enumerate()
+                .all(|(index, segment)| match segment {
+                    DataPathSegment::Plain(_) => true,
+                    DataPathSegment::Func(func) => index == 0 && Self::is_trigger_data(func),
+                });
+        LineageRefs { factors, direct }
+    }
+
+    fn is_trigger_data(func: &FuncDataPath) -> bool {
+        matches!(
+            func.predefined_func(),
+            Some(
+                VariablePredefineFunctions::FromCurrentTriggerData
+                    | VariablePredefineFunctions::FromPreviousTriggerData
+            )
+        )
+    }
+
+    fn refs_of_segments(&self, segments: &[DataPathSegment], factors: &mut Vec<LineageNodeKey>) {
+        let (from_trigger_data, rest) = match segments.first() {
+            Some(DataPathSegment::Func(func)) if Self::is_trigger_data(func) => {
+                (true, &segments[1..])
+            }
+            _ => (false, segments),
+        };
+        let names: Vec<String> = rest
+            .iter()
+            .map_while(|segment| match segment {
+                DataPathSegment::Plain(plain) => Some(plain.this_path()),
+                _ => None,
+            })
+            .collect();
+        if !names.is_empty() {
+            let variable = if from_trigger_data {
+                None
+            } else {
+                self.variables.get(&names[0])
+            };
+            match variable {
+                Some(LineageVariable::Factors(refs)) => factors.extend(refs.iter().cloned()),
+                Some(LineageVariable::Row(topic_id)) => {
+                    self.refs_of_names(topic_id, &names[1..], factors)
+                }
+                _ => self.refs_of_trigger_names(&names, factors),
+            }
+        }
+
+        for segment in segments {
+            if let DataPathSegment::Func(func) = segment {
+                self.refs_of_func(func, factors);
+            }
+        }
+    }
+
+    fn refs_of_func(&self, func: &FuncDataPath, factors: &mut Vec<LineageNodeKey>) {
+        if let Some(params) = func.params() {
+            for param in params {
+                match param {
+                    FuncDataPathParam::Value(_) => {}
+                    FuncDataPathParam::Plain(plain) => {
+                        let name = plain.this_path();
+                        match self.variables.get(&name) {
+                            Some(LineageVariable::Factors(refs)) => {
+                                factors.extend(refs.iter().cloned())
+                            }
+                            Some(LineageVariable::Row(_)) => {}
+                            _ => self.refs_of_trigger_names(&[name], factors),
+                        }
+                    }
+                    FuncDataPathParam::Func(func) => self.refs_of_func(func, factors),
+                    FuncDataPathParam::Path(path) => {
+                        self.refs_of_segments(path.segments(), factors)
+                    }
+                }
+            }
+        }
+    }
+
+    fn refs_of_trigger_names(&self, names: &[String], factors: &mut Vec<LineageNodeKey>) {
+        if let Some(topic_id) = self.trigger_topic_id {
+            self.refs_of_names(topic_id, names, factors)
+        }
+    }
+
+    /// factor name might contain dot, so find the longest matched one.
+    fn refs_of_names(
+        &self,
+        topic_id: &TopicId,
+        names: &[String],
+        factors: &mut Vec<LineageNodeKey>,
+    ) {
+        let Some(topic) = self.topics.get(topic_id) else {
+            return;
+        };
+        for count in (1..=names.len()).rev() {
+            if let Some(factor) = topic.factor_by_name(&names[0..count].join(".")) {
+                factors.push(LineageNodeKey::Factor(
+                    topic_id.clone(),
+                    factor.factor_id.to_string(),
+                ));
+                return;
+            }
+        }
+    }
+}
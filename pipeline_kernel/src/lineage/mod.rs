@@ -0,0 +1,16 @@
+mod data_lineage;
+mod lineage_builder;
+mod lineage_export;
+mod lineage_flow;
+mod lineage_impact;
+mod lineage_node_key;
+mod lineage_pipeline_walker;
+mod lineage_refs_finder;
+
+pub use data_lineage::*;
+pub use lineage_builder::*;
+pub use lineage_flow::*;
+pub use lineage_impact::*;
+pub use lineage_node_key::*;
+pub use lineage_pipeline_walker::*;
+pub use lineage_refs_finder::*;
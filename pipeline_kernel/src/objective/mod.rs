@@ -0,0 +1,11 @@
+mod objective_breakdown;
+mod objective_evaluator;
+mod objective_formula;
+mod objective_service;
+mod objective_time_range;
+
+pub use objective_breakdown::*;
+pub use objective_evaluator::*;
+pub use objective_formula::*;
+pub use objective_service::*;
+pub use objective_time_range::*;
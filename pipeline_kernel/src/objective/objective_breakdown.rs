@@ -0,0 +1,443 @@
+use crate::{
+    ArcTopicData, ArcTopicDataValue, DataPath, InMemoryData, IndicatorBucket, IndicatorService,
+    ObjectiveDatePart, ObjectiveEvaluator, ObjectiveFormula, ObjectiveResources,
+    PipelineKernelErrorCode,
+};
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use elf_base::{ErrorCode, StdR};
+use elf_model::{
+    BreakdownDimension, BreakdownDimensionType, BreakdownTarget, BreakdownTargetValues, DataResult,
+    DataResultSetCell, Objective, ObjectiveTarget, TopicId,
+};
+use elf_runtime_model_kernel::TopicSchema;
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
+enum BreakdownDimensionOn {
+    Value,
+    Bucket(IndicatorBucket),
+    Time(ObjectiveDatePart, String),
+}
+
+struct CompiledBreakdownDimension {
+    factor_id: String,
+    on: BreakdownDimensionOn,
+}
+
+/// value of row on dimension, ordered by text, segment index or date part
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum BreakdownDimensionValue {
+    Text(String),
+    Segment(usize),
+    Number(u32),
+}
+
+/// key of row, values of all dimensions
+type BreakdownKey = Vec<BreakdownDimensionValue>;
+
+/// breakdown as is value of objective target by dimensions.
+/// value of row on dimension is
+/// - value: text of factor (or column) value,
+/// - bucket: segment which factor value falls in,
+/// - time related: date part of factor value by measure method, see [ObjectiveDatePart].
+///
+/// row is ignored when it falls in no segment, or its time value is none.
+/// dimension factor must exist in topic or subject of every indicator which the target relies on,
+/// and dimension values are collected from the rows accepted by factor filters on current cycle.
+/// then the as is value is computed on each combination of dimension values.
+pub struct ObjectiveBreakdown<'r, R: ObjectiveResources> {
+    objective: &'r Objective,
+    resources: &'r R,
+    now: NaiveDateTime,
+}
+
+impl<'r, R: ObjectiveResources> ObjectiveBreakdown<'r, R> {
+    pub fn new(objective: &'r Objective, resources: &'r R, now: &NaiveDateTime) -> Self {
+        Self {
+            objective,
+            resources,
+            now: *now,
+        }
+    }
+
+    /// target is failed when any factor it relies on is failed
+    pub fn breakdown(&self, breakdown: &BreakdownTarget) -> StdR<BreakdownTargetValues> {
+        let target = self.target_of(breakdown)?;
+        let dimensions = self.compile(breakdown)?;
+        let mut values = BreakdownTargetValues::new();
+        values.uuid = breakdown.uuid.clone();
+        values.target_id = breakdown.target_id.clone();
+        values.name = breakdown.name.clone();
+
+        let paths = RefCell::new(HashMap::new());
+        let names = RefCell::new(HashMap::new());
+        let key_of = |schema: &Arc<TopicSchema>, row: &ArcTopicData| {
+            Self::key_of(&dimensions, schema, row, &paths, &names)
+        };
+
+        let keys = RefCell::new(BTreeSet::new());
+        let mut evaluator = ObjectiveEvaluator::new(self.objective, self.resources, &self.now)?;
+        evaluator.filter_rows(Some(Box::new(|schema, row| {
+            if let Some(key) = key_of(schema, row)? {
+                keys.borrow_mut().insert(key);
+            }
+            Ok(true)
+        })));
+        evaluator.current_asis(target)?;
+        if evaluator.has_failed() {
+            return Ok(values.failed(true));
+        }
+
+        let mut data = vec![];
+        for key in keys.take() {
+            let row_key = key.clone();
+            evaluator.filter_rows(Some(Box::new(move |schema, row| {
+                Ok(key_of(schema, row)?.is_some_and(|key| key == row_key))
+            })));
+            let value = evaluator.current_asis(target)?;
+            if evaluator.has_failed() {
+                return Ok(values.failed(true));
+            }
+            let mut row: Vec<DataResultSetCell> = key
+                .into_iter()
+                .zip(dimensions.iter())
+                .map(|(value, dimension)| Self::cell_of(value, dimension))
+                .collect();
+            row.push(match value {
+                Some(value) => DataResultSetCell::Num(value),
+                _ => DataResultSetCell::None,
+            });
+            data.push(row);
+        }
+
+        let names = names.borrow();
+        let mut columns: Vec<String> = dimensions
+            .iter()
+            .map(|dimension| {
+                let name = names
+                    .get(&dimension.factor_id)
+                    .cloned()
+                    .unwrap_or_else(|| dimension.factor_id.clone());
+                match &dimension.on {
+                    BreakdownDimensionOn::Value => name,
+                    BreakdownDimensionOn::Bucket(bucket) => bucket.name().clone(),
+                    BreakdownDimensionOn::Time(_, method) => format!("{}({})", name, method),
+                }
+            })
+            .collect();
+        columns.push(
+            target
+                .name
+                .clone()
+                .or_else(|| target.uuid.clone())
+                .unwrap_or_default(),
+        );
+        Ok(values
+            .data(DataResult::new().columns(columns).data(data))
+            .failed(false))
+    }
+
+    fn target_of(&self, breakdown: &BreakdownTarget) -> StdR<&'r ObjectiveTarget> {
+        let target_id = breakdown.target_id.as_deref().unwrap_or("");
+        match self
+            .objective
+            .targets
+            .iter()
+            .flatten()
+            .find(|target| target.uuid.as_deref() == Some(target_id))
+        {
+            Some(target) => Ok(target),
+            _ => PipelineKernelErrorCode::ObjectiveTargetNotFound.msg(format!(
+                "Objective target[{}] of breakdown target[{}] not found.",
+                target_id,
+                breakdown.uuid.as_deref().unwrap_or("")
+            )),
+        }
+    }
+
+    fn compile(&self, breakdown: &BreakdownTarget) -> StdR<Vec<CompiledBreakdownDimension>> {
+        let mut compiled = vec![];
+        for dimension in breakdown.dimensions.iter().flatten() {
+            compiled.push(self.compile_dimension(dimension)?);
+        }
+        Ok(compiled)
+    }
+
+    fn compile_dimension(
+        &self,
+        dimension: &BreakdownDimension,
+    ) -> StdR<CompiledBreakdownDimension> {
+        let Some(factor_id) = &dimension.factor_or_column_id else {
+            return PipelineKernelErrorCode::BreakdownDimensionInvalid
+                .msg("Factor or column of breakdown dimension is missed.");
+        };
+        let on = match &dimension.r#type {
+            Some(BreakdownDimensionType::Bucket) => match &dimension.bucket_id {
+                Some(bucket_id) => BreakdownDimensionOn::Bucket(IndicatorBucket::compile(
+                    self.resources.bucket(bucket_id)?,
+                )?),
+                _ => {
+                    return PipelineKernelErrorCode::BreakdownDimensionInvalid.msg(format!(
+                        "Bucket of breakdown dimension on factor[{}] is missed.",
+                        factor_id
+                    ));
+                }
+            },
+            Some(BreakdownDimensionType::TimeRelated) => {
+                match dimension.time_measure_method.as_ref().and_then(|method| {
+                    ObjectiveDatePart::of_measure_method(method).map(|part| (part, method))
+                }) {
+                    Some((part, method)) => BreakdownDimensionOn::Time(part, method.to_string()),
+                    _ => {
+                        return PipelineKernelErrorCode::BreakdownDimensionInvalid.msg(format!(
+                        "Time measure method[{}] of breakdown dimension on factor[{}] is not supported.",
+                        dimension
+                            .time_measure_method
+                            .as_ref()
+                            .map(|method| method.to_string())
+                            .unwrap_or_default(),
+                        factor_id
+                    ));
+                    }
+                }
+            }
+            _ => BreakdownDimensionOn::Value,
+        };
+        Ok(CompiledBreakdownDimension {
+            factor_id: factor_id.clone(),
+            on,
+        })
+    }
+
+    /// none when row is ignored
+    fn key_of(
+        dimensions: &[CompiledBreakdownDimension],
+        schema: &Arc<TopicSchema>,
+        row: &ArcTopicData,
+        paths: &RefCell<HashMap<(TopicId, String), DataPath>>,
+        names: &RefCell<HashMap<String, String>>,
+    ) -> StdR<Option<BreakdownKey>> {
+        let in_memory_data = InMemoryData::new(None, Some(row.clone()));
+        let mut key = vec![];
+        for dimension in dimensions {
+            let path_key = (schema.topic_id().to_string(), dimension.factor_id.clone());
+            if !paths.borrow().contains_key(&path_key) {
+                let path = IndicatorService::path_of(schema, &dimension.factor_id)?;
+                paths.borrow_mut().insert(path_key.clone(), path);
+                if let Some(factor) = schema.factor_by_id(&dimension.factor_id) {
+                    names
+                        .borrow_mut()
+                        .entry(dimension.factor_id.clone())
+                        .or_insert_with(|| factor.name.to_string());
+                }
+            }
+            let value = in_memory_data.value_of(&paths.borrow()[&path_key])?;
+            let value = match &dimension.on {
+                BreakdownDimensionOn::Value => BreakdownDimensionValue::Text(Self::text_of(&value)),
+                BreakdownDimensionOn::Bucket(bucket) => match bucket.segment_of(&value) {
+                    Some(index) => BreakdownDimensionValue::Segment(index),
+                    _ => return Ok(None),
+                },
+                BreakdownDimensionOn::Time(part, _) => match ObjectiveFormula::to_datetime(&value)?
+                {
+                    Some(datetime) => BreakdownDimensionValue::Number(part.of(&datetime.date())),
+                    _ => return Ok(None),
+                },
+            };
+            key.push(value);
+        }
+        Ok(Some(key))
+    }
+
+    fn text_of(value: &ArcTopicDataValue) -> String {
+        match value {
+            ArcTopicDataValue::None => String::new(),
+            ArcTopicDataValue::Str(str) => str.to_string(),
+            ArcTopicDataValue::Num(num) => num.normalized().to_string(),
+            other => other.to_string(),
+        }
+    }
+
+    fn cell_of(
+        value: BreakdownDimensionValue,
+        dimension: &CompiledBreakdownDimension,
+    ) -> DataResultSetCell {
+        match (value, &dimension.on) {
+            (BreakdownDimensionValue::Segment(index), BreakdownDimensionOn::Bucket(bucket)) => {
+                DataResultSetCell::Str(bucket.segment_name(index).clone())
+            }
+            (BreakdownDimensionValue::Number(number), _) => {
+                DataResultSetCell::Num(BigDecimal::from(number))
+            }
+            (BreakdownDimensionValue::Text(text), _) => DataResultSetCell::Str(text),
+            (BreakdownDimensionValue::Segment(index), _) => {
+                DataResultSetCell::Num(BigDecimal::from(index as u64))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        IndicatorDataset, ObjectiveResources, ObjectiveService, SubjectDataSource,
+        SubjectSourceCriteria,
+    };
+    use bigdecimal::BigDecimal;
+    use chrono::NaiveDate;
+    use elf_base::StdR;
+    use elf_model::{
+        BreakdownDimension, BreakdownDimensionType, BreakdownTarget, Bucket, BucketId,
+        DataResultSetCell, Factor, FactorType, Indicator, IndicatorAggregateArithmetic,
+        IndicatorBaseOn, IndicatorId, MeasureMethod, Objective, ObjectiveFactorOnIndicator,
+        ObjectiveInExpression, ObjectiveParameterJoint, ObjectiveTarget, ObjectiveTargetAsIs,
+        ObjectiveTimeframe, ObjectiveTimeframeKind, ObjectiveTimeframeTill,
+        ReferObjectiveParameter, TimeframeObjectiveParameter, Topic, TopicData, TopicDataValue,
+        TopicKind, TopicType,
+    };
+    use elf_runtime_model_kernel::TopicSchema;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    struct TestResources;
+
+    impl SubjectDataSource for TestResources {
+        fn rows(
+            &self,
+            _topic: &Arc<TopicSchema>,
+            _criteria: &SubjectSourceCriteria,
+        ) -> StdR<Vec<TopicData>> {
+            let sale = |region: &str, amount: i32, year: i32, month: u32, day: u32| {
+                let mut data = HashMap::new();
+                data.insert(
+                    String::from("region"),
+                    TopicDataValue::Str(region.to_string()),
+                );
+                data.insert(
+                    String::from("amount"),
+                    TopicDataValue::Num(BigDecimal::from(amount)),
+                );
+                data.insert(
+                    String::from("soldDate"),
+                    TopicDataValue::Date(NaiveDate::from_ymd_opt(year, month, day).unwrap()),
+                );
+                data
+            };
+            Ok(vec![
+                sale("west", 5, 2025, 4, 20),
+                sale("east", 10, 2025, 1, 5),
+                sale("east", 20, 2025, 4, 10),
+                sale("west", 100, 2026, 1, 1),
+                sale("north", 7, 2024, 3, 1),
+            ])
+        }
+    }
+
+    impl ObjectiveResources for TestResources {
+        fn indicator(&self, indicator_id: &IndicatorId) -> StdR<Indicator> {
+            Ok(Indicator::new()
+                .indicator_id(indicator_id.clone())
+                .topic_or_subject_id(String::from("sales"))
+                .base_on(IndicatorBaseOn::Topic)
+                .factor_id(String::from("s2"))
+                .aggregate_arithmetic(IndicatorAggregateArithmetic::Sum))
+        }
+
+        fn bucket(&self, _bucket_id: &BucketId) -> StdR<Bucket> {
+            panic!("no bucket")
+        }
+
+        fn dataset(&self, _indicator: &Indicator) -> StdR<IndicatorDataset> {
+            let topic = Topic::new()
+                .topic_id(String::from("sales"))
+                .name(String::from("sales"))
+                .r#type(TopicType::Raw)
+                .kind(TopicKind::Business)
+                .factors(vec![
+                    Factor::new()
+                        .factor_id(String::from("s1"))
+                        .name(String::from("region"))
+                        .r#type(FactorType::Text),
+                    Factor::new()
+                        .factor_id(String::from("s2"))
+                        .name(String::from("amount"))
+                        .r#type(FactorType::Number),
+                    Factor::new()
+                        .factor_id(String::from("s3"))
+                        .name(String::from("soldDate"))
+                        .r#type(FactorType::Date),
+                ])
+                .tenant_id(String::from("tenant-1"));
+            IndicatorDataset::of_topic(Arc::new(TopicSchema::new(topic)?), self)
+        }
+    }
+
+    #[test]
+    fn test_breakdown() {
+        let objective = Objective::new()
+            .time_frame(
+                ObjectiveTimeframe::new()
+                    .kind(ObjectiveTimeframeKind::Year)
+                    .till(ObjectiveTimeframeTill::LastCompleteCycle),
+            )
+            .factors(vec![ObjectiveFactorOnIndicator::init()
+                .uuid(String::from("f1"))
+                .indicator_id(String::from("i1"))
+                .conditional(true)
+                .filter(ObjectiveParameterJoint::and(vec![
+                    ObjectiveInExpression::init()
+                        .left(ReferObjectiveParameter::of(String::from("s3")).to_parameter())
+                        .right(TimeframeObjectiveParameter::init().to_parameter())
+                        .to_condition(),
+                ]))
+                .to_factor()])
+            .targets(vec![ObjectiveTarget::new()
+                .uuid(String::from("t1"))
+                .name(String::from("sales"))
+                .asis(ObjectiveTargetAsIs::Factor(String::from("f1")))]);
+        let breakdown = BreakdownTarget::new()
+            .uuid(String::from("b1"))
+            .target_id(String::from("t1"))
+            .dimensions(vec![
+                BreakdownDimension::new()
+                    .r#type(BreakdownDimensionType::Value)
+                    .factor_or_column_id(String::from("s1")),
+                BreakdownDimension::new()
+                    .r#type(BreakdownDimensionType::TimeRelated)
+                    .factor_or_column_id(String::from("s3"))
+                    .time_measure_method(MeasureMethod::Quarter),
+            ]);
+        let now = NaiveDate::from_ymd_opt(2026, 2, 10)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        let values = ObjectiveService::breakdown_on(&objective, &[breakdown], &TestResources, &now)
+            .unwrap()
+            .pop()
+            .unwrap();
+        assert_eq!(values.failed, Some(false));
+        let data = values.data.unwrap();
+        assert_eq!(
+            data.columns.unwrap(),
+            vec!["region", "soldDate(quarter)", "sales"]
+        );
+        let rows: Vec<String> = data
+            .data
+            .unwrap()
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| match cell {
+                        DataResultSetCell::Str(str) => str.clone(),
+                        DataResultSetCell::Num(num) => num.normalized().to_string(),
+                        other => format!("{:?}", other),
+                    })
+                    .collect::<Vec<String>>()
+                    .join(",")
+            })
+            .collect();
+        assert_eq!(rows, vec!["east,1,10", "east,2,20", "west,2,5"]);
+    }
+}
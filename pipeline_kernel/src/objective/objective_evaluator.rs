@@ -0,0 +1,726 @@
+use crate::{
+    ArcFrom, ArcTopicData, ArcTopicDataValue, DataPath, InMemoryData, IndicatorBucket,
+    IndicatorDataset, IndicatorService, ObjectiveFormula, ObjectiveParameterContext,
+    ObjectiveTimeRange, ObjectiveTimeRanges, ObjectiveValueRange, ObjectiveVariableValue,
+    PipelineKernelErrorCode,
+};
+use bigdecimal::{BigDecimal, Zero};
+use chrono::NaiveDateTime;
+use elf_base::{ErrorCode, NumericUtils, StdR};
+use elf_model::{
+    Bucket, BucketId, Indicator, IndicatorId, Objective, ObjectiveFactor, ObjectiveFactorId,
+    ObjectiveFactorValues, ObjectiveTarget, ObjectiveTargetAsIs, ObjectiveTargetBetterSide,
+    ObjectiveTargetValues, ObjectiveValues, ObjectiveVariable,
+};
+use elf_runtime_model_kernel::TopicSchema;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// resources which objective evaluating depends on.
+pub trait ObjectiveResources {
+    fn indicator(&self, indicator_id: &IndicatorId) -> StdR<Indicator>;
+    fn bucket(&self, bucket_id: &BucketId) -> StdR<Bucket>;
+    /// rows of topic or subject which indicator based on
+    fn dataset(&self, indicator: &Indicator) -> StdR<IndicatorDataset>;
+}
+
+/// additional filter on rows of indicator dataset, with schema of dataset.
+/// applied after factor filter.
+pub type ObjectiveRowFilter<'a> = Box<dyn Fn(&Arc<TopicSchema>, &ArcTopicData) -> StdR<bool> + 'a>;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum ObjectiveCycle {
+    Current,
+    Previous,
+    Chain,
+}
+
+/// evaluate objective, factors are computed lazily and cached by cycle.
+/// - factor on indicator: value of indicator, filtered by factor filter when it is conditional,
+/// - computed factor: value of formula, refer parameter in formula is another factor.
+///
+/// factor is failed when error occurred in computing, value of failed factor is none,
+/// and is treated as none when it is referred by another factor or target.
+pub struct ObjectiveEvaluator<'a, R: ObjectiveResources> {
+    objective: &'a Objective,
+    resources: &'a R,
+    ranges: Option<ObjectiveTimeRanges>,
+    variables: HashMap<String, ObjectiveVariableValue>,
+    buckets: RefCell<HashMap<BucketId, Arc<IndicatorBucket>>>,
+    datasets: RefCell<HashMap<IndicatorId, Arc<IndicatorDataset>>>,
+    values: RefCell<HashMap<(ObjectiveCycle, ObjectiveFactorId), Option<BigDecimal>>>,
+    failed: RefCell<HashSet<(ObjectiveCycle, ObjectiveFactorId)>>,
+    /// factors in computing, to detect circular reference
+    computing: RefCell<Vec<ObjectiveFactorId>>,
+    row_filter: Option<ObjectiveRowFilter<'a>>,
+}
+
+impl<'a, R: ObjectiveResources> ObjectiveEvaluator<'a, R> {
+    pub fn new(objective: &'a Objective, resources: &'a R, now: &NaiveDateTime) -> StdR<Self> {
+        let mut evaluator = Self {
+            objective,
+            resources,
+            ranges: ObjectiveTimeRanges::of(&objective.time_frame, now)?,
+            variables: HashMap::new(),
+            buckets: RefCell::new(HashMap::new()),
+            datasets: RefCell::new(HashMap::new()),
+            values: RefCell::new(HashMap::new()),
+            failed: RefCell::new(HashSet::new()),
+            computing: RefCell::new(vec![]),
+            row_filter: None,
+        };
+        evaluator.variables = evaluator.resolve_variables()?;
+        Ok(evaluator)
+    }
+
+    fn resolve_variables(&self) -> StdR<HashMap<String, ObjectiveVariableValue>> {
+        let mut variables = HashMap::new();
+        for variable in self.objective.variables.iter().flatten() {
+            let (name, value) = match variable {
+                ObjectiveVariable::SingleValue(variable) => (
+                    &variable.name,
+                    ObjectiveVariableValue::Value(variable.value.clone().unwrap_or_default()),
+                ),
+                ObjectiveVariable::Bucket(variable) => {
+                    let Some(bucket_id) = &variable.bucket_id else {
+                        continue;
+                    };
+                    let bucket = self.bucket_of(bucket_id)?;
+                    let segment_name = variable.segment_name.clone().unwrap_or_default();
+                    let Some(index) = bucket.segment_index(&segment_name) else {
+                        return PipelineKernelErrorCode::ObjectiveBucketSegmentNotFound.msg(
+                            format!(
+                                "Segment[{}] not found in bucket[{}].",
+                                segment_name, bucket_id
+                            ),
+                        );
+                    };
+                    (
+                        &variable.name,
+                        ObjectiveVariableValue::Segment(bucket, index),
+                    )
+                }
+                ObjectiveVariable::Range(variable) => (
+                    &variable.name,
+                    ObjectiveVariableValue::Range(ObjectiveValueRange {
+                        min: Self::bound_of(&variable.min)?,
+                        include_min: variable.include_min.unwrap_or(true),
+                        max: Self::bound_of(&variable.max)?,
+                        include_max: variable.include_max.unwrap_or(false),
+                    }),
+                ),
+            };
+            if let Some(name) = name {
+                variables.insert(name.clone(), value);
+            }
+        }
+        Ok(variables)
+    }
+
+    fn bound_of(bound: &Option<String>) -> StdR<Option<BigDecimal>> {
+        match bound {
+            Some(bound) if !bound.trim().is_empty() => ObjectiveFormula::to_decimal(
+                &ArcTopicDataValue::Str(Arc::new(bound.trim().to_string())),
+            ),
+            _ => Ok(None),
+        }
+    }
+
+    fn bucket_of(&self, bucket_id: &BucketId) -> StdR<Arc<IndicatorBucket>> {
+        if let Some(bucket) = self.buckets.borrow().get(bucket_id) {
+            return Ok(bucket.clone());
+        }
+        let bucket = Arc::new(IndicatorBucket::compile(self.resources.bucket(bucket_id)?)?);
+        self.buckets
+            .borrow_mut()
+            .insert(bucket_id.clone(), bucket.clone());
+        Ok(bucket)
+    }
+
+    fn dataset_of(&self, indicator: &Indicator) -> StdR<Arc<IndicatorDataset>> {
+        let indicator_id = indicator.indicator_id.clone().unwrap_or_default();
+        if let Some(dataset) = self.datasets.borrow().get(&indicator_id) {
+            return Ok(dataset.clone());
+        }
+        let dataset = Arc::new(self.resources.dataset(indicator)?);
+        self.datasets
+            .borrow_mut()
+            .insert(indicator_id, dataset.clone());
+        Ok(dataset)
+    }
+
+    /// replace the additional row filter, computed values are cleared.
+    /// buckets and datasets are kept.
+    pub fn filter_rows(&mut self, row_filter: Option<ObjectiveRowFilter<'a>>) {
+        self.row_filter = row_filter;
+        self.values.borrow_mut().clear();
+        self.failed.borrow_mut().clear();
+    }
+
+    /// none when objective is not on time frame
+    fn range_of(&self, cycle: ObjectiveCycle) -> Option<&ObjectiveTimeRange> {
+        self.ranges.as_ref().map(|ranges| match cycle {
+            ObjectiveCycle::Current => &ranges.current,
+            ObjectiveCycle::Previous => &ranges.previous,
+            ObjectiveCycle::Chain => &ranges.chain,
+        })
+    }
+}
+
+impl<'a, R: ObjectiveResources> ObjectiveEvaluator<'a, R> {
+    /// previous and chain values are computed only when objective is on time frame,
+    /// and they are asked by any target.
+    pub fn evaluate(&self) -> StdR<ObjectiveValues> {
+        let targets = self.objective.targets.as_deref().unwrap_or_default();
+        let on_time_frame = self.ranges.is_some();
+        let ask_previous = on_time_frame
+            && targets
+                .iter()
+                .any(|t| t.ask_previous_cycle.unwrap_or(false));
+        let ask_chain = on_time_frame && targets.iter().any(|t| t.ask_chain_cycle.unwrap_or(false));
+
+        let target_values = targets
+            .iter()
+            .map(|target| self.target_values(target, on_time_frame))
+            .collect();
+
+        let mut factor_values = vec![];
+        for factor in self.objective.factors.iter().flatten() {
+            let (uuid, name) = match factor {
+                ObjectiveFactor::Indicator(factor) => (&factor.uuid, &factor.name),
+                ObjectiveFactor::Computed(factor) => (&factor.uuid, &factor.name),
+            };
+            let Some(uuid) = uuid else {
+                continue;
+            };
+            let mut values = ObjectiveFactorValues::new();
+            values.uuid = Some(uuid.clone());
+            values.name = name.clone();
+            values.current_value = self.factor_value(uuid, ObjectiveCycle::Current)?;
+            let mut failed = self.is_failed(uuid, ObjectiveCycle::Current);
+            if ask_previous {
+                values.previous_value = self.factor_value(uuid, ObjectiveCycle::Previous)?;
+                failed = failed || self.is_failed(uuid, ObjectiveCycle::Previous);
+            }
+            if ask_chain {
+                values.chain_value = self.factor_value(uuid, ObjectiveCycle::Chain)?;
+                failed = failed || self.is_failed(uuid, ObjectiveCycle::Chain);
+            }
+            values.failed = Some(failed);
+            factor_values.push(values);
+        }
+
+        let mut values = ObjectiveValues::new()
+            .targets(target_values)
+            .factors(factor_values);
+        values.objective_id = self.objective.objective_id.clone();
+        if let Some(ranges) = &self.ranges {
+            values.current = Some(ranges.current.to_values());
+            if ask_previous {
+                values.previous = Some(ranges.previous.to_values());
+            }
+            if ask_chain {
+                values.chain = Some(ranges.chain.to_values());
+            }
+        }
+        Ok(values)
+    }
+
+    /// as is value of target on current cycle
+    pub fn current_asis(&self, target: &ObjectiveTarget) -> StdR<Option<BigDecimal>> {
+        self.asis_of(target, ObjectiveCycle::Current)
+    }
+
+    /// is any factor failed since created or rows filter replaced
+    pub fn has_failed(&self) -> bool {
+        !self.failed.borrow().is_empty()
+    }
+
+    fn is_failed(&self, uuid: &ObjectiveFactorId, cycle: ObjectiveCycle) -> bool {
+        self.failed.borrow().contains(&(cycle, uuid.clone()))
+    }
+
+    /// value of factor on given cycle, none when factor is failed.
+    /// error only when factor not found, or it is referred circularly.
+    fn factor_value(&self, uuid: &str, cycle: ObjectiveCycle) -> StdR<Option<BigDecimal>> {
+        let key = (cycle, uuid.to_string());
+        if let Some(value) = self.values.borrow().get(&key) {
+            return Ok(value.clone());
+        }
+        if self
+            .computing
+            .borrow()
+            .iter()
+            .any(|computing| computing == uuid)
+        {
+            return PipelineKernelErrorCode::ObjectiveFactorCircularReference.msg(format!(
+                "Objective factor[{}] is referred circularly, via [{}].",
+                uuid,
+                self.computing.borrow().join(" -> ")
+            ));
+        }
+        let Some(factor) = self.objective.factors.iter().flatten().find(|f| match f {
+            ObjectiveFactor::Indicator(factor) => factor.uuid.as_deref() == Some(uuid),
+            ObjectiveFactor::Computed(factor) => factor.uuid.as_deref() == Some(uuid),
+        }) else {
+            return PipelineKernelErrorCode::ObjectiveFactorNotFound
+                .msg(format!("Objective factor[{}] not found.", uuid));
+        };
+
+        self.computing.borrow_mut().push(uuid.to_string());
+        let computed = self.compute_factor(factor, cycle);
+        self.computing.borrow_mut().pop();
+
+        let value = match computed {
+            Ok(value) => value,
+            Err(_) => {
+                self.failed.borrow_mut().insert(key.clone());
+                None
+            }
+        };
+        self.values.borrow_mut().insert(key, value.clone());
+        Ok(value)
+    }
+
+    fn compute_factor(
+        &self,
+        factor: &ObjectiveFactor,
+        cycle: ObjectiveCycle,
+    ) -> StdR<Option<BigDecimal>> {
+        let context = ObjectiveCycleContext {
+            evaluator: self,
+            cycle,
+        };
+        match factor {
+            ObjectiveFactor::Indicator(factor) => {
+                let Some(indicator_id) = &factor.indicator_id else {
+                    return PipelineKernelErrorCode::IndicatorIdMissed.msg(format!(
+                        "Indicator of objective factor[{}] is missed.",
+                        factor.uuid.as_deref().unwrap_or("")
+                    ));
+                };
+                let indicator = self.resources.indicator(indicator_id)?;
+                let dataset = self.dataset_of(&indicator)?;
+                let filter = match &factor.filter {
+                    Some(filter) if factor.conditional.unwrap_or(false) => Some(filter),
+                    _ => None,
+                };
+                let paths = RefCell::new(HashMap::new());
+                let value = IndicatorService::value_on(indicator, &dataset, |row| {
+                    let accepted = match filter {
+                        Some(filter) => ObjectiveFormula::is_true(
+                            filter,
+                            &ObjectiveRowContext {
+                                context: &context,
+                                schema: dataset.schema(),
+                                paths: &paths,
+                                row,
+                            },
+                        )?,
+                        _ => true,
+                    };
+                    match &self.row_filter {
+                        Some(row_filter) if accepted => row_filter(dataset.schema(), row),
+                        _ => Ok(accepted),
+                    }
+                })?;
+                ObjectiveFormula::to_decimal(&value)
+            }
+            ObjectiveFactor::Computed(factor) => match &factor.formula {
+                Some(formula) => ObjectiveFormula::decimal_computed_of(formula, &context),
+                _ => PipelineKernelErrorCode::ObjectiveParameterMissed.msg(format!(
+                    "Formula of objective factor[{}] is missed.",
+                    factor.uuid.as_deref().unwrap_or("")
+                )),
+            },
+        }
+    }
+}
+
+impl<'a, R: ObjectiveResources> ObjectiveEvaluator<'a, R> {
+    fn target_values(
+        &self,
+        target: &ObjectiveTarget,
+        on_time_frame: bool,
+    ) -> ObjectiveTargetValues {
+        let mut values = ObjectiveTargetValues::new();
+        values.uuid = target.uuid.clone();
+        values.name = target.name.clone();
+        match self.compute_target(target, on_time_frame, &mut values) {
+            Ok(_) => values.failed = Some(false),
+            Err(_) => {
+                values = ObjectiveTargetValues::new().failed(true);
+                values.uuid = target.uuid.clone();
+                values.name = target.name.clone();
+            }
+        }
+        values
+    }
+
+    fn compute_target(
+        &self,
+        target: &ObjectiveTarget,
+        on_time_frame: bool,
+        values: &mut ObjectiveTargetValues,
+    ) -> StdR<()> {
+        let tobe = self.tobe_of(target)?;
+        let current = self.asis_of(target, ObjectiveCycle::Current)?;
+        let more_is_better = !matches!(target.better_side, Some(ObjectiveTargetBetterSide::Less));
+
+        if let (Some(tobe), Some(current)) = (&tobe, &current) {
+            let (numerator, denominator) = match more_is_better {
+                true => (current, tobe),
+                false => (tobe, current),
+            };
+            values.achievement_rate = Self::rate(numerator, denominator);
+            values.achieved = Some(match more_is_better {
+                true => current >= tobe,
+                false => current <= tobe,
+            });
+        }
+        if on_time_frame && target.ask_previous_cycle.unwrap_or(false) {
+            values.previous_value = self.asis_of(target, ObjectiveCycle::Previous)?;
+            values.previous_change_rate = Self::change_rate(&current, &values.previous_value);
+        }
+        if on_time_frame && target.ask_chain_cycle.unwrap_or(false) {
+            values.chain_value = self.asis_of(target, ObjectiveCycle::Chain)?;
+            values.chain_change_rate = Self::change_rate(&current, &values.chain_value);
+        }
+        values.tobe = tobe;
+        values.current_value = current;
+        Ok(())
+    }
+
+    /// to be value is numeric, or percentage (e.g. 80% is 0.8), thousands separators are ignored.
+    /// variables are substituted.
+    fn tobe_of(&self, target: &ObjectiveTarget) -> StdR<Option<BigDecimal>> {
+        let context = ObjectiveCycleContext {
+            evaluator: self,
+            cycle: ObjectiveCycle::Current,
+        };
+        let tobe = ObjectiveFormula::substitute(target.tobe.as_deref().unwrap_or(""), &context)
+            .replace(',', "");
+        let tobe = tobe.trim();
+        if tobe.is_empty() {
+            return Ok(None);
+        }
+        let (tobe, percentage) = match tobe.strip_suffix('%') {
+            Some(tobe) => (tobe.trim().to_string(), true),
+            _ => (tobe.to_string(), false),
+        };
+        if !tobe.is_numeric() {
+            return PipelineKernelErrorCode::ObjectiveValueNotNumeric.msg(format!(
+                "To be value[{}] of objective target[{}] is not numeric.",
+                target.tobe.as_deref().unwrap_or(""),
+                target.uuid.as_deref().unwrap_or("")
+            ));
+        }
+        let tobe = tobe.to_decimal()?;
+        Ok(Some(match percentage {
+            true => tobe / BigDecimal::from(100),
+            false => tobe,
+        }))
+    }
+
+    fn asis_of(&self, target: &ObjectiveTarget, cycle: ObjectiveCycle) -> StdR<Option<BigDecimal>> {
+        match &target.asis {
+            Some(ObjectiveTargetAsIs::Factor(uuid)) => self.factor_value(uuid, cycle),
+            Some(ObjectiveTargetAsIs::Parameter(parameter)) => {
+                ObjectiveFormula::decimal_computed_of(
+                    parameter,
+                    &ObjectiveCycleContext {
+                        evaluator: self,
+                        cycle,
+                    },
+                )
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// rounded to 4 decimal places, none when denominator is zero
+    fn rate(numerator: &BigDecimal, denominator: &BigDecimal) -> Option<BigDecimal> {
+        if denominator.is_zero() {
+            None
+        } else {
+            Some((numerator / denominator).round(4))
+        }
+    }
+
+    /// (current - base) / base
+    fn change_rate(current: &Option<BigDecimal>, base: &Option<BigDecimal>) -> Option<BigDecimal> {
+        match (current, base) {
+            (Some(current), Some(base)) => Self::rate(&(current - base), base),
+            _ => None,
+        }
+    }
+}
+
+/// context of formula, on cycle. refer is another factor.
+struct ObjectiveCycleContext<'e, 'a, R: ObjectiveResources> {
+    evaluator: &'e ObjectiveEvaluator<'a, R>,
+    cycle: ObjectiveCycle,
+}
+
+impl<'e, 'a, R: ObjectiveResources> ObjectiveParameterContext for ObjectiveCycleContext<'e, 'a, R> {
+    fn refer(&self, uuid: &str) -> StdR<Arc<ArcTopicDataValue>> {
+        Ok(match self.evaluator.factor_value(uuid, self.cycle)? {
+            Some(value) => ArcTopicDataValue::arc_from(value),
+            _ => Arc::new(ArcTopicDataValue::None),
+        })
+    }
+
+    fn variable(&self, name: &str) -> Option<&ObjectiveVariableValue> {
+        self.evaluator.variables.get(name)
+    }
+
+    fn bucket(&self, bucket_id: &BucketId) -> StdR<Arc<IndicatorBucket>> {
+        self.evaluator.bucket_of(bucket_id)
+    }
+
+    fn time_range(&self) -> Option<&ObjectiveTimeRange> {
+        self.evaluator.range_of(self.cycle)
+    }
+}
+
+/// context of factor filter, refer is factor (or column) of row.
+struct ObjectiveRowContext<'c, 'e, 'a, R: ObjectiveResources> {
+    context: &'c ObjectiveCycleContext<'e, 'a, R>,
+    schema: &'c Arc<TopicSchema>,
+    /// paths of factors, shared by rows
+    paths: &'c RefCell<HashMap<String, DataPath>>,
+    row: &'c ArcTopicData,
+}
+
+impl<'c, 'e, 'a, R: ObjectiveResources> ObjectiveParameterContext
+    for ObjectiveRowContext<'c, 'e, 'a, R>
+{
+    fn refer(&self, uuid: &str) -> StdR<Arc<ArcTopicDataValue>> {
+        if !self.paths.borrow().contains_key(uuid) {
+            let path = IndicatorService::path_of(self.schema, &uuid.to_string())?;
+            self.paths.borrow_mut().insert(uuid.to_string(), path);
+        }
+        let paths = self.paths.borrow();
+        InMemoryData::new(None, Some(self.row.clone()))
+            .current_only()
+            .value_of(&paths[uuid])
+    }
+
+    fn variable(&self, name: &str) -> Option<&ObjectiveVariableValue> {
+        self.context.variable(name)
+    }
+
+    fn bucket(&self, bucket_id: &BucketId) -> StdR<Arc<IndicatorBucket>> {
+        self.context.bucket(bucket_id)
+    }
+
+    fn time_range(&self) -> Option<&ObjectiveTimeRange> {
+        self.context.time_range()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        IndicatorDataset, ObjectiveResources, ObjectiveService, SubjectDataSource,
+        SubjectSourceCriteria,
+    };
+    use bigdecimal::BigDecimal;
+    use chrono::NaiveDate;
+    use elf_base::StdR;
+    use elf_model::{
+        Bucket, BucketId, ConstantObjectiveParameter, Factor, FactorType, Indicator,
+        IndicatorAggregateArithmetic, IndicatorBaseOn, IndicatorId, Objective,
+        ObjectiveAddParameter, ObjectiveDivideParameter, ObjectiveFactorOnComputation,
+        ObjectiveFactorOnIndicator, ObjectiveInExpression, ObjectiveMultiplyParameter,
+        ObjectiveParameter, ObjectiveParameterJoint, ObjectiveTarget, ObjectiveTargetAsIs,
+        ObjectiveTargetBetterSide, ObjectiveTimeframe, ObjectiveTimeframeKind,
+        ObjectiveTimeframeTill, ReferObjectiveParameter, TimeframeObjectiveParameter, Topic,
+        TopicData, TopicDataValue, TopicKind, TopicType,
+    };
+    use elf_runtime_model_kernel::TopicSchema;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    struct TestResources;
+
+    impl SubjectDataSource for TestResources {
+        fn rows(
+            &self,
+            _topic: &Arc<TopicSchema>,
+            _criteria: &SubjectSourceCriteria,
+        ) -> StdR<Vec<TopicData>> {
+            let sale = |amount: i32, year: i32, month: u32, day: u32| {
+                let mut data = HashMap::new();
+                data.insert(
+                    String::from("amount"),
+                    TopicDataValue::Num(BigDecimal::from(amount)),
+                );
+                data.insert(
+                    String::from("soldDate"),
+                    TopicDataValue::Date(NaiveDate::from_ymd_opt(year, month, day).unwrap()),
+                );
+                data
+            };
+            Ok(vec![
+                sale(30, 2026, 1, 5),
+                sale(50, 2026, 1, 31),
+                sale(40, 2025, 12, 1),
+                sale(100, 2025, 1, 20),
+                sale(1000, 2026, 2, 1),
+            ])
+        }
+    }
+
+    impl ObjectiveResources for TestResources {
+        fn indicator(&self, indicator_id: &IndicatorId) -> StdR<Indicator> {
+            Ok(Indicator::new()
+                .indicator_id(indicator_id.clone())
+                .topic_or_subject_id(String::from("sales"))
+                .base_on(IndicatorBaseOn::Topic)
+                .factor_id(String::from("s1"))
+                .aggregate_arithmetic(IndicatorAggregateArithmetic::Sum))
+        }
+
+        fn bucket(&self, _bucket_id: &BucketId) -> StdR<Bucket> {
+            panic!("no bucket")
+        }
+
+        fn dataset(&self, _indicator: &Indicator) -> StdR<IndicatorDataset> {
+            let topic = Topic::new()
+                .topic_id(String::from("sales"))
+                .name(String::from("sales"))
+                .r#type(TopicType::Raw)
+                .kind(TopicKind::Business)
+                .factors(vec![
+                    Factor::new()
+                        .factor_id(String::from("s1"))
+                        .name(String::from("amount"))
+                        .r#type(FactorType::Number),
+                    Factor::new()
+                        .factor_id(String::from("s2"))
+                        .name(String::from("soldDate"))
+                        .r#type(FactorType::Date),
+                ])
+                .tenant_id(String::from("tenant-1"));
+            IndicatorDataset::of_topic(Arc::new(TopicSchema::new(topic)?), self)
+        }
+    }
+
+    fn refer(uuid: &str) -> ObjectiveParameter {
+        ReferObjectiveParameter::of(uuid.to_string()).to_parameter()
+    }
+
+    fn constant(value: &str) -> ObjectiveParameter {
+        ConstantObjectiveParameter::of(value.to_string()).to_parameter()
+    }
+
+    fn text(value: &Option<BigDecimal>) -> String {
+        value
+            .as_ref()
+            .map(|value| value.normalized().to_string())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let objective = Objective::new()
+            .objective_id(String::from("o1"))
+            .time_frame(
+                ObjectiveTimeframe::new()
+                    .kind(ObjectiveTimeframeKind::Month)
+                    .till(ObjectiveTimeframeTill::LastCompleteCycle),
+            )
+            .factors(vec![
+                ObjectiveFactorOnIndicator::init()
+                    .uuid(String::from("f1"))
+                    .indicator_id(String::from("i1"))
+                    .conditional(true)
+                    .filter(ObjectiveParameterJoint::and(vec![
+                        ObjectiveInExpression::init()
+                            .left(refer("s2"))
+                            .right(TimeframeObjectiveParameter::init().to_parameter())
+                            .to_condition(),
+                    ]))
+                    .to_factor(),
+                ObjectiveFactorOnComputation::init()
+                    .uuid(String::from("f2"))
+                    .formula(
+                        ObjectiveMultiplyParameter::init()
+                            .parameters(vec![refer("f1"), constant("2")])
+                            .to_computed(),
+                    )
+                    .to_factor(),
+                // refer itself
+                ObjectiveFactorOnComputation::init()
+                    .uuid(String::from("f3"))
+                    .formula(
+                        ObjectiveAddParameter::init()
+                            .parameters(vec![refer("f3"), constant("1")])
+                            .to_computed(),
+                    )
+                    .to_factor(),
+            ])
+            .targets(vec![
+                ObjectiveTarget::new()
+                    .uuid(String::from("t1"))
+                    .tobe(String::from("100"))
+                    .asis(ObjectiveTargetAsIs::Factor(String::from("f1")))
+                    .better_side(ObjectiveTargetBetterSide::More)
+                    .ask_previous_cycle(true)
+                    .ask_chain_cycle(true),
+                ObjectiveTarget::new()
+                    .uuid(String::from("t2"))
+                    .tobe(String::from("80%"))
+                    .asis(ObjectiveTargetAsIs::Parameter(
+                        ObjectiveDivideParameter::init()
+                            .parameters(vec![refer("f1"), constant("200")])
+                            .to_computed(),
+                    ))
+                    .better_side(ObjectiveTargetBetterSide::Less),
+                ObjectiveTarget::new()
+                    .uuid(String::from("t3"))
+                    .tobe(String::from("abc"))
+                    .asis(ObjectiveTargetAsIs::Factor(String::from("f1"))),
+            ]);
+        let now = NaiveDate::from_ymd_opt(2026, 2, 10)
+            .unwrap()
+            .and_hms_opt(8, 0, 0)
+            .unwrap();
+        let values = ObjectiveService::evaluate_on(&objective, &TestResources, &now).unwrap();
+
+        let current = values.current.unwrap();
+        assert_eq!(
+            current.start.unwrap(),
+            NaiveDate::from_ymd_opt(2026, 1, 1)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
+        );
+
+        let targets = values.targets.unwrap();
+        let t1 = &targets[0];
+        assert_eq!(text(&t1.current_value), "80");
+        assert_eq!(text(&t1.achievement_rate), "0.8");
+        assert_eq!(t1.achieved, Some(false));
+        assert_eq!(text(&t1.previous_value), "40");
+        assert_eq!(text(&t1.previous_change_rate), "1");
+        assert_eq!(text(&t1.chain_value), "100");
+        assert_eq!(text(&t1.chain_change_rate), "-0.2");
+        let t2 = &targets[1];
+        assert_eq!(text(&t2.tobe), "0.8");
+        assert_eq!(text(&t2.current_value), "0.4");
+        assert_eq!(text(&t2.achievement_rate), "2");
+        assert_eq!(t2.achieved, Some(true));
+        assert_eq!(t2.previous_value, None);
+        assert_eq!(targets[2].failed, Some(true));
+
+        let factors = values.factors.unwrap();
+        assert_eq!(text(&factors[1].current_value), "160");
+        assert_eq!(text(&factors[1].previous_value), "80");
+        assert_eq!(factors[1].failed, Some(false));
+        assert_eq!(factors[2].current_value, None);
+        assert_eq!(factors[2].failed, Some(true));
+    }
+}
@@ -0,0 +1,851 @@
+use crate::{
+    ArcFrom, ArcTopicDataValue, IndicatorBucket, ObjectiveTimeRange, PipelineKernelErrorCode,
+};
+use bigdecimal::{BigDecimal, RoundingMode, Zero};
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime};
+use elf_base::{DateConstValues, DateTimeUtils, ErrorCode, NumericUtils, StdR};
+use elf_model::{
+    BucketId, ComputedObjectiveParameter, MeasureMethod, ObjectiveCaseThenParameterRoute,
+    ObjectiveParameter, ObjectiveParameterCondition, ObjectiveParameterExpression,
+    ObjectiveParameterJoint, ObjectiveParameterJointType,
+};
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// numeric range of range variable
+#[derive(Clone)]
+pub struct ObjectiveValueRange {
+    /// no lower bound when not declared
+    pub min: Option<BigDecimal>,
+    pub include_min: bool,
+    /// no upper bound when not declared
+    pub max: Option<BigDecimal>,
+    pub include_max: bool,
+}
+
+impl ObjectiveValueRange {
+    pub fn contains(&self, value: &BigDecimal) -> bool {
+        let above_min = match &self.min {
+            Some(min) if self.include_min => value >= min,
+            Some(min) => value > min,
+            _ => true,
+        };
+        let below_max = match &self.max {
+            Some(max) if self.include_max => value <= max,
+            Some(max) => value < max,
+            _ => true,
+        };
+        above_min && below_max
+    }
+}
+
+/// date part, of date part computed parameters and time measure methods.
+/// week starts from sunday, day of week is 1 (sunday) to 7 (saturday).
+#[derive(Clone, Copy)]
+pub enum ObjectiveDatePart {
+    Year,
+    HalfYear,
+    Quarter,
+    Month,
+    WeekOfYear,
+    WeekOfMonth,
+    DayOfMonth,
+    DayOfWeek,
+}
+
+impl ObjectiveDatePart {
+    /// none when measure method is not on date part
+    pub fn of_measure_method(method: &MeasureMethod) -> Option<Self> {
+        match method {
+            MeasureMethod::Year => Some(Self::Year),
+            MeasureMethod::HalfYear => Some(Self::HalfYear),
+            MeasureMethod::Quarter => Some(Self::Quarter),
+            MeasureMethod::Month => Some(Self::Month),
+            MeasureMethod::WeekOfYear => Some(Self::WeekOfYear),
+            MeasureMethod::WeekOfMonth => Some(Self::WeekOfMonth),
+            MeasureMethod::DayOfMonth => Some(Self::DayOfMonth),
+            MeasureMethod::DayOfWeek => Some(Self::DayOfWeek),
+            _ => None,
+        }
+    }
+
+    pub fn of(&self, date: &NaiveDate) -> u32 {
+        match self {
+            Self::Year => date.year() as u32,
+            Self::HalfYear => date.half_year() as u32,
+            Self::Quarter => DateConstValues::quarter(date) as u32,
+            Self::Month => date.month(),
+            Self::WeekOfYear => date.week_of_year() as u32,
+            Self::WeekOfMonth => date.week_of_month() as u32,
+            Self::DayOfMonth => date.day(),
+            Self::DayOfWeek => date.weekday().number_from_sunday(),
+        }
+    }
+}
+
+/// resolved value of objective variable
+pub enum ObjectiveVariableValue {
+    Value(String),
+    /// bucket and index of segment
+    Segment(Arc<IndicatorBucket>, usize),
+    Range(ObjectiveValueRange),
+}
+
+/// operand of objective parameter.
+/// segment, range and time range can only be used as the right side of equals/in/not-in expressions.
+pub enum ObjectiveOperand {
+    Value(Arc<ArcTopicDataValue>),
+    /// bucket and index of segment
+    Segment(Arc<IndicatorBucket>, usize),
+    Range(ObjectiveValueRange),
+    /// none when objective is not on time frame, then everything is in
+    TimeRange(Option<ObjectiveTimeRange>),
+}
+
+/// context of objective parameter evaluating.
+pub trait ObjectiveParameterContext {
+    /// value of referred.
+    /// - in factor or target formula, it is the value of another objective factor,
+    /// - in factor filter, it is the value of factor (or column) of row.
+    fn refer(&self, uuid: &str) -> StdR<Arc<ArcTopicDataValue>>;
+    fn variable(&self, name: &str) -> Option<&ObjectiveVariableValue>;
+    fn bucket(&self, bucket_id: &BucketId) -> StdR<Arc<IndicatorBucket>>;
+    /// none when objective is not on time frame
+    fn time_range(&self) -> Option<&ObjectiveTimeRange>;
+}
+
+/// evaluate objective parameters and conditions.
+/// - constant value is a literal, variables are substituted by "{name}",
+///   numeric literal is treated as decimal, blank literal is treated as none,
+/// - arithmetic of computed parameter is on decimals, none is returned if any operand is none,
+///   and none is returned when divided by zero.
+pub struct ObjectiveFormula;
+
+impl ObjectiveFormula {
+    pub fn value_of<C: ObjectiveParameterContext>(
+        parameter: &ObjectiveParameter,
+        context: &C,
+    ) -> StdR<Arc<ArcTopicDataValue>> {
+        match Self::operand_of(parameter, context)? {
+            ObjectiveOperand::Value(value) => Ok(value),
+            _ => PipelineKernelErrorCode::ObjectiveParameterNotSupported
+                .msg("Bucket, range or time frame cannot be used as value."),
+        }
+    }
+
+    pub fn decimal_value_of<C: ObjectiveParameterContext>(
+        parameter: &ObjectiveParameter,
+        context: &C,
+    ) -> StdR<Option<BigDecimal>> {
+        let value = Self::value_of(parameter, context)?;
+        Self::to_decimal(&value)
+    }
+
+    pub fn decimal_computed_of<C: ObjectiveParameterContext>(
+        computed: &ComputedObjectiveParameter,
+        context: &C,
+    ) -> StdR<Option<BigDecimal>> {
+        let value = Self::computed(computed, context)?;
+        Self::to_decimal(&value)
+    }
+
+    pub fn operand_of<C: ObjectiveParameterContext>(
+        parameter: &ObjectiveParameter,
+        context: &C,
+    ) -> StdR<ObjectiveOperand> {
+        match parameter {
+            ObjectiveParameter::Refer(refer) => match &refer.uuid {
+                Some(uuid) => Ok(ObjectiveOperand::Value(context.refer(uuid)?)),
+                _ => PipelineKernelErrorCode::ObjectiveParameterMissed
+                    .msg("Uuid of refer parameter is missed."),
+            },
+            ObjectiveParameter::Constant(constant) => {
+                Self::constant(constant.value.as_deref().unwrap_or(""), context)
+            }
+            ObjectiveParameter::Computed(computed) => {
+                Ok(ObjectiveOperand::Value(Self::computed(computed, context)?))
+            }
+            ObjectiveParameter::Bucket(bucket) => {
+                let Some(bucket_id) = &bucket.bucket_id else {
+                    return PipelineKernelErrorCode::ObjectiveParameterMissed
+                        .msg("Bucket id of bucket parameter is missed.");
+                };
+                let segment_name = bucket.segment_name.clone().unwrap_or_default();
+                let bucket = context.bucket(bucket_id)?;
+                match bucket.segment_index(&segment_name) {
+                    Some(index) => Ok(ObjectiveOperand::Segment(bucket, index)),
+                    _ => PipelineKernelErrorCode::ObjectiveBucketSegmentNotFound.msg(format!(
+                        "Segment[{}] not found in bucket[{}].",
+                        segment_name, bucket_id
+                    )),
+                }
+            }
+            ObjectiveParameter::Timeframe(_) => {
+                Ok(ObjectiveOperand::TimeRange(context.time_range().copied()))
+            }
+        }
+    }
+
+    fn constant<C: ObjectiveParameterContext>(value: &str, context: &C) -> StdR<ObjectiveOperand> {
+        let trimmed = value.trim();
+        // whole value is a bucket or range variable
+        if let Some(name) = trimmed
+            .strip_prefix('{')
+            .and_then(|name| name.strip_suffix('}'))
+        {
+            match context.variable(name.trim()) {
+                Some(ObjectiveVariableValue::Segment(bucket, index)) => {
+                    return Ok(ObjectiveOperand::Segment(bucket.clone(), *index));
+                }
+                Some(ObjectiveVariableValue::Range(range)) => {
+                    return Ok(ObjectiveOperand::Range(range.clone()));
+                }
+                _ => {}
+            }
+        }
+
+        let value = Self::substitute(value, context);
+        let value = value.trim();
+        Ok(ObjectiveOperand::Value(if value.is_empty() {
+            Arc::new(ArcTopicDataValue::None)
+        } else {
+            let value = value.to_string();
+            match value.is_numeric() {
+                true => ArcTopicDataValue::arc_from(value.to_decimal()?),
+                false => ArcTopicDataValue::arc_from(value),
+            }
+        }))
+    }
+
+    /// replace "{name}" with value of single value variable, keep it when variable not found.
+    pub fn substitute<C: ObjectiveParameterContext>(value: &str, context: &C) -> String {
+        let mut substituted = String::new();
+        let mut rest = value;
+        while let Some(start) = rest.find('{') {
+            let Some(end) = rest[start..].find('}') else {
+                break;
+            };
+            let end = start + end;
+            substituted.push_str(&rest[..start]);
+            match context.variable(rest[start + 1..end].trim()) {
+                Some(ObjectiveVariableValue::Value(value)) => substituted.push_str(value),
+                _ => substituted.push_str(&rest[start..=end]),
+            }
+            rest = &rest[end + 1..];
+        }
+        substituted.push_str(rest);
+        substituted
+    }
+}
+
+impl ObjectiveFormula {
+    fn computed<C: ObjectiveParameterContext>(
+        computed: &ComputedObjectiveParameter,
+        context: &C,
+    ) -> StdR<Arc<ArcTopicDataValue>> {
+        let value = match computed {
+            ComputedObjectiveParameter::None(p) => {
+                return match p.parameters.as_ref().and_then(|p| p.first()) {
+                    Some(parameter) => Self::value_of(parameter, context),
+                    _ => Ok(Arc::new(ArcTopicDataValue::None)),
+                };
+            }
+            ComputedObjectiveParameter::Add(p) => {
+                Self::fold(Self::decimals(&p.parameters, context)?, |a, b| Some(a + b))
+            }
+            ComputedObjectiveParameter::Subtract(p) => {
+                Self::fold(Self::decimals(&p.parameters, context)?, |a, b| Some(a - b))
+            }
+            ComputedObjectiveParameter::Multiply(p) => {
+                Self::fold(Self::decimals(&p.parameters, context)?, |a, b| Some(a * b))
+            }
+            ComputedObjectiveParameter::Divide(p) => {
+                Self::fold(Self::decimals(&p.parameters, context)?, |a, b| {
+                    if b.is_zero() {
+                        None
+                    } else {
+                        Some(a / b)
+                    }
+                })
+            }
+            ComputedObjectiveParameter::Modulus(p) => {
+                Self::fold(Self::decimals(&p.parameters, context)?, |a, b| {
+                    if b.is_zero() {
+                        None
+                    } else {
+                        Some(a % b)
+                    }
+                })
+            }
+            ComputedObjectiveParameter::YearOf(p) => {
+                Self::date_part(&p.parameter, context, ObjectiveDatePart::Year)?
+            }
+            ComputedObjectiveParameter::HalfYearOf(p) => {
+                Self::date_part(&p.parameter, context, ObjectiveDatePart::HalfYear)?
+            }
+            ComputedObjectiveParameter::QuarterOf(p) => {
+                Self::date_part(&p.parameter, context, ObjectiveDatePart::Quarter)?
+            }
+            ComputedObjectiveParameter::MonthOf(p) => {
+                Self::date_part(&p.parameter, context, ObjectiveDatePart::Month)?
+            }
+            ComputedObjectiveParameter::WeekOfYear(p) => {
+                Self::date_part(&p.parameter, context, ObjectiveDatePart::WeekOfYear)?
+            }
+            ComputedObjectiveParameter::WeekOfMonth(p) => {
+                Self::date_part(&p.parameter, context, ObjectiveDatePart::WeekOfMonth)?
+            }
+            ComputedObjectiveParameter::DayOfMonth(p) => {
+                Self::date_part(&p.parameter, context, ObjectiveDatePart::DayOfMonth)?
+            }
+            ComputedObjectiveParameter::DayOfWeek(p) => {
+                Self::date_part(&p.parameter, context, ObjectiveDatePart::DayOfWeek)?
+            }
+            ComputedObjectiveParameter::Round(p) => {
+                Self::decimal(&p.parameter, context)?.map(|value| value.round(0))
+            }
+            ComputedObjectiveParameter::Floor(p) => Self::decimal(&p.parameter, context)?
+                .map(|value| value.with_scale_round(0, RoundingMode::Floor)),
+            ComputedObjectiveParameter::Ceil(p) => Self::decimal(&p.parameter, context)?
+                .map(|value| value.with_scale_round(0, RoundingMode::Ceiling)),
+            ComputedObjectiveParameter::Abs(p) => {
+                Self::decimal(&p.parameter, context)?.map(|value| value.abs())
+            }
+            ComputedObjectiveParameter::Max(p) => Self::decimals(&p.parameters, context)?
+                .into_iter()
+                .flatten()
+                .max(),
+            ComputedObjectiveParameter::Min(p) => Self::decimals(&p.parameters, context)?
+                .into_iter()
+                .flatten()
+                .min(),
+            ComputedObjectiveParameter::Interpolate(p) => {
+                Self::interpolate(Self::decimals(&p.parameters, context)?)?
+            }
+            ComputedObjectiveParameter::CaseThen(p) => {
+                return Self::case_then(&p.parameters, context);
+            }
+        };
+        Ok(match value {
+            Some(value) => ArcTopicDataValue::arc_from(value),
+            _ => Arc::new(ArcTopicDataValue::None),
+        })
+    }
+
+    fn decimals<C: ObjectiveParameterContext>(
+        parameters: &Option<Vec<ObjectiveParameter>>,
+        context: &C,
+    ) -> StdR<Vec<Option<BigDecimal>>> {
+        let parameters = parameters.as_deref().unwrap_or_default();
+        if parameters.is_empty() {
+            return PipelineKernelErrorCode::ObjectiveParameterMissed
+                .msg("Parameters of computed parameter are missed.");
+        }
+        parameters
+            .iter()
+            .map(|parameter| Self::decimal_value_of(parameter, context))
+            .collect()
+    }
+
+    fn decimal<C: ObjectiveParameterContext>(
+        parameter: &Option<Box<ObjectiveParameter>>,
+        context: &C,
+    ) -> StdR<Option<BigDecimal>> {
+        match parameter {
+            Some(parameter) => Self::decimal_value_of(parameter, context),
+            _ => PipelineKernelErrorCode::ObjectiveParameterMissed
+                .msg("Parameter of computed parameter is missed."),
+        }
+    }
+
+    /// none when any is none
+    fn fold<F>(values: Vec<Option<BigDecimal>>, f: F) -> Option<BigDecimal>
+    where
+        F: Fn(BigDecimal, BigDecimal) -> Option<BigDecimal>,
+    {
+        let mut values = values.into_iter();
+        let first = values.next()??;
+        values.try_fold(first, |a, b| f(a, b?))
+    }
+
+    fn date_part<C: ObjectiveParameterContext>(
+        parameter: &Option<Box<ObjectiveParameter>>,
+        context: &C,
+        part: ObjectiveDatePart,
+    ) -> StdR<Option<BigDecimal>> {
+        let Some(parameter) = parameter else {
+            return PipelineKernelErrorCode::ObjectiveParameterMissed
+                .msg("Parameter of computed parameter is missed.");
+        };
+        let value = Self::value_of(parameter, context)?;
+        Ok(Self::to_datetime(&value)?.map(|datetime| BigDecimal::from(part.of(&datetime.date()))))
+    }
+
+    /// parameters are [value, min value, min result, max value, max result].
+    /// returns min result when value is less than min value, max result when value is more than max value,
+    /// otherwise linear interpolated.
+    fn interpolate(values: Vec<Option<BigDecimal>>) -> StdR<Option<BigDecimal>> {
+        let [value, min_value, min_result, max_value, max_result]: [Option<BigDecimal>; 5] =
+            match values.try_into() {
+                Ok(values) => values,
+                _ => {
+                    return PipelineKernelErrorCode::ObjectiveParameterMissed
+                        .msg("Interpolate requires 5 parameters.");
+                }
+            };
+        let (Some(value), Some(min_value), Some(min_result), Some(max_value), Some(max_result)) =
+            (value, min_value, min_result, max_value, max_result)
+        else {
+            return Ok(None);
+        };
+        Ok(Some(if value <= min_value {
+            min_result
+        } else if value >= max_value {
+            max_result
+        } else {
+            &min_result
+                + (value - &min_value) * (max_result - &min_result) / (max_value - min_value)
+        }))
+    }
+
+    /// first route which is true, or the default route
+    fn case_then<C: ObjectiveParameterContext>(
+        routes: &Option<Vec<ObjectiveCaseThenParameterRoute>>,
+        context: &C,
+    ) -> StdR<Arc<ArcTopicDataValue>> {
+        let routes = routes.as_deref().unwrap_or_default();
+        let mut default = None;
+        for route in routes {
+            if route.conditional.unwrap_or(false) {
+                let matched = match &route.on {
+                    Some(joint) => Self::is_true(joint, context)?,
+                    _ => true,
+                };
+                if matched {
+                    return Self::route_value(route, context);
+                }
+            } else if default.is_none() {
+                default = Some(route);
+            }
+        }
+        match default {
+            Some(route) => Self::route_value(route, context),
+            _ => Ok(Arc::new(ArcTopicDataValue::None)),
+        }
+    }
+
+    fn route_value<C: ObjectiveParameterContext>(
+        route: &ObjectiveCaseThenParameterRoute,
+        context: &C,
+    ) -> StdR<Arc<ArcTopicDataValue>> {
+        match &route.parameter {
+            Some(parameter) => Self::value_of(parameter, context),
+            _ => PipelineKernelErrorCode::ObjectiveParameterMissed
+                .msg("Parameter of case then route is missed."),
+        }
+    }
+}
+
+impl ObjectiveFormula {
+    /// and by default, true when there is no filter
+    pub fn is_true<C: ObjectiveParameterContext>(
+        joint: &ObjectiveParameterJoint,
+        context: &C,
+    ) -> StdR<bool> {
+        let filters = joint.filters.as_deref().unwrap_or_default();
+        match joint.conj {
+            Some(ObjectiveParameterJointType::Or) if !filters.is_empty() => {
+                for filter in filters {
+                    if Self::is_condition_true(filter, context)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            _ => {
+                for filter in filters {
+                    if !Self::is_condition_true(filter, context)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+        }
+    }
+
+    fn is_condition_true<C: ObjectiveParameterContext>(
+        condition: &ObjectiveParameterCondition,
+        context: &C,
+    ) -> StdR<bool> {
+        match condition {
+            ObjectiveParameterCondition::Joint(joint) => Self::is_true(joint, context),
+            ObjectiveParameterCondition::Expression(expression) => {
+                Self::is_expression_true(expression, context)
+            }
+        }
+    }
+
+    fn is_expression_true<C: ObjectiveParameterContext>(
+        expression: &ObjectiveParameterExpression,
+        context: &C,
+    ) -> StdR<bool> {
+        match expression {
+            ObjectiveParameterExpression::Empty(e) => Ok(Self::left(&e.left, context)?.is_empty()),
+            ObjectiveParameterExpression::NotEmpty(e) => {
+                Ok(Self::left(&e.left, context)?.is_not_empty())
+            }
+            ObjectiveParameterExpression::Equals(e) => {
+                Self::is_in(&e.left, &e.right, context, |l, r| Ok(l.is_same_as(r)))
+            }
+            ObjectiveParameterExpression::NotEquals(e) => {
+                Self::is_in(&e.left, &e.right, context, |l, r| Ok(l.is_same_as(r))).map(|b| !b)
+            }
+            ObjectiveParameterExpression::LessThan(e) => {
+                Self::compare(&e.left, &e.right, context, |l, r| l.is_less_than(r))
+            }
+            ObjectiveParameterExpression::LessThanOrEquals(e) => {
+                Self::compare(&e.left, &e.right, context, |l, r| {
+                    l.is_less_than_or_equals(r)
+                })
+            }
+            ObjectiveParameterExpression::MoreThan(e) => {
+                Self::compare(&e.left, &e.right, context, |l, r| l.is_more_than(r))
+            }
+            ObjectiveParameterExpression::MoreThanOrEquals(e) => {
+                Self::compare(&e.left, &e.right, context, |l, r| {
+                    l.is_more_than_or_equals(r)
+                })
+            }
+            ObjectiveParameterExpression::In(e) => {
+                Self::is_in(&e.left, &e.right, context, |l, r| l.is_in(r))
+            }
+            ObjectiveParameterExpression::NotIn(e) => {
+                Self::is_in(&e.left, &e.right, context, |l, r| l.is_in(r)).map(|b| !b)
+            }
+        }
+    }
+
+    fn left<C: ObjectiveParameterContext>(
+        left: &Option<ObjectiveParameter>,
+        context: &C,
+    ) -> StdR<Arc<ArcTopicDataValue>> {
+        match left {
+            Some(left) => Self::value_of(left, context),
+            _ => PipelineKernelErrorCode::ObjectiveParameterMissed
+                .msg("Left of expression is missed."),
+        }
+    }
+
+    fn right<C: ObjectiveParameterContext>(
+        right: &Option<ObjectiveParameter>,
+        context: &C,
+    ) -> StdR<ObjectiveOperand> {
+        match right {
+            Some(right) => Self::operand_of(right, context),
+            _ => PipelineKernelErrorCode::ObjectiveParameterMissed
+                .msg("Right of expression is missed."),
+        }
+    }
+
+    fn compare<C: ObjectiveParameterContext, F>(
+        left: &Option<ObjectiveParameter>,
+        right: &Option<ObjectiveParameter>,
+        context: &C,
+        f: F,
+    ) -> StdR<bool>
+    where
+        F: Fn(&ArcTopicDataValue, &ArcTopicDataValue) -> StdR<bool>,
+    {
+        let left = Self::left(left, context)?;
+        match Self::right(right, context)? {
+            ObjectiveOperand::Value(right) => f(&left, &right),
+            _ => PipelineKernelErrorCode::ObjectiveParameterNotSupported
+                .msg("Bucket, range or time frame cannot be compared."),
+        }
+    }
+
+    /// value is compared by given function,
+    /// or check the left value is in segment of bucket, range or time range.
+    fn is_in<C: ObjectiveParameterContext, F>(
+        left: &Option<ObjectiveParameter>,
+        right: &Option<ObjectiveParameter>,
+        context: &C,
+        f: F,
+    ) -> StdR<bool>
+    where
+        F: Fn(&ArcTopicDataValue, &ArcTopicDataValue) -> StdR<bool>,
+    {
+        let left = Self::left(left, context)?;
+        match Self::right(right, context)? {
+            ObjectiveOperand::Value(right) => f(&left, &right),
+            ObjectiveOperand::Segment(bucket, index) => Ok(bucket.segment_of(&left) == Some(index)),
+            ObjectiveOperand::Range(range) => {
+                Ok(Self::to_decimal(&left)?.is_some_and(|value| range.contains(&value)))
+            }
+            ObjectiveOperand::TimeRange(None) => Ok(true),
+            ObjectiveOperand::TimeRange(Some(range)) => {
+                Ok(Self::to_datetime(&left)?.is_some_and(|time| range.contains(&time)))
+            }
+        }
+    }
+}
+
+impl ObjectiveFormula {
+    /// none when value is none or empty string
+    pub fn to_decimal(value: &ArcTopicDataValue) -> StdR<Option<BigDecimal>> {
+        match value {
+            ArcTopicDataValue::None => Ok(None),
+            ArcTopicDataValue::Num(num) => Ok(Some(num.deref().clone())),
+            ArcTopicDataValue::Str(str) if str.trim().is_empty() => Ok(None),
+            ArcTopicDataValue::Str(str) if str.is_numeric() => Ok(Some(str.to_decimal()?)),
+            other => PipelineKernelErrorCode::ObjectiveValueNotNumeric
+                .msg(format!("Value[{}] is not numeric.", other)),
+        }
+    }
+
+    /// none when value is none or empty string
+    pub fn to_datetime(value: &ArcTopicDataValue) -> StdR<Option<NaiveDateTime>> {
+        match value {
+            ArcTopicDataValue::None => Ok(None),
+            ArcTopicDataValue::DateTime(datetime) => Ok(Some(**datetime)),
+            ArcTopicDataValue::Date(date) => Ok(Some(date.and_time(NaiveTime::MIN))),
+            ArcTopicDataValue::Str(str) if str.trim().is_empty() => Ok(None),
+            ArcTopicDataValue::Str(str) => match str.to_datetime_loose() {
+                Ok(datetime) => Ok(Some(datetime)),
+                _ => PipelineKernelErrorCode::ObjectiveValueNotDate
+                    .msg(format!("Value[{}] is not a date.", str)),
+            },
+            other => PipelineKernelErrorCode::ObjectiveValueNotDate
+                .msg(format!("Value[{}] is not a date.", other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ArcFrom, ArcTopicDataValue, IndicatorBucket, ObjectiveFormula, ObjectiveParameterContext,
+        ObjectiveTimeRange, ObjectiveVariableValue,
+    };
+    use bigdecimal::BigDecimal;
+    use chrono::NaiveDate;
+    use elf_base::StdR;
+    use elf_model::{
+        BucketId, BucketObjectiveParameter, ConstantObjectiveParameter, NumericSegmentValue,
+        NumericValueBucket, NumericValueSegment, ObjectiveCaseThenParameter,
+        ObjectiveCaseThenParameterRoute, ObjectiveDayOfWeekParameter, ObjectiveDivideParameter,
+        ObjectiveEqualsExpression, ObjectiveInExpression, ObjectiveInterpolateParameter,
+        ObjectiveMoreThanExpression, ObjectiveParameter, ObjectiveParameterJoint,
+        ReferObjectiveParameter, TimeframeObjectiveParameter,
+    };
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    struct TestContext {
+        refers: HashMap<String, Arc<ArcTopicDataValue>>,
+        variables: HashMap<String, ObjectiveVariableValue>,
+        time_range: Option<ObjectiveTimeRange>,
+    }
+
+    impl ObjectiveParameterContext for TestContext {
+        fn refer(&self, uuid: &str) -> StdR<Arc<ArcTopicDataValue>> {
+            Ok(self
+                .refers
+                .get(uuid)
+                .cloned()
+                .unwrap_or_else(|| Arc::new(ArcTopicDataValue::None)))
+        }
+
+        fn variable(&self, name: &str) -> Option<&ObjectiveVariableValue> {
+            self.variables.get(name)
+        }
+
+        fn bucket(&self, _bucket_id: &BucketId) -> StdR<Arc<IndicatorBucket>> {
+            Ok(Arc::new(bucket()))
+        }
+
+        fn time_range(&self) -> Option<&ObjectiveTimeRange> {
+            self.time_range.as_ref()
+        }
+    }
+
+    fn bucket() -> IndicatorBucket {
+        let segment = |name: &str, min: &str, max: &str| {
+            let mut value = NumericSegmentValue::new();
+            value.min = Some(min.to_string());
+            value.max = Some(max.to_string());
+            NumericValueSegment::new()
+                .name(name.to_string())
+                .value(value)
+        };
+        IndicatorBucket::compile(
+            NumericValueBucket::init()
+                .bucket_id(String::from("b1"))
+                .segments(vec![
+                    segment("low", "0", "10"),
+                    segment("high", "10", "100"),
+                ])
+                .to_bucket(),
+        )
+        .unwrap()
+    }
+
+    fn context() -> TestContext {
+        let mut refers = HashMap::new();
+        refers.insert(
+            String::from("f1"),
+            ArcTopicDataValue::arc_from(BigDecimal::from(30)),
+        );
+        refers.insert(
+            String::from("f2"),
+            ArcTopicDataValue::arc_from(BigDecimal::from(0)),
+        );
+        refers.insert(
+            String::from("d1"),
+            ArcTopicDataValue::arc_from(NaiveDate::from_ymd_opt(2026, 1, 4).unwrap()),
+        );
+        let mut variables = HashMap::new();
+        variables.insert(
+            String::from("min"),
+            ObjectiveVariableValue::Value(String::from("20")),
+        );
+        TestContext {
+            refers,
+            variables,
+            time_range: Some(ObjectiveTimeRange {
+                start: NaiveDate::from_ymd_opt(2026, 1, 1)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap(),
+                end: NaiveDate::from_ymd_opt(2026, 2, 1)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap(),
+            }),
+        }
+    }
+
+    fn refer(uuid: &str) -> ObjectiveParameter {
+        ReferObjectiveParameter::of(uuid.to_string()).to_parameter()
+    }
+
+    fn constant(value: &str) -> ObjectiveParameter {
+        ConstantObjectiveParameter::of(value.to_string()).to_parameter()
+    }
+
+    fn decimal(parameter: ObjectiveParameter) -> Option<String> {
+        ObjectiveFormula::decimal_value_of(&parameter, &context())
+            .unwrap()
+            .map(|value| value.normalized().to_string())
+    }
+
+    #[test]
+    fn test_computed() {
+        assert_eq!(
+            decimal(
+                ObjectiveDivideParameter::init()
+                    .parameters(vec![refer("f1"), constant("{min}")])
+                    .to_parameter()
+            ),
+            Some(String::from("1.5"))
+        );
+        // divided by zero
+        assert_eq!(
+            decimal(
+                ObjectiveDivideParameter::init()
+                    .parameters(vec![refer("f1"), refer("f2")])
+                    .to_parameter()
+            ),
+            None
+        );
+        // 2026-01-04 is sunday
+        assert_eq!(
+            decimal(
+                ObjectiveDayOfWeekParameter::init()
+                    .parameter(Box::new(refer("d1")))
+                    .to_parameter()
+            ),
+            Some(String::from("1"))
+        );
+        let interpolate = |value: &str| {
+            decimal(
+                ObjectiveInterpolateParameter::init()
+                    .parameters(vec![
+                        constant(value),
+                        constant("{min}"),
+                        constant("0"),
+                        constant("40"),
+                        constant("100"),
+                    ])
+                    .to_parameter(),
+            )
+        };
+        assert_eq!(interpolate("10"), Some(String::from("0")));
+        assert_eq!(interpolate("30"), Some(String::from("50")));
+        assert_eq!(interpolate("50"), Some(String::from("100")));
+    }
+
+    #[test]
+    fn test_case_then() {
+        let case_then = |value: &str| {
+            decimal(
+                ObjectiveCaseThenParameter::init()
+                    .parameters(vec![
+                        ObjectiveCaseThenParameterRoute::case(ObjectiveParameterJoint::and(vec![
+                            ObjectiveMoreThanExpression::init()
+                                .left(constant(value))
+                                .right(refer("f1"))
+                                .to_condition(),
+                        ]))
+                        .parameter(constant("1")),
+                        ObjectiveCaseThenParameterRoute::case(ObjectiveParameterJoint::and(vec![
+                            ObjectiveEqualsExpression::init()
+                                .left(constant(value))
+                                .right(
+                                    BucketObjectiveParameter::of(String::from("b1"))
+                                        .segment_name(String::from("low"))
+                                        .to_parameter(),
+                                )
+                                .to_condition(),
+                        ]))
+                        .parameter(constant("2")),
+                        ObjectiveCaseThenParameterRoute::default().parameter(constant("3")),
+                    ])
+                    .to_parameter(),
+            )
+        };
+        assert_eq!(case_then("50"), Some(String::from("1")));
+        assert_eq!(case_then("5"), Some(String::from("2")));
+        assert_eq!(case_then("20"), Some(String::from("3")));
+    }
+
+    #[test]
+    fn test_condition() {
+        let in_timeframe = |uuid: &str| {
+            ObjectiveFormula::is_true(
+                &ObjectiveParameterJoint::and(vec![ObjectiveInExpression::init()
+                    .left(refer(uuid))
+                    .right(TimeframeObjectiveParameter::init().to_parameter())
+                    .to_condition()]),
+                &context(),
+            )
+            .unwrap()
+        };
+        assert!(in_timeframe("d1"));
+        // none is not in time frame
+        assert!(!in_timeframe("d2"));
+        // number is not a date
+        assert!(ObjectiveFormula::is_true(
+            &ObjectiveParameterJoint::and(vec![ObjectiveInExpression::init()
+                .left(refer("f1"))
+                .right(TimeframeObjectiveParameter::init().to_parameter())
+                .to_condition(),]),
+            &context(),
+        )
+        .is_err());
+        // empty filters
+        assert!(
+            ObjectiveFormula::is_true(&ObjectiveParameterJoint::or(vec![]), &context()).unwrap()
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use crate::{
+    IndicatorDataset, IndicatorService, ObjectiveBreakdown, ObjectiveEvaluator, ObjectiveResources,
+    PipelineKernelErrorCode,
+};
+use chrono::{NaiveDateTime, Utc};
+use elf_base::{ErrorCode, StdR};
+use elf_model::{
+    BreakdownTarget, BreakdownTargetValues, Bucket, BucketId, DerivedObjective, Indicator,
+    IndicatorId, Objective, ObjectiveId, ObjectiveValues, TenantId,
+};
+use elf_runtime_model_kernel::ObjectiveMetaProvider;
+
+/// resources found by id in tenant, rows are read from topic storage.
+pub struct ObjectiveMetaResources<'a> {
+    tenant_id: &'a TenantId,
+}
+
+impl<'a> ObjectiveMetaResources<'a> {
+    pub fn new(tenant_id: &'a TenantId) -> Self {
+        Self { tenant_id }
+    }
+}
+
+impl<'a> ObjectiveResources for ObjectiveMetaResources<'a> {
+    fn indicator(&self, indicator_id: &IndicatorId) -> StdR<Indicator> {
+        IndicatorService::find_indicator_by_id(indicator_id, self.tenant_id)
+    }
+
+    fn bucket(&self, bucket_id: &BucketId) -> StdR<Bucket> {
+        IndicatorService::find_bucket(bucket_id, self.tenant_id)
+    }
+
+    fn dataset(&self, indicator: &Indicator) -> StdR<IndicatorDataset> {
+        IndicatorService::dataset_of(indicator, self.tenant_id)
+    }
+}
+
+/// evaluate objective, time frame is resolved by now.
+pub struct ObjectiveService;
+
+impl ObjectiveMetaProvider for ObjectiveService {}
+
+impl ObjectiveService {
+    pub fn find_objective_by_id(
+        objective_id: &ObjectiveId,
+        tenant_id: &TenantId,
+    ) -> StdR<Objective> {
+        match Self::meta()?.find_by_id(objective_id, tenant_id)? {
+            Some(objective) => Ok(objective),
+            _ => PipelineKernelErrorCode::ObjectiveNotFound
+                .msg(format!("Objective[{}] not found.", objective_id)),
+        }
+    }
+
+    pub fn evaluate(objective: &Objective, tenant_id: &TenantId) -> StdR<ObjectiveValues> {
+        Self::evaluate_on(
+            objective,
+            &ObjectiveMetaResources::new(tenant_id),
+            &Utc::now().naive_utc(),
+        )
+    }
+
+    pub fn evaluate_on<R: ObjectiveResources>(
+        objective: &Objective,
+        resources: &R,
+        now: &NaiveDateTime,
+    ) -> StdR<ObjectiveValues> {
+        ObjectiveEvaluator::new(objective, resources, now)?.evaluate()
+    }
+
+    /// breakdown targets of derived objective, on its definition
+    pub fn breakdown(
+        derived_objective: &DerivedObjective,
+        tenant_id: &TenantId,
+    ) -> StdR<Vec<BreakdownTargetValues>> {
+        let Some(objective) = &derived_objective.definition else {
+            return PipelineKernelErrorCode::DerivedObjectiveDefinitionMissed.msg(format!(
+                "Definition of derived objective[{}] is missed.",
+                derived_objective
+                    .derived_objective_id
+                    .as_deref()
+                    .unwrap_or("")
+            ));
+        };
+        Self::breakdown_on(
+            objective,
+            derived_objective
+                .breakdown_targets
+                .as_deref()
+                .unwrap_or_default(),
+            &ObjectiveMetaResources::new(tenant_id),
+            &Utc::now().naive_utc(),
+        )
+    }
+
+    pub fn breakdown_on<R: ObjectiveResources>(
+        objective: &Objective,
+        breakdown_targets: &[BreakdownTarget],
+        resources: &R,
+        now: &NaiveDateTime,
+    ) -> StdR<Vec<BreakdownTargetValues>> {
+        let breakdown = ObjectiveBreakdown::new(objective, resources, now);
+        breakdown_targets
+            .iter()
+            .map(|breakdown_target| breakdown.breakdown(breakdown_target))
+            .collect()
+    }
+}
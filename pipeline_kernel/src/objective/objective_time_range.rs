@@ -0,0 +1,280 @@
+use crate::PipelineKernelErrorCode;
+use chrono::{Datelike, Days, Months, NaiveDate, NaiveDateTime, NaiveTime};
+use elf_base::{DateTimeUtils, ErrorCode, StdR};
+use elf_model::{
+    ObjectiveTimeRangeValues, ObjectiveTimeframe, ObjectiveTimeframeKind, ObjectiveTimeframeTill,
+};
+
+/// time range, [start, end).
+#[derive(Clone, Copy)]
+pub struct ObjectiveTimeRange {
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+}
+
+impl ObjectiveTimeRange {
+    pub fn contains(&self, time: &NaiveDateTime) -> bool {
+        time >= &self.start && time < &self.end
+    }
+
+    pub fn to_values(&self) -> ObjectiveTimeRangeValues {
+        ObjectiveTimeRangeValues::new()
+            .start(self.start)
+            .end(self.end)
+    }
+}
+
+/// the period which time frame is on
+enum ObjectiveTimePeriod {
+    Year,
+    HalfYear,
+    Quarter,
+    Month,
+    Week,
+    Day,
+}
+
+impl ObjectiveTimePeriod {
+    /// first day of period which contains given date, week starts from sunday
+    fn first_day_of(&self, date: &NaiveDate) -> NaiveDate {
+        let first_day_of_month =
+            |month: u32| NaiveDate::from_ymd_opt(date.year(), month, 1).unwrap_or(*date);
+        match self {
+            Self::Year => first_day_of_month(1),
+            Self::HalfYear => first_day_of_month(if date.month() <= 6 { 1 } else { 7 }),
+            Self::Quarter => first_day_of_month((date.month() - 1) / 3 * 3 + 1),
+            Self::Month => first_day_of_month(date.month()),
+            Self::Week => *date - Days::new(date.weekday().num_days_from_sunday() as u64),
+            Self::Day => *date,
+        }
+    }
+
+    /// move back given times of period
+    fn back(&self, time: &NaiveDateTime, times: u32) -> NaiveDateTime {
+        let months = |months: u32| *time - Months::new(months * times);
+        match self {
+            Self::Year => months(12),
+            Self::HalfYear => months(6),
+            Self::Quarter => months(3),
+            Self::Month => months(1),
+            Self::Week => *time - Days::new(7 * times as u64),
+            Self::Day => *time - Days::new(times as u64),
+        }
+    }
+}
+
+/// time ranges of objective, computed by time frame.
+/// - current: the time frame itself,
+/// - previous: the previous cycle, e.g. last month when time frame is on month (month-on-month),
+/// - chain: the same cycle of last year (year-on-year).
+///
+/// current range is decided by [ObjectiveTimeframeTill]:
+/// - now: from start of the cycle which contains now, till now.
+///   for last n kinds, from n periods before now, till now,
+/// - last complete cycle: the cycle right before the one which contains now.
+///   for last n kinds, n complete periods before the one which contains now,
+/// - specified: same as now, but till the end of specified day.
+pub struct ObjectiveTimeRanges {
+    pub current: ObjectiveTimeRange,
+    pub previous: ObjectiveTimeRange,
+    pub chain: ObjectiveTimeRange,
+}
+
+impl ObjectiveTimeRanges {
+    /// returns none when objective is not on time frame
+    pub fn of(timeframe: &Option<ObjectiveTimeframe>, now: &NaiveDateTime) -> StdR<Option<Self>> {
+        let Some(timeframe) = timeframe else {
+            return Ok(None);
+        };
+        let (period, last_n) = match &timeframe.kind {
+            None | Some(ObjectiveTimeframeKind::None) => return Ok(None),
+            Some(ObjectiveTimeframeKind::Year) => (ObjectiveTimePeriod::Year, None),
+            Some(ObjectiveTimeframeKind::HalfYear) => (ObjectiveTimePeriod::HalfYear, None),
+            Some(ObjectiveTimeframeKind::Quarter) => (ObjectiveTimePeriod::Quarter, None),
+            Some(ObjectiveTimeframeKind::Month) => (ObjectiveTimePeriod::Month, None),
+            Some(ObjectiveTimeframeKind::WeekOfYear) => (ObjectiveTimePeriod::Week, None),
+            Some(ObjectiveTimeframeKind::DayOfMonth) | Some(ObjectiveTimeframeKind::DayOfWeek) => {
+                (ObjectiveTimePeriod::Day, None)
+            }
+            Some(ObjectiveTimeframeKind::LastNYears) => {
+                (ObjectiveTimePeriod::Year, Some(Self::last_n(timeframe)?))
+            }
+            Some(ObjectiveTimeframeKind::LastNMonths) => {
+                (ObjectiveTimePeriod::Month, Some(Self::last_n(timeframe)?))
+            }
+            Some(ObjectiveTimeframeKind::LastNWeeks) => {
+                (ObjectiveTimePeriod::Week, Some(Self::last_n(timeframe)?))
+            }
+            Some(ObjectiveTimeframeKind::LastNDays) => {
+                (ObjectiveTimePeriod::Day, Some(Self::last_n(timeframe)?))
+            }
+        };
+        let times = last_n.unwrap_or(1);
+
+        let (start, end) = match &timeframe.till {
+            Some(ObjectiveTimeframeTill::LastCompleteCycle) => {
+                let end = period.first_day_of(&now.date()).and_time(NaiveTime::MIN);
+                (period.back(&end, times), end)
+            }
+            Some(ObjectiveTimeframeTill::Specified) => {
+                let till = Self::specified_till(timeframe)?;
+                let end = (till + Days::new(1)).and_time(NaiveTime::MIN);
+                match last_n {
+                    Some(_) => (period.back(&end, times), end),
+                    _ => (period.first_day_of(&till).and_time(NaiveTime::MIN), end),
+                }
+            }
+            _ => match last_n {
+                Some(_) => (period.back(now, times), *now),
+                _ => (
+                    period.first_day_of(&now.date()).and_time(NaiveTime::MIN),
+                    *now,
+                ),
+            },
+        };
+
+        Ok(Some(Self {
+            previous: ObjectiveTimeRange {
+                start: period.back(&start, times),
+                end: period.back(&end, times),
+            },
+            chain: ObjectiveTimeRange {
+                start: ObjectiveTimePeriod::Year.back(&start, 1),
+                end: ObjectiveTimePeriod::Year.back(&end, 1),
+            },
+            current: ObjectiveTimeRange { start, end },
+        }))
+    }
+
+    fn last_n(timeframe: &ObjectiveTimeframe) -> StdR<u32> {
+        match timeframe
+            .last_n
+            .as_ref()
+            .and_then(|n| n.trim().parse::<u32>().ok())
+        {
+            Some(n) if n > 0 => Ok(n),
+            _ => PipelineKernelErrorCode::ObjectiveTimeframeInvalid.msg(format!(
+                "Last n[{}] of objective time frame must be a positive integer.",
+                timeframe.last_n.as_deref().unwrap_or("")
+            )),
+        }
+    }
+
+    fn specified_till(timeframe: &ObjectiveTimeframe) -> StdR<NaiveDate> {
+        match &timeframe.specified_till {
+            Some(till) => match till.to_date_loose() {
+                Ok(till) => Ok(till),
+                _ => PipelineKernelErrorCode::ObjectiveTimeframeInvalid.msg(format!(
+                    "Specified till[{}] of objective time frame is not a date.",
+                    till
+                )),
+            },
+            _ => PipelineKernelErrorCode::ObjectiveTimeframeInvalid
+                .msg("Specified till of objective time frame is missed."),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ObjectiveTimeRanges;
+    use chrono::{NaiveDate, NaiveDateTime};
+    use elf_model::{ObjectiveTimeframe, ObjectiveTimeframeKind, ObjectiveTimeframeTill};
+
+    fn time(year: i32, month: u32, day: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+    }
+
+    fn ranges(timeframe: ObjectiveTimeframe, now: &NaiveDateTime) -> ObjectiveTimeRanges {
+        ObjectiveTimeRanges::of(&Some(timeframe), now)
+            .unwrap()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_cycle() {
+        let now = NaiveDate::from_ymd_opt(2026, 5, 20)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap();
+
+        let month = ranges(
+            ObjectiveTimeframe::new()
+                .kind(ObjectiveTimeframeKind::Month)
+                .till(ObjectiveTimeframeTill::Now),
+            &now,
+        );
+        assert_eq!(month.current.start, time(2026, 5, 1));
+        assert_eq!(month.current.end, now);
+        assert_eq!(month.previous.start, time(2026, 4, 1));
+        assert_eq!(month.chain.start, time(2025, 5, 1));
+
+        let quarter = ranges(
+            ObjectiveTimeframe::new()
+                .kind(ObjectiveTimeframeKind::Quarter)
+                .till(ObjectiveTimeframeTill::LastCompleteCycle),
+            &now,
+        );
+        assert_eq!(quarter.current.start, time(2026, 1, 1));
+        assert_eq!(quarter.current.end, time(2026, 4, 1));
+        assert_eq!(quarter.previous.start, time(2025, 10, 1));
+        assert_eq!(quarter.previous.end, time(2026, 1, 1));
+
+        let year = ranges(
+            ObjectiveTimeframe::new()
+                .kind(ObjectiveTimeframeKind::Year)
+                .till(ObjectiveTimeframeTill::Specified)
+                .specified_till(String::from("2025-06-30")),
+            &now,
+        );
+        assert_eq!(year.current.start, time(2025, 1, 1));
+        assert_eq!(year.current.end, time(2025, 7, 1));
+        assert!(year.current.contains(&time(2025, 6, 30)));
+        assert!(!year.current.contains(&time(2025, 7, 1)));
+
+        assert!(ObjectiveTimeRanges::of(
+            &Some(ObjectiveTimeframe::new().kind(ObjectiveTimeframeKind::None)),
+            &now
+        )
+        .unwrap()
+        .is_none());
+    }
+
+    #[test]
+    fn test_last_n() {
+        let now = time(2026, 5, 20);
+        let months = ranges(
+            ObjectiveTimeframe::new()
+                .kind(ObjectiveTimeframeKind::LastNMonths)
+                .last_n(String::from("3"))
+                .till(ObjectiveTimeframeTill::LastCompleteCycle),
+            &now,
+        );
+        assert_eq!(months.current.start, time(2026, 2, 1));
+        assert_eq!(months.current.end, time(2026, 5, 1));
+        assert_eq!(months.previous.start, time(2025, 11, 1));
+        assert_eq!(months.previous.end, time(2026, 2, 1));
+
+        let days = ranges(
+            ObjectiveTimeframe::new()
+                .kind(ObjectiveTimeframeKind::LastNDays)
+                .last_n(String::from("7")),
+            &now,
+        );
+        assert_eq!(days.current.start, time(2026, 5, 13));
+        assert_eq!(days.current.end, now);
+
+        assert!(ObjectiveTimeRanges::of(
+            &Some(
+                ObjectiveTimeframe::new()
+                    .kind(ObjectiveTimeframeKind::LastNDays)
+                    .last_n(String::from("x"))
+            ),
+            &now
+        )
+        .is_err());
+    }
+}
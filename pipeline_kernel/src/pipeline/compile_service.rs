@@ -1,4 +1,4 @@
-use crate::CompiledPipeline;
+use crate::{CompiledPipeline, ExecuteTimer, PipelineExecuteMetrics};
 use elf_base::StdR;
 use elf_runtime_model_kernel::{PipelineSchema, PipelineService, TopicSchema};
 use std::sync::Arc;
@@ -17,10 +17,15 @@ impl PipelineCompileService {
         topic_schema: Arc<TopicSchema>,
         pipeline_schema: Arc<PipelineSchema>,
     ) -> StdR<Arc<CompiledPipeline>> {
-        Ok(Arc::new(CompiledPipeline::compile(
-            topic_schema,
-            pipeline_schema,
-        )?))
+        // no cache yet, always missed
+        PipelineExecuteMetrics::cache_requested("compiled_pipeline", false);
+
+        let timer = ExecuteTimer::start();
+        let compiled = CompiledPipeline::compile(topic_schema, pipeline_schema)
+            .inspect_err(PipelineExecuteMetrics::error_occurred)?;
+        PipelineExecuteMetrics::compiled(timer.elapsed());
+
+        Ok(Arc::new(compiled))
     }
 }
 
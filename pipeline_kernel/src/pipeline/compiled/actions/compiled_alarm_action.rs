@@ -44,7 +44,7 @@ impl ActionCompiler for CompiledAlarmAction {
         tenant_id: &Arc<TenantId>,
     ) -> StdR<Self> {
         let message = if let Some(message) = &action.message {
-            Some(DataPath::from_str(message.deref())?)
+            Some(DataPath::from_str_of_tenant(message.deref(), tenant_id)?)
         } else {
             None
         };
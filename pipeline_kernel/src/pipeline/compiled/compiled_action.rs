@@ -6,7 +6,7 @@ use crate::{
     CompiledWriteFactorAction, CompiledWriteToExternalAction,
 };
 use elf_base::StdR;
-use elf_model::{TenantId, TopicId};
+use elf_model::{PipelineActionId, PipelineActionType, TenantId, TopicId};
 use elf_runtime_model_kernel::{
     ArcPipeline, ArcPipelineAction, ArcPipelineStage, ArcPipelineUnit, TopicSchema,
 };
@@ -111,4 +111,24 @@ impl CompiledAction {
             }
         }
     }
+
+    /// id and type of source action
+    pub fn action_id_and_type(&self) -> (&Arc<PipelineActionId>, &Arc<PipelineActionType>) {
+        match self {
+            Self::Alarm(action) => (&action.action().action_id, &action.action().r#type),
+            Self::CopyToMemory(action) => (&action.action().action_id, &action.action().r#type),
+            Self::WriteToExternal(action) => (&action.action().action_id, &action.action().r#type),
+            Self::ReadRow(action) => (&action.action().action_id, &action.action().r#type),
+            Self::ReadFactor(action) => (&action.action().action_id, &action.action().r#type),
+            Self::Exists(action) => (&action.action().action_id, &action.action().r#type),
+            Self::ReadRows(action) => (&action.action().action_id, &action.action().r#type),
+            Self::ReadFactors(action) => (&action.action().action_id, &action.action().r#type),
+            Self::MergeRow(action) => (&action.action().action_id, &action.action().r#type),
+            Self::InsertRow(action) => (&action.action().action_id, &action.action().r#type),
+            Self::InsertOrMergeRow(action) => (&action.action().action_id, &action.action().r#type),
+            Self::WriteFactor(action) => (&action.action().action_id, &action.action().r#type),
+            Self::DeleteRow(action) => (&action.action().action_id, &action.action().r#type),
+            Self::DeleteRows(action) => (&action.action().action_id, &action.action().r#type),
+        }
+    }
 }
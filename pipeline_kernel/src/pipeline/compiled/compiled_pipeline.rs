@@ -1,5 +1,6 @@
-use crate::{CompiledConditional, CompiledStage};
+use crate::{CompiledConditional, CompiledStage, PipelineTypeChecker};
 use elf_base::StdR;
+use elf_model::TopicId;
 use elf_runtime_model_kernel::{PipelineSchema, TopicSchema};
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -17,8 +18,17 @@ impl CompiledPipeline {
         topic_schema: Arc<TopicSchema>,
         pipeline_schema: Arc<PipelineSchema>,
     ) -> StdR<Self> {
+        Self::compile_with_topics(topic_schema, pipeline_schema, HashMap::new())
+    }
+
+    /// compile with given topic schemas, which are used before loading from [TopicService].
+    /// e.g. compile pipelines against topics of meta files, without meta storage.
+    pub fn compile_with_topics(
+        topic_schema: Arc<TopicSchema>,
+        pipeline_schema: Arc<PipelineSchema>,
         // cache all the topic schemas which touched in pipeline compiling phase
-        let mut topic_schemas = HashMap::new();
+        mut topic_schemas: HashMap<Arc<TopicId>, Arc<TopicSchema>>,
+    ) -> StdR<Self> {
         topic_schemas.insert(topic_schema.topic_id().clone(), topic_schema.clone());
 
         let pipeline = pipeline_schema.pipeline();
@@ -33,6 +43,9 @@ impl CompiledPipeline {
                 &pipeline.tenant_id,
             )?);
         }
+        // all touched topics are cached now, check types before pipeline runs
+        PipelineTypeChecker::new(topic_schema.topic_id().clone(), &topic_schemas)
+            .check_or_raise(pipeline)?;
 
         Ok(Self {
             topic: topic_schema,
@@ -13,7 +13,7 @@ impl CompiledConstantParameter {
     pub fn compile(
         parameter: &Arc<ArcConstantParameter>,
         _topic_schemas: &mut HashMap<Arc<TopicId>, Arc<TopicSchema>>,
-        _tenant_id: &Arc<TenantId>,
+        tenant_id: &Arc<TenantId>,
     ) -> StdR<Self> {
         let value = &parameter.value;
         if value.is_empty() {
@@ -24,7 +24,7 @@ impl CompiledConstantParameter {
                 .msg("Value of constant parameter cannot be blank.")
         } else {
             Ok(CompiledConstantParameter {
-                path: DataPath::from_str(parameter.value.as_str())?,
+                path: DataPath::from_str_of_tenant(parameter.value.as_str(), tenant_id)?,
             })
         }
     }
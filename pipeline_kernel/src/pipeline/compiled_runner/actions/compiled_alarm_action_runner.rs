@@ -1,8 +1,7 @@
 use crate::{
     create_spec_action_runner, ActionExecuteLog, ActionRunResult, CompiledAlarmAction, CompiledPipeline,
-    CompiledStage, CompiledUnit, DataPath, InMemoryData, SpecCompiledActionRunner,
+    CompiledStage, CompiledUnit, DataPath, InMemoryData, PipelineExecuteMetrics, SpecCompiledActionRunner,
 };
-use chrono::{NaiveDateTime, Utc};
 use elf_auth::Principal;
 use elf_base::{StdErr, StdR};
 use elf_model::{AlarmActionSeverity, MonitorLogStatus};
@@ -26,8 +25,9 @@ impl CompiledAlarmActionRunner {
     }
 
     fn create_monitor_log(&self, prerequisite: bool, error: Option<StdErr>) -> ActionExecuteLog {
-        let spent_in_mills =
-            (Utc::now().timestamp() - self.start_time.and_utc().timestamp()) as u32;
+        if let Some(error) = &error {
+            PipelineExecuteMetrics::error_occurred(error);
+        }
 
         let status = if error.is_some() {
             MonitorLogStatus::ERROR
@@ -45,8 +45,8 @@ impl CompiledAlarmActionRunner {
                 None => None,
             },
             status,
-            start_time: self.start_time,
-            spent_in_mills,
+            start_time: self.timer.start_time(),
+            spent_in_mills: self.timer.spent_in_mills(),
             error: error.map(|e| format!("{}", e)),
             prerequisite,
             find_by: None,
@@ -2,7 +2,6 @@ use crate::{
     create_spec_action_runner, ActionRunResult, CompiledExistsAction, CompiledPipeline, CompiledStage,
     CompiledUnit, InMemoryData, SpecCompiledActionRunner,
 };
-use chrono::{NaiveDateTime, Utc};
 use elf_auth::Principal;
 use std::sync::Arc;
 
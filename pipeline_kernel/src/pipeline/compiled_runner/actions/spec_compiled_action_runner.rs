@@ -29,7 +29,7 @@ macro_rules! create_spec_action_runner {
                 compiled_action: Arc<$name>,
                 principal: Arc<Principal>,
 
-                start_time: NaiveDateTime,
+                timer: $crate::ExecuteTimer,
             }
 
             impl SpecCompiledActionRunner for [<$name Runner>] {
@@ -49,7 +49,7 @@ macro_rules! create_spec_action_runner {
                         compiled_unit,
                         compiled_action,
                         principal,
-                        start_time: Utc::now().naive_utc(),
+                        timer: $crate::ExecuteTimer::start(),
                     }
                     .do_run(in_memory_data)
                     .await
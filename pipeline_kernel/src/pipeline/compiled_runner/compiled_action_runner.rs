@@ -5,12 +5,13 @@ use crate::{
     CompiledMergeRowActionRunner, CompiledPipeline, CompiledReadFactorActionRunner,
     CompiledReadFactorsActionRunner, CompiledReadRowActionRunner, CompiledReadRowsActionRunner,
     CompiledStage, CompiledUnit, CompiledWriteFactorActionRunner,
-    CompiledWriteToExternalActionRunner, InMemoryData, PipelineExecutionTask,
-    SpecCompiledActionRunner,
+    CompiledWriteToExternalActionRunner, ExecuteTimer, InMemoryData, PipelineExecuteMetrics,
+    PipelineExecuteSpan, PipelineExecutionTask, SpecCompiledActionRunner,
 };
 use elf_auth::Principal;
 use std::ops::Deref;
 use std::sync::Arc;
+use tracing::Instrument;
 
 pub struct CompiledActionRunner {
     compiled_pipeline: Arc<CompiledPipeline>,
@@ -33,14 +34,34 @@ impl CompiledActionRunner {
         compiled_action: Arc<CompiledAction>,
         principal: Arc<Principal>,
     ) -> ActionRunResult {
-        Self {
+        let (action_id, action_type) = compiled_action.action_id_and_type();
+        let span = PipelineExecuteSpan::action(action_id, &action_type.to_string());
+        let timer = ExecuteTimer::start();
+        let result = Self {
             compiled_pipeline,
             compiled_stage,
             compiled_unit,
             principal,
         }
         .do_run_action(in_memory_data, compiled_action)
-        .await
+        .instrument(span.clone())
+        .await;
+
+        let log = &result.log;
+        span.in_scope(|| {
+            PipelineExecuteSpan::finished("Action", &log.status, log.spent_in_mills, &log.error)
+        });
+        PipelineExecuteMetrics::run_finished("action", &log.status, timer.elapsed());
+        for (operation, count) in [
+            ("insert", log.insert_count),
+            ("update", log.update_count),
+            ("delete", log.delete_count),
+        ] {
+            if let Some(count) = count {
+                PipelineExecuteMetrics::rows_touched(&log.r#type, operation, count);
+            }
+        }
+        result
     }
 
     async fn do_run_action(
@@ -1,13 +1,14 @@
 use crate::{
-    ArcTopicData, CompiledPipeline, CompiledStageRunner, InMemoryData, PipelineExecuteLog,
-    PipelineExecutionTask, StageExecuteLog, StageRunResult,
+    ArcTopicData, CompiledPipeline, CompiledStageRunner, ExecuteTimer, InMemoryData,
+    PipelineExecuteLog, PipelineExecuteMetrics, PipelineExecuteSpan, PipelineExecutionTask,
+    StageExecuteLog, StageRunResult,
 };
-use chrono::{NaiveDateTime, Utc};
 use elf_auth::Principal;
 use elf_base::{StdErr, StdR};
 use elf_model::{MonitorLogStatus, PipelineTriggerTraceId, TopicDataId};
 use elf_runtime_model_kernel::IdGen;
 use std::sync::Arc;
+use tracing::Instrument;
 
 pub struct CompiledPipelineRunner {
     topic_data_id: Arc<TopicDataId>,
@@ -17,7 +18,7 @@ pub struct CompiledPipelineRunner {
     trace_id: Arc<PipelineTriggerTraceId>,
     async_monitor_log: bool,
 
-    start_time: NaiveDateTime,
+    timer: ExecuteTimer,
 }
 
 impl CompiledPipelineRunner {
@@ -29,6 +30,12 @@ impl CompiledPipelineRunner {
         trace_id: Arc<PipelineTriggerTraceId>,
         async_monitor_log: bool,
     ) -> Option<Vec<PipelineExecutionTask>> {
+        let pipeline_schema = compiled_pipeline.pipeline_schema();
+        let span = PipelineExecuteSpan::pipeline(
+            pipeline_schema.pipeline_id(),
+            pipeline_schema.name(),
+            compiled_pipeline.topic_schema().topic_id(),
+        );
         Self {
             topic_data_id,
             compiled_pipeline,
@@ -36,9 +43,10 @@ impl CompiledPipelineRunner {
             trace_id,
             async_monitor_log,
 
-            start_time: Utc::now().naive_utc(),
+            timer: ExecuteTimer::start(),
         }
         .do_run(in_memory_data)
+        .instrument(span)
         .await
     }
 
@@ -68,8 +76,9 @@ impl CompiledPipelineRunner {
         stage_logs: Option<(Vec<StageExecuteLog>, bool)>,
         error: Option<StdErr>,
     ) -> StdR<PipelineExecuteLog> {
-        let spent_in_mills =
-            (Utc::now().timestamp() - self.start_time.and_utc().timestamp()) as u32;
+        if let Some(error) = &error {
+            PipelineExecuteMetrics::error_occurred(error);
+        }
 
         let (stage_logs, all_stage_accomplished) =
             if let Some((stage_logs, all_stage_accomplished)) = stage_logs {
@@ -82,6 +91,7 @@ impl CompiledPipelineRunner {
         } else {
             MonitorLogStatus::DONE
         };
+        PipelineExecuteMetrics::run_finished("pipeline", &status, self.timer.elapsed());
 
         Ok(PipelineExecuteLog {
             uid: IdGen::next_id()?.to_string(),
@@ -94,8 +104,8 @@ impl CompiledPipelineRunner {
             topic_id: self.compiled_pipeline.topic_schema().topic_id().clone(),
             prerequisite_defined_as: self.compiled_pipeline.conditional().defined_as(),
             status,
-            start_time: self.start_time,
-            spent_in_mills,
+            start_time: self.timer.start_time(),
+            spent_in_mills: self.timer.spent_in_mills(),
             error: error.map(|e| format!("{}", e)),
             prerequisite,
             data_id: self.topic_data_id.clone(),
@@ -113,7 +123,10 @@ impl CompiledPipelineRunner {
         stage_logs: Option<(Vec<StageExecuteLog>, bool)>,
         error: Option<StdErr>,
     ) {
-        let _log = self.create_monitor_log(in_memory_data, prerequisite, stage_logs, error);
+        let log = self.create_monitor_log(in_memory_data, prerequisite, stage_logs, error);
+        if let Ok(log) = &log {
+            PipelineExecuteSpan::finished("Pipeline", &log.status, log.spent_in_mills, &log.error);
+        }
         let _async_monitor_log = self.async_monitor_log;
         todo!("implement save_monitor_log for CompiledPipelineRunner")
     }
@@ -1,19 +1,20 @@
 use crate::{
-    CompiledPipeline, CompiledStage, CompiledUnitRunner, InMemoryData, PipelineExecutionTask,
-    StageExecuteLog, UnitExecuteLog, UnitRunResult,
+    CompiledPipeline, CompiledStage, CompiledUnitRunner, ExecuteTimer, InMemoryData,
+    PipelineExecuteMetrics, PipelineExecuteSpan, PipelineExecutionTask, StageExecuteLog,
+    UnitExecuteLog, UnitRunResult,
 };
-use chrono::{NaiveDateTime, Utc};
 use elf_auth::Principal;
 use elf_base::{StdErr, StdR};
 use elf_model::MonitorLogStatus;
 use std::sync::Arc;
+use tracing::Instrument;
 
 pub struct CompiledStageRunner {
     compiled_pipeline: Arc<CompiledPipeline>,
     compiled_stage: Arc<CompiledStage>,
     principal: Arc<Principal>,
 
-    start_time: NaiveDateTime,
+    timer: ExecuteTimer,
 }
 
 pub struct StageRunResult {
@@ -28,15 +29,24 @@ impl CompiledStageRunner {
         compiled_stage: Arc<CompiledStage>,
         principal: Arc<Principal>,
     ) -> StageRunResult {
-        Self {
+        let stage = compiled_stage.stage();
+        let span = PipelineExecuteSpan::stage(&stage.stage_id, &stage.name);
+        let result = Self {
             compiled_pipeline,
             compiled_stage,
             principal,
 
-            start_time: Utc::now().naive_utc(),
+            timer: ExecuteTimer::start(),
         }
         .do_run(in_memory_data)
-        .await
+        .instrument(span.clone())
+        .await;
+
+        let log = &result.log;
+        span.in_scope(|| {
+            PipelineExecuteSpan::finished("Stage", &log.status, log.spent_in_mills, &log.error)
+        });
+        result
     }
 
     fn check_prerequisite(&self, in_memory_data: &mut InMemoryData) -> StdR<bool> {
@@ -50,8 +60,9 @@ impl CompiledStageRunner {
         unit_logs: Option<(Vec<UnitExecuteLog>, bool)>,
         error: Option<StdErr>,
     ) -> StageExecuteLog {
-        let spent_in_mills =
-            (Utc::now().timestamp() - self.start_time.and_utc().timestamp()) as u32;
+        if let Some(error) = &error {
+            PipelineExecuteMetrics::error_occurred(error);
+        }
 
         let (unit_logs, all_unit_accomplished) =
             if let Some((unit_logs, all_unit_accomplished)) = unit_logs {
@@ -64,14 +75,15 @@ impl CompiledStageRunner {
         } else {
             MonitorLogStatus::DONE
         };
+        PipelineExecuteMetrics::run_finished("stage", &status, self.timer.elapsed());
 
         StageExecuteLog {
             stage_id: self.compiled_stage.stage().stage_id.clone(),
             name: self.compiled_stage.stage().name.clone(),
             prerequisite_defined_as: self.compiled_stage.conditional().defined_as(),
             status,
-            start_time: self.start_time,
-            spent_in_mills,
+            start_time: self.timer.start_time(),
+            spent_in_mills: self.timer.spent_in_mills(),
             error: error.map(|e| format!("{}", e)),
             prerequisite,
             units: unit_logs,
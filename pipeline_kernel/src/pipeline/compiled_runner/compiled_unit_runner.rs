@@ -1,14 +1,15 @@
 use crate::{
     ActionExecuteLog, ActionRunResult, ArcTopicDataValue, CompiledActionRunner, CompiledPipeline,
-    CompiledStage, CompiledUnit, InMemoryData, PipelineExecuteEnvs, PipelineExecutionTask,
-    PipelineKernelErrorCode, UnitExecuteLog,
+    CompiledStage, CompiledUnit, ExecuteTimer, InMemoryData, PipelineExecuteEnvs,
+    PipelineExecuteMetrics, PipelineExecuteSpan, PipelineExecutionTask, PipelineKernelErrorCode,
+    UnitExecuteLog,
 };
-use chrono::{NaiveDateTime, Utc};
 use elf_auth::Principal;
 use elf_base::{ErrorCode, StdErr, StdR};
 use elf_model::MonitorLogStatus;
 use std::ops::Deref;
 use std::sync::Arc;
+use tracing::Instrument;
 
 pub struct CompiledUnitRunner {
     compiled_pipeline: Arc<CompiledPipeline>,
@@ -16,7 +17,7 @@ pub struct CompiledUnitRunner {
     compiled_unit: Arc<CompiledUnit>,
     principal: Arc<Principal>,
 
-    start_time: NaiveDateTime,
+    timer: ExecuteTimer,
 }
 
 pub struct UnitRunResult {
@@ -32,16 +33,27 @@ impl CompiledUnitRunner {
         compiled_unit: Arc<CompiledUnit>,
         principal: Arc<Principal>,
     ) -> Vec<UnitRunResult> {
-        Self {
+        let unit = compiled_unit.unit();
+        let span = PipelineExecuteSpan::unit(&unit.unit_id, &unit.name);
+        let results = Self {
             compiled_pipeline,
             compiled_stage,
             compiled_unit,
             principal,
 
-            start_time: Utc::now().naive_utc(),
+            timer: ExecuteTimer::start(),
         }
         .do_run(in_memory_data)
-        .await
+        .instrument(span.clone())
+        .await;
+
+        // one log for each round when unit has loop
+        span.in_scope(|| {
+            for UnitRunResult { log, .. } in results.iter() {
+                PipelineExecuteSpan::finished("Unit", &log.status, log.spent_in_mills, &log.error)
+            }
+        });
+        results
     }
 
     async fn do_run(self, in_memory_data: &mut InMemoryData) -> Vec<UnitRunResult> {
@@ -71,8 +83,9 @@ impl CompiledUnitRunner {
         action_logs: Option<(Vec<ActionExecuteLog>, bool)>,
         error: Option<StdErr>,
     ) -> UnitExecuteLog {
-        let spent_in_mills =
-            (Utc::now().timestamp() - self.start_time.and_utc().timestamp()) as u32;
+        if let Some(error) = &error {
+            PipelineExecuteMetrics::error_occurred(error);
+        }
 
         let (action_logs, all_action_accomplished) =
             if let Some((action_logs, all_action_accomplished)) = action_logs {
@@ -85,6 +98,7 @@ impl CompiledUnitRunner {
         } else {
             MonitorLogStatus::DONE
         };
+        PipelineExecuteMetrics::run_finished("unit", &status, self.timer.elapsed());
 
         UnitExecuteLog {
             unit_id: self.compiled_unit.stage().stage_id.clone(),
@@ -92,8 +106,8 @@ impl CompiledUnitRunner {
             loop_variable_name: self.compiled_unit.loop_variable_name().clone(),
             prerequisite_defined_as: self.compiled_unit.conditional().defined_as(),
             status,
-            start_time: self.start_time,
-            spent_in_mills,
+            start_time: self.timer.start_time(),
+            spent_in_mills: self.timer.spent_in_mills(),
             error: error.map(|e| format!("{}", e)),
             prerequisite,
             loop_variable_value,
@@ -246,7 +260,7 @@ impl CompiledUnitRunner {
             compiled_stage: self.compiled_stage.clone(),
             compiled_unit: self.compiled_unit.clone(),
             principal: self.principal.clone(),
-            start_time: Utc::now().naive_utc(),
+            timer: ExecuteTimer::start(),
         }
     }
 
@@ -295,11 +309,14 @@ impl CompiledUnitRunner {
                 loop_variable_name,
                 element,
             );
-            let handle = tokio::spawn(async move {
-                runner
-                    .do_run_unit(Some(loop_variable_value), &mut in_memory_data)
-                    .await
-            });
+            let handle = tokio::spawn(
+                async move {
+                    runner
+                        .do_run_unit(Some(loop_variable_value), &mut in_memory_data)
+                        .await
+                }
+                .in_current_span(),
+            );
             handles.push((handle, element));
         }
         for (handle, element) in handles {
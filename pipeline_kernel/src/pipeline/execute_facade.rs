@@ -200,6 +200,11 @@ impl PipelineExecuteFacade {
         })
     }
 
+    /// trace id given by trigger data, or generated when not given
+    pub fn trace_id(&self) -> Arc<PipelineTriggerTraceId> {
+        self.input.trace_id()
+    }
+
     /// execute synchronized, trigger all pipelines
     pub async fn execute(self) -> StdR<TopicDataId> {
         let request = PipelinePreExecute::pre_execute(self.input, None)?;
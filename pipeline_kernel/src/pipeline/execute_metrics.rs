@@ -0,0 +1,120 @@
+use elf_base::{MetricsRegistry, StdErr};
+use elf_model::{MonitorLogStatus, PipelineActionType};
+use std::time::Duration;
+
+pub const METRIC_PIPELINE_TRIGGERS: &str = "elf_pipeline_triggers_total";
+pub const METRIC_PIPELINE_RUN_DURATION: &str = "elf_pipeline_run_duration_seconds";
+pub const METRIC_PIPELINE_COMPILE_DURATION: &str = "elf_pipeline_compile_duration_seconds";
+pub const METRIC_PIPELINE_ACTION_ROWS: &str = "elf_pipeline_action_rows_total";
+pub const METRIC_PIPELINE_CACHE_REQUESTS: &str = "elf_pipeline_cache_requests_total";
+pub const METRIC_PIPELINE_ERRORS: &str = "elf_pipeline_errors_total";
+
+/// metrics of pipeline execution, recorded into [MetricsRegistry].
+pub struct PipelineExecuteMetrics;
+
+impl PipelineExecuteMetrics {
+    /// trigger of topic data, no matter there is pipeline to run or not
+    pub fn triggered(tenant_id: &str, topic_id: &str) {
+        MetricsRegistry::increase_counter(
+            METRIC_PIPELINE_TRIGGERS,
+            "Count of pipeline triggers.",
+            &[("tenant_id", tenant_id), ("topic_id", topic_id)],
+            1,
+        )
+    }
+
+    /// step is one of pipeline, stage, unit and action
+    pub fn run_finished(step: &str, status: &MonitorLogStatus, elapsed: Duration) {
+        MetricsRegistry::observe_histogram(
+            METRIC_PIPELINE_RUN_DURATION,
+            "Duration of pipeline, stage, unit and action runs.",
+            &[("step", step), ("status", &status.to_string())],
+            elapsed.as_secs_f64(),
+        )
+    }
+
+    pub fn compiled(elapsed: Duration) {
+        MetricsRegistry::observe_histogram(
+            METRIC_PIPELINE_COMPILE_DURATION,
+            "Duration of pipeline compiling.",
+            &[],
+            elapsed.as_secs_f64(),
+        )
+    }
+
+    /// operation is one of read, insert, update and delete, zero count is ignored.
+    pub fn rows_touched(action_type: &PipelineActionType, operation: &str, count: u32) {
+        if count == 0 {
+            return;
+        }
+        MetricsRegistry::increase_counter(
+            METRIC_PIPELINE_ACTION_ROWS,
+            "Count of rows read or written by actions.",
+            &[
+                ("action_type", &action_type.to_string()),
+                ("operation", operation),
+            ],
+            count as u64,
+        )
+    }
+
+    pub fn cache_requested(cache: &str, hit: bool) {
+        MetricsRegistry::increase_counter(
+            METRIC_PIPELINE_CACHE_REQUESTS,
+            "Count of cache requests, by hit or miss.",
+            &[
+                ("cache", cache),
+                ("result", if hit { "hit" } else { "miss" }),
+            ],
+            1,
+        )
+    }
+
+    /// by error code, e.g. [crate::PipelineKernelErrorCode]
+    pub fn error_occurred(error: &StdErr) {
+        MetricsRegistry::increase_counter(
+            METRIC_PIPELINE_ERRORS,
+            "Count of errors occurred in pipeline execution, by error code.",
+            &[("code", error.code())],
+            1,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        PipelineExecuteMetrics, PipelineKernelErrorCode, METRIC_PIPELINE_ERRORS,
+        METRIC_PIPELINE_RUN_DURATION,
+    };
+    use elf_base::{
+        ErrorCode, MetricValue, MetricsExporter, MetricsRegistry, PrometheusTextExporter,
+    };
+    use elf_model::MonitorLogStatus;
+    use std::time::Duration;
+
+    #[test]
+    fn test_metrics() {
+        PipelineExecuteMetrics::error_occurred(
+            &PipelineKernelErrorCode::UnitLoopVariableNotAVec.e_msg("test"),
+        );
+        let family = MetricsRegistry::gather_one(METRIC_PIPELINE_ERRORS).unwrap();
+        let sample = family
+            .samples
+            .iter()
+            .find(|sample| sample.labels[0].1 == "PLKN-00404")
+            .unwrap();
+        assert!(matches!(sample.value, MetricValue::Counter(count) if count >= 1));
+
+        PipelineExecuteMetrics::run_finished(
+            "action",
+            &MonitorLogStatus::DONE,
+            Duration::from_micros(300),
+        );
+        let exported = PrometheusTextExporter.export_all();
+        assert!(exported.contains(&format!(
+            "{}_bucket{{status=\"DONE\",step=\"action\",le=\"0.0005\"}}",
+            METRIC_PIPELINE_RUN_DURATION
+        )));
+    }
+}
@@ -39,6 +39,10 @@ impl PipelineExecuteRequest {
         self.topic_schema.clone()
     }
 
+    pub fn trace_id(&self) -> Arc<PipelineTriggerTraceId> {
+        self.trace_id.clone()
+    }
+
     pub fn create_execution_context(self, async_monitor_log: bool) -> PipelineExecutionContext {
         PipelineExecutionContext::create(
             self.principal,
@@ -0,0 +1,59 @@
+use crate::ExecuteLogErrorStackTrace;
+use elf_model::MonitorLogStatus;
+use tracing::{Level, Span};
+
+/// tracing spans of pipeline execution, nested as trigger → pipeline → stage → unit → action.
+/// fields of outer spans are attached to logs of inner steps,
+/// e.g. all logs of one trigger can be found by its trace id.
+///
+/// when step finished, an event is logged in its span,
+/// with the same status, spent and error as the execute log of step.
+pub struct PipelineExecuteSpan;
+
+impl PipelineExecuteSpan {
+    pub fn trigger(trace_id: &str, tenant_id: &str, topic_id: &str, data_id: &str) -> Span {
+        tracing::info_span!("trigger", trace_id, tenant_id, topic_id, data_id)
+    }
+
+    pub fn pipeline(pipeline_id: &str, pipeline_name: &str, topic_id: &str) -> Span {
+        tracing::info_span!("pipeline", pipeline_id, pipeline_name, topic_id)
+    }
+
+    pub fn stage(stage_id: &str, stage_name: &str) -> Span {
+        tracing::info_span!("stage", stage_id, stage_name)
+    }
+
+    pub fn unit(unit_id: &str, unit_name: &str) -> Span {
+        tracing::info_span!("unit", unit_id, unit_name)
+    }
+
+    pub fn action(action_id: &str, action_type: &str) -> Span {
+        tracing::info_span!("action", action_id, action_type)
+    }
+
+    /// log finished event of step in current span, at warn level when status is error, otherwise debug.
+    pub fn finished(
+        step: &str,
+        status: &MonitorLogStatus,
+        spent_in_mills: u32,
+        error: &Option<ExecuteLogErrorStackTrace>,
+    ) {
+        match status {
+            MonitorLogStatus::ERROR => tracing::event!(
+                Level::WARN,
+                %status,
+                spent_in_mills,
+                error = error.as_deref().unwrap_or(""),
+                "{} finished.",
+                step
+            ),
+            _ => tracing::event!(
+                Level::DEBUG,
+                %status,
+                spent_in_mills,
+                "{} finished.",
+                step
+            ),
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use chrono::{NaiveDateTime, Utc};
+use std::time::{Duration, Instant};
+
+/// start time of step for execute log, and monotonic clock for spent.
+/// wall clock is not used for spent, since it might be adjusted during execution.
+#[derive(Clone, Copy)]
+pub struct ExecuteTimer {
+    start_time: NaiveDateTime,
+    started_at: Instant,
+}
+
+impl ExecuteTimer {
+    pub fn start() -> Self {
+        Self {
+            start_time: Utc::now().naive_utc(),
+            started_at: Instant::now(),
+        }
+    }
+
+    pub fn start_time(&self) -> NaiveDateTime {
+        self.start_time
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+
+    /// elapsed in milliseconds, [u32::MAX] when overflowed
+    pub fn spent_in_mills(&self) -> u32 {
+        u32::try_from(self.elapsed().as_millis()).unwrap_or(u32::MAX)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ExecuteTimer;
+    use std::time::Duration;
+
+    #[test]
+    fn test_spent() {
+        let timer = ExecuteTimer::start();
+        std::thread::sleep(Duration::from_millis(15));
+        assert!(timer.spent_in_mills() >= 15);
+        assert!(timer.elapsed() >= Duration::from_millis(15));
+        assert!(timer.elapsed().as_secs_f64() < 10.0);
+    }
+}
@@ -1,7 +1,11 @@
-use crate::{PipelineExecuteRequest, PipelineExecutionContext, PipelineExecutionTaskRunner};
+use crate::{
+    PipelineExecuteMetrics, PipelineExecuteRequest, PipelineExecuteSpan, PipelineExecutionContext,
+    PipelineExecutionTaskRunner,
+};
 use elf_base::{StdR, VoidR};
 use elf_model::TopicDataId;
 use std::ops::Deref;
+use tracing::{Instrument, Span};
 
 enum BuiltContext {
     Context((TopicDataId, PipelineExecutionContext)),
@@ -17,9 +21,10 @@ impl PipelineExecutor {
     ) -> StdR<BuiltContext> {
         let topic_data_id = request.topic_data_id();
         let topic_schema = request.topic_schema();
+        PipelineExecuteMetrics::triggered(topic_schema.tenant_id(), topic_schema.topic_id());
         let context = request.create_execution_context(async_monitor_log);
         if !context.has_more_task() {
-            println!(
+            tracing::info!(
                 "No pipeline needs to be triggered by topic[topic_id={}, topic_name={}].",
                 topic_schema.topic_id(),
                 topic_schema.name()
@@ -33,6 +38,16 @@ impl PipelineExecutor {
         }
     }
 
+    fn create_span(request: &PipelineExecuteRequest) -> Span {
+        let topic_schema = request.topic_schema();
+        PipelineExecuteSpan::trigger(
+            &request.trace_id(),
+            topic_schema.tenant_id(),
+            topic_schema.topic_id(),
+            &request.topic_data_id(),
+        )
+    }
+
     /// - execute task one-by-one.
     /// - tasks created by task are added into next round
     /// - all tasks are finished in [round n], then start tasks in [round n + 1].
@@ -59,10 +74,11 @@ impl PipelineExecutor {
 
     /// execute synchronized
     pub async fn execute(request: PipelineExecuteRequest) -> StdR<TopicDataId> {
-        match PipelineExecutor::build_context(request, false)? {
+        let span = Self::create_span(&request);
+        match span.in_scope(|| PipelineExecutor::build_context(request, false))? {
             BuiltContext::NoContext(topic_data_id) => Ok(topic_data_id),
             BuiltContext::Context((topic_data_id, context)) => {
-                Self::do_execute_async(context).await?;
+                Self::do_execute_async(context).instrument(span).await?;
 
                 Ok(topic_data_id)
             }
@@ -71,11 +87,12 @@ impl PipelineExecutor {
 
     /// execute asynchronized
     pub fn execute_async(request: PipelineExecuteRequest) -> StdR<TopicDataId> {
-        match PipelineExecutor::build_context(request, true)? {
+        let span = Self::create_span(&request);
+        match span.in_scope(|| PipelineExecutor::build_context(request, true))? {
             BuiltContext::NoContext(topic_data_id) => Ok(topic_data_id),
             BuiltContext::Context((topic_data_id, context)) => {
                 // TODO how spawn doing here, and how to configure the Runtime?
-                tokio::spawn(Self::do_execute_async(context));
+                tokio::spawn(Self::do_execute_async(context).instrument(span));
                 Ok(topic_data_id)
             }
         }
@@ -6,7 +6,10 @@ mod execute_envs;
 mod execute_facade;
 mod execute_input;
 mod execute_log;
+mod execute_metrics;
 mod execute_request;
+mod execute_span;
+mod execute_timer;
 mod execute_topic_data;
 mod execution_context;
 mod execution_round;
@@ -14,6 +17,7 @@ mod execution_task;
 mod execution_task_runner;
 mod executor;
 mod pre_execute;
+mod type_check;
 
 pub use compile_service::*;
 pub use compiled::*;
@@ -23,7 +27,10 @@ pub use execute_envs::*;
 pub use execute_facade::*;
 pub use execute_input::*;
 pub use execute_log::*;
+pub use execute_metrics::*;
 pub use execute_request::*;
+pub use execute_span::*;
+pub use execute_timer::*;
 pub use execute_topic_data::*;
 pub use execution_context::*;
 pub use execution_round::*;
@@ -31,3 +38,4 @@ pub use execution_task::*;
 pub use execution_task_runner::*;
 pub use executor::*;
 pub use pre_execute::*;
+pub use type_check::*;
@@ -0,0 +1,124 @@
+use elf_model::{FactorType, FactorTypeCategory, TopicId};
+use elf_runtime_model_kernel::ArcFactor;
+use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+
+/// type of value which a data path or parameter evaluates to, inferred at compile time.
+/// [DataPathType::Any] is used when the type cannot be inferred, and never leads to a mismatch.
+#[derive(Clone, PartialEq)]
+pub enum DataPathType {
+    Any,
+    /// definitely none, e.g. a blank parameter
+    None,
+    Str,
+    Num,
+    Bool,
+    Date,
+    DateTime,
+    Time,
+    /// row of topic, or value of object factor.
+    /// properties are the factors whose name starts with prefix, prefix is empty for row.
+    Object {
+        topic_id: Arc<TopicId>,
+        prefix: String,
+    },
+    Vec(Box<DataPathType>),
+}
+
+impl DataPathType {
+    pub fn row_of(topic_id: &Arc<TopicId>) -> Self {
+        Self::Object {
+            topic_id: topic_id.clone(),
+            prefix: String::new(),
+        }
+    }
+
+    /// value of encrypted factor might be a masked string, so it is [DataPathType::Any].
+    pub fn of_factor(topic_id: &Arc<TopicId>, factor: &ArcFactor) -> Self {
+        if factor.encrypt.is_some() {
+            return Self::Any;
+        }
+        match factor.r#type.category() {
+            FactorTypeCategory::Text
+            | FactorTypeCategory::TextLike
+            | FactorTypeCategory::EnumText => Self::Str,
+            FactorTypeCategory::Numeric | FactorTypeCategory::DatetimeNumeric => Self::Num,
+            FactorTypeCategory::FullDatetime | FactorTypeCategory::Datetime => Self::DateTime,
+            FactorTypeCategory::Date => Self::Date,
+            FactorTypeCategory::Time => Self::Time,
+            FactorTypeCategory::Boolean => Self::Bool,
+            FactorTypeCategory::Complex => {
+                let object = Self::Object {
+                    topic_id: topic_id.clone(),
+                    prefix: factor.name.to_string(),
+                };
+                if matches!(factor.r#type.as_ref(), FactorType::Array) {
+                    Self::Vec(Box::new(object))
+                } else {
+                    object
+                }
+            }
+        }
+    }
+
+    pub fn vec_of(element: DataPathType) -> Self {
+        match element {
+            Self::Any => Self::Any,
+            // nested vec is flattened when retrieving property from vec
+            Self::Vec(element) => Self::Vec(element),
+            element => Self::Vec(Box::new(element)),
+        }
+    }
+
+    pub fn is_scalar(&self) -> bool {
+        matches!(
+            self,
+            Self::Str | Self::Num | Self::Bool | Self::Date | Self::DateTime | Self::Time
+        )
+    }
+
+    /// string can be cast to decimal
+    pub fn is_num_like(&self) -> bool {
+        matches!(self, Self::Any | Self::None | Self::Str | Self::Num)
+    }
+
+    /// string can be cast to date/datetime
+    pub fn is_date_like(&self) -> bool {
+        matches!(
+            self,
+            Self::Any | Self::None | Self::Str | Self::Date | Self::DateTime
+        )
+    }
+
+    pub fn is_date_or_time_like(&self) -> bool {
+        self.is_date_like() || matches!(self, Self::Time)
+    }
+
+    /// same type, or the other one is none, otherwise any
+    pub fn join(self, other: DataPathType) -> Self {
+        match (self, other) {
+            (Self::None, other) => other,
+            (this, Self::None) => this,
+            (this, other) if this == other => this,
+            _ => Self::Any,
+        }
+    }
+}
+
+impl Display for DataPathType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Any => write!(f, "any"),
+            Self::None => write!(f, "none"),
+            Self::Str => write!(f, "str"),
+            Self::Num => write!(f, "num"),
+            Self::Bool => write!(f, "bool"),
+            Self::Date => write!(f, "date"),
+            Self::DateTime => write!(f, "datetime"),
+            Self::Time => write!(f, "time"),
+            Self::Object { prefix, .. } if prefix.is_empty() => write!(f, "row"),
+            Self::Object { prefix, .. } => write!(f, "object[{}]", prefix),
+            Self::Vec(element) => write!(f, "vec<{}>", element),
+        }
+    }
+}
@@ -0,0 +1,359 @@
+use crate::{
+    DataPath, DataPathFunc, DataPathSegment, DataPathType, FuncDataPath, FuncDataPathParam, FuncParamValue,
+    PathStr, PipelineTypeChecker,
+};
+use elf_model::VariablePredefineFunctions;
+use std::ops::Deref;
+
+/// type inference of data path, follows the rules of in-memory evaluation
+impl<'a> PipelineTypeChecker<'a> {
+    pub fn type_of_path(&mut self, path: &DataPath) -> DataPathType {
+        if let Some(value) = path.static_value() {
+            return Self::type_of_value(value);
+        }
+
+        let mut segments = path.segments().iter();
+        let mut r#type = match segments.next() {
+            Some(DataPathSegment::Plain(plain_path)) => {
+                self.type_of_name(&plain_path.this_path(), plain_path.path())
+            }
+            Some(DataPathSegment::Func(func_path)) => self.type_of_func(func_path, None),
+            None => return DataPathType::Any,
+        };
+        for segment in segments {
+            r#type = match segment {
+                DataPathSegment::Plain(plain_path) => {
+                    self.type_of_property(&r#type, &plain_path.this_path(), plain_path.path())
+                }
+                DataPathSegment::Func(func_path) => self.type_of_func(func_path, Some(r#type)),
+            };
+        }
+        r#type
+    }
+
+    fn type_of_value(value: &FuncParamValue) -> DataPathType {
+        match value {
+            FuncParamValue::Str(_) => DataPathType::Str,
+            FuncParamValue::Num(_) => DataPathType::Num,
+            FuncParamValue::Bool(_) => DataPathType::Bool,
+            FuncParamValue::DateTime(_) => DataPathType::DateTime,
+            FuncParamValue::Date(_) => DataPathType::Date,
+            FuncParamValue::Time(_) => DataPathType::Time,
+            FuncParamValue::None => DataPathType::None,
+        }
+    }
+
+    /// from variables first, then the trigger topic
+    fn type_of_name(&mut self, name: &str, path: &PathStr) -> DataPathType {
+        if let Some(r#type) = self.variable(name) {
+            return r#type.clone();
+        }
+        let row = DataPathType::row_of(self.trigger_topic_id());
+        self.type_of_property(&row, name, path)
+    }
+
+    /// property of row or object is the factor, [DataPathType::Any] when factor not found.
+    /// property of vec is vec of property of elements, elements must be rows or objects.
+    pub(crate) fn type_of_property(
+        &mut self,
+        source: &DataPathType,
+        name: &str,
+        path: &PathStr,
+    ) -> DataPathType {
+        match source {
+            DataPathType::Any | DataPathType::None => DataPathType::Any,
+            DataPathType::Object { topic_id, prefix } => {
+                let factor_name = if prefix.is_empty() {
+                    name.to_string()
+                } else {
+                    format!("{}.{}", prefix, name)
+                };
+                self.topic(topic_id)
+                    .and_then(|topic| topic.factor_by_name(&factor_name))
+                    .map(|factor| DataPathType::of_factor(topic_id, factor))
+                    .unwrap_or(DataPathType::Any)
+            }
+            DataPathType::Vec(element) if !element.is_scalar() => {
+                let r#type = self.type_of_property(element, name, path);
+                DataPathType::vec_of(r#type)
+            }
+            DataPathType::Vec(element) => {
+                self.mismatch(
+                    Some(path),
+                    format!(
+                        "Cannot retrieve[current={}] from vec of [{}], caused by element type of vec is not map.",
+                        name, element
+                    ),
+                );
+                DataPathType::Any
+            }
+            other => {
+                self.mismatch(
+                    Some(path),
+                    format!(
+                        "Cannot retrieve[current={}] from [{}], caused by data type is not vec or map.",
+                        name, other
+                    ),
+                );
+                DataPathType::Any
+            }
+        }
+    }
+
+    fn type_of_param(&mut self, param: &FuncDataPathParam) -> DataPathType {
+        match param {
+            FuncDataPathParam::Value(value_path) => Self::type_of_value(value_path.value()),
+            FuncDataPathParam::Plain(plain_path) => {
+                self.type_of_name(&plain_path.this_path(), plain_path.path())
+            }
+            FuncDataPathParam::Func(func_path) => self.type_of_func(func_path, None),
+            FuncDataPathParam::Path(data_path) => self.type_of_path(data_path),
+        }
+    }
+
+    fn path_of_param(param: &FuncDataPathParam) -> &PathStr {
+        match param {
+            FuncDataPathParam::Value(value_path) => value_path.path(),
+            FuncDataPathParam::Plain(plain_path) => plain_path.path(),
+            FuncDataPathParam::Func(func_path) => func_path.path(),
+            FuncDataPathParam::Path(data_path) => data_path.path(),
+        }
+    }
+
+    /// context is the type of previous segment, when function is not the first segment.
+    /// otherwise, the first param is context when function requires context.
+    fn type_of_func(
+        &mut self,
+        func_path: &FuncDataPath,
+        context: Option<DataPathType>,
+    ) -> DataPathType {
+        let func = match func_path.func() {
+            DataPathFunc::Predefined(func) => func,
+            // registered function is opaque, only its params are checked
+            DataPathFunc::Registered(_) => {
+                for param in func_path.params().iter().flatten() {
+                    self.type_of_param(param);
+                }
+                return DataPathType::Any;
+            }
+        };
+        match func {
+            VariablePredefineFunctions::NextSeq => return DataPathType::Num,
+            VariablePredefineFunctions::Now => return DataPathType::DateTime,
+            VariablePredefineFunctions::FromCurrentTriggerData
+            | VariablePredefineFunctions::FromPreviousTriggerData => {
+                return DataPathType::row_of(self.trigger_topic_id());
+            }
+            _ => {}
+        }
+
+        let params = func_path.params().as_deref().unwrap_or_default();
+        let (context, params) = match context {
+            Some(context) => (context, params),
+            None => match params.split_first() {
+                Some((first, rest)) => (self.type_of_param(first), rest),
+                None => return DataPathType::Any,
+            },
+        };
+        let param_types: Vec<(DataPathType, &PathStr)> = params
+            .iter()
+            .map(|param| (self.type_of_param(param), Self::path_of_param(param)))
+            .collect();
+
+        let path = Some(func_path.path());
+        if matches!(context, DataPathType::None) {
+            if !func.allow_none_context() {
+                self.mismatch(
+                    path,
+                    format!("Function[{}] does not allow none context.", func),
+                );
+            }
+        } else if !Self::accepts_context(func, &context) {
+            self.mismatch(
+                path,
+                format!("Function[{}] does not support context[{}].", func, context),
+            );
+        }
+        for (index, (r#type, param_path)) in param_types.iter().enumerate() {
+            if let Some(expected) = Self::unaccepted_param(func, r#type) {
+                self.mismatch(
+                    Some(param_path),
+                    format!(
+                        "Parameter[{}] of function[{}] must be {}, current is [{}].",
+                        index, func, expected, r#type
+                    ),
+                );
+            }
+        }
+
+        Self::type_of_result(func, context)
+    }
+
+    fn accepts_context(func: &VariablePredefineFunctions, context: &DataPathType) -> bool {
+        let element_matches = |matcher: fn(&DataPathType) -> bool| match context {
+            DataPathType::Vec(element) => matcher(element),
+            _ => false,
+        };
+        match func {
+            _ if matches!(context, DataPathType::Any) => true,
+            VariablePredefineFunctions::Count => {
+                matches!(context, DataPathType::Vec(_) | DataPathType::Object { .. })
+            }
+            VariablePredefineFunctions::Length | VariablePredefineFunctions::Len => {
+                matches!(context, DataPathType::Str | DataPathType::Num)
+            }
+            VariablePredefineFunctions::Slice
+            | VariablePredefineFunctions::Substr
+            | VariablePredefineFunctions::Find
+            | VariablePredefineFunctions::Index
+            | VariablePredefineFunctions::StartsWith
+            | VariablePredefineFunctions::Startswith
+            | VariablePredefineFunctions::EndsWith
+            | VariablePredefineFunctions::Endswith
+            | VariablePredefineFunctions::Strip
+            | VariablePredefineFunctions::Trim
+            | VariablePredefineFunctions::Replace
+            | VariablePredefineFunctions::ReplaceFirst
+            | VariablePredefineFunctions::Upper
+            | VariablePredefineFunctions::Lower
+            | VariablePredefineFunctions::Contains
+            | VariablePredefineFunctions::Split => matches!(context, DataPathType::Str),
+            VariablePredefineFunctions::Concat | VariablePredefineFunctions::ConcatWith => {
+                context.is_scalar()
+            }
+            VariablePredefineFunctions::Join => {
+                !matches!(context, DataPathType::Object { .. })
+                    && !element_matches(|element| !element.is_scalar())
+            }
+            VariablePredefineFunctions::Distinct => !matches!(context, DataPathType::Object { .. }),
+            VariablePredefineFunctions::Sum
+            | VariablePredefineFunctions::Avg
+            | VariablePredefineFunctions::MaxNum
+            | VariablePredefineFunctions::MinNum => element_matches(DataPathType::is_num_like),
+            VariablePredefineFunctions::Max | VariablePredefineFunctions::Min => {
+                element_matches(|element| element.is_num_like() || element.is_date_or_time_like())
+            }
+            VariablePredefineFunctions::MaxDate
+            | VariablePredefineFunctions::MinDate
+            | VariablePredefineFunctions::MaxDatetime
+            | VariablePredefineFunctions::MinDatetime
+            | VariablePredefineFunctions::MaxDt
+            | VariablePredefineFunctions::MinDt => element_matches(DataPathType::is_date_like),
+            VariablePredefineFunctions::MaxTime | VariablePredefineFunctions::MinTime => {
+                element_matches(|element| {
+                    matches!(
+                        element,
+                        DataPathType::Any
+                            | DataPathType::None
+                            | DataPathType::Str
+                            | DataPathType::Time
+                    )
+                })
+            }
+            VariablePredefineFunctions::DayDiff
+            | VariablePredefineFunctions::MonthDiff
+            | VariablePredefineFunctions::YearDiff => context.is_date_like(),
+            VariablePredefineFunctions::MoveDate | VariablePredefineFunctions::DateFormat => {
+                context.is_date_or_time_like()
+            }
+            VariablePredefineFunctions::NextSeq
+            | VariablePredefineFunctions::Now
+            | VariablePredefineFunctions::FromCurrentTriggerData
+            | VariablePredefineFunctions::FromPreviousTriggerData => true,
+        }
+    }
+
+    /// returns the expected type when param is not accepted
+    fn unaccepted_param(
+        func: &VariablePredefineFunctions,
+        r#type: &DataPathType,
+    ) -> Option<&'static str> {
+        match func {
+            VariablePredefineFunctions::DayDiff
+            | VariablePredefineFunctions::MonthDiff
+            | VariablePredefineFunctions::YearDiff
+                if !r#type.is_date_like() =>
+            {
+                Some("a date")
+            }
+            VariablePredefineFunctions::Slice | VariablePredefineFunctions::Substr
+                if !r#type.is_num_like() =>
+            {
+                Some("a decimal")
+            }
+            // movement might be vec when it is computed
+            VariablePredefineFunctions::MoveDate => None,
+            _ if matches!(r#type, DataPathType::Object { .. } | DataPathType::Vec(_)) => {
+                Some("neither vec nor map")
+            }
+            _ => None,
+        }
+    }
+
+    fn type_of_result(func: &VariablePredefineFunctions, context: DataPathType) -> DataPathType {
+        match func {
+            VariablePredefineFunctions::NextSeq
+            | VariablePredefineFunctions::Count
+            | VariablePredefineFunctions::Length
+            | VariablePredefineFunctions::Len
+            | VariablePredefineFunctions::Find
+            | VariablePredefineFunctions::Index
+            | VariablePredefineFunctions::Sum
+            | VariablePredefineFunctions::Avg
+            | VariablePredefineFunctions::MaxNum
+            | VariablePredefineFunctions::MinNum
+            | VariablePredefineFunctions::DayDiff
+            | VariablePredefineFunctions::MonthDiff
+            | VariablePredefineFunctions::YearDiff => DataPathType::Num,
+            VariablePredefineFunctions::Slice
+            | VariablePredefineFunctions::Substr
+            | VariablePredefineFunctions::Strip
+            | VariablePredefineFunctions::Trim
+            | VariablePredefineFunctions::Replace
+            | VariablePredefineFunctions::ReplaceFirst
+            | VariablePredefineFunctions::Upper
+            | VariablePredefineFunctions::Lower
+            | VariablePredefineFunctions::Concat
+            | VariablePredefineFunctions::ConcatWith
+            | VariablePredefineFunctions::Join
+            | VariablePredefineFunctions::DateFormat => DataPathType::Str,
+            VariablePredefineFunctions::StartsWith
+            | VariablePredefineFunctions::Startswith
+            | VariablePredefineFunctions::EndsWith
+            | VariablePredefineFunctions::Endswith
+            | VariablePredefineFunctions::Contains => DataPathType::Bool,
+            VariablePredefineFunctions::Split => DataPathType::vec_of(DataPathType::Str),
+            VariablePredefineFunctions::Distinct => match context {
+                DataPathType::Any | DataPathType::Vec(_) => context,
+                other => DataPathType::vec_of(other),
+            },
+            // string elements might be cast to any of decimal/date/datetime/time
+            VariablePredefineFunctions::Max | VariablePredefineFunctions::Min => match context {
+                DataPathType::Vec(element)
+                    if element.is_scalar() && !matches!(element.deref(), DataPathType::Str) =>
+                {
+                    *element
+                }
+                _ => DataPathType::Any,
+            },
+            VariablePredefineFunctions::MaxDate | VariablePredefineFunctions::MinDate => {
+                DataPathType::Date
+            }
+            VariablePredefineFunctions::MaxDatetime
+            | VariablePredefineFunctions::MinDatetime
+            | VariablePredefineFunctions::MaxDt
+            | VariablePredefineFunctions::MinDt
+            | VariablePredefineFunctions::Now => DataPathType::DateTime,
+            VariablePredefineFunctions::MaxTime | VariablePredefineFunctions::MinTime => {
+                DataPathType::Time
+            }
+            VariablePredefineFunctions::MoveDate => match context {
+                DataPathType::Date | DataPathType::DateTime | DataPathType::Time => context,
+                _ => DataPathType::Any,
+            },
+            // handled before, trigger data is row of trigger topic
+            VariablePredefineFunctions::FromCurrentTriggerData
+            | VariablePredefineFunctions::FromPreviousTriggerData => DataPathType::Any,
+        }
+    }
+}
@@ -0,0 +1,8 @@
+mod data_path_type;
+mod data_path_type_inference;
+mod pipeline_type_checker;
+mod pipeline_type_mismatch;
+
+pub use data_path_type::*;
+pub use pipeline_type_checker::*;
+pub use pipeline_type_mismatch::*;
@@ -0,0 +1,655 @@
+use crate::{DataPath, DataPathType, PathStr, PipelineKernelErrorCode, PipelineTypeMismatch};
+use elf_base::{ErrorCode, StdR, StringUtils, VoidR};
+use elf_model::{AggregateArithmetic, FactorId, ParameterComputeType, TenantId, TopicId};
+use elf_runtime_model_kernel::{
+    ArcComputedParameter, ArcMappingFactor, ArcParameter, ArcParameterCondition,
+    ArcParameterExpression, ArcParameterJoint, ArcPipeline, ArcPipelineAction, TopicSchema,
+};
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// infer the types of data paths and parameters of pipeline, by
+/// - factor types of topics,
+/// - restrictions and accepted context of predefined functions,
+/// - kinds of computed parameters,
+///
+/// and collect all mismatches, which lead to errors when pipeline runs.
+/// memory variables are tracked in declaration order, the loop variable of unit is element of vec.
+/// topics not given are treated as unknown, and the types of their factors are [DataPathType::Any].
+pub struct PipelineTypeChecker<'a> {
+    topics: &'a HashMap<Arc<TopicId>, Arc<TopicSchema>>,
+    trigger_topic_id: Arc<TopicId>,
+    variables: HashMap<String, DataPathType>,
+    location: String,
+    mismatches: Vec<PipelineTypeMismatch>,
+    /// tenant of pipeline, to resolve the registered functions in data path
+    tenant_id: Option<Arc<TenantId>>,
+}
+
+impl<'a> PipelineTypeChecker<'a> {
+    pub fn new(
+        trigger_topic_id: Arc<TopicId>,
+        topics: &'a HashMap<Arc<TopicId>, Arc<TopicSchema>>,
+    ) -> Self {
+        Self {
+            topics,
+            trigger_topic_id,
+            variables: HashMap::new(),
+            location: String::new(),
+            mismatches: vec![],
+            tenant_id: None,
+        }
+    }
+
+    fn parse_path(&self, path: &str) -> StdR<DataPath> {
+        match &self.tenant_id {
+            Some(tenant_id) => DataPath::from_str_of_tenant(path, tenant_id),
+            None => DataPath::from_str(path),
+        }
+    }
+
+    pub fn check(mut self, pipeline: &ArcPipeline) -> StdR<Vec<PipelineTypeMismatch>> {
+        self.tenant_id = Some(pipeline.tenant_id.clone());
+        self.joint(&pipeline.on)?;
+        for stage in pipeline.stages.iter() {
+            self.location = format!("stage[{}]", stage.stage_id);
+            self.joint(&stage.on)?;
+            for unit in stage.units.iter() {
+                self.location = format!("stage[{}], unit[{}]", stage.stage_id, unit.unit_id);
+                self.joint(&unit.on)?;
+                let loop_variable = match &unit.loop_variable_name {
+                    Some(name) if name.is_not_blank() => {
+                        let element = self.element_of_loop_variable(name);
+                        Some((name, self.variables.insert(name.to_string(), element)))
+                    }
+                    _ => None,
+                };
+                for action in unit.r#do.iter() {
+                    self.location = format!(
+                        "stage[{}], unit[{}], action[{}]",
+                        stage.stage_id,
+                        unit.unit_id,
+                        Self::action_id_of(action)
+                    );
+                    self.action(action)?;
+                }
+                // loop variable is visible in unit only
+                match loop_variable {
+                    Some((name, Some(previous))) => {
+                        self.variables.insert(name.to_string(), previous);
+                    }
+                    Some((name, None)) => {
+                        self.variables.remove(name.as_str());
+                    }
+                    None => {}
+                }
+            }
+        }
+        Ok(self.mismatches)
+    }
+
+    /// check pipeline, raise error which lists all mismatches when there is any.
+    pub fn check_or_raise(self, pipeline: &ArcPipeline) -> VoidR {
+        let mismatches = self.check(pipeline)?;
+        if mismatches.is_empty() {
+            return Ok(());
+        }
+        PipelineKernelErrorCode::PipelineTypeMismatch.msg(format!(
+            "Type mismatches found in pipeline[pipeline_id={}]:\n{}",
+            pipeline.pipeline_id,
+            mismatches
+                .iter()
+                .map(|mismatch| format!("- {}", mismatch))
+                .collect::<Vec<String>>()
+                .join("\n")
+        ))
+    }
+
+    pub(crate) fn mismatch(&mut self, path: Option<&PathStr>, message: String) {
+        self.mismatches.push(PipelineTypeMismatch {
+            location: self.location.clone(),
+            path: path.cloned(),
+            message,
+        });
+    }
+
+    pub(crate) fn topic(&self, topic_id: &TopicId) -> Option<&Arc<TopicSchema>> {
+        self.topics.get(topic_id)
+    }
+
+    pub(crate) fn trigger_topic_id(&self) -> &Arc<TopicId> {
+        &self.trigger_topic_id
+    }
+
+    pub(crate) fn variable(&self, name: &str) -> Option<&DataPathType> {
+        self.variables.get(name)
+    }
+
+    /// variable name might be a path, in this case, type of the first part is unknown after declared.
+    fn declare(&mut self, variable_name: &str, r#type: DataPathType) {
+        match variable_name.split_once('.') {
+            Some((name, _)) => self.variables.insert(name.to_string(), DataPathType::Any),
+            None => self.variables.insert(variable_name.to_string(), r#type),
+        };
+    }
+
+    fn element_of_loop_variable(&mut self, name: &str) -> DataPathType {
+        match self.variables.get(name) {
+            Some(DataPathType::Vec(element)) => element.deref().clone(),
+            Some(DataPathType::Any) | Some(DataPathType::None) | None => DataPathType::Any,
+            Some(other) => {
+                let message = format!(
+                    "Loop variable[{}] must be a vec, current is [{}].",
+                    name, other
+                );
+                self.mismatch(None, message);
+                DataPathType::Any
+            }
+        }
+    }
+
+    fn action_id_of(action: &ArcPipelineAction) -> &str {
+        match action {
+            ArcPipelineAction::Alarm(action) => &action.action_id,
+            ArcPipelineAction::CopyToMemory(action) => &action.action_id,
+            ArcPipelineAction::WriteToExternal(action) => &action.action_id,
+            ArcPipelineAction::ReadRow(action) => &action.action_id,
+            ArcPipelineAction::ReadFactor(action) => &action.action_id,
+            ArcPipelineAction::Exists(action) => &action.action_id,
+            ArcPipelineAction::ReadRows(action) => &action.action_id,
+            ArcPipelineAction::ReadFactors(action) => &action.action_id,
+            ArcPipelineAction::MergeRow(action) => &action.action_id,
+            ArcPipelineAction::InsertRow(action) => &action.action_id,
+            ArcPipelineAction::InsertOrMergeRow(action) => &action.action_id,
+            ArcPipelineAction::WriteFactor(action) => &action.action_id,
+            ArcPipelineAction::DeleteRow(action) => &action.action_id,
+            ArcPipelineAction::DeleteRows(action) => &action.action_id,
+        }
+    }
+
+    fn type_of_factor(&self, topic_id: &Arc<TopicId>, factor_id: &FactorId) -> DataPathType {
+        self.topic(topic_id)
+            .and_then(|topic| topic.factor_by_id(factor_id))
+            .map(|factor| DataPathType::of_factor(topic_id, factor))
+            .unwrap_or(DataPathType::Any)
+    }
+
+    fn action(&mut self, action: &Arc<ArcPipelineAction>) -> VoidR {
+        match action.deref() {
+            ArcPipelineAction::Alarm(action) => self.joint(&action.on)?,
+            ArcPipelineAction::CopyToMemory(action) => {
+                let r#type = self.type_of_parameter(&action.source)?;
+                self.declare(&action.variable_name, r#type);
+            }
+            ArcPipelineAction::WriteToExternal(_) => {}
+            ArcPipelineAction::ReadRow(action) => {
+                self.joint_of(&action.by)?;
+                self.declare(
+                    &action.variable_name,
+                    DataPathType::row_of(&action.topic_id),
+                );
+            }
+            ArcPipelineAction::ReadRows(action) => {
+                self.joint_of(&action.by)?;
+                self.declare(
+                    &action.variable_name,
+                    DataPathType::vec_of(DataPathType::row_of(&action.topic_id)),
+                );
+            }
+            ArcPipelineAction::ReadFactor(action) => {
+                self.joint_of(&action.by)?;
+                let r#type = match action.arithmetic.deref() {
+                    AggregateArithmetic::None => {
+                        self.type_of_factor(&action.topic_id, &action.factor_id)
+                    }
+                    _ => DataPathType::Num,
+                };
+                self.declare(&action.variable_name, r#type);
+            }
+            ArcPipelineAction::ReadFactors(action) => {
+                self.joint_of(&action.by)?;
+                let r#type = self.type_of_factor(&action.topic_id, &action.factor_id);
+                self.declare(&action.variable_name, DataPathType::vec_of(r#type));
+            }
+            ArcPipelineAction::Exists(action) => {
+                self.joint_of(&action.by)?;
+                self.declare(&action.variable_name, DataPathType::Bool);
+            }
+            ArcPipelineAction::InsertRow(action) => self.mapping(&action.mapping)?,
+            ArcPipelineAction::MergeRow(action) => {
+                self.mapping(&action.mapping)?;
+                self.joint_of(&action.by)?;
+            }
+            ArcPipelineAction::InsertOrMergeRow(action) => {
+                self.mapping(&action.mapping)?;
+                self.joint_of(&action.by)?;
+            }
+            ArcPipelineAction::WriteFactor(action) => {
+                self.type_of_parameter(&action.source)?;
+                self.joint_of(&action.by)?;
+            }
+            ArcPipelineAction::DeleteRow(action) => self.joint_of(&action.by)?,
+            ArcPipelineAction::DeleteRows(action) => self.joint_of(&action.by)?,
+        }
+        Ok(())
+    }
+
+    fn mapping(&mut self, mapping: &Arc<Vec<Arc<ArcMappingFactor>>>) -> VoidR {
+        for mapping_factor in mapping.iter() {
+            self.type_of_parameter(&mapping_factor.source)?;
+        }
+        Ok(())
+    }
+
+    fn joint(&mut self, joint: &Option<Arc<ArcParameterJoint>>) -> VoidR {
+        if let Some(joint) = joint {
+            self.joint_of(joint)?;
+        }
+        Ok(())
+    }
+
+    fn joint_of(&mut self, joint: &Arc<ArcParameterJoint>) -> VoidR {
+        for filter in joint.filters.iter() {
+            match filter.deref() {
+                ArcParameterCondition::Joint(joint) => self.joint_of(joint)?,
+                ArcParameterCondition::Expression(expression) => {
+                    let (left, right) = match expression.deref() {
+                        ArcParameterExpression::Empty(e) => (&e.left, None),
+                        ArcParameterExpression::NotEmpty(e) => (&e.left, None),
+                        ArcParameterExpression::Equals(e) => (&e.left, Some(&e.right)),
+                        ArcParameterExpression::NotEquals(e) => (&e.left, Some(&e.right)),
+                        ArcParameterExpression::LessThan(e) => (&e.left, Some(&e.right)),
+                        ArcParameterExpression::LessThanOrEquals(e) => (&e.left, Some(&e.right)),
+                        ArcParameterExpression::MoreThan(e) => (&e.left, Some(&e.right)),
+                        ArcParameterExpression::MoreThanOrEquals(e) => (&e.left, Some(&e.right)),
+                        ArcParameterExpression::In(e) => (&e.left, Some(&e.right)),
+                        ArcParameterExpression::NotIn(e) => (&e.left, Some(&e.right)),
+                    };
+                    self.type_of_parameter(left)?;
+                    if let Some(right) = right {
+                        self.type_of_parameter(right)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// arguments of computed parameter, routes of case-then.
+    fn arguments_of(parameter: &ArcComputedParameter) -> Vec<&Arc<ArcParameter>> {
+        match parameter {
+            ArcComputedParameter::None(p) => p.parameters.iter().collect(),
+            ArcComputedParameter::Add(p) => p.parameters.iter().collect(),
+            ArcComputedParameter::Subtract(p) => p.parameters.iter().collect(),
+            ArcComputedParameter::Multiply(p) => p.parameters.iter().collect(),
+            ArcComputedParameter::Divide(p) => p.parameters.iter().collect(),
+            ArcComputedParameter::Modulus(p) => p.parameters.iter().collect(),
+            ArcComputedParameter::YearOf(p) => vec![&p.parameter],
+            ArcComputedParameter::HalfYearOf(p) => vec![&p.parameter],
+            ArcComputedParameter::QuarterOf(p) => vec![&p.parameter],
+            ArcComputedParameter::MonthOf(p) => vec![&p.parameter],
+            ArcComputedParameter::WeekOfYear(p) => vec![&p.parameter],
+            ArcComputedParameter::WeekOfMonth(p) => vec![&p.parameter],
+            ArcComputedParameter::DayOfMonth(p) => vec![&p.parameter],
+            ArcComputedParameter::DayOfWeek(p) => vec![&p.parameter],
+            ArcComputedParameter::CaseThen(p) => {
+                p.parameters.iter().map(|route| &route.parameter).collect()
+            }
+        }
+    }
+
+    fn compute_type_of(parameter: &ArcComputedParameter) -> &ParameterComputeType {
+        match parameter {
+            ArcComputedParameter::None(p) => &p.r#type,
+            ArcComputedParameter::Add(p) => &p.r#type,
+            ArcComputedParameter::Subtract(p) => &p.r#type,
+            ArcComputedParameter::Multiply(p) => &p.r#type,
+            ArcComputedParameter::Divide(p) => &p.r#type,
+            ArcComputedParameter::Modulus(p) => &p.r#type,
+            ArcComputedParameter::YearOf(p) => &p.r#type,
+            ArcComputedParameter::HalfYearOf(p) => &p.r#type,
+            ArcComputedParameter::QuarterOf(p) => &p.r#type,
+            ArcComputedParameter::MonthOf(p) => &p.r#type,
+            ArcComputedParameter::WeekOfYear(p) => &p.r#type,
+            ArcComputedParameter::WeekOfMonth(p) => &p.r#type,
+            ArcComputedParameter::DayOfMonth(p) => &p.r#type,
+            ArcComputedParameter::DayOfWeek(p) => &p.r#type,
+            ArcComputedParameter::CaseThen(p) => &p.r#type,
+        }
+    }
+
+    /// text of parameter, topic factor is written as factor name, constant as is,
+    /// and computed as [type(argument, ...)].
+    fn write_text(&self, parameter: &ArcParameter, text: &mut Vec<char>) {
+        match parameter {
+            ArcParameter::Topic(parameter) => {
+                let name = self
+                    .topic(&parameter.topic_id)
+                    .and_then(|topic| topic.factor_by_id(&parameter.factor_id))
+                    .map(|factor| factor.name.as_str())
+                    .unwrap_or(parameter.factor_id.as_str());
+                text.extend(name.chars());
+            }
+            ArcParameter::Constant(parameter) => text.extend(parameter.value.chars()),
+            ArcParameter::Computed(parameter) => self.write_computed_text(parameter, text, None),
+        }
+    }
+
+    /// spans of arguments are collected when given
+    fn write_computed_text(
+        &self,
+        parameter: &ArcComputedParameter,
+        text: &mut Vec<char>,
+        mut spans: Option<&mut Vec<(usize, usize)>>,
+    ) {
+        text.extend(Self::compute_type_of(parameter).to_string().chars());
+        text.push('(');
+        for (index, argument) in Self::arguments_of(parameter).into_iter().enumerate() {
+            if index != 0 {
+                text.extend([',', ' ']);
+            }
+            let start = text.len();
+            self.write_text(argument, text);
+            if let Some(spans) = spans.as_mut() {
+                spans.push((start, text.len()));
+            }
+        }
+        text.push(')');
+    }
+
+    /// path of argument of computed parameter, for locating the mismatch,
+    /// full path is text of computed parameter, span is the argument.
+    /// span of constant argument is narrowed to its last segment, which decides the type.
+    fn path_of_argument(&self, computed: &ArcComputedParameter, index: usize) -> Option<PathStr> {
+        let argument = *Self::arguments_of(computed).get(index)?;
+        let mut text = vec![];
+        let mut spans = vec![];
+        self.write_computed_text(computed, &mut text, Some(&mut spans));
+        let (mut start, mut end) = *spans.get(index)?;
+        if let ArcParameter::Constant(parameter) = argument.deref()
+            && let Ok(path) = self.parse_path(&parameter.value)
+            && !path.is_static()
+            && let Some(segment) = path.segments().last()
+            && segment.full_path() == *parameter.value
+        {
+            end = start + segment.end_at();
+            start += segment.start_at();
+        }
+        Some(PathStr::part_of_chars(Arc::new(text), start, end))
+    }
+
+    pub fn type_of_parameter(&mut self, parameter: &Arc<ArcParameter>) -> StdR<DataPathType> {
+        match parameter.deref() {
+            ArcParameter::Topic(parameter) => {
+                let Some(factor) = self
+                    .topic(&parameter.topic_id)
+                    .and_then(|topic| topic.factor_by_id(&parameter.factor_id))
+                    .cloned()
+                else {
+                    return Ok(DataPathType::Any);
+                };
+                // factor name might contain dot, which means a property of object or vec,
+                // mismatch is located at the name part.
+                let chars: Arc<Vec<char>> = Arc::new(factor.name.chars().collect());
+                let mut r#type = DataPathType::row_of(&parameter.topic_id);
+                let mut start = 0;
+                for name in factor.name.split('.') {
+                    let end = start + name.chars().count();
+                    let path = PathStr::part_of_chars(chars.clone(), start, end);
+                    r#type = self.type_of_property(&r#type, name, &path);
+                    start = end + 1;
+                }
+                Ok(r#type)
+            }
+            ArcParameter::Constant(parameter) => {
+                if parameter.value.is_blank() {
+                    Ok(DataPathType::None)
+                } else {
+                    Ok(self.type_of_path(&self.parse_path(&parameter.value)?))
+                }
+            }
+            ArcParameter::Computed(computed) => match computed.deref() {
+                ArcComputedParameter::None(_) => Ok(DataPathType::Any),
+                ArcComputedParameter::Add(_) => self.arithmetic("add", computed),
+                ArcComputedParameter::Subtract(_) => self.arithmetic("subtract", computed),
+                ArcComputedParameter::Multiply(_) => self.arithmetic("multiply", computed),
+                ArcComputedParameter::Divide(_) => self.arithmetic("divide", computed),
+                ArcComputedParameter::Modulus(_) => self.arithmetic("modulus", computed),
+                ArcComputedParameter::YearOf(_) => self.date_part("year of", computed),
+                ArcComputedParameter::HalfYearOf(_) => self.date_part("half year of", computed),
+                ArcComputedParameter::QuarterOf(_) => self.date_part("quarter of", computed),
+                ArcComputedParameter::MonthOf(_) => self.date_part("month of", computed),
+                ArcComputedParameter::WeekOfYear(_) => self.date_part("week of year", computed),
+                ArcComputedParameter::WeekOfMonth(_) => self.date_part("week of month", computed),
+                ArcComputedParameter::DayOfMonth(_) => self.date_part("day of month", computed),
+                ArcComputedParameter::DayOfWeek(_) => self.date_part("day of week", computed),
+                ArcComputedParameter::CaseThen(p) => {
+                    let mut r#type = DataPathType::None;
+                    for route in p.parameters.iter() {
+                        self.joint(&route.on)?;
+                        r#type = r#type.join(self.type_of_parameter(&route.parameter)?);
+                    }
+                    Ok(r#type)
+                }
+            },
+        }
+    }
+
+    /// arguments must be decimals, or strings which can be cast to decimal
+    fn arithmetic(&mut self, name: &str, computed: &ArcComputedParameter) -> StdR<DataPathType> {
+        for (index, parameter) in Self::arguments_of(computed).into_iter().enumerate() {
+            let r#type = self.type_of_parameter(parameter)?;
+            if !r#type.is_num_like() {
+                let path = self.path_of_argument(computed, index);
+                self.mismatch(
+                    path.as_ref(),
+                    format!(
+                        "Argument value of {} must be a decimal, current is [{}].",
+                        name, r#type
+                    ),
+                );
+            }
+        }
+        Ok(DataPathType::Num)
+    }
+
+    /// argument must be a date/datetime, or string which can be cast to date
+    fn date_part(&mut self, name: &str, computed: &ArcComputedParameter) -> StdR<DataPathType> {
+        for (index, parameter) in Self::arguments_of(computed).into_iter().enumerate() {
+            let r#type = self.type_of_parameter(parameter)?;
+            if !r#type.is_date_like() {
+                let path = self.path_of_argument(computed, index);
+                self.mismatch(
+                    path.as_ref(),
+                    format!(
+                        "Argument value of {} must be a date, current is [{}].",
+                        name, r#type
+                    ),
+                );
+            }
+        }
+        Ok(DataPathType::Num)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{PipelineTypeChecker, TestFixtures};
+    use elf_model::{
+        AddParameter, ConstantParameter, CopyToMemoryAction, FactorType, Pipeline, PipelineAction,
+        PipelineStage, PipelineTriggerType, PipelineUnit, YearOfParameter,
+    };
+    use elf_runtime_model_kernel::{PipelineSchema, TopicSchema};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    fn topic() -> Arc<TopicSchema> {
+        let factors = vec![
+            ("f1", "amount", FactorType::Number),
+            ("f2", "code", FactorType::Text),
+            ("f3", "date", FactorType::Date),
+            ("f4", "items", FactorType::Array),
+            ("f5", "items.price", FactorType::Number),
+            ("f6", "items.name", FactorType::Text),
+        ];
+        TestFixtures::topic("t1", factors)
+    }
+
+    fn copy(action_id: &str, variable_name: &str, source: elf_model::Parameter) -> PipelineAction {
+        CopyToMemoryAction::init()
+            .action_id(action_id.to_string())
+            .variable_name(variable_name.to_string())
+            .source(source)
+            .to_action()
+    }
+
+    fn constant(value: &str) -> elf_model::Parameter {
+        ConstantParameter::of(value.to_string()).to_parameter()
+    }
+
+    fn topic_factor(factor_id: &str) -> elf_model::Parameter {
+        TestFixtures::topic_factor("t1", factor_id)
+    }
+
+    fn unit(
+        unit_id: &str,
+        loop_variable_name: Option<&str>,
+        actions: Vec<PipelineAction>,
+    ) -> PipelineUnit {
+        let unit = PipelineUnit::new()
+            .unit_id(unit_id.to_string())
+            .r#do(actions);
+        match loop_variable_name {
+            Some(name) => unit.loop_variable_name(name.to_string()),
+            None => unit,
+        }
+    }
+
+    fn pipeline(units: Vec<PipelineUnit>) -> Arc<PipelineSchema> {
+        let pipeline = Pipeline::new()
+            .pipeline_id(String::from("p1"))
+            .name(String::from("orders"))
+            .topic_id(String::from("t1"))
+            .r#type(PipelineTriggerType::Insert)
+            .stages(vec![PipelineStage::new()
+                .stage_id(String::from("s1"))
+                .units(units)])
+            .tenant_id(TestFixtures::tenant_id());
+        Arc::new(PipelineSchema::new(pipeline).unwrap())
+    }
+
+    fn check(units: Vec<PipelineUnit>) -> Vec<String> {
+        let topic = topic();
+        let topics = HashMap::from([(topic.topic_id().clone(), topic.clone())]);
+        PipelineTypeChecker::new(topic.topic_id().clone(), &topics)
+            .check(pipeline(units).pipeline())
+            .unwrap()
+            .iter()
+            .map(|mismatch| mismatch.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_data_path() {
+        let mismatches = check(vec![unit(
+            "u1",
+            None,
+            vec![
+                copy("a1", "total", constant("items.price.&sum")),
+                copy("a2", "upper", constant("&upper(&cur.code)")),
+                copy("a3", "diff", constant("&dayDiff(date,{}2024-01-01)")),
+                // property of text
+                copy("a4", "x", constant("code.length")),
+                // sum of text
+                copy("a5", "y", constant("code.&sum")),
+                // property of vec of numbers, and variable
+                copy("a6", "z", constant("{}total:{total.value}")),
+                // parameter of day diff
+                copy("a7", "w", constant("&dayDiff(date,items)")),
+            ],
+        )]);
+        assert_eq!(
+            mismatches,
+            vec![
+                "stage[s1], unit[u1], action[a4], path[code.length] at [5, 11): Cannot retrieve[current=length] from [str], caused by data type is not vec or map.",
+                "stage[s1], unit[u1], action[a5], path[code.&sum] at [5, 9): Function[&sum] does not support context[str].",
+                "stage[s1], unit[u1], action[a6], path[{}total:{total.value}] at [15, 20): Cannot retrieve[current=value] from [num], caused by data type is not vec or map.",
+                "stage[s1], unit[u1], action[a7], path[&dayDiff(date,items)] at [14, 19): Parameter[0] of function[&dayDiff] must be a date, current is [vec<object[items]>].",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_computed_and_loop() {
+        let mismatches = check(vec![
+            unit(
+                "u1",
+                None,
+                vec![
+                    copy(
+                        "a1",
+                        "sum",
+                        AddParameter::init()
+                            .parameters(vec![topic_factor("f1"), topic_factor("f3")])
+                            .to_parameter(),
+                    ),
+                    copy("a2", "names", constant("items.name")),
+                ],
+            ),
+            // element of vec
+            unit(
+                "u2",
+                Some("names"),
+                vec![copy("a3", "n", constant("names.&upper"))],
+            ),
+            unit("u3", Some("sum"), vec![copy("a4", "m", constant("sum"))]),
+            // loop variable is visible in unit only
+            unit("u4", None, vec![copy("a5", "n", constant("names.&upper"))]),
+        ]);
+        assert_eq!(
+            mismatches,
+            vec![
+                "stage[s1], unit[u1], action[a1], path[add(amount, date)] at [12, 16): Argument value of add must be a decimal, current is [date].",
+                "stage[s1], unit[u3], Loop variable[sum] must be a vec, current is [num].",
+                "stage[s1], unit[u4], action[a5], path[names.&upper] at [6, 12): Function[&upper] does not support context[vec<str>].",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_spans() {
+        let mismatches = check(vec![unit(
+            "u1",
+            None,
+            vec![
+                copy(
+                    "a1",
+                    "year",
+                    YearOfParameter::init()
+                        .parameter(Box::new(
+                            AddParameter::init()
+                                .parameters(vec![topic_factor("f1"), topic_factor("f3")])
+                                .to_parameter(),
+                        ))
+                        .to_parameter(),
+                ),
+                copy(
+                    "a2",
+                    "sum",
+                    AddParameter::init()
+                        .parameters(vec![topic_factor("f1"), constant("items.name")])
+                        .to_parameter(),
+                ),
+            ],
+        )]);
+        assert_eq!(
+            mismatches,
+            vec![
+                "stage[s1], unit[u1], action[a1], path[add(amount, date)] at [12, 16): Argument value of add must be a decimal, current is [date].",
+                "stage[s1], unit[u1], action[a1], path[year-of(add(amount, date))] at [8, 25): Argument value of year of must be a date, current is [num].",
+                "stage[s1], unit[u1], action[a2], path[add(amount, items.name)] at [18, 22): Argument value of add must be a decimal, current is [vec<str>].",
+            ]
+        );
+    }
+}
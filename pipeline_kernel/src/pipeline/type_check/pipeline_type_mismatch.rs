@@ -0,0 +1,32 @@
+use crate::PathStr;
+use std::fmt::{Display, Formatter};
+
+/// a type mismatch found at compile time, which leads to an error when pipeline runs.
+/// location is the stage, unit and action where the mismatch is found, empty for pipeline prerequisite.
+/// path is the data path where the mismatch is found, span is [start, end) of the offending segment in full path.
+/// for argument of computed parameter, full path is text of computed parameter, e.g. [add(amount, date)].
+/// path is none when mismatch is not on any parameter, e.g. loop variable.
+pub struct PipelineTypeMismatch {
+    pub location: String,
+    pub path: Option<PathStr>,
+    pub message: String,
+}
+
+impl Display for PipelineTypeMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if !self.location.is_empty() {
+            write!(f, "{}, ", self.location)?;
+        }
+        match &self.path {
+            Some(path) => write!(
+                f,
+                "path[{}] at [{}, {}): {}",
+                path.full(),
+                path.start_index(),
+                path.end_index(),
+                self.message
+            ),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
@@ -0,0 +1,9 @@
+mod report_chart_data;
+mod report_data_service;
+mod report_echarts_option;
+mod report_funnel_filter;
+
+pub use report_chart_data::*;
+pub use report_data_service::*;
+pub use report_echarts_option::*;
+pub use report_funnel_filter::*;
@@ -0,0 +1,205 @@
+use crate::{ArcTopicDataValue, SubjectDatasetEngine, SubjectJoiner, SubjectValueRow};
+use bigdecimal::BigDecimal;
+use elf_model::{ChartType, DataResult, DataResultSetCell, DataResultSetRow};
+use std::collections::HashMap;
+use std::ops::Deref;
+
+/// shape the aggregated rows of report for its chart,
+/// each row has values of dimensions first, and then values of indicators.
+/// - bar and line: first dimension is the axis, and the others are series.
+///   with one dimension, each indicator is a series.
+///   with more dimensions, rows are pivoted, each combination of the other dimensions (and indicator,
+///   when there are more indicators) is a series, named by values joined with " / ",
+/// - sunburst, tree and treemap: nodes of hierarchy, one node for each distinct path of dimension values,
+///   as columns "id", "parentId", "name" and indicators.
+///   id is the path joined with "/", parent id of root node is none,
+///   value of node is the sum of its leaves, none if any of them is not numeric,
+/// - count: indicators only,
+/// - others: rows as they are.
+pub struct ReportChartData;
+
+impl ReportChartData {
+    pub fn shape(
+        chart_type: Option<&ChartType>,
+        dimensions: Vec<String>,
+        indicators: Vec<String>,
+        rows: Vec<SubjectValueRow>,
+    ) -> DataResult {
+        match chart_type {
+            Some(ChartType::Bar | ChartType::Line) if dimensions.len() > 1 => {
+                Self::pivot(dimensions, indicators, rows)
+            }
+            Some(ChartType::Sunburst | ChartType::Tree | ChartType::Treemap)
+                if !dimensions.is_empty() =>
+            {
+                Self::hierarchy(dimensions.len(), indicators, rows)
+            }
+            Some(ChartType::Count) => {
+                let dimension_count = dimensions.len();
+                Self::flat(
+                    indicators,
+                    rows.into_iter()
+                        .map(|row| row.into_iter().skip(dimension_count).collect())
+                        .collect(),
+                )
+            }
+            _ => Self::flat(dimensions.into_iter().chain(indicators).collect(), rows),
+        }
+    }
+
+    fn flat(columns: Vec<String>, rows: Vec<SubjectValueRow>) -> DataResult {
+        DataResult::new().columns(columns).data(
+            rows.iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|value| SubjectDatasetEngine::cell_of(value))
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+
+    fn label_of(value: &ArcTopicDataValue) -> String {
+        SubjectJoiner::key_of(value).unwrap_or_default()
+    }
+
+    fn pivot(
+        dimensions: Vec<String>,
+        indicators: Vec<String>,
+        rows: Vec<SubjectValueRow>,
+    ) -> DataResult {
+        let dimension_count = dimensions.len();
+        let mut axis_index: HashMap<Option<String>, usize> = HashMap::new();
+        let mut axes: Vec<DataResultSetCell> = vec![];
+        let mut series_index: HashMap<String, usize> = HashMap::new();
+        let mut series: Vec<String> = vec![];
+        // (axis index, series index) -> value
+        let mut values: HashMap<(usize, usize), DataResultSetCell> = HashMap::new();
+        for row in rows {
+            let axis_key = SubjectJoiner::key_of(&row[0]);
+            let axis = *axis_index.entry(axis_key).or_insert_with(|| {
+                axes.push(SubjectDatasetEngine::cell_of(&row[0]));
+                axes.len() - 1
+            });
+            let series_name = row[1..dimension_count]
+                .iter()
+                .map(|value| Self::label_of(value))
+                .collect::<Vec<String>>()
+                .join(" / ");
+            for (indicator_index, indicator) in indicators.iter().enumerate() {
+                let name = if indicators.len() == 1 {
+                    series_name.clone()
+                } else {
+                    format!("{} / {}", series_name, indicator)
+                };
+                let index = *series_index.entry(name.clone()).or_insert_with(|| {
+                    series.push(name);
+                    series.len() - 1
+                });
+                values.insert(
+                    (axis, index),
+                    SubjectDatasetEngine::cell_of(&row[dimension_count + indicator_index]),
+                );
+            }
+        }
+
+        let data = axes
+            .into_iter()
+            .enumerate()
+            .map(|(axis, cell)| {
+                let mut row: DataResultSetRow = vec![cell];
+                for index in 0..series.len() {
+                    row.push(
+                        values
+                            .remove(&(axis, index))
+                            .unwrap_or(DataResultSetCell::None),
+                    );
+                }
+                row
+            })
+            .collect();
+        let mut columns = vec![dimensions[0].clone()];
+        columns.extend(series);
+        DataResult::new().columns(columns).data(data)
+    }
+
+    fn hierarchy(
+        dimension_count: usize,
+        indicators: Vec<String>,
+        rows: Vec<SubjectValueRow>,
+    ) -> DataResult {
+        struct Node {
+            id: String,
+            parent_id: Option<String>,
+            name: String,
+            values: Vec<Option<BigDecimal>>,
+        }
+
+        let mut node_index: HashMap<String, usize> = HashMap::new();
+        let mut nodes: Vec<Node> = vec![];
+        for row in rows {
+            let leaf_values: Vec<Option<BigDecimal>> = row[dimension_count..]
+                .iter()
+                .map(|value| match value.deref() {
+                    ArcTopicDataValue::Num(num) => Some(num.deref().clone()),
+                    _ => None,
+                })
+                .collect();
+            let mut parent_id: Option<String> = None;
+            for value in row[..dimension_count].iter() {
+                let name = Self::label_of(value);
+                let id = match &parent_id {
+                    Some(parent_id) => format!("{}/{}", parent_id, name),
+                    _ => name.clone(),
+                };
+                match node_index.get(&id) {
+                    Some(index) => {
+                        let node = &mut nodes[*index];
+                        for (sum, value) in node.values.iter_mut().zip(leaf_values.iter()) {
+                            *sum = match (sum.take(), value) {
+                                (Some(sum), Some(value)) => Some(sum + value),
+                                _ => None,
+                            };
+                        }
+                    }
+                    _ => {
+                        node_index.insert(id.clone(), nodes.len());
+                        nodes.push(Node {
+                            id: id.clone(),
+                            parent_id: parent_id.clone(),
+                            name,
+                            values: leaf_values.clone(),
+                        });
+                    }
+                }
+                parent_id = Some(id);
+            }
+        }
+
+        let mut columns = vec![
+            String::from("id"),
+            String::from("parentId"),
+            String::from("name"),
+        ];
+        columns.extend(indicators);
+        let data = nodes
+            .into_iter()
+            .map(|node| {
+                let mut row: DataResultSetRow = vec![
+                    DataResultSetCell::Str(node.id),
+                    match node.parent_id {
+                        Some(parent_id) => DataResultSetCell::Str(parent_id),
+                        _ => DataResultSetCell::None,
+                    },
+                    DataResultSetCell::Str(node.name),
+                ];
+                row.extend(node.values.into_iter().map(|value| match value {
+                    Some(value) => DataResultSetCell::Num(value),
+                    _ => DataResultSetCell::None,
+                }));
+                row
+            })
+            .collect();
+        DataResult::new().columns(columns).data(data)
+    }
+}
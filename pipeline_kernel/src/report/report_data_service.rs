@@ -0,0 +1,429 @@
+use crate::{
+    InMemoryData, PipelineKernelErrorCode, ReportChartData, ReportEChartsOption,
+    ReportFunnelFilter, SubjectAggregator, SubjectDataSource, SubjectDatasetPlan,
+    TopicStorageDataSource,
+};
+use elf_base::{ErrorCode, StdR, StringUtils};
+use elf_model::{
+    ChartRecitation, ChartType, DataResult, Report, ReportIndicatorArithmetic,
+    SubjectColumnArithmetic, TenantId,
+};
+use elf_runtime_model_kernel::{
+    SubjectMetaProvider, SubjectService, TopicSchemaProvider, TopicService,
+};
+use serde_json::Value;
+
+/// query data of report over its subject dataset:
+/// - filters of report filter the rows of dataset,
+/// - enabled funnels filter the rows, see [ReportFunnelFilter],
+/// - group by dimensions, and aggregate indicators,
+/// - data is shaped for the chart of report, see [ReportChartData].
+pub struct ReportDataService;
+
+impl ReportDataService {
+    /// subject is found by id of report, rows are read from topic storage.
+    pub fn query(report: Report, tenant_id: &TenantId) -> StdR<DataResult> {
+        let subject_id = match &report.subject_id {
+            Some(subject_id) if subject_id.is_not_blank() => subject_id,
+            _ => {
+                return PipelineKernelErrorCode::ReportSubjectMissed.msg(format!(
+                    "Subject of report[{}] is missed.",
+                    report.report_id.as_deref().unwrap_or("")
+                ));
+            }
+        };
+        let Some(subject) = SubjectService::meta()?.find_by_id(subject_id, tenant_id)? else {
+            return PipelineKernelErrorCode::SubjectNotFound
+                .msg(format!("Subject[{}] not found.", subject_id));
+        };
+        let plan = SubjectDatasetPlan::compile(subject, |topic_id| {
+            TopicService::schema()?.by_id(topic_id, tenant_id)
+        })?;
+        Self::dataset(report, &plan, &TopicStorageDataSource)
+    }
+
+    /// echarts option of report chart with queried data, see [ReportEChartsOption].
+    pub fn echarts_option(report: Report, tenant_id: &TenantId) -> StdR<Value> {
+        let Some(chart) = &report.chart else {
+            return PipelineKernelErrorCode::ReportChartNotSupported.msg(format!(
+                "Chart of report[{}] is missed.",
+                report.report_id.as_deref().unwrap_or("")
+            ));
+        };
+        let chart = ReportEChartsOption::recitation_of(chart)?;
+        // count chart has no dimension
+        let dimension_count = match chart {
+            ChartRecitation::Count(_) => 0,
+            _ => report.dimensions.as_ref().map(Vec::len).unwrap_or(0),
+        };
+        let data = Self::query(report, tenant_id)?;
+        ReportEChartsOption::render(&chart, dimension_count, &data)
+    }
+
+    pub fn dataset<S: SubjectDataSource>(
+        report: Report,
+        plan: &SubjectDatasetPlan,
+        source: &S,
+    ) -> StdR<DataResult> {
+        let indicators = report.indicators.unwrap_or_default();
+        if indicators.is_empty() {
+            return PipelineKernelErrorCode::ReportIndicatorMissed.msg(format!(
+                "Indicator of report[{}] is missed.",
+                report.report_id.as_deref().unwrap_or("")
+            ));
+        }
+
+        let mut rows = plan.execute(source)?;
+        if let Some(filters) = plan.compile_conditions(
+            report
+                .filters
+                .map(|filters| vec![filters.to_condition()])
+                .unwrap_or_default(),
+        )? {
+            let mut filtered = vec![];
+            for row in rows {
+                let mut in_memory_data = InMemoryData::new(None, Some(plan.data_of(&row)));
+                if filters.is_true(&mut in_memory_data)? {
+                    filtered.push(row);
+                }
+            }
+            rows = filtered;
+        }
+        let mut funnels = vec![];
+        for funnel in report.funnels.into_iter().flatten() {
+            if let Some(funnel) = ReportFunnelFilter::compile(funnel, plan)? {
+                funnels.push(funnel);
+            }
+        }
+        rows.retain(|row| funnels.iter().all(|funnel| funnel.matches(row)));
+
+        let chart_type = report
+            .chart
+            .as_ref()
+            .and_then(|chart| chart.r#type.as_ref());
+        // count chart has no dimension
+        let dimensions = match chart_type {
+            Some(ChartType::Count) => vec![],
+            _ => report.dimensions.unwrap_or_default(),
+        };
+        let column_of =
+            |column_id: Option<String>, name: Option<String>| -> StdR<(usize, String)> {
+                let column_id = column_id.unwrap_or_default();
+                let Some(index) = plan.column_index(&column_id) else {
+                    return PipelineKernelErrorCode::ReportColumnNotFound.msg(format!(
+                        "Column[{}] not found in subject[{}].",
+                        column_id,
+                        plan.subject_id()
+                    ));
+                };
+                let name = name
+                    .filter(|name| name.is_not_blank())
+                    .unwrap_or_else(|| plan.columns()[index].name().clone());
+                Ok((index, name))
+            };
+        let mut indexes = vec![];
+        let mut arithmetics = vec![];
+        let mut dimension_names = vec![];
+        for dimension in dimensions {
+            let (index, name) = column_of(dimension.column_id, dimension.name)?;
+            indexes.push(index);
+            arithmetics.push(SubjectColumnArithmetic::None);
+            dimension_names.push(name);
+        }
+        let mut indicator_names = vec![];
+        for indicator in indicators {
+            let (index, name) = column_of(indicator.column_id, indicator.name)?;
+            indexes.push(index);
+            arithmetics.push(Self::arithmetic_of(indicator.arithmetic));
+            indicator_names.push(name);
+        }
+
+        let selected = rows
+            .into_iter()
+            .map(|row| indexes.iter().map(|index| row[*index].clone()).collect())
+            .collect();
+        let arithmetics: Vec<&SubjectColumnArithmetic> = arithmetics.iter().collect();
+        let aggregated = SubjectAggregator::aggregate(selected, &arithmetics)?;
+        Ok(ReportChartData::shape(
+            chart_type,
+            dimension_names,
+            indicator_names,
+            aggregated,
+        ))
+    }
+
+    fn arithmetic_of(arithmetic: Option<ReportIndicatorArithmetic>) -> SubjectColumnArithmetic {
+        match arithmetic {
+            Some(ReportIndicatorArithmetic::Count) => SubjectColumnArithmetic::Count,
+            Some(ReportIndicatorArithmetic::DistinctCount) => {
+                SubjectColumnArithmetic::DistinctCount
+            }
+            Some(ReportIndicatorArithmetic::Sum) => SubjectColumnArithmetic::Sum,
+            Some(ReportIndicatorArithmetic::Avg) => SubjectColumnArithmetic::Avg,
+            Some(ReportIndicatorArithmetic::Max) => SubjectColumnArithmetic::Max,
+            Some(ReportIndicatorArithmetic::Min) => SubjectColumnArithmetic::Min,
+            _ => SubjectColumnArithmetic::None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ReportDataService, SubjectDataSource, SubjectDatasetPlan, SubjectSourceCriteria};
+    use bigdecimal::BigDecimal;
+    use elf_base::StdR;
+    use elf_model::{
+        Chart, ChartType, DataResult, DataResultSetCell, Factor, FactorType, ParameterKind, Report,
+        ReportDimension, ReportFunnel, ReportFunnelType, ReportIndicator,
+        ReportIndicatorArithmetic, Subject, SubjectColumnArithmetic, SubjectDataset,
+        SubjectDatasetColumn, Topic, TopicData, TopicDataValue, TopicFactorParameter, TopicKind,
+        TopicType,
+    };
+    use elf_runtime_model_kernel::TopicSchema;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    struct InMemorySource {
+        rows: Vec<TopicData>,
+    }
+
+    impl SubjectDataSource for InMemorySource {
+        fn rows(
+            &self,
+            _topic: &Arc<TopicSchema>,
+            _criteria: &SubjectSourceCriteria,
+        ) -> StdR<Vec<TopicData>> {
+            Ok(self.rows.clone())
+        }
+    }
+
+    fn factors() -> Vec<(&'static str, &'static str, FactorType)> {
+        vec![
+            ("f1", "region", FactorType::Text),
+            ("f2", "category", FactorType::Text),
+            ("f3", "amount", FactorType::Number),
+            ("f4", "soldOn", FactorType::Date),
+        ]
+    }
+
+    fn plan() -> SubjectDatasetPlan {
+        let topic = Topic::new()
+            .topic_id(String::from("sales"))
+            .name(String::from("sales"))
+            .r#type(TopicType::Raw)
+            .kind(TopicKind::Business)
+            .factors(
+                factors()
+                    .into_iter()
+                    .map(|(factor_id, name, r#type)| {
+                        Factor::new()
+                            .factor_id(factor_id.to_string())
+                            .name(name.to_string())
+                            .r#type(r#type)
+                    })
+                    .collect(),
+            )
+            .tenant_id(String::from("tenant-1"));
+        let topic = Arc::new(TopicSchema::new(topic).unwrap());
+        let columns = factors()
+            .into_iter()
+            .map(|(factor_id, name, _)| {
+                SubjectDatasetColumn::new()
+                    .column_id(name.to_string())
+                    .alias(name.to_string())
+                    .parameter(
+                        TopicFactorParameter {
+                            kind: Some(ParameterKind::Topic),
+                            topic_id: Some(String::from("sales")),
+                            factor_id: Some(factor_id.to_string()),
+                        }
+                        .to_parameter(),
+                    )
+                    .arithmetic(SubjectColumnArithmetic::None)
+            })
+            .collect();
+        let subject = Subject::new()
+            .subject_id(String::from("s1"))
+            .name(String::from("sales"))
+            .dataset(SubjectDataset::new().columns(columns))
+            .tenant_id(String::from("tenant-1"));
+        SubjectDatasetPlan::compile(subject, |_| Ok(topic.clone())).unwrap()
+    }
+
+    fn source() -> InMemorySource {
+        let sale = |region: &str, category: &str, amount: i32, sold_on: &str| {
+            let mut data = HashMap::new();
+            for (name, value) in [
+                ("region", TopicDataValue::Str(region.to_string())),
+                ("category", TopicDataValue::Str(category.to_string())),
+                ("amount", TopicDataValue::Num(BigDecimal::from(amount))),
+                ("soldOn", TopicDataValue::Str(sold_on.to_string())),
+            ] {
+                data.insert(name.to_string(), value);
+            }
+            data
+        };
+        InMemorySource {
+            rows: vec![
+                sale("east", "book", 10, "2026-01-05"),
+                sale("east", "pen", 3, "2026-01-20"),
+                sale("west", "book", 7, "2026-02-03"),
+                sale("east", "book", 5, "2026-02-14"),
+                sale("west", "pen", 2, "2026-03-01"),
+            ],
+        }
+    }
+
+    fn report(chart_type: ChartType, funnels: Vec<ReportFunnel>) -> Report {
+        Report::new()
+            .report_id(String::from("r1"))
+            .subject_id(String::from("s1"))
+            .dimensions(vec![
+                ReportDimension::new().column_id(String::from("region")),
+                ReportDimension::new().column_id(String::from("category")),
+            ])
+            .indicators(vec![ReportIndicator::new()
+                .column_id(String::from("amount"))
+                .name(String::from("total"))
+                .arithmetic(ReportIndicatorArithmetic::Sum)])
+            .funnels(funnels)
+            .chart(Chart::new().r#type(chart_type))
+    }
+
+    fn texts(result: &DataResult) -> Vec<String> {
+        result
+            .data
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| match cell {
+                        DataResultSetCell::Str(str) => str.clone(),
+                        DataResultSetCell::Num(num) => num.to_plain_string(),
+                        DataResultSetCell::None => String::from("-"),
+                        other => format!("{:?}", other),
+                    })
+                    .collect::<Vec<String>>()
+                    .join(",")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_bar() {
+        let result =
+            ReportDataService::dataset(report(ChartType::Bar, vec![]), &plan(), &source()).unwrap();
+        assert_eq!(
+            result.columns.clone().unwrap(),
+            vec!["region", "book", "pen"]
+        );
+        assert_eq!(texts(&result), vec!["east,15,3", "west,7,2"]);
+
+        let result =
+            ReportDataService::dataset(report(ChartType::Pie, vec![]), &plan(), &source()).unwrap();
+        assert_eq!(
+            result.columns.clone().unwrap(),
+            vec!["region", "category", "total"]
+        );
+        assert_eq!(texts(&result).len(), 4);
+
+        let result =
+            ReportDataService::dataset(report(ChartType::Count, vec![]), &plan(), &source())
+                .unwrap();
+        assert_eq!(texts(&result), vec!["27"]);
+    }
+
+    #[test]
+    fn test_sunburst() {
+        let result =
+            ReportDataService::dataset(report(ChartType::Sunburst, vec![]), &plan(), &source())
+                .unwrap();
+        assert_eq!(
+            result.columns.clone().unwrap(),
+            vec!["id", "parentId", "name", "total"]
+        );
+        assert_eq!(
+            texts(&result),
+            vec![
+                "east,-,east,18",
+                "east/book,east,book,15",
+                "east/pen,east,pen,3",
+                "west,-,west,9",
+                "west/book,west,book,7",
+                "west/pen,west,pen,2",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_funnels() {
+        let funnel = |column_id: &str, r#type: ReportFunnelType, range: bool, values: Vec<&str>| {
+            ReportFunnel::new()
+                .column_id(column_id.to_string())
+                .r#type(r#type)
+                .range(range)
+                .enabled(true)
+                .values(
+                    values
+                        .into_iter()
+                        .map(|value| match value {
+                            "" => None,
+                            value => Some(value.to_string()),
+                        })
+                        .collect(),
+                )
+        };
+
+        // amount >= 3, in january or february
+        let result = ReportDataService::dataset(
+            report(
+                ChartType::Bar,
+                vec![
+                    funnel("amount", ReportFunnelType::Numeric, true, vec!["3", ""]),
+                    funnel("soldOn", ReportFunnelType::Month, true, vec!["1", "2"]),
+                ],
+            ),
+            &plan(),
+            &source(),
+        )
+        .unwrap();
+        assert_eq!(texts(&result), vec!["east,15,3", "west,7,-"]);
+
+        // pen only, sold from 2026-01-10, disabled funnel is ignored
+        let result = ReportDataService::dataset(
+            report(
+                ChartType::Pie,
+                vec![
+                    funnel("category", ReportFunnelType::Enum, false, vec!["pen"]),
+                    funnel(
+                        "soldOn",
+                        ReportFunnelType::Date,
+                        true,
+                        vec!["2026-01-10", ""],
+                    ),
+                    funnel("region", ReportFunnelType::Enum, false, vec!["north"]).enabled(false),
+                ],
+            ),
+            &plan(),
+            &source(),
+        )
+        .unwrap();
+        assert_eq!(texts(&result), vec!["east,pen,3", "west,pen,2"]);
+
+        assert!(ReportDataService::dataset(
+            report(
+                ChartType::Bar,
+                vec![funnel(
+                    "amount",
+                    ReportFunnelType::Numeric,
+                    false,
+                    vec!["x"]
+                )],
+            ),
+            &plan(),
+            &source(),
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,1432 @@
+use crate::PipelineKernelErrorCode;
+use bigdecimal::{BigDecimal, ToPrimitive};
+use elf_base::{ErrorCode, StdR};
+use elf_model::{
+    BarChartSettings, Chart, ChartBorder, ChartBorderStyle, ChartColor, ChartFont, ChartRecitation,
+    ChartTruncation, ChartTruncationType, CountChartSettings, DataResult, DataResultSetCell,
+    DataResultSetRow, EChartsAxisSplitLine, EChartsBorderOmitRadius, EChartsGrid,
+    EChartsGridPositionOnly, EChartsHorizontalAlignment, EChartsLegend, EChartsPosition,
+    EChartsTitle, EChartsTitleText, EChartsVerticalAlignment, LineChartSettings, MapChartSettings,
+    PieChartSettings, PieRoseType, PredefinedChartColorSeries, ScatterChartSettings,
+    SunburstChartSettings, TreeChartSettings, TreemapChartSettings,
+};
+use serde_json::{json, Map, Number, Value};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+const REGULAR_COLORS: [&str; 9] = [
+    "#5470c6", "#91cc75", "#fac858", "#ee6666", "#73c0de", "#3ba272", "#fc8452", "#9a60b4",
+    "#ea7ccc",
+];
+const DARK_COLORS: [&str; 9] = [
+    "#2f4554", "#c23531", "#61a0a8", "#d48265", "#749f83", "#ca8622", "#bda29a", "#6e7074",
+    "#546570",
+];
+const LIGHT_COLORS: [&str; 9] = [
+    "#a5b4e8", "#c8e6b9", "#fde3a7", "#f7b2b2", "#b9e0ef", "#9fd5b8", "#fec2a8", "#cdafda",
+    "#f5bde5",
+];
+
+type OptionMap = Map<String, Value>;
+
+/// settings shared by echarts charts, see [elf_model::EChartsSettings]
+trait EChartsCommonSettings {
+    fn title(&self) -> Option<&EChartsTitle>;
+    fn background_color(&self) -> Option<&ChartColor>;
+    fn color_series(&self) -> Option<&PredefinedChartColorSeries>;
+    fn truncation(&self) -> Option<&ChartTruncation>;
+}
+
+macro_rules! impl_echarts_common_settings {
+    ($($settings:ty),*) => {
+        $(
+            impl EChartsCommonSettings for $settings {
+                fn title(&self) -> Option<&EChartsTitle> {
+                    self.title.as_ref()
+                }
+
+                fn background_color(&self) -> Option<&ChartColor> {
+                    self.background_color.as_ref()
+                }
+
+                fn color_series(&self) -> Option<&PredefinedChartColorSeries> {
+                    self.color_series.as_ref()
+                }
+
+                fn truncation(&self) -> Option<&ChartTruncation> {
+                    self.truncation.as_ref()
+                }
+            }
+        )*
+    };
+}
+
+impl_echarts_common_settings!(
+    CountChartSettings,
+    BarChartSettings,
+    LineChartSettings,
+    ScatterChartSettings,
+    PieChartSettings,
+    SunburstChartSettings,
+    TreeChartSettings,
+    TreemapChartSettings,
+    MapChartSettings
+);
+
+/// json of axis settings, x and y axis have same fields with different types.
+macro_rules! axis_of {
+    ($axis:expr) => {{
+        let mut map = OptionMap::new();
+        if let Some(axis) = $axis {
+            Self::put(&mut map, "show", axis.show.map(Value::Bool));
+            Self::put_display(&mut map, "position", axis.position.as_ref());
+            Self::put_display(&mut map, "type", axis.r#type.as_ref());
+            if let Some(name) = &axis.name {
+                Self::put(&mut map, "name", name.text.clone().map(Value::String));
+                Self::put_display(&mut map, "nameLocation", name.location.as_ref());
+                Self::put_number(&mut map, "nameGap", name.gap.as_ref());
+                Self::put_number(&mut map, "nameRotate", name.rotate.as_ref());
+                let mut style = OptionMap::new();
+                Self::put_color(
+                    &mut style,
+                    "backgroundColor",
+                    name.background_color.as_ref(),
+                );
+                Self::put_number(&mut style, "padding", name.padding.as_ref());
+                Self::put_border(&mut style, name.border.as_ref());
+                Self::put_font(&mut style, name.font.as_ref(), "color");
+                Self::put_alignment(
+                    &mut style,
+                    name.horizontal_align.as_ref(),
+                    name.vertical_align.as_ref(),
+                );
+                Self::put_map(&mut map, "nameTextStyle", style);
+            }
+            if let Some(label) = &axis.label {
+                let mut style = OptionMap::new();
+                Self::put(&mut style, "show", label.show.map(Value::Bool));
+                Self::put(&mut style, "inside", label.inside.map(Value::Bool));
+                Self::put_color(
+                    &mut style,
+                    "backgroundColor",
+                    label.background_color.as_ref(),
+                );
+                Self::put_number(&mut style, "margin", label.gap.as_ref());
+                Self::put_number(&mut style, "rotate", label.rotate.as_ref());
+                Self::put_number(&mut style, "padding", label.padding.as_ref());
+                Self::put_border(&mut style, label.border.as_ref());
+                Self::put_font(&mut style, label.font.as_ref(), "color");
+                Self::put_alignment(
+                    &mut style,
+                    label.horizontal_align.as_ref(),
+                    label.vertical_align.as_ref(),
+                );
+                Self::put_map(&mut map, "axisLabel", style);
+            }
+            if axis.auto_min == Some(false) {
+                Self::put_number(&mut map, "min", axis.min.as_ref());
+            }
+            if axis.auto_max == Some(false) {
+                Self::put_number(&mut map, "max", axis.max.as_ref());
+            }
+            Self::put_split_line(&mut map, "splitLine", axis.split_line.as_ref());
+            Self::put_split_line(&mut map, "minorSplitLine", axis.minor_split_line.as_ref());
+        }
+        map
+    }};
+}
+
+/// json of series label settings, bar and pie labels have same fields with different types.
+macro_rules! label_of {
+    ($label:expr) => {{
+        let mut map = OptionMap::new();
+        if let Some(label) = $label {
+            Self::put(&mut map, "show", label.show.map(Value::Bool));
+            Self::put_display(&mut map, "position", label.position.as_ref());
+            Self::put_color(&mut map, "backgroundColor", label.background_color.as_ref());
+            Self::put_number(&mut map, "rotate", label.rotate.as_ref());
+            Self::put_number(&mut map, "distance", label.gap.as_ref());
+            Self::put_number(&mut map, "padding", label.padding.as_ref());
+            Self::put_border(&mut map, label.border.as_ref());
+            Self::put_font(&mut map, label.font.as_ref(), "color");
+            Self::put_alignment(
+                &mut map,
+                label.horizontal_align.as_ref(),
+                label.vertical_align.as_ref(),
+            );
+        }
+        map
+    }};
+}
+
+/// render echarts option from chart and its data, data is shaped by [crate::ReportChartData].
+/// option has title, background color, color palette, tooltip, and legend, axes, series by chart type:
+/// - bar and line: first column is the category axis, each other column is a series,
+///   axes are swapped when series transform axis,
+/// - scatter: with 2 or more indicators, each row is a point of first 2 indicators on value axes,
+///   otherwise as same as line,
+/// - pie, doughnut and nightingale: each row is a slice, named by dimension values joined with " / ",
+///   valued by first indicator,
+/// - sunburst, tree and treemap: nodes of hierarchy rows, valued by first indicator,
+///   tree has a nameless root when there are more than one top nodes,
+/// - map: each row is a region, named by dimension values, valued by first indicator,
+/// - count: first indicator as a text graphic.
+///
+/// label formatting (grouping, percentage and fraction digits) needs script function, which is ignored,
+/// border of chart is drawn by container, which is not a part of option.
+/// customized chart is not supported, its script can only run in browser.
+pub struct ReportEChartsOption;
+
+impl ReportEChartsOption {
+    /// settings of chart are read as settings of its type
+    pub fn recitation_of(chart: &Chart) -> StdR<ChartRecitation> {
+        let Some(chart_type) = &chart.r#type else {
+            return PipelineKernelErrorCode::ReportChartNotSupported
+                .msg("Type of chart is missed.");
+        };
+        match serde_json::to_value(chart).and_then(serde_json::from_value::<ChartRecitation>) {
+            Ok(recitation) => Ok(recitation),
+            Err(e) => PipelineKernelErrorCode::ReportChartNotSupported.msg(format!(
+                "Settings of {} chart are invalid, caused by {}.",
+                chart_type, e
+            )),
+        }
+    }
+
+    /// dimension count is the count of leading dimension columns of flat rows,
+    /// used by scatter, pie, doughnut, nightingale and map.
+    pub fn render(
+        chart: &ChartRecitation,
+        dimension_count: usize,
+        data: &DataResult,
+    ) -> StdR<Value> {
+        let columns = data.columns.clone().unwrap_or_default();
+        let rows: Vec<&DataResultSetRow> = data.data.iter().flatten().collect();
+        let mut option = OptionMap::new();
+        match chart {
+            ChartRecitation::Count(chart) => {
+                let settings = chart.settings.as_ref();
+                Self::put_common(&mut option, settings, None);
+                Self::count(&mut option, settings, &rows);
+            }
+            ChartRecitation::Bar(chart) => {
+                let settings = chart.settings.as_ref();
+                Self::put_common(&mut option, settings, settings.and_then(|s| s.decal));
+                let rows = Self::truncate(rows, settings);
+                Self::bar(&mut option, settings, &columns, &rows);
+            }
+            ChartRecitation::Line(chart) => {
+                let settings = chart.settings.as_ref();
+                Self::put_common(&mut option, settings, None);
+                let rows = Self::truncate(rows, settings);
+                Self::line(&mut option, settings, &columns, &rows);
+            }
+            ChartRecitation::Scatter(chart) => {
+                let settings = chart.settings.as_ref();
+                Self::put_common(&mut option, settings, None);
+                let rows = Self::truncate(rows, settings);
+                Self::scatter(&mut option, settings, dimension_count, &columns, &rows);
+            }
+            ChartRecitation::Pie(chart) => {
+                let settings = chart.settings.as_ref();
+                Self::put_common(&mut option, settings, settings.and_then(|s| s.decal));
+                let rows = Self::truncate(rows, settings);
+                Self::pie(
+                    &mut option,
+                    settings,
+                    None,
+                    dimension_count,
+                    &columns,
+                    &rows,
+                );
+            }
+            ChartRecitation::Doughnut(chart) => {
+                let settings = chart.settings.as_ref();
+                Self::put_common(&mut option, settings, settings.and_then(|s| s.decal));
+                let rows = Self::truncate(rows, settings);
+                let radius = json!(["40%", "70%"]);
+                Self::pie(
+                    &mut option,
+                    settings,
+                    Some(radius),
+                    dimension_count,
+                    &columns,
+                    &rows,
+                );
+            }
+            ChartRecitation::Nightingale(chart) => {
+                let settings = chart.settings.as_ref();
+                Self::put_common(&mut option, settings, settings.and_then(|s| s.decal));
+                let rows = Self::truncate(rows, settings);
+                Self::pie(
+                    &mut option,
+                    settings,
+                    None,
+                    dimension_count,
+                    &columns,
+                    &rows,
+                );
+                // nightingale is always a rose
+                if let Some(Value::Object(series)) = option
+                    .get_mut("series")
+                    .and_then(|series| series.get_mut(0))
+                    && !matches!(series.get("roseType"), Some(Value::String(_)))
+                {
+                    series.insert(String::from("roseType"), json!("radius"));
+                }
+            }
+            ChartRecitation::Sunburst(chart) => {
+                let settings = chart.settings.as_ref();
+                Self::put_common(&mut option, settings, settings.and_then(|s| s.decal));
+                Self::sunburst(&mut option, settings, &rows);
+            }
+            ChartRecitation::Tree(chart) => {
+                let settings = chart.settings.as_ref();
+                Self::put_common(&mut option, settings, None);
+                Self::tree(&mut option, settings, &rows);
+            }
+            ChartRecitation::Treemap(chart) => {
+                let settings = chart.settings.as_ref();
+                Self::put_common(&mut option, settings, None);
+                Self::treemap(&mut option, settings, &rows);
+            }
+            ChartRecitation::Map(chart) => {
+                let settings = chart.settings.as_ref();
+                Self::put_common(&mut option, settings, None);
+                let rows = Self::truncate(rows, settings);
+                Self::map(&mut option, settings, dimension_count, &rows)?;
+            }
+            ChartRecitation::Customized(_) => {
+                return PipelineKernelErrorCode::ReportChartNotSupported.msg(
+                    "Customized chart is rendered by its script, which is not supported on server side.",
+                );
+            }
+        }
+        Ok(Value::Object(option))
+    }
+
+    // values
+
+    fn number(value: &BigDecimal) -> Value {
+        match Number::from_str(&value.normalized().to_string()) {
+            Ok(number) => Value::Number(number),
+            _ => value
+                .to_f64()
+                .and_then(Number::from_f64)
+                .map(Value::Number)
+                .unwrap_or(Value::Null),
+        }
+    }
+
+    fn percentage(value: &BigDecimal) -> Value {
+        Value::String(format!("{}%", value.normalized()))
+    }
+
+    fn cell_value(cell: &DataResultSetCell) -> Value {
+        match cell {
+            DataResultSetCell::Str(str) => Value::String(str.clone()),
+            DataResultSetCell::Num(num) => Self::number(num),
+            DataResultSetCell::Bool(bool) => Value::Bool(*bool),
+            DataResultSetCell::DateTime(datetime) => {
+                Value::String(datetime.format("%Y-%m-%d %H:%M:%S").to_string())
+            }
+            DataResultSetCell::Date(date) => Value::String(date.format("%Y-%m-%d").to_string()),
+            DataResultSetCell::Time(time) => Value::String(time.format("%H:%M:%S").to_string()),
+            DataResultSetCell::None => Value::Null,
+        }
+    }
+
+    fn cell_label(cell: &DataResultSetCell) -> String {
+        match Self::cell_value(cell) {
+            Value::String(str) => str,
+            Value::Null => String::new(),
+            value => value.to_string(),
+        }
+    }
+
+    /// value of cell at given index, null when absent
+    fn row_value(row: &DataResultSetRow, index: usize) -> Value {
+        row.get(index).map(Self::cell_value).unwrap_or(Value::Null)
+    }
+
+    /// dimension values joined with " / "
+    fn row_name(row: &DataResultSetRow, dimension_count: usize) -> String {
+        row.iter()
+            .take(dimension_count)
+            .map(Self::cell_label)
+            .collect::<Vec<String>>()
+            .join(" / ")
+    }
+
+    /// top takes the first rows, bottom takes the last rows
+    fn truncate<'a, S: EChartsCommonSettings>(
+        rows: Vec<&'a DataResultSetRow>,
+        settings: Option<&S>,
+    ) -> Vec<&'a DataResultSetRow> {
+        let Some(truncation) = settings.and_then(|s| s.truncation()) else {
+            return rows;
+        };
+        let count = match truncation.count {
+            Some(count) if count > 0 => count as usize,
+            _ => return rows,
+        };
+        match truncation.r#type {
+            Some(ChartTruncationType::Top) => rows.into_iter().take(count).collect(),
+            Some(ChartTruncationType::Bottom) => {
+                let skip = rows.len().saturating_sub(count);
+                rows.into_iter().skip(skip).collect()
+            }
+            _ => rows,
+        }
+    }
+
+    /// integer part is grouped by every 3 digits
+    fn grouped(value: &BigDecimal) -> String {
+        let value = value.normalized().to_plain_string();
+        let (sign, value) = match value.strip_prefix('-') {
+            Some(value) => ("-", value),
+            _ => ("", value.as_str()),
+        };
+        let (integer, fraction) = match value.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            _ => (value, None),
+        };
+        let mut grouped = String::new();
+        for (index, digit) in integer.chars().enumerate() {
+            if index > 0 && (integer.len() - index) % 3 == 0 {
+                grouped.push(',');
+            }
+            grouped.push(digit);
+        }
+        match fraction {
+            Some(fraction) => format!("{}{}.{}", sign, grouped, fraction),
+            _ => format!("{}{}", sign, grouped),
+        }
+    }
+
+    // settings
+
+    fn put(map: &mut OptionMap, key: &str, value: Option<Value>) {
+        if let Some(value) = value {
+            map.insert(key.to_string(), value);
+        }
+    }
+
+    fn put_number(map: &mut OptionMap, key: &str, value: Option<&BigDecimal>) {
+        Self::put(map, key, value.map(Self::number));
+    }
+
+    fn put_color(map: &mut OptionMap, key: &str, value: Option<&ChartColor>) {
+        Self::put(map, key, value.cloned().map(Value::String));
+    }
+
+    fn put_display<D: ToString>(map: &mut OptionMap, key: &str, value: Option<&D>) {
+        Self::put(
+            map,
+            key,
+            value.map(|value| Value::String(value.to_string())),
+        );
+    }
+
+    /// empty map is ignored
+    fn put_map(map: &mut OptionMap, key: &str, value: OptionMap) {
+        if !value.is_empty() {
+            map.insert(key.to_string(), Value::Object(value));
+        }
+    }
+
+    /// color key is "color" for text style, and "fill" for graphic
+    fn put_font(map: &mut OptionMap, font: Option<&ChartFont>, color_key: &str) {
+        let Some(font) = font else {
+            return;
+        };
+        Self::put(map, "fontFamily", font.family.clone().map(Value::String));
+        Self::put_number(map, "fontSize", font.size.as_ref());
+        Self::put_color(map, color_key, font.color.as_ref());
+        Self::put_display(map, "fontStyle", font.style.as_ref());
+        Self::put(
+            map,
+            "fontWeight",
+            font.weight
+                .as_ref()
+                .map(|weight| match weight.to_string().parse::<u64>() {
+                    Ok(weight) => Value::from(weight),
+                    _ => Value::String(weight.to_string()),
+                }),
+        );
+    }
+
+    /// none style is zero width
+    fn put_border_style(map: &mut OptionMap, style: Option<&ChartBorderStyle>) {
+        match style {
+            Some(ChartBorderStyle::None) => {
+                map.insert(String::from("borderWidth"), Value::from(0));
+            }
+            Some(style) => {
+                map.insert(String::from("borderType"), Value::String(style.to_string()));
+            }
+            _ => {}
+        }
+    }
+
+    fn put_border(map: &mut OptionMap, border: Option<&ChartBorder>) {
+        let Some(border) = border else {
+            return;
+        };
+        Self::put_color(map, "borderColor", border.color.as_ref());
+        Self::put_number(map, "borderWidth", border.width.as_ref());
+        Self::put_number(map, "borderRadius", border.radius.as_ref());
+        Self::put_border_style(map, border.style.as_ref());
+    }
+
+    fn put_border_omit_radius(map: &mut OptionMap, border: Option<&EChartsBorderOmitRadius>) {
+        let Some(border) = border else {
+            return;
+        };
+        Self::put_color(map, "borderColor", border.color.as_ref());
+        Self::put_number(map, "borderWidth", border.width.as_ref());
+        Self::put_border_style(map, border.style.as_ref());
+    }
+
+    fn put_position(map: &mut OptionMap, position: Option<&EChartsPosition>) {
+        let Some(position) = position else {
+            return;
+        };
+        Self::put_number(map, "top", position.top.as_ref());
+        Self::put_number(map, "right", position.right.as_ref());
+        Self::put_number(map, "left", position.left.as_ref());
+        Self::put_number(map, "bottom", position.bottom.as_ref());
+    }
+
+    /// auto alignment is ignored
+    fn put_alignment(
+        map: &mut OptionMap,
+        horizontal: Option<&EChartsHorizontalAlignment>,
+        vertical: Option<&EChartsVerticalAlignment>,
+    ) {
+        if !matches!(horizontal, Some(EChartsHorizontalAlignment::Auto)) {
+            Self::put_display(map, "align", horizontal);
+        }
+        if !matches!(vertical, Some(EChartsVerticalAlignment::Auto)) {
+            Self::put_display(map, "verticalAlign", vertical);
+        }
+    }
+
+    fn put_split_line(map: &mut OptionMap, key: &str, split_line: Option<&EChartsAxisSplitLine>) {
+        let Some(split_line) = split_line else {
+            return;
+        };
+        let mut line = OptionMap::new();
+        Self::put(&mut line, "show", split_line.show.map(Value::Bool));
+        let mut style = OptionMap::new();
+        Self::put_color(&mut style, "color", split_line.color.as_ref());
+        Self::put_number(&mut style, "width", split_line.width.as_ref());
+        Self::put_display(&mut style, "type", split_line.style.as_ref());
+        Self::put_map(&mut line, "lineStyle", style);
+        Self::put_map(map, key, line);
+    }
+
+    fn title_text_style(text: &EChartsTitleText) -> OptionMap {
+        let mut style = OptionMap::new();
+        Self::put_font(&mut style, text.font.as_ref(), "color");
+        style
+    }
+
+    fn put_title(option: &mut OptionMap, title: Option<&EChartsTitle>) {
+        let Some(title) = title else {
+            return;
+        };
+        let mut map = OptionMap::new();
+        if let Some(text) = &title.text {
+            Self::put(&mut map, "text", text.text.clone().map(Value::String));
+            Self::put_map(&mut map, "textStyle", Self::title_text_style(text));
+        }
+        if let Some(subtext) = &title.subtext {
+            Self::put(&mut map, "subtext", subtext.text.clone().map(Value::String));
+            Self::put_map(&mut map, "subtextStyle", Self::title_text_style(subtext));
+        }
+        Self::put_color(&mut map, "backgroundColor", title.background_color.as_ref());
+        Self::put_number(&mut map, "padding", title.padding.as_ref());
+        Self::put_number(&mut map, "itemGap", title.item_gap.as_ref());
+        Self::put_border(&mut map, title.border.as_ref());
+        Self::put_position(&mut map, title.position.as_ref());
+        let mut alignment = OptionMap::new();
+        Self::put_alignment(
+            &mut alignment,
+            title.horizontal_align.as_ref(),
+            title.vertical_align.as_ref(),
+        );
+        Self::put(&mut map, "textAlign", alignment.remove("align"));
+        Self::put(
+            &mut map,
+            "textVerticalAlign",
+            alignment.remove("verticalAlign"),
+        );
+        Self::put_map(option, "title", map);
+    }
+
+    /// title, background color, color palette, and decal when it is on
+    fn put_common<S: EChartsCommonSettings>(
+        option: &mut OptionMap,
+        settings: Option<&S>,
+        decal: Option<bool>,
+    ) {
+        Self::put_title(option, settings.and_then(|s| s.title()));
+        Self::put_color(
+            option,
+            "backgroundColor",
+            settings.and_then(|s| s.background_color()),
+        );
+        let colors = match settings.and_then(|s| s.color_series()) {
+            Some(PredefinedChartColorSeries::Dark) => DARK_COLORS,
+            Some(PredefinedChartColorSeries::Light) => LIGHT_COLORS,
+            _ => REGULAR_COLORS,
+        };
+        option.insert(String::from("color"), json!(colors));
+        if decal == Some(true) {
+            option.insert(
+                String::from("aria"),
+                json!({"enabled": true, "decal": {"show": true}}),
+            );
+        }
+    }
+
+    fn put_tooltip(option: &mut OptionMap, trigger: &str) {
+        option.insert(String::from("tooltip"), json!({"trigger": trigger}));
+    }
+
+    fn put_legend(option: &mut OptionMap, legend: Option<&EChartsLegend>, names: Vec<String>) {
+        let mut map = OptionMap::new();
+        if let Some(legend) = legend {
+            Self::put(&mut map, "show", legend.show.map(Value::Bool));
+            Self::put_display(&mut map, "orient", legend.orient.as_ref());
+            Self::put_color(
+                &mut map,
+                "backgroundColor",
+                legend.background_color.as_ref(),
+            );
+            Self::put_number(&mut map, "padding", legend.padding.as_ref());
+            Self::put_border(&mut map, legend.border.as_ref());
+            Self::put_position(&mut map, legend.position.as_ref());
+            let mut style = OptionMap::new();
+            Self::put_font(&mut style, legend.font.as_ref(), "color");
+            Self::put_map(&mut map, "textStyle", style);
+        }
+        map.insert(String::from("data"), json!(names));
+        option.insert(String::from("legend"), Value::Object(map));
+    }
+
+    fn put_grid(option: &mut OptionMap, grid: Option<&EChartsGrid>) {
+        let Some(grid) = grid else {
+            return;
+        };
+        let mut map = OptionMap::new();
+        Self::put(&mut map, "show", grid.show.map(Value::Bool));
+        Self::put(
+            &mut map,
+            "containLabel",
+            grid.contain_label.map(Value::Bool),
+        );
+        Self::put_color(&mut map, "backgroundColor", grid.background_color.as_ref());
+        Self::put_border_omit_radius(&mut map, grid.border.as_ref());
+        Self::put_position(&mut map, grid.position.as_ref());
+        Self::put_map(option, "grid", map);
+    }
+
+    /// charts without grid put position on series
+    fn put_series_position(series: &mut OptionMap, grid: Option<&EChartsGridPositionOnly>) {
+        Self::put_position(series, grid.and_then(|grid| grid.position.as_ref()));
+    }
+
+    /// center and radius are percentages
+    fn put_circle(
+        series: &mut OptionMap,
+        center: (Option<&BigDecimal>, Option<&BigDecimal>),
+        radius: (Option<&BigDecimal>, Option<&BigDecimal>),
+    ) {
+        if center.0.is_some() || center.1.is_some() {
+            series.insert(
+                String::from("center"),
+                json!([
+                    center.0.map(Self::percentage).unwrap_or(json!("50%")),
+                    center.1.map(Self::percentage).unwrap_or(json!("50%"))
+                ]),
+            );
+        }
+        if radius.0.is_some() || radius.1.is_some() {
+            series.insert(
+                String::from("radius"),
+                json!([
+                    radius.0.map(Self::percentage).unwrap_or(json!("0%")),
+                    radius.1.map(Self::percentage).unwrap_or(json!("75%"))
+                ]),
+            );
+        }
+    }
+
+    // axis charts
+
+    /// category axis and value axis, swapped when transform axis.
+    /// type of axis is from settings, or category and value by default.
+    fn put_axes(
+        option: &mut OptionMap,
+        categories: Option<Vec<Value>>,
+        transform_axis: bool,
+        xaxis: OptionMap,
+        yaxis: OptionMap,
+    ) {
+        let (mut category_axis, mut value_axis, category_key, value_key) = if transform_axis {
+            (yaxis, xaxis, "yAxis", "xAxis")
+        } else {
+            (xaxis, yaxis, "xAxis", "yAxis")
+        };
+        match categories {
+            Some(categories) => {
+                category_axis
+                    .entry("type")
+                    .or_insert(Value::String(String::from("category")));
+                category_axis.insert(String::from("data"), Value::Array(categories));
+            }
+            _ => {
+                category_axis
+                    .entry("type")
+                    .or_insert(Value::String(String::from("value")));
+            }
+        }
+        value_axis
+            .entry("type")
+            .or_insert(Value::String(String::from("value")));
+        option.insert(category_key.to_string(), Value::Object(category_axis));
+        option.insert(value_key.to_string(), Value::Object(value_axis));
+    }
+
+    fn categories(rows: &[&DataResultSetRow]) -> Vec<Value> {
+        rows.iter().map(|row| Self::row_value(row, 0)).collect()
+    }
+
+    /// each column except the first is a series
+    fn column_series(
+        series_type: &str,
+        columns: &[String],
+        rows: &[&DataResultSetRow],
+        attributes: &OptionMap,
+    ) -> Value {
+        Value::Array(
+            columns
+                .iter()
+                .enumerate()
+                .skip(1)
+                .map(|(index, name)| {
+                    let mut series = OptionMap::new();
+                    series.insert(String::from("name"), Value::String(name.clone()));
+                    series.insert(String::from("type"), Value::String(series_type.to_string()));
+                    series.extend(attributes.clone());
+                    series.insert(
+                        String::from("data"),
+                        Value::Array(rows.iter().map(|row| Self::row_value(row, index)).collect()),
+                    );
+                    Value::Object(series)
+                })
+                .collect(),
+        )
+    }
+
+    fn bar(
+        option: &mut OptionMap,
+        settings: Option<&BarChartSettings>,
+        columns: &[String],
+        rows: &[&DataResultSetRow],
+    ) {
+        let transform_axis = settings
+            .and_then(|s| s.series.as_ref())
+            .and_then(|s| s.transform_axis)
+            == Some(true);
+        let mut attributes = OptionMap::new();
+        Self::put_map(
+            &mut attributes,
+            "label",
+            label_of!(settings.and_then(|s| s.label.as_ref())),
+        );
+
+        Self::put_tooltip(option, "axis");
+        Self::put_legend(
+            option,
+            settings.and_then(|s| s.legend.as_ref()),
+            columns.iter().skip(1).cloned().collect(),
+        );
+        Self::put_grid(option, settings.and_then(|s| s.grid.as_ref()));
+        Self::put_axes(
+            option,
+            Some(Self::categories(rows)),
+            transform_axis,
+            axis_of!(settings.and_then(|s| s.xaxis.as_ref())),
+            axis_of!(settings.and_then(|s| s.yaxis.as_ref())),
+        );
+        option.insert(
+            String::from("series"),
+            Self::column_series("bar", columns, rows, &attributes),
+        );
+    }
+
+    fn line(
+        option: &mut OptionMap,
+        settings: Option<&LineChartSettings>,
+        columns: &[String],
+        rows: &[&DataResultSetRow],
+    ) {
+        let series_settings = settings.and_then(|s| s.series.as_ref());
+        let transform_axis = series_settings.and_then(|s| s.transform_axis) == Some(true);
+        let mut attributes = OptionMap::new();
+        Self::put(
+            &mut attributes,
+            "smooth",
+            series_settings.and_then(|s| s.smooth).map(Value::Bool),
+        );
+        Self::put_map(
+            &mut attributes,
+            "label",
+            label_of!(settings.and_then(|s| s.label.as_ref())),
+        );
+
+        Self::put_tooltip(option, "axis");
+        Self::put_legend(
+            option,
+            settings.and_then(|s| s.legend.as_ref()),
+            columns.iter().skip(1).cloned().collect(),
+        );
+        Self::put_grid(option, settings.and_then(|s| s.grid.as_ref()));
+        Self::put_axes(
+            option,
+            Some(Self::categories(rows)),
+            transform_axis,
+            axis_of!(settings.and_then(|s| s.xaxis.as_ref())),
+            axis_of!(settings.and_then(|s| s.yaxis.as_ref())),
+        );
+        option.insert(
+            String::from("series"),
+            Self::column_series("line", columns, rows, &attributes),
+        );
+    }
+
+    fn scatter(
+        option: &mut OptionMap,
+        settings: Option<&ScatterChartSettings>,
+        dimension_count: usize,
+        columns: &[String],
+        rows: &[&DataResultSetRow],
+    ) {
+        let xaxis = axis_of!(settings.and_then(|s| s.xaxis.as_ref()));
+        let yaxis = axis_of!(settings.and_then(|s| s.yaxis.as_ref()));
+        Self::put_tooltip(option, "item");
+        Self::put_grid(option, settings.and_then(|s| s.grid.as_ref()));
+        let legend = settings.and_then(|s| s.legend.as_ref());
+
+        if columns.len() < dimension_count + 2 {
+            Self::put_legend(option, legend, columns.iter().skip(1).cloned().collect());
+            Self::put_axes(option, Some(Self::categories(rows)), false, xaxis, yaxis);
+            option.insert(
+                String::from("series"),
+                Self::column_series("scatter", columns, rows, &OptionMap::new()),
+            );
+            return;
+        }
+
+        let name = format!(
+            "{} / {}",
+            columns[dimension_count],
+            columns[dimension_count + 1]
+        );
+        Self::put_legend(option, legend, vec![name.clone()]);
+        Self::put_axes(option, None, false, xaxis, yaxis);
+        let data: Vec<Value> = rows
+            .iter()
+            .map(|row| {
+                json!({
+                    "name": Self::row_name(row, dimension_count),
+                    "value": [
+                        Self::row_value(row, dimension_count),
+                        Self::row_value(row, dimension_count + 1)
+                    ]
+                })
+            })
+            .collect();
+        option.insert(
+            String::from("series"),
+            json!([{"name": name, "type": "scatter", "data": data}]),
+        );
+    }
+
+    // pie charts
+
+    /// pie, doughnut and nightingale, default radius is used when radius is not set
+    fn pie(
+        option: &mut OptionMap,
+        settings: Option<&PieChartSettings>,
+        default_radius: Option<Value>,
+        dimension_count: usize,
+        columns: &[String],
+        rows: &[&DataResultSetRow],
+    ) {
+        let data: Vec<Value> = rows
+            .iter()
+            .map(|row| {
+                json!({
+                    "name": Self::row_name(row, dimension_count),
+                    "value": Self::row_value(row, dimension_count)
+                })
+            })
+            .collect();
+        let mut series = OptionMap::new();
+        Self::put(
+            &mut series,
+            "name",
+            columns.get(dimension_count).cloned().map(Value::String),
+        );
+        series.insert(String::from("type"), json!("pie"));
+        Self::put_series_position(&mut series, settings.and_then(|s| s.grid.as_ref()));
+        let series_settings = settings.and_then(|s| s.series.as_ref());
+        if let Some(series_settings) = series_settings {
+            Self::put_circle(
+                &mut series,
+                (
+                    series_settings.center_x.as_ref(),
+                    series_settings.center_y.as_ref(),
+                ),
+                (
+                    series_settings.inside_radius.as_ref(),
+                    series_settings.outside_radius.as_ref(),
+                ),
+            );
+            match &series_settings.rose_type {
+                Some(PieRoseType::None) | None => {}
+                Some(rose_type) => Self::put_display(&mut series, "roseType", Some(rose_type)),
+            }
+            let mut item_style = OptionMap::new();
+            Self::put_border(&mut item_style, series_settings.border.as_ref());
+            Self::put_map(&mut series, "itemStyle", item_style);
+        }
+        if let Some(default_radius) = default_radius {
+            series.entry("radius").or_insert(default_radius);
+        }
+        let mut label = label_of!(settings.and_then(|s| s.label.as_ref()));
+        if let Some(label_settings) = settings.and_then(|s| s.label.as_ref()) {
+            Self::put(
+                &mut label,
+                "alignTo",
+                label_settings
+                    .align_to
+                    .as_ref()
+                    .map(|align_to| Value::String(align_to.to_string())),
+            );
+        }
+        if series_settings.and_then(|s| s.show_percentage) == Some(true) {
+            label.insert(String::from("formatter"), json!("{b}: {d}%"));
+        }
+        Self::put_map(&mut series, "label", label);
+        series.insert(String::from("data"), Value::Array(data));
+
+        Self::put_tooltip(option, "item");
+        Self::put_legend(
+            option,
+            settings.and_then(|s| s.legend.as_ref()),
+            rows.iter()
+                .map(|row| Self::row_name(row, dimension_count))
+                .collect(),
+        );
+        option.insert(String::from("series"), json!([series]));
+    }
+
+    // hierarchy charts
+
+    /// nodes of given parent, children are nested
+    fn nodes_of(
+        children: &HashMap<Option<String>, Vec<&DataResultSetRow>>,
+        parent_id: &Option<String>,
+    ) -> Vec<Value> {
+        children
+            .get(parent_id)
+            .map(|rows| {
+                rows.iter()
+                    .map(|row| {
+                        let mut node = OptionMap::new();
+                        node.insert(
+                            String::from("name"),
+                            Value::String(row.get(2).map(Self::cell_label).unwrap_or_default()),
+                        );
+                        node.insert(String::from("value"), Self::row_value(row, 3));
+                        let id = row.first().map(Self::cell_label);
+                        let nodes = Self::nodes_of(children, &id);
+                        if !nodes.is_empty() {
+                            node.insert(String::from("children"), Value::Array(nodes));
+                        }
+                        Value::Object(node)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// top nodes of hierarchy rows, which are "id", "parentId", "name" and indicators
+    fn hierarchy(rows: &[&DataResultSetRow]) -> Vec<Value> {
+        let mut children: HashMap<Option<String>, Vec<&DataResultSetRow>> = HashMap::new();
+        for row in rows {
+            let parent_id = match row.get(1) {
+                Some(DataResultSetCell::None) | None => None,
+                Some(cell) => Some(Self::cell_label(cell)),
+            };
+            children.entry(parent_id).or_default().push(row);
+        }
+        Self::nodes_of(&children, &None)
+    }
+
+    fn sunburst(
+        option: &mut OptionMap,
+        settings: Option<&SunburstChartSettings>,
+        rows: &[&DataResultSetRow],
+    ) {
+        let mut series = OptionMap::new();
+        series.insert(String::from("type"), json!("sunburst"));
+        Self::put_series_position(&mut series, settings.and_then(|s| s.grid.as_ref()));
+        if let Some(series_settings) = settings.and_then(|s| s.series.as_ref()) {
+            Self::put_circle(
+                &mut series,
+                (
+                    series_settings.center_x.as_ref(),
+                    series_settings.center_y.as_ref(),
+                ),
+                (
+                    series_settings.inside_radius.as_ref(),
+                    series_settings.outside_radius.as_ref(),
+                ),
+            );
+            let mut item_style = OptionMap::new();
+            Self::put_border(&mut item_style, series_settings.border.as_ref());
+            Self::put_map(&mut series, "itemStyle", item_style);
+        }
+        Self::put_map(
+            &mut series,
+            "label",
+            label_of!(settings.and_then(|s| s.label.as_ref())),
+        );
+        series.insert(String::from("data"), Value::Array(Self::hierarchy(rows)));
+
+        Self::put_tooltip(option, "item");
+        option.insert(String::from("series"), json!([series]));
+    }
+
+    fn tree(
+        option: &mut OptionMap,
+        settings: Option<&TreeChartSettings>,
+        rows: &[&DataResultSetRow],
+    ) {
+        let mut series = OptionMap::new();
+        series.insert(String::from("type"), json!("tree"));
+        Self::put_series_position(&mut series, settings.and_then(|s| s.grid.as_ref()));
+        if let Some(series_settings) = settings.and_then(|s| s.series.as_ref()) {
+            Self::put_display(&mut series, "layout", series_settings.layout.as_ref());
+            Self::put_display(&mut series, "orient", series_settings.orient.as_ref());
+            Self::put(&mut series, "roam", series_settings.roam.map(Value::Bool));
+        }
+        let mut nodes = Self::hierarchy(rows);
+        let root = if nodes.len() == 1 {
+            nodes.remove(0)
+        } else {
+            json!({"name": "", "children": nodes})
+        };
+        series.insert(String::from("data"), json!([root]));
+
+        Self::put_tooltip(option, "item");
+        option.insert(String::from("series"), json!([series]));
+    }
+
+    fn treemap(
+        option: &mut OptionMap,
+        settings: Option<&TreemapChartSettings>,
+        rows: &[&DataResultSetRow],
+    ) {
+        let mut series = OptionMap::new();
+        series.insert(String::from("type"), json!("treemap"));
+        Self::put_series_position(&mut series, settings.and_then(|s| s.grid.as_ref()));
+        Self::put(
+            &mut series,
+            "roam",
+            settings
+                .and_then(|s| s.series.as_ref())
+                .and_then(|s| s.roam)
+                .map(Value::Bool),
+        );
+        series.insert(String::from("data"), Value::Array(Self::hierarchy(rows)));
+
+        Self::put_tooltip(option, "item");
+        option.insert(String::from("series"), json!([series]));
+    }
+
+    // map chart
+
+    /// visual map ranges from min to max of first indicator
+    fn map(
+        option: &mut OptionMap,
+        settings: Option<&MapChartSettings>,
+        dimension_count: usize,
+        rows: &[&DataResultSetRow],
+    ) -> StdR<()> {
+        let Some(region) = settings
+            .and_then(|s| s.series.as_ref())
+            .and_then(|s| s.region.as_ref())
+        else {
+            return PipelineKernelErrorCode::ReportChartNotSupported
+                .msg("Region of map chart is missed.");
+        };
+        let mut series = OptionMap::new();
+        series.insert(String::from("type"), json!("map"));
+        series.insert(String::from("map"), Value::String(region.to_string()));
+        Self::put_series_position(&mut series, settings.and_then(|s| s.grid.as_ref()));
+        series.insert(
+            String::from("data"),
+            Value::Array(
+                rows.iter()
+                    .map(|row| {
+                        json!({
+                            "name": Self::row_name(row, dimension_count),
+                            "value": Self::row_value(row, dimension_count)
+                        })
+                    })
+                    .collect(),
+            ),
+        );
+
+        let values: Vec<&BigDecimal> = rows
+            .iter()
+            .filter_map(|row| match row.get(dimension_count) {
+                Some(DataResultSetCell::Num(num)) => Some(num),
+                _ => None,
+            })
+            .collect();
+        let min = values.iter().min().map(|min| Self::number(min));
+        let max = values.iter().max().map(|max| Self::number(max));
+        option.insert(
+            String::from("visualMap"),
+            json!({
+                "min": min.unwrap_or(json!(0)),
+                "max": max.unwrap_or(json!(0)),
+                "calculable": true
+            }),
+        );
+        Self::put_tooltip(option, "item");
+        option.insert(String::from("series"), json!([series]));
+        Ok(())
+    }
+
+    // count chart
+
+    fn count(
+        option: &mut OptionMap,
+        settings: Option<&CountChartSettings>,
+        rows: &[&DataResultSetRow],
+    ) {
+        let text_settings = settings.and_then(|s| s.count_text.as_ref());
+        let text = match rows.first().and_then(|row| row.first()) {
+            Some(DataResultSetCell::Num(num))
+                if text_settings.and_then(|s| s.format_use_grouping) == Some(true) =>
+            {
+                Self::grouped(num)
+            }
+            Some(cell) => Self::cell_label(cell),
+            _ => String::new(),
+        };
+        let mut style = OptionMap::new();
+        style.insert(String::from("text"), Value::String(text));
+        Self::put_font(
+            &mut style,
+            text_settings.and_then(|s| s.font.as_ref()),
+            "fill",
+        );
+        option.insert(
+            String::from("graphic"),
+            json!([{"type": "text", "left": "center", "top": "middle", "style": style}]),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ReportEChartsOption;
+    use bigdecimal::BigDecimal;
+    use elf_model::{Chart, ChartRecitation, ChartType, DataResult, DataResultSetCell};
+    use serde_json::{json, Value};
+    use std::fs;
+
+    /// golden files are in "test/echarts", set env "ELF_UPDATE_GOLDEN" to rewrite them.
+    fn assert_golden(name: &str, option: Value) {
+        let path = format!("{}/test/echarts/{}.json", env!("CARGO_MANIFEST_DIR"), name);
+        if std::env::var("ELF_UPDATE_GOLDEN").is_ok() {
+            let mut content = serde_json::to_string_pretty(&option).unwrap();
+            content.push('\n');
+            fs::write(&path, content).unwrap();
+            return;
+        }
+        let golden: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(
+            option, golden,
+            "option of {} differs from golden file.",
+            name
+        );
+    }
+
+    fn recitation(value: Value) -> ChartRecitation {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn str(value: &str) -> DataResultSetCell {
+        DataResultSetCell::Str(value.to_string())
+    }
+
+    fn num(value: i32) -> DataResultSetCell {
+        DataResultSetCell::Num(BigDecimal::from(value))
+    }
+
+    fn data(columns: Vec<&str>, rows: Vec<Vec<DataResultSetCell>>) -> DataResult {
+        DataResult::new()
+            .columns(columns.into_iter().map(String::from).collect())
+            .data(rows)
+    }
+
+    /// region by category, pivoted
+    fn pivoted() -> DataResult {
+        data(
+            vec!["region", "book", "pen"],
+            vec![
+                vec![str("east"), num(15), num(3)],
+                vec![str("west"), num(7), num(2)],
+                vec![str("north"), num(4), DataResultSetCell::None],
+            ],
+        )
+    }
+
+    /// region and category, flat
+    fn flat() -> DataResult {
+        data(
+            vec!["region", "category", "total", "count"],
+            vec![
+                vec![str("east"), str("book"), num(15), num(2)],
+                vec![str("east"), str("pen"), num(3), num(1)],
+                vec![str("west"), str("book"), num(7), num(1)],
+            ],
+        )
+    }
+
+    /// region and category, hierarchy
+    fn hierarchy() -> DataResult {
+        data(
+            vec!["id", "parentId", "name", "total"],
+            vec![
+                vec![str("east"), DataResultSetCell::None, str("east"), num(18)],
+                vec![str("east/book"), str("east"), str("book"), num(15)],
+                vec![str("east/pen"), str("east"), str("pen"), num(3)],
+                vec![str("west"), DataResultSetCell::None, str("west"), num(7)],
+                vec![str("west/book"), str("west"), str("book"), num(7)],
+            ],
+        )
+    }
+
+    fn title() -> Value {
+        json!({
+            "text": {"text": "Sales", "font": {"size": 16, "weight": "700", "color": "#333"}},
+            "subtext": {"text": "by region"},
+            "position": {"top": 8, "left": 16},
+            "horizontalAlign": "center",
+            "verticalAlign": "auto"
+        })
+    }
+
+    #[test]
+    fn test_count() {
+        let chart = recitation(json!({
+            "type": "count",
+            "settings": {
+                "countText": {"font": {"size": 48, "color": "#f00"}, "formatUseGrouping": true},
+                "title": title()
+            }
+        }));
+        let data = data(
+            vec!["total"],
+            vec![vec![DataResultSetCell::Num(
+                BigDecimal::from(1234567) / BigDecimal::from(100),
+            )]],
+        );
+        assert_golden(
+            "count",
+            ReportEChartsOption::render(&chart, 0, &data).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_bar() {
+        let chart = recitation(json!({
+            "type": "bar",
+            "settings": {
+                "series": {"transformAxis": true},
+                "label": {"show": true, "position": "insideRight", "font": {"size": 10}},
+                "decal": true,
+                "legend": {"show": true, "orient": "vertical", "position": {"right": 0}},
+                "grid": {"containLabel": true, "border": {"style": "dashed", "width": 1}},
+                "xaxis": {"name": {"text": "amount", "location": "center", "gap": 24}},
+                "yaxis": {
+                    "label": {"rotate": 45},
+                    "splitLine": {"show": true, "style": "dotted"}
+                },
+                "title": title(),
+                "backgroundColor": "#fff",
+                "colorSeries": "dark",
+                "truncation": {"type": "top", "count": 2}
+            }
+        }));
+        assert_golden(
+            "bar",
+            ReportEChartsOption::render(&chart, 2, &pivoted()).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_line() {
+        let chart = recitation(json!({
+            "type": "line",
+            "settings": {
+                "series": {"smooth": true},
+                "yaxis": {"autoMin": false, "min": 0, "autoMax": true, "max": 100},
+                "colorSeries": "light"
+            }
+        }));
+        assert_golden(
+            "line",
+            ReportEChartsOption::render(&chart, 2, &pivoted()).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_scatter() {
+        let chart =
+            recitation(json!({"type": "scatter", "settings": {"xaxis": {"name": {"text": "total"}}}}));
+        assert_golden(
+            "scatter",
+            ReportEChartsOption::render(&chart, 2, &flat()).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_pie() {
+        let chart = recitation(json!({
+            "type": "pie",
+            "settings": {
+                "series": {"centerX": 40, "insideRadius": 10, "showPercentage": true,
+                    "border": {"color": "#fff", "width": 2}},
+                "label": {"position": "outside", "alignTo": "labelLine"},
+                "legend": {"show": false},
+                "truncation": {"type": "bottom", "count": 2}
+            }
+        }));
+        assert_golden(
+            "pie",
+            ReportEChartsOption::render(&chart, 2, &flat()).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_doughnut() {
+        let chart =
+            recitation(json!({"type": "doughnut", "settings": {"grid": {"position": {"top": 20}}}}));
+        assert_golden(
+            "doughnut",
+            ReportEChartsOption::render(&chart, 2, &flat()).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_nightingale() {
+        let chart =
+            recitation(json!({"type": "nightingale", "settings": {"series": {"roseType": "area"}}}));
+        assert_golden(
+            "nightingale",
+            ReportEChartsOption::render(&chart, 2, &flat()).unwrap(),
+        );
+        let chart = recitation(json!({"type": "nightingale"}));
+        let option = ReportEChartsOption::render(&chart, 2, &flat()).unwrap();
+        assert_eq!(option["series"][0]["roseType"], "radius");
+    }
+
+    #[test]
+    fn test_sunburst() {
+        let chart = recitation(json!({
+            "type": "sunburst",
+            "settings": {"series": {"outsideRadius": 90}, "label": {"rotate": 0}, "decal": true}
+        }));
+        assert_golden(
+            "sunburst",
+            ReportEChartsOption::render(&chart, 2, &hierarchy()).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_tree() {
+        let chart = recitation(json!({
+            "type": "tree",
+            "settings": {"series": {"layout": "radial", "orient": "LR", "roam": true}}
+        }));
+        assert_golden(
+            "tree",
+            ReportEChartsOption::render(&chart, 2, &hierarchy()).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_treemap() {
+        let chart = recitation(json!({"type": "treemap", "settings": {"series": {"roam": false}}}));
+        assert_golden(
+            "treemap",
+            ReportEChartsOption::render(&chart, 2, &hierarchy()).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_map() {
+        let chart = recitation(json!({"type": "map", "settings": {"series": {"region": "usa-l1"}}}));
+        let data = data(
+            vec!["state", "total"],
+            vec![vec![str("Texas"), num(30)], vec![str("Ohio"), num(12)]],
+        );
+        assert_golden(
+            "map",
+            ReportEChartsOption::render(&chart, 1, &data).unwrap(),
+        );
+
+        // region is required
+        let chart = recitation(json!({"type": "map"}));
+        assert!(ReportEChartsOption::render(&chart, 1, &data).is_err());
+    }
+
+    #[test]
+    fn test_customized() {
+        let chart = recitation(json!({"type": "customized", "settings": {"script": "return {};"}}));
+        assert!(ReportEChartsOption::render(&chart, 2, &flat()).is_err());
+    }
+
+    #[test]
+    fn test_recitation_of() {
+        let chart: Chart = serde_json::from_value(json!({
+            "type": "bar",
+            "settings": {"title": {"text": {"text": "Sales"}}, "colorSeries": "dark"}
+        }))
+        .unwrap();
+        let recitation = ReportEChartsOption::recitation_of(&chart).unwrap();
+        let ChartRecitation::Bar(bar) = &recitation else {
+            panic!("bar chart expected.");
+        };
+        assert!(bar.settings.as_ref().unwrap().title.is_some());
+        let option = ReportEChartsOption::render(&recitation, 1, &pivoted()).unwrap();
+        assert_eq!(option["title"]["text"], "Sales");
+        assert_eq!(option["color"][0], "#2f4554");
+
+        assert!(ReportEChartsOption::recitation_of(&Chart::new()).is_err());
+        assert!(ReportEChartsOption::recitation_of(&Chart::new().r#type(ChartType::Pie)).is_ok());
+    }
+}
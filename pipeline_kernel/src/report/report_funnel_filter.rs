@@ -0,0 +1,224 @@
+use crate::{ArcTopicDataValue, PipelineKernelErrorCode, SubjectDatasetPlan, SubjectValueRow};
+use bigdecimal::BigDecimal;
+use chrono::{Datelike, NaiveDate, Timelike};
+use elf_base::{
+    DateConstValues, DateTimeUtils, ErrorCode, NumericUtils, StdR, AM, DAY_KIND_WEEKEND,
+    DAY_KIND_WORKDAY, HALF_MONTH_FIRST, HALF_MONTH_SECOND, HALF_WEEK_FIRST, HALF_WEEK_SECOND,
+    HOUR_KIND_OFF_HOURS, HOUR_KIND_SLEEPING_TIME, HOUR_KIND_WORKTIME, PM,
+};
+use elf_model::{ReportFunnel, ReportFunnelType};
+use std::ops::Deref;
+
+/// value of funnel, or value of row cell converted by funnel type
+#[derive(PartialEq, PartialOrd)]
+enum ReportFunnelValue {
+    Num(BigDecimal),
+    Date(NaiveDate),
+    Str(String),
+}
+
+enum ReportFunnelValues {
+    /// inclusive, none means unbounded
+    Range(Option<ReportFunnelValue>, Option<ReportFunnelValue>),
+    /// any of
+    In(Vec<ReportFunnelValue>),
+}
+
+/// filter rows by an enabled funnel of report.
+/// - numeric funnel compares numbers, date funnel compares dates,
+/// - date part funnels compare the part numbers of date or time, see [elf_base::DateConstValues],
+///   half week is 1 (sunday to wednesday) or 2, day kind is 1 (workday) or 2 (weekend),
+///   hour kind is 1 (9 to 18), 3 (0 to 6) or 2, am/pm is 1 or 2,
+/// - enum funnel compares codes,
+/// - range funnel has 2 values as inclusive bounds, each can be none,
+///   otherwise row matches any of the values.
+///
+/// row is not matched when its value cannot be converted.
+pub struct ReportFunnelFilter {
+    column_index: usize,
+    r#type: ReportFunnelType,
+    values: ReportFunnelValues,
+}
+
+impl ReportFunnelFilter {
+    /// returns none when funnel is disabled or has no value
+    pub fn compile(funnel: ReportFunnel, plan: &SubjectDatasetPlan) -> StdR<Option<Self>> {
+        if funnel.enabled != Some(true) {
+            return Ok(None);
+        }
+        let values: Vec<&String> = funnel.values.iter().flatten().flatten().collect();
+        if values.iter().all(|value| value.trim().is_empty()) {
+            return Ok(None);
+        }
+        let column_id = funnel.column_id.clone().unwrap_or_default();
+        let Some(column_index) = plan.column_index(&column_id) else {
+            return PipelineKernelErrorCode::ReportColumnNotFound.msg(format!(
+                "Column[{}] of funnel not found in subject[{}].",
+                column_id,
+                plan.subject_id()
+            ));
+        };
+        let Some(r#type) = funnel.r#type else {
+            return PipelineKernelErrorCode::ReportFunnelInvalid.msg(format!(
+                "Type of funnel on column[{}] is missed.",
+                column_id
+            ));
+        };
+
+        let values = if funnel.range == Some(true) {
+            let bound = |index: usize| -> StdR<Option<ReportFunnelValue>> {
+                match funnel.values.as_ref().and_then(|values| values.get(index)) {
+                    Some(Some(value)) if !value.trim().is_empty() => {
+                        Ok(Some(Self::parse(&r#type, value)?))
+                    }
+                    _ => Ok(None),
+                }
+            };
+            ReportFunnelValues::Range(bound(0)?, bound(1)?)
+        } else {
+            ReportFunnelValues::In(
+                values
+                    .into_iter()
+                    .filter(|value| !value.trim().is_empty())
+                    .map(|value| Self::parse(&r#type, value))
+                    .collect::<StdR<Vec<ReportFunnelValue>>>()?,
+            )
+        };
+
+        Ok(Some(Self {
+            column_index,
+            r#type,
+            values,
+        }))
+    }
+
+    fn parse(r#type: &ReportFunnelType, value: &String) -> StdR<ReportFunnelValue> {
+        let parsed = match r#type {
+            ReportFunnelType::Enum => Some(ReportFunnelValue::Str(value.clone())),
+            ReportFunnelType::Date => value.to_date_loose().ok().map(ReportFunnelValue::Date),
+            _ => value
+                .trim()
+                .to_string()
+                .to_decimal()
+                .ok()
+                .map(ReportFunnelValue::Num),
+        };
+        match parsed {
+            Some(parsed) => Ok(parsed),
+            _ => PipelineKernelErrorCode::ReportFunnelInvalid
+                .msg(format!("Value[{}] of {} funnel is invalid.", value, r#type)),
+        }
+    }
+
+    fn date_of(value: &ArcTopicDataValue) -> Option<NaiveDate> {
+        match value {
+            ArcTopicDataValue::Date(date) => Some(**date),
+            ArcTopicDataValue::DateTime(datetime) => Some(datetime.date()),
+            ArcTopicDataValue::Str(str) => str.to_date_loose().ok(),
+            _ => None,
+        }
+    }
+
+    fn hour_of(value: &ArcTopicDataValue) -> Option<u32> {
+        match value {
+            ArcTopicDataValue::DateTime(datetime) => Some(datetime.hour()),
+            ArcTopicDataValue::Time(time) => Some(time.hour()),
+            ArcTopicDataValue::Str(str) => str.to_datetime_loose().ok().map(|dt| dt.hour()),
+            _ => None,
+        }
+    }
+
+    fn date_part_of(r#type: &ReportFunnelType, date: NaiveDate) -> u32 {
+        match r#type {
+            ReportFunnelType::Year => date.year() as u32,
+            ReportFunnelType::HalfYear => date.half_year() as u32,
+            ReportFunnelType::Quarter => DateConstValues::quarter(&date) as u32,
+            ReportFunnelType::Month => date.month(),
+            ReportFunnelType::HalfMonth => {
+                if date.day() <= 15 {
+                    HALF_MONTH_FIRST as u32
+                } else {
+                    HALF_MONTH_SECOND as u32
+                }
+            }
+            ReportFunnelType::TenDays => date.ten_days() as u32,
+            ReportFunnelType::WeekOfMonth => date.week_of_month() as u32,
+            ReportFunnelType::HalfWeek => {
+                if date.weekday().number_from_sunday() <= 4 {
+                    HALF_WEEK_FIRST as u32
+                } else {
+                    HALF_WEEK_SECOND as u32
+                }
+            }
+            ReportFunnelType::DayKind => match date.weekday().number_from_sunday() {
+                1 | 7 => DAY_KIND_WEEKEND as u32,
+                _ => DAY_KIND_WORKDAY as u32,
+            },
+            // day of week
+            _ => date.weekday().number_from_sunday(),
+        }
+    }
+
+    fn hour_part_of(r#type: &ReportFunnelType, hour: u32) -> u32 {
+        match r#type {
+            ReportFunnelType::HourKind => match hour {
+                9..18 => HOUR_KIND_WORKTIME as u32,
+                0..6 => HOUR_KIND_SLEEPING_TIME as u32,
+                _ => HOUR_KIND_OFF_HOURS as u32,
+            },
+            ReportFunnelType::AmPm => {
+                if hour < 12 {
+                    AM as u32
+                } else {
+                    PM as u32
+                }
+            }
+            // hour
+            _ => hour,
+        }
+    }
+
+    /// convert cell value by funnel type
+    fn value_of(&self, value: &ArcTopicDataValue) -> Option<ReportFunnelValue> {
+        match &self.r#type {
+            ReportFunnelType::Numeric => match value {
+                ArcTopicDataValue::Num(num) => Some(ReportFunnelValue::Num(num.deref().clone())),
+                ArcTopicDataValue::Str(str) => str.to_decimal().ok().map(ReportFunnelValue::Num),
+                _ => None,
+            },
+            ReportFunnelType::Date => Self::date_of(value).map(ReportFunnelValue::Date),
+            ReportFunnelType::Enum => match value {
+                ArcTopicDataValue::Str(str) => Some(ReportFunnelValue::Str(str.deref().clone())),
+                ArcTopicDataValue::Num(num) => {
+                    Some(ReportFunnelValue::Str(num.normalized().to_string()))
+                }
+                ArcTopicDataValue::Bool(bool) => Some(ReportFunnelValue::Str(bool.to_string())),
+                _ => None,
+            },
+            ReportFunnelType::Hour | ReportFunnelType::HourKind | ReportFunnelType::AmPm => {
+                Self::hour_of(value).map(|hour| {
+                    ReportFunnelValue::Num(BigDecimal::from(Self::hour_part_of(&self.r#type, hour)))
+                })
+            }
+            r#type => Self::date_of(value).map(|date| {
+                ReportFunnelValue::Num(BigDecimal::from(Self::date_part_of(r#type, date)))
+            }),
+        }
+    }
+
+    pub fn matches(&self, row: &SubjectValueRow) -> bool {
+        let Some(value) = row
+            .get(self.column_index)
+            .and_then(|value| self.value_of(value))
+        else {
+            return false;
+        };
+        match &self.values {
+            ReportFunnelValues::Range(from, to) => {
+                from.as_ref().is_none_or(|from| &value >= from)
+                    && to.as_ref().is_none_or(|to| &value <= to)
+            }
+            ReportFunnelValues::In(values) => values.contains(&value),
+        }
+    }
+}
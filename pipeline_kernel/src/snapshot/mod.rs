@@ -0,0 +1,7 @@
+mod topic_snapshot_job;
+mod topic_snapshot_resources;
+mod topic_snapshot_scheduler_engine;
+
+pub use topic_snapshot_job::*;
+pub use topic_snapshot_resources::*;
+pub use topic_snapshot_scheduler_engine::*;
@@ -0,0 +1,177 @@
+use crate::{
+    ArcTopicData, ArcTopicDataBuilder, CompiledParameterJoint, InMemoryData,
+    PipelineKernelErrorCode, TopicSnapshotResources,
+};
+use chrono::{NaiveDate, NaiveDateTime};
+use elf_base::{ErrorCode, StdR, VoidR};
+use elf_model::{
+    PipelineId, TenantId, TopicData, TopicDataColumnNames, TopicDataValue, TopicSnapshotFrequency,
+    TopicSnapshotJobLock, TopicSnapshotJobLockStatus, TopicSnapshotScheduler,
+    TopicSnapshotSchedulerId,
+};
+use elf_runtime_model_kernel::{ArcParameterJoint, IdGen, TopicSchema};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// factor name of snapshot tag in target topic, value is the process date.
+pub const TOPIC_SNAPSHOT_TAG: &str = "snapshotTag";
+
+/// take snapshots of scheduler, on given process dates.
+/// for each process date,
+/// - lock the process date, skip when it is locked by another run,
+/// - read rows of source topic, and filter them by scheduler filter,
+/// - copy each row into target topic with snapshot tag, and trigger the pipeline,
+/// - finish the lock with count of copied rows.
+pub struct TopicSnapshotJob<'a, R: TopicSnapshotResources> {
+    resources: &'a R,
+    scheduler_id: TopicSnapshotSchedulerId,
+    tenant_id: TenantId,
+    frequency: TopicSnapshotFrequency,
+    pipeline_id: Option<PipelineId>,
+    topic_schema: Arc<TopicSchema>,
+    target_topic_schema: Arc<TopicSchema>,
+    filter: Option<CompiledParameterJoint>,
+}
+
+impl<'a, R: TopicSnapshotResources> TopicSnapshotJob<'a, R> {
+    /// topics are loaded and filter is compiled here, nothing is locked yet.
+    pub fn new(resources: &'a R, scheduler: TopicSnapshotScheduler) -> StdR<Self> {
+        let scheduler_id = scheduler.scheduler_id.unwrap_or_default();
+        let missed = |what: &str| {
+            PipelineKernelErrorCode::TopicSnapshotSchedulerIncomplete.msg(format!(
+                "{} of topic snapshot scheduler[scheduler_id={}] is missed.",
+                what, scheduler_id
+            ))
+        };
+        if scheduler_id.is_empty() {
+            return missed("Scheduler id");
+        }
+        let Some(tenant_id) = scheduler.tenant_id else {
+            return missed("Tenant id");
+        };
+        let Some(topic_id) = scheduler.topic_id else {
+            return missed("Topic");
+        };
+        let Some(target_topic_id) = scheduler.target_topic_id else {
+            return missed("Target topic");
+        };
+        let Some(frequency) = scheduler.frequency else {
+            return missed("Frequency");
+        };
+
+        let topic_schema = resources.topic_schema(&topic_id, &tenant_id)?;
+        let target_topic_schema = resources.topic_schema(&target_topic_id, &tenant_id)?;
+        let filter = match scheduler.filter {
+            Some(filter) => {
+                let mut topic_schemas = HashMap::new();
+                topic_schemas.insert(topic_schema.topic_id().clone(), topic_schema.clone());
+                Some(CompiledParameterJoint::compile(
+                    &ArcParameterJoint::new(filter)?,
+                    &mut topic_schemas,
+                    topic_schema.tenant_id(),
+                )?)
+            }
+            _ => None,
+        };
+
+        Ok(Self {
+            resources,
+            scheduler_id,
+            tenant_id,
+            frequency,
+            pipeline_id: scheduler.pipeline_id,
+            topic_schema,
+            target_topic_schema,
+            filter,
+        })
+    }
+
+    pub fn scheduler_id(&self) -> &TopicSnapshotSchedulerId {
+        &self.scheduler_id
+    }
+
+    pub fn tenant_id(&self) -> &TenantId {
+        &self.tenant_id
+    }
+
+    /// returns count of copied rows, or none when process date is locked by another run.
+    pub fn run(&self, process_date: &NaiveDate, now: &NaiveDateTime) -> StdR<Option<u32>> {
+        let lock = TopicSnapshotJobLock::new()
+            .lock_id(IdGen::next_id()?.to_string())
+            .scheduler_id(self.scheduler_id.clone())
+            .tenant_id(self.tenant_id.clone())
+            .frequency(self.frequency.clone())
+            .process_date(*process_date)
+            .row_count(0)
+            .status(TopicSnapshotJobLockStatus::Ready)
+            .created_at(*now);
+        if !self.resources.try_lock(&lock)? {
+            // process date is locked by another run
+            return Ok(None);
+        }
+
+        let mut copied = 0;
+        let result = self.copy(process_date, &mut copied);
+        // lock id is set above
+        let lock_id = lock.lock_id.as_ref().unwrap();
+        match result {
+            Ok(_) => {
+                self.resources
+                    .finish(lock_id, TopicSnapshotJobLockStatus::Success, copied)?;
+                Ok(Some(copied))
+            }
+            Err(e) => {
+                // keep the copy error, failure of finishing lock is logged only
+                if let Err(finish_error) =
+                    self.resources
+                        .finish(lock_id, TopicSnapshotJobLockStatus::Failed, copied)
+                {
+                    tracing::error!(
+                        "Failed to finish topic snapshot job lock[{}], caused by {}.",
+                        lock_id,
+                        finish_error
+                    );
+                }
+                Err(e)
+            }
+        }
+    }
+
+    fn copy(&self, process_date: &NaiveDate, copied: &mut u32) -> VoidR {
+        for row in self.resources.rows(&self.topic_schema)? {
+            if let Some(filter) = &self.filter {
+                let mut in_memory_data =
+                    InMemoryData::new(None, Some(ArcTopicData::build(row.clone())));
+                if !filter.is_true(&mut in_memory_data)? {
+                    continue;
+                }
+            }
+            self.resources.trigger(
+                &self.target_topic_schema,
+                Self::snapshot_of(row, process_date),
+                self.pipeline_id.as_ref(),
+            )?;
+            *copied += 1;
+        }
+        Ok(())
+    }
+
+    /// technical columns of source row are removed, they are generated again by target topic
+    fn snapshot_of(mut row: TopicData, process_date: &NaiveDate) -> TopicData {
+        for column in [
+            TopicDataColumnNames::Id,
+            TopicDataColumnNames::AggregateAssist,
+            TopicDataColumnNames::Version,
+            TopicDataColumnNames::TenantId,
+            TopicDataColumnNames::InsertTime,
+            TopicDataColumnNames::UpdateTime,
+        ] {
+            row.remove(column.column_name());
+        }
+        row.insert(
+            TOPIC_SNAPSHOT_TAG.to_string(),
+            TopicDataValue::Date(*process_date),
+        );
+        row
+    }
+}
@@ -0,0 +1,105 @@
+use crate::PipelineExecuteFacade;
+use elf_auth::Principal;
+use elf_base::{StdR, VoidR};
+use elf_model::{
+    PipelineId, PipelineTriggerData, PipelineTriggerType, TenantId, TopicData, TopicId,
+    TopicSnapshotJobLock, TopicSnapshotJobLockId, TopicSnapshotJobLockStatus,
+    TopicSnapshotScheduler, TopicSnapshotSchedulerId,
+};
+use elf_runtime_model_kernel::{
+    TopicDataProvider, TopicSchema, TopicSchemaProvider, TopicService,
+    TopicSnapshotJobLockProvider, TopicSnapshotSchedulerMetaProvider, TopicSnapshotService,
+};
+use std::sync::Arc;
+
+/// everything topic snapshot scheduler reads from and writes to.
+pub trait TopicSnapshotResources {
+    /// enabled schedulers of all tenants
+    fn schedulers(&self) -> StdR<Vec<TopicSnapshotScheduler>>;
+    /// the lock which has the latest process date of given scheduler
+    fn latest_lock(
+        &self,
+        scheduler_id: &TopicSnapshotSchedulerId,
+        tenant_id: &TenantId,
+    ) -> StdR<Option<TopicSnapshotJobLock>>;
+    /// see [elf_runtime_model_kernel::TopicSnapshotJobLockService::try_lock]
+    fn try_lock(&self, lock: &TopicSnapshotJobLock) -> StdR<bool>;
+    fn finish(
+        &self,
+        lock_id: &TopicSnapshotJobLockId,
+        status: TopicSnapshotJobLockStatus,
+        row_count: u32,
+    ) -> VoidR;
+    fn topic_schema(&self, topic_id: &TopicId, tenant_id: &TenantId) -> StdR<Arc<TopicSchema>>;
+    fn rows(&self, topic_schema: &Arc<TopicSchema>) -> StdR<Vec<TopicData>>;
+    /// insert given row into target topic, and trigger given pipeline.
+    /// all insert pipelines of target topic are triggered when pipeline is not given.
+    fn trigger(
+        &self,
+        target_topic_schema: &Arc<TopicSchema>,
+        row: TopicData,
+        pipeline_id: Option<&PipelineId>,
+    ) -> VoidR;
+}
+
+/// schedulers and locks are found from meta, rows are read from topic storage,
+/// snapshot rows are triggered asynchronized as tenant admin.
+pub struct TopicSnapshotMetaResources;
+
+impl TopicSnapshotResources for TopicSnapshotMetaResources {
+    fn schedulers(&self) -> StdR<Vec<TopicSnapshotScheduler>> {
+        TopicSnapshotService::meta()?.find_enabled()
+    }
+
+    fn latest_lock(
+        &self,
+        scheduler_id: &TopicSnapshotSchedulerId,
+        tenant_id: &TenantId,
+    ) -> StdR<Option<TopicSnapshotJobLock>> {
+        TopicSnapshotService::job_lock()?.find_latest(scheduler_id, tenant_id)
+    }
+
+    fn try_lock(&self, lock: &TopicSnapshotJobLock) -> StdR<bool> {
+        TopicSnapshotService::job_lock()?.try_lock(lock)
+    }
+
+    fn finish(
+        &self,
+        lock_id: &TopicSnapshotJobLockId,
+        status: TopicSnapshotJobLockStatus,
+        row_count: u32,
+    ) -> VoidR {
+        TopicSnapshotService::job_lock()?.finish(lock_id, status, row_count)
+    }
+
+    fn topic_schema(&self, topic_id: &TopicId, tenant_id: &TenantId) -> StdR<Arc<TopicSchema>> {
+        TopicService::schema()?.by_id(topic_id, tenant_id)
+    }
+
+    fn rows(&self, topic_schema: &Arc<TopicSchema>) -> StdR<Vec<TopicData>> {
+        TopicService::data()?.find_all(topic_schema)
+    }
+
+    fn trigger(
+        &self,
+        target_topic_schema: &Arc<TopicSchema>,
+        row: TopicData,
+        pipeline_id: Option<&PipelineId>,
+    ) -> VoidR {
+        let tenant_id = target_topic_schema.tenant_id().to_string();
+        let principal = Principal::fake_tenant_admin(Some(tenant_id.clone()), None, None);
+        let facade = PipelineExecuteFacade::with(
+            &principal,
+            PipelineTriggerData::new()
+                .code(target_topic_schema.name().to_string())
+                .data(row)
+                .trigger_type(PipelineTriggerType::Insert)
+                .tenant_id(tenant_id),
+        )?;
+        match pipeline_id {
+            Some(pipeline_id) => facade.execute_single_async(pipeline_id)?,
+            _ => facade.execute_async()?,
+        };
+        Ok(())
+    }
+}
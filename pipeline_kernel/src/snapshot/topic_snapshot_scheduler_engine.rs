@@ -0,0 +1,360 @@
+use crate::{TopicSnapshotJob, TopicSnapshotResources};
+use chrono::{NaiveDate, NaiveDateTime};
+use elf_base::{StdErr, StdR, VoidResultHelper};
+use elf_model::{TenantId, TopicSnapshotScheduler, TopicSnapshotSchedulerId};
+use elf_runtime_model_kernel::{SchedulerClock, TopicSnapshotSchedule};
+
+/// snapshot taken by this run
+pub struct TopicSnapshotRun {
+    pub scheduler_id: TopicSnapshotSchedulerId,
+    pub tenant_id: TenantId,
+    pub process_date: NaiveDate,
+    pub row_count: u32,
+}
+
+/// fire topic snapshot schedulers, time is given by clock.
+/// host calls [TopicSnapshotSchedulerEngine::tick] periodically,
+/// or sleeps till [TopicSnapshotSchedulerEngine::next_fire_time] and then calls it.
+///
+/// each tick runs all fire times which are due since the latest locked process date of scheduler,
+/// therefore fire times missed during downtime are caught up, at most the latest [max_catch_up] of each scheduler.
+/// fire time is locked before run, so it is run only once in cluster, even when the run is failed.
+pub struct TopicSnapshotSchedulerEngine<C: SchedulerClock, R: TopicSnapshotResources> {
+    clock: C,
+    resources: R,
+    max_catch_up: usize,
+}
+
+impl<C: SchedulerClock, R: TopicSnapshotResources> TopicSnapshotSchedulerEngine<C, R> {
+    pub const DEFAULT_MAX_CATCH_UP: usize = 31;
+
+    pub fn new(clock: C, resources: R) -> Self {
+        Self {
+            clock,
+            resources,
+            max_catch_up: Self::DEFAULT_MAX_CATCH_UP,
+        }
+    }
+
+    pub fn max_catch_up(mut self, max_catch_up: usize) -> Self {
+        self.max_catch_up = max_catch_up;
+        self
+    }
+
+    fn enabled_schedulers(&self) -> StdR<Vec<TopicSnapshotScheduler>> {
+        Ok(self
+            .resources
+            .schedulers()?
+            .into_iter()
+            .filter(|scheduler| scheduler.enabled.unwrap_or(false))
+            .collect())
+    }
+
+    /// fire times of scheduler which are due at given time, ordered by time.
+    /// starts after the fire time of latest locked process date,
+    /// or the created time of scheduler when it never fired,
+    /// nothing is due when neither of them is known.
+    pub fn due(
+        &self,
+        scheduler: &TopicSnapshotScheduler,
+        now: &NaiveDateTime,
+    ) -> StdR<Vec<NaiveDateTime>> {
+        let schedule = TopicSnapshotSchedule::of(scheduler)?;
+        let latest_lock = match (&scheduler.scheduler_id, &scheduler.tenant_id) {
+            (Some(scheduler_id), Some(tenant_id)) => {
+                self.resources.latest_lock(scheduler_id, tenant_id)?
+            }
+            _ => None,
+        };
+        let after = match latest_lock.and_then(|lock| lock.process_date) {
+            Some(process_date) => schedule.fire_time_of(&process_date),
+            _ => match scheduler.created_at {
+                Some(created_at) => created_at,
+                _ => return Ok(vec![]),
+            },
+        };
+
+        let mut fire_times = schedule.fire_times_between(&after, now);
+        if fire_times.len() > self.max_catch_up {
+            fire_times.drain(..fire_times.len() - self.max_catch_up);
+        }
+        Ok(fire_times)
+    }
+
+    /// run due fire times of all enabled schedulers.
+    /// returns snapshots taken by this call, fire times locked by another run are not included.
+    /// errors are accumulated, failure of one scheduler doesn't stop the others.
+    pub fn tick(&self) -> StdR<Vec<TopicSnapshotRun>> {
+        let now = self.clock.now();
+
+        let mut runs = vec![];
+        let mut errors: Vec<StdErr> = vec![];
+        for scheduler in self.enabled_schedulers()? {
+            let fire_times = match self.due(&scheduler, &now) {
+                Ok(fire_times) if fire_times.is_empty() => continue,
+                Ok(fire_times) => fire_times,
+                Err(e) => {
+                    errors.push(e);
+                    continue;
+                }
+            };
+            let job = match TopicSnapshotJob::new(&self.resources, scheduler) {
+                Ok(job) => job,
+                Err(e) => {
+                    errors.push(e);
+                    continue;
+                }
+            };
+            for fire_time in fire_times {
+                let process_date = fire_time.date();
+                match job.run(&process_date, &now) {
+                    Ok(Some(row_count)) => runs.push(TopicSnapshotRun {
+                        scheduler_id: job.scheduler_id().clone(),
+                        tenant_id: job.tenant_id().clone(),
+                        process_date,
+                        row_count,
+                    }),
+                    Ok(None) => {}
+                    Err(e) => errors.push(e),
+                }
+            }
+        }
+        errors.accumulate()?;
+
+        Ok(runs)
+    }
+
+    /// the earliest fire time after now of all enabled schedulers,
+    /// returns none when there is no enabled scheduler.
+    pub fn next_fire_time(&self) -> StdR<Option<NaiveDateTime>> {
+        let now = self.clock.now();
+        let mut next: Option<NaiveDateTime> = None;
+        for scheduler in self.enabled_schedulers()? {
+            let fire_time = TopicSnapshotSchedule::of(&scheduler)?.next_fire_time(&now);
+            next = Some(next.map_or(fire_time, |next| next.min(fire_time)));
+        }
+        Ok(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{TopicSnapshotResources, TopicSnapshotSchedulerEngine, TOPIC_SNAPSHOT_TAG};
+    use bigdecimal::BigDecimal;
+    use chrono::{NaiveDate, NaiveDateTime};
+    use elf_base::{StdR, VoidR};
+    use elf_model::{
+        ConstantParameter, Factor, FactorType, MoreThanExpression, ParameterJoint, ParameterKind,
+        PipelineId, TenantId, Topic, TopicData, TopicDataValue, TopicFactorParameter, TopicId,
+        TopicKind, TopicSnapshotFrequency, TopicSnapshotJobLock, TopicSnapshotJobLockId,
+        TopicSnapshotJobLockStatus, TopicSnapshotScheduler, TopicSnapshotSchedulerId, TopicType,
+    };
+    use elf_runtime_model_kernel::{IdGen, SchedulerClock, SnowflakeIdGenerator, TopicSchema};
+    use std::cell::{Cell, RefCell};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    struct FixedClock(Cell<NaiveDateTime>);
+
+    impl SchedulerClock for &FixedClock {
+        fn now(&self) -> NaiveDateTime {
+            self.0.get()
+        }
+    }
+
+    #[derive(Default)]
+    struct InMemoryResources {
+        /// lock id, process date, status, row count
+        locks: RefCell<Vec<(String, NaiveDate, String, u32)>>,
+        triggered: RefCell<Vec<TopicData>>,
+    }
+
+    impl TopicSnapshotResources for &InMemoryResources {
+        fn schedulers(&self) -> StdR<Vec<TopicSnapshotScheduler>> {
+            let filter = ParameterJoint::and(vec![MoreThanExpression::init()
+                .left(
+                    TopicFactorParameter {
+                        kind: Some(ParameterKind::Topic),
+                        topic_id: Some(String::from("orders")),
+                        factor_id: Some(String::from("o1")),
+                    }
+                    .to_parameter(),
+                )
+                // plain constant is a path, concat with empty to get literal
+                .right(ConstantParameter::of(String::from("6{}")).to_parameter())
+                .to_condition()]);
+            Ok(vec![
+                TopicSnapshotScheduler::new()
+                    .scheduler_id(String::from("s1"))
+                    .topic_id(String::from("orders"))
+                    .target_topic_id(String::from("orders_snapshot"))
+                    .frequency(TopicSnapshotFrequency::Daily)
+                    .hour(1)
+                    .filter(filter)
+                    .enabled(true)
+                    .tenant_id(String::from("tenant-1"))
+                    .created_at(time(2026, 2, 10, 8)),
+                TopicSnapshotScheduler::new()
+                    .scheduler_id(String::from("s2"))
+                    .topic_id(String::from("orders"))
+                    .target_topic_id(String::from("orders_snapshot"))
+                    .frequency(TopicSnapshotFrequency::Daily)
+                    .enabled(false)
+                    .tenant_id(String::from("tenant-1"))
+                    .created_at(time(2026, 2, 10, 0)),
+            ])
+        }
+
+        fn latest_lock(
+            &self,
+            _scheduler_id: &TopicSnapshotSchedulerId,
+            _tenant_id: &TenantId,
+        ) -> StdR<Option<TopicSnapshotJobLock>> {
+            Ok(self
+                .locks
+                .borrow()
+                .iter()
+                .map(|(_, process_date, _, _)| *process_date)
+                .max()
+                .map(|process_date| TopicSnapshotJobLock::new().process_date(process_date)))
+        }
+
+        fn try_lock(&self, lock: &TopicSnapshotJobLock) -> StdR<bool> {
+            let process_date = lock.process_date.unwrap();
+            let mut locks = self.locks.borrow_mut();
+            if locks.iter().any(|(_, date, _, _)| *date == process_date) {
+                return Ok(false);
+            }
+            locks.push((
+                lock.lock_id.clone().unwrap(),
+                process_date,
+                lock.status.as_ref().unwrap().to_string(),
+                0,
+            ));
+            Ok(true)
+        }
+
+        fn finish(
+            &self,
+            lock_id: &TopicSnapshotJobLockId,
+            status: TopicSnapshotJobLockStatus,
+            row_count: u32,
+        ) -> VoidR {
+            for lock in self.locks.borrow_mut().iter_mut() {
+                if &lock.0 == lock_id {
+                    lock.2 = status.to_string();
+                    lock.3 = row_count;
+                }
+            }
+            Ok(())
+        }
+
+        fn topic_schema(&self, topic_id: &TopicId, tenant_id: &TenantId) -> StdR<Arc<TopicSchema>> {
+            let topic = Topic::new()
+                .topic_id(topic_id.clone())
+                .name(topic_id.clone())
+                .r#type(TopicType::Raw)
+                .kind(TopicKind::Business)
+                .factors(vec![Factor::new()
+                    .factor_id(String::from("o1"))
+                    .name(String::from("amount"))
+                    .r#type(FactorType::Number)])
+                .tenant_id(tenant_id.clone());
+            Ok(Arc::new(TopicSchema::new(topic)?))
+        }
+
+        fn rows(&self, _topic_schema: &Arc<TopicSchema>) -> StdR<Vec<TopicData>> {
+            let order = |amount: i32| {
+                let mut data = HashMap::new();
+                data.insert(
+                    String::from("amount"),
+                    TopicDataValue::Num(BigDecimal::from(amount)),
+                );
+                data.insert(String::from("id_"), TopicDataValue::Str(amount.to_string()));
+                data
+            };
+            Ok(vec![order(10), order(5), order(7)])
+        }
+
+        fn trigger(
+            &self,
+            _target_topic_schema: &Arc<TopicSchema>,
+            row: TopicData,
+            _pipeline_id: Option<&PipelineId>,
+        ) -> VoidR {
+            self.triggered.borrow_mut().push(row);
+            Ok(())
+        }
+    }
+
+    fn time(year: i32, month: u32, day: u32, hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_tick() {
+        let _ = IdGen::set(Box::new(SnowflakeIdGenerator::new(1).unwrap()));
+        let clock = FixedClock(Cell::new(time(2026, 2, 10, 12)));
+        let resources = InMemoryResources::default();
+        let engine = TopicSnapshotSchedulerEngine::new(&clock, &resources);
+
+        // first fire time is on 2026-02-10 01:00, which is before created time
+        assert!(engine.tick().unwrap().is_empty());
+        assert_eq!(engine.next_fire_time().unwrap(), Some(time(2026, 2, 11, 1)));
+
+        clock.0.set(time(2026, 2, 11, 1));
+        let runs = engine.tick().unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].scheduler_id, "s1");
+        assert_eq!(runs[0].process_date, time(2026, 2, 11, 0).date());
+        assert_eq!(runs[0].row_count, 2);
+        {
+            let triggered = resources.triggered.borrow();
+            assert_eq!(triggered.len(), 2);
+            assert!(!triggered[0].contains_key("id_"));
+            assert!(matches!(
+                triggered[0].get(TOPIC_SNAPSHOT_TAG),
+                Some(TopicDataValue::Date(date)) if *date == time(2026, 2, 11, 0).date()
+            ));
+        }
+        // fired already
+        assert!(engine.tick().unwrap().is_empty());
+
+        // down from 2026-02-12 to 2026-02-14, catch up 12th, 13th and 14th
+        clock.0.set(time(2026, 2, 14, 3));
+        let runs = engine.tick().unwrap();
+        assert_eq!(runs.len(), 3);
+        assert_eq!(runs[0].process_date, time(2026, 2, 12, 0).date());
+        assert_eq!(runs[2].process_date, time(2026, 2, 14, 0).date());
+        let locks = resources.locks.borrow();
+        assert_eq!(locks.len(), 4);
+        assert!(locks
+            .iter()
+            .all(|(_, _, status, count)| status == "success" && *count == 2));
+    }
+
+    #[test]
+    fn test_max_catch_up() {
+        let clock = FixedClock(Cell::new(time(2026, 3, 10, 0)));
+        let resources = InMemoryResources::default();
+        let engine = TopicSnapshotSchedulerEngine::new(&clock, &resources).max_catch_up(2);
+        let scheduler = TopicSnapshotScheduler::new()
+            .frequency(TopicSnapshotFrequency::Daily)
+            .created_at(time(2026, 2, 1, 0));
+        assert_eq!(
+            engine.due(&scheduler, &clock.0.get()).unwrap(),
+            vec![time(2026, 3, 9, 0), time(2026, 3, 10, 0)]
+        );
+        // never fired and created time is unknown
+        assert!(engine
+            .due(
+                &TopicSnapshotScheduler::new().frequency(TopicSnapshotFrequency::Daily),
+                &clock.0.get()
+            )
+            .unwrap()
+            .is_empty());
+    }
+}
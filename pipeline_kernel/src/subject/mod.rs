@@ -0,0 +1,15 @@
+mod subject_aggregator;
+mod subject_data_source;
+mod subject_dataset_engine;
+mod subject_dataset_envs;
+mod subject_dataset_plan;
+mod subject_joiner;
+mod subject_row_scope;
+
+pub use subject_aggregator::*;
+pub use subject_data_source::*;
+pub use subject_dataset_engine::*;
+pub use subject_dataset_envs::*;
+pub use subject_dataset_plan::*;
+pub use subject_joiner::*;
+pub use subject_row_scope::*;
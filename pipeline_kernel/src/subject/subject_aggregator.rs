@@ -0,0 +1,140 @@
+use crate::{ArcFrom, ArcTopicDataValue, PipelineKernelErrorCode, SubjectJoiner};
+use bigdecimal::{BigDecimal, Zero};
+use elf_base::{ErrorCode, StdR};
+use elf_model::SubjectColumnArithmetic;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// values of one row, in the same order of columns.
+pub type SubjectValueRow = Vec<Arc<ArcTopicDataValue>>;
+
+/// group rows by the columns which has no arithmetic, and aggregate the others.
+/// groups are in the order of their first row.
+pub struct SubjectAggregator;
+
+impl SubjectAggregator {
+    pub fn is_aggregated(arithmetic: &SubjectColumnArithmetic) -> bool {
+        !matches!(arithmetic, SubjectColumnArithmetic::None)
+    }
+
+    /// rows are returned as they are when there is no aggregated column.
+    pub fn aggregate(
+        rows: Vec<SubjectValueRow>,
+        arithmetics: &[&SubjectColumnArithmetic],
+    ) -> StdR<Vec<SubjectValueRow>> {
+        if !arithmetics.iter().any(|a| Self::is_aggregated(a)) {
+            return Ok(rows);
+        }
+
+        let mut group_index: HashMap<Vec<Option<String>>, usize> = HashMap::new();
+        let mut groups: Vec<Vec<SubjectValueRow>> = vec![];
+        for row in rows {
+            let key: Vec<Option<String>> = arithmetics
+                .iter()
+                .zip(row.iter())
+                .filter(|(arithmetic, _)| !Self::is_aggregated(arithmetic))
+                .map(|(_, value)| SubjectJoiner::key_of(value))
+                .collect();
+            match group_index.get(&key) {
+                Some(index) => groups[*index].push(row),
+                _ => {
+                    group_index.insert(key, groups.len());
+                    groups.push(vec![row]);
+                }
+            }
+        }
+
+        let mut aggregated = vec![];
+        for group in groups {
+            let mut row = vec![];
+            for (column_index, arithmetic) in arithmetics.iter().enumerate() {
+                let values: Vec<&Arc<ArcTopicDataValue>> =
+                    group.iter().map(|row| &row[column_index]).collect();
+                row.push(Self::apply(arithmetic, values)?);
+            }
+            aggregated.push(row);
+        }
+        Ok(aggregated)
+    }
+
+    /// aggregate values of one group
+    pub fn apply(
+        arithmetic: &SubjectColumnArithmetic,
+        values: Vec<&Arc<ArcTopicDataValue>>,
+    ) -> StdR<Arc<ArcTopicDataValue>> {
+        let mut present = values.into_iter().filter(|value| !value.is_none());
+        match arithmetic {
+            // dimension, all values in group are same
+            SubjectColumnArithmetic::None => Ok(present
+                .next()
+                .cloned()
+                .unwrap_or_else(|| Arc::new(ArcTopicDataValue::None))),
+            SubjectColumnArithmetic::Count => Ok(ArcTopicDataValue::arc_from(BigDecimal::from(
+                present.count() as u64,
+            ))),
+            SubjectColumnArithmetic::DistinctCount => {
+                let distinct: HashSet<String> = present
+                    .filter_map(|value| SubjectJoiner::key_of(value))
+                    .collect();
+                Ok(ArcTopicDataValue::arc_from(BigDecimal::from(
+                    distinct.len() as u64,
+                )))
+            }
+            // none when there is no value, same as sql
+            SubjectColumnArithmetic::Sum => match Self::sum(present)? {
+                (_, 0) => Ok(Arc::new(ArcTopicDataValue::None)),
+                (sum, _) => Ok(ArcTopicDataValue::arc_from(sum)),
+            },
+            SubjectColumnArithmetic::Avg => match Self::sum(present)? {
+                (_, 0) => Ok(Arc::new(ArcTopicDataValue::None)),
+                (sum, count) => Ok(ArcTopicDataValue::arc_from(
+                    (sum / BigDecimal::from(count)).normalized(),
+                )),
+            },
+            SubjectColumnArithmetic::Max => Self::pick(present, |a, b| b.is_less_than(a)),
+            SubjectColumnArithmetic::Min => Self::pick(present, |a, b| a.is_less_than(b)),
+        }
+    }
+
+    fn sum<'a, I>(values: I) -> StdR<(BigDecimal, u64)>
+    where
+        I: Iterator<Item = &'a Arc<ArcTopicDataValue>>,
+    {
+        let mut sum = BigDecimal::zero();
+        let mut count = 0;
+        for value in values {
+            match value.try_to_decimal() {
+                Ok(decimal) => {
+                    sum += decimal.as_ref();
+                    count += 1;
+                }
+                Err(ArcTopicDataValue::Str(str)) if str.is_empty() => {}
+                Err(other) => {
+                    return PipelineKernelErrorCode::SubjectAggregateValueNotNumeric.msg(format!(
+                        "Value of sum or avg must be a decimal, current is [{}].",
+                        other
+                    ));
+                }
+            }
+        }
+        Ok((sum, count))
+    }
+
+    /// keep the candidate when [replace] returns false.
+    fn pick<'a, I, F>(values: I, replace: F) -> StdR<Arc<ArcTopicDataValue>>
+    where
+        I: Iterator<Item = &'a Arc<ArcTopicDataValue>>,
+        F: Fn(&ArcTopicDataValue, &ArcTopicDataValue) -> StdR<bool>,
+    {
+        let mut picked: Option<&Arc<ArcTopicDataValue>> = None;
+        for value in values {
+            picked = match picked {
+                Some(candidate) if !replace(value, candidate)? => Some(candidate),
+                _ => Some(value),
+            };
+        }
+        Ok(picked
+            .cloned()
+            .unwrap_or_else(|| Arc::new(ArcTopicDataValue::None)))
+    }
+}
@@ -0,0 +1,46 @@
+use crate::SubjectDatasetEnvs;
+use elf_base::StdR;
+use elf_model::TopicData;
+use elf_runtime_model_kernel::{ArcParameterJoint, TopicDataProvider, TopicSchema, TopicService};
+use std::sync::Arc;
+
+/// criteria pushed down to data source when reading rows of topic.
+/// filter is a hint, source might ignore it, since rows are filtered again in memory.
+/// source returns at most [limit] rows, limit is one more than the max rows allowed,
+/// so that exceeding can be detected.
+pub struct SubjectSourceCriteria {
+    pub filter: Option<Arc<ArcParameterJoint>>,
+    pub limit: usize,
+}
+
+impl SubjectSourceCriteria {
+    /// no filter, limited by [SubjectDatasetEnvs::max_rows]
+    pub fn all() -> Self {
+        Self {
+            filter: None,
+            limit: SubjectDatasetEnvs::max_rows() + 1,
+        }
+    }
+}
+
+/// provide rows of topic for subject dataset.
+pub trait SubjectDataSource {
+    fn rows(
+        &self,
+        topic: &Arc<TopicSchema>,
+        criteria: &SubjectSourceCriteria,
+    ) -> StdR<Vec<TopicData>>;
+}
+
+/// read rows from topic storage, filter and limit are applied by storage.
+pub struct TopicStorageDataSource;
+
+impl SubjectDataSource for TopicStorageDataSource {
+    fn rows(
+        &self,
+        topic: &Arc<TopicSchema>,
+        criteria: &SubjectSourceCriteria,
+    ) -> StdR<Vec<TopicData>> {
+        TopicService::data()?.find_by_joint(topic, criteria.filter.as_ref(), criteria.limit)
+    }
+}
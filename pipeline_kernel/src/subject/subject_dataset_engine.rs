@@ -0,0 +1,515 @@
+use crate::{
+    ArcTopicDataValue, InMemoryData, PipelineKernelErrorCode, SubjectAggregator, SubjectDataSource,
+    SubjectDatasetPlan, SubjectValueRow, TopicStorageDataSource,
+};
+use elf_base::{ErrorCode, StdR, StringUtils};
+use elf_model::{
+    DataResult, DataResultSetCell, DataResultSetRow, Pageable, Subject, SubjectColumnArithmetic,
+    SubjectDatasetCriteria, SubjectDatasetCriteriaIndicator,
+    SubjectDatasetCriteriaIndicatorArithmetic, SubjectDatasetPage, TenantId,
+};
+use elf_runtime_model_kernel::{
+    SubjectMetaProvider, SubjectService, TopicSchemaProvider, TopicService,
+};
+use std::ops::Deref;
+
+/// query subject dataset.
+/// subject dataset is treated as a view, and criteria is a query on this view:
+/// - conditions filter the rows of dataset,
+/// - indicators select columns of dataset, group by the columns which has no arithmetic,
+///   all columns are selected when there is no indicator,
+/// - paging is applied at last.
+///
+/// only dataset filter is pushed down to data source, and only when subject has one topic.
+/// joins, criteria conditions, aggregation and paging are applied in memory,
+/// therefore rows read from each topic are limited by `SUBJECT_DATASET_MAX_ROWS`,
+/// query fails when exceeded, narrow the dataset by filter in this case.
+pub struct SubjectDatasetEngine;
+
+impl SubjectDatasetEngine {
+    /// subject is found by id or name of criteria, rows are read from topic storage.
+    pub fn query(
+        criteria: SubjectDatasetCriteria,
+        tenant_id: &TenantId,
+    ) -> StdR<SubjectDatasetPage> {
+        let subject = Self::find_subject(&criteria, tenant_id)?;
+        let plan = SubjectDatasetPlan::compile(subject, |topic_id| {
+            TopicService::schema()?.by_id(topic_id, tenant_id)
+        })?;
+        Self::page(&plan, criteria, &TopicStorageDataSource)
+    }
+
+    fn find_subject(criteria: &SubjectDatasetCriteria, tenant_id: &TenantId) -> StdR<Subject> {
+        let subject = if let Some(subject_id) = &criteria.subject_id
+            && subject_id.is_not_blank()
+        {
+            SubjectService::meta()?.find_by_id(subject_id, tenant_id)?
+        } else if let Some(subject_name) = &criteria.subject_name
+            && subject_name.is_not_blank()
+        {
+            SubjectService::meta()?.find_by_name(subject_name, tenant_id)?
+        } else {
+            return PipelineKernelErrorCode::SubjectNotFound
+                .msg("Subject id or name is required for querying subject dataset.");
+        };
+        match subject {
+            Some(subject) => Ok(subject),
+            _ => PipelineKernelErrorCode::SubjectNotFound.msg(format!(
+                "Subject[id={}, name={}] not found.",
+                criteria.subject_id.as_deref().unwrap_or(""),
+                criteria.subject_name.as_deref().unwrap_or("")
+            )),
+        }
+    }
+
+    /// all rows of subject dataset, without paging.
+    pub fn dataset<S: SubjectDataSource>(
+        plan: &SubjectDatasetPlan,
+        source: &S,
+    ) -> StdR<DataResult> {
+        let rows = plan.execute(source)?;
+        Ok(DataResult::new()
+            .columns(
+                plan.columns()
+                    .iter()
+                    .map(|column| column.name().clone())
+                    .collect(),
+            )
+            .data(rows.iter().map(Self::row_of).collect()))
+    }
+
+    pub fn page<S: SubjectDataSource>(
+        plan: &SubjectDatasetPlan,
+        criteria: SubjectDatasetCriteria,
+        source: &S,
+    ) -> StdR<SubjectDatasetPage> {
+        let page_number = Pageable::page_number(&criteria).max(1);
+        let page_size = Pageable::page_size(&criteria).max(1);
+
+        let mut rows = plan.execute(source)?;
+        if let Some(conditions) =
+            plan.compile_conditions(criteria.conditions.unwrap_or_default())?
+        {
+            let mut filtered = vec![];
+            for row in rows {
+                let mut in_memory_data = InMemoryData::new(None, Some(plan.data_of(&row)));
+                if conditions.is_true(&mut in_memory_data)? {
+                    filtered.push(row);
+                }
+            }
+            rows = filtered;
+        }
+
+        let (columns, rows) = match criteria.indicators {
+            Some(indicators) if !indicators.is_empty() => Self::select(plan, indicators, rows)?,
+            _ => (
+                plan.columns()
+                    .iter()
+                    .map(|column| column.name().clone())
+                    .collect(),
+                rows,
+            ),
+        };
+
+        let item_count = rows.len() as u32;
+        let page_count = item_count.div_ceil(page_size);
+        let data = rows
+            .iter()
+            // paging values are given by client, never overflow
+            .skip((page_number as usize - 1).saturating_mul(page_size as usize))
+            .take(page_size as usize)
+            .map(Self::row_of)
+            .collect();
+        Ok(SubjectDatasetPage::new()
+            .columns(columns)
+            .data(data)
+            .page_number(page_number)
+            .page_size(page_size)
+            .item_count(item_count)
+            .page_count(page_count))
+    }
+
+    /// select columns by indicators, and aggregate
+    fn select(
+        plan: &SubjectDatasetPlan,
+        indicators: Vec<SubjectDatasetCriteriaIndicator>,
+        rows: Vec<SubjectValueRow>,
+    ) -> StdR<(Vec<String>, Vec<SubjectValueRow>)> {
+        let mut names = vec![];
+        let mut indexes = vec![];
+        let mut arithmetics = vec![];
+        for indicator in indicators {
+            let column_id = indicator.column_id.unwrap_or_default();
+            let Some(index) = plan.column_index(&column_id) else {
+                return PipelineKernelErrorCode::SubjectColumnNotFound.msg(format!(
+                    "Column[{}] not found in subject[{}].",
+                    column_id,
+                    plan.subject_id()
+                ));
+            };
+            let name = [indicator.alias, indicator.name]
+                .into_iter()
+                .flatten()
+                .find(|name| name.is_not_blank())
+                .unwrap_or_else(|| plan.columns()[index].name().clone());
+            names.push(name);
+            indexes.push(index);
+            arithmetics.push(Self::arithmetic_of(indicator.arithmetic));
+        }
+
+        let selected = rows
+            .into_iter()
+            .map(|row| indexes.iter().map(|index| row[*index].clone()).collect())
+            .collect();
+        let arithmetics: Vec<&SubjectColumnArithmetic> = arithmetics.iter().collect();
+        Ok((names, SubjectAggregator::aggregate(selected, &arithmetics)?))
+    }
+
+    fn arithmetic_of(
+        arithmetic: Option<SubjectDatasetCriteriaIndicatorArithmetic>,
+    ) -> SubjectColumnArithmetic {
+        match arithmetic {
+            Some(SubjectDatasetCriteriaIndicatorArithmetic::DistinctCount) => {
+                SubjectColumnArithmetic::DistinctCount
+            }
+            Some(SubjectDatasetCriteriaIndicatorArithmetic::Count) => {
+                SubjectColumnArithmetic::Count
+            }
+            Some(SubjectDatasetCriteriaIndicatorArithmetic::Sum) => SubjectColumnArithmetic::Sum,
+            Some(SubjectDatasetCriteriaIndicatorArithmetic::Avg) => SubjectColumnArithmetic::Avg,
+            Some(SubjectDatasetCriteriaIndicatorArithmetic::Max) => SubjectColumnArithmetic::Max,
+            Some(SubjectDatasetCriteriaIndicatorArithmetic::Min) => SubjectColumnArithmetic::Min,
+            _ => SubjectColumnArithmetic::None,
+        }
+    }
+
+    fn row_of(row: &SubjectValueRow) -> DataResultSetRow {
+        row.iter().map(|value| Self::cell_of(value)).collect()
+    }
+
+    /// map and vec are serialized to json string
+    pub fn cell_of(value: &ArcTopicDataValue) -> DataResultSetCell {
+        match value {
+            ArcTopicDataValue::None => DataResultSetCell::None,
+            ArcTopicDataValue::Str(str) => DataResultSetCell::Str(str.deref().clone()),
+            ArcTopicDataValue::Num(num) => DataResultSetCell::Num(num.deref().clone()),
+            ArcTopicDataValue::Bool(bool) => DataResultSetCell::Bool(*bool),
+            ArcTopicDataValue::DateTime(datetime) => DataResultSetCell::DateTime(**datetime),
+            ArcTopicDataValue::Date(date) => DataResultSetCell::Date(**date),
+            ArcTopicDataValue::Time(time) => DataResultSetCell::Time(**time),
+            ArcTopicDataValue::Map(_) | ArcTopicDataValue::Vec(_) => {
+                DataResultSetCell::Str(serde_json::to_string(value).unwrap_or_default())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        SubjectDataSource, SubjectDatasetEngine, SubjectDatasetPlan, SubjectSourceCriteria,
+        TestFixtures,
+    };
+    use bigdecimal::BigDecimal;
+    use elf_base::StdR;
+    use elf_model::{
+        AddParameter, ConstantParameter, DataResultSetCell, DataResultSetRow, FactorType,
+        MoreThanExpression, ParameterJoint, ParameterKind, Subject, SubjectColumnArithmetic,
+        SubjectDataset, SubjectDatasetColumn, SubjectDatasetCriteria,
+        SubjectDatasetCriteriaIndicator, SubjectDatasetCriteriaIndicatorArithmetic,
+        SubjectDatasetJoin, SubjectJoinType, TopicData, TopicDataValue, TopicFactorParameter,
+    };
+    use elf_runtime_model_kernel::TopicSchema;
+    use std::collections::HashMap;
+    use std::ops::Deref;
+    use std::sync::Arc;
+
+    struct InMemorySource {
+        rows: HashMap<String, Vec<TopicData>>,
+    }
+
+    impl SubjectDataSource for InMemorySource {
+        fn rows(
+            &self,
+            topic: &Arc<TopicSchema>,
+            criteria: &SubjectSourceCriteria,
+        ) -> StdR<Vec<TopicData>> {
+            Ok(self
+                .rows
+                .get(topic.topic_id().as_ref())
+                .map(|rows| rows.iter().take(criteria.limit).cloned().collect())
+                .unwrap_or_default())
+        }
+    }
+
+    fn topics() -> HashMap<String, Arc<TopicSchema>> {
+        let mut topics = HashMap::new();
+        topics.insert(
+            String::from("orders"),
+            TestFixtures::topic(
+                "orders",
+                vec![
+                    ("o1", "orderNo", FactorType::Text),
+                    ("o2", "customerId", FactorType::Text),
+                    ("o3", "amount", FactorType::Number),
+                ],
+            ),
+        );
+        topics.insert(
+            String::from("customers"),
+            TestFixtures::topic(
+                "customers",
+                vec![
+                    ("c1", "customerId", FactorType::Text),
+                    ("c2", "name", FactorType::Text),
+                ],
+            ),
+        );
+        topics
+    }
+
+    fn source() -> InMemorySource {
+        let order = |order_no: &str, customer_id: &str, amount: i32| {
+            let mut data = HashMap::new();
+            data.insert(
+                String::from("orderNo"),
+                TopicDataValue::Str(order_no.to_string()),
+            );
+            data.insert(
+                String::from("customerId"),
+                TopicDataValue::Str(customer_id.to_string()),
+            );
+            data.insert(
+                String::from("amount"),
+                TopicDataValue::Num(BigDecimal::from(amount)),
+            );
+            data
+        };
+        let customer = |customer_id: &str, name: &str| {
+            let mut data = HashMap::new();
+            data.insert(
+                String::from("customerId"),
+                TopicDataValue::Str(customer_id.to_string()),
+            );
+            data.insert(String::from("name"), TopicDataValue::Str(name.to_string()));
+            data
+        };
+        let mut rows = HashMap::new();
+        rows.insert(
+            String::from("orders"),
+            vec![
+                order("A1", "C1", 10),
+                order("A2", "C1", 30),
+                order("A3", "C2", 5),
+                order("A4", "C9", 7),
+            ],
+        );
+        rows.insert(
+            String::from("customers"),
+            vec![
+                customer("C1", "Alice"),
+                customer("C2", "Bob"),
+                customer("C3", "Carol"),
+            ],
+        );
+        InMemorySource { rows }
+    }
+
+    fn factor(topic_id: &str, factor_id: &str) -> elf_model::Parameter {
+        TopicFactorParameter {
+            kind: Some(ParameterKind::Topic),
+            topic_id: Some(topic_id.to_string()),
+            factor_id: Some(factor_id.to_string()),
+        }
+        .to_parameter()
+    }
+
+    fn column(
+        column_id: &str,
+        parameter: elf_model::Parameter,
+        arithmetic: SubjectColumnArithmetic,
+    ) -> SubjectDatasetColumn {
+        SubjectDatasetColumn::new()
+            .column_id(column_id.to_string())
+            .alias(column_id.to_string())
+            .parameter(parameter)
+            .arithmetic(arithmetic)
+    }
+
+    fn more_than_six(parameter: elf_model::Parameter) -> ParameterJoint {
+        ParameterJoint::and(vec![MoreThanExpression::init()
+            .left(parameter)
+            // plain constant is a path, concat with empty to get literal
+            .right(ConstantParameter::of(String::from("6{}")).to_parameter())
+            .to_condition()])
+    }
+
+    fn plan(r#type: SubjectJoinType, filters: Option<ParameterJoint>) -> SubjectDatasetPlan {
+        let mut columns = vec![
+            column(
+                "customer",
+                factor("customers", "c2"),
+                SubjectColumnArithmetic::None,
+            ),
+            column(
+                "total",
+                factor("orders", "o3"),
+                SubjectColumnArithmetic::Sum,
+            ),
+            column(
+                "orders",
+                factor("orders", "o1"),
+                SubjectColumnArithmetic::Count,
+            ),
+        ];
+        if matches!(r#type, SubjectJoinType::Inner) {
+            columns.push(
+                column(
+                    "plus",
+                    AddParameter::init()
+                        .parameters(vec![factor("s1", "total"), factor("s1", "orders")])
+                        .to_parameter(),
+                    SubjectColumnArithmetic::Sum,
+                )
+                .recalculate(true),
+            );
+        }
+        let mut dataset =
+            SubjectDataset::new()
+                .columns(columns)
+                .joins(vec![SubjectDatasetJoin::new()
+                    .topic_id(String::from("orders"))
+                    .factor_id(String::from("o2"))
+                    .secondary_topic_id(String::from("customers"))
+                    .secondary_factor_id(String::from("c1"))
+                    .r#type(r#type)]);
+        if let Some(filters) = filters {
+            dataset = dataset.filters(filters);
+        }
+        let subject = Subject::new()
+            .subject_id(String::from("s1"))
+            .name(String::from("customer orders"))
+            .dataset(dataset)
+            .tenant_id(TestFixtures::tenant_id());
+        let topics = topics();
+        SubjectDatasetPlan::compile(subject, |topic_id| Ok(topics[topic_id].clone())).unwrap()
+    }
+
+    fn text(row: &DataResultSetRow) -> String {
+        row.iter()
+            .map(|cell| match cell {
+                DataResultSetCell::Str(str) => str.clone(),
+                DataResultSetCell::Num(num) => num.to_plain_string(),
+                DataResultSetCell::None => String::from("-"),
+                other => format!("{:?}", other),
+            })
+            .collect::<Vec<String>>()
+            .join(",")
+    }
+
+    fn texts(rows: &Option<Vec<DataResultSetRow>>) -> Vec<String> {
+        rows.as_ref().unwrap().iter().map(text).collect()
+    }
+
+    #[test]
+    fn test_dataset() {
+        let source = source();
+
+        let result =
+            SubjectDatasetEngine::dataset(&plan(SubjectJoinType::Inner, None), &source).unwrap();
+        assert_eq!(
+            result.columns.unwrap(),
+            vec!["customer", "total", "orders", "plus"]
+        );
+        assert_eq!(texts(&result.data), vec!["Alice,40,2,42", "Bob,5,1,6"]);
+
+        let result = SubjectDatasetEngine::dataset(
+            &plan(
+                SubjectJoinType::Inner,
+                Some(more_than_six(factor("orders", "o3"))),
+            ),
+            &source,
+        )
+        .unwrap();
+        assert_eq!(texts(&result.data), vec!["Alice,40,2,42"]);
+
+        let result =
+            SubjectDatasetEngine::dataset(&plan(SubjectJoinType::Left, None), &source).unwrap();
+        assert_eq!(texts(&result.data), vec!["Alice,40,2", "Bob,5,1", "-,7,1"]);
+
+        let result =
+            SubjectDatasetEngine::dataset(&plan(SubjectJoinType::Right, None), &source).unwrap();
+        assert_eq!(
+            texts(&result.data),
+            vec!["Alice,40,2", "Bob,5,1", "Carol,-,0"]
+        );
+    }
+
+    #[test]
+    fn test_page() {
+        let criteria = SubjectDatasetCriteria::new()
+            .subject_id(String::from("s1"))
+            .indicators(vec![
+                SubjectDatasetCriteriaIndicator::new().column_id(String::from("customer")),
+                SubjectDatasetCriteriaIndicator::new()
+                    .column_id(String::from("total"))
+                    .arithmetic(SubjectDatasetCriteriaIndicatorArithmetic::Max)
+                    .alias(String::from("max")),
+            ])
+            .conditions(vec![more_than_six(factor("s1", "total")).to_condition()])
+            .page_number(2)
+            .page_size(1);
+        let page =
+            SubjectDatasetEngine::page(&plan(SubjectJoinType::Left, None), criteria, &source())
+                .unwrap();
+        assert_eq!(page.columns.clone().unwrap(), vec!["customer", "max"]);
+        assert_eq!(page.item_count, Some(2));
+        assert_eq!(page.page_count, Some(2));
+        assert_eq!(texts(&page.data), vec!["-,7"]);
+    }
+
+    #[test]
+    fn test_page_out_of_range() {
+        let criteria = SubjectDatasetCriteria::new()
+            .subject_id(String::from("s1"))
+            .page_number(u32::MAX)
+            .page_size(u32::MAX);
+        let page =
+            SubjectDatasetEngine::page(&plan(SubjectJoinType::Left, None), criteria, &source())
+                .unwrap();
+        assert_eq!(page.item_count, Some(3));
+        assert_eq!(page.page_count, Some(1));
+        assert!(page.data.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_column_types() {
+        let plan = plan(SubjectJoinType::Inner, None);
+        let type_of = |column_id: &str| {
+            plan.subject_schema()
+                .factor_by_id(&column_id.to_string())
+                .unwrap()
+                .r#type
+                .deref()
+                .clone()
+        };
+        assert_eq!(type_of("customer"), FactorType::Text);
+        assert_eq!(type_of("total"), FactorType::Number);
+        assert_eq!(type_of("orders"), FactorType::Number);
+        assert_eq!(type_of("plus"), FactorType::Number);
+    }
+
+    #[test]
+    fn test_too_large() {
+        let source = source();
+
+        // 4 orders, 3 customers
+        let plan = plan(SubjectJoinType::Inner, None).with_max_rows(4);
+        assert!(SubjectDatasetEngine::dataset(&plan, &source).is_ok());
+        let plan = plan.with_max_rows(3);
+        match SubjectDatasetEngine::dataset(&plan, &source) {
+            Err(error) => assert!(error.to_string().contains("PLKN-00613")),
+            _ => panic!("dataset should exceed the max rows"),
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use elf_base::{EnvConfig, ErrorCode, StdErrCode, VoidR};
+use std::sync::OnceLock;
+
+pub struct SubjectDatasetEnvs {
+    max_rows: usize,
+}
+
+static SUBJECT_DATASET_ENVS: OnceLock<SubjectDatasetEnvs> = OnceLock::new();
+
+impl SubjectDatasetEnvs {
+    const DEFAULT_MAX_ROWS: usize = 100_000;
+
+    fn init_default() -> Self {
+        Self {
+            max_rows: Self::DEFAULT_MAX_ROWS,
+        }
+    }
+
+    /// initialize subject dataset environments by given environment
+    /// TIP call it at system startup
+    pub fn init(envs: &EnvConfig) -> VoidR {
+        let max_rows = match envs.get_usize("SUBJECT_DATASET_MAX_ROWS")? {
+            Some(0) | None => Self::DEFAULT_MAX_ROWS,
+            Some(max_rows) => max_rows,
+        };
+
+        SUBJECT_DATASET_ENVS
+            .set(SubjectDatasetEnvs { max_rows })
+            .or_else(|_| {
+                StdErrCode::EnvInit.msg("Failed to initialize subject dataset environment.")
+            })
+    }
+
+    /// max rows read from each topic of subject dataset
+    pub fn max_rows() -> usize {
+        SUBJECT_DATASET_ENVS
+            .get_or_init(Self::init_default)
+            .max_rows
+    }
+}
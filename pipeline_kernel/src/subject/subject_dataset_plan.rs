@@ -0,0 +1,619 @@
+use crate::{
+    ArcTopicData, ArcTopicDataValue, CompiledParameter, CompiledParameterJoint, DataPath,
+    InMemoryData, LineageNodeKey, LineageRefsFinder, PipelineKernelErrorCode, SubjectAggregator,
+    SubjectDataSource, SubjectJoiner, SubjectPlanJoin, SubjectRowScope, SubjectSourceCriteria,
+    SubjectValueRow,
+};
+use elf_base::{ErrorCode, StdR, StringUtils};
+use elf_model::{
+    Factor, FactorId, FactorType, ParameterCondition, ParameterJoint, Subject,
+    SubjectColumnArithmetic, SubjectDatasetColumn, SubjectDatasetColumnId, SubjectDatasetJoin,
+    SubjectId, SubjectJoinType, TenantId, Topic, TopicId, TopicKind, TopicType,
+};
+use elf_runtime_model_kernel::{
+    ArcComputedParameter, ArcParameter, ArcParameterJoint, TopicSchema,
+};
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::Arc;
+
+pub struct SubjectPlanColumn {
+    pub column_id: SubjectDatasetColumnId,
+    pub alias: Option<String>,
+    pub arithmetic: SubjectColumnArithmetic,
+    pub recalculate: bool,
+    parameter: CompiledParameter,
+    scope: SubjectRowScope,
+}
+
+impl SubjectPlanColumn {
+    /// alias, or column id when alias not declared
+    pub fn name(&self) -> &String {
+        match &self.alias {
+            Some(alias) if alias.is_not_blank() => alias,
+            _ => &self.column_id,
+        }
+    }
+}
+
+/// subject dataset compiled against topics.
+/// subject itself is compiled as a topic also,
+/// each column is a factor which id and name are column id,
+/// so the recalculated columns and criteria conditions can refer to columns
+/// by topic factor parameter (topic id is subject id, factor id is column id),
+/// or by constant parameter with column id or alias (e.g. `amount`).
+/// type of column is deduced from its parameter and arithmetic, text when cannot be deduced.
+///
+/// rows of topics are read by source criteria, in the same order of topics.
+/// dataset filter is pushed down to source when there is only one topic,
+/// and rows of each topic are limited by [SubjectDatasetEnvs::max_rows].
+pub struct SubjectDatasetPlan {
+    subject_id: SubjectId,
+    tenant_id: Arc<TenantId>,
+    topics: Vec<Arc<TopicSchema>>,
+    source_criteria: Vec<SubjectSourceCriteria>,
+    joins: Vec<SubjectPlanJoin>,
+    filter: Option<(CompiledParameterJoint, SubjectRowScope)>,
+    columns: Vec<SubjectPlanColumn>,
+    subject_schema: Arc<TopicSchema>,
+    topic_schemas: HashMap<Arc<TopicId>, Arc<TopicSchema>>,
+}
+
+/// join definition before ordered
+struct SubjectJoinDef {
+    topic_id: TopicId,
+    factor_id: FactorId,
+    secondary_topic_id: TopicId,
+    secondary_factor_id: FactorId,
+    r#type: SubjectJoinType,
+}
+
+/// ordered topic ids, and joins with flag of additional condition
+type SubjectOrderedJoins = (Vec<TopicId>, Vec<(SubjectJoinDef, bool)>);
+
+struct SubjectColumnDef {
+    column_id: SubjectDatasetColumnId,
+    alias: Option<String>,
+    arithmetic: SubjectColumnArithmetic,
+    recalculate: bool,
+    parameter: Arc<ArcParameter>,
+}
+
+impl SubjectDatasetPlan {
+    pub fn compile<F>(subject: Subject, find_topic: F) -> StdR<Self>
+    where
+        F: Fn(&TopicId) -> StdR<Arc<TopicSchema>>,
+    {
+        let Some(subject_id) = subject.subject_id.filter(|id| id.is_not_blank()) else {
+            return PipelineKernelErrorCode::SubjectIdMissed.msg("Subject must have an id.");
+        };
+        let Some(tenant_id) = subject.tenant_id.filter(|id| id.is_not_blank()) else {
+            return PipelineKernelErrorCode::SubjectTenantIdMissed
+                .msg(format!("Subject[{}] must have a tenant id.", subject_id));
+        };
+        let tenant_id = Arc::new(tenant_id);
+        let Some(dataset) = subject.dataset else {
+            return PipelineKernelErrorCode::SubjectDatasetMissed
+                .msg(format!("Subject[{}] has no dataset.", subject_id));
+        };
+        let column_defs = Self::column_defs(&subject_id, dataset.columns)?;
+        let filter = match dataset.filters {
+            Some(filters) => Some(ArcParameterJoint::new(filters)?),
+            _ => None,
+        };
+
+        // topic factor parameters are resolved without topics
+        let no_topics = HashMap::new();
+        let finder = LineageRefsFinder::new(&no_topics, None);
+        let mut column_refs = vec![];
+        for column in column_defs.iter() {
+            if column.recalculate {
+                column_refs.push(vec![]);
+            } else {
+                column_refs.push(finder.refs_of_parameter(&column.parameter)?.factors);
+            }
+        }
+        let mut filter_refs = vec![];
+        if let Some(filter) = &filter {
+            finder.refs_of_joint(filter, &mut filter_refs)?;
+        }
+
+        let referred_topic_ids = column_refs
+            .iter()
+            .flatten()
+            .chain(filter_refs.iter())
+            .filter_map(|key| match key {
+                LineageNodeKey::Factor(topic_id, _) => Some(topic_id.clone()),
+                _ => None,
+            })
+            .fold(vec![], |mut topic_ids: Vec<TopicId>, topic_id| {
+                if !topic_ids.contains(&topic_id) {
+                    topic_ids.push(topic_id);
+                }
+                topic_ids
+            });
+        let join_defs = Self::join_defs(dataset.joins)?;
+        let (topic_ids, ordered_joins) = if join_defs.is_empty() {
+            match referred_topic_ids.len() {
+                0 => {
+                    return PipelineKernelErrorCode::SubjectTopicNotDetermined
+                        .msg(format!("Subject[{}] refers to no topic.", subject_id));
+                }
+                1 => (referred_topic_ids, vec![]),
+                _ => {
+                    return PipelineKernelErrorCode::SubjectTopicNotJoined.msg(format!(
+                        "Subject[{}] refers to multiple topics, but no join declared.",
+                        subject_id
+                    ));
+                }
+            }
+        } else {
+            Self::order_joins(join_defs)?
+        };
+
+        let mut topics = vec![];
+        let mut topic_schemas = HashMap::new();
+        for topic_id in topic_ids.iter() {
+            let topic = find_topic(topic_id)?;
+            topic_schemas.insert(Arc::new(topic_id.clone()), topic.clone());
+            topics.push(topic);
+        }
+        let index_of = |topic_id: &TopicId| topic_ids.iter().position(|id| id == topic_id);
+        let mut joins = vec![];
+        for (join, also) in ordered_joins {
+            // topics are collected from joins, index always exists
+            let primary = index_of(&join.topic_id).unwrap_or_default();
+            let secondary = index_of(&join.secondary_topic_id).unwrap_or_default();
+            joins.push(SubjectPlanJoin {
+                primary,
+                primary_path: Self::factor_path(&topics[primary], &join.factor_id)?,
+                secondary,
+                secondary_path: Self::factor_path(&topics[secondary], &join.secondary_factor_id)?,
+                r#type: join.r#type,
+                also,
+            });
+        }
+
+        let source_criteria = topics
+            .iter()
+            .map(|_| SubjectSourceCriteria {
+                filter: match &filter {
+                    Some(filter) if topics.len() == 1 => Some(filter.clone()),
+                    _ => None,
+                },
+                ..SubjectSourceCriteria::all()
+            })
+            .collect();
+        let filter = match filter {
+            Some(filter) => Some((
+                CompiledParameterJoint::compile(&filter, &mut topic_schemas, &tenant_id)?,
+                SubjectRowScope::of(&filter_refs, &topics)?,
+            )),
+            _ => None,
+        };
+
+        // columns of topics
+        let mut compiled = vec![];
+        for (column, refs) in column_defs.iter().zip(column_refs.iter()) {
+            if column.recalculate {
+                compiled.push(None);
+            } else {
+                compiled.push(Some((
+                    CompiledParameter::compile(&column.parameter, &mut topic_schemas, &tenant_id)?,
+                    SubjectRowScope::of(refs, &topics)?,
+                )));
+            }
+        }
+
+        // columns of subject
+        let subject_schema = Self::schema_of_subject(
+            &subject_id,
+            subject.name.unwrap_or_else(|| subject_id.clone()),
+            &tenant_id,
+            &column_defs,
+            &topic_schemas,
+        )?;
+        topic_schemas.insert(Arc::new(subject_id.clone()), subject_schema.clone());
+        let mut columns = vec![];
+        for (column, compiled) in column_defs.into_iter().zip(compiled) {
+            let (parameter, scope) = match compiled {
+                Some(compiled) => compiled,
+                _ => (
+                    CompiledParameter::compile(&column.parameter, &mut topic_schemas, &tenant_id)?,
+                    SubjectRowScope::All,
+                ),
+            };
+            columns.push(SubjectPlanColumn {
+                column_id: column.column_id,
+                alias: column.alias,
+                arithmetic: column.arithmetic,
+                recalculate: column.recalculate,
+                parameter,
+                scope,
+            });
+        }
+
+        Ok(Self {
+            subject_id,
+            tenant_id,
+            topics,
+            source_criteria,
+            joins,
+            filter,
+            columns,
+            subject_schema,
+            topic_schemas,
+        })
+    }
+
+    fn column_defs(
+        subject_id: &SubjectId,
+        columns: Option<Vec<SubjectDatasetColumn>>,
+    ) -> StdR<Vec<SubjectColumnDef>> {
+        let columns = columns.unwrap_or_default();
+        if columns.is_empty() {
+            return PipelineKernelErrorCode::SubjectDatasetMissed
+                .msg(format!("Subject[{}] has no column.", subject_id));
+        }
+
+        let mut defs = vec![];
+        for column in columns {
+            let Some(column_id) = column.column_id.filter(|id| id.is_not_blank()) else {
+                return PipelineKernelErrorCode::SubjectColumnIdMissed.msg(format!(
+                    "Column of subject[{}] must have an id.",
+                    subject_id
+                ));
+            };
+            let Some(parameter) = column.parameter else {
+                return PipelineKernelErrorCode::SubjectColumnParameterMissed.msg(format!(
+                    "Column[{}] of subject[{}] must have a parameter.",
+                    column_id, subject_id
+                ));
+            };
+            let recalculate = column.recalculate.unwrap_or(false);
+            defs.push(SubjectColumnDef {
+                column_id,
+                alias: column.alias,
+                // arithmetic of recalculated column is ignored
+                arithmetic: match column.arithmetic {
+                    Some(arithmetic) if !recalculate => arithmetic,
+                    _ => SubjectColumnArithmetic::None,
+                },
+                recalculate,
+                parameter: ArcParameter::new(parameter)?,
+            });
+        }
+
+        // recalculated column cannot refer to another recalculated column
+        let no_topics = HashMap::new();
+        let finder = LineageRefsFinder::new(&no_topics, None);
+        for column in defs.iter().filter(|column| column.recalculate) {
+            for key in finder.refs_of_parameter(&column.parameter)?.factors {
+                if let LineageNodeKey::Factor(topic_id, factor_id) = key
+                    && &topic_id == subject_id
+                    && defs
+                        .iter()
+                        .any(|def| def.recalculate && def.column_id == factor_id)
+                {
+                    return PipelineKernelErrorCode::SubjectRecalculateColumnRefersRecalculate
+                        .msg(format!(
+                            "Recalculated column[{}] of subject[{}] refers to another recalculated column[{}].",
+                            column.column_id, subject_id, factor_id
+                        ));
+                }
+            }
+        }
+
+        Ok(defs)
+    }
+
+    fn join_defs(joins: Option<Vec<SubjectDatasetJoin>>) -> StdR<Vec<SubjectJoinDef>> {
+        let mut defs = vec![];
+        for join in joins.unwrap_or_default() {
+            match (
+                join.topic_id,
+                join.factor_id,
+                join.secondary_topic_id,
+                join.secondary_factor_id,
+            ) {
+                (
+                    Some(topic_id),
+                    Some(factor_id),
+                    Some(secondary_topic_id),
+                    Some(secondary_factor_id),
+                ) => defs.push(SubjectJoinDef {
+                    topic_id,
+                    factor_id,
+                    secondary_topic_id,
+                    secondary_factor_id,
+                    r#type: join.r#type.unwrap_or(SubjectJoinType::Inner),
+                }),
+                _ => {
+                    return PipelineKernelErrorCode::SubjectJoinIncomplete.msg(
+                        "Join must have topic, factor, secondary topic and secondary factor.",
+                    );
+                }
+            }
+        }
+        Ok(defs)
+    }
+
+    /// make sure the primary topic of join is joined by former joins.
+    /// join is swapped when secondary topic is joined but primary is not,
+    /// and join is an additional condition when both are joined.
+    fn order_joins(mut joins: Vec<SubjectJoinDef>) -> StdR<SubjectOrderedJoins> {
+        let mut topic_ids = vec![joins[0].topic_id.clone()];
+        let mut ordered = vec![];
+        while !joins.is_empty() {
+            let Some(position) = joins.iter().position(|join| {
+                topic_ids.contains(&join.topic_id) || topic_ids.contains(&join.secondary_topic_id)
+            }) else {
+                return PipelineKernelErrorCode::SubjectJoinIncomplete.msg(format!(
+                    "Join between topic[{}] and topic[{}] is not connected to other joins.",
+                    joins[0].topic_id, joins[0].secondary_topic_id
+                ));
+            };
+            let join = joins.remove(position);
+            let join = if topic_ids.contains(&join.topic_id) {
+                join
+            } else {
+                SubjectJoinDef {
+                    topic_id: join.secondary_topic_id,
+                    factor_id: join.secondary_factor_id,
+                    secondary_topic_id: join.topic_id,
+                    secondary_factor_id: join.factor_id,
+                    r#type: match join.r#type {
+                        SubjectJoinType::Left => SubjectJoinType::Right,
+                        SubjectJoinType::Right => SubjectJoinType::Left,
+                        SubjectJoinType::Inner => SubjectJoinType::Inner,
+                    },
+                }
+            };
+            let also = topic_ids.contains(&join.secondary_topic_id);
+            if !also {
+                topic_ids.push(join.secondary_topic_id.clone());
+            }
+            ordered.push((join, also));
+        }
+        Ok((topic_ids, ordered))
+    }
+
+    fn factor_path(topic: &Arc<TopicSchema>, factor_id: &FactorId) -> StdR<DataPath> {
+        match topic.factor_by_id(factor_id) {
+            Some(factor) => DataPath::from_factor(factor, topic),
+            _ => PipelineKernelErrorCode::FactorNotFound.msg(format!(
+                "Factor[{}] not found in topic[{}].",
+                factor_id,
+                topic.topic_id()
+            )),
+        }
+    }
+
+    fn schema_of_subject(
+        subject_id: &SubjectId,
+        name: String,
+        tenant_id: &TenantId,
+        columns: &[SubjectColumnDef],
+        topic_schemas: &HashMap<Arc<TopicId>, Arc<TopicSchema>>,
+    ) -> StdR<Arc<TopicSchema>> {
+        // recalculated columns refer to columns which are not recalculated,
+        // so type them after others
+        let mut types = HashMap::new();
+        for column in columns.iter().filter(|column| !column.recalculate) {
+            let r#type = Self::type_of_column(column, subject_id, topic_schemas, &types);
+            types.insert(column.column_id.clone(), r#type);
+        }
+        for column in columns.iter().filter(|column| column.recalculate) {
+            let r#type = Self::type_of_column(column, subject_id, topic_schemas, &types);
+            types.insert(column.column_id.clone(), r#type);
+        }
+
+        let topic = Topic::new()
+            .topic_id(subject_id.clone())
+            .name(name)
+            .r#type(TopicType::Distinct)
+            .kind(TopicKind::Business)
+            .factors(
+                columns
+                    .iter()
+                    .map(|column| {
+                        Factor::new()
+                            .factor_id(column.column_id.clone())
+                            .name(column.column_id.clone())
+                            .r#type(types.remove(&column.column_id).unwrap_or(FactorType::Text))
+                    })
+                    .collect(),
+            )
+            .tenant_id(tenant_id.clone());
+        Ok(Arc::new(TopicSchema::new(topic)?))
+    }
+
+    fn type_of_column(
+        column: &SubjectColumnDef,
+        subject_id: &SubjectId,
+        topic_schemas: &HashMap<Arc<TopicId>, Arc<TopicSchema>>,
+        column_types: &HashMap<SubjectDatasetColumnId, FactorType>,
+    ) -> FactorType {
+        match column.arithmetic {
+            SubjectColumnArithmetic::Count
+            | SubjectColumnArithmetic::DistinctCount
+            | SubjectColumnArithmetic::Sum
+            | SubjectColumnArithmetic::Avg => FactorType::Number,
+            SubjectColumnArithmetic::None
+            | SubjectColumnArithmetic::Max
+            | SubjectColumnArithmetic::Min => {
+                Self::type_of_parameter(&column.parameter, subject_id, topic_schemas, column_types)
+            }
+        }
+    }
+
+    /// type of factor when parameter is topic factor, type of column when it refers to subject,
+    /// number for math operations, date part for date related operations,
+    /// type of routes of case then when they are same. otherwise text.
+    fn type_of_parameter(
+        parameter: &ArcParameter,
+        subject_id: &SubjectId,
+        topic_schemas: &HashMap<Arc<TopicId>, Arc<TopicSchema>>,
+        column_types: &HashMap<SubjectDatasetColumnId, FactorType>,
+    ) -> FactorType {
+        match parameter {
+            ArcParameter::Topic(parameter) => if parameter.topic_id.deref() == subject_id {
+                column_types.get(parameter.factor_id.deref()).cloned()
+            } else {
+                topic_schemas
+                    .get(&parameter.topic_id)
+                    .and_then(|topic| topic.factor_by_id(&parameter.factor_id))
+                    .map(|factor| factor.r#type.deref().clone())
+            }
+            .unwrap_or(FactorType::Text),
+            ArcParameter::Constant(_) => FactorType::Text,
+            ArcParameter::Computed(computed) => match computed.deref() {
+                ArcComputedParameter::Add(_)
+                | ArcComputedParameter::Subtract(_)
+                | ArcComputedParameter::Multiply(_)
+                | ArcComputedParameter::Divide(_)
+                | ArcComputedParameter::Modulus(_) => FactorType::Number,
+                ArcComputedParameter::YearOf(_) => FactorType::Year,
+                ArcComputedParameter::HalfYearOf(_) => FactorType::HalfYear,
+                ArcComputedParameter::QuarterOf(_) => FactorType::Quarter,
+                ArcComputedParameter::MonthOf(_) => FactorType::Month,
+                ArcComputedParameter::WeekOfYear(_) => FactorType::WeekOfYear,
+                ArcComputedParameter::WeekOfMonth(_) => FactorType::WeekOfMonth,
+                ArcComputedParameter::DayOfMonth(_) => FactorType::DayOfMonth,
+                ArcComputedParameter::DayOfWeek(_) => FactorType::DayOfWeek,
+                ArcComputedParameter::CaseThen(case_then) => {
+                    let mut types = case_then.parameters.iter().map(|route| {
+                        Self::type_of_parameter(
+                            &route.parameter,
+                            subject_id,
+                            topic_schemas,
+                            column_types,
+                        )
+                    });
+                    match types.next() {
+                        Some(first) if types.all(|r#type| r#type == first) => first,
+                        _ => FactorType::Text,
+                    }
+                }
+                ArcComputedParameter::None(_) => FactorType::Text,
+            },
+        }
+    }
+}
+
+impl SubjectDatasetPlan {
+    pub fn subject_id(&self) -> &SubjectId {
+        &self.subject_id
+    }
+
+    pub fn topics(&self) -> &Vec<Arc<TopicSchema>> {
+        &self.topics
+    }
+
+    /// change the max rows read from each topic, default is [SubjectDatasetEnvs::max_rows].
+    pub fn with_max_rows(mut self, max_rows: usize) -> Self {
+        for criteria in self.source_criteria.iter_mut() {
+            criteria.limit = max_rows + 1;
+        }
+        self
+    }
+
+    pub fn columns(&self) -> &Vec<SubjectPlanColumn> {
+        &self.columns
+    }
+
+    /// schema of subject itself, factors are columns
+    pub fn subject_schema(&self) -> &Arc<TopicSchema> {
+        &self.subject_schema
+    }
+
+    /// find column by id, or by alias
+    pub fn column_index(&self, column_id: &String) -> Option<usize> {
+        self.columns
+            .iter()
+            .position(|column| &column.column_id == column_id)
+            .or_else(|| {
+                self.columns
+                    .iter()
+                    .position(|column| column.alias.as_ref() == Some(column_id))
+            })
+    }
+
+    /// compile criteria conditions against subject,
+    /// returns none when there is no condition.
+    pub fn compile_conditions(
+        &self,
+        conditions: Vec<ParameterCondition>,
+    ) -> StdR<Option<CompiledParameterJoint>> {
+        if conditions.is_empty() {
+            return Ok(None);
+        }
+        let joint = ArcParameterJoint::new(ParameterJoint::and(conditions))?;
+        let mut topic_schemas = self.topic_schemas.clone();
+        Ok(Some(CompiledParameterJoint::compile(
+            &joint,
+            &mut topic_schemas,
+            &self.tenant_id,
+        )?))
+    }
+
+    /// row of subject as topic data, value can be retrieved by column id or alias.
+    pub fn data_of(&self, row: &SubjectValueRow) -> ArcTopicData {
+        let mut data = HashMap::with_capacity(self.columns.len() * 2);
+        for (column, value) in self.columns.iter().zip(row.iter()) {
+            if let Some(alias) = &column.alias
+                && alias.is_not_blank()
+            {
+                data.insert(alias.clone(), value.clone());
+            }
+            data.insert(column.column_id.clone(), value.clone());
+        }
+        Arc::new(data)
+    }
+
+    /// join, filter, evaluate columns, aggregate, and evaluate recalculated columns.
+    pub fn execute<S: SubjectDataSource>(&self, source: &S) -> StdR<Vec<SubjectValueRow>> {
+        let joined = SubjectJoiner::join(&self.topics, &self.joins, source, &self.source_criteria)?;
+
+        let mut rows = vec![];
+        for row in joined {
+            if let Some((filter, scope)) = &self.filter {
+                let mut in_memory_data = InMemoryData::new(None, Some(scope.data_of(&row)));
+                if !filter.is_true(&mut in_memory_data)? {
+                    continue;
+                }
+            }
+            let mut values = vec![];
+            for column in self.columns.iter() {
+                if column.recalculate {
+                    values.push(Arc::new(ArcTopicDataValue::None));
+                } else {
+                    let mut in_memory_data =
+                        InMemoryData::new(None, Some(column.scope.data_of(&row)));
+                    values.push(column.parameter.value_from(&mut in_memory_data)?);
+                }
+            }
+            rows.push(values);
+        }
+
+        let arithmetics: Vec<&SubjectColumnArithmetic> = self
+            .columns
+            .iter()
+            .map(|column| &column.arithmetic)
+            .collect();
+        let mut rows = SubjectAggregator::aggregate(rows, &arithmetics)?;
+
+        if self.columns.iter().any(|column| column.recalculate) {
+            for row in rows.iter_mut() {
+                let data = self.data_of(row);
+                for (index, column) in self.columns.iter().enumerate() {
+                    if column.recalculate {
+                        let mut in_memory_data = InMemoryData::new(None, Some(data.clone()));
+                        row[index] = column.parameter.value_from(&mut in_memory_data)?;
+                    }
+                }
+            }
+        }
+        Ok(rows)
+    }
+}
@@ -0,0 +1,171 @@
+use crate::{
+    ArcTopicData, ArcTopicDataBuilder, ArcTopicDataValue, DataPath, InMemoryData,
+    PipelineKernelErrorCode, SubjectDataSource, SubjectJoinedRow, SubjectSourceCriteria,
+};
+use elf_base::{ErrorCode, NumericUtils, StdR};
+use elf_model::SubjectJoinType;
+use elf_runtime_model_kernel::TopicSchema;
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// join between two topics of subject, by equality of two factors.
+/// primary topic is always joined before secondary topic, except [also] is true,
+/// which means both are joined by former joins, and this join is an additional condition.
+pub struct SubjectPlanJoin {
+    pub primary: usize,
+    pub primary_path: DataPath,
+    pub secondary: usize,
+    pub secondary_path: DataPath,
+    pub r#type: SubjectJoinType,
+    pub also: bool,
+}
+
+/// join rows of topics in memory.
+/// - inner: keep matched only,
+/// - left: keep all rows of joined topics, secondary is none when not matched,
+/// - right: keep all rows of secondary topic, joined topics are none when not matched,
+/// - additional condition: drop the rows which both sides exist but not equal.
+///
+/// none value never matches.
+pub struct SubjectJoiner;
+
+impl SubjectJoiner {
+    /// criteria of source are in the same order of topics.
+    pub fn join<S: SubjectDataSource>(
+        topics: &[Arc<TopicSchema>],
+        joins: &[SubjectPlanJoin],
+        source: &S,
+        criteria: &[SubjectSourceCriteria],
+    ) -> StdR<Vec<SubjectJoinedRow>> {
+        let Some(first) = topics.first() else {
+            return Ok(vec![]);
+        };
+        let mut rows: Vec<SubjectJoinedRow> = Self::rows_of(first, source, &criteria[0])?
+            .into_iter()
+            .map(|data| {
+                let mut row = vec![None; topics.len()];
+                row[0] = Some(data);
+                row
+            })
+            .collect();
+
+        for join in joins {
+            rows = if join.also {
+                Self::filter(rows, join)?
+            } else {
+                let secondary_rows =
+                    Self::rows_of(&topics[join.secondary], source, &criteria[join.secondary])?;
+                Self::join_one(rows, secondary_rows, join, topics.len())?
+            };
+        }
+        Ok(rows)
+    }
+
+    /// rows are joined in memory, fails when rows exceed the limit of criteria.
+    pub fn rows_of<S: SubjectDataSource>(
+        topic: &Arc<TopicSchema>,
+        source: &S,
+        criteria: &SubjectSourceCriteria,
+    ) -> StdR<Vec<ArcTopicData>> {
+        let rows = source.rows(topic, criteria)?;
+        if rows.len() >= criteria.limit {
+            return PipelineKernelErrorCode::SubjectDatasetTooLarge.msg(format!(
+                "Rows of topic[{}] exceed the max rows[{}] of subject dataset, narrow it by dataset filter.",
+                topic.topic_id(),
+                criteria.limit - 1
+            ));
+        }
+        Ok(rows.into_iter().map(ArcTopicData::build).collect())
+    }
+
+    fn value_of(data: &Option<ArcTopicData>, path: &DataPath) -> StdR<Option<String>> {
+        match data {
+            Some(data) => {
+                let value = InMemoryData::new(None, Some(data.clone())).value_of(path)?;
+                Ok(Self::key_of(&value))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// numeric string and number are treated as same.
+    pub fn key_of(value: &ArcTopicDataValue) -> Option<String> {
+        match value {
+            ArcTopicDataValue::None => None,
+            ArcTopicDataValue::Num(num) => Some(num.normalized().to_string()),
+            ArcTopicDataValue::Str(str) => match str.is_numeric() {
+                true => str
+                    .to_decimal()
+                    .ok()
+                    .map(|num| num.normalized().to_string()),
+                false => Some(str.deref().clone()),
+            },
+            other => Some(other.to_string()),
+        }
+    }
+
+    fn join_one(
+        rows: Vec<SubjectJoinedRow>,
+        secondary_rows: Vec<ArcTopicData>,
+        join: &SubjectPlanJoin,
+        width: usize,
+    ) -> StdR<Vec<SubjectJoinedRow>> {
+        let mut index: HashMap<String, Vec<usize>> = HashMap::new();
+        for (row_index, data) in secondary_rows.iter().enumerate() {
+            if let Some(key) = Self::value_of(&Some(data.clone()), &join.secondary_path)? {
+                index.entry(key).or_default().push(row_index);
+            }
+        }
+
+        let mut matched = vec![false; secondary_rows.len()];
+        let mut joined = vec![];
+        for row in rows {
+            let key = Self::value_of(&row[join.primary], &join.primary_path)?;
+            match key.and_then(|key| index.get(&key)) {
+                Some(row_indexes) => {
+                    for row_index in row_indexes {
+                        matched[*row_index] = true;
+                        let mut joined_row = row.clone();
+                        joined_row[join.secondary] = Some(secondary_rows[*row_index].clone());
+                        joined.push(joined_row);
+                    }
+                }
+                _ => {
+                    if matches!(join.r#type, SubjectJoinType::Left) {
+                        joined.push(row);
+                    }
+                }
+            }
+        }
+
+        if matches!(join.r#type, SubjectJoinType::Right) {
+            for (row_index, data) in secondary_rows.into_iter().enumerate() {
+                if !matched[row_index] {
+                    let mut row = vec![None; width];
+                    row[join.secondary] = Some(data);
+                    joined.push(row);
+                }
+            }
+        }
+        Ok(joined)
+    }
+
+    fn filter(rows: Vec<SubjectJoinedRow>, join: &SubjectPlanJoin) -> StdR<Vec<SubjectJoinedRow>> {
+        let mut filtered = vec![];
+        for row in rows {
+            let keep = match (&row[join.primary], &row[join.secondary]) {
+                (Some(_), Some(_)) => {
+                    let primary = Self::value_of(&row[join.primary], &join.primary_path)?;
+                    let secondary = Self::value_of(&row[join.secondary], &join.secondary_path)?;
+                    primary.is_some() && primary == secondary
+                }
+                _ => true,
+            };
+            if keep {
+                filtered.push(row);
+            }
+        }
+        Ok(filtered)
+    }
+}
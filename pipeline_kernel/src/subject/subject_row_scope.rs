@@ -0,0 +1,94 @@
+use crate::{ArcTopicData, LineageNodeKey, PipelineKernelErrorCode};
+use elf_base::{ErrorCode, StdR};
+use elf_model::TopicId;
+use elf_runtime_model_kernel::TopicSchema;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// joined row of subject, data of each topic is in the same order of topics in plan.
+/// data is none when topic is not matched on outer join.
+pub type SubjectJoinedRow = Vec<Option<ArcTopicData>>;
+
+/// parameters are compiled against topic, which always read from current data by factor name.
+/// so the data of topics in joined row is merged into one before evaluating,
+/// only the referred factors are merged, to avoid the same name factors of different topics.
+pub enum SubjectRowScope {
+    /// no factor referred, all factors of all topics are merged, latter topic overrides former.
+    All,
+    /// index of topic, and name of top level factor
+    Factors(Vec<(usize, String)>),
+}
+
+impl SubjectRowScope {
+    pub fn of(refs: &Vec<LineageNodeKey>, topics: &[Arc<TopicSchema>]) -> StdR<Self> {
+        let index_of: HashMap<&TopicId, usize> = topics
+            .iter()
+            .enumerate()
+            .map(|(index, topic)| (topic.topic_id().as_ref(), index))
+            .collect();
+
+        let mut factors: Vec<(usize, String)> = vec![];
+        for key in refs {
+            let LineageNodeKey::Factor(topic_id, factor_id) = key else {
+                continue;
+            };
+            let Some(index) = index_of.get(topic_id) else {
+                return PipelineKernelErrorCode::SubjectTopicNotJoined.msg(format!(
+                    "Topic[{}] is referred, but not joined in subject.",
+                    topic_id
+                ));
+            };
+            let Some(factor) = topics[*index].factor_by_id(factor_id) else {
+                return PipelineKernelErrorCode::FactorNotFound.msg(format!(
+                    "Factor[{}] not found in topic[{}].",
+                    factor_id, topic_id
+                ));
+            };
+            let name = match factor.name.split_once('.') {
+                Some((name, _)) => name.to_string(),
+                _ => factor.name.to_string(),
+            };
+            match factors.iter().find(|(_, existing)| existing == &name) {
+                Some((existing_index, _)) if existing_index != index => {
+                    return PipelineKernelErrorCode::SubjectFactorNameConflict.msg(format!(
+                        "Factor[{}] is referred from both topic[{}] and topic[{}] in one parameter.",
+                        name,
+                        topics[*existing_index].topic_id(),
+                        topic_id
+                    ));
+                }
+                Some(_) => {}
+                _ => factors.push((*index, name)),
+            }
+        }
+
+        if factors.is_empty() {
+            Ok(Self::All)
+        } else {
+            Ok(Self::Factors(factors))
+        }
+    }
+
+    pub fn data_of(&self, row: &SubjectJoinedRow) -> ArcTopicData {
+        match self {
+            Self::All => {
+                let mut data = HashMap::new();
+                for topic_data in row.iter().flatten() {
+                    data.extend(topic_data.iter().map(|(k, v)| (k.clone(), v.clone())));
+                }
+                Arc::new(data)
+            }
+            Self::Factors(factors) => {
+                let mut data = HashMap::with_capacity(factors.len());
+                for (index, name) in factors {
+                    if let Some(Some(topic_data)) = row.get(*index)
+                        && let Some(value) = topic_data.get(name)
+                    {
+                        data.insert(name.clone(), value.clone());
+                    }
+                }
+                Arc::new(data)
+            }
+        }
+    }
+}
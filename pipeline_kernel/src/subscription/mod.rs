@@ -0,0 +1,15 @@
+mod notification_sender;
+mod notification_senders;
+mod smtp_notification_sender;
+mod subscription_content;
+mod subscription_dispatcher;
+mod subscription_resources;
+mod webhook_notification_sender;
+
+pub use notification_sender::*;
+pub use notification_senders::*;
+pub use smtp_notification_sender::*;
+pub use subscription_content::*;
+pub use subscription_dispatcher::*;
+pub use subscription_resources::*;
+pub use webhook_notification_sender::*;
@@ -0,0 +1,64 @@
+use crate::PipelineKernelErrorCode;
+use elf_base::{ErrorCode, StdR, StringUtils, VoidR};
+use elf_model::NotificationDefinition;
+use std::collections::HashMap;
+
+/// message to send, rendered from content of subscription event.
+pub struct NotificationMessage {
+    pub subject: String,
+    pub body: String,
+    /// body is html when true, otherwise plain text
+    pub html: bool,
+}
+
+/// params of notification definition, blank values are ignored.
+pub struct NotificationParams {
+    r#type: String,
+    params: HashMap<String, String>,
+}
+
+impl NotificationParams {
+    pub fn of(notification: &NotificationDefinition) -> Self {
+        let mut params = HashMap::new();
+        for param in notification.params.iter().flatten() {
+            if let (Some(name), Some(value)) = (&param.name, &param.value)
+                && value.is_not_blank()
+            {
+                params.insert(name.clone(), value.clone());
+            }
+        }
+        Self {
+            r#type: notification
+                .r#type
+                .as_ref()
+                .map(|t| t.to_string())
+                .unwrap_or_default(),
+            params,
+        }
+    }
+
+    /// replace or add param, for the ones decided at runtime, such as receivers
+    pub fn with(mut self, name: &str, value: String) -> Self {
+        self.params.insert(name.to_string(), value);
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.params.get(name).map(|value| value.as_str())
+    }
+
+    pub fn required(&self, name: &str) -> StdR<&str> {
+        match self.get(name) {
+            Some(value) => Ok(value),
+            _ => PipelineKernelErrorCode::NotificationParamMissed.msg(format!(
+                "Param[{}] of notification[type={}] is missed.",
+                name, self.r#type
+            )),
+        }
+    }
+}
+
+/// send message through one kind of [elf_model::NotificationType].
+pub trait NotificationSender {
+    fn send(&self, params: &NotificationParams, message: &NotificationMessage) -> VoidR;
+}
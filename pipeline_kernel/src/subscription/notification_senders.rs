@@ -0,0 +1,53 @@
+use crate::{NotificationSender, SmtpNotificationSender, WebhookNotificationSender};
+use elf_model::NotificationType;
+
+/// sender of each [NotificationType], replace them to use other implementations.
+pub struct NotificationSenders {
+    email: Box<dyn NotificationSender>,
+    web_url: Box<dyn NotificationSender>,
+    slack: Box<dyn NotificationSender>,
+    feishu: Box<dyn NotificationSender>,
+}
+
+impl NotificationSenders {
+    pub fn email(mut self, sender: Box<dyn NotificationSender>) -> Self {
+        self.email = sender;
+        self
+    }
+
+    pub fn web_url(mut self, sender: Box<dyn NotificationSender>) -> Self {
+        self.web_url = sender;
+        self
+    }
+
+    pub fn slack(mut self, sender: Box<dyn NotificationSender>) -> Self {
+        self.slack = sender;
+        self
+    }
+
+    pub fn feishu(mut self, sender: Box<dyn NotificationSender>) -> Self {
+        self.feishu = sender;
+        self
+    }
+
+    pub fn sender_of(&self, r#type: &NotificationType) -> &dyn NotificationSender {
+        match r#type {
+            NotificationType::Email => self.email.as_ref(),
+            NotificationType::WebUrl => self.web_url.as_ref(),
+            NotificationType::Slack => self.slack.as_ref(),
+            NotificationType::Feishu => self.feishu.as_ref(),
+        }
+    }
+}
+
+impl Default for NotificationSenders {
+    /// smtp for email, webhook for the others
+    fn default() -> Self {
+        Self {
+            email: Box::new(SmtpNotificationSender::default()),
+            web_url: Box::new(WebhookNotificationSender::web_url()),
+            slack: Box::new(WebhookNotificationSender::slack()),
+            feishu: Box::new(WebhookNotificationSender::feishu()),
+        }
+    }
+}
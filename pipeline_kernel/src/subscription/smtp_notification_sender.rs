@@ -0,0 +1,499 @@
+use crate::{NotificationMessage, NotificationParams, NotificationSender, PipelineKernelErrorCode};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use elf_base::{ErrorCode, StdR, VoidR};
+use rustls::pki_types::ServerName;
+use rustls::{ClientConfig, ClientConnection, RootCertStore, StreamOwned};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// connection to smtp server, upgraded to tls by STARTTLS
+enum SmtpStream {
+    Plain(TcpStream),
+    Tls(Box<StreamOwned<ClientConnection, TcpStream>>),
+}
+
+impl Read for SmtpStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Plain(stream) => stream.read(buf),
+            Self::Tls(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for SmtpStream {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Plain(stream) => stream.write(buf),
+            Self::Tls(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Self::Plain(stream) => stream.flush(),
+            Self::Tls(stream) => stream.flush(),
+        }
+    }
+}
+
+/// send email by smtp, params of notification:
+/// - host: smtp server host,
+/// - port: smtp server port, default 25,
+/// - username, password: optional, authenticate by AUTH PLAIN when given,
+/// - from: sender address,
+/// - to: receiver addresses, separated by comma, at least one.
+///
+/// connection is upgraded by STARTTLS when server supports it,
+/// and credential is never sent over plain connection, sending fails instead.
+/// implicit tls (e.g. port 465) is not supported.
+pub struct SmtpNotificationSender {
+    timeout: Duration,
+}
+
+impl SmtpNotificationSender {
+    pub fn new(timeout: Duration) -> Self {
+        Self { timeout }
+    }
+
+    fn failed<R>(step: &str, detail: String) -> StdR<R> {
+        PipelineKernelErrorCode::NotificationSendFailed
+            .msg(format!("Failed to send email on [{}], {}.", step, detail))
+    }
+
+    /// value is used in smtp command or mail header, line break is not allowed.
+    fn single_line<'a>(name: &str, value: &'a str) -> StdR<&'a str> {
+        if value.contains(['\r', '\n']) {
+            PipelineKernelErrorCode::NotificationParamInvalid.msg(format!(
+                "Value of [{}] of email cannot contain line break.",
+                name
+            ))
+        } else {
+            Ok(value)
+        }
+    }
+
+    /// read reply of server, multiline reply is joined by new line.
+    /// returns error when reply code doesn't match any of the expected ones.
+    fn expect(reader: &mut impl BufRead, step: &str, codes: &[&str]) -> StdR<String> {
+        let mut reply = String::new();
+        loop {
+            let mut line = String::new();
+            match reader.read_line(&mut line) {
+                Ok(0) => return Self::failed(step, String::from("connection closed")),
+                Ok(_) => {}
+                Err(e) => return Self::failed(step, e.to_string()),
+            }
+            reply.push_str(&line);
+            // "250-..." is continued, "250 ..." is the last line
+            if line.len() < 4 || line.as_bytes()[3] != b'-' {
+                break;
+            }
+        }
+        if codes.iter().any(|code| reply.starts_with(code)) {
+            Ok(reply)
+        } else {
+            Self::failed(step, format!("server replied [{}]", reply.trim_end()))
+        }
+    }
+
+    fn write(stream: &mut impl Write, step: &str, command: &str) -> VoidR {
+        match stream.write_all(format!("{}\r\n", command).as_bytes()) {
+            Ok(_) => Ok(()),
+            Err(e) => Self::failed(step, e.to_string()),
+        }
+    }
+
+    /// returns extensions of server, each line of reply is an extension, in upper case.
+    fn ehlo(reader: &mut BufReader<SmtpStream>) -> StdR<Vec<String>> {
+        Self::write(reader.get_mut(), "EHLO", "EHLO elf")?;
+        let reply = Self::expect(reader, "EHLO", &["250"])?;
+        Ok(reply
+            .lines()
+            .skip(1)
+            .filter(|line| line.len() > 4)
+            .map(|line| line[4..].trim().to_uppercase())
+            .collect())
+    }
+
+    fn starttls(reader: BufReader<SmtpStream>, host: &str) -> StdR<BufReader<SmtpStream>> {
+        let SmtpStream::Plain(stream) = reader.into_inner() else {
+            return Self::failed("STARTTLS", String::from("connection is secured already"));
+        };
+        let server_name = match ServerName::try_from(host.to_string()) {
+            Ok(server_name) => server_name,
+            Err(e) => return Self::failed("STARTTLS", e.to_string()),
+        };
+        let roots = RootCertStore {
+            roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+        };
+        let config = match ClientConfig::builder_with_provider(Arc::new(
+            rustls::crypto::ring::default_provider(),
+        ))
+        .with_safe_default_protocol_versions()
+        {
+            Ok(builder) => builder.with_root_certificates(roots).with_no_client_auth(),
+            Err(e) => return Self::failed("STARTTLS", e.to_string()),
+        };
+        let mut connection = match ClientConnection::new(Arc::new(config), server_name) {
+            Ok(connection) => connection,
+            Err(e) => return Self::failed("STARTTLS", e.to_string()),
+        };
+        let mut stream = stream;
+        // handshake now, so failure is reported on STARTTLS
+        while connection.is_handshaking() {
+            if let Err(e) = connection.complete_io(&mut stream) {
+                return Self::failed("STARTTLS", e.to_string());
+            }
+        }
+        Ok(BufReader::new(SmtpStream::Tls(Box::new(StreamOwned::new(
+            connection, stream,
+        )))))
+    }
+
+    /// non-ascii subject is encoded as rfc 2047
+    fn encode_header(value: &str) -> String {
+        if value.is_ascii() {
+            value.to_string()
+        } else {
+            format!("=?UTF-8?B?{}?=", STANDARD.encode(value))
+        }
+    }
+
+    /// body is encoded by base64, lines are wrapped at 76 characters,
+    /// therefore no dot stuffing is needed.
+    fn data_of(from: &str, to: &[&str], message: &NotificationMessage) -> StdR<String> {
+        let body = STANDARD.encode(&message.body);
+        let mut data = format!(
+            "From: {}\r\nTo: {}\r\nSubject: {}\r\nMIME-Version: 1.0\r\nContent-Type: {}; charset=UTF-8\r\nContent-Transfer-Encoding: base64\r\n\r\n",
+            from,
+            to.join(", "),
+            Self::encode_header(Self::single_line("subject", &message.subject)?),
+            if message.html { "text/html" } else { "text/plain" }
+        );
+        for chunk in body.as_bytes().chunks(76) {
+            data.push_str(&String::from_utf8_lossy(chunk));
+            data.push_str("\r\n");
+        }
+        data.push('.');
+        Ok(data)
+    }
+}
+
+impl Default for SmtpNotificationSender {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(30))
+    }
+}
+
+impl NotificationSender for SmtpNotificationSender {
+    fn send(&self, params: &NotificationParams, message: &NotificationMessage) -> VoidR {
+        let host = params.required("host")?;
+        let port = match params.get("port") {
+            Some(port) => match port.trim().parse::<u16>() {
+                Ok(port) => port,
+                _ => return Self::failed("connect", format!("port[{}] is invalid", port)),
+            },
+            _ => 25,
+        };
+        let from = Self::single_line("from", params.required("from")?)?;
+        let mut to = vec![];
+        for receiver in params.required("to")?.split(',') {
+            let receiver = Self::single_line("to", receiver.trim())?;
+            if !receiver.is_empty() {
+                to.push(receiver);
+            }
+        }
+        if to.is_empty() {
+            return PipelineKernelErrorCode::NotificationParamInvalid
+                .msg("Email must have at least one receiver.");
+        }
+        let credential = match params.get("username") {
+            Some(username) => Some((
+                Self::single_line("username", username)?,
+                Self::single_line("password", params.get("password").unwrap_or(""))?,
+            )),
+            _ => None,
+        };
+        let data = Self::data_of(from, &to, message)?;
+
+        let stream = match TcpStream::connect((host, port)) {
+            Ok(stream) => stream,
+            Err(e) => return Self::failed("connect", e.to_string()),
+        };
+        if let Err(e) = stream
+            .set_read_timeout(Some(self.timeout))
+            .and_then(|_| stream.set_write_timeout(Some(self.timeout)))
+        {
+            return Self::failed("connect", e.to_string());
+        }
+        let mut reader = BufReader::new(SmtpStream::Plain(stream));
+
+        Self::expect(&mut reader, "connect", &["220"])?;
+        let mut extensions = Self::ehlo(&mut reader)?;
+        let mut secured = false;
+        if extensions.iter().any(|extension| extension == "STARTTLS") {
+            Self::write(reader.get_mut(), "STARTTLS", "STARTTLS")?;
+            Self::expect(&mut reader, "STARTTLS", &["220"])?;
+            reader = Self::starttls(reader, host)?;
+            secured = true;
+            // extensions must be discarded after tls negotiation
+            extensions = Self::ehlo(&mut reader)?;
+        }
+        if let Some((username, password)) = credential {
+            if !secured {
+                return Self::failed(
+                    "AUTH",
+                    String::from("server doesn't support STARTTLS, credential is not sent over plain connection"),
+                );
+            }
+            if !extensions.iter().any(|extension| {
+                extension.starts_with("AUTH") && extension.split(' ').any(|m| m == "PLAIN")
+            }) {
+                return Self::failed("AUTH", String::from("server doesn't support AUTH PLAIN"));
+            }
+            let credential = STANDARD.encode(format!("\0{}\0{}", username, password));
+            Self::write(
+                reader.get_mut(),
+                "AUTH",
+                &format!("AUTH PLAIN {}", credential),
+            )?;
+            Self::expect(&mut reader, "AUTH", &["235"])?;
+        }
+        Self::write(reader.get_mut(), "MAIL", &format!("MAIL FROM:<{}>", from))?;
+        Self::expect(&mut reader, "MAIL", &["250"])?;
+        for receiver in to.iter() {
+            Self::write(reader.get_mut(), "RCPT", &format!("RCPT TO:<{}>", receiver))?;
+            Self::expect(&mut reader, "RCPT", &["250", "251"])?;
+        }
+        Self::write(reader.get_mut(), "DATA", "DATA")?;
+        Self::expect(&mut reader, "DATA", &["354"])?;
+        Self::write(reader.get_mut(), "DATA", &data)?;
+        Self::expect(&mut reader, "DATA", &["250"])?;
+        Self::write(reader.get_mut(), "QUIT", "QUIT")?;
+        // mail is accepted already, ignore reply of quit
+        let _ = Self::expect(&mut reader, "QUIT", &["221"]);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        NotificationMessage, NotificationParams, NotificationSender, SmtpNotificationSender,
+    };
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+    use elf_base::VoidR;
+    use elf_model::{NotificationDefinition, NotificationParam, NotificationType};
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// accepts one session, returns received commands and data.
+    /// session is closed after STARTTLS accepted, since mock server cannot negotiate tls.
+    fn mock_server(listener: TcpListener, ehlo: &'static [u8]) -> thread::JoinHandle<Vec<String>> {
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut received = vec![];
+            stream.write_all(b"220 mock\r\n").unwrap();
+            let mut in_data = false;
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap() == 0 {
+                    break;
+                }
+                let line = line.trim_end().to_string();
+                if in_data {
+                    if line == "." {
+                        in_data = false;
+                        stream.write_all(b"250 queued\r\n").unwrap();
+                    }
+                    received.push(line);
+                    continue;
+                }
+                let reply: &[u8] = if line.starts_with("EHLO") {
+                    ehlo
+                } else if line == "STARTTLS" {
+                    received.push(line);
+                    stream.write_all(b"220 ready\r\n").unwrap();
+                    break;
+                } else if line.starts_with("AUTH") {
+                    b"235 ok\r\n"
+                } else if line == "DATA" {
+                    in_data = true;
+                    b"354 go ahead\r\n"
+                } else if line == "QUIT" {
+                    received.push(line);
+                    stream.write_all(b"221 bye\r\n").unwrap();
+                    break;
+                } else {
+                    b"250 ok\r\n"
+                };
+                received.push(line);
+                stream.write_all(reply).unwrap();
+            }
+            received
+        })
+    }
+
+    fn param(name: &str, value: &str) -> NotificationParam {
+        NotificationParam::new()
+            .name(name.to_string())
+            .value(value.to_string())
+    }
+
+    const PLAIN_EHLO: &[u8] = b"250-mock\r\n250 AUTH PLAIN\r\n";
+    const STARTTLS_EHLO: &[u8] = b"250-mock\r\n250-STARTTLS\r\n250 AUTH PLAIN\r\n";
+
+    fn send(params: Vec<NotificationParam>, subject: &str) -> VoidR {
+        let notification = NotificationDefinition::new()
+            .r#type(NotificationType::Email)
+            .params(params);
+        SmtpNotificationSender::default().send(
+            &NotificationParams::of(&notification),
+            &NotificationMessage {
+                subject: subject.to_string(),
+                body: String::from("region,amount\nnorth,10"),
+                html: false,
+            },
+        )
+    }
+
+    fn params(port: u16, to: &str) -> Vec<NotificationParam> {
+        vec![
+            param("host", "127.0.0.1"),
+            param("port", &port.to_string()),
+            param("from", "elf@example.com"),
+            param("to", to),
+        ]
+    }
+
+    fn with_credential(mut params: Vec<NotificationParam>) -> Vec<NotificationParam> {
+        params.push(param("username", "elf"));
+        params.push(param("password", "secret"));
+        params
+    }
+
+    #[test]
+    fn test_send() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = mock_server(listener, PLAIN_EHLO);
+
+        send(params(port, "a@example.com, b@example.com"), "Daily sales").unwrap();
+
+        let received = server.join().unwrap();
+        assert_eq!(received[0], "EHLO elf");
+        assert_eq!(received[1], "MAIL FROM:<elf@example.com>");
+        assert_eq!(received[2], "RCPT TO:<a@example.com>");
+        assert_eq!(received[3], "RCPT TO:<b@example.com>");
+        assert_eq!(received[4], "DATA");
+        assert!(received.contains(&String::from("Subject: Daily sales")));
+        assert!(received.contains(&String::from("Content-Type: text/plain; charset=UTF-8")));
+        assert!(received.contains(&STANDARD.encode("region,amount\nnorth,10")));
+        assert_eq!(received.last().unwrap(), "QUIT");
+    }
+
+    #[test]
+    fn test_auth_without_tls() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = mock_server(listener, PLAIN_EHLO);
+
+        let error = send(
+            with_credential(params(port, "a@example.com")),
+            "Daily sales",
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("PLKN-01003"));
+        assert!(error.to_string().contains("[AUTH]"));
+
+        let received = server.join().unwrap();
+        assert_eq!(received, vec!["EHLO elf"]);
+    }
+
+    #[test]
+    fn test_starttls() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = mock_server(listener, STARTTLS_EHLO);
+
+        // mock server closes session instead of negotiating tls
+        let error = send(
+            with_credential(params(port, "a@example.com")),
+            "Daily sales",
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("[STARTTLS]"));
+
+        let received = server.join().unwrap();
+        assert_eq!(received, vec!["EHLO elf", "STARTTLS"]);
+    }
+
+    #[test]
+    fn test_line_break_rejected() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = mock_server(listener, PLAIN_EHLO);
+
+        let invalid = |params: Vec<NotificationParam>, subject: &str| {
+            let error = send(params, subject).unwrap_err();
+            assert!(error.to_string().contains("PLKN-01004"));
+        };
+        invalid(
+            params(port, "a@example.com"),
+            "Daily sales\r\nBcc: x@example.com",
+        );
+        invalid(params(port, "a@example.com\nb@example.com"), "Daily sales");
+        let mut from_injected = params(port, "a@example.com");
+        from_injected[2] = param("from", "elf@example.com\r\nRCPT TO:<x@example.com>");
+        invalid(from_injected, "Daily sales");
+        let mut username_injected = with_credential(params(port, "a@example.com"));
+        username_injected[4] = param("username", "elf\r\nQUIT");
+        invalid(username_injected, "Daily sales");
+
+        // rejected before connecting, server still waits for the session
+        send(params(port, "a@example.com"), "Daily sales").unwrap();
+        let received = server.join().unwrap();
+        assert_eq!(received[0], "EHLO elf");
+        assert!(received.contains(&String::from("Subject: Daily sales")));
+    }
+
+    #[test]
+    fn test_no_receiver() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = mock_server(listener, PLAIN_EHLO);
+
+        let error = send(params(port, " , "), "Daily sales").unwrap_err();
+        assert!(error.to_string().contains("PLKN-01004"));
+
+        // rejected before connecting, server still waits for the session
+        send(params(port, "a@example.com"), "Daily sales").unwrap();
+        let received = server.join().unwrap();
+        assert_eq!(received[2], "RCPT TO:<a@example.com>");
+    }
+
+    #[test]
+    fn test_param_missed() {
+        let notification = NotificationDefinition::new()
+            .r#type(NotificationType::Email)
+            .params(vec![param("host", "127.0.0.1")]);
+        assert!(SmtpNotificationSender::default()
+            .send(
+                &NotificationParams::of(&notification),
+                &NotificationMessage {
+                    subject: String::new(),
+                    body: String::new(),
+                    html: false,
+                },
+            )
+            .is_err());
+    }
+}
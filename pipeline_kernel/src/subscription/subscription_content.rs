@@ -0,0 +1,135 @@
+use crate::NotificationMessage;
+use bigdecimal::BigDecimal;
+use elf_model::{DataResult, DataResultSetCell, DataResultSetRow, Objective, ObjectiveValues};
+
+/// content of subscription event, a titled table.
+pub struct SubscriptionContent {
+    pub title: String,
+    pub data: DataResult,
+}
+
+impl SubscriptionContent {
+    /// one row for each target of objective
+    pub fn of_objective(objective: &Objective, values: &ObjectiveValues) -> Self {
+        let num = |value: &Option<BigDecimal>| match value {
+            Some(value) => DataResultSetCell::Num(value.clone()),
+            _ => DataResultSetCell::None,
+        };
+        let rows: Vec<DataResultSetRow> = values
+            .targets
+            .iter()
+            .flatten()
+            .map(|target| {
+                vec![
+                    DataResultSetCell::Str(target.name.clone().unwrap_or_default()),
+                    num(&target.current_value),
+                    num(&target.tobe),
+                    num(&target.achievement_rate),
+                    match target.achieved {
+                        Some(achieved) => DataResultSetCell::Bool(achieved),
+                        _ => DataResultSetCell::None,
+                    },
+                ]
+            })
+            .collect();
+        Self {
+            title: objective.name.clone().unwrap_or_default(),
+            data: DataResult::new()
+                .columns(
+                    [
+                        "Target",
+                        "Current Value",
+                        "To Be",
+                        "Achievement Rate",
+                        "Achieved",
+                    ]
+                    .iter()
+                    .map(|column| column.to_string())
+                    .collect(),
+                )
+                .data(rows),
+        }
+    }
+
+    fn text_of(cell: &DataResultSetCell) -> String {
+        match cell {
+            DataResultSetCell::Str(str) => str.clone(),
+            DataResultSetCell::Num(num) => num.normalized().to_string(),
+            DataResultSetCell::Bool(bool) => bool.to_string(),
+            DataResultSetCell::DateTime(datetime) => {
+                datetime.format("%Y-%m-%d %H:%M:%S").to_string()
+            }
+            DataResultSetCell::Date(date) => date.format("%Y-%m-%d").to_string(),
+            DataResultSetCell::Time(time) => time.format("%H:%M:%S").to_string(),
+            DataResultSetCell::None => String::new(),
+        }
+    }
+
+    fn csv_of(value: &str) -> String {
+        if value.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value.to_string()
+        }
+    }
+
+    fn html_of(value: &str) -> String {
+        value
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    }
+
+    fn to_csv(&self) -> String {
+        let mut lines = vec![];
+        if let Some(columns) = &self.data.columns {
+            lines.push(
+                columns
+                    .iter()
+                    .map(|column| Self::csv_of(column))
+                    .collect::<Vec<String>>()
+                    .join(","),
+            );
+        }
+        for row in self.data.data.iter().flatten() {
+            lines.push(
+                row.iter()
+                    .map(|cell| Self::csv_of(&Self::text_of(cell)))
+                    .collect::<Vec<String>>()
+                    .join(","),
+            );
+        }
+        lines.join("\n")
+    }
+
+    fn to_html(&self) -> String {
+        let mut html = format!("<h3>{}</h3><table>", Self::html_of(&self.title));
+        if let Some(columns) = &self.data.columns {
+            html.push_str("<tr>");
+            for column in columns {
+                html.push_str(&format!("<th>{}</th>", Self::html_of(column)));
+            }
+            html.push_str("</tr>");
+        }
+        for row in self.data.data.iter().flatten() {
+            html.push_str("<tr>");
+            for cell in row {
+                html.push_str(&format!("<td>{}</td>", Self::html_of(&Self::text_of(cell))));
+            }
+            html.push_str("</tr>");
+        }
+        html.push_str("</table>");
+        html
+    }
+
+    /// content type "html" renders a html table, otherwise renders csv text.
+    pub fn to_message(&self, content_type: Option<&str>) -> NotificationMessage {
+        let html = content_type.is_some_and(|t| t.trim().eq_ignore_ascii_case("html"));
+        NotificationMessage {
+            subject: self.title.clone(),
+            body: if html { self.to_html() } else { self.to_csv() },
+            html,
+        }
+    }
+}
@@ -0,0 +1,386 @@
+use crate::{
+    NotificationParams, NotificationSenders, PipelineKernelErrorCode, SubscriptionResources,
+};
+use chrono::{NaiveDate, NaiveDateTime};
+use elf_base::{ErrorCode, StdErr, StdR, VoidR, VoidResultHelper};
+use elf_model::{
+    SubscriptionEvent, SubscriptionEventId, SubscriptionEventJobLockStatus, SubscriptionEventLock,
+    TenantId,
+};
+use elf_runtime_model_kernel::{IdGen, SchedulerClock, SubscriptionEventSchedule};
+
+/// notification sent by this run
+pub struct SubscriptionDelivery {
+    pub subscription_event_id: SubscriptionEventId,
+    pub tenant_id: TenantId,
+    pub process_date: NaiveDate,
+}
+
+/// fire due subscription events, render content of event source, and send it by notification of event.
+/// host calls [SubscriptionDispatcher::tick] periodically,
+/// or sleeps till [SubscriptionDispatcher::next_fire_time] and then calls it.
+///
+/// only the latest due fire time of event is dispatched, the earlier ones missed during downtime are skipped,
+/// since content is always rendered on current data.
+/// fire time is locked before sending, so it is sent by only one node in cluster,
+/// and never be sent again even when sending is failed.
+pub struct SubscriptionDispatcher<C: SchedulerClock, R: SubscriptionResources> {
+    clock: C,
+    resources: R,
+    senders: NotificationSenders,
+}
+
+impl<C: SchedulerClock, R: SubscriptionResources> SubscriptionDispatcher<C, R> {
+    pub fn new(clock: C, resources: R, senders: NotificationSenders) -> Self {
+        Self {
+            clock,
+            resources,
+            senders,
+        }
+    }
+
+    fn enabled_events(&self) -> StdR<Vec<SubscriptionEvent>> {
+        Ok(self
+            .resources
+            .events()?
+            .into_iter()
+            .filter(|event| event.enabled.unwrap_or(false))
+            .collect())
+    }
+
+    /// the latest fire time of event which is due at given time.
+    /// starts after the fire time of latest locked process date,
+    /// or the created time of event when it never fired,
+    /// nothing is due when neither of them is known.
+    pub fn due(
+        &self,
+        event: &SubscriptionEvent,
+        now: &NaiveDateTime,
+    ) -> StdR<Option<NaiveDateTime>> {
+        let schedule = SubscriptionEventSchedule::of(event)?;
+        let latest_lock = match (&event.subscription_event_id, &event.tenant_id) {
+            (Some(event_id), Some(tenant_id)) => self.resources.latest_lock(event_id, tenant_id)?,
+            _ => None,
+        };
+        let after = match latest_lock.and_then(|lock| lock.process_date) {
+            Some(process_date) => schedule.fire_time_of(&process_date),
+            _ => match event.created_at {
+                Some(created_at) => created_at,
+                _ => return Ok(None),
+            },
+        };
+        Ok(schedule.fire_times_between(&after, now).pop())
+    }
+
+    /// dispatch due events of all enabled events.
+    /// returns notifications sent by this call, fire times locked by another run are not included.
+    /// errors are accumulated, failure of one event doesn't stop the others.
+    pub fn tick(&self) -> StdR<Vec<SubscriptionDelivery>> {
+        let now = self.clock.now();
+
+        let mut deliveries = vec![];
+        let mut errors: Vec<StdErr> = vec![];
+        for event in self.enabled_events()? {
+            let fire_time = match self.due(&event, &now) {
+                Ok(Some(fire_time)) => fire_time,
+                Ok(None) => continue,
+                Err(e) => {
+                    errors.push(e);
+                    continue;
+                }
+            };
+            match self.dispatch(&event, &fire_time.date(), &now) {
+                Ok(Some(delivery)) => deliveries.push(delivery),
+                Ok(None) => {}
+                Err(e) => errors.push(e),
+            }
+        }
+        errors.accumulate()?;
+
+        Ok(deliveries)
+    }
+
+    /// returns none when process date is locked by another run.
+    pub fn dispatch(
+        &self,
+        event: &SubscriptionEvent,
+        process_date: &NaiveDate,
+        now: &NaiveDateTime,
+    ) -> StdR<Option<SubscriptionDelivery>> {
+        let (Some(event_id), Some(tenant_id)) = (&event.subscription_event_id, &event.tenant_id)
+        else {
+            return PipelineKernelErrorCode::SubscriptionEventIncomplete
+                .msg("Subscription event id and tenant id are required.");
+        };
+
+        let mut lock = SubscriptionEventLock::new()
+            .subscription_event_lock_id(IdGen::next_id()?.to_string())
+            .tenant_id(tenant_id.clone())
+            .subscription_event_id(event_id.clone())
+            .process_date(*process_date)
+            .status(SubscriptionEventJobLockStatus::Ready)
+            .created_at(*now);
+        lock.user_id = event.user_id.clone();
+        if !self.resources.try_lock(&lock)? {
+            // process date is locked by another run
+            return Ok(None);
+        }
+
+        let result = self.send(event, event_id, tenant_id);
+        // lock id is set above
+        let lock_id = lock.subscription_event_lock_id.as_ref().unwrap();
+        match result {
+            Ok(_) => {
+                self.resources
+                    .finish(lock_id, SubscriptionEventJobLockStatus::Success)?;
+                Ok(Some(SubscriptionDelivery {
+                    subscription_event_id: event_id.clone(),
+                    tenant_id: tenant_id.clone(),
+                    process_date: *process_date,
+                }))
+            }
+            Err(e) => {
+                // keep the send error, failure of finishing lock is logged only
+                if let Err(finish_error) = self
+                    .resources
+                    .finish(lock_id, SubscriptionEventJobLockStatus::Failed)
+                {
+                    tracing::error!(
+                        "Failed to finish subscription event lock[{}], caused by {}.",
+                        lock_id,
+                        finish_error
+                    );
+                }
+                Err(e)
+            }
+        }
+    }
+
+    fn send(
+        &self,
+        event: &SubscriptionEvent,
+        event_id: &SubscriptionEventId,
+        tenant_id: &TenantId,
+    ) -> VoidR {
+        let Some(notification_id) = &event.notification_id else {
+            return PipelineKernelErrorCode::SubscriptionEventIncomplete.msg(format!(
+                "Notification of subscription event[subscription_event_id={}] is missed.",
+                event_id
+            ));
+        };
+        let notification = self.resources.notification(notification_id, tenant_id)?;
+        let Some(r#type) = &notification.r#type else {
+            return PipelineKernelErrorCode::NotificationNotFound.msg(format!(
+                "Type of notification[{}] is missed.",
+                notification_id
+            ));
+        };
+
+        let message = self
+            .resources
+            .content(event)?
+            .to_message(event.content_type.as_deref());
+        self.senders
+            .sender_of(r#type)
+            .send(&NotificationParams::of(&notification), &message)
+    }
+
+    /// the earliest fire time after now of all enabled events,
+    /// returns none when there is no enabled event.
+    pub fn next_fire_time(&self) -> StdR<Option<NaiveDateTime>> {
+        let now = self.clock.now();
+        let mut next: Option<NaiveDateTime> = None;
+        for event in self.enabled_events()? {
+            let fire_time = SubscriptionEventSchedule::of(&event)?.next_fire_time(&now);
+            next = Some(next.map_or(fire_time, |next| next.min(fire_time)));
+        }
+        Ok(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        NotificationMessage, NotificationParams, NotificationSender, NotificationSenders,
+        SubscriptionContent, SubscriptionDispatcher, SubscriptionResources,
+    };
+    use bigdecimal::BigDecimal;
+    use chrono::{NaiveDate, NaiveDateTime};
+    use elf_base::{StdR, VoidR};
+    use elf_model::{
+        DataResult, DataResultSetCell, EventSource, NotificationDefinition,
+        NotificationDefinitionId, NotificationParam, NotificationType, SubscriptionEvent,
+        SubscriptionEventFrequency, SubscriptionEventId, SubscriptionEventJobLockStatus,
+        SubscriptionEventLock, SubscriptionEventLockId, TenantId,
+    };
+    use elf_runtime_model_kernel::{IdGen, SchedulerClock, SnowflakeIdGenerator};
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
+
+    struct FixedClock(Cell<NaiveDateTime>);
+
+    impl SchedulerClock for &FixedClock {
+        fn now(&self) -> NaiveDateTime {
+            self.0.get()
+        }
+    }
+
+    #[derive(Default)]
+    struct InMemoryResources {
+        /// lock id, process date, status
+        locks: RefCell<Vec<(String, NaiveDate, SubscriptionEventJobLockStatus)>>,
+    }
+
+    impl SubscriptionResources for &InMemoryResources {
+        fn events(&self) -> StdR<Vec<SubscriptionEvent>> {
+            Ok(vec![SubscriptionEvent::new()
+                .subscription_event_id(String::from("e1"))
+                .event_source(EventSource::Subject)
+                .source_id(String::from("sales"))
+                .notification_id(String::from("n1"))
+                .frequency(SubscriptionEventFrequency::Daily)
+                .hour(8)
+                .content_type(String::from("csv"))
+                .enabled(true)
+                .tenant_id(String::from("tenant-1"))
+                .created_at(time(2026, 2, 10, 9))])
+        }
+
+        fn latest_lock(
+            &self,
+            _subscription_event_id: &SubscriptionEventId,
+            _tenant_id: &TenantId,
+        ) -> StdR<Option<SubscriptionEventLock>> {
+            Ok(self
+                .locks
+                .borrow()
+                .iter()
+                .map(|(_, process_date, _)| *process_date)
+                .max()
+                .map(|process_date| SubscriptionEventLock::new().process_date(process_date)))
+        }
+
+        fn try_lock(&self, lock: &SubscriptionEventLock) -> StdR<bool> {
+            let process_date = lock.process_date.unwrap();
+            let mut locks = self.locks.borrow_mut();
+            if locks.iter().any(|(_, date, _)| *date == process_date) {
+                return Ok(false);
+            }
+            locks.push((
+                lock.subscription_event_lock_id.clone().unwrap(),
+                process_date,
+                SubscriptionEventJobLockStatus::Ready,
+            ));
+            Ok(true)
+        }
+
+        fn finish(
+            &self,
+            lock_id: &SubscriptionEventLockId,
+            status: SubscriptionEventJobLockStatus,
+        ) -> VoidR {
+            for lock in self.locks.borrow_mut().iter_mut() {
+                if &lock.0 == lock_id {
+                    lock.2 = status.clone();
+                }
+            }
+            Ok(())
+        }
+
+        fn notification(
+            &self,
+            notification_id: &NotificationDefinitionId,
+            _tenant_id: &TenantId,
+        ) -> StdR<NotificationDefinition> {
+            Ok(NotificationDefinition::new()
+                .notification_id(notification_id.clone())
+                .r#type(NotificationType::Slack)
+                .params(vec![NotificationParam::new()
+                    .name(String::from("url"))
+                    .value(String::from("http://localhost/hook"))]))
+        }
+
+        fn content(&self, _event: &SubscriptionEvent) -> StdR<SubscriptionContent> {
+            Ok(SubscriptionContent {
+                title: String::from("Sales"),
+                data: DataResult::new()
+                    .columns(vec![String::from("region"), String::from("amount")])
+                    .data(vec![vec![
+                        DataResultSetCell::Str(String::from("north, east")),
+                        DataResultSetCell::Num(BigDecimal::from(10)),
+                    ]]),
+            })
+        }
+    }
+
+    struct RecordingSender(Rc<RefCell<Vec<(String, String)>>>);
+
+    impl NotificationSender for RecordingSender {
+        fn send(&self, params: &NotificationParams, message: &NotificationMessage) -> VoidR {
+            self.0.borrow_mut().push((
+                params.get("url").unwrap_or("").to_string(),
+                format!("{}|{}", message.subject, message.body),
+            ));
+            Ok(())
+        }
+    }
+
+    fn time(year: i32, month: u32, day: u32, hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_tick() {
+        let _ = IdGen::set(Box::new(SnowflakeIdGenerator::new(1).unwrap()));
+        let clock = FixedClock(Cell::new(time(2026, 2, 10, 12)));
+        let resources = InMemoryResources::default();
+        let sent = Rc::new(RefCell::new(vec![]));
+        let dispatcher = SubscriptionDispatcher::new(
+            &clock,
+            &resources,
+            NotificationSenders::default().slack(Box::new(RecordingSender(sent.clone()))),
+        );
+
+        // created after 08:00 of 2026-02-10
+        assert!(dispatcher.tick().unwrap().is_empty());
+        assert_eq!(
+            dispatcher.next_fire_time().unwrap(),
+            Some(time(2026, 2, 11, 8))
+        );
+
+        // down for days, only the latest is sent
+        clock.0.set(time(2026, 2, 13, 9));
+        let deliveries = dispatcher.tick().unwrap();
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(deliveries[0].subscription_event_id, "e1");
+        assert_eq!(deliveries[0].process_date, time(2026, 2, 13, 0).date());
+        assert_eq!(
+            sent.borrow().as_slice(),
+            &[(
+                String::from("http://localhost/hook"),
+                String::from("Sales|region,amount\n\"north, east\",10")
+            )]
+        );
+
+        // sent already
+        assert!(dispatcher.tick().unwrap().is_empty());
+        assert_eq!(sent.borrow().len(), 1);
+        assert_eq!(
+            resources.locks.borrow()[0].2,
+            SubscriptionEventJobLockStatus::Success
+        );
+
+        // locked by another node
+        clock.0.set(time(2026, 2, 14, 8));
+        resources.locks.borrow_mut().push((
+            String::from("another"),
+            time(2026, 2, 14, 0).date(),
+            SubscriptionEventJobLockStatus::Ready,
+        ));
+        assert!(dispatcher.tick().unwrap().is_empty());
+        assert_eq!(sent.borrow().len(), 1);
+    }
+}
@@ -0,0 +1,118 @@
+use crate::{
+    ObjectiveService, PipelineKernelErrorCode, SubjectDatasetEngine, SubjectDatasetPlan,
+    SubscriptionContent, TopicStorageDataSource,
+};
+use elf_base::{ErrorCode, StdR, VoidR};
+use elf_model::{
+    EventSource, NotificationDefinition, NotificationDefinitionId, SubscriptionEvent,
+    SubscriptionEventId, SubscriptionEventJobLockStatus, SubscriptionEventLock,
+    SubscriptionEventLockId, TenantId,
+};
+use elf_runtime_model_kernel::{
+    NotificationDefinitionMetaProvider, SubjectMetaProvider, SubjectService,
+    SubscriptionEventLockProvider, SubscriptionEventMetaProvider, SubscriptionService,
+    TopicSchemaProvider, TopicService,
+};
+
+/// everything subscription dispatcher reads from and writes to.
+pub trait SubscriptionResources {
+    /// enabled subscription events of all tenants
+    fn events(&self) -> StdR<Vec<SubscriptionEvent>>;
+    /// the lock which has the latest process date of given event
+    fn latest_lock(
+        &self,
+        subscription_event_id: &SubscriptionEventId,
+        tenant_id: &TenantId,
+    ) -> StdR<Option<SubscriptionEventLock>>;
+    /// see [elf_runtime_model_kernel::SubscriptionEventLockService::try_lock]
+    fn try_lock(&self, lock: &SubscriptionEventLock) -> StdR<bool>;
+    fn finish(
+        &self,
+        lock_id: &SubscriptionEventLockId,
+        status: SubscriptionEventJobLockStatus,
+    ) -> VoidR;
+    fn notification(
+        &self,
+        notification_id: &NotificationDefinitionId,
+        tenant_id: &TenantId,
+    ) -> StdR<NotificationDefinition>;
+    /// content of event source, source is decided by event source and source id.
+    fn content(&self, event: &SubscriptionEvent) -> StdR<SubscriptionContent>;
+}
+
+/// events, locks and notifications are found from meta,
+/// subject dataset is read from topic storage, objective is evaluated on now.
+pub struct SubscriptionMetaResources;
+
+impl SubscriptionResources for SubscriptionMetaResources {
+    fn events(&self) -> StdR<Vec<SubscriptionEvent>> {
+        SubscriptionService::meta()?.find_enabled()
+    }
+
+    fn latest_lock(
+        &self,
+        subscription_event_id: &SubscriptionEventId,
+        tenant_id: &TenantId,
+    ) -> StdR<Option<SubscriptionEventLock>> {
+        SubscriptionService::event_lock()?.find_latest(subscription_event_id, tenant_id)
+    }
+
+    fn try_lock(&self, lock: &SubscriptionEventLock) -> StdR<bool> {
+        SubscriptionService::event_lock()?.try_lock(lock)
+    }
+
+    fn finish(
+        &self,
+        lock_id: &SubscriptionEventLockId,
+        status: SubscriptionEventJobLockStatus,
+    ) -> VoidR {
+        SubscriptionService::event_lock()?.finish(lock_id, status)
+    }
+
+    fn notification(
+        &self,
+        notification_id: &NotificationDefinitionId,
+        tenant_id: &TenantId,
+    ) -> StdR<NotificationDefinition> {
+        match SubscriptionService::notification_meta()?.find_by_id(notification_id, tenant_id)? {
+            Some(notification) => Ok(notification),
+            _ => PipelineKernelErrorCode::NotificationNotFound
+                .msg(format!("Notification[{}] not found.", notification_id)),
+        }
+    }
+
+    fn content(&self, event: &SubscriptionEvent) -> StdR<SubscriptionContent> {
+        let (Some(source_id), Some(tenant_id)) = (&event.source_id, &event.tenant_id) else {
+            return PipelineKernelErrorCode::SubscriptionEventIncomplete.msg(format!(
+                "Source id and tenant id of subscription event[subscription_event_id={}] are required.",
+                event.subscription_event_id.as_deref().unwrap_or("")
+            ));
+        };
+        match &event.event_source {
+            Some(EventSource::Subject) => {
+                let Some(subject) = SubjectService::meta()?.find_by_id(source_id, tenant_id)?
+                else {
+                    return PipelineKernelErrorCode::SubjectNotFound
+                        .msg(format!("Subject[{}] not found.", source_id));
+                };
+                let title = subject.name.clone().unwrap_or_default();
+                let plan = SubjectDatasetPlan::compile(subject, |topic_id| {
+                    TopicService::schema()?.by_id(topic_id, tenant_id)
+                })?;
+                Ok(SubscriptionContent {
+                    title,
+                    data: SubjectDatasetEngine::dataset(&plan, &TopicStorageDataSource)?,
+                })
+            }
+            Some(EventSource::ObjectiveAnalysis) => {
+                let objective = ObjectiveService::find_objective_by_id(source_id, tenant_id)?;
+                let values = ObjectiveService::evaluate(&objective, tenant_id)?;
+                Ok(SubscriptionContent::of_objective(&objective, &values))
+            }
+            _ => PipelineKernelErrorCode::SubscriptionEventIncomplete.msg(format!(
+                "Event source of subscription event[subscription_event_id={}] is missed.",
+                event.subscription_event_id.as_deref().unwrap_or("")
+            )),
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use crate::{NotificationMessage, NotificationParams, NotificationSender, PipelineKernelErrorCode};
+use elf_base::{ErrorCode, VoidR};
+use serde_json::{json, Value};
+use std::time::Duration;
+use ureq::Agent;
+
+/// payload format of webhook
+pub enum WebhookFormat {
+    /// {"subject": "...", "body": "...", "html": false}
+    Plain,
+    /// incoming webhook of slack, {"text": "..."}
+    Slack,
+    /// custom bot of feishu, {"msg_type": "text", "content": {"text": "..."}}
+    Feishu,
+}
+
+/// post message to webhook as json, params of notification:
+/// - url: webhook url.
+pub struct WebhookNotificationSender {
+    format: WebhookFormat,
+    agent: Agent,
+}
+
+impl WebhookNotificationSender {
+    pub fn new(format: WebhookFormat, timeout: Duration) -> Self {
+        Self {
+            format,
+            agent: Agent::config_builder()
+                .timeout_global(Some(timeout))
+                .build()
+                .into(),
+        }
+    }
+
+    pub fn web_url() -> Self {
+        Self::new(WebhookFormat::Plain, Duration::from_secs(30))
+    }
+
+    pub fn slack() -> Self {
+        Self::new(WebhookFormat::Slack, Duration::from_secs(30))
+    }
+
+    pub fn feishu() -> Self {
+        Self::new(WebhookFormat::Feishu, Duration::from_secs(30))
+    }
+
+    fn payload_of(&self, message: &NotificationMessage) -> Value {
+        match self.format {
+            WebhookFormat::Plain => json!({
+                "subject": message.subject,
+                "body": message.body,
+                "html": message.html,
+            }),
+            WebhookFormat::Slack => json!({
+                "text": format!("*{}*\n{}", message.subject, message.body),
+            }),
+            WebhookFormat::Feishu => json!({
+                "msg_type": "text",
+                "content": {"text": format!("{}\n{}", message.subject, message.body)},
+            }),
+        }
+    }
+}
+
+impl NotificationSender for WebhookNotificationSender {
+    fn send(&self, params: &NotificationParams, message: &NotificationMessage) -> VoidR {
+        let url = params.required("url")?;
+        // status other than 2xx is treated as error
+        match self
+            .agent
+            .post(url)
+            .header("Content-Type", "application/json")
+            .send(self.payload_of(message).to_string())
+        {
+            Ok(_) => Ok(()),
+            Err(e) => PipelineKernelErrorCode::NotificationSendFailed.msg(format!(
+                "Failed to post message to webhook[{}], caused by {}.",
+                url, e
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        NotificationMessage, NotificationParams, NotificationSender, WebhookNotificationSender,
+    };
+    use elf_model::{NotificationDefinition, NotificationParam, NotificationType};
+    use serde_json::Value;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// accepts one request, replies given status, returns request line and body
+    fn mock_server(
+        listener: TcpListener,
+        status: &'static str,
+    ) -> thread::JoinHandle<(String, String)> {
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let line = line.trim_end();
+                if line.is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':')
+                    && name.eq_ignore_ascii_case("content-length")
+                {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            stream
+                .write_all(
+                    format!(
+                        "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                        status
+                    )
+                    .as_bytes(),
+                )
+                .unwrap();
+            (
+                request_line.trim_end().to_string(),
+                String::from_utf8(body).unwrap(),
+            )
+        })
+    }
+
+    fn params(r#type: NotificationType, url: String) -> NotificationParams {
+        NotificationParams::of(&NotificationDefinition::new().r#type(r#type).params(vec![
+                NotificationParam::new()
+                    .name(String::from("url"))
+                    .value(url),
+            ]))
+    }
+
+    fn message() -> NotificationMessage {
+        NotificationMessage {
+            subject: String::from("Daily sales"),
+            body: String::from("north,10"),
+            html: false,
+        }
+    }
+
+    #[test]
+    fn test_slack() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = mock_server(listener, "200 OK");
+
+        WebhookNotificationSender::slack()
+            .send(
+                &params(
+                    NotificationType::Slack,
+                    format!("http://127.0.0.1:{}/hooks/abc", port),
+                ),
+                &message(),
+            )
+            .unwrap();
+
+        let (request_line, body) = server.join().unwrap();
+        assert_eq!(request_line, "POST /hooks/abc HTTP/1.1");
+        let payload: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(payload["text"], "*Daily sales*\nnorth,10");
+    }
+
+    #[test]
+    fn test_failed() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = mock_server(listener, "500 Internal Server Error");
+
+        assert!(WebhookNotificationSender::feishu()
+            .send(
+                &params(
+                    NotificationType::Feishu,
+                    format!("http://127.0.0.1:{}/bot", port),
+                ),
+                &message(),
+            )
+            .is_err());
+        let (_, body) = server.join().unwrap();
+        let payload: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(payload["msg_type"], "text");
+    }
+}
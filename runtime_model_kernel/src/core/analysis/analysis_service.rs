@@ -0,0 +1,6 @@
+use crate::{FactorIndexProvider, PipelineIndexProvider};
+
+pub struct AnalysisService;
+
+impl FactorIndexProvider for AnalysisService {}
+impl PipelineIndexProvider for AnalysisService {}
@@ -0,0 +1,81 @@
+use chrono::{NaiveDateTime, Utc};
+use elf_model::{FactorIndex, FactorType, Topic};
+
+/// build factor indexes of topic, one index for each factor.
+/// index id is not assigned here, should be assigned before persist.
+pub struct FactorIndexBuilder;
+
+impl FactorIndexBuilder {
+    pub fn of_topic(topic: &Topic) -> Vec<FactorIndex> {
+        Self::of_topic_at(topic, Utc::now().naive_utc())
+    }
+
+    pub fn of_topic_at(topic: &Topic, now: NaiveDateTime) -> Vec<FactorIndex> {
+        match &topic.factors {
+            Some(factors) => factors
+                .iter()
+                .map(|factor| {
+                    let mut index = FactorIndex::new();
+                    index.factor_id = factor.factor_id.clone();
+                    // factor type is not cloneable, copy it by its string form
+                    index.factor_type = factor
+                        .r#type
+                        .as_ref()
+                        .and_then(|t| FactorType::try_parse(t.to_string()));
+                    index.factor_name = factor.name.clone();
+                    index.factor_label = factor.label.clone();
+                    index.factor_description = factor.description.clone();
+                    index.topic_id = topic.topic_id.clone();
+                    index.topic_name = topic.name.clone();
+                    index.tenant_id = topic.tenant_id.clone();
+                    index.created_at = Some(now);
+                    index.last_modified_at = Some(now);
+                    index
+                })
+                .collect(),
+            _ => vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::FactorIndexBuilder;
+    use elf_model::{Factor, FactorType, Topic};
+
+    #[test]
+    fn test_of_topic() {
+        let topic = Topic::new()
+            .topic_id("t1".to_string())
+            .name("order".to_string())
+            .tenant_id("tenant".to_string())
+            .factors(vec![
+                Factor::new()
+                    .factor_id("f1".to_string())
+                    .name("order_no".to_string())
+                    .r#type(FactorType::Text)
+                    .label("Order No.".to_string()),
+                Factor::new()
+                    .factor_id("f2".to_string())
+                    .name("amount".to_string())
+                    .r#type(FactorType::Number)
+                    .description("total amount".to_string()),
+            ]);
+        let indexes = FactorIndexBuilder::of_topic(&topic);
+        assert_eq!(2, indexes.len());
+        assert_eq!(Some("f1".to_string()), indexes[0].factor_id);
+        assert_eq!(Some("order_no".to_string()), indexes[0].factor_name);
+        assert_eq!(Some("Order No.".to_string()), indexes[0].factor_label);
+        assert_eq!(Some("order".to_string()), indexes[0].topic_name);
+        assert_eq!(Some("tenant".to_string()), indexes[1].tenant_id);
+        assert_eq!(
+            Some("total amount".to_string()),
+            indexes[1].factor_description
+        );
+        assert!(indexes[1].factor_index_id.is_none());
+        assert_eq!(Some(FactorType::Number), indexes[1].factor_type);
+        assert!(indexes[1].created_at.is_some());
+
+        assert!(FactorIndexBuilder::of_topic(&Topic::new()).is_empty());
+    }
+}
@@ -0,0 +1,47 @@
+use elf_base::{StdR, VoidR};
+use elf_model::{FactorIndex, TenantId, TopicId};
+use std::sync::Arc;
+
+/// TODO factor index service using tenant and it's meta datasource (or the global meta datasource)
+///  to persist the factor indexes.
+///  factor indexes are always replaced by topic, never updated one by one.
+pub struct FactorIndexService;
+
+impl FactorIndexService {
+    fn new() -> StdR<Arc<Self>> {
+        Ok(Arc::new(Self {}))
+    }
+
+    /// remove all existing indexes of given topic, and insert the given ones.
+    pub fn replace_by_topic(
+        &self,
+        _topic_id: &TopicId,
+        _tenant_id: &TenantId,
+        _indexes: Vec<FactorIndex>,
+    ) -> VoidR {
+        todo!("implement replace_by_topic for FactorIndexService")
+    }
+
+    pub fn remove_by_topic(&self, _topic_id: &TopicId, _tenant_id: &TenantId) -> VoidR {
+        todo!("implement remove_by_topic for FactorIndexService")
+    }
+
+    pub fn find_by_topic(
+        &self,
+        _topic_id: &TopicId,
+        _tenant_id: &TenantId,
+    ) -> StdR<Vec<FactorIndex>> {
+        todo!("implement find_by_topic for FactorIndexService")
+    }
+
+    /// match factor name, label and description, case-insensitive.
+    pub fn find_by_text(&self, _text: &String, _tenant_id: &TenantId) -> StdR<Vec<FactorIndex>> {
+        todo!("implement find_by_text for FactorIndexService")
+    }
+}
+
+pub trait FactorIndexProvider {
+    fn factor_index() -> StdR<Arc<FactorIndexService>> {
+        FactorIndexService::new()
+    }
+}
@@ -0,0 +1,9 @@
+mod analysis_service;
+mod factor_index_builder;
+mod factor_index_service;
+mod pipeline_index_service;
+
+pub use analysis_service::*;
+pub use factor_index_builder::*;
+pub use factor_index_service::*;
+pub use pipeline_index_service::*;
@@ -0,0 +1,62 @@
+use elf_base::{StdR, VoidR};
+use elf_model::{FactorId, PipelineId, PipelineIndex, TenantId, TopicId};
+use std::sync::Arc;
+
+/// TODO pipeline index service using tenant and it's meta datasource (or the global meta datasource)
+///  to persist the pipeline indexes.
+///  pipeline indexes are always replaced by pipeline, never updated one by one.
+pub struct PipelineIndexService;
+
+impl PipelineIndexService {
+    fn new() -> StdR<Arc<Self>> {
+        Ok(Arc::new(Self {}))
+    }
+
+    /// remove all existing indexes of given pipeline, and insert the given ones.
+    pub fn replace_by_pipeline(
+        &self,
+        _pipeline_id: &PipelineId,
+        _tenant_id: &TenantId,
+        _indexes: Vec<PipelineIndex>,
+    ) -> VoidR {
+        todo!("implement replace_by_pipeline for PipelineIndexService")
+    }
+
+    pub fn remove_by_pipeline(&self, _pipeline_id: &PipelineId, _tenant_id: &TenantId) -> VoidR {
+        todo!("implement remove_by_pipeline for PipelineIndexService")
+    }
+
+    pub fn find_by_pipeline(
+        &self,
+        _pipeline_id: &PipelineId,
+        _tenant_id: &TenantId,
+    ) -> StdR<Vec<PipelineIndex>> {
+        todo!("implement find_by_pipeline for PipelineIndexService")
+    }
+
+    /// find indexes by mapping to topic and factor, factor is ignored when it is none.
+    pub fn find_by_mapping_to(
+        &self,
+        _topic_id: &TopicId,
+        _factor_id: Option<&FactorId>,
+        _tenant_id: &TenantId,
+    ) -> StdR<Vec<PipelineIndex>> {
+        todo!("implement find_by_mapping_to for PipelineIndexService")
+    }
+
+    /// find indexes by source from topic and factor, factor is ignored when it is none.
+    pub fn find_by_source_from(
+        &self,
+        _topic_id: &TopicId,
+        _factor_id: Option<&FactorId>,
+        _tenant_id: &TenantId,
+    ) -> StdR<Vec<PipelineIndex>> {
+        todo!("implement find_by_source_from for PipelineIndexService")
+    }
+}
+
+pub trait PipelineIndexProvider {
+    fn pipeline_index() -> StdR<Arc<PipelineIndexService>> {
+        PipelineIndexService::new()
+    }
+}
@@ -0,0 +1,33 @@
+use elf_base::StdR;
+use elf_model::{Catalog, CatalogId, TenantId};
+use std::sync::Arc;
+
+/// TODO catalog meta service using tenant and it's meta datasource (or the global meta datasource)
+///  to find out catalogs.
+pub struct CatalogMetaService;
+
+impl CatalogMetaService {
+    fn new() -> StdR<Arc<Self>> {
+        // TODO maybe find from cache
+        Ok(Arc::new(Self {}))
+    }
+
+    pub fn find_by_id(
+        &self,
+        _catalog_id: &CatalogId,
+        _tenant_id: &TenantId,
+    ) -> StdR<Option<Catalog>> {
+        todo!("implement find_by_id for CatalogMetaService")
+    }
+
+    /// all catalogs of tenant, ordered by name
+    pub fn find_all(&self, _tenant_id: &TenantId) -> StdR<Vec<Catalog>> {
+        todo!("implement find_all for CatalogMetaService")
+    }
+}
+
+pub trait CatalogMetaProvider {
+    fn meta() -> StdR<Arc<CatalogMetaService>> {
+        CatalogMetaService::new()
+    }
+}
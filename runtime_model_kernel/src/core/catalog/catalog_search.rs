@@ -0,0 +1,308 @@
+use elf_base::StringUtils;
+use elf_model::{Catalog, CatalogCriteria, Topic, TopicId};
+use std::collections::HashMap;
+
+/// searchable field of catalog, including the topics in catalog
+pub enum CatalogSearchField {
+    Name,
+    Tag,
+    TopicName,
+    FactorLabel,
+    Description,
+}
+
+impl CatalogSearchField {
+    /// name weighs most, description weighs least
+    pub fn weight(&self) -> u32 {
+        match self {
+            Self::Name => 5,
+            Self::Tag => 4,
+            Self::TopicName => 3,
+            Self::FactorLabel => 2,
+            Self::Description => 1,
+        }
+    }
+}
+
+/// value of field which matches search text,
+/// topic id is given when the value is from topic, or its factor.
+pub struct CatalogSearchMatch {
+    pub field: CatalogSearchField,
+    pub topic_id: Option<TopicId>,
+    pub text: String,
+}
+
+pub struct CatalogSearchHit {
+    pub catalog: Catalog,
+    /// sum of weight of matched values
+    pub score: u32,
+    pub matches: Vec<CatalogSearchMatch>,
+}
+
+/// full-text search on catalogs, case-insensitive.
+/// text is split into terms by whitespace, catalog is hit when each term is contained in any of
+/// - name, tags and description of catalog,
+/// - name and description of topics in catalog,
+/// - label of factors of topics in catalog, or name of factor when it has no label,
+///   and description of factors.
+///
+/// hits are ordered by score descending, and then by catalog name.
+/// each catalog is hit when text is blank, with zero score.
+pub struct CatalogSearch;
+
+impl CatalogSearch {
+    /// blank criteria value is ignored, name of criteria is contained in catalog name case-insensitively.
+    pub fn matches_criteria(criteria: &CatalogCriteria, catalog: &Catalog) -> bool {
+        let is_given = |value: &Option<String>| value.as_ref().is_some_and(|v| v.is_not_blank());
+        if is_given(&criteria.name) {
+            let name = criteria.name.as_deref().unwrap_or_default().to_lowercase();
+            if !catalog
+                .name
+                .as_ref()
+                .is_some_and(|catalog_name| catalog_name.to_lowercase().contains(name.trim()))
+            {
+                return false;
+            }
+        }
+        if is_given(&criteria.topic_id)
+            && !catalog
+                .topic_ids
+                .iter()
+                .flatten()
+                .any(|topic_id| Some(topic_id) == criteria.topic_id.as_ref())
+        {
+            return false;
+        }
+        if is_given(&criteria.tech_owner_id) && catalog.tech_owner_id != criteria.tech_owner_id {
+            return false;
+        }
+        if is_given(&criteria.biz_owner_id) && catalog.biz_owner_id != criteria.biz_owner_id {
+            return false;
+        }
+        true
+    }
+
+    /// searchable values of catalog, topics not given are ignored
+    fn values_of(catalog: &Catalog, topics: &HashMap<TopicId, Topic>) -> Vec<CatalogSearchMatch> {
+        let mut values = vec![];
+        let mut push =
+            |field: CatalogSearchField, topic_id: Option<&TopicId>, text: &Option<String>| {
+                if let Some(text) = text
+                    && text.is_not_blank()
+                {
+                    values.push(CatalogSearchMatch {
+                        field,
+                        topic_id: topic_id.cloned(),
+                        text: text.clone(),
+                    });
+                }
+            };
+        push(CatalogSearchField::Name, None, &catalog.name);
+        for tag in catalog.tags.iter().flatten() {
+            push(CatalogSearchField::Tag, None, &Some(tag.clone()));
+        }
+        push(CatalogSearchField::Description, None, &catalog.description);
+        for topic_id in catalog.topic_ids.iter().flatten() {
+            let Some(topic) = topics.get(topic_id) else {
+                continue;
+            };
+            push(CatalogSearchField::TopicName, Some(topic_id), &topic.name);
+            push(
+                CatalogSearchField::Description,
+                Some(topic_id),
+                &topic.description,
+            );
+            for factor in topic.factors.iter().flatten() {
+                let label = match &factor.label {
+                    Some(label) if label.is_not_blank() => &factor.label,
+                    _ => &factor.name,
+                };
+                push(CatalogSearchField::FactorLabel, Some(topic_id), label);
+                push(
+                    CatalogSearchField::Description,
+                    Some(topic_id),
+                    &factor.description,
+                );
+            }
+        }
+        values
+    }
+
+    pub fn search(
+        catalogs: Vec<Catalog>,
+        topics: &HashMap<TopicId, Topic>,
+        text: &str,
+    ) -> Vec<CatalogSearchHit> {
+        let terms: Vec<String> = text
+            .split_whitespace()
+            .map(|term| term.to_lowercase())
+            .collect();
+        let mut hits: Vec<CatalogSearchHit> = catalogs
+            .into_iter()
+            .filter_map(|catalog| {
+                let values = Self::values_of(&catalog, topics);
+                let lowered: Vec<String> = values
+                    .iter()
+                    .map(|value| value.text.to_lowercase())
+                    .collect();
+                let all_matched = terms
+                    .iter()
+                    .all(|term| lowered.iter().any(|value| value.contains(term)));
+                if !all_matched {
+                    return None;
+                }
+                let matches: Vec<CatalogSearchMatch> = values
+                    .into_iter()
+                    .zip(lowered)
+                    .filter(|(_, value)| terms.iter().any(|term| value.contains(term)))
+                    .map(|(value, _)| value)
+                    .collect();
+                Some(CatalogSearchHit {
+                    score: matches.iter().map(|m| m.field.weight()).sum(),
+                    catalog,
+                    matches,
+                })
+            })
+            .collect();
+        hits.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then_with(|| a.catalog.name.cmp(&b.catalog.name))
+        });
+        hits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CatalogSearch, CatalogSearchField};
+    use elf_model::{Catalog, CatalogCriteria, Factor, Topic};
+    use std::collections::HashMap;
+
+    fn catalogs() -> Vec<Catalog> {
+        vec![
+            Catalog::new()
+                .catalog_id(String::from("c1"))
+                .name(String::from("Sales"))
+                .topic_ids(vec![String::from("t1")])
+                .tech_owner_id(String::from("u1"))
+                .biz_owner_id(String::from("u2"))
+                .tags(vec![String::from("finance")]),
+            Catalog::new()
+                .catalog_id(String::from("c2"))
+                .name(String::from("Customers"))
+                .topic_ids(vec![String::from("t2")])
+                .tech_owner_id(String::from("u1"))
+                .description(String::from("customers who placed sales orders")),
+            Catalog::new()
+                .catalog_id(String::from("c3"))
+                .name(String::from("Logistics"))
+                .biz_owner_id(String::from("u3")),
+        ]
+    }
+
+    fn topics() -> HashMap<String, Topic> {
+        let topic = |topic_id: &str, name: &str, factors: Vec<Factor>| {
+            (
+                topic_id.to_string(),
+                Topic::new()
+                    .topic_id(topic_id.to_string())
+                    .name(name.to_string())
+                    .factors(factors),
+            )
+        };
+        HashMap::from([
+            topic(
+                "t1",
+                "orders",
+                vec![
+                    Factor::new()
+                        .name(String::from("amount"))
+                        .label(String::from("Order Amount")),
+                    Factor::new().name(String::from("customerId")),
+                ],
+            ),
+            topic(
+                "t2",
+                "customer",
+                vec![Factor::new()
+                    .name(String::from("name"))
+                    .description(String::from("legal name of customer"))],
+            ),
+        ])
+    }
+
+    fn ids(text: &str) -> Vec<String> {
+        CatalogSearch::search(catalogs(), &topics(), text)
+            .into_iter()
+            .map(|hit| hit.catalog.catalog_id.unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_search() {
+        // catalog name weighs more than description
+        assert_eq!(ids("SALES"), vec!["c1", "c2"]);
+        // factor label, and factor name when no label
+        assert_eq!(ids("amount"), vec!["c1"]);
+        assert_eq!(ids("customerid"), vec!["c1"]);
+        // each term must be matched
+        assert_eq!(ids("customer legal"), vec!["c2"]);
+        assert_eq!(ids("finance orders"), vec!["c1"]);
+        assert!(ids("finance legal").is_empty());
+        // blank text hits all, ordered by name
+        assert_eq!(ids(" "), vec!["c2", "c3", "c1"]);
+
+        let hits = CatalogSearch::search(catalogs(), &topics(), "order");
+        assert_eq!(hits.len(), 2);
+        let hit = &hits[0];
+        assert_eq!(hit.catalog.catalog_id.as_deref(), Some("c1"));
+        // topic name and factor label
+        assert_eq!(hit.score, 5);
+        assert!(hit
+            .matches
+            .iter()
+            .all(|m| m.topic_id.as_deref() == Some("t1")));
+        assert!(matches!(
+            hit.matches[0].field,
+            CatalogSearchField::TopicName
+        ));
+    }
+
+    #[test]
+    fn test_matches_criteria() {
+        let filter = |criteria: CatalogCriteria| -> Vec<String> {
+            catalogs()
+                .into_iter()
+                .filter(|catalog| CatalogSearch::matches_criteria(&criteria, catalog))
+                .map(|catalog| catalog.catalog_id.unwrap())
+                .collect()
+        };
+        assert_eq!(filter(CatalogCriteria::new()).len(), 3);
+        assert_eq!(
+            filter(CatalogCriteria::new().tech_owner_id(String::from("u1"))),
+            vec!["c1", "c2"]
+        );
+        assert_eq!(
+            filter(
+                CatalogCriteria::new()
+                    .tech_owner_id(String::from("u1"))
+                    .topic_id(String::from("t2"))
+            ),
+            vec!["c2"]
+        );
+        assert_eq!(
+            filter(CatalogCriteria::new().biz_owner_id(String::from("u3"))),
+            vec!["c3"]
+        );
+        assert_eq!(
+            filter(CatalogCriteria::new().name(String::from("cust"))),
+            vec!["c2"]
+        );
+        // blank is ignored
+        assert_eq!(
+            filter(CatalogCriteria::new().name(String::from(" "))).len(),
+            3
+        );
+    }
+}
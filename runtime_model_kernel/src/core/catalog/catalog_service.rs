@@ -0,0 +1,142 @@
+use crate::{
+    CatalogMetaProvider, CatalogSearch, CatalogSearchHit, RuntimeModelKernelErrorCode,
+    TopicMetaProvider, TopicService, UserMetaProvider, UserService,
+};
+use elf_base::{ErrorCode, StdR, StringUtils};
+use elf_model::{Catalog, CatalogCriteria, CatalogId, TenantId, Topic, TopicId, User, UserId};
+use std::collections::HashMap;
+
+/// owner of data, resolved from user, password is never included.
+/// only user id is given when user not found.
+pub struct DataOwner {
+    pub user_id: UserId,
+    pub name: Option<String>,
+    pub nick_name: Option<String>,
+    pub email: Option<String>,
+    pub active: bool,
+}
+
+impl DataOwner {
+    pub fn of(user_id: &UserId, user: Option<User>) -> Self {
+        match user {
+            Some(user) => Self {
+                user_id: user_id.clone(),
+                name: user.name,
+                nick_name: user.nick_name,
+                email: user.email,
+                active: user.is_active.unwrap_or(false),
+            },
+            _ => Self {
+                user_id: user_id.clone(),
+                name: None,
+                nick_name: None,
+                email: None,
+                active: false,
+            },
+        }
+    }
+}
+
+/// owners of catalog, and so the topics in it.
+pub struct DataOwnership {
+    pub catalog_id: CatalogId,
+    pub catalog_name: Option<String>,
+    pub tech_owner: Option<DataOwner>,
+    pub biz_owner: Option<DataOwner>,
+}
+
+impl DataOwnership {
+    /// tech owner first, same user is returned once
+    pub fn owners(&self) -> Vec<&DataOwner> {
+        let mut owners: Vec<&DataOwner> = vec![];
+        for owner in [&self.tech_owner, &self.biz_owner].into_iter().flatten() {
+            if !owners.iter().any(|o| o.user_id == owner.user_id) {
+                owners.push(owner);
+            }
+        }
+        owners
+    }
+}
+
+/// search catalogs of tenant, and tell who owns the data, owners are resolved through users.
+pub struct CatalogService;
+
+impl CatalogMetaProvider for CatalogService {}
+
+impl CatalogService {
+    /// catalogs filtered by criteria, and then searched by text, see [CatalogSearch].
+    pub fn search(
+        text: &str,
+        criteria: &CatalogCriteria,
+        tenant_id: &TenantId,
+    ) -> StdR<Vec<CatalogSearchHit>> {
+        let catalogs: Vec<Catalog> = Self::meta()?
+            .find_all(tenant_id)?
+            .into_iter()
+            .filter(|catalog| CatalogSearch::matches_criteria(criteria, catalog))
+            .collect();
+        let topics = if text.is_blank() {
+            HashMap::new()
+        } else {
+            Self::topics_of(&catalogs, tenant_id)?
+        };
+        Ok(CatalogSearch::search(catalogs, &topics, text))
+    }
+
+    fn topics_of(catalogs: &[Catalog], tenant_id: &TenantId) -> StdR<HashMap<TopicId, Topic>> {
+        let topic_meta = TopicService::meta()?;
+        let mut topics = HashMap::new();
+        for topic_id in catalogs
+            .iter()
+            .flat_map(|catalog| catalog.topic_ids.iter().flatten())
+        {
+            if !topics.contains_key(topic_id) {
+                topics.insert(
+                    topic_id.clone(),
+                    topic_meta.find_by_id(topic_id, tenant_id)?,
+                );
+            }
+        }
+        Ok(topics)
+    }
+
+    fn owner_of(user_id: &Option<UserId>, tenant_id: &TenantId) -> StdR<Option<DataOwner>> {
+        match user_id {
+            Some(user_id) if user_id.is_not_blank() => {
+                let user = UserService::meta()?.find_by_id(user_id, tenant_id)?;
+                Ok(Some(DataOwner::of(user_id, user)))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    pub fn ownership_of(catalog: &Catalog, tenant_id: &TenantId) -> StdR<DataOwnership> {
+        Ok(DataOwnership {
+            catalog_id: catalog.catalog_id.clone().unwrap_or_default(),
+            catalog_name: catalog.name.clone(),
+            tech_owner: Self::owner_of(&catalog.tech_owner_id, tenant_id)?,
+            biz_owner: Self::owner_of(&catalog.biz_owner_id, tenant_id)?,
+        })
+    }
+
+    pub fn ownership_by_id(catalog_id: &CatalogId, tenant_id: &TenantId) -> StdR<DataOwnership> {
+        match Self::meta()?.find_by_id(catalog_id, tenant_id)? {
+            Some(catalog) => Self::ownership_of(&catalog, tenant_id),
+            _ => RuntimeModelKernelErrorCode::CatalogNotFound.msg(format!(
+                "Catalog[{}] of tenant[{}] not found.",
+                catalog_id, tenant_id
+            )),
+        }
+    }
+
+    /// ownerships of catalogs which contain the topic, empty when topic is not in any catalog.
+    pub fn owners_of_topic(topic_id: &TopicId, tenant_id: &TenantId) -> StdR<Vec<DataOwnership>> {
+        let criteria = CatalogCriteria::new().topic_id(topic_id.clone());
+        Self::meta()?
+            .find_all(tenant_id)?
+            .iter()
+            .filter(|catalog| CatalogSearch::matches_criteria(&criteria, catalog))
+            .map(|catalog| Self::ownership_of(catalog, tenant_id))
+            .collect()
+    }
+}
@@ -0,0 +1,7 @@
+mod catalog_meta_service;
+mod catalog_search;
+mod catalog_service;
+
+pub use catalog_meta_service::*;
+pub use catalog_search::*;
+pub use catalog_service::*;
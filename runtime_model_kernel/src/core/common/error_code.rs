@@ -53,6 +53,38 @@ pub enum RuntimeModelKernelErrorCode {
 
     EncryptNotSupport,
     AesCrypto,
+
+    MonitorRuleCodeMissed,
+    MonitorRuleFactorMissed,
+    MonitorRuleFactorNotFound,
+    MonitorRuleParameterMissed,
+    MonitorRuleRegexpInvalid,
+    MonitorRuleTopicNotFound,
+
+    SystemTopicNameMissed,
+    SystemTopicSerialize,
+    SystemPackageVersionInvalid,
+    SystemPackageVersionDowngrade,
+
+    TopicSnapshotFrequencyMissed,
+    TopicSnapshotScheduleInvalid,
+
+    SubscriptionEventFrequencyMissed,
+    SubscriptionEventScheduleInvalid,
+
+    EnumNotFound,
+
+    MetaPackageInvalid,
+    MetaPackageTupleIdMissed,
+    MetaPackageTupleTypeInvalid,
+    MetaPackageOperationInvalid,
+
+    OperationContentInvalid,
+    OperationTupleNotFound,
+    OperationRestoreNotSupported,
+    OperationVersionNotFound,
+
+    CatalogNotFound,
 }
 
 impl ErrorCode for RuntimeModelKernelErrorCode {
@@ -108,6 +140,38 @@ impl ErrorCode for RuntimeModelKernelErrorCode {
 
             Self::EncryptNotSupport => "RTMK-00800",
             Self::AesCrypto => "RTMK-00801",
+
+            Self::MonitorRuleCodeMissed => "RTMK-00900",
+            Self::MonitorRuleFactorMissed => "RTMK-00901",
+            Self::MonitorRuleFactorNotFound => "RTMK-00902",
+            Self::MonitorRuleParameterMissed => "RTMK-00903",
+            Self::MonitorRuleRegexpInvalid => "RTMK-00904",
+            Self::MonitorRuleTopicNotFound => "RTMK-00905",
+
+            Self::SystemTopicNameMissed => "RTMK-01000",
+            Self::SystemTopicSerialize => "RTMK-01001",
+            Self::SystemPackageVersionInvalid => "RTMK-01002",
+            Self::SystemPackageVersionDowngrade => "RTMK-01003",
+
+            Self::TopicSnapshotFrequencyMissed => "RTMK-01100",
+            Self::TopicSnapshotScheduleInvalid => "RTMK-01101",
+
+            Self::SubscriptionEventFrequencyMissed => "RTMK-01200",
+            Self::SubscriptionEventScheduleInvalid => "RTMK-01201",
+
+            Self::EnumNotFound => "RTMK-01300",
+
+            Self::MetaPackageInvalid => "RTMK-01400",
+            Self::MetaPackageTupleIdMissed => "RTMK-01401",
+            Self::MetaPackageTupleTypeInvalid => "RTMK-01402",
+            Self::MetaPackageOperationInvalid => "RTMK-01403",
+
+            Self::OperationContentInvalid => "RTMK-01500",
+            Self::OperationTupleNotFound => "RTMK-01501",
+            Self::OperationRestoreNotSupported => "RTMK-01502",
+            Self::OperationVersionNotFound => "RTMK-01503",
+
+            Self::CatalogNotFound => "RTMK-01600",
         }
     }
 }
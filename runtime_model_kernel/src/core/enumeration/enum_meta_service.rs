@@ -0,0 +1,24 @@
+use elf_base::StdR;
+use elf_model::{Enum, EnumId, TenantId};
+use std::sync::Arc;
+
+/// TODO enum meta service using tenant and it's meta datasource (or the global meta datasource)
+///  to find out enum meta, with items.
+pub struct EnumMetaService;
+
+impl EnumMetaService {
+    fn new() -> StdR<Arc<Self>> {
+        // TODO maybe find from cache
+        Ok(Arc::new(Self {}))
+    }
+
+    pub fn find_by_id(&self, _enum_id: &EnumId, _tenant_id: &TenantId) -> StdR<Option<Enum>> {
+        todo!("implement find_by_id for EnumMetaService")
+    }
+}
+
+pub trait EnumMetaProvider {
+    fn meta() -> StdR<Arc<EnumMetaService>> {
+        EnumMetaService::new()
+    }
+}
@@ -0,0 +1,5 @@
+use crate::EnumMetaProvider;
+
+pub struct EnumService;
+
+impl EnumMetaProvider for EnumService {}
@@ -0,0 +1,5 @@
+mod enum_meta_service;
+mod enum_service;
+
+pub use enum_meta_service::*;
+pub use enum_service::*;
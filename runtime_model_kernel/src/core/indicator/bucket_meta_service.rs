@@ -0,0 +1,24 @@
+use elf_base::StdR;
+use elf_model::{Bucket, BucketId, TenantId};
+use std::sync::Arc;
+
+/// TODO bucket meta service using tenant and it's meta datasource (or the global meta datasource)
+///  to find out bucket meta.
+pub struct BucketMetaService;
+
+impl BucketMetaService {
+    fn new() -> StdR<Arc<Self>> {
+        // TODO maybe find from cache
+        Ok(Arc::new(Self {}))
+    }
+
+    pub fn find_by_id(&self, _bucket_id: &BucketId, _tenant_id: &TenantId) -> StdR<Option<Bucket>> {
+        todo!("implement find_by_id for BucketMetaService")
+    }
+}
+
+pub trait BucketMetaProvider {
+    fn bucket() -> StdR<Arc<BucketMetaService>> {
+        BucketMetaService::new()
+    }
+}
@@ -0,0 +1,28 @@
+use elf_base::StdR;
+use elf_model::{Indicator, IndicatorId, TenantId};
+use std::sync::Arc;
+
+/// TODO indicator meta service using tenant and it's meta datasource (or the global meta datasource)
+///  to find out indicator meta.
+pub struct IndicatorMetaService;
+
+impl IndicatorMetaService {
+    fn new() -> StdR<Arc<Self>> {
+        // TODO maybe find from cache
+        Ok(Arc::new(Self {}))
+    }
+
+    pub fn find_by_id(
+        &self,
+        _indicator_id: &IndicatorId,
+        _tenant_id: &TenantId,
+    ) -> StdR<Option<Indicator>> {
+        todo!("implement find_by_id for IndicatorMetaService")
+    }
+}
+
+pub trait IndicatorMetaProvider {
+    fn meta() -> StdR<Arc<IndicatorMetaService>> {
+        IndicatorMetaService::new()
+    }
+}
@@ -0,0 +1,5 @@
+mod bucket_meta_service;
+mod indicator_meta_service;
+
+pub use bucket_meta_service::*;
+pub use indicator_meta_service::*;
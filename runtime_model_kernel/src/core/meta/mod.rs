@@ -1,3 +1,7 @@
 mod key_store_service;
+mod operation_meta_service;
+mod package_version_service;
 
 pub use key_store_service::*;
+pub use operation_meta_service::*;
+pub use package_version_service::*;
@@ -0,0 +1,33 @@
+use crate::OperationCriteria;
+use elf_base::{StdR, VoidR};
+use elf_model::{Operation, TenantId};
+use std::sync::Arc;
+
+/// TODO operation meta service using tenant and it's meta datasource (or the global meta datasource)
+///  to persist the operations of tuples.
+pub struct OperationMetaService;
+
+impl OperationMetaService {
+    fn new() -> StdR<Arc<Self>> {
+        Ok(Arc::new(Self {}))
+    }
+
+    pub fn create(&self, _operation: &Operation) -> VoidR {
+        todo!("implement create for OperationMetaService")
+    }
+
+    /// returns operations match the given criteria, ordered by created time ascending
+    pub fn find(
+        &self,
+        _criteria: &OperationCriteria,
+        _tenant_id: &TenantId,
+    ) -> StdR<Vec<Operation>> {
+        todo!("implement find for OperationMetaService")
+    }
+}
+
+pub trait OperationMetaProvider {
+    fn operation_meta() -> StdR<Arc<OperationMetaService>> {
+        OperationMetaService::new()
+    }
+}
@@ -0,0 +1,29 @@
+use elf_base::{StdR, VoidR};
+use elf_model::{PackageVersion, TenantId};
+use std::sync::Arc;
+
+/// TODO package version service using tenant and it's meta datasource (or the global meta datasource)
+///  to persist the installed version of system package.
+///  there is only one package version for each tenant.
+pub struct PackageVersionService;
+
+impl PackageVersionService {
+    fn new() -> StdR<Arc<Self>> {
+        Ok(Arc::new(Self {}))
+    }
+
+    pub fn find_by_tenant_id(&self, _tenant_id: &TenantId) -> StdR<Option<PackageVersion>> {
+        todo!("implement find_by_tenant_id for PackageVersionService")
+    }
+
+    /// create when version id not exists, otherwise update.
+    pub fn save(&self, _version: &PackageVersion) -> VoidR {
+        todo!("implement save for PackageVersionService")
+    }
+}
+
+pub trait PackageVersionProvider {
+    fn package_version() -> StdR<Arc<PackageVersionService>> {
+        PackageVersionService::new()
+    }
+}
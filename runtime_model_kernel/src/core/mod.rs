@@ -1,15 +1,43 @@
+mod analysis;
+mod catalog;
 mod common;
 mod encrypt;
+mod enumeration;
+mod indicator;
 mod meta;
+mod monitor;
+mod objective;
+mod operation;
+mod package;
 mod pipeline;
+mod schedule;
 mod schema;
+mod snapshot;
+mod subject;
+mod subscription;
 mod system;
+mod system_topic;
 mod topic;
+mod user;
 
+pub use analysis::*;
+pub use catalog::*;
 pub use common::*;
 pub use encrypt::*;
+pub use enumeration::*;
+pub use indicator::*;
 pub use meta::*;
+pub use monitor::*;
+pub use objective::*;
+pub use operation::*;
+pub use package::*;
 pub use pipeline::*;
+pub use schedule::*;
 pub use schema::*;
+pub use snapshot::*;
+pub use subject::*;
+pub use subscription::*;
 pub use system::*;
+pub use system_topic::*;
 pub use topic::*;
+pub use user::*;
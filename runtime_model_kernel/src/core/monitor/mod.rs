@@ -0,0 +1,17 @@
+mod monitor_job_lock_service;
+mod monitor_rule_check;
+mod monitor_rule_checker;
+mod monitor_rule_engine;
+mod monitor_rule_meta_service;
+mod monitor_rule_service;
+mod monitor_rule_values;
+mod monitor_rule_window;
+
+pub use monitor_job_lock_service::*;
+pub use monitor_rule_check::*;
+pub use monitor_rule_checker::*;
+pub use monitor_rule_engine::*;
+pub use monitor_rule_meta_service::*;
+pub use monitor_rule_service::*;
+pub use monitor_rule_values::*;
+pub use monitor_rule_window::*;
@@ -0,0 +1,31 @@
+use elf_base::{StdR, VoidR};
+use elf_model::{MonitorJobLock, MonitorJobLockId, MonitorJobLockStatus};
+use std::sync::Arc;
+
+/// TODO monitor job lock service using tenant and it's meta datasource (or the global meta datasource)
+///  to persist the locks.
+///  lock is unique by [tenant_id, topic_id, frequency, process_date].
+pub struct MonitorJobLockService;
+
+impl MonitorJobLockService {
+    fn new() -> StdR<Arc<Self>> {
+        Ok(Arc::new(Self {}))
+    }
+
+    /// try to create the given lock,
+    /// - Ok(true) -> lock created, current node owns the job,
+    /// - Ok(false) -> lock exists, job is owned by another run.
+    pub fn try_lock(&self, _lock: &MonitorJobLock) -> StdR<bool> {
+        todo!("implement try_lock for MonitorJobLockService")
+    }
+
+    pub fn finish(&self, _lock_id: &MonitorJobLockId, _status: MonitorJobLockStatus) -> VoidR {
+        todo!("implement finish for MonitorJobLockService")
+    }
+}
+
+pub trait MonitorJobLockProvider {
+    fn job_lock() -> StdR<Arc<MonitorJobLockService>> {
+        MonitorJobLockService::new()
+    }
+}
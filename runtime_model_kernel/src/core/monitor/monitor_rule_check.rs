@@ -0,0 +1,41 @@
+use elf_model::TopicData;
+
+/// result of checking one monitor rule
+#[derive(Debug, PartialEq)]
+pub enum MonitorRuleCheck {
+    /// rule cannot be checked by scanning data, such as structure or type mismatching,
+    /// which are detected when data is prepared.
+    NotApplicable,
+    Passed,
+    /// issue detected, with count of occurrences
+    Detected(i32),
+}
+
+impl MonitorRuleCheck {
+    pub fn of_count(count: usize) -> Self {
+        if count == 0 {
+            Self::Passed
+        } else {
+            Self::Detected(count as i32)
+        }
+    }
+
+    pub fn of_bool(detected: bool) -> Self {
+        if detected {
+            Self::Detected(1)
+        } else {
+            Self::Passed
+        }
+    }
+}
+
+/// data of one window, to check rules of one topic
+pub struct MonitorRuleCheckData<'a> {
+    /// rows updated in window, in insert order
+    pub rows: &'a Vec<TopicData>,
+    /// count of rows inserted in window
+    pub inserted_count: u64,
+    /// count of rows inserted in window of another topic,
+    /// only for [elf_model::MonitorRuleCode::RowsCountMismatchAndAnother]
+    pub another_inserted_count: Option<u64>,
+}
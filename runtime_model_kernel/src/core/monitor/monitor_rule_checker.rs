@@ -0,0 +1,667 @@
+use crate::{
+    MonitorRuleCheck, MonitorRuleCheckData, MonitorRuleValues, RuntimeModelKernelErrorCode,
+    TopicSchema,
+};
+use bigdecimal::BigDecimal;
+use elf_base::{ErrorCode, StdR, StringUtils};
+use elf_model::{
+    MonitorRule, MonitorRuleCode, MonitorRuleCompareOperator, MonitorRuleParameters, TopicDataValue,
+};
+use regex::Regex;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// check one monitor rule against the data of one window.
+pub struct MonitorRuleChecker<'a> {
+    rule: &'a MonitorRule,
+    topic_schema: &'a TopicSchema,
+    data: &'a MonitorRuleCheckData<'a>,
+}
+
+impl<'a> MonitorRuleChecker<'a> {
+    pub fn check(
+        rule: &'a MonitorRule,
+        topic_schema: &'a TopicSchema,
+        data: &'a MonitorRuleCheckData<'a>,
+    ) -> StdR<MonitorRuleCheck> {
+        let checker = Self {
+            rule,
+            topic_schema,
+            data,
+        };
+        let Some(code) = &rule.code else {
+            return RuntimeModelKernelErrorCode::MonitorRuleCodeMissed
+                .msg(format!("{} must have a code.", checker.rule_name()));
+        };
+
+        match code {
+            // detected when data prepared, not by scanning data
+            MonitorRuleCode::RawMismatchStructure
+            | MonitorRuleCode::FactorMismatchEnum
+            | MonitorRuleCode::FactorMismatchType
+            | MonitorRuleCode::FactorMismatchDateType
+            | MonitorRuleCode::FactorUseCast => Ok(MonitorRuleCheck::NotApplicable),
+            // topic level
+            MonitorRuleCode::RowsNotExists => {
+                Ok(MonitorRuleCheck::of_bool(data.inserted_count == 0))
+            }
+            MonitorRuleCode::RowsNoChange => Ok(MonitorRuleCheck::of_bool(data.rows.is_empty())),
+            MonitorRuleCode::RowsCountMismatchAndAnother => {
+                checker.rows_count_mismatch_and_another()
+            }
+            // for all factor types
+            MonitorRuleCode::FactorIsEmpty => checker.factor_is_empty(),
+            MonitorRuleCode::FactorEmptyOverCoverage => checker.factor_empty_over_coverage(),
+            MonitorRuleCode::FactorCommonValueOverCoverage => {
+                checker.factor_common_value_over_coverage()
+            }
+            // for number type
+            MonitorRuleCode::FactorBreaksMonotoneIncreasing => {
+                checker.factor_breaks_monotone(Ordering::Less)
+            }
+            MonitorRuleCode::FactorBreaksMonotoneDecreasing => {
+                checker.factor_breaks_monotone(Ordering::Greater)
+            }
+            MonitorRuleCode::FactorNotInRange => checker.factor_not_in_range(),
+            MonitorRuleCode::FactorMaxNotInRange => {
+                checker.statistic_not_in_range(|sorted| sorted.last().cloned())
+            }
+            MonitorRuleCode::FactorMinNotInRange => {
+                checker.statistic_not_in_range(|sorted| sorted.first().cloned())
+            }
+            MonitorRuleCode::FactorAvgNotInRange => {
+                checker.statistic_not_in_range(MonitorRuleValues::avg)
+            }
+            MonitorRuleCode::FactorMedianNotInRange => {
+                checker.statistic_not_in_range(MonitorRuleValues::median)
+            }
+            MonitorRuleCode::FactorQuantileNotInRange => {
+                let quantile = checker.param(|p| p.quantile, "quantile")?;
+                checker.statistic_not_in_range(|sorted| {
+                    MonitorRuleValues::quantile(sorted, quantile.max(0) as u32)
+                })
+            }
+            MonitorRuleCode::FactorStdevNotInRange => {
+                checker.statistic_not_in_range(MonitorRuleValues::stdev)
+            }
+            MonitorRuleCode::FactorCommonValueNotInRange => {
+                checker.factor_common_value_not_in_range()
+            }
+            // for string type
+            MonitorRuleCode::FactorIsBlank => checker.factor_is_blank(),
+            MonitorRuleCode::FactorStringLengthMismatch => checker.factor_string_length_mismatch(),
+            MonitorRuleCode::FactorStringLengthNotInRange => {
+                checker.factor_string_length_not_in_range()
+            }
+            MonitorRuleCode::FactorMatchRegexp => checker.factor_regexp(true),
+            MonitorRuleCode::FactorMismatchRegexp => checker.factor_regexp(false),
+            // for 2 factors
+            MonitorRuleCode::FactorAndAnother => checker.factor_and_another(),
+        }
+    }
+}
+
+/// helpers
+impl<'a> MonitorRuleChecker<'a> {
+    fn rule_name(&self) -> String {
+        format!(
+            "Monitor rule[rule_id={}, code={}]",
+            self.rule.rule_id.as_deref().unwrap_or(""),
+            self.rule
+                .code
+                .as_ref()
+                .map(|c| c.to_string())
+                .unwrap_or_default()
+        )
+    }
+
+    fn param<T, F>(&self, get: F, name: &str) -> StdR<T>
+    where
+        F: Fn(&MonitorRuleParameters) -> Option<T>,
+    {
+        match self.rule.params.as_ref().and_then(get) {
+            Some(value) => Ok(value),
+            None => RuntimeModelKernelErrorCode::MonitorRuleParameterMissed.msg(format!(
+                "{} must have parameter[{}].",
+                self.rule_name(),
+                name
+            )),
+        }
+    }
+
+    /// at least one of min and max must be given
+    fn range(&self) -> StdR<(Option<BigDecimal>, Option<BigDecimal>)> {
+        let params = self.rule.params.as_ref();
+        let min = params.and_then(|p| p.min).map(BigDecimal::from);
+        let max = params.and_then(|p| p.max).map(BigDecimal::from);
+        if min.is_none() && max.is_none() {
+            RuntimeModelKernelErrorCode::MonitorRuleParameterMissed.msg(format!(
+                "{} must have parameter[min] or [max].",
+                self.rule_name()
+            ))
+        } else {
+            Ok((min, max))
+        }
+    }
+
+    fn in_range(value: &BigDecimal, (min, max): &(Option<BigDecimal>, Option<BigDecimal>)) -> bool {
+        min.as_ref().map(|min| value >= min).unwrap_or(true)
+            && max.as_ref().map(|max| value <= max).unwrap_or(true)
+    }
+
+    fn factor_name_of(&self, factor_id: Option<&String>, key: &str) -> StdR<&Arc<String>> {
+        let Some(factor_id) = factor_id else {
+            return RuntimeModelKernelErrorCode::MonitorRuleFactorMissed.msg(format!(
+                "{} must have {}.",
+                self.rule_name(),
+                key
+            ));
+        };
+        match self.topic_schema.factor_by_id(factor_id) {
+            Some(factor) => Ok(&factor.name),
+            None => RuntimeModelKernelErrorCode::MonitorRuleFactorNotFound.msg(format!(
+                "Factor[{}] of {} not found in topic[{}].",
+                factor_id,
+                self.rule_name(),
+                self.topic_schema.name()
+            )),
+        }
+    }
+
+    fn factor_name(&self) -> StdR<&Arc<String>> {
+        self.factor_name_of(self.rule.factor_id.as_ref(), "factor")
+    }
+
+    /// non-empty values of factor in all rows
+    fn values(&self) -> StdR<Vec<&'a TopicDataValue>> {
+        let name = self.factor_name()?;
+        Ok(self
+            .data
+            .rows
+            .iter()
+            .flat_map(|row| MonitorRuleValues::of(row, name))
+            .filter(|value| !MonitorRuleValues::is_empty(value))
+            .collect())
+    }
+
+    /// non-empty values of factor in all rows which can be cast to decimal, keep the row order
+    fn decimals(&self) -> StdR<Vec<BigDecimal>> {
+        Ok(self
+            .values()?
+            .into_iter()
+            .filter_map(MonitorRuleValues::to_decimal)
+            .collect())
+    }
+
+    fn strings(&self) -> StdR<Vec<String>> {
+        Ok(self
+            .values()?
+            .into_iter()
+            .filter_map(MonitorRuleValues::to_str)
+            .collect())
+    }
+
+    /// value frequencies, sorted by count descending
+    fn frequencies<K, V>(values: Vec<V>, key: K) -> Vec<(V, usize)>
+    where
+        K: Fn(&V) -> String,
+    {
+        let mut counts: HashMap<String, (V, usize)> = HashMap::new();
+        for value in values {
+            counts
+                .entry(key(&value))
+                .and_modify(|(_, count)| *count += 1)
+                .or_insert((value, 1));
+        }
+        let mut frequencies: Vec<(V, usize)> = counts.into_values().collect();
+        frequencies.sort_by(|(_, a), (_, b)| b.cmp(a));
+        frequencies
+    }
+}
+
+/// rules
+impl<'a> MonitorRuleChecker<'a> {
+    fn rows_count_mismatch_and_another(&self) -> StdR<MonitorRuleCheck> {
+        match self.data.another_inserted_count {
+            Some(count) => Ok(MonitorRuleCheck::of_bool(count != self.data.inserted_count)),
+            None => RuntimeModelKernelErrorCode::MonitorRuleParameterMissed.msg(format!(
+                "{} must have rows count of another topic.",
+                self.rule_name()
+            )),
+        }
+    }
+
+    fn count_empty_rows(&self) -> StdR<usize> {
+        let name = self.factor_name()?;
+        Ok(self
+            .data
+            .rows
+            .iter()
+            .filter(|row| {
+                MonitorRuleValues::of(row, name)
+                    .into_iter()
+                    .all(MonitorRuleValues::is_empty)
+            })
+            .count())
+    }
+
+    fn factor_is_empty(&self) -> StdR<MonitorRuleCheck> {
+        Ok(MonitorRuleCheck::of_count(self.count_empty_rows()?))
+    }
+
+    fn factor_empty_over_coverage(&self) -> StdR<MonitorRuleCheck> {
+        let coverage_rate = self.param(|p| p.coverage_rate, "coverageRate")?;
+        let total = self.data.rows.len();
+        if total == 0 {
+            return Ok(MonitorRuleCheck::Passed);
+        }
+        let empty = self.count_empty_rows()?;
+        if empty * 100 > coverage_rate.max(0) as usize * total {
+            Ok(MonitorRuleCheck::Detected(empty as i32))
+        } else {
+            Ok(MonitorRuleCheck::Passed)
+        }
+    }
+
+    fn factor_common_value_over_coverage(&self) -> StdR<MonitorRuleCheck> {
+        let coverage_rate = self.param(|p| p.coverage_rate, "coverageRate")?;
+        let values = self.strings()?;
+        let total = values.len();
+        let frequencies = Self::frequencies(values, |s| s.clone());
+        match frequencies.first() {
+            Some((_, count)) if count * 100 > coverage_rate.max(0) as usize * total => {
+                Ok(MonitorRuleCheck::Detected(*count as i32))
+            }
+            _ => Ok(MonitorRuleCheck::Passed),
+        }
+    }
+
+    /// count the breaks, break means the comparison of next value and previous value is given ordering
+    fn factor_breaks_monotone(&self, breaks_on: Ordering) -> StdR<MonitorRuleCheck> {
+        let decimals = self.decimals()?;
+        let breaks = decimals
+            .windows(2)
+            .filter(|pair| pair[1].cmp(&pair[0]) == breaks_on)
+            .count();
+        Ok(MonitorRuleCheck::of_count(breaks))
+    }
+
+    fn factor_not_in_range(&self) -> StdR<MonitorRuleCheck> {
+        let range = self.range()?;
+        let count = self
+            .decimals()?
+            .iter()
+            .filter(|value| !Self::in_range(value, &range))
+            .count();
+        Ok(MonitorRuleCheck::of_count(count))
+    }
+
+    /// statistic is computed on sorted values, passed when no value.
+    fn statistic_not_in_range<S>(&self, statistic: S) -> StdR<MonitorRuleCheck>
+    where
+        S: Fn(&[BigDecimal]) -> Option<BigDecimal>,
+    {
+        let range = self.range()?;
+        let mut decimals = self.decimals()?;
+        decimals.sort();
+        match statistic(&decimals) {
+            Some(value) => Ok(MonitorRuleCheck::of_bool(!Self::in_range(&value, &range))),
+            None => Ok(MonitorRuleCheck::Passed),
+        }
+    }
+
+    /// common values are the most frequent values, which cover the [aggregation] percentage of values.
+    fn factor_common_value_not_in_range(&self) -> StdR<MonitorRuleCheck> {
+        let aggregation = self.param(|p| p.aggregation, "aggregation")?;
+        let range = self.range()?;
+        let decimals = self.decimals()?;
+        let total = decimals.len();
+        let frequencies = Self::frequencies(decimals, |d| d.normalized().to_plain_string());
+
+        let mut covered = 0;
+        let mut count = 0;
+        for (value, frequency) in frequencies {
+            if covered * 100 >= aggregation.max(0) as usize * total {
+                break;
+            }
+            covered += frequency;
+            if !Self::in_range(&value, &range) {
+                count += 1;
+            }
+        }
+        Ok(MonitorRuleCheck::of_count(count))
+    }
+
+    fn factor_is_blank(&self) -> StdR<MonitorRuleCheck> {
+        let name = self.factor_name()?;
+        let count = self
+            .data
+            .rows
+            .iter()
+            .flat_map(|row| MonitorRuleValues::of(row, name))
+            .filter(|value| matches!(value, TopicDataValue::Str(s) if s.is_blank()))
+            .count();
+        Ok(MonitorRuleCheck::of_count(count))
+    }
+
+    fn factor_string_length_mismatch(&self) -> StdR<MonitorRuleCheck> {
+        let length = self.param(|p| p.length, "length")?.max(0) as usize;
+        let count = self
+            .strings()?
+            .iter()
+            .filter(|s| s.chars().count() != length)
+            .count();
+        Ok(MonitorRuleCheck::of_count(count))
+    }
+
+    fn factor_string_length_not_in_range(&self) -> StdR<MonitorRuleCheck> {
+        let range = self.range()?;
+        let count = self
+            .strings()?
+            .iter()
+            .filter(|s| !Self::in_range(&BigDecimal::from(s.chars().count() as u64), &range))
+            .count();
+        Ok(MonitorRuleCheck::of_count(count))
+    }
+
+    /// - matches is true, count the values which match the regexp,
+    /// - matches is false, count the values which mismatch the regexp.
+    fn factor_regexp(&self, matches: bool) -> StdR<MonitorRuleCheck> {
+        let regexp = self.param(|p| p.regexp.clone(), "regexp")?;
+        let regex = Regex::new(&regexp).map_err(|e| {
+            RuntimeModelKernelErrorCode::MonitorRuleRegexpInvalid.e_msg(format!(
+                "Regexp[{}] of {} is invalid, caused by {}.",
+                regexp,
+                self.rule_name(),
+                e
+            ))
+        })?;
+        let count = self
+            .strings()?
+            .iter()
+            .filter(|s| regex.is_match(s) == matches)
+            .count();
+        Ok(MonitorRuleCheck::of_count(count))
+    }
+
+    /// count the rows which value of factor and value of another factor are not satisfied with the operator.
+    /// rows which any of values is empty or values are not comparable are ignored.
+    fn factor_and_another(&self) -> StdR<MonitorRuleCheck> {
+        let name = self.factor_name()?;
+        let another_name = self.factor_name_of(
+            self.rule.params.as_ref().and_then(|p| p.factor_id.as_ref()),
+            "another factor",
+        )?;
+        let operator = self.param(|p| p.compare_operator.clone(), "compareOperator")?;
+
+        let first_value = |values: Vec<&'a TopicDataValue>| {
+            values
+                .into_iter()
+                .find(|value| !MonitorRuleValues::is_empty(value))
+        };
+        let count = self
+            .data
+            .rows
+            .iter()
+            .filter_map(|row| {
+                let value = first_value(MonitorRuleValues::of(row, name))?;
+                let another = first_value(MonitorRuleValues::of(row, another_name))?;
+                MonitorRuleValues::compare(value, another)
+            })
+            .filter(|ordering| match operator {
+                MonitorRuleCompareOperator::Equal => ordering.is_ne(),
+                MonitorRuleCompareOperator::LessThan => ordering.is_ge(),
+                MonitorRuleCompareOperator::LessThanOrEqual => ordering.is_gt(),
+                MonitorRuleCompareOperator::GreaterThan => ordering.is_le(),
+                MonitorRuleCompareOperator::GreaterThanOrEqual => ordering.is_lt(),
+            })
+            .count();
+        Ok(MonitorRuleCheck::of_count(count))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{MonitorRuleCheck, MonitorRuleCheckData, MonitorRuleChecker, TopicSchema};
+    use bigdecimal::BigDecimal;
+    use elf_model::{
+        Factor, FactorType, MonitorRule, MonitorRuleCode, MonitorRuleCompareOperator,
+        MonitorRuleParameters, Topic, TopicData, TopicDataValue, TopicKind, TopicType,
+    };
+    use std::collections::HashMap;
+
+    fn create_topic_schema() -> TopicSchema {
+        let topic = Topic::new()
+            .topic_id(String::from("topic-1"))
+            .name(String::from("policy"))
+            .r#type(TopicType::Distinct)
+            .kind(TopicKind::Business)
+            .factors(vec![
+                Factor::new()
+                    .factor_id(String::from("f1"))
+                    .name(String::from("premium"))
+                    .r#type(FactorType::Number),
+                Factor::new()
+                    .factor_id(String::from("f2"))
+                    .name(String::from("code"))
+                    .r#type(FactorType::Text),
+                Factor::new()
+                    .factor_id(String::from("f3"))
+                    .name(String::from("minPremium"))
+                    .r#type(FactorType::Number),
+            ])
+            .tenant_id(String::from("tenant-1"));
+        TopicSchema::new(topic).expect("failed to create topic schema")
+    }
+
+    fn row(premium: Option<i32>, code: &str, min_premium: i32) -> TopicData {
+        let mut data = HashMap::new();
+        data.insert(
+            String::from("premium"),
+            premium
+                .map(|p| TopicDataValue::Num(BigDecimal::from(p)))
+                .unwrap_or(TopicDataValue::None),
+        );
+        data.insert(String::from("code"), TopicDataValue::Str(code.to_string()));
+        data.insert(
+            String::from("minPremium"),
+            TopicDataValue::Num(BigDecimal::from(min_premium)),
+        );
+        data
+    }
+
+    fn create_rows() -> Vec<TopicData> {
+        vec![
+            row(Some(100), "A001", 50),
+            row(Some(200), "A002", 50),
+            row(None, " ", 50),
+            row(Some(150), "B1", 300),
+            row(Some(100), "A003", 50),
+        ]
+    }
+
+    fn rule(code: MonitorRuleCode, factor_id: &str, params: MonitorRuleParameters) -> MonitorRule {
+        MonitorRule::new()
+            .rule_id(String::from("rule-1"))
+            .code(code)
+            .topic_id(String::from("topic-1"))
+            .factor_id(factor_id.to_string())
+            .params(params)
+            .enabled(true)
+    }
+
+    fn check_rule(rule: &MonitorRule) -> MonitorRuleCheck {
+        let topic_schema = create_topic_schema();
+        let rows = create_rows();
+        let data = MonitorRuleCheckData {
+            rows: &rows,
+            inserted_count: 5,
+            another_inserted_count: Some(4),
+        };
+        MonitorRuleChecker::check(rule, &topic_schema, &data).expect("failed to check rule")
+    }
+
+    #[test]
+    fn test_rows() {
+        let params = MonitorRuleParameters::new();
+        let check = check_rule(&rule(MonitorRuleCode::RowsNoChange, "f1", params));
+        assert_eq!(check, MonitorRuleCheck::Passed);
+
+        let params = MonitorRuleParameters::new().topic_id(String::from("topic-2"));
+        let check = check_rule(&rule(
+            MonitorRuleCode::RowsCountMismatchAndAnother,
+            "f1",
+            params,
+        ));
+        assert_eq!(check, MonitorRuleCheck::Detected(1));
+
+        let params = MonitorRuleParameters::new();
+        let check = check_rule(&rule(MonitorRuleCode::FactorMismatchType, "f1", params));
+        assert_eq!(check, MonitorRuleCheck::NotApplicable);
+    }
+
+    #[test]
+    fn test_empty() {
+        let params = MonitorRuleParameters::new();
+        let check = check_rule(&rule(MonitorRuleCode::FactorIsEmpty, "f1", params));
+        assert_eq!(check, MonitorRuleCheck::Detected(1));
+
+        // 1 of 5 is empty, 20%
+        let params = MonitorRuleParameters::new().coverage_rate(20);
+        let check = check_rule(&rule(
+            MonitorRuleCode::FactorEmptyOverCoverage,
+            "f1",
+            params,
+        ));
+        assert_eq!(check, MonitorRuleCheck::Passed);
+        let params = MonitorRuleParameters::new().coverage_rate(10);
+        let check = check_rule(&rule(
+            MonitorRuleCode::FactorEmptyOverCoverage,
+            "f1",
+            params,
+        ));
+        assert_eq!(check, MonitorRuleCheck::Detected(1));
+
+        // 2 of 4 values are 100
+        let params = MonitorRuleParameters::new().coverage_rate(40);
+        let check = check_rule(&rule(
+            MonitorRuleCode::FactorCommonValueOverCoverage,
+            "f1",
+            params,
+        ));
+        assert_eq!(check, MonitorRuleCheck::Detected(2));
+    }
+
+    #[test]
+    fn test_numeric() {
+        // 100, 200, 150, 100
+        let params = MonitorRuleParameters::new();
+        let check = check_rule(&rule(
+            MonitorRuleCode::FactorBreaksMonotoneIncreasing,
+            "f1",
+            params,
+        ));
+        assert_eq!(check, MonitorRuleCheck::Detected(2));
+
+        let params = MonitorRuleParameters::new().min(100).max(180);
+        let check = check_rule(&rule(MonitorRuleCode::FactorNotInRange, "f1", params));
+        assert_eq!(check, MonitorRuleCheck::Detected(1));
+
+        // avg is 137.5
+        let params = MonitorRuleParameters::new().min(130).max(140);
+        let check = check_rule(&rule(MonitorRuleCode::FactorAvgNotInRange, "f1", params));
+        assert_eq!(check, MonitorRuleCheck::Passed);
+
+        // median is 125
+        let params = MonitorRuleParameters::new().min(130);
+        let check = check_rule(&rule(MonitorRuleCode::FactorMedianNotInRange, "f1", params));
+        assert_eq!(check, MonitorRuleCheck::Detected(1));
+
+        // quantile 75 of [100, 100, 150, 200] is 162.5
+        let params = MonitorRuleParameters::new().quantile(75).min(160).max(165);
+        let check = check_rule(&rule(
+            MonitorRuleCode::FactorQuantileNotInRange,
+            "f1",
+            params,
+        ));
+        assert_eq!(check, MonitorRuleCheck::Passed);
+
+        // stdev is 41.46
+        let params = MonitorRuleParameters::new().max(40);
+        let check = check_rule(&rule(MonitorRuleCode::FactorStdevNotInRange, "f1", params));
+        assert_eq!(check, MonitorRuleCheck::Detected(1));
+
+        // 100 covers 50%, then 200 (or 150) covers to 75%
+        let params = MonitorRuleParameters::new().aggregation(50).max(100);
+        let check = check_rule(&rule(
+            MonitorRuleCode::FactorCommonValueNotInRange,
+            "f1",
+            params,
+        ));
+        assert_eq!(check, MonitorRuleCheck::Passed);
+        let params = MonitorRuleParameters::new().aggregation(60).max(100);
+        let check = check_rule(&rule(
+            MonitorRuleCode::FactorCommonValueNotInRange,
+            "f1",
+            params,
+        ));
+        assert_eq!(check, MonitorRuleCheck::Detected(1));
+    }
+
+    #[test]
+    fn test_string() {
+        let params = MonitorRuleParameters::new();
+        let check = check_rule(&rule(MonitorRuleCode::FactorIsBlank, "f2", params));
+        assert_eq!(check, MonitorRuleCheck::Detected(1));
+
+        let params = MonitorRuleParameters::new().length(4);
+        let check = check_rule(&rule(
+            MonitorRuleCode::FactorStringLengthMismatch,
+            "f2",
+            params,
+        ));
+        assert_eq!(check, MonitorRuleCheck::Detected(2));
+
+        let params = MonitorRuleParameters::new().min(2).max(4);
+        let check = check_rule(&rule(
+            MonitorRuleCode::FactorStringLengthNotInRange,
+            "f2",
+            params,
+        ));
+        assert_eq!(check, MonitorRuleCheck::Detected(1));
+
+        let params = MonitorRuleParameters::new().regexp(String::from("^A\\d{3}$"));
+        let check = check_rule(&rule(MonitorRuleCode::FactorMatchRegexp, "f2", params));
+        assert_eq!(check, MonitorRuleCheck::Detected(3));
+        let params = MonitorRuleParameters::new().regexp(String::from("^A\\d{3}$"));
+        let check = check_rule(&rule(MonitorRuleCode::FactorMismatchRegexp, "f2", params));
+        assert_eq!(check, MonitorRuleCheck::Detected(2));
+    }
+
+    #[test]
+    fn test_factor_and_another() {
+        let params = MonitorRuleParameters::new()
+            .factor_id(String::from("f3"))
+            .compare_operator(MonitorRuleCompareOperator::GreaterThan);
+        let check = check_rule(&rule(MonitorRuleCode::FactorAndAnother, "f1", params));
+        assert_eq!(check, MonitorRuleCheck::Detected(1));
+    }
+
+    #[test]
+    fn test_missed_parameter() {
+        let topic_schema = create_topic_schema();
+        let rows = create_rows();
+        let data = MonitorRuleCheckData {
+            rows: &rows,
+            inserted_count: 5,
+            another_inserted_count: None,
+        };
+        let rule = rule(
+            MonitorRuleCode::FactorNotInRange,
+            "f1",
+            MonitorRuleParameters::new(),
+        );
+        assert!(MonitorRuleChecker::check(&rule, &topic_schema, &data).is_err());
+    }
+}
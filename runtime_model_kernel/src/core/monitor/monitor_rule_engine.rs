@@ -0,0 +1,257 @@
+use crate::{
+    IdGen, MonitorJobLockProvider, MonitorRuleCheck, MonitorRuleCheckData, MonitorRuleChecker,
+    MonitorRuleMetaProvider, MonitorRuleService, MonitorRuleWindow, RuntimeModelKernelErrorCode,
+    TopicDataProvider, TopicSchema, TopicSchemaProvider, TopicService,
+};
+use chrono::{NaiveDate, NaiveDateTime, Utc};
+use elf_base::{ErrorCode, StdErr, StdR, VoidResultHelper};
+use elf_model::{
+    MonitorJobLock, MonitorJobLockStatus, MonitorRule, MonitorRuleCode, MonitorRuleDetected,
+    MonitorRuleLog, MonitorRuleStatisticalInterval, TenantId, TopicData, TopicId,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// results of running rules of one topic in one window
+pub struct MonitorRuleRunResult {
+    /// one for each checked rule, no matter detected or not
+    pub detected: Vec<MonitorRuleDetected>,
+    /// one for each detected rule
+    pub logs: Vec<MonitorRuleLog>,
+}
+
+/// run enabled monitor rules of topic, over the window of given statistical interval.
+/// job lock is used to make sure the rules of topic are run once in one window,
+/// concurrent runs are skipped.
+pub struct MonitorRuleEngine;
+
+impl MonitorRuleEngine {
+    /// rule is enabled, and its statistical interval matches given one.
+    /// statistical interval of rule is [MonitorRuleStatisticalInterval::Daily] if not given.
+    fn should_run(rule: &MonitorRule, interval: &MonitorRuleStatisticalInterval) -> bool {
+        rule.enabled.unwrap_or(false)
+            && rule
+                .params
+                .as_ref()
+                .and_then(|p| p.statistical_interval.as_ref())
+                .unwrap_or(&MonitorRuleStatisticalInterval::Daily)
+                == interval
+    }
+
+    /// run rules of all topics of given tenant.
+    /// returns results of topics which are run by this call, skipped topics are not included.
+    pub fn run_tenant(
+        tenant_id: &TenantId,
+        interval: &MonitorRuleStatisticalInterval,
+        process_date: &NaiveDate,
+    ) -> StdR<HashMap<TopicId, MonitorRuleRunResult>> {
+        let mut rules_by_topic: HashMap<TopicId, Vec<MonitorRule>> = HashMap::new();
+        for rule in MonitorRuleService::meta()?.find_by_tenant_id(tenant_id)? {
+            if let Some(topic_id) = &rule.topic_id {
+                rules_by_topic
+                    .entry(topic_id.clone())
+                    .or_default()
+                    .push(rule);
+            }
+        }
+
+        let mut results = HashMap::new();
+        let mut errors = vec![];
+        for (topic_id, rules) in rules_by_topic {
+            match Self::run_rules(&topic_id, tenant_id, rules, interval, process_date) {
+                Ok(Some(result)) => {
+                    results.insert(topic_id, result);
+                }
+                Ok(None) => {}
+                Err(e) => errors.push(e),
+            }
+        }
+        errors.accumulate()?;
+
+        Ok(results)
+    }
+
+    /// run rules of given topic.
+    /// returns none when there is no rule to run, or the window is locked by another run.
+    pub fn run(
+        topic_id: &TopicId,
+        tenant_id: &TenantId,
+        interval: &MonitorRuleStatisticalInterval,
+        process_date: &NaiveDate,
+    ) -> StdR<Option<MonitorRuleRunResult>> {
+        let rules = MonitorRuleService::meta()?.find_by_topic_id(topic_id, tenant_id)?;
+        Self::run_rules(topic_id, tenant_id, rules, interval, process_date)
+    }
+
+    fn run_rules(
+        topic_id: &TopicId,
+        tenant_id: &TenantId,
+        rules: Vec<MonitorRule>,
+        interval: &MonitorRuleStatisticalInterval,
+        process_date: &NaiveDate,
+    ) -> StdR<Option<MonitorRuleRunResult>> {
+        let rules: Vec<MonitorRule> = rules
+            .into_iter()
+            .filter(|rule| Self::should_run(rule, interval))
+            .collect();
+        if rules.is_empty() {
+            return Ok(None);
+        }
+
+        let window = MonitorRuleWindow::of(interval, process_date);
+        let lock = MonitorJobLock::new()
+            .lock_id(IdGen::next_id()?.to_string())
+            .tenant_id(tenant_id.clone())
+            .topic_id(topic_id.clone())
+            .frequency(interval.clone())
+            .process_date(window.process_date)
+            .status(MonitorJobLockStatus::Ready)
+            .created_at(Utc::now().naive_utc());
+        let job_lock = MonitorRuleService::job_lock()?;
+        if !job_lock.try_lock(&lock)? {
+            // window is locked by another run
+            return Ok(None);
+        }
+
+        let result = Self::check_window(topic_id, tenant_id, &rules, &window);
+        // lock id is set above
+        let lock_id = lock.lock_id.as_ref().unwrap();
+        match result {
+            Ok(result) => {
+                job_lock.finish(lock_id, MonitorJobLockStatus::Success)?;
+                Ok(Some(result))
+            }
+            Err(e) => {
+                // keep the check error, failure of finishing lock is logged only
+                if let Err(finish_error) = job_lock.finish(lock_id, MonitorJobLockStatus::Failed) {
+                    tracing::error!(
+                        "Failed to finish monitor job lock[{}], caused by {}.",
+                        lock_id,
+                        finish_error
+                    );
+                }
+                Err(e)
+            }
+        }
+    }
+
+    fn count_inserted(
+        topic_id: &TopicId,
+        tenant_id: &TenantId,
+        window: &MonitorRuleWindow,
+    ) -> StdR<u64> {
+        let topic_schema = TopicService::schema()?.by_id(topic_id, tenant_id)?;
+        TopicService::data()?.count_inserted_between(&topic_schema, &window.start, &window.end)
+    }
+
+    fn check_window(
+        topic_id: &TopicId,
+        tenant_id: &TenantId,
+        rules: &Vec<MonitorRule>,
+        window: &MonitorRuleWindow,
+    ) -> StdR<MonitorRuleRunResult> {
+        let topic_schema = TopicService::schema()?.by_id(topic_id, tenant_id)?;
+        let data_service = TopicService::data()?;
+        let rows = data_service.find_updated_between(&topic_schema, &window.start, &window.end)?;
+        let inserted_count =
+            data_service.count_inserted_between(&topic_schema, &window.start, &window.end)?;
+
+        let mut another_counts: HashMap<TopicId, u64> = HashMap::new();
+        for rule in rules {
+            if rule.code != Some(MonitorRuleCode::RowsCountMismatchAndAnother) {
+                continue;
+            }
+            match rule.params.as_ref().and_then(|p| p.topic_id.as_ref()) {
+                Some(another_topic_id) if !another_counts.contains_key(another_topic_id) => {
+                    let count = Self::count_inserted(another_topic_id, tenant_id, window)?;
+                    another_counts.insert(another_topic_id.clone(), count);
+                }
+                Some(_) => {}
+                None => {
+                    return RuntimeModelKernelErrorCode::MonitorRuleTopicNotFound.msg(format!(
+                        "Monitor rule[rule_id={}] must have another topic.",
+                        rule.rule_id.as_deref().unwrap_or("")
+                    ));
+                }
+            }
+        }
+
+        Self::check_rules(
+            &topic_schema,
+            rules,
+            &rows,
+            inserted_count,
+            &another_counts,
+            window,
+            Utc::now().naive_utc(),
+        )
+    }
+
+    /// check all rules, errors are accumulated
+    pub fn check_rules(
+        topic_schema: &Arc<TopicSchema>,
+        rules: &Vec<MonitorRule>,
+        rows: &Vec<TopicData>,
+        inserted_count: u64,
+        another_counts: &HashMap<TopicId, u64>,
+        window: &MonitorRuleWindow,
+        now: NaiveDateTime,
+    ) -> StdR<MonitorRuleRunResult> {
+        let mut detected = vec![];
+        let mut logs = vec![];
+        let mut errors: Vec<StdErr> = vec![];
+
+        for rule in rules {
+            let data = MonitorRuleCheckData {
+                rows,
+                inserted_count,
+                another_inserted_count: rule
+                    .params
+                    .as_ref()
+                    .and_then(|p| p.topic_id.as_ref())
+                    .and_then(|topic_id| another_counts.get(topic_id))
+                    .copied(),
+            };
+            let found = match MonitorRuleChecker::check(rule, topic_schema, &data) {
+                Ok(MonitorRuleCheck::NotApplicable) => continue,
+                Ok(MonitorRuleCheck::Passed) => None,
+                Ok(MonitorRuleCheck::Detected(count)) => Some(count),
+                Err(e) => {
+                    errors.push(e);
+                    continue;
+                }
+            };
+
+            let factor = rule
+                .factor_id
+                .as_ref()
+                .and_then(|factor_id| topic_schema.factor_by_id(factor_id));
+            detected.push(
+                MonitorRuleDetected::new()
+                    .rule_code(rule.code.clone().unwrap())
+                    .topic_id(topic_schema.topic_id().to_string())
+                    .topic_name(topic_schema.name().to_string())
+                    .detected(found.is_some())
+                    .process_date(window.process_date)
+                    .build(),
+            );
+            let last = detected.last_mut().unwrap();
+            last.factor_id = factor.map(|f| f.factor_id.to_string());
+            last.factor_name = factor.map(|f| f.name.to_string());
+            last.severity = rule.severity.clone();
+
+            if let Some(count) = found {
+                let mut log = MonitorRuleLog::new()
+                    .rule_code(rule.code.clone().unwrap())
+                    .topic_id(topic_schema.topic_id().to_string())
+                    .count(count)
+                    .last_occurred_time(now);
+                log.factor_id = factor.map(|f| f.factor_id.to_string());
+                logs.push(log);
+            }
+        }
+
+        errors.accumulate()?;
+        Ok(MonitorRuleRunResult { detected, logs })
+    }
+}
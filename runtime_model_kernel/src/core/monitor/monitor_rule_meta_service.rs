@@ -0,0 +1,35 @@
+use elf_base::StdR;
+use elf_model::{MonitorRule, TenantId, TopicId};
+use std::sync::Arc;
+
+/// TODO monitor rule meta service using tenant and it's meta datasource (or the global meta datasource)
+///  to find out monitor rules.
+///  the tenant meta datasource is a new feature, which is defined on tenant
+pub struct MonitorRuleMetaService;
+
+impl MonitorRuleMetaService {
+    fn new() -> StdR<Arc<Self>> {
+        // TODO maybe find from cache
+        Ok(Arc::new(Self {}))
+    }
+
+    /// find all rules of given topic, including the disabled ones
+    pub fn find_by_topic_id(
+        &self,
+        _topic_id: &TopicId,
+        _tenant_id: &TenantId,
+    ) -> StdR<Vec<MonitorRule>> {
+        todo!("implement find_by_topic_id for MonitorRuleMetaService")
+    }
+
+    /// find all rules of given tenant, including the disabled ones
+    pub fn find_by_tenant_id(&self, _tenant_id: &TenantId) -> StdR<Vec<MonitorRule>> {
+        todo!("implement find_by_tenant_id for MonitorRuleMetaService")
+    }
+}
+
+pub trait MonitorRuleMetaProvider {
+    fn meta() -> StdR<Arc<MonitorRuleMetaService>> {
+        MonitorRuleMetaService::new()
+    }
+}
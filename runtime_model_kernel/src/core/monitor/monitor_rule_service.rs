@@ -0,0 +1,6 @@
+use crate::{MonitorJobLockProvider, MonitorRuleMetaProvider};
+
+pub struct MonitorRuleService;
+
+impl MonitorRuleMetaProvider for MonitorRuleService {}
+impl MonitorJobLockProvider for MonitorRuleService {}
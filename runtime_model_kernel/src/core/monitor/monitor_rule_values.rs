@@ -0,0 +1,151 @@
+use bigdecimal::BigDecimal;
+use elf_base::{NumericUtils, StringConverterFrom};
+use elf_model::{TopicData, TopicDataValue};
+use std::cmp::Ordering;
+
+/// value helpers for monitor rules
+pub struct MonitorRuleValues;
+
+impl MonitorRuleValues {
+    fn collect<'a>(
+        value: &'a TopicDataValue,
+        segments: &[&str],
+        values: &mut Vec<&'a TopicDataValue>,
+    ) {
+        if segments.is_empty() {
+            values.push(value);
+            return;
+        }
+        match value {
+            TopicDataValue::Map(map) => {
+                if let Some(value) = map.get(segments[0]) {
+                    Self::collect(value, &segments[1..], values);
+                }
+            }
+            TopicDataValue::Vec(vec) => {
+                for element in vec {
+                    Self::collect(element, segments, values);
+                }
+            }
+            // cannot go deeper
+            _ => {}
+        }
+    }
+
+    /// get values of factor from given row.
+    /// - the flatten value (key is factor name) is used if exists,
+    /// - otherwise, factor name is split by [.] to find values level by level,
+    ///   vec on the way is expanded, so there might be multiple values.
+    pub fn of<'a>(row: &'a TopicData, factor_name: &str) -> Vec<&'a TopicDataValue> {
+        if let Some(value) = row.get(factor_name) {
+            return vec![value];
+        }
+
+        let segments: Vec<&str> = factor_name.split('.').collect();
+        let mut values = vec![];
+        if let Some(value) = row.get(segments[0]) {
+            Self::collect(value, &segments[1..], &mut values);
+        }
+        values
+    }
+
+    /// none and empty string are treated as empty
+    pub fn is_empty(value: &TopicDataValue) -> bool {
+        match value {
+            TopicDataValue::None => true,
+            TopicDataValue::Str(s) => s.is_empty(),
+            _ => false,
+        }
+    }
+
+    pub fn to_decimal(value: &TopicDataValue) -> Option<BigDecimal> {
+        match value {
+            TopicDataValue::Num(n) => Some(n.clone()),
+            TopicDataValue::Str(s) => s.trim().to_string().to_decimal().ok(),
+            _ => None,
+        }
+    }
+
+    /// string representation of plain values, none for map, vec and none
+    pub fn to_str(value: &TopicDataValue) -> Option<String> {
+        match value {
+            TopicDataValue::Str(s) => Some(s.clone()),
+            TopicDataValue::Num(n) => Some(String::from_decimal(n)),
+            TopicDataValue::Bool(b) => Some(String::from_bool(b)),
+            TopicDataValue::DateTime(dt) => Some(String::from_datetime(dt)),
+            TopicDataValue::Date(d) => Some(String::from_date(d)),
+            TopicDataValue::Time(t) => Some(String::from_time(t)),
+            TopicDataValue::Map(_) | TopicDataValue::Vec(_) | TopicDataValue::None => None,
+        }
+    }
+
+    /// returns none when values are not comparable
+    pub fn compare(a: &TopicDataValue, b: &TopicDataValue) -> Option<Ordering> {
+        match (a, b) {
+            (TopicDataValue::Num(_), _) | (_, TopicDataValue::Num(_)) => {
+                match (Self::to_decimal(a), Self::to_decimal(b)) {
+                    (Some(a), Some(b)) => Some(a.cmp(&b)),
+                    _ => None,
+                }
+            }
+            (TopicDataValue::Date(a), TopicDataValue::Date(b)) => Some(a.cmp(b)),
+            (TopicDataValue::DateTime(a), TopicDataValue::DateTime(b)) => Some(a.cmp(b)),
+            (TopicDataValue::Date(a), TopicDataValue::DateTime(b)) => Some(a.cmp(&b.date())),
+            (TopicDataValue::DateTime(a), TopicDataValue::Date(b)) => Some(a.date().cmp(b)),
+            (TopicDataValue::Time(a), TopicDataValue::Time(b)) => Some(a.cmp(b)),
+            (TopicDataValue::Str(a), TopicDataValue::Str(b)) => Some(a.cmp(b)),
+            (TopicDataValue::Bool(a), TopicDataValue::Bool(b)) => Some(a.cmp(b)),
+            _ => None,
+        }
+    }
+
+    pub fn avg(values: &[BigDecimal]) -> Option<BigDecimal> {
+        if values.is_empty() {
+            None
+        } else {
+            let sum: BigDecimal = values.iter().sum();
+            Some(sum / BigDecimal::from(values.len() as u64))
+        }
+    }
+
+    /// given values must be sorted, quantile is in percentage [0, 100].
+    /// linear interpolation between the closest ranks.
+    pub fn quantile(sorted: &[BigDecimal], quantile: u32) -> Option<BigDecimal> {
+        if sorted.is_empty() {
+            return None;
+        }
+        let quantile = quantile.min(100) as usize;
+        let position = quantile * (sorted.len() - 1);
+        let index = position / 100;
+        let remainder = position % 100;
+        let lower = &sorted[index];
+        if remainder == 0 {
+            Some(lower.clone())
+        } else {
+            let upper = &sorted[index + 1];
+            Some(
+                lower
+                    + (upper - lower) * BigDecimal::from(remainder as u64) / BigDecimal::from(100),
+            )
+        }
+    }
+
+    /// given values must be sorted
+    pub fn median(sorted: &[BigDecimal]) -> Option<BigDecimal> {
+        Self::quantile(sorted, 50)
+    }
+
+    /// population standard deviation
+    pub fn stdev(values: &[BigDecimal]) -> Option<BigDecimal> {
+        let avg = Self::avg(values)?;
+        let variance: BigDecimal = values
+            .iter()
+            .map(|value| {
+                let diff = value - &avg;
+                &diff * &diff
+            })
+            .sum::<BigDecimal>()
+            / BigDecimal::from(values.len() as u64);
+        variance.sqrt()
+    }
+}
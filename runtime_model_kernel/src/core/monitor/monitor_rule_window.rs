@@ -0,0 +1,89 @@
+use chrono::{Datelike, Days, Months, NaiveDate, NaiveDateTime, NaiveTime};
+use elf_model::MonitorRuleStatisticalInterval;
+
+/// the time range which monitor rules run on, [start, end).
+/// - daily: the given day,
+/// - weekly: the week which contains given day, starts from sunday,
+/// - monthly: the month which contains given day, starts from 1st.
+pub struct MonitorRuleWindow {
+    /// the start day of window, used as the process date of lock and detected results
+    pub process_date: NaiveDate,
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+}
+
+impl MonitorRuleWindow {
+    fn first_day(interval: &MonitorRuleStatisticalInterval, date: &NaiveDate) -> NaiveDate {
+        match interval {
+            MonitorRuleStatisticalInterval::Daily => *date,
+            MonitorRuleStatisticalInterval::Weekly => {
+                *date - Days::new(date.weekday().num_days_from_sunday() as u64)
+            }
+            MonitorRuleStatisticalInterval::Monthly => date.with_day(1).unwrap_or(*date),
+        }
+    }
+
+    fn next_first_day(
+        interval: &MonitorRuleStatisticalInterval,
+        first_day: &NaiveDate,
+    ) -> NaiveDate {
+        match interval {
+            MonitorRuleStatisticalInterval::Daily => *first_day + Days::new(1),
+            MonitorRuleStatisticalInterval::Weekly => *first_day + Days::new(7),
+            MonitorRuleStatisticalInterval::Monthly => *first_day + Months::new(1),
+        }
+    }
+
+    pub fn of(interval: &MonitorRuleStatisticalInterval, date: &NaiveDate) -> Self {
+        let process_date = Self::first_day(interval, date);
+        let end_date = Self::next_first_day(interval, &process_date);
+
+        Self {
+            process_date,
+            start: process_date.and_time(NaiveTime::MIN),
+            end: end_date.and_time(NaiveTime::MIN),
+        }
+    }
+
+    /// the window right before this one
+    pub fn previous(&self, interval: &MonitorRuleStatisticalInterval) -> Self {
+        let date = self.process_date - Days::new(1);
+        Self::of(interval, &date)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::MonitorRuleWindow;
+    use chrono::NaiveDate;
+    use elf_model::MonitorRuleStatisticalInterval;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_window() {
+        let daily =
+            MonitorRuleWindow::of(&MonitorRuleStatisticalInterval::Daily, &date(2026, 2, 18));
+        assert_eq!(daily.process_date, date(2026, 2, 18));
+        assert_eq!(daily.end.date(), date(2026, 2, 19));
+
+        // 2026-02-18 is wednesday
+        let weekly =
+            MonitorRuleWindow::of(&MonitorRuleStatisticalInterval::Weekly, &date(2026, 2, 18));
+        assert_eq!(weekly.process_date, date(2026, 2, 15));
+        assert_eq!(weekly.end.date(), date(2026, 2, 22));
+
+        let monthly = MonitorRuleWindow::of(
+            &MonitorRuleStatisticalInterval::Monthly,
+            &date(2026, 12, 18),
+        );
+        assert_eq!(monthly.process_date, date(2026, 12, 1));
+        assert_eq!(monthly.end.date(), date(2027, 1, 1));
+
+        let previous = monthly.previous(&MonitorRuleStatisticalInterval::Monthly);
+        assert_eq!(previous.process_date, date(2026, 11, 1));
+        assert_eq!(previous.end, monthly.start);
+    }
+}
@@ -0,0 +1,3 @@
+mod objective_meta_service;
+
+pub use objective_meta_service::*;
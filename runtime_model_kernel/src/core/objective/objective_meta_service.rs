@@ -0,0 +1,28 @@
+use elf_base::StdR;
+use elf_model::{Objective, ObjectiveId, TenantId};
+use std::sync::Arc;
+
+/// TODO objective meta service using tenant and it's meta datasource (or the global meta datasource)
+///  to find out objective meta.
+pub struct ObjectiveMetaService;
+
+impl ObjectiveMetaService {
+    fn new() -> StdR<Arc<Self>> {
+        // TODO maybe find from cache
+        Ok(Arc::new(Self {}))
+    }
+
+    pub fn find_by_id(
+        &self,
+        _objective_id: &ObjectiveId,
+        _tenant_id: &TenantId,
+    ) -> StdR<Option<Objective>> {
+        todo!("implement find_by_id for ObjectiveMetaService")
+    }
+}
+
+pub trait ObjectiveMetaProvider {
+    fn meta() -> StdR<Arc<ObjectiveMetaService>> {
+        ObjectiveMetaService::new()
+    }
+}
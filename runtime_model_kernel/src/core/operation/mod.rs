@@ -0,0 +1,11 @@
+mod operation_criteria;
+mod operation_diff;
+mod operation_log_service;
+mod operation_record;
+mod operation_resources;
+
+pub use operation_criteria::*;
+pub use operation_diff::*;
+pub use operation_log_service::*;
+pub use operation_record::*;
+pub use operation_resources::*;
@@ -0,0 +1,39 @@
+use crate::MetaTupleType;
+use chrono::NaiveDateTime;
+use elf_model::UserId;
+
+/// criteria to find operations, none means not filtered.
+#[derive(Default)]
+pub struct OperationCriteria {
+    pub tuple_type: Option<MetaTupleType>,
+    pub tuple_id: Option<String>,
+    /// operator
+    pub user_id: Option<UserId>,
+    /// inclusive
+    pub from: Option<NaiveDateTime>,
+    /// exclusive
+    pub to: Option<NaiveDateTime>,
+}
+
+impl OperationCriteria {
+    pub fn of_tuple(tuple_type: MetaTupleType, tuple_id: String) -> Self {
+        Self {
+            tuple_type: Some(tuple_type),
+            tuple_id: Some(tuple_id),
+            ..Self::default()
+        }
+    }
+
+    pub fn of_user(user_id: UserId) -> Self {
+        Self {
+            user_id: Some(user_id),
+            ..Self::default()
+        }
+    }
+
+    pub fn between(mut self, from: NaiveDateTime, to: NaiveDateTime) -> Self {
+        self.from = Some(from);
+        self.to = Some(to);
+        self
+    }
+}
@@ -0,0 +1,188 @@
+use serde_json::{Map, Value};
+use std::fmt::{Display, Formatter};
+
+/// json keys of tuple not compared, they are maintained by storage
+const IGNORED_KEYS: [&str; 6] = [
+    "version",
+    "createdAt",
+    "createdBy",
+    "lastModifiedAt",
+    "lastModifiedBy",
+    "lastVisitTime",
+];
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum OperationDiffKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+impl Display for OperationDiffKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Added => write!(f, "added"),
+            Self::Removed => write!(f, "removed"),
+            Self::Changed => write!(f, "changed"),
+        }
+    }
+}
+
+/// one difference between two contents of tuple.
+#[derive(PartialEq, Debug)]
+pub struct OperationDiff {
+    /// path of value, such as "factors[1].name"
+    pub path: String,
+    pub kind: OperationDiffKind,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+impl OperationDiff {
+    /// structural diff of given contents, none is treated as an empty object.
+    /// - objects are compared key by key, arrays are compared element by element,
+    /// - null is treated as absent,
+    /// - audit and version keys of tuple are ignored.
+    pub fn between(before: Option<&Value>, after: Option<&Value>) -> Vec<Self> {
+        let empty = Value::Object(Map::new());
+        let mut diffs = vec![];
+        Self::compare(
+            String::new(),
+            Some(before.unwrap_or(&empty)),
+            Some(after.unwrap_or(&empty)),
+            &mut diffs,
+        );
+        diffs
+    }
+
+    fn child_path(path: &str, key: &str) -> String {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", path, key)
+        }
+    }
+
+    fn is_compared(path: &str, key: &str) -> bool {
+        !path.is_empty() || !IGNORED_KEYS.contains(&key)
+    }
+
+    fn compare(path: String, before: Option<&Value>, after: Option<&Value>, diffs: &mut Vec<Self>) {
+        let before = before.filter(|value| !value.is_null());
+        let after = after.filter(|value| !value.is_null());
+        match (before, after) {
+            (Some(Value::Object(before)), Some(Value::Object(after))) => {
+                for (key, value) in before {
+                    if Self::is_compared(&path, key) {
+                        Self::compare(
+                            Self::child_path(&path, key),
+                            Some(value),
+                            after.get(key),
+                            diffs,
+                        );
+                    }
+                }
+                for (key, value) in after {
+                    if !before.contains_key(key) && Self::is_compared(&path, key) {
+                        Self::compare(Self::child_path(&path, key), None, Some(value), diffs);
+                    }
+                }
+            }
+            (Some(Value::Array(before)), Some(Value::Array(after))) => {
+                for index in 0..before.len().max(after.len()) {
+                    Self::compare(
+                        format!("{}[{}]", path, index),
+                        before.get(index),
+                        after.get(index),
+                        diffs,
+                    );
+                }
+            }
+            (Some(before), Some(after)) => {
+                if before != after {
+                    diffs.push(Self {
+                        path,
+                        kind: OperationDiffKind::Changed,
+                        before: Some(before.clone()),
+                        after: Some(after.clone()),
+                    });
+                }
+            }
+            (Some(before), None) => diffs.push(Self {
+                path,
+                kind: OperationDiffKind::Removed,
+                before: Some(before.clone()),
+                after: None,
+            }),
+            (None, Some(after)) => diffs.push(Self {
+                path,
+                kind: OperationDiffKind::Added,
+                before: None,
+                after: Some(after.clone()),
+            }),
+            (None, None) => {}
+        }
+    }
+
+    pub fn to_value(&self) -> Value {
+        let mut map = Map::new();
+        map.insert(String::from("path"), Value::String(self.path.clone()));
+        map.insert(String::from("kind"), Value::String(self.kind.to_string()));
+        if let Some(before) = &self.before {
+            map.insert(String::from("before"), before.clone());
+        }
+        if let Some(after) = &self.after {
+            map.insert(String::from("after"), after.clone());
+        }
+        Value::Object(map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{OperationDiff, OperationDiffKind};
+    use serde_json::json;
+
+    #[test]
+    fn test_between() {
+        let before = json!({
+            "topicId": "t1",
+            "name": "orders",
+            "version": 1,
+            "description": null,
+            "factors": [{"factorId": "f1", "name": "id"}, {"factorId": "f2", "name": "amount"}],
+        });
+        let after = json!({
+            "topicId": "t1",
+            "name": "orders",
+            "version": 2,
+            "description": "orders of shop",
+            "factors": [{"factorId": "f1", "name": "order_id"}],
+        });
+        let diffs = OperationDiff::between(Some(&before), Some(&after));
+        let summary: Vec<(&str, OperationDiffKind)> = diffs
+            .iter()
+            .map(|diff| (diff.path.as_str(), diff.kind))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("description", OperationDiffKind::Added),
+                ("factors[0].name", OperationDiffKind::Changed),
+                ("factors[1]", OperationDiffKind::Removed),
+            ]
+        );
+        assert_eq!(diffs[1].before, Some(json!("id")));
+        assert_eq!(diffs[1].after, Some(json!("order_id")));
+        assert_eq!(
+            diffs[2].to_value(),
+            json!({"path": "factors[1]", "kind": "removed", "before": {"factorId": "f2", "name": "amount"}})
+        );
+
+        // created, all keys are added
+        let diffs = OperationDiff::between(None, Some(&after));
+        assert_eq!(diffs.len(), 4);
+        assert!(diffs.iter().all(|d| d.kind == OperationDiffKind::Added));
+        assert!(OperationDiff::between(Some(&before), Some(&before)).is_empty());
+    }
+}
@@ -0,0 +1,346 @@
+use crate::{
+    IdGen, MetaTupleType, OperationCriteria, OperationRecord, OperationResources,
+    RuntimeModelKernelErrorCode,
+};
+use chrono::NaiveDateTime;
+use elf_auth::Principal;
+use elf_base::{ErrorCode, StdR};
+use elf_model::{Operation, TenantId};
+use serde::Serialize;
+use serde_json::Value;
+
+/// change tuples of principal's tenant, each change is recorded as an operation by [OperationRecord].
+/// operations can be found by tuple, operator and time range,
+/// and topic or pipeline can be restored to a version recorded by operations.
+/// tuples and operations are read from and written to the given [OperationResources].
+pub struct OperationLogService<R: OperationResources> {
+    resources: R,
+}
+
+impl<R: OperationResources> OperationLogService<R> {
+    pub fn new(resources: R) -> Self {
+        Self { resources }
+    }
+
+    fn tuple_id_of(tuple_type: &MetaTupleType, tuple: &Value) -> StdR<String> {
+        match tuple.get(tuple_type.id_key()).and_then(Value::as_str) {
+            Some(tuple_id) => Ok(tuple_id.to_string()),
+            _ => RuntimeModelKernelErrorCode::OperationContentInvalid
+                .msg(format!("Id of {} is missed.", tuple_type)),
+        }
+    }
+
+    fn find_tuple(
+        &self,
+        tuple_type: &MetaTupleType,
+        tuple_id: &String,
+        tenant_id: &TenantId,
+    ) -> StdR<Value> {
+        match self.resources.find_tuple(tuple_type, tuple_id, tenant_id)? {
+            Some(tuple) => Ok(tuple),
+            _ => RuntimeModelKernelErrorCode::OperationTupleNotFound.msg(format!(
+                "{}[{}] of tenant[{}] not found.",
+                tuple_type, tuple_id, tenant_id
+            )),
+        }
+    }
+
+    fn record(
+        &self,
+        principal: &Principal,
+        record: OperationRecord,
+        operated_at: &NaiveDateTime,
+    ) -> StdR<Operation> {
+        let operation = record.to_operation(
+            IdGen::next_id()?.to_string(),
+            principal.tenant_id(),
+            principal.user_id(),
+            operated_at,
+        )?;
+        self.resources.create_operation(&operation)?;
+        Ok(operation)
+    }
+
+    /// save topic, pipeline or other meta model as tuple, see [OperationLogService::save].
+    pub fn save_model<T: Serialize>(
+        &self,
+        principal: &Principal,
+        tuple_type: &MetaTupleType,
+        model: &T,
+        operated_at: &NaiveDateTime,
+    ) -> StdR<Operation> {
+        let tuple = match serde_json::to_value(model) {
+            Ok(tuple) => tuple,
+            Err(e) => {
+                return RuntimeModelKernelErrorCode::OperationContentInvalid.msg(format!(
+                    "Failed to serialize {}, caused by {}.",
+                    tuple_type, e
+                ));
+            }
+        };
+        self.save(principal, tuple_type, tuple, operated_at)
+    }
+
+    /// create when tuple id not exists, otherwise update.
+    /// tenant of tuple is replaced by principal's, tuple is reloaded after saved to record the stored content.
+    pub fn save(
+        &self,
+        principal: &Principal,
+        tuple_type: &MetaTupleType,
+        mut tuple: Value,
+        operated_at: &NaiveDateTime,
+    ) -> StdR<Operation> {
+        let tuple_id = Self::tuple_id_of(tuple_type, &tuple)?;
+        let tenant_id = principal.tenant_id();
+        tuple["tenantId"] = Value::String(tenant_id.clone());
+
+        let before = self
+            .resources
+            .find_tuple(tuple_type, &tuple_id, tenant_id)?;
+        self.resources.save_tuple(tuple_type, &tuple)?;
+        let after = self.find_tuple(tuple_type, &tuple_id, tenant_id)?;
+
+        let record = match before {
+            Some(before) => OperationRecord::updated(*tuple_type, tuple_id, before, after),
+            _ => OperationRecord::created(*tuple_type, tuple_id, after),
+        };
+        self.record(principal, record, operated_at)
+    }
+
+    pub fn delete(
+        &self,
+        principal: &Principal,
+        tuple_type: &MetaTupleType,
+        tuple_id: &String,
+        operated_at: &NaiveDateTime,
+    ) -> StdR<Operation> {
+        let tenant_id = principal.tenant_id();
+        let before = self.find_tuple(tuple_type, tuple_id, tenant_id)?;
+        self.resources
+            .delete_tuple(tuple_type, tuple_id, tenant_id)?;
+
+        let record = OperationRecord::deleted(*tuple_type, tuple_id.clone(), before);
+        self.record(principal, record, operated_at)
+    }
+
+    /// operations of principal's tenant, ordered by created time ascending
+    pub fn find(
+        &self,
+        principal: &Principal,
+        criteria: &OperationCriteria,
+    ) -> StdR<Vec<Operation>> {
+        self.resources
+            .find_operations(criteria, principal.tenant_id())
+    }
+
+    /// restore topic or pipeline to given version, restore is recorded as an update operation.
+    /// tuple must exist, deleted one cannot be restored.
+    pub fn restore(
+        &self,
+        principal: &Principal,
+        tuple_type: &MetaTupleType,
+        tuple_id: &String,
+        version: u64,
+        operated_at: &NaiveDateTime,
+    ) -> StdR<Operation> {
+        if !matches!(tuple_type, MetaTupleType::Topic | MetaTupleType::Pipeline) {
+            return RuntimeModelKernelErrorCode::OperationRestoreNotSupported.msg(format!(
+                "Restore {} to version is not supported, only topic and pipeline are supported.",
+                tuple_type
+            ));
+        }
+
+        let tenant_id = principal.tenant_id();
+        let current = self.find_tuple(tuple_type, tuple_id, tenant_id)?;
+        let operations = self.find(
+            principal,
+            &OperationCriteria::of_tuple(*tuple_type, tuple_id.clone()),
+        )?;
+        let Some(mut snapshot) = OperationRecord::snapshot_of(&operations, version)? else {
+            return RuntimeModelKernelErrorCode::OperationVersionNotFound.msg(format!(
+                "Version[{}] of {}[{}] not found in operations.",
+                version, tuple_type, tuple_id
+            ));
+        };
+        // current version is used for optimistic lock
+        match current.get("version") {
+            Some(version) => snapshot["version"] = version.clone(),
+            _ => {
+                if let Some(snapshot) = snapshot.as_object_mut() {
+                    snapshot.remove("version");
+                }
+            }
+        }
+        self.save(principal, tuple_type, snapshot, operated_at)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        IdGen, MetaTupleType, OperationCriteria, OperationLogService, OperationResources,
+        SnowflakeIdGenerator,
+    };
+    use chrono::NaiveDate;
+    use elf_auth::Principal;
+    use elf_base::{StdR, VoidR};
+    use elf_model::{Operation, OperationType, TenantId};
+    use serde_json::{json, Value};
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    /// operation is not cloneable, copy fields used in this test
+    fn copy_of(operation: &Operation) -> Operation {
+        Operation {
+            record_id: operation.record_id.clone(),
+            operation_type: operation.operation_type.clone(),
+            tuple_key: operation.tuple_key.clone(),
+            tuple_type: operation.tuple_type.clone(),
+            tuple_id: operation.tuple_id.clone(),
+            content: operation.content.clone(),
+            tenant_id: operation.tenant_id.clone(),
+            created_at: operation.created_at,
+            created_by: operation.created_by.clone(),
+            ..Operation::new()
+        }
+    }
+
+    #[derive(Default)]
+    struct InMemoryResources {
+        /// key is (tuple type, tuple id)
+        tuples: RefCell<HashMap<(String, String), Value>>,
+        operations: RefCell<Vec<Operation>>,
+    }
+
+    impl OperationResources for &InMemoryResources {
+        fn find_tuple(
+            &self,
+            tuple_type: &MetaTupleType,
+            tuple_id: &str,
+            _tenant_id: &TenantId,
+        ) -> StdR<Option<Value>> {
+            let key = (tuple_type.to_string(), tuple_id.to_string());
+            Ok(self.tuples.borrow().get(&key).cloned())
+        }
+
+        fn save_tuple(&self, tuple_type: &MetaTupleType, tuple: &Value) -> VoidR {
+            let tuple_id = tuple[tuple_type.id_key()].as_str().unwrap().to_string();
+            self.tuples
+                .borrow_mut()
+                .insert((tuple_type.to_string(), tuple_id), tuple.clone());
+            Ok(())
+        }
+
+        fn delete_tuple(
+            &self,
+            tuple_type: &MetaTupleType,
+            tuple_id: &str,
+            _tenant_id: &TenantId,
+        ) -> VoidR {
+            let key = (tuple_type.to_string(), tuple_id.to_string());
+            self.tuples.borrow_mut().remove(&key);
+            Ok(())
+        }
+
+        fn create_operation(&self, operation: &Operation) -> VoidR {
+            self.operations.borrow_mut().push(copy_of(operation));
+            Ok(())
+        }
+
+        fn find_operations(
+            &self,
+            criteria: &OperationCriteria,
+            _tenant_id: &TenantId,
+        ) -> StdR<Vec<Operation>> {
+            Ok(self
+                .operations
+                .borrow()
+                .iter()
+                .map(copy_of)
+                .filter(|operation| {
+                    criteria.tuple_id.is_none() || operation.tuple_id == criteria.tuple_id
+                })
+                .collect())
+        }
+    }
+
+    fn topic(name: &str, version: u64) -> Value {
+        json!({"topicId": "t1", "name": name, "version": version})
+    }
+
+    #[test]
+    fn test_save_restore_delete() {
+        let _ = IdGen::set(Box::new(SnowflakeIdGenerator::new(1).unwrap()));
+        let principal = Principal::fake_tenant_admin(
+            Some(String::from("t-1")),
+            Some(String::from("u-1")),
+            None,
+        );
+        let operated_at = NaiveDate::from_ymd_opt(2026, 3, 2)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        let resources = InMemoryResources::default();
+        let service = OperationLogService::new(&resources);
+        let tuple_type = MetaTupleType::Topic;
+        let tuple_id = String::from("t1");
+
+        service
+            .save(&principal, &tuple_type, topic("orders", 1), &operated_at)
+            .unwrap();
+        service
+            .save(
+                &principal,
+                &tuple_type,
+                topic("sales_orders", 2),
+                &operated_at,
+            )
+            .unwrap();
+        let restored = service
+            .restore(&principal, &tuple_type, &tuple_id, 1, &operated_at)
+            .unwrap();
+        assert_eq!(restored.operation_type.as_deref(), Some("update"));
+        let current =
+            resources.tuples.borrow()[&(tuple_type.to_string(), tuple_id.clone())].clone();
+        assert_eq!(current["name"], "orders");
+        // current version is kept
+        assert_eq!(current["version"], 2);
+        assert_eq!(current["tenantId"], "t-1");
+        // version not recorded
+        assert!(service
+            .restore(&principal, &tuple_type, &tuple_id, 5, &operated_at)
+            .is_err());
+
+        service
+            .delete(&principal, &tuple_type, &tuple_id, &operated_at)
+            .unwrap();
+        assert!(resources.tuples.borrow().is_empty());
+        // deleted one cannot be restored
+        assert!(service
+            .restore(&principal, &tuple_type, &tuple_id, 1, &operated_at)
+            .is_err());
+
+        let operations = service
+            .find(
+                &principal,
+                &OperationCriteria::of_tuple(tuple_type, tuple_id),
+            )
+            .unwrap();
+        let operation_types = operations
+            .iter()
+            .map(|operation| OperationType::try_parse(operation.operation_type.as_deref().unwrap()))
+            .collect::<Vec<_>>();
+        assert!(matches!(
+            operation_types.as_slice(),
+            [
+                Some(OperationType::Create),
+                Some(OperationType::Update),
+                Some(OperationType::Update),
+                Some(OperationType::Delete)
+            ]
+        ));
+        assert!(operations
+            .iter()
+            .all(|operation| operation.created_by.as_deref() == Some("u-1")));
+    }
+}
@@ -0,0 +1,349 @@
+use crate::{MetaTupleType, OperationDiff, RuntimeModelKernelErrorCode};
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use elf_base::{ErrorCode, StdR};
+use elf_model::{Operation, OperationContent, OperationType, TenantId, UserId};
+use serde_json::{Map, Number, Value};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// one change of tuple, recorded as [Operation], its content is
+/// - before: tuple before change, absent when tuple is created,
+/// - after: tuple after change, absent when tuple is deleted,
+/// - diff: structural diff of before and after, see [OperationDiff].
+pub struct OperationRecord {
+    pub operation_type: OperationType,
+    pub tuple_type: MetaTupleType,
+    pub tuple_id: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+impl OperationRecord {
+    pub fn created(tuple_type: MetaTupleType, tuple_id: String, after: Value) -> Self {
+        Self {
+            operation_type: OperationType::Create,
+            tuple_type,
+            tuple_id,
+            before: None,
+            after: Some(after),
+        }
+    }
+
+    pub fn updated(
+        tuple_type: MetaTupleType,
+        tuple_id: String,
+        before: Value,
+        after: Value,
+    ) -> Self {
+        Self {
+            operation_type: OperationType::Update,
+            tuple_type,
+            tuple_id,
+            before: Some(before),
+            after: Some(after),
+        }
+    }
+
+    pub fn deleted(tuple_type: MetaTupleType, tuple_id: String, before: Value) -> Self {
+        Self {
+            operation_type: OperationType::Delete,
+            tuple_type,
+            tuple_id,
+            before: Some(before),
+            after: None,
+        }
+    }
+
+    fn invalid<R>(detail: String) -> StdR<R> {
+        RuntimeModelKernelErrorCode::OperationContentInvalid.msg(detail)
+    }
+
+    /// optimistic lock version of tuple after change
+    pub fn version(&self) -> Option<u64> {
+        self.after
+            .as_ref()
+            .and_then(|after| after.get("version"))
+            .and_then(Value::as_u64)
+    }
+
+    pub fn diffs(&self) -> Vec<OperationDiff> {
+        OperationDiff::between(self.before.as_ref(), self.after.as_ref())
+    }
+
+    /// operator is recorded as creator of operation.
+    /// version num of operation is left as none, it is the package version when operation is imported.
+    pub fn to_operation(
+        &self,
+        record_id: String,
+        tenant_id: &TenantId,
+        user_id: &UserId,
+        created_at: &NaiveDateTime,
+    ) -> StdR<Operation> {
+        let mut content = HashMap::new();
+        if let Some(before) = &self.before {
+            content.insert(String::from("before"), Self::json_to_content(before)?);
+        }
+        if let Some(after) = &self.after {
+            content.insert(String::from("after"), Self::json_to_content(after)?);
+        }
+        let diffs = self
+            .diffs()
+            .iter()
+            .map(|diff| Self::json_to_content(&diff.to_value()))
+            .collect::<StdR<Vec<OperationContent>>>()?;
+        content.insert(String::from("diff"), OperationContent::Vec(diffs));
+
+        Ok(Operation::new()
+            .record_id(record_id)
+            .operation_type(self.operation_type.to_string())
+            .tuple_key(self.tuple_type.id_key().to_string())
+            .tuple_type(self.tuple_type.to_string())
+            .tuple_id(self.tuple_id.clone())
+            .content(content)
+            .tenant_id(tenant_id.clone())
+            .created_at(*created_at)
+            .created_by(user_id.clone())
+            .last_modified_at(*created_at)
+            .last_modified_by(user_id.clone()))
+    }
+
+    pub fn from_operation(operation: &Operation) -> StdR<Self> {
+        let record_id = operation.record_id.as_deref().unwrap_or("");
+        let Some(operation_type) =
+            OperationType::try_parse(operation.operation_type.as_deref().unwrap_or(""))
+        else {
+            return Self::invalid(format!(
+                "Type of operation[{}] is not supported.",
+                record_id
+            ));
+        };
+        let tuple_type = MetaTupleType::parse(operation.tuple_type.as_deref().unwrap_or(""))?;
+        let Some(tuple_id) = operation.tuple_id.clone() else {
+            return Self::invalid(format!("Tuple id of operation[{}] is missed.", record_id));
+        };
+        let content = operation.content.as_ref();
+        let tuple_of = |key: &str| -> StdR<Option<Value>> {
+            match content.and_then(|content| content.get(key)) {
+                Some(tuple) => Ok(Some(Self::content_to_json(tuple)?)),
+                _ => Ok(None),
+            }
+        };
+        let (before, after) = (tuple_of("before")?, tuple_of("after")?);
+        let missed = match operation_type {
+            OperationType::Create => after.is_none(),
+            OperationType::Update => before.is_none() || after.is_none(),
+            OperationType::Delete => before.is_none(),
+        };
+        if missed {
+            return Self::invalid(format!("Content of operation[{}] is missed.", record_id));
+        }
+
+        Ok(Self {
+            operation_type,
+            tuple_type,
+            tuple_id,
+            before,
+            after,
+        })
+    }
+
+    /// content of tuple of given version, which is the latest one recorded by created or updated operations.
+    pub fn snapshot_of(operations: &[Operation], version: u64) -> StdR<Option<Value>> {
+        for operation in operations.iter().rev() {
+            let record = Self::from_operation(operation)?;
+            if matches!(record.operation_type, OperationType::Delete) {
+                continue;
+            }
+            if record.version() == Some(version) {
+                return Ok(record.after);
+            }
+        }
+        Ok(None)
+    }
+
+    /// null is ignored, since operation content has no null
+    fn optional_content_of(value: &Value) -> StdR<Option<OperationContent>> {
+        Ok(match value {
+            Value::Null => None,
+            Value::Bool(bool) => Some(OperationContent::Bool(*bool)),
+            Value::Number(number) => match BigDecimal::from_str(&number.to_string()) {
+                Ok(number) => Some(OperationContent::Num(number)),
+                Err(e) => {
+                    return Self::invalid(format!(
+                        "Number[{}] cannot be recorded, caused by {}.",
+                        number, e
+                    ));
+                }
+            },
+            Value::String(str) => Some(OperationContent::Str(str.clone())),
+            Value::Array(elements) => {
+                let mut vec = vec![];
+                for element in elements {
+                    if let Some(element) = Self::optional_content_of(element)? {
+                        vec.push(element);
+                    }
+                }
+                Some(OperationContent::Vec(vec))
+            }
+            Value::Object(map) => {
+                let mut content = HashMap::new();
+                for (key, value) in map {
+                    if let Some(value) = Self::optional_content_of(value)? {
+                        content.insert(key.clone(), value);
+                    }
+                }
+                Some(OperationContent::Map(content))
+            }
+        })
+    }
+
+    /// json to operation content, null is not allowed.
+    pub fn json_to_content(value: &Value) -> StdR<OperationContent> {
+        match Self::optional_content_of(value)? {
+            Some(content) => Ok(content),
+            _ => Self::invalid(String::from("Null cannot be recorded.")),
+        }
+    }
+
+    /// operation content to json
+    pub fn content_to_json(content: &OperationContent) -> StdR<Value> {
+        Ok(match content {
+            OperationContent::Str(str) => Value::String(str.clone()),
+            OperationContent::Bool(bool) => Value::Bool(*bool),
+            OperationContent::Num(number) => {
+                // integer keeps integer, such as version
+                let number = number.normalized().to_string();
+                match Number::from_str(&number) {
+                    Ok(number) => Value::Number(number),
+                    Err(e) => {
+                        return Self::invalid(format!(
+                            "Number[{}] cannot be restored, caused by {}.",
+                            number, e
+                        ));
+                    }
+                }
+            }
+            OperationContent::Vec(vec) => Value::Array(
+                vec.iter()
+                    .map(Self::content_to_json)
+                    .collect::<StdR<Vec<Value>>>()?,
+            ),
+            OperationContent::Map(map) => Value::Object(
+                map.iter()
+                    .map(|(key, value)| Ok((key.clone(), Self::content_to_json(value)?)))
+                    .collect::<StdR<Map<String, Value>>>()?,
+            ),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{IdGen, MetaTupleType, OperationRecord, SnowflakeIdGenerator};
+    use chrono::NaiveDate;
+    use elf_model::{Operation, OperationContent, OperationType};
+    use serde_json::{json, Value};
+
+    fn operation(record: OperationRecord) -> Operation {
+        record
+            .to_operation(
+                IdGen::next_id().unwrap().to_string(),
+                &String::from("t-1"),
+                &String::from("u-1"),
+                &NaiveDate::from_ymd_opt(2026, 3, 2)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap(),
+            )
+            .unwrap()
+    }
+
+    fn topic(name: &str, version: u64) -> Value {
+        json!({"topicId": "t1", "name": name, "version": version})
+    }
+
+    #[test]
+    fn test_operation() {
+        let before = json!({
+            "topicId": "t1",
+            "name": "orders",
+            "version": 3,
+            "description": null,
+            "factors": [{"factorId": "f1", "flatten": true, "precision": "10.2"}],
+        });
+        let mut after = before.clone();
+        after["name"] = json!("sales_orders");
+        after["version"] = json!(4);
+        let record =
+            OperationRecord::updated(MetaTupleType::Topic, String::from("t1"), before, after);
+        let operation = record
+            .to_operation(
+                String::from("r1"),
+                &String::from("t-1"),
+                &String::from("u-1"),
+                &NaiveDate::from_ymd_opt(2026, 3, 2)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap(),
+            )
+            .unwrap();
+        assert_eq!(operation.operation_type.as_deref(), Some("update"));
+        assert_eq!(operation.tuple_key.as_deref(), Some("topicId"));
+        assert_eq!(operation.created_by.as_deref(), Some("u-1"));
+        let content = operation.content.as_ref().unwrap();
+        assert!(
+            matches!(content.get("diff"), Some(OperationContent::Vec(diffs)) if diffs.len() == 1)
+        );
+
+        let restored = OperationRecord::from_operation(&operation).unwrap();
+        assert!(matches!(restored.operation_type, OperationType::Update));
+        assert_eq!(restored.version(), Some(4));
+        let before = restored.before.unwrap();
+        assert!(before.get("description").is_none());
+        assert_eq!(before["version"], 3);
+        assert_eq!(before["factors"][0]["flatten"], true);
+        assert_eq!(restored.after.unwrap()["name"], "sales_orders");
+
+        // content of update is missed
+        assert!(OperationRecord::from_operation(
+            &Operation::new()
+                .operation_type(String::from("update"))
+                .tuple_type(String::from("topic"))
+                .tuple_id(String::from("t1"))
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_snapshot_of() {
+        let _ = IdGen::set(Box::new(SnowflakeIdGenerator::new(1).unwrap()));
+        let tuple_id = String::from("t1");
+        let operations = vec![
+            operation(OperationRecord::created(
+                MetaTupleType::Topic,
+                tuple_id.clone(),
+                topic("orders", 1),
+            )),
+            operation(OperationRecord::updated(
+                MetaTupleType::Topic,
+                tuple_id.clone(),
+                topic("orders", 1),
+                topic("sales_orders", 2),
+            )),
+            operation(OperationRecord::updated(
+                MetaTupleType::Topic,
+                tuple_id.clone(),
+                topic("sales_orders", 2),
+                topic("shop_orders", 3),
+            )),
+        ];
+        let snapshot = OperationRecord::snapshot_of(&operations, 2).unwrap();
+        assert_eq!(snapshot.unwrap()["name"], "sales_orders");
+        let snapshot = OperationRecord::snapshot_of(&operations, 1).unwrap();
+        assert_eq!(snapshot.unwrap()["name"], "orders");
+        assert!(OperationRecord::snapshot_of(&operations, 5)
+            .unwrap()
+            .is_none());
+    }
+}
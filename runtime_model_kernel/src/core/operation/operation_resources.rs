@@ -0,0 +1,71 @@
+use crate::{MetaTupleMetaProvider, MetaTupleType, OperationCriteria, OperationMetaProvider};
+use elf_base::{StdR, VoidR};
+use elf_model::{Operation, TenantId};
+use serde_json::Value;
+
+/// everything operation log reads from and writes to, tuples and their operations.
+pub trait OperationResources {
+    fn find_tuple(
+        &self,
+        tuple_type: &MetaTupleType,
+        tuple_id: &str,
+        tenant_id: &TenantId,
+    ) -> StdR<Option<Value>>;
+    /// create when tuple id not exists, otherwise update.
+    fn save_tuple(&self, tuple_type: &MetaTupleType, tuple: &Value) -> VoidR;
+    fn delete_tuple(
+        &self,
+        tuple_type: &MetaTupleType,
+        tuple_id: &str,
+        tenant_id: &TenantId,
+    ) -> VoidR;
+    fn create_operation(&self, operation: &Operation) -> VoidR;
+    /// returns operations match the given criteria, ordered by created time ascending
+    fn find_operations(
+        &self,
+        criteria: &OperationCriteria,
+        tenant_id: &TenantId,
+    ) -> StdR<Vec<Operation>>;
+}
+
+/// tuples and operations are read from and written to meta.
+pub struct OperationMetaResources;
+
+impl MetaTupleMetaProvider for OperationMetaResources {}
+impl OperationMetaProvider for OperationMetaResources {}
+
+impl OperationResources for OperationMetaResources {
+    fn find_tuple(
+        &self,
+        tuple_type: &MetaTupleType,
+        tuple_id: &str,
+        tenant_id: &TenantId,
+    ) -> StdR<Option<Value>> {
+        Self::tuple_meta()?.find_by_id(tuple_type, &tuple_id.to_string(), tenant_id)
+    }
+
+    fn save_tuple(&self, tuple_type: &MetaTupleType, tuple: &Value) -> VoidR {
+        Self::tuple_meta()?.save(tuple_type, tuple)
+    }
+
+    fn delete_tuple(
+        &self,
+        tuple_type: &MetaTupleType,
+        tuple_id: &str,
+        tenant_id: &TenantId,
+    ) -> VoidR {
+        Self::tuple_meta()?.delete(tuple_type, &tuple_id.to_string(), tenant_id)
+    }
+
+    fn create_operation(&self, operation: &Operation) -> VoidR {
+        Self::operation_meta()?.create(operation)
+    }
+
+    fn find_operations(
+        &self,
+        criteria: &OperationCriteria,
+        tenant_id: &TenantId,
+    ) -> StdR<Vec<Operation>> {
+        Self::operation_meta()?.find(criteria, tenant_id)
+    }
+}
@@ -0,0 +1,284 @@
+use crate::{MetaTupleType, RuntimeModelKernelErrorCode};
+use chrono::NaiveDateTime;
+use elf_base::serde::naive_datetime;
+use elf_base::{ErrorCode, StdR, VoidR};
+use elf_model::{
+    Enum, Indicator, PackageVersion, Pipeline, Report, Space, Subject, TenantId, Topic,
+};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::io::{Cursor, Read, Write};
+use zip::write::SimpleFileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct MetaPackageManifest {
+    pub source_tenant_id: TenantId,
+    /// pre version is the version of previous package exported from source tenant
+    pub version: PackageVersion,
+    #[serde(with = "naive_datetime")]
+    pub exported_at: NaiveDateTime,
+}
+
+/// tuples to move between tenants, with manifest.
+/// as json, it is one object, tuples are grouped by type.
+/// as zip, manifest and each group are separated json files, see [MetaPackage::to_zip].
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct MetaPackage {
+    pub manifest: MetaPackageManifest,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub enums: Vec<Enum>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub topics: Vec<Topic>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pipelines: Vec<Pipeline>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spaces: Vec<Space>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subjects: Vec<Subject>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reports: Vec<Report>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub indicators: Vec<Indicator>,
+}
+
+impl MetaPackage {
+    pub fn new(manifest: MetaPackageManifest) -> Self {
+        Self {
+            manifest,
+            enums: vec![],
+            topics: vec![],
+            pipelines: vec![],
+            spaces: vec![],
+            subjects: vec![],
+            reports: vec![],
+            indicators: vec![],
+        }
+    }
+
+    fn invalid<R>(detail: String) -> StdR<R> {
+        RuntimeModelKernelErrorCode::MetaPackageInvalid.msg(detail)
+    }
+
+    fn from_value<T: DeserializeOwned>(tuple_type: &MetaTupleType, value: Value) -> StdR<T> {
+        serde_json::from_value(value).or_else(|e| {
+            Self::invalid(format!(
+                "Content of {} is invalid, caused by {}.",
+                tuple_type, e
+            ))
+        })
+    }
+
+    fn to_values<T: Serialize>(tuple_type: &MetaTupleType, tuples: &[T]) -> StdR<Vec<Value>> {
+        tuples
+            .iter()
+            .map(|tuple| {
+                serde_json::to_value(tuple).or_else(|e| {
+                    Self::invalid(format!(
+                        "Failed to serialize {}, caused by {}.",
+                        tuple_type, e
+                    ))
+                })
+            })
+            .collect()
+    }
+
+    /// json key of tuples of given type
+    fn group_key(tuple_type: &MetaTupleType) -> &'static str {
+        match tuple_type {
+            MetaTupleType::Enum => "enums",
+            MetaTupleType::Topic => "topics",
+            MetaTupleType::Pipeline => "pipelines",
+            MetaTupleType::Space => "spaces",
+            MetaTupleType::Subject => "subjects",
+            MetaTupleType::Report => "reports",
+            MetaTupleType::Indicator => "indicators",
+        }
+    }
+
+    pub fn add(&mut self, tuple_type: &MetaTupleType, content: Value) -> VoidR {
+        match tuple_type {
+            MetaTupleType::Enum => self.enums.push(Self::from_value(tuple_type, content)?),
+            MetaTupleType::Topic => self.topics.push(Self::from_value(tuple_type, content)?),
+            MetaTupleType::Pipeline => self.pipelines.push(Self::from_value(tuple_type, content)?),
+            MetaTupleType::Space => self.spaces.push(Self::from_value(tuple_type, content)?),
+            MetaTupleType::Subject => self.subjects.push(Self::from_value(tuple_type, content)?),
+            MetaTupleType::Report => self.reports.push(Self::from_value(tuple_type, content)?),
+            MetaTupleType::Indicator => {
+                self.indicators.push(Self::from_value(tuple_type, content)?)
+            }
+        }
+        Ok(())
+    }
+
+    /// tuples of given type, as json
+    pub fn tuples_of(&self, tuple_type: &MetaTupleType) -> StdR<Vec<Value>> {
+        match tuple_type {
+            MetaTupleType::Enum => Self::to_values(tuple_type, &self.enums),
+            MetaTupleType::Topic => Self::to_values(tuple_type, &self.topics),
+            MetaTupleType::Pipeline => Self::to_values(tuple_type, &self.pipelines),
+            MetaTupleType::Space => Self::to_values(tuple_type, &self.spaces),
+            MetaTupleType::Subject => Self::to_values(tuple_type, &self.subjects),
+            MetaTupleType::Report => Self::to_values(tuple_type, &self.reports),
+            MetaTupleType::Indicator => Self::to_values(tuple_type, &self.indicators),
+        }
+    }
+
+    /// all tuples as json, in import order
+    pub fn tuples(&self) -> StdR<Vec<(MetaTupleType, Value)>> {
+        let mut tuples = vec![];
+        for tuple_type in MetaTupleType::all() {
+            for tuple in self.tuples_of(&tuple_type)? {
+                tuples.push((tuple_type, tuple));
+            }
+        }
+        Ok(tuples)
+    }
+
+    pub fn to_json(&self) -> StdR<String> {
+        serde_json::to_string_pretty(self)
+            .or_else(|e| Self::invalid(format!("Failed to serialize package, caused by {}.", e)))
+    }
+
+    pub fn from_json(json: &str) -> StdR<Self> {
+        serde_json::from_str(json)
+            .or_else(|e| Self::invalid(format!("Failed to parse package, caused by {}.", e)))
+    }
+
+    fn write_entry(zip: &mut ZipWriter<Cursor<Vec<u8>>>, name: &str, value: &Value) -> VoidR {
+        let json = serde_json::to_vec_pretty(value).or_else(|e| {
+            Self::invalid(format!("Failed to serialize [{}], caused by {}.", name, e))
+        })?;
+        zip.start_file(name, SimpleFileOptions::default())
+            .or_else(|e| Self::invalid(format!("Failed to write [{}], caused by {}.", name, e)))?;
+        zip.write_all(&json)
+            .or_else(|e| Self::invalid(format!("Failed to write [{}], caused by {}.", name, e)))
+    }
+
+    /// zip file contains "manifest.json", and "topics.json", "pipelines.json", etc.
+    /// group file is absent when there is no tuple of its type.
+    pub fn to_zip(&self) -> StdR<Vec<u8>> {
+        let mut zip = ZipWriter::new(Cursor::new(vec![]));
+        let manifest = serde_json::to_value(&self.manifest).or_else(|e| {
+            Self::invalid(format!("Failed to serialize manifest, caused by {}.", e))
+        })?;
+        Self::write_entry(&mut zip, "manifest.json", &manifest)?;
+        for tuple_type in MetaTupleType::all() {
+            let tuples = self.tuples_of(&tuple_type)?;
+            if !tuples.is_empty() {
+                Self::write_entry(
+                    &mut zip,
+                    &format!("{}.json", Self::group_key(&tuple_type)),
+                    &Value::Array(tuples),
+                )?;
+            }
+        }
+        match zip.finish() {
+            Ok(cursor) => Ok(cursor.into_inner()),
+            Err(e) => Self::invalid(format!("Failed to write zip, caused by {}.", e)),
+        }
+    }
+
+    fn read_entry(zip: &mut ZipArchive<Cursor<&[u8]>>, name: &str) -> StdR<Option<Value>> {
+        let mut file = match zip.by_name(name) {
+            Ok(file) => file,
+            Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+            Err(e) => return Self::invalid(format!("Failed to read [{}], caused by {}.", name, e)),
+        };
+        let mut json = String::new();
+        file.read_to_string(&mut json)
+            .or_else(|e| Self::invalid(format!("Failed to read [{}], caused by {}.", name, e)))?;
+        serde_json::from_str(&json)
+            .map(Some)
+            .or_else(|e| Self::invalid(format!("Failed to parse [{}], caused by {}.", name, e)))
+    }
+
+    pub fn from_zip(bytes: &[u8]) -> StdR<Self> {
+        let mut zip = ZipArchive::new(Cursor::new(bytes))
+            .or_else(|e| Self::invalid(format!("Failed to read zip, caused by {}.", e)))?;
+        let mut package = Map::new();
+        match Self::read_entry(&mut zip, "manifest.json")? {
+            Some(manifest) => package.insert(String::from("manifest"), manifest),
+            _ => return Self::invalid(String::from("Manifest of package is missed.")),
+        };
+        for tuple_type in MetaTupleType::all() {
+            let key = Self::group_key(&tuple_type);
+            if let Some(tuples) = Self::read_entry(&mut zip, &format!("{}.json", key))? {
+                package.insert(key.to_string(), tuples);
+            }
+        }
+        serde_json::from_value(Value::Object(package))
+            .or_else(|e| Self::invalid(format!("Failed to parse package, caused by {}.", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{MetaPackage, MetaPackageManifest, MetaTupleType};
+    use chrono::NaiveDate;
+    use elf_model::{Enum, PackageVersion, Topic};
+    use serde_json::json;
+
+    fn sample_package() -> MetaPackage {
+        let mut package = MetaPackage::new(MetaPackageManifest {
+            source_tenant_id: String::from("dev"),
+            version: PackageVersion::new()
+                .pre_version(String::from("1.0.0"))
+                .curr_version(String::from("1.1.0")),
+            exported_at: NaiveDate::from_ymd_opt(2026, 3, 1)
+                .unwrap()
+                .and_hms_opt(10, 0, 0)
+                .unwrap(),
+        });
+        package.topics.push(
+            Topic::new()
+                .topic_id(String::from("t1"))
+                .name(String::from("orders"))
+                .version(2),
+        );
+        package
+            .add(
+                &MetaTupleType::Enum,
+                json!({"enumId": "e1", "name": "status", "items": [{"code": "paid"}]}),
+            )
+            .unwrap();
+        package
+    }
+
+    #[test]
+    fn test_json() {
+        let json = sample_package().to_json().unwrap();
+        let package = MetaPackage::from_json(&json).unwrap();
+        assert_eq!(package.manifest.source_tenant_id, "dev");
+        assert_eq!(
+            package.manifest.version.curr_version.as_deref(),
+            Some("1.1.0")
+        );
+        let tuples = package.tuples().unwrap();
+        // enum goes first
+        assert_eq!(tuples[0].0, MetaTupleType::Enum);
+        assert_eq!(tuples[1].0, MetaTupleType::Topic);
+        assert_eq!(tuples[1].1["version"], 2);
+
+        assert!(MetaPackage::from_json(r#"{"manifest": {}}"#).is_err());
+        assert!(sample_package()
+            .add(&MetaTupleType::Enum, json!({"unknown": 1}))
+            .is_err());
+    }
+
+    #[test]
+    fn test_zip() {
+        let bytes = sample_package().to_zip().unwrap();
+        let package = MetaPackage::from_zip(&bytes).unwrap();
+        assert_eq!(package.topics.len(), 1);
+        assert_eq!(package.enums.len(), 1);
+        assert!(package.pipelines.is_empty());
+        let enumeration: &Enum = &package.enums[0];
+        assert_eq!(enumeration.items.as_ref().unwrap().len(), 1);
+
+        assert!(MetaPackage::from_zip(b"not a zip").is_err());
+    }
+}
@@ -0,0 +1,454 @@
+use crate::{IdGen, MetaPackage, MetaTupleType, OperationRecord, RuntimeModelKernelErrorCode};
+use chrono::NaiveDateTime;
+use elf_base::{ErrorCode, StdR};
+use elf_model::{Operation, TenantId, UserId};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// how to resolve the conflict when tuple exists in target tenant and is changed since.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum MetaPackageConflictStrategy {
+    /// keep the existing one
+    Skip,
+    /// replace the existing one
+    Overwrite,
+    /// import as a new tuple, with a new id and renamed
+    Rename,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum MetaPackageTupleAction {
+    /// not exists in target tenant
+    Create,
+    /// exists in target tenant, and not newer than the one in package
+    Update,
+    /// exists in target tenant, and same as the one in package
+    Unchanged,
+    /// conflicts, existing one is kept
+    Skip,
+    /// conflicts, existing one is replaced
+    Overwrite,
+    /// conflicts, imported as a new tuple
+    Rename,
+}
+
+impl MetaPackageTupleAction {
+    pub fn is_changed(&self) -> bool {
+        matches!(
+            self,
+            Self::Create | Self::Update | Self::Overwrite | Self::Rename
+        )
+    }
+
+    pub fn is_conflict(&self) -> bool {
+        matches!(self, Self::Skip | Self::Overwrite | Self::Rename)
+    }
+}
+
+pub struct MetaPackageTupleChange {
+    pub tuple_type: MetaTupleType,
+    /// id in package
+    pub source_id: String,
+    /// id in target tenant, differs from source id only when renamed
+    pub tuple_id: String,
+    pub action: MetaPackageTupleAction,
+    /// tuple to save, tenant and references are remapped
+    pub content: Value,
+    /// existing tuple in target tenant
+    pub existing: Option<Value>,
+}
+
+/// what should be done to import a meta package to a tenant.
+pub struct MetaPackageImportPlan {
+    pub tenant_id: TenantId,
+    pub user_id: UserId,
+    /// current version of package
+    pub version: Option<String>,
+    pub changes: Vec<MetaPackageTupleChange>,
+}
+
+/// json keys which refer to tuples in package, are remapped when referred tuple is renamed
+const REFERENCE_KEYS: [&str; 10] = [
+    "enumId",
+    "parentEnumId",
+    "topicId",
+    "topicIds",
+    "pipelineId",
+    "spaceId",
+    "subjectId",
+    "reportId",
+    "indicatorId",
+    "topicOrSubjectId",
+];
+
+/// json keys not compared, they are maintained by target tenant
+const IGNORED_KEYS: [&str; 8] = [
+    "tenantId",
+    "userId",
+    "version",
+    "createdAt",
+    "createdBy",
+    "lastModifiedAt",
+    "lastModifiedBy",
+    "lastVisitTime",
+];
+
+const RENAME_SUFFIX: &str = "_imported";
+
+impl MetaPackageImportPlan {
+    /// build plan by comparing tuples of package to the existing ones of target tenant,
+    /// existing one is located by id.
+    /// - tenant of all tuples is replaced by target tenant, user of user based tuples is replaced by given user,
+    /// - tuple which has an optimistic lock version is updated when existing version is less than package one,
+    ///   otherwise it is a conflict, unless content is same.
+    ///   tuple which has no version is a conflict when content is different,
+    /// - conflict is resolved by given strategy,
+    /// - references to renamed tuples are remapped to the new ids.
+    pub fn build<FindTuple>(
+        package: &MetaPackage,
+        tenant_id: &TenantId,
+        user_id: &UserId,
+        strategy: MetaPackageConflictStrategy,
+        find_tuple: FindTuple,
+    ) -> StdR<Self>
+    where
+        FindTuple: Fn(&MetaTupleType, &String) -> StdR<Option<Value>>,
+    {
+        let mut changes = vec![];
+        let mut renamed_ids = HashMap::new();
+        for (tuple_type, mut content) in package.tuples()? {
+            let Some(source_id) = content
+                .get(tuple_type.id_key())
+                .and_then(Value::as_str)
+                .map(String::from)
+            else {
+                return RuntimeModelKernelErrorCode::MetaPackageTupleIdMissed
+                    .msg(format!("Id of {} in package is missed.", tuple_type));
+            };
+            content["tenantId"] = Value::String(tenant_id.clone());
+            if tuple_type.is_user_based() {
+                content["userId"] = Value::String(user_id.clone());
+            }
+
+            let existing = find_tuple(&tuple_type, &source_id)?;
+            let action = Self::action_of(&tuple_type, &content, existing.as_ref(), strategy);
+            let mut tuple_id = source_id.clone();
+            match action {
+                MetaPackageTupleAction::Create => Self::remove_version(&mut content),
+                MetaPackageTupleAction::Update | MetaPackageTupleAction::Overwrite => {
+                    // existing version is used for optimistic lock
+                    match existing
+                        .as_ref()
+                        .and_then(|existing| existing.get("version"))
+                    {
+                        Some(version) => content["version"] = version.clone(),
+                        _ => Self::remove_version(&mut content),
+                    }
+                }
+                MetaPackageTupleAction::Rename => {
+                    tuple_id = IdGen::next_id()?.to_string();
+                    content[tuple_type.id_key()] = Value::String(tuple_id.clone());
+                    if let Some(name) = content.get("name").and_then(Value::as_str) {
+                        content["name"] = Value::String(format!("{}{}", name, RENAME_SUFFIX));
+                    }
+                    Self::remove_version(&mut content);
+                    renamed_ids.insert(source_id.clone(), tuple_id.clone());
+                }
+                MetaPackageTupleAction::Unchanged | MetaPackageTupleAction::Skip => {}
+            }
+            changes.push(MetaPackageTupleChange {
+                tuple_type,
+                source_id,
+                tuple_id,
+                action,
+                content,
+                existing,
+            });
+        }
+
+        if !renamed_ids.is_empty() {
+            for change in changes.iter_mut().filter(|c| c.action.is_changed()) {
+                Self::remap(&mut change.content, &renamed_ids);
+            }
+        }
+
+        Ok(Self {
+            tenant_id: tenant_id.clone(),
+            user_id: user_id.clone(),
+            version: package.manifest.version.curr_version.clone(),
+            changes,
+        })
+    }
+
+    fn remove_version(content: &mut Value) {
+        if let Some(content) = content.as_object_mut() {
+            content.remove("version");
+        }
+    }
+
+    fn version_of(content: &Value) -> u64 {
+        content.get("version").and_then(Value::as_u64).unwrap_or(0)
+    }
+
+    fn is_same(content: &Value, existing: &Value) -> bool {
+        match (content.as_object(), existing.as_object()) {
+            (Some(content), Some(existing)) => {
+                let compared = |&(key, value): &(&String, &Value)| {
+                    !IGNORED_KEYS.contains(&key.as_str()) && !value.is_null()
+                };
+                content.iter().filter(compared).count() == existing.iter().filter(compared).count()
+                    && content
+                        .iter()
+                        .filter(compared)
+                        .all(|(key, value)| existing.get(key) == Some(value))
+            }
+            _ => content == existing,
+        }
+    }
+
+    fn action_of(
+        tuple_type: &MetaTupleType,
+        content: &Value,
+        existing: Option<&Value>,
+        strategy: MetaPackageConflictStrategy,
+    ) -> MetaPackageTupleAction {
+        let Some(existing) = existing else {
+            return MetaPackageTupleAction::Create;
+        };
+        if Self::is_same(content, existing) {
+            MetaPackageTupleAction::Unchanged
+        } else if tuple_type.is_versioned()
+            && Self::version_of(existing) < Self::version_of(content)
+        {
+            MetaPackageTupleAction::Update
+        } else {
+            match strategy {
+                MetaPackageConflictStrategy::Skip => MetaPackageTupleAction::Skip,
+                MetaPackageConflictStrategy::Overwrite => MetaPackageTupleAction::Overwrite,
+                MetaPackageConflictStrategy::Rename => MetaPackageTupleAction::Rename,
+            }
+        }
+    }
+
+    fn remap_value(value: &mut Value, renamed_ids: &HashMap<String, String>) {
+        match value {
+            Value::String(id) => {
+                if let Some(renamed_id) = renamed_ids.get(id) {
+                    *id = renamed_id.clone();
+                }
+            }
+            Value::Array(ids) => {
+                for id in ids {
+                    Self::remap_value(id, renamed_ids);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// remap references at any depth
+    fn remap(content: &mut Value, renamed_ids: &HashMap<String, String>) {
+        match content {
+            Value::Object(map) => {
+                for (key, value) in map.iter_mut() {
+                    if REFERENCE_KEYS.contains(&key.as_str()) {
+                        Self::remap_value(value, renamed_ids);
+                    } else {
+                        Self::remap(value, renamed_ids);
+                    }
+                }
+            }
+            Value::Array(elements) => {
+                for element in elements {
+                    Self::remap(element, renamed_ids);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn conflicts(&self) -> Vec<&MetaPackageTupleChange> {
+        self.changes
+            .iter()
+            .filter(|change| change.action.is_conflict())
+            .collect()
+    }
+
+    pub fn has_changes(&self) -> bool {
+        self.changes.iter().any(|change| change.action.is_changed())
+    }
+
+    /// one operation for each changed tuple, in import order, see [OperationRecord].
+    /// - created or renamed: create operation,
+    /// - updated or overwritten: update operation, with the existing tuple before import,
+    ///
+    /// version num of operation is the current version of package,
+    /// so import can be rolled back by [crate::MetaPackageOperations::rollback].
+    pub fn operations(&self, created_at: &NaiveDateTime) -> StdR<Vec<Operation>> {
+        let mut operations = vec![];
+        for change in self.changes.iter().filter(|c| c.action.is_changed()) {
+            let record = match (&change.action, &change.existing) {
+                (
+                    MetaPackageTupleAction::Update | MetaPackageTupleAction::Overwrite,
+                    Some(existing),
+                ) => OperationRecord::updated(
+                    change.tuple_type,
+                    change.tuple_id.clone(),
+                    existing.clone(),
+                    change.content.clone(),
+                ),
+                _ => OperationRecord::created(
+                    change.tuple_type,
+                    change.tuple_id.clone(),
+                    change.content.clone(),
+                ),
+            };
+            let mut operation = record.to_operation(
+                IdGen::next_id()?.to_string(),
+                &self.tenant_id,
+                &self.user_id,
+                created_at,
+            )?;
+            operation.version_num = self.version.clone();
+            operations.push(operation);
+        }
+        Ok(operations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        IdGen, MetaPackage, MetaPackageConflictStrategy, MetaPackageImportPlan,
+        MetaPackageManifest, MetaPackageTupleAction, MetaTupleType, SnowflakeIdGenerator,
+    };
+    use chrono::NaiveDate;
+    use elf_model::PackageVersion;
+    use serde_json::{json, Value};
+
+    fn package() -> MetaPackage {
+        let mut package = MetaPackage::new(MetaPackageManifest {
+            source_tenant_id: String::from("dev"),
+            version: PackageVersion::new().curr_version(String::from("1.1.0")),
+            exported_at: NaiveDate::from_ymd_opt(2026, 3, 1)
+                .unwrap()
+                .and_hms_opt(10, 0, 0)
+                .unwrap(),
+        });
+        let tuples = [
+            (
+                MetaTupleType::Topic,
+                json!({"topicId": "t1", "name": "orders", "version": 3, "tenantId": "dev"}),
+            ),
+            (
+                MetaTupleType::Topic,
+                json!({"topicId": "t2", "name": "customers", "version": 2, "tenantId": "dev"}),
+            ),
+            (
+                MetaTupleType::Topic,
+                json!({"topicId": "t3", "name": "products", "version": 1, "tenantId": "dev"}),
+            ),
+            (
+                MetaTupleType::Pipeline,
+                json!({"pipelineId": "p1", "name": "customers", "topicId": "t2", "version": 1, "tenantId": "dev"}),
+            ),
+            (
+                MetaTupleType::Subject,
+                json!({"subjectId": "s1", "name": "sales", "tenantId": "dev", "userId": "u-dev"}),
+            ),
+        ];
+        for (tuple_type, content) in tuples {
+            package.add(&tuple_type, content).unwrap();
+        }
+        package
+    }
+
+    /// t1 exists with older version and different content, t2 exists with newer version, t3 is same, s1 not exists
+    fn find_tuple(tuple_type: &MetaTupleType, id: &str) -> Option<Value> {
+        match (tuple_type, id) {
+            (MetaTupleType::Topic, "t1") => Some(
+                json!({"topicId": "t1", "name": "orders", "description": "old", "version": 1, "tenantId": "prod"}),
+            ),
+            (MetaTupleType::Topic, "t2") => Some(
+                json!({"topicId": "t2", "name": "customers_v2", "version": 5, "tenantId": "prod"}),
+            ),
+            (MetaTupleType::Topic, "t3") => {
+                Some(json!({"topicId": "t3", "name": "products", "version": 7, "tenantId": "prod"}))
+            }
+            _ => None,
+        }
+    }
+
+    fn plan(strategy: MetaPackageConflictStrategy) -> MetaPackageImportPlan {
+        MetaPackageImportPlan::build(
+            &package(),
+            &String::from("prod"),
+            &String::from("u-prod"),
+            strategy,
+            |tuple_type, id| Ok(find_tuple(tuple_type, id)),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_skip() {
+        let plan = plan(MetaPackageConflictStrategy::Skip);
+        let actions: Vec<MetaPackageTupleAction> =
+            plan.changes.iter().map(|c| c.action.clone()).collect();
+        assert_eq!(
+            actions,
+            vec![
+                MetaPackageTupleAction::Update,
+                MetaPackageTupleAction::Skip,
+                MetaPackageTupleAction::Unchanged,
+                MetaPackageTupleAction::Create,
+                MetaPackageTupleAction::Create,
+            ]
+        );
+        assert_eq!(plan.conflicts().len(), 1);
+        // existing version for optimistic lock
+        assert_eq!(plan.changes[0].content["version"], 1);
+        assert_eq!(plan.changes[0].content["tenantId"], "prod");
+        assert_eq!(plan.changes[4].content["userId"], "u-prod");
+        assert!(plan.changes[4].content.get("version").is_none());
+    }
+
+    #[test]
+    fn test_rename() {
+        let _ = IdGen::set(Box::new(SnowflakeIdGenerator::new(1).unwrap()));
+        let plan = plan(MetaPackageConflictStrategy::Rename);
+        let renamed = &plan.changes[1];
+        assert_eq!(renamed.action, MetaPackageTupleAction::Rename);
+        assert_ne!(renamed.tuple_id, "t2");
+        assert_eq!(renamed.content["topicId"], renamed.tuple_id.as_str());
+        assert_eq!(renamed.content["name"], "customers_imported");
+        // pipeline refers to renamed topic
+        assert_eq!(
+            plan.changes[3].content["topicId"],
+            renamed.tuple_id.as_str()
+        );
+
+        let operations = plan
+            .operations(
+                &NaiveDate::from_ymd_opt(2026, 3, 2)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap(),
+            )
+            .unwrap();
+        // update t1, create renamed t2, create p1, create s1
+        assert_eq!(operations.len(), 4);
+        assert_eq!(operations[0].operation_type.as_deref(), Some("update"));
+        assert_eq!(operations[0].version_num.as_deref(), Some("1.1.0"));
+        assert_eq!(operations[1].operation_type.as_deref(), Some("create"));
+        assert_eq!(operations[1].tuple_id.as_ref(), Some(&renamed.tuple_id));
+    }
+
+    #[test]
+    fn test_overwrite() {
+        let plan = plan(MetaPackageConflictStrategy::Overwrite);
+        assert_eq!(plan.changes[1].action, MetaPackageTupleAction::Overwrite);
+        assert_eq!(plan.changes[1].content["version"], 5);
+        assert_eq!(plan.changes[1].content["name"], "customers");
+    }
+}
@@ -0,0 +1,106 @@
+use crate::{
+    MetaPackage, MetaPackageConflictStrategy, MetaPackageImportPlan, MetaPackageManifest,
+    MetaPackageOperations, MetaPackageRollbackChange, MetaTupleMetaProvider, MetaTupleType,
+    OperationMetaProvider, RuntimeModelKernelErrorCode,
+};
+use chrono::NaiveDateTime;
+use elf_base::{ErrorCode, StdR, VoidR};
+use elf_model::{Operation, PackageVersion, TenantId, UserId};
+
+/// export tuples of a tenant to meta package, and import meta package to another tenant.
+/// each change of import is recorded as an operation, which can be used to roll back the import.
+pub struct MetaPackageImporter;
+
+impl MetaTupleMetaProvider for MetaPackageImporter {}
+impl OperationMetaProvider for MetaPackageImporter {}
+
+impl MetaPackageImporter {
+    /// raise error when any of given tuples not found.
+    pub fn export(
+        tenant_id: &TenantId,
+        tuples: &[(MetaTupleType, String)],
+        version: PackageVersion,
+        exported_at: NaiveDateTime,
+    ) -> StdR<MetaPackage> {
+        let tuple_meta = Self::tuple_meta()?;
+        let mut package = MetaPackage::new(MetaPackageManifest {
+            source_tenant_id: tenant_id.clone(),
+            version,
+            exported_at,
+        });
+        for (tuple_type, tuple_id) in tuples {
+            match tuple_meta.find_by_id(tuple_type, tuple_id, tenant_id)? {
+                Some(tuple) => package.add(tuple_type, tuple)?,
+                _ => {
+                    return RuntimeModelKernelErrorCode::MetaPackageInvalid.msg(format!(
+                        "{}[{}] of tenant[{}] not found.",
+                        tuple_type, tuple_id, tenant_id
+                    ));
+                }
+            }
+        }
+        Ok(package)
+    }
+
+    /// compare the package to the target tenant, nothing will be saved.
+    pub fn diff(
+        package: &MetaPackage,
+        tenant_id: &TenantId,
+        user_id: &UserId,
+        strategy: MetaPackageConflictStrategy,
+    ) -> StdR<MetaPackageImportPlan> {
+        let tuple_meta = Self::tuple_meta()?;
+        MetaPackageImportPlan::build(
+            package,
+            tenant_id,
+            user_id,
+            strategy,
+            |tuple_type, tuple_id| tuple_meta.find_by_id(tuple_type, tuple_id, tenant_id),
+        )
+    }
+
+    /// returns the operations of import, in import order.
+    pub fn import(
+        package: &MetaPackage,
+        tenant_id: &TenantId,
+        user_id: &UserId,
+        strategy: MetaPackageConflictStrategy,
+        imported_at: &NaiveDateTime,
+    ) -> StdR<Vec<Operation>> {
+        let plan = Self::diff(package, tenant_id, user_id, strategy)?;
+        let operations = plan.operations(imported_at)?;
+
+        let tuple_meta = Self::tuple_meta()?;
+        let operation_meta = Self::operation_meta()?;
+        // operations are in the same order of changed tuples
+        for (change, operation) in plan
+            .changes
+            .iter()
+            .filter(|change| change.action.is_changed())
+            .zip(operations.iter())
+        {
+            tuple_meta.save(&change.tuple_type, &change.content)?;
+            operation_meta.create(operation)?;
+        }
+
+        Ok(operations)
+    }
+
+    /// roll back an import by its operations, created tuples are deleted, updated tuples are restored.
+    pub fn rollback(tenant_id: &TenantId, operations: &[Operation]) -> VoidR {
+        let tuple_meta = Self::tuple_meta()?;
+        for change in MetaPackageOperations::rollback(operations)? {
+            match change {
+                MetaPackageRollbackChange::Delete {
+                    tuple_type,
+                    tuple_id,
+                } => tuple_meta.delete(&tuple_type, &tuple_id, tenant_id)?,
+                MetaPackageRollbackChange::Restore {
+                    tuple_type,
+                    content,
+                } => tuple_meta.save(&tuple_type, &content)?,
+            }
+        }
+        Ok(())
+    }
+}
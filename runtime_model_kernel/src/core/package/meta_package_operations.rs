@@ -0,0 +1,105 @@
+use crate::{MetaTupleType, OperationRecord, RuntimeModelKernelErrorCode};
+use elf_base::{ErrorCode, StdR};
+use elf_model::{Operation, OperationType};
+use serde_json::Value;
+
+/// what should be done to roll back one operation
+#[derive(PartialEq, Debug)]
+pub enum MetaPackageRollbackChange {
+    /// tuple is created by operation
+    Delete {
+        tuple_type: MetaTupleType,
+        tuple_id: String,
+    },
+    /// tuple is updated or deleted by operation, restore to the content before
+    Restore {
+        tuple_type: MetaTupleType,
+        content: Value,
+    },
+}
+
+/// roll back tuple changes by their [Operation]s, see [OperationRecord].
+pub struct MetaPackageOperations;
+
+impl MetaPackageOperations {
+    /// changes to roll back given operations, in reverse order.
+    pub fn rollback(operations: &[Operation]) -> StdR<Vec<MetaPackageRollbackChange>> {
+        let mut changes = vec![];
+        for operation in operations.iter().rev() {
+            let record = OperationRecord::from_operation(operation)?;
+            let change = match (record.operation_type, record.before) {
+                (OperationType::Create, _) => MetaPackageRollbackChange::Delete {
+                    tuple_type: record.tuple_type,
+                    tuple_id: record.tuple_id,
+                },
+                (_, Some(before)) => MetaPackageRollbackChange::Restore {
+                    tuple_type: record.tuple_type,
+                    content: before,
+                },
+                _ => {
+                    return RuntimeModelKernelErrorCode::MetaPackageOperationInvalid.msg(format!(
+                        "Operation[{}] cannot be rolled back, content before is missed.",
+                        operation.record_id.as_deref().unwrap_or("")
+                    ));
+                }
+            };
+            changes.push(change);
+        }
+        Ok(changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{MetaPackageOperations, MetaPackageRollbackChange, MetaTupleType, OperationRecord};
+    use chrono::NaiveDate;
+    use elf_model::Operation;
+    use serde_json::json;
+
+    #[test]
+    fn test_rollback() {
+        let created_at = NaiveDate::from_ymd_opt(2026, 3, 2)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        let (tenant_id, user_id) = (String::from("t-1"), String::from("u-1"));
+        let topic = json!({"topicId": "t1", "name": "orders", "version": 3});
+        let operations = vec![
+            OperationRecord::updated(
+                MetaTupleType::Topic,
+                String::from("t1"),
+                topic.clone(),
+                json!({"topicId": "t1", "name": "sales_orders", "version": 4}),
+            )
+            .to_operation(String::from("r1"), &tenant_id, &user_id, &created_at)
+            .unwrap(),
+            OperationRecord::created(
+                MetaTupleType::Pipeline,
+                String::from("p1"),
+                json!({"pipelineId": "p1"}),
+            )
+            .to_operation(String::from("r2"), &tenant_id, &user_id, &created_at)
+            .unwrap(),
+        ];
+        let changes = MetaPackageOperations::rollback(&operations).unwrap();
+        assert_eq!(
+            changes[0],
+            MetaPackageRollbackChange::Delete {
+                tuple_type: MetaTupleType::Pipeline,
+                tuple_id: String::from("p1"),
+            }
+        );
+        assert_eq!(
+            changes[1],
+            MetaPackageRollbackChange::Restore {
+                tuple_type: MetaTupleType::Topic,
+                content: topic,
+            }
+        );
+
+        assert!(MetaPackageOperations::rollback(&[Operation::new()
+            .operation_type(String::from("delete"))
+            .tuple_type(String::from("topic"))])
+        .is_err());
+    }
+}
@@ -0,0 +1,44 @@
+use crate::MetaTupleType;
+use elf_base::{StdR, VoidR};
+use elf_model::TenantId;
+use serde_json::Value;
+use std::sync::Arc;
+
+/// TODO meta tuple service using tenant and it's meta datasource (or the global meta datasource)
+///  to read and write tuples of meta package as json, regardless of their types.
+pub struct MetaTupleMetaService;
+
+impl MetaTupleMetaService {
+    fn new() -> StdR<Arc<Self>> {
+        Ok(Arc::new(Self {}))
+    }
+
+    pub fn find_by_id(
+        &self,
+        _tuple_type: &MetaTupleType,
+        _tuple_id: &String,
+        _tenant_id: &TenantId,
+    ) -> StdR<Option<Value>> {
+        todo!("implement find_by_id for MetaTupleMetaService")
+    }
+
+    /// create when tuple id not exists, otherwise update.
+    pub fn save(&self, _tuple_type: &MetaTupleType, _tuple: &Value) -> VoidR {
+        todo!("implement save for MetaTupleMetaService")
+    }
+
+    pub fn delete(
+        &self,
+        _tuple_type: &MetaTupleType,
+        _tuple_id: &String,
+        _tenant_id: &TenantId,
+    ) -> VoidR {
+        todo!("implement delete for MetaTupleMetaService")
+    }
+}
+
+pub trait MetaTupleMetaProvider {
+    fn tuple_meta() -> StdR<Arc<MetaTupleMetaService>> {
+        MetaTupleMetaService::new()
+    }
+}
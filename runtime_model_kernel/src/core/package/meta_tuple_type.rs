@@ -0,0 +1,78 @@
+use crate::RuntimeModelKernelErrorCode;
+use elf_base::{ErrorCode, StdR};
+use std::fmt::{Display, Formatter};
+
+/// type of tuple which can be moved between tenants by meta package.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum MetaTupleType {
+    Enum,
+    Topic,
+    Pipeline,
+    Space,
+    Subject,
+    Report,
+    Indicator,
+}
+
+impl MetaTupleType {
+    /// in import order, tuple is imported after the tuples it refers to.
+    pub fn all() -> [Self; 7] {
+        [
+            Self::Enum,
+            Self::Topic,
+            Self::Pipeline,
+            Self::Space,
+            Self::Subject,
+            Self::Report,
+            Self::Indicator,
+        ]
+    }
+
+    /// json key of id
+    pub fn id_key(&self) -> &'static str {
+        match self {
+            Self::Enum => "enumId",
+            Self::Topic => "topicId",
+            Self::Pipeline => "pipelineId",
+            Self::Space => "spaceId",
+            Self::Subject => "subjectId",
+            Self::Report => "reportId",
+            Self::Indicator => "indicatorId",
+        }
+    }
+
+    /// returns true when tuple has optimistic lock version
+    pub fn is_versioned(&self) -> bool {
+        !self.is_user_based()
+    }
+
+    /// returns true when tuple belongs to user
+    pub fn is_user_based(&self) -> bool {
+        matches!(self, Self::Subject | Self::Report)
+    }
+
+    pub fn parse(tuple_type: &str) -> StdR<Self> {
+        match Self::all()
+            .into_iter()
+            .find(|t| t.to_string() == tuple_type)
+        {
+            Some(tuple_type) => Ok(tuple_type),
+            _ => RuntimeModelKernelErrorCode::MetaPackageTupleTypeInvalid
+                .msg(format!("Tuple type[{}] is not supported.", tuple_type)),
+        }
+    }
+}
+
+impl Display for MetaTupleType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Enum => write!(f, "enum"),
+            Self::Topic => write!(f, "topic"),
+            Self::Pipeline => write!(f, "pipeline"),
+            Self::Space => write!(f, "space"),
+            Self::Subject => write!(f, "subject"),
+            Self::Report => write!(f, "report"),
+            Self::Indicator => write!(f, "indicator"),
+        }
+    }
+}
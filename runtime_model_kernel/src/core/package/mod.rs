@@ -0,0 +1,13 @@
+mod meta_package;
+mod meta_package_import_plan;
+mod meta_package_importer;
+mod meta_package_operations;
+mod meta_tuple_meta_service;
+mod meta_tuple_type;
+
+pub use meta_package::*;
+pub use meta_package_import_plan::*;
+pub use meta_package_importer::*;
+pub use meta_package_operations::*;
+pub use meta_tuple_meta_service::*;
+pub use meta_tuple_type::*;
@@ -1,5 +1,8 @@
+use crate::{MetaTupleType, OperationLogService, OperationMetaResources, OperationResources};
+use chrono::Utc;
+use elf_auth::Principal;
 use elf_base::StdR;
-use elf_model::{Pipeline, PipelineId, TenantId, TopicId};
+use elf_model::{Operation, Pipeline, PipelineId, TenantId, TopicId};
 use std::sync::Arc;
 
 /// TODO pipeline meta service using tenant and it's meta datasource (or the global meta datasource)
@@ -28,6 +31,27 @@ impl PipelineMetaService {
     ) -> StdR<Option<Vec<Pipeline>>> {
         todo!("implement find_pipeline_by_topic for PipelineMetaService")
     }
+
+    /// create when pipeline id not exists, otherwise update.
+    /// pipeline is saved by [OperationLogService], so the change is always recorded as an operation of principal.
+    pub fn save(&self, principal: &Principal, pipeline: &Pipeline) -> StdR<Operation> {
+        self.save_by(OperationMetaResources, principal, pipeline)
+    }
+
+    /// save pipeline into given resources, see [PipelineMetaService::save].
+    pub fn save_by<R: OperationResources>(
+        &self,
+        resources: R,
+        principal: &Principal,
+        pipeline: &Pipeline,
+    ) -> StdR<Operation> {
+        OperationLogService::new(resources).save_model(
+            principal,
+            &MetaTupleType::Pipeline,
+            pipeline,
+            &Utc::now().naive_utc(),
+        )
+    }
 }
 
 pub trait PipelineMetaProvider {
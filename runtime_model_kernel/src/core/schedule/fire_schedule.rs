@@ -0,0 +1,99 @@
+use chrono::{Datelike, Days, Months, NaiveDate, NaiveDateTime, NaiveTime};
+
+pub enum FireFrequency {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+/// fire times of scheduler, fires once in each period.
+/// - daily: at [hour:minute] of every day,
+/// - weekly: at [hour:minute] of given weekday, week starts from sunday,
+/// - monthly: at [hour:minute] of given day, or the last day of month when month doesn't have the day.
+///
+/// the date of fire time is used as the process date of job lock.
+pub struct FireSchedule {
+    frequency: FireFrequency,
+    weekday: u32,
+    day: u32,
+    time: NaiveTime,
+}
+
+impl FireSchedule {
+    /// - weekday: 0 is sunday, 6 is saturday, only for weekly,
+    /// - day: 1 - 31, only for monthly,
+    /// - time is midnight when hour or minute is out of range.
+    pub fn new(frequency: FireFrequency, weekday: u32, day: u32, hour: u32, minute: u32) -> Self {
+        Self {
+            frequency,
+            weekday: weekday.min(6),
+            day: day.clamp(1, 31),
+            time: NaiveTime::from_hms_opt(hour, minute, 0).unwrap_or(NaiveTime::MIN),
+        }
+    }
+
+    fn first_day(&self, date: &NaiveDate) -> NaiveDate {
+        match self.frequency {
+            FireFrequency::Daily => *date,
+            FireFrequency::Weekly => {
+                *date - Days::new(date.weekday().num_days_from_sunday() as u64)
+            }
+            FireFrequency::Monthly => date.with_day(1).unwrap_or(*date),
+        }
+    }
+
+    fn next_first_day(&self, first_day: &NaiveDate) -> NaiveDate {
+        match self.frequency {
+            FireFrequency::Daily => *first_day + Days::new(1),
+            FireFrequency::Weekly => *first_day + Days::new(7),
+            FireFrequency::Monthly => *first_day + Months::new(1),
+        }
+    }
+
+    /// fire time of the period which starts from given first day
+    fn fire_time_in(&self, first_day: &NaiveDate) -> NaiveDateTime {
+        let date = match self.frequency {
+            FireFrequency::Daily => *first_day,
+            FireFrequency::Weekly => *first_day + Days::new(self.weekday as u64),
+            FireFrequency::Monthly => {
+                let last_day = self.next_first_day(first_day) - Days::new(1);
+                first_day
+                    .with_day(self.day.min(last_day.day()))
+                    .unwrap_or(last_day)
+            }
+        };
+        date.and_time(self.time)
+    }
+
+    /// the fire time which process date is given date
+    pub fn fire_time_of(&self, process_date: &NaiveDate) -> NaiveDateTime {
+        process_date.and_time(self.time)
+    }
+
+    /// the first fire time after given time, exclusive
+    pub fn next_fire_time(&self, after: &NaiveDateTime) -> NaiveDateTime {
+        let mut first_day = self.first_day(&after.date());
+        loop {
+            let fire_time = self.fire_time_in(&first_day);
+            if fire_time > *after {
+                return fire_time;
+            }
+            first_day = self.next_first_day(&first_day);
+        }
+    }
+
+    /// fire times in (after, till], ordered by time
+    pub fn fire_times_between(
+        &self,
+        after: &NaiveDateTime,
+        till: &NaiveDateTime,
+    ) -> Vec<NaiveDateTime> {
+        let mut fire_times = vec![];
+        let mut fire_time = self.next_fire_time(after);
+        while fire_time <= *till {
+            fire_times.push(fire_time);
+            fire_time = self.next_fire_time(&fire_time);
+        }
+        fire_times
+    }
+}
@@ -0,0 +1,5 @@
+mod fire_schedule;
+mod scheduler_clock;
+
+pub use fire_schedule::*;
+pub use scheduler_clock::*;
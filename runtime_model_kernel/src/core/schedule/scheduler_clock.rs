@@ -0,0 +1,15 @@
+use chrono::{NaiveDateTime, Utc};
+
+/// clock of schedulers, replace it to run schedulers on a given time.
+pub trait SchedulerClock {
+    fn now(&self) -> NaiveDateTime;
+}
+
+/// utc now
+pub struct SystemSchedulerClock;
+
+impl SchedulerClock for SystemSchedulerClock {
+    fn now(&self) -> NaiveDateTime {
+        Utc::now().naive_utc()
+    }
+}
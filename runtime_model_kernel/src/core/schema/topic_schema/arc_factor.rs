@@ -52,4 +52,9 @@ impl ArcFactor {
     pub fn is_date_or_time(&self) -> bool {
         self.r#type.is_date_or_time()
     }
+
+    /// returns true when factor type is enum and enum id is given
+    pub fn is_enum(&self) -> bool {
+        *self.r#type == FactorType::Enum && self.enum_id.is_some()
+    }
 }
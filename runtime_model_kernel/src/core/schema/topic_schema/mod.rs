@@ -6,6 +6,7 @@ mod fake_topic_schema_factor;
 mod simple_topic_schema_factor;
 mod topic_data_value_prepper;
 mod topic_schema;
+mod topic_schema_enums;
 mod topic_schema_factor;
 mod topic_schema_factor_utils;
 mod topic_schema_factors;
@@ -20,6 +21,7 @@ pub use fake_topic_schema_factor::*;
 pub use simple_topic_schema_factor::*;
 pub use topic_data_value_prepper::*;
 pub use topic_schema::*;
+pub use topic_schema_enums::*;
 pub use topic_schema_factor::*;
 pub use topic_schema_factor_utils::*;
 pub use topic_schema_factors::*;
@@ -10,6 +10,7 @@ pub struct SimpleTopicSchemaFactor {
     pub name: String,
     pub is_date_or_time: bool,
     pub is_encryptable: bool,
+    pub is_enum: bool,
     pub default_value: Option<Arc<TopicDataValue>>,
     pub is_flatten: bool,
 }
@@ -18,12 +19,13 @@ impl Display for SimpleTopicSchemaFactor {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "SimpleTopicSchemaFactor[name={}, factor_name={}, factor_id={}, is_date_or_time={}, is_encryptable={}, default_value={}, is_flatten={}]",
+            "SimpleTopicSchemaFactor[name={}, factor_name={}, factor_id={}, is_date_or_time={}, is_encryptable={}, is_enum={}, default_value={}, is_flatten={}]",
             self.name,
             self.factor.name,
             self.factor.factor_id,
             self.is_date_or_time,
             self.is_encryptable,
+            self.is_enum,
             if let Some(value) = &self.default_value {
                 format!("{}", value)
             } else {
@@ -47,6 +49,7 @@ impl SimpleTopicSchemaFactor {
             } else {
                 false
             },
+            is_enum: factor.is_enum(),
             default_value: Self::compute_default_value(&factor),
             is_flatten: factor.flatten,
             factor,
@@ -131,6 +134,7 @@ impl SimpleTopicSchemaFactor {
     pub fn if_functional(self) -> Option<TopicSchemaFactor> {
         if self.is_date_or_time
             || self.is_encryptable
+            || self.is_enum
             || self.is_flatten
             || self.default_value.is_some()
         {
@@ -1,12 +1,12 @@
 use crate::{
 	ArcFactor, ArcTopic, FakeTopicSchemaFactor, HierarchyAid, RuntimeModelKernelErrorCode,
-	SimpleTopicSchemaFactor, TopicSchemaFactor, TopicSchemaFactors, TriedTDV, TriedTopicDataValue,
-	VecOrMapTopicSchemaFactor,
+	SimpleTopicSchemaFactor, TopicDataFinding, TopicSchemaEnums, TopicSchemaFactor,
+	TopicSchemaFactors, TriedTDV, TriedTopicDataValue, VecOrMapTopicSchemaFactor,
 };
 use bigdecimal::BigDecimal;
 use chrono::NaiveTime;
 use elf_base::{DateTimeUtils, ErrorCode, StdR, VoidR};
-use elf_model::{FactorType, TopicData, TopicDataValue};
+use elf_model::{EnumId, FactorType, MonitorRuleCode, TopicData, TopicDataValue};
 use std::collections::HashMap;
 use std::fmt::Display;
 use std::ops::Deref;
@@ -55,6 +55,22 @@ impl TopicSchemaFactorValuePrepper {
 
         Ok(())
     }
+
+    /// validate values of enum factors before preparing, and replace codes when item has replace code.
+    /// invalid values are kept as is, and returned as findings.
+    pub fn prepare_and_validate(
+        &self,
+        factors: &Arc<TopicSchemaFactors>,
+        data: &mut HashMap<String, TopicDataValue>,
+        enums: &TopicSchemaEnums,
+    ) -> StdR<Vec<TopicDataFinding>> {
+        let mut findings = vec![];
+        // validate before encryption
+        self.validate_enum_level(&factors.factors, data, enums, &mut findings);
+        self.prepare(factors, data)?;
+
+        Ok(findings)
+    }
 }
 
 /// for default value, date/time cast, encrypt/decrypt
@@ -362,6 +378,172 @@ impl TopicSchemaFactorValuePrepper {
     }
 }
 
+/// for enum validation
+impl TopicSchemaFactorValuePrepper {
+    fn enum_of(factor: &SimpleTopicSchemaFactor) -> Option<&Arc<EnumId>> {
+        if factor.is_enum {
+            factor.factor.enum_id.as_ref()
+        } else {
+            None
+        }
+    }
+
+    fn enum_code_of(value: &TopicDataValue) -> Option<String> {
+        match value {
+            TopicDataValue::Str(str) => Some(str.clone()),
+            TopicDataValue::Num(num) => Some(num.normalized().to_string()),
+            _ => None,
+        }
+    }
+
+    fn enum_finding(
+        factor: &SimpleTopicSchemaFactor,
+        value: String,
+        message: String,
+    ) -> TopicDataFinding {
+        TopicDataFinding {
+            rule_code: MonitorRuleCode::FactorMismatchEnum,
+            factor_id: factor.factor.factor_id.deref().clone(),
+            factor_name: factor.factor.name.deref().clone(),
+            value,
+            message,
+        }
+    }
+
+    /// parent codes are the original codes of enum factors on same level,
+    /// keyed by enum id.
+    fn validate_enum_value(
+        &self,
+        factor: &SimpleTopicSchemaFactor,
+        data: &mut HashMap<String, TopicDataValue>,
+        enums: &TopicSchemaEnums,
+        parent_codes: &HashMap<&EnumId, String>,
+        findings: &mut Vec<TopicDataFinding>,
+    ) {
+        let Some(enum_id) = Self::enum_of(factor) else {
+            return;
+        };
+        // enum not given, not validated
+        let Some(r#enum) = enums.get(enum_id) else {
+            return;
+        };
+        let value = match data.get(&factor.name) {
+            None | Some(TopicDataValue::None) => return,
+            Some(value) => value,
+        };
+        let Some(code) = Self::enum_code_of(value) else {
+            findings.push(Self::enum_finding(
+                factor,
+                value.to_string(),
+                format!(
+                    "Value[{}] of factor[factor_id={}, factor_name={}] is not a code of enum[{}].",
+                    value, factor.factor.factor_id, factor.factor.name, enum_id
+                ),
+            ));
+            return;
+        };
+        let Some(item) = r#enum.item(&code) else {
+            if !r#enum.is_replaced(&code) {
+                findings.push(Self::enum_finding(
+                    factor,
+                    code.clone(),
+                    format!(
+                        "Value[{}] of factor[factor_id={}, factor_name={}] is not an item of enum[{}].",
+                        value, factor.factor.factor_id, factor.factor.name, enum_id
+                    ),
+                ));
+            }
+            return;
+        };
+        if let (Some(parent_enum_id), Some(parent_code)) = (&r#enum.parent_enum_id, &item.parent_code)
+            && let Some(parent_value) = parent_codes.get(parent_enum_id)
+            && parent_value != parent_code
+            // parent value might be replaced already
+            && enums
+                .get(parent_enum_id)
+                .and_then(|parent_enum| parent_enum.item(parent_code))
+                .and_then(|parent_item| parent_item.replace_code.as_ref())
+                != Some(parent_value)
+        {
+            findings.push(Self::enum_finding(
+                factor,
+                code.clone(),
+                format!(
+                    "Value[{}] of factor[factor_id={}, factor_name={}] is a child of [{}] in enum[{}], but parent value is [{}].",
+                    value, factor.factor.factor_id, factor.factor.name, parent_code, enum_id, parent_value
+                ),
+            ));
+        }
+        if let Some(replace_code) = &item.replace_code {
+            data.insert(
+                factor.name.clone(),
+                TopicDataValue::Str(replace_code.clone()),
+            );
+        }
+    }
+
+    /// value of vec or map factor, structure is not checked here.
+    fn validate_enum_values(
+        &self,
+        child_factors: &Vec<TopicSchemaFactor>,
+        value: &mut TopicDataValue,
+        enums: &TopicSchemaEnums,
+        findings: &mut Vec<TopicDataFinding>,
+    ) {
+        match value {
+            TopicDataValue::Map(map) => {
+                self.validate_enum_level(child_factors, map, enums, findings)
+            }
+            TopicDataValue::Vec(vec) => {
+                for element in vec {
+                    if let TopicDataValue::Map(map) = element {
+                        self.validate_enum_level(child_factors, map, enums, findings);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn validate_enum_level(
+        &self,
+        factors: &Vec<TopicSchemaFactor>,
+        data: &mut HashMap<String, TopicDataValue>,
+        enums: &TopicSchemaEnums,
+        findings: &mut Vec<TopicDataFinding>,
+    ) {
+        // collect before replacing, parent/child is checked on the original codes
+        let parent_codes: HashMap<&EnumId, String> = factors
+            .iter()
+            .filter_map(|factor| match factor {
+                TopicSchemaFactor::Simple(factor) => Self::enum_of(factor).and_then(|enum_id| {
+                    data.get(&factor.name)
+                        .and_then(Self::enum_code_of)
+                        .map(|code| (enum_id.deref(), code))
+                }),
+                _ => None,
+            })
+            .collect();
+        for factor in factors {
+            match factor {
+                TopicSchemaFactor::Simple(factor) => {
+                    self.validate_enum_value(factor, data, enums, &parent_codes, findings)
+                }
+                TopicSchemaFactor::VecOrMap(factor) => {
+                    if let Some(value) = data.get_mut(&factor.name) {
+                        self.validate_enum_values(&factor.children, value, enums, findings)
+                    }
+                }
+                TopicSchemaFactor::Fake(factor) => {
+                    if let Some(value) = data.get_mut(&factor.name) {
+                        self.validate_enum_values(&factor.children, value, enums, findings)
+                    }
+                }
+            }
+        }
+    }
+}
+
 /// for aid hierarchy
 impl TopicSchemaFactorValuePrepper {
     fn aid_hierarchy(&self, data: &mut TopicData) -> VoidR {
@@ -446,12 +628,20 @@ impl TopicSchemaFactorValuePrepper {
 					}
 				}
 			}
-			Some(other) => return RuntimeModelKernelErrorCode::TopicDataComplete.msg(
-				format!(
-					"Value[{}] of factor[factor_name={}] cannot be of the none or any type other than map.",
-					other, full_name,
-				)
-			)
+			Some(other) => {
+				// no flatten factor under this part, value of any type is allowed
+				let mut flatten_factors = vec![];
+				self.get_flatten_factors(child_factors, &mut flatten_factors);
+				if flatten_factors.is_empty() {
+					return Ok(());
+				}
+				return RuntimeModelKernelErrorCode::TopicDataComplete.msg(
+					format!(
+						"Value[{}] of factor[factor_name={}] cannot be of the none or any type other than map.",
+						other, full_name,
+					)
+				);
+			}
 		}
 
         Ok(())
@@ -1,4 +1,7 @@
-use crate::{ArcFactor, ArcTopic, TopicSchemaFactorValuePrepper, TopicSchemaFactors};
+use crate::{
+    ArcFactor, ArcTopic, TopicDataFinding, TopicSchemaEnums, TopicSchemaFactorValuePrepper,
+    TopicSchemaFactors,
+};
 use elf_base::{StdR, VoidR};
 use elf_model::{FactorId, TenantId, Topic, TopicCode, TopicData, TopicId};
 use std::ops::Deref;
@@ -125,14 +128,37 @@ impl TopicSchema {
         }
         Ok(())
     }
+
+    /// prepare with enum validation, see [TopicSchemaFactorValuePrepper::prepare_and_validate].
+    /// given data might be changed
+    pub fn prepare_and_validate(
+        &self,
+        data: &mut TopicData,
+        enums: &TopicSchemaEnums,
+    ) -> StdR<Vec<TopicDataFinding>> {
+        if let Some(factors) = &self.factors {
+            TopicSchemaFactorValuePrepper::with(
+                self.topic.clone(),
+                self.should_init_default_values(),
+                self.should_encrypt(),
+                false,
+                self.should_aid_hierarchy(),
+                self.should_flatten(),
+            )
+            .prepare_and_validate(factors, data, enums)
+        } else {
+            Ok(vec![])
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::TopicSchema;
+    use crate::{TopicSchema, TopicSchemaEnums};
     use chrono::Datelike;
     use elf_model::{
-        Factor, FactorEncryptMethod, FactorType, Topic, TopicDataValue, TopicKind, TopicType,
+        Enum, EnumItem, Factor, FactorEncryptMethod, FactorType, MonitorRuleCode, Topic,
+        TopicDataValue, TopicKind, TopicType,
     };
     use std::collections::HashMap;
 
@@ -302,4 +328,125 @@ mod tests {
             }
         }
     }
+
+    fn enum_item(code: &str, parent_code: Option<&str>, replace_code: Option<&str>) -> EnumItem {
+        let mut item = EnumItem::new().code(code.to_string());
+        item.parent_code = parent_code.map(|c| c.to_string());
+        item.replace_code = replace_code.map(|c| c.to_string());
+        item
+    }
+
+    fn create_enum_topic() -> Topic {
+        Topic::new()
+            .topic_id(String::from("topic-2"))
+            .name(String::from("Enum Topic"))
+            .r#type(TopicType::Distinct)
+            .kind(TopicKind::Business)
+            .factors(vec![
+                Factor::new()
+                    .factor_id("f1".to_string())
+                    .name(String::from("province"))
+                    .r#type(FactorType::Enum)
+                    .enum_id(String::from("e-province")),
+                Factor::new()
+                    .factor_id("f2".to_string())
+                    .name(String::from("city"))
+                    .r#type(FactorType::Enum)
+                    .enum_id(String::from("e-city")),
+                Factor::new()
+                    .factor_id("f3".to_string())
+                    .name(String::from("orders"))
+                    .r#type(FactorType::Array),
+                Factor::new()
+                    .factor_id("f4".to_string())
+                    .name(String::from("orders.status"))
+                    .r#type(FactorType::Enum)
+                    .enum_id(String::from("e-status")),
+            ])
+            .tenant_id(String::from("Tenant-1"))
+            .version(1)
+    }
+
+    fn create_enums() -> Vec<Enum> {
+        vec![
+            Enum::new().enum_id(String::from("e-province")).items(vec![
+                enum_item("ZJ", None, Some("330000")),
+                enum_item("JS", None, None),
+            ]),
+            Enum::new()
+                .enum_id(String::from("e-city"))
+                .parent_enum_id(String::from("e-province"))
+                .items(vec![
+                    enum_item("HZ", Some("ZJ"), None),
+                    enum_item("NJ", Some("JS"), None),
+                ]),
+            Enum::new().enum_id(String::from("e-status")).items(vec![
+                enum_item("paid", None, None),
+                enum_item("shipped", None, None),
+            ]),
+        ]
+    }
+
+    #[test]
+    fn test_prepare_and_validate() {
+        let topic_schema = TopicSchema::new(create_enum_topic()).unwrap();
+        let enums = TopicSchemaEnums::load(&topic_schema, |enum_id| {
+            Ok(create_enums()
+                .into_iter()
+                .find(|e| e.enum_id.as_ref() == Some(enum_id)))
+        })
+        .unwrap();
+
+        let order = |status: &str| {
+            TopicDataValue::Map(HashMap::from([(
+                "status".to_string(),
+                TopicDataValue::Str(status.to_string()),
+            )]))
+        };
+        let mut data = HashMap::from([
+            (
+                "province".to_string(),
+                TopicDataValue::Str("ZJ".to_string()),
+            ),
+            ("city".to_string(), TopicDataValue::Str("NJ".to_string())),
+            (
+                "orders".to_string(),
+                TopicDataValue::Vec(vec![order("paid"), order("lost")]),
+            ),
+        ]);
+        let findings = topic_schema
+            .prepare_and_validate(&mut data, &enums)
+            .unwrap();
+
+        assert_eq!(findings.len(), 2);
+        assert!(findings
+            .iter()
+            .all(|f| f.rule_code == MonitorRuleCode::FactorMismatchEnum));
+        assert_eq!(findings[0].factor_name, "city");
+        assert_eq!(findings[0].value, "NJ");
+        assert_eq!(findings[1].factor_name, "orders.status");
+        assert_eq!(findings[1].value, "lost");
+        // replaced, and replaced code is accepted when prepared again
+        assert!(matches!(data.get("province"), Some(TopicDataValue::Str(s)) if s == "330000"));
+        let mut data = HashMap::from([
+            (
+                "province".to_string(),
+                TopicDataValue::Str("330000".to_string()),
+            ),
+            ("city".to_string(), TopicDataValue::Str("HZ".to_string())),
+        ]);
+        assert!(topic_schema
+            .prepare_and_validate(&mut data, &enums)
+            .unwrap()
+            .is_empty());
+        // keep invalid value
+        let mut data = HashMap::from([("city".to_string(), TopicDataValue::Num(1.into()))]);
+        let findings = topic_schema
+            .prepare_and_validate(&mut data, &enums)
+            .unwrap();
+        assert_eq!(findings.len(), 1);
+        assert!(matches!(data.get("city"), Some(TopicDataValue::Num(_))));
+
+        assert!(TopicSchemaEnums::load(&topic_schema, |_| Ok(None)).is_err());
+    }
 }
@@ -0,0 +1,129 @@
+use crate::{RuntimeModelKernelErrorCode, TopicSchema};
+use elf_base::{ErrorCode, StdR};
+use elf_model::{Enum, EnumId, FactorId, FactorType, MonitorRuleCode};
+use std::collections::HashMap;
+use std::ops::Deref;
+
+/// item of enum, only codes are kept
+pub struct TopicSchemaEnumItem {
+    pub parent_code: Option<String>,
+    pub replace_code: Option<String>,
+}
+
+/// items of enum, keyed by code
+pub struct TopicSchemaEnum {
+    pub parent_enum_id: Option<EnumId>,
+    pub items: HashMap<String, TopicSchemaEnumItem>,
+    /// non-blank replace codes of items
+    pub replace_codes: Vec<String>,
+}
+
+impl TopicSchemaEnum {
+    fn blank_to_none(value: Option<String>) -> Option<String> {
+        value.filter(|value| !value.trim().is_empty())
+    }
+
+    pub fn new(r#enum: Enum) -> Self {
+        let mut items = HashMap::new();
+        let mut replace_codes = vec![];
+        for item in r#enum.items.unwrap_or_default() {
+            let Some(code) = item.code else {
+                continue;
+            };
+            let replace_code = Self::blank_to_none(item.replace_code);
+            if let Some(replace_code) = &replace_code {
+                replace_codes.push(replace_code.clone());
+            }
+            items.insert(
+                code,
+                TopicSchemaEnumItem {
+                    parent_code: Self::blank_to_none(item.parent_code),
+                    replace_code,
+                },
+            );
+        }
+        Self {
+            parent_enum_id: Self::blank_to_none(r#enum.parent_enum_id),
+            items,
+            replace_codes,
+        }
+    }
+
+    pub fn item(&self, code: &str) -> Option<&TopicSchemaEnumItem> {
+        self.items.get(code)
+    }
+
+    /// code is replace code of one of items, which means it is replaced already
+    pub fn is_replaced(&self, code: &str) -> bool {
+        self.replace_codes
+            .iter()
+            .any(|replace_code| replace_code == code)
+    }
+}
+
+/// enums used by enum factors of topic, to validate factor values when topic data is prepared.
+pub struct TopicSchemaEnums {
+    enums: HashMap<EnumId, TopicSchemaEnum>,
+}
+
+impl TopicSchemaEnums {
+    pub fn new(enums: Vec<Enum>) -> Self {
+        Self {
+            enums: enums
+                .into_iter()
+                .filter_map(|r#enum| {
+                    r#enum
+                        .enum_id
+                        .clone()
+                        .map(|enum_id| (enum_id, TopicSchemaEnum::new(r#enum)))
+                })
+                .collect(),
+        }
+    }
+
+    /// load enums of all enum factors of given topic, raise error when enum not found.
+    pub fn load<F>(topic_schema: &TopicSchema, find: F) -> StdR<Self>
+    where
+        F: Fn(&EnumId) -> StdR<Option<Enum>>,
+    {
+        let mut enums = HashMap::new();
+        for factor in topic_schema.topic().factors.iter() {
+            if *factor.r#type.deref() != FactorType::Enum {
+                continue;
+            }
+            let Some(enum_id) = &factor.enum_id else {
+                continue;
+            };
+            if enums.contains_key(enum_id.deref()) {
+                continue;
+            }
+            match find(enum_id)? {
+                Some(r#enum) => {
+                    enums.insert(enum_id.deref().clone(), TopicSchemaEnum::new(r#enum));
+                }
+                _ => {
+                    return RuntimeModelKernelErrorCode::EnumNotFound.msg(format!(
+                        "Enum[{}] of factor[factor_id={}, factor_name={}] not found.",
+                        enum_id, factor.factor_id, factor.name
+                    ));
+                }
+            }
+        }
+        Ok(Self { enums })
+    }
+
+    pub fn get(&self, enum_id: &EnumId) -> Option<&TopicSchemaEnum> {
+        self.enums.get(enum_id)
+    }
+}
+
+/// issue found when topic data is prepared, value is kept as is.
+#[derive(Debug, PartialEq)]
+pub struct TopicDataFinding {
+    pub rule_code: MonitorRuleCode,
+    pub factor_id: FactorId,
+    /// full name of factor
+    pub factor_name: String,
+    pub value: String,
+    pub message: String,
+}
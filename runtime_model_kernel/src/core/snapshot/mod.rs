@@ -0,0 +1,9 @@
+mod topic_snapshot_job_lock_service;
+mod topic_snapshot_schedule;
+mod topic_snapshot_scheduler_meta_service;
+mod topic_snapshot_service;
+
+pub use topic_snapshot_job_lock_service::*;
+pub use topic_snapshot_schedule::*;
+pub use topic_snapshot_scheduler_meta_service::*;
+pub use topic_snapshot_service::*;
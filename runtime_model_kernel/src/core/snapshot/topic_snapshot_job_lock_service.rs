@@ -0,0 +1,48 @@
+use elf_base::{StdR, VoidR};
+use elf_model::{
+    TenantId, TopicSnapshotJobLock, TopicSnapshotJobLockId, TopicSnapshotJobLockStatus,
+    TopicSnapshotSchedulerId,
+};
+use std::sync::Arc;
+
+/// TODO topic snapshot job lock service using tenant and it's meta datasource (or the global meta datasource)
+///  to persist the locks.
+///  lock is unique by [tenant_id, scheduler_id, process_date].
+pub struct TopicSnapshotJobLockService;
+
+impl TopicSnapshotJobLockService {
+    fn new() -> StdR<Arc<Self>> {
+        Ok(Arc::new(Self {}))
+    }
+
+    /// try to create the given lock,
+    /// - Ok(true) -> lock created, current node owns the job,
+    /// - Ok(false) -> lock exists, job is owned by another run.
+    pub fn try_lock(&self, _lock: &TopicSnapshotJobLock) -> StdR<bool> {
+        todo!("implement try_lock for TopicSnapshotJobLockService")
+    }
+
+    pub fn finish(
+        &self,
+        _lock_id: &TopicSnapshotJobLockId,
+        _status: TopicSnapshotJobLockStatus,
+        _row_count: u32,
+    ) -> VoidR {
+        todo!("implement finish for TopicSnapshotJobLockService")
+    }
+
+    /// find the lock which has the latest process date of given scheduler, no matter what the status is
+    pub fn find_latest(
+        &self,
+        _scheduler_id: &TopicSnapshotSchedulerId,
+        _tenant_id: &TenantId,
+    ) -> StdR<Option<TopicSnapshotJobLock>> {
+        todo!("implement find_latest for TopicSnapshotJobLockService")
+    }
+}
+
+pub trait TopicSnapshotJobLockProvider {
+    fn job_lock() -> StdR<Arc<TopicSnapshotJobLockService>> {
+        TopicSnapshotJobLockService::new()
+    }
+}
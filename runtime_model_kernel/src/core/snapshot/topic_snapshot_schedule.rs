@@ -0,0 +1,139 @@
+use crate::{FireFrequency, FireSchedule, RuntimeModelKernelErrorCode};
+use elf_base::{ErrorCode, StdR};
+use elf_model::{TopicSnapshotFrequency, TopicSnapshotScheduler};
+
+/// fire schedule of topic snapshot scheduler.
+pub struct TopicSnapshotSchedule;
+
+impl TopicSnapshotSchedule {
+    /// weekday and day are checked only when the frequency requires,
+    /// hour and minute are 0 if not given.
+    pub fn of(scheduler: &TopicSnapshotScheduler) -> StdR<FireSchedule> {
+        let Some(frequency) = &scheduler.frequency else {
+            return RuntimeModelKernelErrorCode::TopicSnapshotFrequencyMissed.msg(format!(
+                "Frequency of topic snapshot scheduler[scheduler_id={}] is missed.",
+                scheduler.scheduler_id.as_deref().unwrap_or("")
+            ));
+        };
+        let in_range = |name: &str, value: Option<i8>, min: i8, max: i8| match value {
+            Some(v) if v >= min && v <= max => Ok(v as u32),
+            _ => RuntimeModelKernelErrorCode::TopicSnapshotScheduleInvalid.msg(format!(
+                "{}[{}] of topic snapshot scheduler[scheduler_id={}] must be in [{}, {}].",
+                name,
+                value.map(|v| v.to_string()).unwrap_or_default(),
+                scheduler.scheduler_id.as_deref().unwrap_or(""),
+                min,
+                max
+            )),
+        };
+
+        let (frequency, weekday, day) = match frequency {
+            TopicSnapshotFrequency::Daily => (FireFrequency::Daily, 0, 1),
+            TopicSnapshotFrequency::Weekly => (
+                FireFrequency::Weekly,
+                in_range("Weekday", scheduler.weekday, 0, 6)?,
+                1,
+            ),
+            TopicSnapshotFrequency::Monthly => (
+                FireFrequency::Monthly,
+                0,
+                in_range("Day", scheduler.day, 1, 31)?,
+            ),
+        };
+        let hour = in_range("Hour", scheduler.hour.or(Some(0)), 0, 23)?;
+        let minute = in_range("Minute", scheduler.minute.or(Some(0)), 0, 59)?;
+
+        Ok(FireSchedule::new(frequency, weekday, day, hour, minute))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::TopicSnapshotSchedule;
+    use chrono::{NaiveDate, NaiveDateTime};
+    use elf_model::{TopicSnapshotFrequency, TopicSnapshotScheduler};
+
+    fn time(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_next_fire_time() {
+        let daily = TopicSnapshotSchedule::of(
+            &TopicSnapshotScheduler::new()
+                .frequency(TopicSnapshotFrequency::Daily)
+                .hour(2)
+                .minute(30),
+        )
+        .unwrap();
+        assert_eq!(
+            daily.next_fire_time(&time(2026, 2, 18, 1, 0)),
+            time(2026, 2, 18, 2, 30)
+        );
+        assert_eq!(
+            daily.next_fire_time(&time(2026, 2, 18, 2, 30)),
+            time(2026, 2, 19, 2, 30)
+        );
+
+        // 2026-02-18 is wednesday
+        let weekly = TopicSnapshotSchedule::of(
+            &TopicSnapshotScheduler::new()
+                .frequency(TopicSnapshotFrequency::Weekly)
+                .weekday(1),
+        )
+        .unwrap();
+        assert_eq!(
+            weekly.next_fire_time(&time(2026, 2, 18, 0, 0)),
+            time(2026, 2, 23, 0, 0)
+        );
+
+        let monthly = TopicSnapshotSchedule::of(
+            &TopicSnapshotScheduler::new()
+                .frequency(TopicSnapshotFrequency::Monthly)
+                .day(31)
+                .hour(23),
+        )
+        .unwrap();
+        assert_eq!(
+            monthly.next_fire_time(&time(2026, 1, 31, 23, 0)),
+            time(2026, 2, 28, 23, 0)
+        );
+        assert_eq!(
+            monthly.next_fire_time(&time(2026, 2, 28, 23, 0)),
+            time(2026, 3, 31, 23, 0)
+        );
+
+        assert!(TopicSnapshotSchedule::of(
+            &TopicSnapshotScheduler::new().frequency(TopicSnapshotFrequency::Weekly)
+        )
+        .is_err());
+        assert!(TopicSnapshotSchedule::of(
+            &TopicSnapshotScheduler::new()
+                .frequency(TopicSnapshotFrequency::Daily)
+                .hour(24)
+        )
+        .is_err());
+        assert!(TopicSnapshotSchedule::of(&TopicSnapshotScheduler::new()).is_err());
+    }
+
+    #[test]
+    fn test_fire_times_between() {
+        let daily = TopicSnapshotSchedule::of(
+            &TopicSnapshotScheduler::new()
+                .frequency(TopicSnapshotFrequency::Daily)
+                .hour(6),
+        )
+        .unwrap();
+        let last = daily.fire_time_of(&NaiveDate::from_ymd_opt(2026, 2, 15).unwrap());
+        assert_eq!(
+            daily.fire_times_between(&last, &time(2026, 2, 18, 5, 59)),
+            vec![time(2026, 2, 16, 6, 0), time(2026, 2, 17, 6, 0)]
+        );
+        assert!(daily
+            .fire_times_between(&last, &time(2026, 2, 15, 23, 0))
+            .is_empty());
+    }
+}
@@ -0,0 +1,31 @@
+use elf_base::StdR;
+use elf_model::{TenantId, TopicSnapshotScheduler};
+use std::sync::Arc;
+
+/// TODO topic snapshot scheduler meta service using tenant and it's meta datasource (or the global meta datasource)
+///  to find out schedulers.
+///  the tenant meta datasource is a new feature, which is defined on tenant
+pub struct TopicSnapshotSchedulerMetaService;
+
+impl TopicSnapshotSchedulerMetaService {
+    fn new() -> StdR<Arc<Self>> {
+        // TODO maybe find from cache
+        Ok(Arc::new(Self {}))
+    }
+
+    /// find all schedulers of given tenant, including the disabled ones
+    pub fn find_by_tenant_id(&self, _tenant_id: &TenantId) -> StdR<Vec<TopicSnapshotScheduler>> {
+        todo!("implement find_by_tenant_id for TopicSnapshotSchedulerMetaService")
+    }
+
+    /// find enabled schedulers of all tenants
+    pub fn find_enabled(&self) -> StdR<Vec<TopicSnapshotScheduler>> {
+        todo!("implement find_enabled for TopicSnapshotSchedulerMetaService")
+    }
+}
+
+pub trait TopicSnapshotSchedulerMetaProvider {
+    fn meta() -> StdR<Arc<TopicSnapshotSchedulerMetaService>> {
+        TopicSnapshotSchedulerMetaService::new()
+    }
+}
@@ -0,0 +1,6 @@
+use crate::{TopicSnapshotJobLockProvider, TopicSnapshotSchedulerMetaProvider};
+
+pub struct TopicSnapshotService;
+
+impl TopicSnapshotSchedulerMetaProvider for TopicSnapshotService {}
+impl TopicSnapshotJobLockProvider for TopicSnapshotService {}
@@ -0,0 +1,5 @@
+mod subject_meta_service;
+mod subject_service;
+
+pub use subject_meta_service::*;
+pub use subject_service::*;
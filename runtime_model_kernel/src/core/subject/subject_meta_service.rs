@@ -0,0 +1,33 @@
+use elf_base::StdR;
+use elf_model::{Subject, SubjectId, TenantId};
+use std::sync::Arc;
+
+/// TODO subject meta service using tenant and it's meta datasource (or the global meta datasource)
+///  to find out subject meta.
+pub struct SubjectMetaService;
+
+impl SubjectMetaService {
+    fn new() -> StdR<Arc<Self>> {
+        // TODO maybe find from cache
+        Ok(Arc::new(Self {}))
+    }
+
+    pub fn find_by_id(
+        &self,
+        _subject_id: &SubjectId,
+        _tenant_id: &TenantId,
+    ) -> StdR<Option<Subject>> {
+        todo!("implement find_by_id for SubjectMetaService")
+    }
+
+    /// subject name is not unique, returns the first one.
+    pub fn find_by_name(&self, _name: &String, _tenant_id: &TenantId) -> StdR<Option<Subject>> {
+        todo!("implement find_by_name for SubjectMetaService")
+    }
+}
+
+pub trait SubjectMetaProvider {
+    fn meta() -> StdR<Arc<SubjectMetaService>> {
+        SubjectMetaService::new()
+    }
+}
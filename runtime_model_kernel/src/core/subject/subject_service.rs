@@ -0,0 +1,5 @@
+use crate::SubjectMetaProvider;
+
+pub struct SubjectService;
+
+impl SubjectMetaProvider for SubjectService {}
@@ -0,0 +1,11 @@
+mod notification_definition_meta_service;
+mod subscription_event_lock_service;
+mod subscription_event_meta_service;
+mod subscription_event_schedule;
+mod subscription_service;
+
+pub use notification_definition_meta_service::*;
+pub use subscription_event_lock_service::*;
+pub use subscription_event_meta_service::*;
+pub use subscription_event_schedule::*;
+pub use subscription_service::*;
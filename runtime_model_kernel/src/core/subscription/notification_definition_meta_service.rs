@@ -0,0 +1,28 @@
+use elf_base::StdR;
+use elf_model::{NotificationDefinition, NotificationDefinitionId, TenantId};
+use std::sync::Arc;
+
+/// TODO notification definition meta service using tenant and it's meta datasource (or the global meta datasource)
+///  to find out notification definitions.
+pub struct NotificationDefinitionMetaService;
+
+impl NotificationDefinitionMetaService {
+    fn new() -> StdR<Arc<Self>> {
+        // TODO maybe find from cache
+        Ok(Arc::new(Self {}))
+    }
+
+    pub fn find_by_id(
+        &self,
+        _notification_id: &NotificationDefinitionId,
+        _tenant_id: &TenantId,
+    ) -> StdR<Option<NotificationDefinition>> {
+        todo!("implement find_by_id for NotificationDefinitionMetaService")
+    }
+}
+
+pub trait NotificationDefinitionMetaProvider {
+    fn notification_meta() -> StdR<Arc<NotificationDefinitionMetaService>> {
+        NotificationDefinitionMetaService::new()
+    }
+}
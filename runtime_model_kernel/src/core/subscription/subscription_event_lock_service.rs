@@ -0,0 +1,47 @@
+use elf_base::{StdR, VoidR};
+use elf_model::{
+    SubscriptionEventId, SubscriptionEventJobLockStatus, SubscriptionEventLock,
+    SubscriptionEventLockId, TenantId,
+};
+use std::sync::Arc;
+
+/// TODO subscription event lock service using tenant and it's meta datasource (or the global meta datasource)
+///  to persist the locks.
+///  lock is unique by [tenant_id, subscription_event_id, process_date].
+pub struct SubscriptionEventLockService;
+
+impl SubscriptionEventLockService {
+    fn new() -> StdR<Arc<Self>> {
+        Ok(Arc::new(Self {}))
+    }
+
+    /// try to create the given lock,
+    /// - Ok(true) -> lock created, current node owns the job,
+    /// - Ok(false) -> lock exists, job is owned by another run.
+    pub fn try_lock(&self, _lock: &SubscriptionEventLock) -> StdR<bool> {
+        todo!("implement try_lock for SubscriptionEventLockService")
+    }
+
+    pub fn finish(
+        &self,
+        _lock_id: &SubscriptionEventLockId,
+        _status: SubscriptionEventJobLockStatus,
+    ) -> VoidR {
+        todo!("implement finish for SubscriptionEventLockService")
+    }
+
+    /// find the lock which has the latest process date of given event, no matter what the status is
+    pub fn find_latest(
+        &self,
+        _subscription_event_id: &SubscriptionEventId,
+        _tenant_id: &TenantId,
+    ) -> StdR<Option<SubscriptionEventLock>> {
+        todo!("implement find_latest for SubscriptionEventLockService")
+    }
+}
+
+pub trait SubscriptionEventLockProvider {
+    fn event_lock() -> StdR<Arc<SubscriptionEventLockService>> {
+        SubscriptionEventLockService::new()
+    }
+}
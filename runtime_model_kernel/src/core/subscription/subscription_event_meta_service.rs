@@ -0,0 +1,31 @@
+use elf_base::StdR;
+use elf_model::{SubscriptionEvent, TenantId};
+use std::sync::Arc;
+
+/// TODO subscription event meta service using tenant and it's meta datasource (or the global meta datasource)
+///  to find out subscription events.
+///  the tenant meta datasource is a new feature, which is defined on tenant
+pub struct SubscriptionEventMetaService;
+
+impl SubscriptionEventMetaService {
+    fn new() -> StdR<Arc<Self>> {
+        // TODO maybe find from cache
+        Ok(Arc::new(Self {}))
+    }
+
+    /// find all subscription events of given tenant, including the disabled ones
+    pub fn find_by_tenant_id(&self, _tenant_id: &TenantId) -> StdR<Vec<SubscriptionEvent>> {
+        todo!("implement find_by_tenant_id for SubscriptionEventMetaService")
+    }
+
+    /// find enabled subscription events of all tenants
+    pub fn find_enabled(&self) -> StdR<Vec<SubscriptionEvent>> {
+        todo!("implement find_enabled for SubscriptionEventMetaService")
+    }
+}
+
+pub trait SubscriptionEventMetaProvider {
+    fn meta() -> StdR<Arc<SubscriptionEventMetaService>> {
+        SubscriptionEventMetaService::new()
+    }
+}
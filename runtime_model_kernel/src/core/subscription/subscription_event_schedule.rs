@@ -0,0 +1,150 @@
+use crate::{FireFrequency, FireSchedule, RuntimeModelKernelErrorCode};
+use elf_base::{ErrorCode, StdR};
+use elf_model::{SubscriptionEvent, SubscriptionEventFrequency};
+
+/// fire schedule of subscription event.
+pub struct SubscriptionEventSchedule;
+
+impl SubscriptionEventSchedule {
+    /// - weekday: 0 - 6, or name of weekday (sun, mon, ..., or sunday, monday, ...), 0 is sunday,
+    /// - day: 1 - 31, or "L" or "last" for the last day of month.
+    ///
+    /// weekday and day are checked only when the frequency requires,
+    /// hour and minute are 0 if not given.
+    pub fn of(event: &SubscriptionEvent) -> StdR<FireSchedule> {
+        let Some(frequency) = &event.frequency else {
+            return RuntimeModelKernelErrorCode::SubscriptionEventFrequencyMissed.msg(format!(
+                "Frequency of subscription event[subscription_event_id={}] is missed.",
+                event.subscription_event_id.as_deref().unwrap_or("")
+            ));
+        };
+        let invalid = |name: &str, value: &str| {
+            RuntimeModelKernelErrorCode::SubscriptionEventScheduleInvalid.msg(format!(
+                "{}[{}] of subscription event[subscription_event_id={}] is invalid.",
+                name,
+                value,
+                event.subscription_event_id.as_deref().unwrap_or("")
+            ))
+        };
+
+        let (frequency, weekday, day) = match frequency {
+            SubscriptionEventFrequency::Daily => (FireFrequency::Daily, 0, 1),
+            SubscriptionEventFrequency::Weekly => {
+                let value = event.weekday.as_deref().unwrap_or("");
+                match Self::weekday_of(value) {
+                    Some(weekday) => (FireFrequency::Weekly, weekday, 1),
+                    _ => return invalid("Weekday", value),
+                }
+            }
+            SubscriptionEventFrequency::Monthly => {
+                let value = event.day.as_deref().unwrap_or("");
+                match Self::day_of(value) {
+                    Some(day) => (FireFrequency::Monthly, 0, day),
+                    _ => return invalid("Day", value),
+                }
+            }
+        };
+        let hour = event.hour.unwrap_or(0);
+        if hour > 23 {
+            return invalid("Hour", &hour.to_string());
+        }
+        let minute = event.minute.unwrap_or(0);
+        if minute > 59 {
+            return invalid("Minute", &minute.to_string());
+        }
+
+        Ok(FireSchedule::new(
+            frequency,
+            weekday,
+            day,
+            hour as u32,
+            minute as u32,
+        ))
+    }
+
+    fn weekday_of(value: &str) -> Option<u32> {
+        let value = value.trim().to_lowercase();
+        if let Ok(weekday) = value.parse::<u32>() {
+            return if weekday <= 6 { Some(weekday) } else { None };
+        }
+        ["sun", "mon", "tue", "wed", "thu", "fri", "sat"]
+            .iter()
+            .zip([
+                "sunday",
+                "monday",
+                "tuesday",
+                "wednesday",
+                "thursday",
+                "friday",
+                "saturday",
+            ])
+            .position(|(short, full)| value == *short || value == full)
+            .map(|weekday| weekday as u32)
+    }
+
+    fn day_of(value: &str) -> Option<u32> {
+        let value = value.trim().to_lowercase();
+        if value == "l" || value == "last" {
+            // clamped to the last day of month
+            return Some(31);
+        }
+        match value.parse::<u32>() {
+            Ok(day) if (1..=31).contains(&day) => Some(day),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SubscriptionEventSchedule;
+    use chrono::{NaiveDate, NaiveDateTime};
+    use elf_model::{SubscriptionEvent, SubscriptionEventFrequency};
+
+    fn time(year: i32, month: u32, day: u32, hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_schedule() {
+        // 2026-02-18 is wednesday
+        let weekly = SubscriptionEventSchedule::of(
+            &SubscriptionEvent::new()
+                .frequency(SubscriptionEventFrequency::Weekly)
+                .weekday(String::from("Fri"))
+                .hour(9),
+        )
+        .unwrap();
+        assert_eq!(
+            weekly.next_fire_time(&time(2026, 2, 18, 0)),
+            time(2026, 2, 20, 9)
+        );
+
+        let monthly = SubscriptionEventSchedule::of(
+            &SubscriptionEvent::new()
+                .frequency(SubscriptionEventFrequency::Monthly)
+                .day(String::from("last")),
+        )
+        .unwrap();
+        assert_eq!(
+            monthly.next_fire_time(&time(2026, 2, 1, 0)),
+            time(2026, 2, 28, 0)
+        );
+
+        assert!(SubscriptionEventSchedule::of(
+            &SubscriptionEvent::new()
+                .frequency(SubscriptionEventFrequency::Weekly)
+                .weekday(String::from("7"))
+        )
+        .is_err());
+        assert!(SubscriptionEventSchedule::of(
+            &SubscriptionEvent::new()
+                .frequency(SubscriptionEventFrequency::Daily)
+                .minute(60)
+        )
+        .is_err());
+    }
+}
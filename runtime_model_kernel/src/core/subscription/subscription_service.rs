@@ -0,0 +1,10 @@
+use crate::{
+    NotificationDefinitionMetaProvider, SubscriptionEventLockProvider,
+    SubscriptionEventMetaProvider,
+};
+
+pub struct SubscriptionService;
+
+impl SubscriptionEventMetaProvider for SubscriptionService {}
+impl SubscriptionEventLockProvider for SubscriptionService {}
+impl NotificationDefinitionMetaProvider for SubscriptionService {}
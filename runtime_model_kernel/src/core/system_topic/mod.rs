@@ -0,0 +1,13 @@
+mod system_topic_change;
+mod system_topic_definitions;
+mod system_topic_differ;
+mod system_topic_install_plan;
+mod system_topic_installer;
+mod system_topic_version;
+
+pub use system_topic_change::*;
+pub use system_topic_definitions::*;
+pub use system_topic_differ::*;
+pub use system_topic_install_plan::*;
+pub use system_topic_installer::*;
+pub use system_topic_version::*;
@@ -0,0 +1,30 @@
+use elf_model::{Pipeline, Topic};
+
+/// change of a system tuple (topic or pipeline), compares the definition to installed one.
+#[derive(PartialEq, Debug, Clone)]
+pub enum SystemTupleChange {
+    /// not installed yet
+    Create,
+    /// installed, but differs from the definition. with the differences described
+    Upgrade(Vec<String>),
+    /// installed, and same as the definition
+    Unchanged,
+}
+
+impl SystemTupleChange {
+    pub fn is_changed(&self) -> bool {
+        !matches!(self, Self::Unchanged)
+    }
+}
+
+/// topic is merged from definition and installed one, which is ready to be saved.
+pub struct SystemTopicChange {
+    pub topic: Topic,
+    pub change: SystemTupleChange,
+}
+
+/// pipeline is merged from definition and installed one, which is ready to be saved.
+pub struct SystemPipelineChange {
+    pub pipeline: Pipeline,
+    pub change: SystemTupleChange,
+}
@@ -0,0 +1,29 @@
+use elf_base::StdR;
+use elf_model::pipelines::{ask_dqc_pipelines, ask_query_performance_pipelines};
+use elf_model::topics::{
+    ask_dqc_topics, ask_pipeline_monitor_topics, ask_query_performance_topics,
+};
+use elf_model::{Pipeline, Topic};
+
+/// system topics and pipelines which are defined in code.
+pub struct SystemTopicDefinitions;
+
+impl SystemTopicDefinitions {
+    /// topics are located by name, and no topic id assigned.
+    pub fn topics() -> Vec<Topic> {
+        let mut topics = Vec::new();
+        topics.extend(ask_dqc_topics());
+        topics.extend(ask_pipeline_monitor_topics());
+        topics.extend(ask_query_performance_topics());
+        topics
+    }
+
+    /// given topics must have topic id assigned,
+    /// pipelines are located by topic id and name.
+    pub fn pipelines(topics: &Vec<Topic>) -> StdR<Vec<Pipeline>> {
+        let mut pipelines = Vec::new();
+        pipelines.extend(ask_dqc_pipelines(topics)?);
+        pipelines.extend(ask_query_performance_pipelines(topics)?);
+        Ok(pipelines)
+    }
+}
@@ -0,0 +1,241 @@
+use crate::{IdGen, RuntimeModelKernelErrorCode, SystemTupleChange};
+use elf_base::{ErrorCode, StdR};
+use elf_model::{Pipeline, TenantId, Topic};
+use serde::Serialize;
+use serde_json::Value;
+
+/// compare the system topic/pipeline definition to the installed one,
+/// and merge them to the one which is ready to be saved.
+pub struct SystemTopicDiffer;
+
+impl SystemTopicDiffer {
+    fn to_json<T: Serialize>(value: &T) -> StdR<Value> {
+        serde_json::to_value(value).or_else(|e| {
+            RuntimeModelKernelErrorCode::SystemTopicSerialize.msg(format!(
+                "Failed to serialize system tuple, caused by {}.",
+                e
+            ))
+        })
+    }
+
+    fn differs<T: Serialize>(
+        definition: &T,
+        installed: &T,
+        name: &str,
+        differences: &mut Vec<String>,
+    ) -> StdR<()> {
+        if Self::to_json(definition)? != Self::to_json(installed)? {
+            differences.push(format!("{} changed", name));
+        }
+        Ok(())
+    }
+
+    fn change_of(differences: Vec<String>) -> SystemTupleChange {
+        if differences.is_empty() {
+            SystemTupleChange::Unchanged
+        } else {
+            SystemTupleChange::Upgrade(differences)
+        }
+    }
+
+    /// topic id is generated when not installed, otherwise keeps the installed one.
+    /// factor ids are kept as installed one when they are matched by name,
+    /// and factors which are not in definition (appended by user) are retained.
+    pub fn topic(
+        definition: Topic,
+        installed: Option<Topic>,
+        tenant_id: &TenantId,
+    ) -> StdR<(Topic, SystemTupleChange)> {
+        let mut topic = definition;
+        if topic.name.is_none() {
+            return RuntimeModelKernelErrorCode::SystemTopicNameMissed
+                .msg("System topic must have a name.");
+        }
+
+        let Some(installed) = installed else {
+            topic.topic_id = Some(IdGen::next_id()?.to_string());
+            topic.tenant_id = Some(tenant_id.clone());
+            return Ok((topic, SystemTupleChange::Create));
+        };
+
+        let mut differences = vec![];
+        Self::differs(&topic.r#type, &installed.r#type, "type", &mut differences)?;
+        Self::differs(&topic.kind, &installed.kind, "kind", &mut differences)?;
+        Self::differs(
+            &topic.description,
+            &installed.description,
+            "description",
+            &mut differences,
+        )?;
+
+        let mut installed_factors = installed.factors.unwrap_or_default();
+        let mut factors = vec![];
+        for mut factor in topic.factors.take().unwrap_or_default() {
+            let Some(name) = factor.name.clone() else {
+                return RuntimeModelKernelErrorCode::SystemTopicNameMissed.msg(format!(
+                    "Factor of system topic[{}] must have a name.",
+                    topic.name.as_ref().unwrap()
+                ));
+            };
+            let index = installed_factors
+                .iter()
+                .position(|installed| installed.name.as_ref() == Some(&name));
+            if let Some(index) = index {
+                let installed_factor = installed_factors.remove(index);
+                factor.factor_id = installed_factor.factor_id.clone();
+                Self::differs(
+                    &factor,
+                    &installed_factor,
+                    format!("factor[{}]", name).as_str(),
+                    &mut differences,
+                )?;
+            } else {
+                differences.push(format!("factor[{}] added", name));
+            }
+            factors.push(factor);
+        }
+        factors.extend(installed_factors);
+        topic.factors = Some(factors);
+
+        topic.topic_id = installed.topic_id;
+        topic.tenant_id = Some(tenant_id.clone());
+        topic.data_source_id = installed.data_source_id;
+        topic.version = installed.version;
+        topic.created_at = installed.created_at;
+        topic.created_by = installed.created_by;
+        topic.last_modified_at = installed.last_modified_at;
+        topic.last_modified_by = installed.last_modified_by;
+
+        Ok((topic, Self::change_of(differences)))
+    }
+
+    /// pipeline id is generated when not installed, otherwise keeps the installed one.
+    /// enabled is kept as installed one, since it might be switched by user.
+    pub fn pipeline(
+        definition: Pipeline,
+        installed: Option<Pipeline>,
+        tenant_id: &TenantId,
+    ) -> StdR<(Pipeline, SystemTupleChange)> {
+        let mut pipeline = definition;
+        pipeline.validated = Some(true);
+
+        let Some(installed) = installed else {
+            pipeline.pipeline_id = Some(IdGen::next_id()?.to_string());
+            pipeline.tenant_id = Some(tenant_id.clone());
+            pipeline.enabled = Some(true);
+            return Ok((pipeline, SystemTupleChange::Create));
+        };
+
+        let mut differences = vec![];
+        Self::differs(
+            &pipeline.topic_id,
+            &installed.topic_id,
+            "topic",
+            &mut differences,
+        )?;
+        Self::differs(
+            &pipeline.r#type,
+            &installed.r#type,
+            "type",
+            &mut differences,
+        )?;
+        Self::differs(
+            &pipeline.conditional,
+            &installed.conditional,
+            "conditional",
+            &mut differences,
+        )?;
+        Self::differs(
+            &pipeline.on,
+            &installed.on,
+            "prerequisite",
+            &mut differences,
+        )?;
+        Self::differs(
+            &pipeline.stages,
+            &installed.stages,
+            "stages",
+            &mut differences,
+        )?;
+        Self::differs(
+            &pipeline.validated,
+            &installed.validated,
+            "validated",
+            &mut differences,
+        )?;
+
+        pipeline.pipeline_id = installed.pipeline_id;
+        pipeline.tenant_id = Some(tenant_id.clone());
+        pipeline.enabled = installed.enabled;
+        pipeline.version = installed.version;
+        pipeline.created_at = installed.created_at;
+        pipeline.created_by = installed.created_by;
+        pipeline.last_modified_at = installed.last_modified_at;
+        pipeline.last_modified_by = installed.last_modified_by;
+
+        Ok((pipeline, Self::change_of(differences)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{SystemTopicDiffer, SystemTupleChange};
+    use elf_model::topics::ask_dqc_rule_daily_topic;
+    use elf_model::{Factor, FactorType};
+
+    #[test]
+    fn test_topic_create() {
+        let tenant_id = String::from("t-1");
+        let (topic, change) =
+            SystemTopicDiffer::topic(ask_dqc_rule_daily_topic(), None, &tenant_id).unwrap();
+        assert_eq!(change, SystemTupleChange::Create);
+        assert!(topic.topic_id.is_some());
+        assert_eq!(topic.tenant_id, Some(tenant_id));
+    }
+
+    #[test]
+    fn test_topic_unchanged() {
+        let tenant_id = String::from("t-1");
+        let (installed, _) =
+            SystemTopicDiffer::topic(ask_dqc_rule_daily_topic(), None, &tenant_id).unwrap();
+        let topic_id = installed.topic_id.clone();
+        let (topic, change) =
+            SystemTopicDiffer::topic(ask_dqc_rule_daily_topic(), Some(installed), &tenant_id)
+                .unwrap();
+        assert_eq!(change, SystemTupleChange::Unchanged);
+        assert_eq!(topic.topic_id, topic_id);
+    }
+
+    #[test]
+    fn test_topic_upgrade() {
+        let tenant_id = String::from("t-1");
+        let (mut installed, _) =
+            SystemTopicDiffer::topic(ask_dqc_rule_daily_topic(), None, &tenant_id).unwrap();
+        let factors = installed.factors.as_mut().unwrap();
+        // factor id changed by user, type changed, one removed, one appended
+        factors[0].factor_id = Some(String::from("user-f-1"));
+        factors[0].r#type = Some(FactorType::Sequence);
+        factors.retain(|factor| factor.name.as_deref() != Some("count"));
+        factors.push(
+            Factor::new()
+                .factor_id(String::from("user-f-2"))
+                .name(String::from("remark"))
+                .r#type(FactorType::Text),
+        );
+
+        let (topic, change) =
+            SystemTopicDiffer::topic(ask_dqc_rule_daily_topic(), Some(installed), &tenant_id)
+                .unwrap();
+        assert_eq!(
+            change,
+            SystemTupleChange::Upgrade(vec![
+                String::from("factor[ruleCode] changed"),
+                String::from("factor[count] added"),
+            ])
+        );
+        let factors = topic.factors.unwrap();
+        assert_eq!(factors[0].factor_id, Some(String::from("user-f-1")));
+        assert_eq!(factors[0].r#type, Some(FactorType::Text));
+        assert_eq!(factors.last().unwrap().name, Some(String::from("remark")));
+    }
+}
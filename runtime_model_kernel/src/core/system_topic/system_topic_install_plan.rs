@@ -0,0 +1,167 @@
+use crate::{
+    RuntimeModelKernelErrorCode, SystemPipelineChange, SystemTopicChange, SystemTopicDefinitions,
+    SystemTopicDiffer, SystemTopicVersion, SYSTEM_TOPIC_PACKAGE_VERSION,
+};
+use elf_base::{ErrorCode, StdR};
+use elf_model::{PackageVersion, Pipeline, TenantId, Topic, TopicCode, TopicId};
+use std::cmp::Ordering;
+
+/// what should be done to install system topics and pipelines to a tenant.
+pub struct SystemTopicInstallPlan {
+    pub tenant_id: TenantId,
+    pub installed_version: Option<PackageVersion>,
+    pub target_version: String,
+    pub topics: Vec<SystemTopicChange>,
+    pub pipelines: Vec<SystemPipelineChange>,
+}
+
+impl SystemTopicInstallPlan {
+    /// build plan by comparing the definitions to installed ones.
+    /// - installed topic is located by topic name,
+    /// - installed pipeline is located by topic id and pipeline name.
+    ///
+    /// raise error when installed version is newer than the definitions.
+    pub fn build<FindTopic, FindPipeline>(
+        tenant_id: &TenantId,
+        installed_version: Option<PackageVersion>,
+        find_topic: FindTopic,
+        find_pipeline: FindPipeline,
+    ) -> StdR<Self>
+    where
+        FindTopic: Fn(&TopicCode) -> StdR<Option<Topic>>,
+        FindPipeline: Fn(&TopicId, &String) -> StdR<Option<Pipeline>>,
+    {
+        let target_version = SYSTEM_TOPIC_PACKAGE_VERSION.to_string();
+        if let Some(curr_version) = installed_version
+            .as_ref()
+            .and_then(|version| version.curr_version.as_ref())
+            && SystemTopicVersion::compare(curr_version, &target_version)? == Ordering::Greater
+        {
+            return RuntimeModelKernelErrorCode::SystemPackageVersionDowngrade.msg(format!(
+                "Installed package version[{}] of tenant[{}] is newer than [{}].",
+                curr_version, tenant_id, target_version
+            ));
+        }
+
+        let mut topics = vec![];
+        let mut topic_changes = vec![];
+        for definition in SystemTopicDefinitions::topics() {
+            let installed = match &definition.name {
+                Some(name) => find_topic(name)?,
+                _ => None,
+            };
+            let (topic, change) = SystemTopicDiffer::topic(definition, installed, tenant_id)?;
+            topics.push(topic);
+            topic_changes.push(change);
+        }
+
+        let mut pipelines = vec![];
+        for definition in SystemTopicDefinitions::pipelines(&topics)? {
+            let installed = match (&definition.topic_id, &definition.name) {
+                (Some(topic_id), Some(name)) => find_pipeline(topic_id, name)?,
+                _ => None,
+            };
+            let (pipeline, change) = SystemTopicDiffer::pipeline(definition, installed, tenant_id)?;
+            pipelines.push(SystemPipelineChange { pipeline, change });
+        }
+
+        Ok(Self {
+            tenant_id: tenant_id.clone(),
+            installed_version,
+            target_version,
+            topics: topics
+                .into_iter()
+                .zip(topic_changes)
+                .map(|(topic, change)| SystemTopicChange { topic, change })
+                .collect(),
+            pipelines,
+        })
+    }
+
+    pub fn is_version_changed(&self) -> bool {
+        self.installed_version
+            .as_ref()
+            .and_then(|version| version.curr_version.as_ref())
+            .map(|curr_version| curr_version != &self.target_version)
+            .unwrap_or(true)
+    }
+
+    pub fn has_changes(&self) -> bool {
+        self.topics.iter().any(|topic| topic.change.is_changed())
+            || self
+                .pipelines
+                .iter()
+                .any(|pipeline| pipeline.change.is_changed())
+    }
+
+    pub fn is_up_to_date(&self) -> bool {
+        !self.is_version_changed() && !self.has_changes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{SystemTopicInstallPlan, SystemTupleChange};
+    use elf_model::{PackageVersion, Pipeline, Topic};
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_install_twice() {
+        let tenant_id = String::from("t-1");
+        let plan =
+            SystemTopicInstallPlan::build(&tenant_id, None, |_| Ok(None), |_, _| Ok(None)).unwrap();
+        assert!(plan.is_version_changed());
+        assert!(plan
+            .topics
+            .iter()
+            .all(|topic| topic.change == SystemTupleChange::Create));
+        assert_eq!(plan.pipelines.len(), 1);
+        assert_eq!(plan.pipelines[0].change, SystemTupleChange::Create);
+
+        // simulate the storage
+        let topics = RefCell::new(plan.topics.into_iter().map(|t| t.topic).collect::<Vec<_>>());
+        let pipelines = RefCell::new(
+            plan.pipelines
+                .into_iter()
+                .map(|p| p.pipeline)
+                .collect::<Vec<_>>(),
+        );
+        let version = PackageVersion::new()
+            .tenant_id(tenant_id.clone())
+            .curr_version(plan.target_version);
+
+        let take_topic = |name: &String| -> Option<Topic> {
+            let mut topics = topics.borrow_mut();
+            let index = topics.iter().position(|t| t.name.as_ref() == Some(name))?;
+            Some(topics.remove(index))
+        };
+        let take_pipeline = |topic_id: &String, name: &String| -> Option<Pipeline> {
+            let mut pipelines = pipelines.borrow_mut();
+            let index = pipelines.iter().position(|p| {
+                p.topic_id.as_ref() == Some(topic_id) && p.name.as_ref() == Some(name)
+            })?;
+            Some(pipelines.remove(index))
+        };
+
+        let plan = SystemTopicInstallPlan::build(
+            &tenant_id,
+            Some(version),
+            |name| Ok(take_topic(name)),
+            |topic_id, name| Ok(take_pipeline(topic_id, name)),
+        )
+        .unwrap();
+        assert!(plan.is_up_to_date());
+    }
+
+    #[test]
+    fn test_downgrade() {
+        let version = PackageVersion::new().curr_version(String::from("999.0.0"));
+        let plan = SystemTopicInstallPlan::build(
+            &String::from("t-1"),
+            Some(version),
+            |_| Ok(None),
+            |_, _| Ok(None),
+        );
+        assert!(plan.is_err());
+    }
+}
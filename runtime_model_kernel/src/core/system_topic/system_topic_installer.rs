@@ -0,0 +1,79 @@
+use crate::{
+    IdGen, PackageVersionProvider, PipelineMetaProvider, PipelineService, SystemTopicInstallPlan,
+    TopicMetaProvider, TopicService,
+};
+use elf_auth::Principal;
+use elf_base::{StdR, VoidR};
+use elf_model::{PackageVersion, TenantId};
+
+/// install or upgrade the system topics and pipelines of a tenant,
+/// the installed package version is saved after all topics and pipelines are saved.
+/// it is idempotent, nothing will be saved when tenant is up to date.
+/// each saved topic and pipeline is recorded as an operation of the installing principal.
+///
+/// TODO storage of the created topics is not created here,
+///  should be done by the topic data storage when it is ready.
+pub struct SystemTopicInstaller;
+
+impl PackageVersionProvider for SystemTopicInstaller {}
+
+impl SystemTopicInstaller {
+    /// compare the definitions to the installed, nothing will be saved.
+    pub fn diff(tenant_id: &TenantId) -> StdR<SystemTopicInstallPlan> {
+        let topic_meta = TopicService::meta()?;
+        let pipeline_meta = PipelineService::meta()?;
+        let installed_version = Self::package_version()?.find_by_tenant_id(tenant_id)?;
+
+        SystemTopicInstallPlan::build(
+            tenant_id,
+            installed_version,
+            |topic_name| topic_meta.find_by_name(topic_name, tenant_id),
+            |topic_id, pipeline_name| {
+                let pipelines = pipeline_meta.by_topic_id(topic_id, tenant_id)?;
+                Ok(pipelines.and_then(|pipelines| {
+                    pipelines
+                        .into_iter()
+                        .find(|pipeline| pipeline.name.as_ref() == Some(pipeline_name))
+                }))
+            },
+        )
+    }
+
+    /// install to tenant of principal, returns the plan which is applied.
+    pub fn install(principal: &Principal) -> StdR<SystemTopicInstallPlan> {
+        let plan = Self::diff(principal.tenant_id())?;
+        Self::apply(&plan, principal)?;
+        Ok(plan)
+    }
+
+    fn apply(plan: &SystemTopicInstallPlan, principal: &Principal) -> VoidR {
+        if plan.is_up_to_date() {
+            return Ok(());
+        }
+
+        let topic_meta = TopicService::meta()?;
+        for change in plan.topics.iter().filter(|t| t.change.is_changed()) {
+            topic_meta.save(principal, &change.topic)?;
+        }
+        let pipeline_meta = PipelineService::meta()?;
+        for change in plan.pipelines.iter().filter(|p| p.change.is_changed()) {
+            pipeline_meta.save(principal, &change.pipeline)?;
+        }
+
+        if plan.is_version_changed() {
+            let mut version = PackageVersion::new()
+                .tenant_id(plan.tenant_id.clone())
+                .curr_version(plan.target_version.clone());
+            match &plan.installed_version {
+                Some(installed) => {
+                    version.version_id = installed.version_id.clone();
+                    version.pre_version = installed.curr_version.clone();
+                }
+                _ => version.version_id = Some(IdGen::next_id()?.to_string()),
+            }
+            Self::package_version()?.save(&version)?;
+        }
+
+        Ok(())
+    }
+}
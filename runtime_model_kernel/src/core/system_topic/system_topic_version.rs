@@ -0,0 +1,64 @@
+use crate::RuntimeModelKernelErrorCode;
+use elf_base::{ErrorCode, StdR};
+use std::cmp::Ordering;
+
+/// version of system topics and pipelines definitions,
+/// must be increased when any of definitions changed.
+pub const SYSTEM_TOPIC_PACKAGE_VERSION: &str = "1.0.0";
+
+/// dot separated numeric version, e.g. 1.0.0
+pub struct SystemTopicVersion;
+
+impl SystemTopicVersion {
+    fn parse(version: &str) -> StdR<Vec<u32>> {
+        version
+            .trim()
+            .split('.')
+            .map(|part| {
+                part.parse::<u32>().or_else(|_| {
+                    RuntimeModelKernelErrorCode::SystemPackageVersionInvalid
+                        .msg(format!("Package version[{}] is invalid.", version))
+                })
+            })
+            .collect()
+    }
+
+    /// missing parts are treated as 0, so 1.0 equals 1.0.0
+    pub fn compare(v1: &str, v2: &str) -> StdR<Ordering> {
+        let parts1 = Self::parse(v1)?;
+        let parts2 = Self::parse(v2)?;
+        let len = parts1.len().max(parts2.len());
+        for index in 0..len {
+            let p1 = parts1.get(index).unwrap_or(&0);
+            let p2 = parts2.get(index).unwrap_or(&0);
+            match p1.cmp(p2) {
+                Ordering::Equal => {}
+                ordering => return Ok(ordering),
+            }
+        }
+        Ok(Ordering::Equal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SystemTopicVersion;
+    use std::cmp::Ordering;
+
+    #[test]
+    fn test_compare() {
+        assert_eq!(
+            SystemTopicVersion::compare("1.0.0", "1.0").unwrap(),
+            Ordering::Equal
+        );
+        assert_eq!(
+            SystemTopicVersion::compare("1.0.1", "1.0.0").unwrap(),
+            Ordering::Greater
+        );
+        assert_eq!(
+            SystemTopicVersion::compare("1.2.0", "1.10.0").unwrap(),
+            Ordering::Less
+        );
+        assert!(SystemTopicVersion::compare("1.x", "1.0").is_err());
+    }
+}
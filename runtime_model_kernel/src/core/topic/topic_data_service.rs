@@ -1,4 +1,5 @@
-use crate::{TopicMetaProvider, TopicSchema};
+use crate::{ArcParameterJoint, TopicMetaProvider, TopicSchema};
+use chrono::NaiveDateTime;
 use elf_base::StdR;
 use elf_model::TopicData;
 use std::sync::Arc;
@@ -36,6 +37,42 @@ impl TopicDataService {
     pub fn delete(&self, _topic_schema: &Arc<TopicSchema>, _data: TopicData) -> StdR<TopicData> {
         todo!("implement delete for TopicDataService")
     }
+
+    /// find all rows of topic.
+    pub fn find_all(&self, _topic_schema: &Arc<TopicSchema>) -> StdR<Vec<TopicData>> {
+        todo!("implement find_all for TopicDataService")
+    }
+
+    /// find rows matched given joint, all rows when joint is none.
+    /// at most [limit] rows are returned.
+    pub fn find_by_joint(
+        &self,
+        _topic_schema: &Arc<TopicSchema>,
+        _joint: Option<&Arc<ArcParameterJoint>>,
+        _limit: usize,
+    ) -> StdR<Vec<TopicData>> {
+        todo!("implement find_by_joint for TopicDataService")
+    }
+
+    /// find rows which updated in [start, end), rows are ordered by insert time.
+    pub fn find_updated_between(
+        &self,
+        _topic_schema: &Arc<TopicSchema>,
+        _start: &NaiveDateTime,
+        _end: &NaiveDateTime,
+    ) -> StdR<Vec<TopicData>> {
+        todo!("implement find_updated_between for TopicDataService")
+    }
+
+    /// count rows which inserted in [start, end).
+    pub fn count_inserted_between(
+        &self,
+        _topic_schema: &Arc<TopicSchema>,
+        _start: &NaiveDateTime,
+        _end: &NaiveDateTime,
+    ) -> StdR<u64> {
+        todo!("implement count_inserted_between for TopicDataService")
+    }
 }
 
 pub trait TopicDataProvider {
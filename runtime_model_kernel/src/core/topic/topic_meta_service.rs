@@ -1,5 +1,8 @@
+use crate::{MetaTupleType, OperationLogService, OperationMetaResources, OperationResources};
+use chrono::Utc;
+use elf_auth::Principal;
 use elf_base::StdR;
-use elf_model::{TenantId, Topic, TopicCode, TopicId};
+use elf_model::{Operation, TenantId, Topic, TopicCode, TopicId};
 use std::sync::Arc;
 
 /// TODO topic meta service using tenant and it's meta datasource (or the global meta datasource)
@@ -20,6 +23,36 @@ impl TopicMetaService {
     pub fn find_by_code(&self, _topic_code: &TopicCode, _tenant_id: &TenantId) -> StdR<Topic> {
         todo!("implement find_by_code for TopicMetaService")
     }
+
+    /// returns none when topic not found, instead of raise error
+    pub fn find_by_name(
+        &self,
+        _topic_name: &TopicCode,
+        _tenant_id: &TenantId,
+    ) -> StdR<Option<Topic>> {
+        todo!("implement find_by_name for TopicMetaService")
+    }
+
+    /// create when topic id not exists, otherwise update.
+    /// topic is saved by [OperationLogService], so the change is always recorded as an operation of principal.
+    pub fn save(&self, principal: &Principal, topic: &Topic) -> StdR<Operation> {
+        self.save_by(OperationMetaResources, principal, topic)
+    }
+
+    /// save topic into given resources, see [TopicMetaService::save].
+    pub fn save_by<R: OperationResources>(
+        &self,
+        resources: R,
+        principal: &Principal,
+        topic: &Topic,
+    ) -> StdR<Operation> {
+        OperationLogService::new(resources).save_model(
+            principal,
+            &MetaTupleType::Topic,
+            topic,
+            &Utc::now().naive_utc(),
+        )
+    }
 }
 
 pub trait TopicMetaProvider {
@@ -27,3 +60,120 @@ pub trait TopicMetaProvider {
         TopicMetaService::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        IdGen, MetaTupleType, OperationCriteria, OperationRecord, OperationResources,
+        SnowflakeIdGenerator, TopicMetaProvider, TopicService,
+    };
+    use elf_auth::Principal;
+    use elf_base::{StdR, VoidR};
+    use elf_model::{Operation, OperationType, TenantId, Topic, TopicKind, TopicType};
+    use serde_json::Value;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    /// operation is not cloneable, copy fields used in this test
+    fn copy_of(operation: &Operation) -> Operation {
+        Operation {
+            record_id: operation.record_id.clone(),
+            operation_type: operation.operation_type.clone(),
+            tuple_key: operation.tuple_key.clone(),
+            tuple_type: operation.tuple_type.clone(),
+            tuple_id: operation.tuple_id.clone(),
+            content: operation.content.clone(),
+            tenant_id: operation.tenant_id.clone(),
+            created_at: operation.created_at,
+            created_by: operation.created_by.clone(),
+            ..Operation::new()
+        }
+    }
+
+    #[derive(Default)]
+    struct InMemoryResources {
+        /// key is topic id
+        topics: RefCell<HashMap<String, Value>>,
+        operations: RefCell<Vec<Operation>>,
+    }
+
+    impl OperationResources for &InMemoryResources {
+        fn find_tuple(
+            &self,
+            _tuple_type: &MetaTupleType,
+            tuple_id: &str,
+            _tenant_id: &TenantId,
+        ) -> StdR<Option<Value>> {
+            Ok(self.topics.borrow().get(tuple_id).cloned())
+        }
+
+        fn save_tuple(&self, tuple_type: &MetaTupleType, tuple: &Value) -> VoidR {
+            let tuple_id = tuple[tuple_type.id_key()].as_str().unwrap().to_string();
+            self.topics.borrow_mut().insert(tuple_id, tuple.clone());
+            Ok(())
+        }
+
+        fn delete_tuple(
+            &self,
+            _tuple_type: &MetaTupleType,
+            tuple_id: &str,
+            _tenant_id: &TenantId,
+        ) -> VoidR {
+            self.topics.borrow_mut().remove(tuple_id);
+            Ok(())
+        }
+
+        fn create_operation(&self, operation: &Operation) -> VoidR {
+            self.operations.borrow_mut().push(copy_of(operation));
+            Ok(())
+        }
+
+        fn find_operations(
+            &self,
+            _criteria: &OperationCriteria,
+            _tenant_id: &TenantId,
+        ) -> StdR<Vec<Operation>> {
+            unreachable!("operations are not found when save topic")
+        }
+    }
+
+    fn topic(name: &str) -> Topic {
+        Topic::new()
+            .topic_id(String::from("t1"))
+            .name(name.to_string())
+            .r#type(TopicType::Distinct)
+            .kind(TopicKind::Business)
+            .tenant_id(String::from("another-tenant"))
+    }
+
+    #[test]
+    fn test_save() {
+        let _ = IdGen::set(Box::new(SnowflakeIdGenerator::new(1).unwrap()));
+        let principal = Principal::fake_tenant_admin(
+            Some(String::from("topic-meta-tenant")),
+            Some(String::from("u-1")),
+            None,
+        );
+        let resources = InMemoryResources::default();
+        let topic_meta = TopicService::meta().unwrap();
+        topic_meta
+            .save_by(&resources, &principal, &topic("orders"))
+            .unwrap();
+        let operation = topic_meta
+            .save_by(&resources, &principal, &topic("sales_orders"))
+            .unwrap();
+        assert_eq!(operation.created_by.as_deref(), Some("u-1"));
+
+        let operations = resources.operations.borrow();
+        assert_eq!(operations.len(), 2);
+        let created = OperationRecord::from_operation(&operations[0]).unwrap();
+        assert!(matches!(created.operation_type, OperationType::Create));
+        let updated = OperationRecord::from_operation(&operations[1]).unwrap();
+        assert!(matches!(updated.operation_type, OperationType::Update));
+        assert_eq!(updated.before.unwrap()["name"], "orders");
+        let after = updated.after.unwrap();
+        assert_eq!(after["name"], "sales_orders");
+        // tenant of principal is used
+        assert_eq!(after["tenantId"], "topic-meta-tenant");
+    }
+}
@@ -0,0 +1,5 @@
+mod user_meta_service;
+mod user_service;
+
+pub use user_meta_service::*;
+pub use user_service::*;
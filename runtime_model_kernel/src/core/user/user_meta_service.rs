@@ -0,0 +1,24 @@
+use elf_base::StdR;
+use elf_model::{TenantId, User, UserId};
+use std::sync::Arc;
+
+/// TODO user meta service using tenant and it's meta datasource (or the global meta datasource)
+///  to find out users.
+pub struct UserMetaService;
+
+impl UserMetaService {
+    fn new() -> StdR<Arc<Self>> {
+        // TODO maybe find from cache
+        Ok(Arc::new(Self {}))
+    }
+
+    pub fn find_by_id(&self, _user_id: &UserId, _tenant_id: &TenantId) -> StdR<Option<User>> {
+        todo!("implement find_by_id for UserMetaService")
+    }
+}
+
+pub trait UserMetaProvider {
+    fn meta() -> StdR<Arc<UserMetaService>> {
+        UserMetaService::new()
+    }
+}
@@ -0,0 +1,5 @@
+use crate::UserMetaProvider;
+
+pub struct UserService;
+
+impl UserMetaProvider for UserService {}